@@ -21,18 +21,35 @@ alias cd="cdir_cd"
 
 # Create a new shortcut in cdir
 function p {
-   cdir add-shortcut $1 "`pwd`" "${2}"
+   cdir add-shortcut $1 "`pwd`" "${2}" "${@:3}"
 }
 
-# c command to change the current directory using shortcuts
+# c command to change the current directory using shortcuts, optionally
+# followed by a path inside the shortcut (c docs/reports/2024),
+# then apply the environment hints of the shortcut
 function c {
     if [ $# -eq 0 ]; then
         TMP_FILE=`mktemp`
-        cdir gui $TMP_FILE
-        DIR="`cat $TMP_FILE`"
-        [[ ! -z $DIR ]] && cd $DIR
+        cdir gui --eval $TMP_FILE
+        eval "`cat $TMP_FILE`"
         rm -f $TMP_FILE
     else
-         cd "`cdir print-shortcut $1`"
+        eval "`cdir expand --eval "$1"`"
     fi
 }
+
+# cf command to open a recent file, recorded with cdir add-file, in $EDITOR
+function cf {
+    TMP_FILE=`mktemp`
+    cdir gui --view files --eval $TMP_FILE
+    eval "`cat $TMP_FILE`"
+    rm -f $TMP_FILE
+}
+
+# Uncomment to record the last command run in each directory (zsh), shown
+# under its row in the history of the GUI
+# function cdir_preexec {
+#     cdir add-cmd "$PWD" "$1"
+# }
+# autoload -Uz add-zsh-hook
+# add-zsh-hook preexec cdir_preexec
@@ -0,0 +1,2 @@
+ALTER TABLE paths_history ADD COLUMN container TEXT;
+CREATE INDEX IF NOT EXISTS paths_history_container ON paths_history (container, path);
@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS dir_sizes (
+    path TEXT PRIMARY KEY,
+    size INTEGER NOT NULL,
+    date INTEGER NOT NULL
+);
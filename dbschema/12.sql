@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS path_links (
+    path TEXT PRIMARY KEY,
+    canonical TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS path_links_canonical ON path_links (canonical);
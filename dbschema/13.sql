@@ -0,0 +1 @@
+ALTER TABLE paths ADD COLUMN sensitive INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS search_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    view TEXT NOT NULL,
+    search TEXT NOT NULL,
+    fuzzy_match INTEGER NOT NULL,
+    date INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS search_history_view_date ON search_history (view, date DESC, id DESC);
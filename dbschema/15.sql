@@ -0,0 +1,2 @@
+ALTER TABLE shortcuts ADD COLUMN broken INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE shortcuts ADD COLUMN checked INTEGER;
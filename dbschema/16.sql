@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS project_types (
+    path TEXT PRIMARY KEY,
+    type TEXT,
+    date INTEGER NOT NULL
+);
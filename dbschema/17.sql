@@ -0,0 +1,5 @@
+-- the older shortcuts sharing a name are kept as `name~id`, so that the names can be unique
+UPDATE shortcuts SET name = name || '~' || id
+    WHERE id NOT IN (SELECT MAX(id) FROM shortcuts GROUP BY name);
+DROP INDEX IF EXISTS shortcuts_name;
+CREATE UNIQUE INDEX IF NOT EXISTS shortcuts_name ON shortcuts (name);
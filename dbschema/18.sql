@@ -0,0 +1,2 @@
+-- the date the path was moved to the trash, NULL when it is not deleted
+ALTER TABLE paths ADD COLUMN deleted_at INTEGER;
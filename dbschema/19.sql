@@ -0,0 +1,2 @@
+-- the date the shortcut was created, NULL for the shortcuts created before it was recorded
+ALTER TABLE shortcuts ADD COLUMN created INTEGER;
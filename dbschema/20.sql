@@ -0,0 +1,8 @@
+-- the bundle a shortcut was installed from, NULL for the personal shortcuts
+ALTER TABLE shortcuts ADD COLUMN bundle TEXT;
+-- the installed bundles of shortcuts, at their version, installed at date
+CREATE TABLE IF NOT EXISTS bundles (
+    name TEXT PRIMARY KEY,
+    version INTEGER NOT NULL,
+    date INTEGER NOT NULL
+);
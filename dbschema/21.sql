@@ -0,0 +1,6 @@
+-- the files opened recently, at date, recorded by the editor hooks calling add-file
+CREATE TABLE IF NOT EXISTS files (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    path TEXT NOT NULL UNIQUE,
+    date INTEGER NOT NULL
+);
@@ -0,0 +1,6 @@
+-- the last command run in a directory, at date, recorded by the shell hooks calling add-cmd
+CREATE TABLE IF NOT EXISTS last_commands (
+    path TEXT PRIMARY KEY,
+    command TEXT NOT NULL,
+    date INTEGER NOT NULL
+);
@@ -0,0 +1,7 @@
+-- the smart suggestions parameters of a directory and of its sub directories, overriding the
+-- configured smart_suggestions_depth and smart_suggestions_count when not NULL
+CREATE TABLE IF NOT EXISTS suggestion_settings (
+    path TEXT PRIMARY KEY,
+    depth INTEGER,
+    count INTEGER
+);
@@ -0,0 +1,7 @@
+-- the smart suggestions of a directory computed at date, one suggested path per line, the best
+-- first; read by the gui when it starts, before computing them again
+CREATE TABLE IF NOT EXISTS suggestions_cache (
+    path TEXT PRIMARY KEY,
+    suggestions TEXT NOT NULL,
+    date INTEGER NOT NULL
+);
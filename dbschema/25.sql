@@ -0,0 +1,6 @@
+-- the ids of the paths of the first page of the history view, by position, shown by the gui
+-- when it starts while the history is queried
+CREATE TABLE IF NOT EXISTS warm_start_page (
+    position INTEGER PRIMARY KEY,
+    id INTEGER NOT NULL
+);
@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS gui_session (
+    id INTEGER PRIMARY KEY CHECK (id = 0),
+    search TEXT NOT NULL,
+    fuzzy_match INTEGER NOT NULL,
+    view TEXT NOT NULL,
+    selected_row INTEGER NOT NULL
+);
@@ -0,0 +1,18 @@
+CREATE TABLE IF NOT EXISTS path_transitions (
+    from_path TEXT NOT NULL,
+    to_path TEXT NOT NULL,
+    time_slot INTEGER NOT NULL,
+    count INTEGER NOT NULL,
+    PRIMARY KEY (from_path, to_path, time_slot)
+);
+-- Build the transitions from the existing history, time_slot being (day of week from monday) * 4 + hour / 6
+INSERT OR IGNORE INTO path_transitions (from_path, to_path, time_slot, count)
+SELECT from_path, to_path, time_slot, COUNT(*) FROM (
+    SELECT LAG(path) OVER (ORDER BY id) AS from_path,
+           path AS to_path,
+           ((CAST(strftime('%w', date, 'unixepoch', 'localtime') AS INTEGER) + 6) % 7) * 4
+               + CAST(strftime('%H', date, 'unixepoch', 'localtime') AS INTEGER) / 6 AS time_slot
+    FROM paths_history
+)
+WHERE from_path IS NOT NULL AND from_path != to_path
+GROUP BY from_path, to_path, time_slot;
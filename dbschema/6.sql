@@ -0,0 +1,2 @@
+ALTER TABLE shortcuts ADD COLUMN env TEXT;
+ALTER TABLE shortcuts ADD COLUMN post_cd TEXT;
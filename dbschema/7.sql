@@ -0,0 +1,2 @@
+ALTER TABLE paths_history ADD COLUMN tmux_pane TEXT;
+CREATE INDEX IF NOT EXISTS paths_history_tmux_pane ON paths_history (tmux_pane, path);
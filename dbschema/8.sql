@@ -0,0 +1,3 @@
+ALTER TABLE paths ADD COLUMN hostname TEXT;
+ALTER TABLE paths_history ADD COLUMN hostname TEXT;
+CREATE INDEX IF NOT EXISTS paths_history_hostname ON paths_history (hostname, path);
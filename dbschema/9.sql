@@ -0,0 +1,2 @@
+ALTER TABLE paths ADD COLUMN remote INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE shortcuts ADD COLUMN remote INTEGER NOT NULL DEFAULT 0;
@@ -5,27 +5,145 @@ CREATE TABLE IF NOT EXISTS version (
     version INTEGER PRIMARY KEY
 );
 
--- Path table
+-- Path table, the hostname being the one of the last visit, remote being set for the
+-- [user@]host:/path entries, sensitive for the entries hidden until revealed and deleted_at
+-- for the entries in the trash
 CREATE TABLE IF NOT EXISTS paths (
     id INTEGER PRIMARY KEY AUTOINCREMENT,
     path TEXT NOT NULL,
-    date INTEGER NOT NULL
+    date INTEGER NOT NULL,
+    hostname TEXT,
+    remote INTEGER NOT NULL DEFAULT 0,
+    sensitive INTEGER NOT NULL DEFAULT 0,
+    deleted_at INTEGER
 );
 CREATE INDEX IF NOT EXISTS paths_date ON paths (date);
 
--- Shortcuts table
+-- Shortcuts table, broken being set when the path was found missing by the last check of the
+-- path, made at the checked date, created being the date the shortcut was added and bundle the
+-- bundle it was installed from, NULL for the personal shortcuts
 CREATE TABLE IF NOT EXISTS shortcuts (
     id INTEGER PRIMARY KEY AUTOINCREMENT,
     name TEXT NOT NULL,
     path TEXT NOT NULL,
-    description TEXT
+    description TEXT,
+    env TEXT,
+    post_cd TEXT,
+    remote INTEGER NOT NULL DEFAULT 0,
+    broken INTEGER NOT NULL DEFAULT 0,
+    checked INTEGER,
+    created INTEGER,
+    bundle TEXT
 );
-CREATE INDEX IF NOT EXISTS shortcuts_name ON shortcuts (name);
+CREATE UNIQUE INDEX IF NOT EXISTS shortcuts_name ON shortcuts (name);
 
--- Path table
+-- Path table, the tmux pane ($TMUX_PANE), the host and the container (NULL on the host)
+-- the path was visited from
 CREATE TABLE IF NOT EXISTS paths_history (
     id INTEGER PRIMARY KEY AUTOINCREMENT,
     path TEXT NOT NULL,
-    date INTEGER NOT NULL
+    date INTEGER NOT NULL,
+    tmux_pane TEXT,
+    hostname TEXT,
+    container TEXT
 );
 CREATE INDEX IF NOT EXISTS paths_history_path_date_id ON paths_history (path, date DESC, id DESC);
+CREATE INDEX IF NOT EXISTS paths_history_tmux_pane ON paths_history (tmux_pane, path);
+CREATE INDEX IF NOT EXISTS paths_history_hostname ON paths_history (hostname, path);
+CREATE INDEX IF NOT EXISTS paths_history_container ON paths_history (container, path);
+
+
+-- GUI session table, holding the single session to restore
+CREATE TABLE IF NOT EXISTS gui_session (
+    id INTEGER PRIMARY KEY CHECK (id = 0),
+    search TEXT NOT NULL,
+    fuzzy_match INTEGER NOT NULL,
+    view TEXT NOT NULL,
+    selected_row INTEGER NOT NULL
+);
+
+-- The searches made in the GUI, by view ("path" or "shortcut"), the most recent last
+CREATE TABLE IF NOT EXISTS search_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    view TEXT NOT NULL,
+    search TEXT NOT NULL,
+    fuzzy_match INTEGER NOT NULL,
+    date INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS search_history_view_date ON search_history (view, date DESC, id DESC);
+
+-- Transitions between paths, counted by time slot ((day of week from monday) * 4 + hour / 6)
+CREATE TABLE IF NOT EXISTS path_transitions (
+    from_path TEXT NOT NULL,
+    to_path TEXT NOT NULL,
+    time_slot INTEGER NOT NULL,
+    count INTEGER NOT NULL,
+    PRIMARY KEY (from_path, to_path, time_slot)
+);
+
+-- Directory sizes cache, the size (in bytes) being computed at date
+CREATE TABLE IF NOT EXISTS dir_sizes (
+    path TEXT PRIMARY KEY,
+    size INTEGER NOT NULL,
+    date INTEGER NOT NULL
+);
+
+-- Equivalent paths (symbolic links, bind mounts or declared with link-paths), grouped under
+-- a canonical path; the canonical path of a group belongs to it
+CREATE TABLE IF NOT EXISTS path_links (
+    path TEXT PRIMARY KEY,
+    canonical TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS path_links_canonical ON path_links (canonical);
+
+-- Project types cache, the type (NULL when the directory is not a project) being detected at
+-- date from the marker files of the directory
+CREATE TABLE IF NOT EXISTS project_types (
+    path TEXT PRIMARY KEY,
+    type TEXT,
+    date INTEGER NOT NULL
+);
+
+-- The installed bundles of shortcuts, at their version, installed at date
+CREATE TABLE IF NOT EXISTS bundles (
+    name TEXT PRIMARY KEY,
+    version INTEGER NOT NULL,
+    date INTEGER NOT NULL
+);
+
+-- The files opened recently, at date, recorded by the editor hooks calling add-file
+CREATE TABLE IF NOT EXISTS files (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    path TEXT NOT NULL UNIQUE,
+    date INTEGER NOT NULL
+);
+
+-- The last command run in a directory, at date, recorded by the shell hooks calling add-cmd
+CREATE TABLE IF NOT EXISTS last_commands (
+    path TEXT PRIMARY KEY,
+    command TEXT NOT NULL,
+    date INTEGER NOT NULL
+);
+
+-- The smart suggestions parameters of a directory and of its sub directories, overriding the
+-- configured smart_suggestions_depth and smart_suggestions_count when not NULL
+CREATE TABLE IF NOT EXISTS suggestion_settings (
+    path TEXT PRIMARY KEY,
+    depth INTEGER,
+    count INTEGER
+);
+
+-- The smart suggestions of a directory computed at date, one suggested path per line, the best
+-- first; read by the GUI when it starts, before computing them again
+CREATE TABLE IF NOT EXISTS suggestions_cache (
+    path TEXT PRIMARY KEY,
+    suggestions TEXT NOT NULL,
+    date INTEGER NOT NULL
+);
+
+-- The ids of the paths of the first page of the history view, by position, shown by the GUI
+-- when it starts while the history is queried
+CREATE TABLE IF NOT EXISTS warm_start_page (
+    position INTEGER PRIMARY KEY,
+    id INTEGER NOT NULL
+);
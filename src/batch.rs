@@ -0,0 +1,172 @@
+use std::{fmt, fs, path::Path};
+
+use serde::Deserialize;
+use tracing::{debug, info};
+
+use crate::{
+    expimp::{self, FileFormat},
+    remote::RemotePath,
+    shortcut_hints,
+    store::{ShortcutHints, Store},
+};
+
+#[cfg(test)]
+#[path = "batch_tests.rs"]
+mod batch_tests;
+
+/// An operation of a batch file, written as a single-key map, e.g. `add_path: /home/me/work`
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum Operation {
+    /// Record a visit of a path
+    AddPath(String),
+    /// Add a shortcut, replacing the one of the same name
+    AddShortcut {
+        name: String,
+        path: String,
+        description: Option<String>,
+        /// The environment variables exported after jumping into the shortcut, as NAME=value
+        #[serde(default)]
+        env: Vec<String>,
+        /// A command run after jumping into the shortcut
+        post_cd: Option<String>,
+    },
+    /// Delete a shortcut, if it exists
+    DeleteShortcut(String),
+    /// Mark a path of the history as sensitive
+    MarkSensitive(String),
+    /// Declare two paths as the same directory
+    LinkPaths { path: String, alias: String },
+    /// Purge the paths in the trash for more than `retention_days` days
+    PurgeTrash { retention_days: u64 },
+    /// Write the shortcuts to a YAML, JSON or TOML file, from its extension, in the format of
+    /// `import-shortcuts`
+    ExportShortcuts(String),
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Operation::AddPath(path) => write!(f, "add path {}", path),
+            Operation::AddShortcut { name, path, .. } => {
+                write!(f, "add shortcut {} {}", name, path)
+            }
+            Operation::DeleteShortcut(name) => write!(f, "delete shortcut {}", name),
+            Operation::MarkSensitive(path) => write!(f, "mark {} as sensitive", path),
+            Operation::LinkPaths { path, alias } => write!(f, "link {} to {}", alias, path),
+            Operation::PurgeTrash { retention_days } => {
+                write!(f, "purge the trash older than {} days", retention_days)
+            }
+            Operation::ExportShortcuts(file) => write!(f, "export the shortcuts to {}", file),
+        }
+    }
+}
+
+/// Parse a list of operations, each one being a single-key map rather than a YAML tag.
+pub fn parse_operations(contents: &str) -> Result<Vec<Operation>, serde_yaml::Error> {
+    serde_yaml::with::singleton_map_recursive::deserialize(serde_yaml::Deserializer::from_str(
+        contents,
+    ))
+}
+
+/// Read a batch file holding a list of operations.
+pub fn read_batch_file(file: &Path) -> Result<Vec<Operation>, String> {
+    let contents = fs::read_to_string(file)
+        .map_err(|e| format!("Failed to read file {}: {}", file.display(), e))?;
+    parse_operations(&contents)
+        .map_err(|e| format!("Failed to parse the file {}: {}", file.display(), e))
+}
+
+/// Apply an operation to the store, the files to export being added to `exports` rather
+/// than written, so that they are only written once all the operations succeeded.
+fn apply(
+    store: &Store,
+    operation: &Operation,
+    exports: &mut Vec<(String, String)>,
+) -> Result<(), String> {
+    let db_error = |e: rusqlite::Error| e.to_string();
+    match operation {
+        Operation::AddPath(path) => {
+            RemotePath::validate(path)?;
+            store.add_path(path).map_err(db_error)
+        }
+        Operation::AddShortcut {
+            name,
+            path,
+            description,
+            env,
+            post_cd,
+        } => {
+            if name.is_empty() || path.is_empty() {
+                return Err(String::from("a shortcut needs a name and a path"));
+            }
+            RemotePath::validate(path)?;
+            let hints = ShortcutHints {
+                env: shortcut_hints::parse_env(env)?,
+                post_cd: post_cd.clone(),
+            };
+            store
+                .upsert_shortcut(name, path, description.as_deref())
+                .map_err(db_error)?;
+            if !hints.is_empty() {
+                store.set_shortcut_hints(name, &hints).map_err(db_error)?;
+            }
+            Ok(())
+        }
+        Operation::DeleteShortcut(name) => store.delete_shortcut(name).map_err(db_error),
+        Operation::MarkSensitive(path) => match store.set_path_sensitive(path, true) {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(format!("'{}' is not in the history", path)),
+            Err(e) => Err(e.to_string()),
+        },
+        Operation::LinkPaths { path, alias } => store.link_paths(path, alias).map_err(db_error),
+        Operation::PurgeTrash { retention_days } => store
+            .purge_trash(*retention_days)
+            .map(|_| ())
+            .map_err(db_error),
+        Operation::ExportShortcuts(file) => {
+            exports.push((
+                file.clone(),
+                expimp::export_shortcuts(store, FileFormat::detect(file))?,
+            ));
+            Ok(())
+        }
+    }
+}
+
+/// Run the operations of a batch, in a single transaction: when an operation fails, none of
+/// them is applied and no file is exported. With `dry_run`, the operations are run then
+/// rolled back, checking that they would succeed without changing anything.
+/// `report` is called with each operation once it succeeded.
+///
+/// ### Returns
+/// Ok(()) if all the operations succeeded, otherwise an error naming the failed operation.
+pub fn run(
+    store: &Store,
+    operations: &[Operation],
+    dry_run: bool,
+    report: &mut dyn FnMut(&Operation),
+) -> Result<(), String> {
+    debug!(
+        "batch run {} operations dry_run={}",
+        operations.len(),
+        dry_run
+    );
+    let transaction = store.transaction().map_err(|e| e.to_string())?;
+    let mut exports = vec![];
+    for (idx, operation) in operations.iter().enumerate() {
+        apply(store, operation, &mut exports)
+            .map_err(|e| format!("Operation {} ({}) failed: {}", idx + 1, operation, e))?;
+        report(operation);
+    }
+    if dry_run {
+        info!("batch of {} operations rolled back", operations.len());
+        return Ok(());
+    }
+    transaction.commit().map_err(|e| e.to_string())?;
+    for (file, contents) in exports {
+        fs::write(&file, contents).map_err(|e| format!("Failed to write {}: {}", file, e))?;
+    }
+    info!("batch of {} operations applied", operations.len());
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use super::*;
+
+#[test]
+fn test_parse_operations() {
+    let operations = parse_operations(
+        "- add_path: /work\n\
+         - add_shortcut: {name: w, path: /work, env: [A=1]}\n\
+         - delete_shortcut: old\n\
+         - link_paths: {path: /work, alias: /w}\n\
+         - purge_trash: {retention_days: 30}\n",
+    )
+    .unwrap();
+    assert_eq!(
+        operations,
+        vec![
+            Operation::AddPath(String::from("/work")),
+            Operation::AddShortcut {
+                name: String::from("w"),
+                path: String::from("/work"),
+                description: None,
+                env: vec![String::from("A=1")],
+                post_cd: None,
+            },
+            Operation::DeleteShortcut(String::from("old")),
+            Operation::LinkPaths {
+                path: String::from("/work"),
+                alias: String::from("/w"),
+            },
+            Operation::PurgeTrash { retention_days: 30 },
+        ]
+    );
+    assert!(parse_operations("- add_tag: /work\n").is_err());
+}
+
+#[test]
+fn test_run() {
+    let store = Store::setup_test_store();
+    let dir = tempfile::tempdir().unwrap();
+    let export = dir.path().join("shortcuts.yaml");
+    let operations = vec![
+        Operation::AddPath(String::from("/work")),
+        Operation::AddShortcut {
+            name: String::from("w"),
+            path: String::from("/work"),
+            description: None,
+            env: vec![String::from("A=1")],
+            post_cd: None,
+        },
+        Operation::MarkSensitive(String::from("/work")),
+        Operation::ExportShortcuts(export.display().to_string()),
+    ];
+
+    let mut reported = 0;
+    run(&store, &operations, true, &mut |_| reported += 1).unwrap();
+    assert_eq!(reported, 4);
+    assert!(store.list_all_shortcuts().unwrap().is_empty());
+    assert!(!export.exists());
+
+    run(&store, &operations, false, &mut |_| {}).unwrap();
+    assert_eq!(store.find_shortcut("w").unwrap().path, "/work");
+    assert_eq!(
+        store.find_shortcut_hints("w").unwrap().unwrap().env,
+        vec![(String::from("A"), String::from("1"))]
+    );
+    assert!(fs::read_to_string(&export).unwrap().contains("name: w"));
+}
+
+#[test]
+fn test_run_all_or_nothing() {
+    let store = Store::setup_test_store();
+    let operations = vec![
+        Operation::AddShortcut {
+            name: String::from("w"),
+            path: String::from("/work"),
+            description: None,
+            env: vec![],
+            post_cd: None,
+        },
+        Operation::MarkSensitive(String::from("/unknown")),
+    ];
+
+    let error = run(&store, &operations, false, &mut |_| {}).unwrap_err();
+    assert!(error.starts_with("Operation 2 (mark /unknown as sensitive) failed"));
+    assert!(store.find_shortcut("w").is_none());
+}
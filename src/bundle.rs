@@ -0,0 +1,295 @@
+use std::{collections::HashSet, fmt};
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use serde::Deserialize;
+use tracing::{debug, info, warn};
+
+use crate::{fetch, remote::RemotePath, store::Store};
+
+#[cfg(test)]
+#[path = "bundle_tests.rs"]
+mod bundle_tests;
+
+/// The extension of the signature of a bundle file, read from the location of the file
+pub const SIGNATURE_EXTENSION: &str = ".sig";
+
+/// A shortcut of a bundle
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct BundleShortcut {
+    pub name: String,
+    pub path: String,
+    pub description: Option<String>,
+}
+
+/// A named and versioned set of shortcuts shared by a team, installed, updated and removed as
+/// a unit
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Bundle {
+    pub name: String,
+    /// The version of the bundle, increased at each change of its shortcuts
+    pub version: u32,
+    pub description: Option<String>,
+    pub shortcuts: Vec<BundleShortcut>,
+}
+
+/// The outcome of the installation of a bundle
+#[derive(Debug, Default, PartialEq)]
+pub struct InstallSummary {
+    pub name: String,
+    pub version: u32,
+    /// The version installed before, if any
+    pub previous_version: Option<u32>,
+    pub added: usize,
+    /// The shortcuts of the bundle whose path or description changed
+    pub updated: usize,
+    pub unchanged: usize,
+    /// The shortcuts of the previous version which are no longer in the bundle
+    pub removed: usize,
+    /// The shortcuts of the bundle not installed because a personal shortcut, or the one of
+    /// another bundle, has the same name
+    pub conflicts: Vec<String>,
+}
+
+impl fmt::Display for InstallSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.previous_version {
+            Some(previous) => write!(
+                f,
+                "{} updated from version {} to {}",
+                self.name, previous, self.version
+            )?,
+            None => write!(f, "{} version {} installed", self.name, self.version)?,
+        }
+        write!(
+            f,
+            ": {} added, {} updated, {} unchanged, {} removed, {} conflicts",
+            self.added,
+            self.updated,
+            self.unchanged,
+            self.removed,
+            self.conflicts.len()
+        )?;
+        for conflict in &self.conflicts {
+            write!(f, "\n  {}: kept the existing shortcut", conflict)?;
+        }
+        Ok(())
+    }
+}
+
+impl Bundle {
+    /// Check the fields of the bundle: a name, and shortcuts with a unique name and a path.
+    fn validate(&self) -> Result<(), String> {
+        if self.name.is_empty() {
+            return Err(String::from("the bundle has no name"));
+        }
+        let mut names = HashSet::new();
+        for shortcut in &self.shortcuts {
+            if shortcut.name.is_empty() || shortcut.path.is_empty() {
+                return Err(format!(
+                    "the shortcut '{}' has no name or no path",
+                    shortcut.name
+                ));
+            }
+            if !names.insert(&shortcut.name) {
+                return Err(format!("the shortcut '{}' is duplicated", shortcut.name));
+            }
+            RemotePath::validate(&shortcut.path)?;
+        }
+        Ok(())
+    }
+}
+
+/// Decode a key or a signature of `N` bytes, in base64.
+fn decode<const N: usize>(what: &str, text: &str) -> Result<[u8; N], String> {
+    let bytes = STANDARD
+        .decode(text.trim())
+        .map_err(|e| format!("invalid {}: {}", what, e))?;
+    bytes
+        .try_into()
+        .map_err(|_| format!("invalid {}: expected {} bytes", what, N))
+}
+
+/// Decode an ed25519 public key, in base64.
+fn public_key(text: &str) -> Result<VerifyingKey, String> {
+    VerifyingKey::from_bytes(&decode("public key", text)?)
+        .map_err(|e| format!("invalid public key: {}", e))
+}
+
+/// Check a public key trusted to sign the bundles, in base64.
+pub fn validate_public_key(text: &str) -> Result<(), String> { public_key(text).map(|_| ()) }
+
+/// Generate an ed25519 key pair signing the bundles.
+///
+/// ### Returns
+/// The secret key and the public key, in base64.
+pub fn generate_key() -> (String, String) {
+    let key = SigningKey::generate(&mut rand_core::OsRng);
+    (
+        STANDARD.encode(key.to_bytes()),
+        STANDARD.encode(key.verifying_key().to_bytes()),
+    )
+}
+
+/// Sign the contents of a bundle file with `secret_key`, in base64.
+///
+/// ### Returns
+/// The signature, in base64, to write next to the file with the `.sig` extension.
+pub fn sign(contents: &str, secret_key: &str) -> Result<String, String> {
+    let key = SigningKey::from_bytes(&decode("secret key", secret_key)?);
+    Ok(STANDARD.encode(key.sign(contents.as_bytes()).to_bytes()))
+}
+
+/// Check that `signature`, in base64, is the one of the contents of a bundle file by one of
+/// the `public_keys`.
+pub fn verify(contents: &str, signature: &str, public_keys: &[String]) -> Result<(), String> {
+    let signature = Signature::from_bytes(&decode("signature", signature)?);
+    for text in public_keys {
+        if public_key(text)?
+            .verify_strict(contents.as_bytes(), &signature)
+            .is_ok()
+        {
+            return Ok(());
+        }
+    }
+    Err(String::from(
+        "the signature does not match any of the trusted keys",
+    ))
+}
+
+/// Read a bundle file, from a local path or an https:// URL whose SHA-256 is checked when
+/// `sha256` is set. With `public_keys`, the file must be signed by one of them, its
+/// signature being read from the same location with the `.sig` extension.
+pub fn read_bundle_file(
+    location: &str,
+    sha256: Option<&str>,
+    public_keys: &[String],
+) -> Result<Bundle, String> {
+    let contents = fetch::read_location(location, sha256)?;
+    if !public_keys.is_empty() {
+        let signature_location = format!("{}{}", location, SIGNATURE_EXTENSION);
+        let signature = fetch::read_location(&signature_location, None)
+            .map_err(|e| format!("The bundle {} is not signed: {}", location, e))?;
+        verify(&contents, &signature, public_keys)
+            .map_err(|e| format!("The bundle {} is refused: {}", location, e))?;
+        info!("bundle {} signature verified", location);
+    }
+    serde_yaml::from_str(&contents)
+        .map_err(|e| format!("Failed to parse the file {}: {}", location, e))
+}
+
+/// Install or update a bundle, in a single transaction: its shortcuts are added or updated,
+/// and the ones of its previous version which are no longer in it are removed. The personal
+/// shortcuts and the ones of the other bundles are never changed, a shortcut of the bundle
+/// having the same name being reported as a conflict.
+/// An older version than the installed one is only installed with `force`. With `dry_run`,
+/// the installation is rolled back, only reporting what it would change.
+///
+/// ### Returns
+/// The summary of the installation if the operation was successful, otherwise an error.
+pub fn install(
+    store: &Store,
+    bundle: &Bundle,
+    force: bool,
+    dry_run: bool,
+) -> Result<InstallSummary, String> {
+    debug!(
+        "install bundle {} version {} force={} dry_run={}",
+        bundle.name, bundle.version, force, dry_run
+    );
+    bundle.validate()?;
+    let db_error = |e: rusqlite::Error| e.to_string();
+    let previous_version = store.find_bundle_version(&bundle.name).map_err(db_error)?;
+    if let Some(previous) = previous_version
+        && previous > bundle.version
+        && !force
+    {
+        return Err(format!(
+            "{} version {} is installed, use --force to install the older version {}",
+            bundle.name, previous, bundle.version
+        ));
+    }
+
+    let transaction = store.transaction().map_err(db_error)?;
+    let mut summary = InstallSummary {
+        name: bundle.name.clone(),
+        version: bundle.version,
+        previous_version,
+        ..Default::default()
+    };
+    for shortcut in &bundle.shortcuts {
+        let owner = store
+            .find_shortcut_bundle(&shortcut.name)
+            .map_err(db_error)?;
+        match store.find_shortcut(&shortcut.name) {
+            Some(existing) if owner.as_deref() != Some(&bundle.name) => {
+                warn!(
+                    "Shortcut '{}' of the bundle {} not installed, kept on {}",
+                    shortcut.name, bundle.name, existing.path
+                );
+                summary.conflicts.push(shortcut.name.clone());
+                continue;
+            }
+            Some(existing)
+                if existing.path == shortcut.path
+                    && existing.description == shortcut.description =>
+            {
+                summary.unchanged += 1;
+                continue;
+            }
+            Some(_) => summary.updated += 1,
+            None => summary.added += 1,
+        }
+        store
+            .upsert_shortcut(
+                &shortcut.name,
+                &shortcut.path,
+                shortcut.description.as_deref(),
+            )
+            .map_err(db_error)?;
+        store
+            .set_shortcut_bundle(&shortcut.name, Some(&bundle.name))
+            .map_err(db_error)?;
+    }
+    for name in store
+        .list_bundle_shortcut_names(&bundle.name)
+        .map_err(db_error)?
+    {
+        if !bundle
+            .shortcuts
+            .iter()
+            .any(|shortcut| shortcut.name == name)
+        {
+            store.delete_shortcut(&name).map_err(db_error)?;
+            summary.removed += 1;
+        }
+    }
+    store
+        .set_bundle_version(&bundle.name, bundle.version)
+        .map_err(db_error)?;
+    if !dry_run {
+        transaction.commit().map_err(db_error)?;
+    }
+    info!("bundle install summary: {:?}", summary);
+    Ok(summary)
+}
+
+/// Remove a bundle and its shortcuts, the personal shortcuts being kept. With `dry_run`, the
+/// removal is rolled back.
+///
+/// ### Returns
+/// The number of shortcuts removed if the operation was successful, otherwise an error.
+pub fn remove(store: &Store, name: &str, dry_run: bool) -> Result<usize, String> {
+    debug!("remove bundle {} dry_run={}", name, dry_run);
+    let transaction = store.transaction().map_err(|e| e.to_string())?;
+    let removed = store
+        .delete_bundle(name)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("the bundle '{}' is not installed", name))?;
+    if !dry_run {
+        transaction.commit().map_err(|e| e.to_string())?;
+    }
+    Ok(removed)
+}
@@ -0,0 +1,153 @@
+use super::*;
+
+fn bundle(version: u32, shortcuts: &[(&str, &str)]) -> Bundle {
+    Bundle {
+        name: String::from("team"),
+        version,
+        description: None,
+        shortcuts: shortcuts
+            .iter()
+            .map(|(name, path)| BundleShortcut {
+                name: name.to_string(),
+                path: path.to_string(),
+                description: None,
+            })
+            .collect(),
+    }
+}
+
+#[test]
+fn test_parse_bundle() {
+    let parsed: Bundle = serde_yaml::from_str(
+        "name: team\nversion: 2\nshortcuts:\n  - name: mono\n    path: /src/mono\n",
+    )
+    .unwrap();
+    assert_eq!(parsed, bundle(2, &[("mono", "/src/mono")]));
+    assert!(serde_yaml::from_str::<Bundle>("name: team\nshortcuts: []\n").is_err());
+}
+
+#[test]
+fn test_install_update_remove() {
+    let store = Store::setup_test_store();
+    store.upsert_shortcut("mine", "/mine", None).unwrap();
+
+    let summary = install(
+        &store,
+        &bundle(
+            1,
+            &[("mono", "/src/mono"), ("docs", "/src/docs"), ("mine", "/x")],
+        ),
+        false,
+        false,
+    )
+    .unwrap();
+    assert_eq!(summary.added, 2);
+    assert_eq!(summary.conflicts, vec![String::from("mine")]);
+    assert_eq!(store.find_shortcut("mine").unwrap().path, "/mine");
+    assert_eq!(
+        store.find_shortcut_bundle("mono").unwrap(),
+        Some(String::from("team"))
+    );
+    assert_eq!(store.find_shortcut_bundle("mine").unwrap(), None);
+
+    let summary = install(
+        &store,
+        &bundle(2, &[("mono", "/src/monorepo"), ("api", "/src/api")]),
+        false,
+        false,
+    )
+    .unwrap();
+    assert_eq!(summary.previous_version, Some(1));
+    assert_eq!((summary.added, summary.updated, summary.removed), (1, 1, 1));
+    assert!(store.find_shortcut("docs").is_none());
+    assert_eq!(store.find_shortcut("mono").unwrap().path, "/src/monorepo");
+    assert_eq!(
+        store.list_bundles().unwrap(),
+        vec![(String::from("team"), 2, 2)]
+    );
+
+    assert!(install(&store, &bundle(1, &[]), false, false).is_err());
+
+    // a dry run changes nothing
+    let summary = install(&store, &bundle(3, &[]), false, true).unwrap();
+    assert_eq!(summary.removed, 2);
+    assert_eq!(remove(&store, "team", true).unwrap(), 2);
+    assert_eq!(store.find_bundle_version("team").unwrap(), Some(2));
+    assert!(store.find_shortcut("mono").is_some());
+
+    assert_eq!(remove(&store, "team", false).unwrap(), 2);
+    assert!(store.find_shortcut("mono").is_none());
+    assert_eq!(store.find_shortcut("mine").unwrap().path, "/mine");
+    assert!(remove(&store, "team", false).is_err());
+}
+
+#[test]
+fn test_personal_shortcut_replacing_a_bundle_one() {
+    let store = Store::setup_test_store();
+    install(&store, &bundle(1, &[("mono", "/src/mono")]), false, false).unwrap();
+    store
+        .upsert_shortcut("mono", "/home/me/mono", None)
+        .unwrap();
+
+    let summary = install(&store, &bundle(2, &[("mono", "/src/mono")]), false, false).unwrap();
+    assert_eq!(summary.conflicts, vec![String::from("mono")]);
+    assert_eq!(store.find_shortcut("mono").unwrap().path, "/home/me/mono");
+}
+
+#[test]
+fn test_invalid_bundle() {
+    let store = Store::setup_test_store();
+    assert!(
+        install(
+            &store,
+            &bundle(1, &[("a", "/a"), ("a", "/b")]),
+            false,
+            false
+        )
+        .is_err()
+    );
+    assert!(install(&store, &bundle(1, &[("a", "")]), false, false).is_err());
+    assert!(store.list_bundles().unwrap().is_empty());
+}
+
+#[test]
+fn test_sign_verify() {
+    let contents = "name: team\nversion: 1\nshortcuts: []\n";
+    let (secret_key, public_key) = generate_key();
+    let (_, other_key) = generate_key();
+    validate_public_key(&public_key).unwrap();
+    assert!(validate_public_key("not a key").is_err());
+
+    let signature = sign(contents, &secret_key).unwrap();
+    let trusted = [other_key.clone(), public_key];
+    verify(contents, &signature, &trusted).unwrap();
+    // a changed file, or another key, is refused
+    assert!(verify("name: evil\n", &signature, &trusted).is_err());
+    assert!(verify(contents, &signature, &[other_key]).is_err());
+    assert!(verify(contents, "garbage", &trusted).is_err());
+}
+
+#[test]
+fn test_read_signed_bundle_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("team.yaml");
+    let location = file.to_str().unwrap();
+    let contents = "name: team\nversion: 1\nshortcuts:\n  - name: mono\n    path: /src/mono\n";
+    std::fs::write(&file, contents).unwrap();
+    let (secret_key, public_key) = generate_key();
+    let keys = [public_key];
+
+    // without a trusted key, the signature is not needed
+    assert_eq!(read_bundle_file(location, None, &[]).unwrap().version, 1);
+    assert!(read_bundle_file(location, None, &keys).is_err());
+
+    let signature_file = format!("{}{}", location, SIGNATURE_EXTENSION);
+    std::fs::write(&signature_file, sign(contents, &secret_key).unwrap()).unwrap();
+    assert_eq!(
+        read_bundle_file(location, None, &keys).unwrap().name,
+        "team"
+    );
+
+    std::fs::write(&file, contents.replace("/src/mono", "/tmp/evil")).unwrap();
+    assert!(read_bundle_file(location, None, &keys).is_err());
+}
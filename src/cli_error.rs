@@ -0,0 +1,93 @@
+use std::{fmt, io, process::ExitCode};
+
+#[cfg(test)]
+#[path = "cli_error_tests.rs"]
+mod cli_error_tests;
+
+/// The kinds of failures of the commands, each one with its exit code, documented in
+/// docs/commands.md
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum ErrorKind {
+    /// The shortcut, path, bundle or file the command is about does not exist
+    NotFound,
+    /// The database cannot be read or written
+    Database,
+    /// The configuration cannot be loaded
+    Config,
+    /// An argument or the content of a file is invalid
+    InvalidInput,
+    /// A file cannot be read, written or downloaded
+    Io,
+    /// The confirmation of a destructive command was declined or could not be asked
+    Cancelled,
+    /// The command line cannot be parsed
+    Usage,
+    /// A failure of cdir itself
+    Internal,
+}
+
+impl ErrorKind {
+    /// The exit code of the process failing with this kind of error
+    pub(crate) fn exit_code(self) -> u8 {
+        match self {
+            ErrorKind::NotFound => 1,
+            ErrorKind::Database => 2,
+            ErrorKind::Config => 3,
+            ErrorKind::InvalidInput => 4,
+            ErrorKind::Io => 5,
+            ErrorKind::Cancelled => 6,
+            ErrorKind::Usage => 64,
+            ErrorKind::Internal => 70,
+        }
+    }
+}
+
+/// The failure of a command, reported by `main` and turned into the exit code of its kind
+#[derive(Debug, PartialEq)]
+pub(crate) struct CliError {
+    pub kind: ErrorKind,
+    pub message: String,
+}
+
+impl CliError {
+    pub(crate) fn new(kind: ErrorKind, message: impl fmt::Display) -> CliError {
+        CliError {
+            kind,
+            message: message.to_string(),
+        }
+    }
+
+    pub(crate) fn not_found(message: impl fmt::Display) -> CliError {
+        Self::new(ErrorKind::NotFound, message)
+    }
+
+    pub(crate) fn database(message: impl fmt::Display) -> CliError {
+        Self::new(ErrorKind::Database, message)
+    }
+
+    pub(crate) fn config(message: impl fmt::Display) -> CliError {
+        Self::new(ErrorKind::Config, message)
+    }
+
+    pub(crate) fn invalid(message: impl fmt::Display) -> CliError {
+        Self::new(ErrorKind::InvalidInput, message)
+    }
+
+    pub(crate) fn io(message: impl fmt::Display) -> CliError { Self::new(ErrorKind::Io, message) }
+
+    pub(crate) fn exit_code(&self) -> ExitCode { ExitCode::from(self.kind.exit_code()) }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { write!(f, "{}", self.message) }
+}
+
+impl std::error::Error for CliError {}
+
+impl From<rusqlite::Error> for CliError {
+    fn from(e: rusqlite::Error) -> Self { Self::database(e) }
+}
+
+impl From<io::Error> for CliError {
+    fn from(e: io::Error) -> Self { Self::io(e) }
+}
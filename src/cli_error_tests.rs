@@ -0,0 +1,21 @@
+use super::*;
+
+#[test]
+fn test_exit_codes() {
+    assert_eq!(ErrorKind::NotFound.exit_code(), 1);
+    assert_eq!(ErrorKind::Database.exit_code(), 2);
+    assert_eq!(ErrorKind::Config.exit_code(), 3);
+    assert_eq!(ErrorKind::Usage.exit_code(), 64);
+}
+
+#[test]
+fn test_conversions() {
+    let error = CliError::from(rusqlite::Error::InvalidQuery);
+    assert_eq!(error.kind, ErrorKind::Database);
+    let error = CliError::from(io::Error::other("disk full"));
+    assert_eq!(error, CliError::io("disk full"));
+    assert_eq!(
+        CliError::not_found("no shortcut w").to_string(),
+        "no shortcut w"
+    );
+}
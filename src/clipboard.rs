@@ -0,0 +1,69 @@
+use std::{cell::RefCell, env, io::Write};
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use tracing::{debug, warn};
+
+#[cfg(test)]
+#[path = "clipboard_tests.rs"]
+mod clipboard_tests;
+
+thread_local! {
+    // The clipboard is kept alive as long as the GUI runs: on X11 the content is served
+    // by its owner and would vanish as soon as the clipboard is dropped.
+    static CLIPBOARD: RefCell<Option<arboard::Clipboard>> = const { RefCell::new(None) };
+}
+
+/// Return true when running through SSH, where the local clipboard is not the user's one.
+fn is_ssh_session() -> bool {
+    env::var_os("SSH_TTY").is_some() || env::var_os("SSH_CONNECTION").is_some()
+}
+
+/// Build the OSC 52 escape sequence asking the terminal to set its clipboard to `text`.
+/// Inside tmux, the sequence is wrapped into a DCS passthrough.
+pub(crate) fn osc52_sequence(text: &str, tmux: bool) -> String {
+    let sequence = format!("\x1b]52;c;{}\x07", STANDARD.encode(text));
+    if tmux {
+        format!("\x1bPtmux;\x1b{}\x1b\\", sequence)
+    } else {
+        sequence
+    }
+}
+
+fn copy_with_osc52(text: &str) -> Result<(), String> {
+    debug!("copy with OSC 52");
+    let mut stdout = std::io::stdout();
+    stdout
+        .write_all(osc52_sequence(text, env::var_os("TMUX").is_some()).as_bytes())
+        .and_then(|_| stdout.flush())
+        .map_err(|e| format!("failed to write the OSC 52 sequence: {}", e))
+}
+
+fn copy_with_system_clipboard(text: &str) -> Result<(), String> {
+    CLIPBOARD.with(|clipboard| {
+        let mut clipboard = clipboard.borrow_mut();
+        if clipboard.is_none() {
+            *clipboard = Some(arboard::Clipboard::new().map_err(|e| e.to_string())?);
+        }
+        clipboard
+            .as_mut()
+            .unwrap()
+            .set_text(text)
+            .map_err(|e| e.to_string())
+    })
+}
+
+/// Copy a text to the clipboard.
+/// The system clipboard is used for local sessions, the terminal clipboard (OSC 52) is
+/// used for SSH sessions or when the system clipboard is not available.
+pub(crate) fn copy(text: &str) -> Result<(), String> {
+    if is_ssh_session() {
+        return copy_with_osc52(text);
+    }
+    copy_with_system_clipboard(text).or_else(|e| {
+        warn!(
+            "system clipboard not available ({}), falling back on OSC 52",
+            e
+        );
+        copy_with_osc52(text)
+    })
+}
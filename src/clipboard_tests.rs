@@ -0,0 +1,17 @@
+use super::*;
+
+#[test]
+fn test_osc52_sequence() {
+    assert_eq!(
+        osc52_sequence("/home/user", false),
+        "\x1b]52;c;L2hvbWUvdXNlcg==\x07"
+    );
+}
+
+#[test]
+fn test_osc52_sequence_in_tmux() {
+    assert_eq!(
+        osc52_sequence("/tmp", true),
+        "\x1bPtmux;\x1b\x1b]52;c;L3RtcA==\x07\x1b\\"
+    );
+}
@@ -0,0 +1,133 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Padding, Paragraph},
+};
+use tracing::debug;
+
+use crate::{
+    theme::ThemeStyles,
+    tui::{EventCaptured, ManagerAction, View, ViewBuilder, ViewManager},
+};
+
+#[cfg(test)]
+#[path = "command_output_view_tests.rs"]
+mod command_output_view_tests;
+
+/// A modal showing the output of a command, scrollable with the arrow and page keys.
+pub struct CommandOutputView {
+    styles: ThemeStyles,
+    title: String,
+    lines: Vec<String>,
+    /// Index of the first displayed line
+    scroll: usize,
+    /// Number of lines displayed by the last draw
+    page_height: usize,
+}
+
+impl CommandOutputView {
+    pub fn builder(title: String, output: &str, styles: ThemeStyles) -> ViewBuilder {
+        ViewBuilder::from(Box::new(Self::new(title, output, styles)))
+    }
+
+    fn new(title: String, output: &str, styles: ThemeStyles) -> Self {
+        let mut lines: Vec<String> = output
+            .lines()
+            .map(|line| line.replace('\t', "    "))
+            .collect();
+        if lines.is_empty() {
+            lines.push(String::from("(no output)"));
+        }
+        Self {
+            styles,
+            title,
+            lines,
+            scroll: 0,
+            page_height: 1,
+        }
+    }
+
+    fn max_scroll(&self) -> usize { self.lines.len().saturating_sub(self.page_height) }
+
+    fn scroll_by(&mut self, delta: isize) {
+        self.scroll = self
+            .scroll
+            .saturating_add_signed(delta)
+            .min(self.max_scroll());
+    }
+}
+
+impl View for CommandOutputView {
+    fn draw(&mut self, frame: &mut ratatui::Frame, _area: Rect, _active: bool) {
+        debug!("Drawing command output");
+        let area = frame.area();
+        let modal_width = (area.width.saturating_mul(4) / 5).max(20).min(area.width);
+        let modal_height = (self.lines.len() as u16)
+            .saturating_add(4)
+            .min(area.height.saturating_mul(4) / 5)
+            .max(5)
+            .min(area.height);
+        let modal_area = ViewManager::centered_rect(area, modal_width, modal_height);
+        self.page_height = (modal_height.saturating_sub(4) as usize).max(1);
+        self.scroll = self.scroll.min(self.max_scroll());
+
+        frame.render_widget(Clear, modal_area);
+        if let Some(bg_color) = &self.styles.background_color {
+            let background = Paragraph::new("").style(Style::default().bg(*bg_color));
+            frame.render_widget(background, modal_area);
+        }
+
+        let mut block = Block::default()
+            .title(Span::styled(
+                format!(" {} ", self.title),
+                self.styles.title_style,
+            ))
+            .borders(Borders::ALL)
+            .padding(Padding::new(1, 1, 1, 1));
+        if self.lines.len() > self.page_height {
+            block = block.title_bottom(Span::styled(
+                format!(
+                    " {}-{}/{} ",
+                    self.scroll + 1,
+                    (self.scroll + self.page_height).min(self.lines.len()),
+                    self.lines.len()
+                ),
+                self.styles.text_style,
+            ));
+        }
+        if let Some(border_color) = self.styles.border_color {
+            block = block.border_style(Style::default().fg(border_color));
+        }
+
+        let lines: Vec<Line> = self
+            .lines
+            .iter()
+            .skip(self.scroll)
+            .take(self.page_height)
+            .map(|line| Line::from(Span::styled(line.as_str(), self.styles.text_style)))
+            .collect();
+        frame.render_widget(Paragraph::new(lines).block(block), modal_area);
+    }
+
+    fn handle_key_event(&mut self, key_event: KeyEvent) -> (EventCaptured, ManagerAction) {
+        let page = self.page_height as isize;
+        match key_event.code {
+            KeyCode::Enter | KeyCode::Char('q') => {
+                return (
+                    EventCaptured::Yes,
+                    ManagerAction::new(false).with_close(true),
+                );
+            }
+            KeyCode::Down => self.scroll_by(1),
+            KeyCode::Up => self.scroll_by(-1),
+            KeyCode::PageDown => self.scroll_by(page),
+            KeyCode::PageUp => self.scroll_by(-page),
+            KeyCode::Home => self.scroll = 0,
+            KeyCode::End => self.scroll = self.max_scroll(),
+            _ => return (EventCaptured::Yes, ManagerAction::new(false)),
+        }
+        (EventCaptured::Yes, ManagerAction::new(true))
+    }
+}
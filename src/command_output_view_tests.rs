@@ -0,0 +1,37 @@
+use crossterm::event::KeyModifiers;
+
+use super::*;
+
+fn press(view: &mut CommandOutputView, code: KeyCode) {
+    view.handle_key_event(KeyEvent::new(code, KeyModifiers::NONE));
+}
+
+#[test]
+fn test_empty_output() {
+    let view = CommandOutputView::new(String::from("ls"), "", ThemeStyles::default());
+    assert_eq!(view.lines, vec!["(no output)"]);
+}
+
+#[test]
+fn test_scroll_is_bounded() {
+    let output: Vec<String> = (0..10).map(|i| i.to_string()).collect();
+    let mut view = CommandOutputView::new(
+        String::from("seq"),
+        &output.join("\n"),
+        ThemeStyles::default(),
+    );
+    view.page_height = 4;
+
+    press(&mut view, KeyCode::Up);
+    assert_eq!(view.scroll, 0);
+    press(&mut view, KeyCode::PageDown);
+    assert_eq!(view.scroll, 4);
+    press(&mut view, KeyCode::PageDown);
+    assert_eq!(view.scroll, 6);
+    press(&mut view, KeyCode::Down);
+    assert_eq!(view.scroll, 6);
+    press(&mut view, KeyCode::Home);
+    assert_eq!(view.scroll, 0);
+    press(&mut view, KeyCode::End);
+    assert_eq!(view.scroll, 6);
+}
@@ -0,0 +1,266 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+use tokio::sync::broadcast;
+use tracing::{debug, error, info};
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    config::FuzzyConfig,
+    keybindings::{self, Action, KEY_BINDINGS, KeyBinding, Scope},
+    matcher,
+    theme::ThemeStyles,
+    tui::{
+        EventCaptured, GenericEvent, ManagerAction, View, ViewBuilder, ViewManager,
+        event::{ApplicationEvent, ViewManagerEvent},
+    },
+};
+
+#[cfg(test)]
+#[path = "command_palette_tests.rs"]
+mod command_palette_tests;
+
+/// The id of the application event purging every path of the trash
+pub(crate) const EMPTY_TRASH_EVENT: &str = "trash.empty";
+
+/// What running a command of the palette does
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum PaletteAction {
+    /// Handle the key as if it was pressed
+    Key(KeyEvent),
+    /// Publish the application event of this id, for the commands not bound to a key
+    Event(String),
+}
+
+/// A command listed in the palette
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct PaletteCommand {
+    pub(crate) title: String,
+    /// The keys running the command without the palette, empty if none
+    pub(crate) keys: String,
+    pub(crate) action: PaletteAction,
+}
+
+impl PaletteCommand {
+    /// Run the command, through the event loop so that it runs once the modal running it closed.
+    pub(crate) fn run(&self, tx: &broadcast::Sender<GenericEvent>) {
+        info!("running the command '{}'", self.title);
+        let event = match &self.action {
+            PaletteAction::Key(key_event) => {
+                GenericEvent::ViewManagerEvent(ViewManagerEvent::Key(*key_event))
+            }
+            PaletteAction::Event(id) => GenericEvent::ApplicationEvent(ApplicationEvent {
+                id: id.clone(),
+                payload: None,
+            }),
+        };
+        if let Err(e) = tx.send(event) {
+            error!("Failed to run the command '{}': {}", self.title, e);
+        }
+    }
+
+    /// The command running an application event
+    pub(crate) fn event(title: &str, event_id: &str) -> PaletteCommand {
+        PaletteCommand {
+            title: String::from(title),
+            keys: String::new(),
+            action: PaletteAction::Event(String::from(event_id)),
+        }
+    }
+
+    /// The command pressing the first key of `binding`, None for the families of keys such as
+    /// alt+1..9 and the keys taken by the readline bindings
+    pub(crate) fn binding(binding: &KeyBinding, title: &str) -> Option<PaletteCommand> {
+        let key_event = binding
+            .keys
+            .iter()
+            .filter_map(|key| key.event())
+            .find(|key_event| !keybindings::is_shadowed(key_event))?;
+        Some(PaletteCommand {
+            title: String::from(title),
+            keys: keybindings::keys_label(binding),
+            action: PaletteAction::Key(key_event),
+        })
+    }
+}
+
+/// The scopes of the bindings applying to the view of `scope`
+pub(crate) fn view_scopes(scope: Scope) -> [Scope; 4] {
+    [Scope::Global, Scope::Tables, scope, Scope::Search]
+}
+
+/// The commands bound to a key in the view of `scope` (with the global, table and search
+/// ones), the families of keys such as alt+1..9 being left out.
+pub(crate) fn key_commands(scope: Scope) -> Vec<PaletteCommand> {
+    let scopes = view_scopes(scope);
+    KEY_BINDINGS
+        .iter()
+        .filter(|binding| scopes.contains(&binding.scope))
+        .filter(|binding| binding.action != Action::CommandPalette)
+        .filter_map(|binding| PaletteCommand::binding(binding, binding.description))
+        .collect()
+}
+
+/// A modal searching the actions of the GUI, the chosen one being run once the modal is closed.
+pub(crate) struct CommandPalette {
+    tx: broadcast::Sender<GenericEvent>,
+    styles: ThemeStyles,
+    fuzzy_config: FuzzyConfig,
+    commands: Vec<PaletteCommand>,
+    query: String,
+    /// The indexes of the commands matching the query, the best match first
+    matches: Vec<usize>,
+    selected: usize,
+}
+
+impl CommandPalette {
+    pub(crate) fn builder(
+        tx: broadcast::Sender<GenericEvent>,
+        commands: Vec<PaletteCommand>,
+        fuzzy_config: FuzzyConfig,
+        styles: ThemeStyles,
+    ) -> ViewBuilder {
+        ViewBuilder::from(Box::new(Self::new(tx, commands, fuzzy_config, styles)))
+    }
+
+    fn new(
+        tx: broadcast::Sender<GenericEvent>,
+        commands: Vec<PaletteCommand>,
+        fuzzy_config: FuzzyConfig,
+        styles: ThemeStyles,
+    ) -> Self {
+        let mut view = Self {
+            tx,
+            styles,
+            fuzzy_config,
+            commands,
+            query: String::new(),
+            matches: vec![],
+            selected: 0,
+        };
+        view.update_matches();
+        view
+    }
+
+    /// Match the commands against the query, by their title and their keys, the commands keeping
+    /// their order among equal matches.
+    fn update_matches(&mut self) {
+        self.selected = 0;
+        if self.query.is_empty() {
+            self.matches = (0..self.commands.len()).collect();
+            return;
+        }
+        let mut matcher = matcher::build(&self.fuzzy_config, &self.query, false);
+        let mut scored: Vec<(usize, u32)> = self
+            .commands
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, command)| {
+                matcher
+                    .score(&format!("{} {}", command.title, command.keys))
+                    .map(|score| (idx, score))
+            })
+            .collect();
+        scored.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+        self.matches = scored.into_iter().map(|(idx, _)| idx).collect();
+    }
+
+    /// Run the selected command, the palette being closed by then.
+    fn run_selected(&self) {
+        let Some(command) = self
+            .matches
+            .get(self.selected)
+            .map(|idx| &self.commands[*idx])
+        else {
+            return;
+        };
+        command.run(&self.tx);
+    }
+}
+
+impl View for CommandPalette {
+    fn draw(&mut self, frame: &mut ratatui::Frame, _area: Rect, _active: bool) {
+        debug!("Drawing the command palette");
+        let area = frame.area();
+        // the borders and the query take 3 lines
+        let height = (self.matches.len() as u16 + 3)
+            .clamp(4, 20)
+            .min(area.height);
+        let width = 80.min(area.width);
+        let modal_area = ViewManager::centered_rect(area, width, height);
+
+        frame.render_widget(Clear, modal_area);
+        if let Some(bg_color) = &self.styles.background_color {
+            let background = Paragraph::new("").style(Style::default().bg(*bg_color));
+            frame.render_widget(background, modal_area);
+        }
+
+        let visible = height.saturating_sub(3) as usize;
+        let first = self.selected.saturating_sub(visible.saturating_sub(1));
+        let mut lines = vec![Line::from(vec![
+            Span::styled("> ", self.styles.date_style),
+            Span::styled(self.query.clone(), self.styles.text_em_style),
+        ])];
+        // the keys are aligned on the right of the window
+        let inner_width = width.saturating_sub(2) as usize;
+        for (row, idx) in self.matches.iter().enumerate().skip(first).take(visible) {
+            let command = &self.commands[*idx];
+            let mut style = self.styles.path_style;
+            if row == self.selected {
+                style = self.styles.highlight(style);
+            }
+            let padding = inner_width.saturating_sub(command.title.width() + command.keys.width());
+            lines.push(Line::from(vec![
+                Span::styled(command.title.clone(), style),
+                Span::styled(" ".repeat(padding), style),
+                Span::styled(command.keys.clone(), self.styles.date_style),
+            ]));
+        }
+
+        let mut block = Block::default()
+            .title(Span::styled(" Commands ", self.styles.title_style))
+            .borders(Borders::ALL);
+        if let Some(border_color) = self.styles.border_color {
+            block = block.border_style(Style::default().fg(border_color));
+        }
+        frame.render_widget(Paragraph::new(lines).block(block), modal_area);
+        frame.set_cursor_position((
+            modal_area.x + 3 + self.query.chars().count() as u16,
+            modal_area.y + 1,
+        ));
+    }
+
+    fn handle_key_event(&mut self, key_event: KeyEvent) -> (EventCaptured, ManagerAction) {
+        match key_event.code {
+            KeyCode::Enter => {
+                self.run_selected();
+                return (
+                    EventCaptured::Yes,
+                    ManagerAction::new(false).with_close(true),
+                );
+            }
+            KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down => {
+                self.selected = (self.selected + 1).min(self.matches.len().saturating_sub(1))
+            }
+            KeyCode::Backspace => {
+                self.query.pop();
+                self.update_matches();
+            }
+            KeyCode::Char(c)
+                if !key_event
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                self.query.push(c);
+                self.update_matches();
+            }
+            _ => return (EventCaptured::Yes, ManagerAction::new(false)),
+        }
+        (EventCaptured::Yes, ManagerAction::new(true))
+    }
+}
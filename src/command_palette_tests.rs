@@ -0,0 +1,64 @@
+use super::*;
+use crate::tui::ViewManager;
+
+#[test]
+fn test_key_commands() {
+    let commands = key_commands(Scope::History);
+    let titles: Vec<&str> = commands
+        .iter()
+        .map(|command| command.title.as_str())
+        .collect();
+    assert!(titles.contains(&"switch between the views"));
+    assert!(titles.contains(&"list the deleted paths (the trash), or the history again"));
+    // the bindings of the other view and the families of keys are left out
+    assert!(!titles.contains(&"suggest shortcuts for the directories visited often"));
+    assert!(!titles.contains(&"choose one of the numbered paths of the history"));
+    assert!(!titles.contains(&"search the actions and run one"));
+
+    let trash = commands
+        .iter()
+        .find(|command| command.keys == "ctrl+b")
+        .unwrap();
+    assert_eq!(
+        trash.action,
+        PaletteAction::Key(KeyEvent::new(KeyCode::Char('b'), KeyModifiers::CONTROL))
+    );
+}
+
+#[test]
+fn test_filter_and_run() {
+    let vm = ViewManager::new();
+    let mut rx = vm.tx().subscribe();
+    let mut commands = key_commands(Scope::Shortcuts);
+    commands.push(PaletteCommand::event("empty the trash", EMPTY_TRASH_EVENT));
+    let mut palette = CommandPalette::new(
+        vm.tx(),
+        commands,
+        FuzzyConfig::default(),
+        ThemeStyles::default(),
+    );
+    assert_eq!(palette.matches.len(), palette.commands.len());
+
+    for c in "sgst shrtc".chars() {
+        palette.handle_key_event(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+    }
+    let first = &palette.commands[palette.matches[0]];
+    assert_eq!(first.keys, "ctrl+l");
+    let (_, action) = palette.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    assert!(action.close());
+    match rx.try_recv().unwrap() {
+        GenericEvent::ViewManagerEvent(ViewManagerEvent::Key(key_event)) => assert_eq!(
+            key_event,
+            KeyEvent::new(KeyCode::Char('l'), KeyModifiers::CONTROL)
+        ),
+        event => panic!("unexpected event {:?}", event),
+    }
+
+    palette.query = String::from("empty trash");
+    palette.update_matches();
+    palette.run_selected();
+    match rx.try_recv().unwrap() {
+        GenericEvent::ApplicationEvent(ae) => assert_eq!(ae.id, EMPTY_TRASH_EVENT),
+        event => panic!("unexpected event {:?}", event),
+    }
+}
@@ -0,0 +1,85 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::Span,
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+use tracing::debug;
+use tui_textarea::{Input, TextArea};
+
+use crate::{
+    theme::ThemeStyles,
+    tui::{EventCaptured, ManagerAction, View, ViewBuilder, ViewManager},
+};
+
+/// A modal prompting for a one-off shell command to run in a directory.
+pub struct CommandPrompt {
+    styles: ThemeStyles,
+    directory: String,
+    textarea: TextArea<'static>,
+    command: Option<String>,
+}
+
+impl CommandPrompt {
+    pub fn builder(directory: String, styles: ThemeStyles) -> ViewBuilder {
+        let mut textarea = TextArea::default();
+        textarea.set_cursor_line_style(styles.text_style);
+        textarea.set_cursor_style(Style::default().add_modifier(Modifier::REVERSED));
+        ViewBuilder::from(Box::new(Self {
+            styles,
+            directory,
+            textarea,
+            command: None,
+        }))
+    }
+
+    /// The directory the command will run in
+    pub fn directory(&self) -> &str { &self.directory }
+
+    /// The validated command, None if the prompt was cancelled or left empty
+    pub fn command(&self) -> Option<&str> { self.command.as_deref() }
+}
+
+impl View for CommandPrompt {
+    fn draw(&mut self, frame: &mut ratatui::Frame, _area: Rect, _active: bool) {
+        debug!("Drawing command prompt");
+        let area = frame.area();
+        let modal_width = (area.width.saturating_mul(4) / 5).max(20).min(area.width);
+        let modal_area = ViewManager::centered_rect(area, modal_width, 3.min(area.height));
+
+        frame.render_widget(Clear, modal_area);
+        if let Some(bg_color) = &self.styles.background_color {
+            let background = Paragraph::new("").style(Style::default().bg(*bg_color));
+            frame.render_widget(background, modal_area);
+        }
+
+        let mut block = Block::default()
+            .title(Span::styled(
+                format!(" Run in {} ", self.directory),
+                self.styles.title_style,
+            ))
+            .borders(Borders::ALL);
+        if let Some(border_color) = self.styles.border_color {
+            block = block.border_style(Style::default().fg(border_color));
+        }
+        self.textarea.set_block(block);
+        frame.render_widget(&self.textarea, modal_area);
+    }
+
+    fn handle_key_event(&mut self, key_event: KeyEvent) -> (EventCaptured, ManagerAction) {
+        if key_event.code == KeyCode::Enter {
+            let command = self.textarea.lines().join(" ");
+            let command = command.trim();
+            if !command.is_empty() {
+                self.command = Some(command.to_string());
+            }
+            return (
+                EventCaptured::Yes,
+                ManagerAction::new(false).with_close(true),
+            );
+        }
+        self.textarea.input(Input::from(key_event));
+        (EventCaptured::Yes, ManagerAction::new(true))
+    }
+}
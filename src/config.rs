@@ -1,23 +1,44 @@
-use std::{
-    env, fs,
-    io::Write,
-    path::PathBuf,
-    sync::{Arc, OnceLock},
-};
+use std::{collections::BTreeMap, env, fs, io::Write, path::PathBuf, sync::OnceLock};
 
-use chrono::{DateTime, Local};
-use log::{debug, error, info, trace};
+use ratatui::layout::Constraint;
 use serde::{Deserialize, Serialize};
 use serde_yaml::Value;
+use tracing::{debug, error, info, trace};
 use yamlpatch::{Op, Patch, apply_yaml_patches};
 use yamlpath::route;
 
-use crate::theme::{Theme, ThemeStyles};
+use crate::{
+    bundle,
+    date_formatter::{DateFormatters, DateStyle},
+    remote,
+    theme::{Theme, ThemeStyles},
+};
 
-pub(crate) const CDIR_CONFIG_VAR: &str = "CDIR_CONFIG";
+pub const CDIR_CONFIG_VAR: &str = "CDIR_CONFIG";
 
 static CONFIG_FILE_PATH: OnceLock<PathBuf> = OnceLock::new();
 
+/// The columns available in the history table
+pub const HISTORY_COLUMNS: [&str; 7] = [
+    "date",
+    "path",
+    "description",
+    "host",
+    "size",
+    "type",
+    "visits",
+];
+
+/// The columns available in the shortcuts table
+pub const SHORTCUTS_COLUMNS: [&str; 4] = ["shortcut", "path", "description", "type"];
+
+/// The keys which cannot be bound to an "open with" command, as alt+key is already used
+const RESERVED_COMMAND_KEYS: [char; 12] =
+    ['b', 'f', 'y', '1', '2', '3', '4', '5', '6', '7', '8', '9'];
+
+/// Width of the date and shortcut name columns when not configured
+const DEFAULT_FIXED_COLUMN_WIDTH: u16 = 20;
+
 const DEFAULT_DB_PATH: fn() -> Option<PathBuf> = || {
     let mut path = dirs::data_dir().unwrap();
     path.push("cdir");
@@ -25,22 +46,53 @@ const DEFAULT_DB_PATH: fn() -> Option<PathBuf> = || {
     Some(path)
 };
 
-const DEFAULT_LOG_CONFIG_PATH: fn() -> Option<PathBuf> = || {
-    let mut path = dirs::home_dir().unwrap();
-    path.push(".config");
+const DEFAULT_LOG_FILE: fn() -> Option<PathBuf> = || {
+    let mut path = dirs::data_dir().unwrap();
     path.push("cdir");
-    path.push("log4rs.yaml");
+    path.push("cdir.log");
     Some(path)
 };
 
+const DEFAULT_LOG_LEVEL: fn() -> String = || String::from("info");
+
 const PATH_VIEW_PATH_COLUMN_WEIGHT: fn() -> usize = || 3;
 
 const PATH_VIEW_DESCRIPTION_COLUMN_WEIGHT: fn() -> usize = || 2;
 
 const DEFAULT_SMART_SUGGESTIONS_DEPTH: fn() -> usize = || 5;
 
+const DEFAULT_SMART_SUGGESTIONS_HALF_LIFE_DAYS: fn() -> u32 = || 30;
+
+const DEFAULT_SMART_SUGGESTIONS_ADJACENCY_WEIGHT: fn() -> u32 = || 10;
+
+const DEFAULT_SMART_SUGGESTIONS_RECENCY_WEIGHT: fn() -> u32 = || 1;
+
+const DEFAULT_SMART_SUGGESTIONS_FREQUENCY_WEIGHT: fn() -> u32 = || 1;
+
 const DEFAULT_SMART_SUGGESTIONS_COUNT: fn() -> usize = || 3;
 
+const DEFAULT_SUGGESTIONS_PANEL_RATIO: fn() -> u16 = || 50;
+
+const DEFAULT_FRAME_BUDGET_MS: fn() -> u64 = || 16;
+
+const DEFAULT_DIR_SIZE_TTL: fn() -> u64 = || 24 * 3600;
+
+const DEFAULT_SHORTCUT_CHECK_INTERVAL: fn() -> u64 = || 3600;
+
+const DEFAULT_TRASH_RETENTION_DAYS: fn() -> u64 = || 30;
+
+const DEFAULT_SESSION_IDLE_MINUTES: fn() -> u64 = || 30;
+
+const DEFAULT_SHORTCUT_SUGGESTIONS_MIN_VISITS: fn() -> usize = || 10;
+
+const DEFAULT_SHORTCUT_SUGGESTIONS_MIN_DEPTH: fn() -> usize = || 3;
+
+const DEFAULT_BUSY_TIMEOUT_MS: fn() -> u64 = || 5000;
+
+const DEFAULT_FUZZY_RECENCY_BOOST: fn() -> u32 = || 8;
+
+const DEFAULT_FUZZY_FREQUENCY_BOOST: fn() -> u32 = || 8;
+
 const DEFAULT_THEMES_DIRECTORY_PATH: fn() -> Option<PathBuf> = || {
     let mut path = dirs::home_dir().unwrap();
     path.push(".config");
@@ -51,17 +103,357 @@ const DEFAULT_THEMES_DIRECTORY_PATH: fn() -> Option<PathBuf> = || {
 
 const DEFAULT_DATE_FORMAT: fn() -> String = || String::from("%d-%b-%y %H:%M:%S");
 
+const DEFAULT_REMOTE_COMMAND_TEMPLATE: fn() -> String =
+    || String::from("ssh -t {target} {command}");
+
 const DEFAULT_THEME: fn() -> Option<String> = || Some(String::from("default"));
 
 const DEFAULT_COLORS: fn() -> Theme = || serde_yaml::from_str("").unwrap();
 
-const DEFAULT_DATE_FORMATER: fn() -> Arc<dyn Fn(i64) -> String + Send + Sync> =
-    || Arc::from(|_| String::from(""));
+/// The seconds since the epoch, until the configuration is processed
+const DEFAULT_DATE_FORMATTERS: fn() -> DateFormatters =
+    || DateFormatters::new(DateStyle::Format, DateStyle::Format, "%s");
 
 const DEFAULT_NONE: fn() -> Option<String> = || None;
 
 const DEFAULT_TRUE: fn() -> bool = || true;
 
+const DEFAULT_FALSE: fn() -> bool = || false;
+
+/// How the smart suggestions are ranked
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SmartSuggestionsStrategy {
+    /// The directories visited right after the previous visits of the current one
+    #[default]
+    Window,
+    /// The transitions from the current directory, weighted by the time of the day and the
+    /// day of the week they were made at
+    Transitions,
+}
+
+/// A column displayed in the history or shortcuts table.
+/// The column either has a fixed `width`, or takes a share of the remaining space
+/// proportional to its `weight` (1 if neither is set).
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ColumnConfig {
+    pub name: String,
+
+    #[serde(default)]
+    pub width: Option<u16>,
+
+    #[serde(default)]
+    pub weight: Option<u16>,
+}
+
+impl ColumnConfig {
+    fn fixed(name: &str, width: u16) -> ColumnConfig {
+        ColumnConfig {
+            name: String::from(name),
+            width: Some(width),
+            weight: None,
+        }
+    }
+
+    fn weighted(name: &str, weight: u16) -> ColumnConfig {
+        ColumnConfig {
+            name: String::from(name),
+            width: None,
+            weight: Some(weight),
+        }
+    }
+
+    pub fn constraint(&self) -> Constraint {
+        match (self.width, self.weight) {
+            (Some(width), _) => Constraint::Length(width),
+            (None, Some(weight)) => Constraint::Fill(weight),
+            (None, None) => Constraint::Fill(1),
+        }
+    }
+
+    /// Check a list of columns against the columns available for a table.
+    fn validate(table: &str, columns: &[ColumnConfig], available: &[&str]) -> Result<(), String> {
+        if columns.is_empty() {
+            return Err(format!(
+                "columns.{}: at least one column is required",
+                table
+            ));
+        }
+        for (i, column) in columns.iter().enumerate() {
+            if !available.contains(&column.name.as_str()) {
+                return Err(format!(
+                    "columns.{}: unknown column '{}' (expected one of: {})",
+                    table,
+                    column.name,
+                    available.join(", ")
+                ));
+            }
+            if columns[..i].iter().any(|c| c.name == column.name) {
+                return Err(format!(
+                    "columns.{}: column '{}' is defined twice",
+                    table, column.name
+                ));
+            }
+            if column.width.is_some() && column.weight.is_some() {
+                return Err(format!(
+                    "columns.{}: column '{}' cannot have both a width and a weight",
+                    table, column.name
+                ));
+            }
+            if column.width == Some(0) || column.weight == Some(0) {
+                return Err(format!(
+                    "columns.{}: column '{}' must have a non-zero width or weight",
+                    table, column.name
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The column layouts of the tables; a table without any layout uses its default columns.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ColumnsConfig {
+    #[serde(default)]
+    pub history: Option<Vec<ColumnConfig>>,
+
+    #[serde(default)]
+    pub shortcuts: Option<Vec<ColumnConfig>>,
+}
+
+/// How the dates are rendered in each view, see `DateStyle`
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct DatesConfig {
+    #[serde(default)]
+    pub history: DateStyle,
+
+    #[serde(default)]
+    pub details: DateStyle,
+}
+
+/// The user commands run on events, with the parameters of the event in the environment.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct HooksConfig {
+    /// Run when a path is added to the history, with `CDIR_PATH`
+    #[serde(default)]
+    pub on_add_path: Option<String>,
+
+    /// Run when a directory is chosen, with `CDIR_PATH` and `CDIR_SHORTCUT` when chosen by
+    /// shortcut name
+    #[serde(default)]
+    pub on_select: Option<String>,
+
+    /// Run when a shortcut is deleted, with `CDIR_SHORTCUT` and `CDIR_PATH`
+    #[serde(default)]
+    pub on_delete_shortcut: Option<String>,
+}
+
+/// The algorithm matching the paths and shortcuts in a fuzzy search
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum FuzzyAlgorithm {
+    /// The optimal matching of fzf, with its search syntax (`'exact`, `^prefix`, `suffix$`,
+    /// `!negation`)
+    #[default]
+    FzfV2,
+    /// The greedy matching of skim, faster on large histories
+    Skim,
+    /// Each word of the search must appear as is in the path
+    Substring,
+}
+
+/// Whether the searches respect the case of the letters
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum CaseSensitivity {
+    /// The case is ignored
+    #[default]
+    Ignore,
+    /// The case is ignored unless the search contains an uppercase letter
+    Smart,
+    /// The case is respected
+    Respect,
+}
+
+impl CaseSensitivity {
+    /// Whether the search of `text` respects the case
+    pub fn is_case_sensitive(&self, text: &str) -> bool {
+        match self {
+            CaseSensitivity::Ignore => false,
+            CaseSensitivity::Smart => text.chars().any(char::is_uppercase),
+            CaseSensitivity::Respect => true,
+        }
+    }
+
+    /// The next sensitivity of the toggle, cycling through ignore, smart and respect
+    pub fn next(&self) -> CaseSensitivity {
+        match self {
+            CaseSensitivity::Ignore => CaseSensitivity::Smart,
+            CaseSensitivity::Smart => CaseSensitivity::Respect,
+            CaseSensitivity::Respect => CaseSensitivity::Ignore,
+        }
+    }
+}
+
+/// The icons displayed before the paths
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Icons {
+    /// The glyphs of a Nerd Font, which must be the font of the terminal
+    Nerdfont,
+    /// Short ASCII tags
+    Ascii,
+    /// No icon
+    #[default]
+    None,
+}
+
+/// How the paths are rendered in the tables
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PathDisplay {
+    /// The path as is, e.g. `~/work/api`
+    #[default]
+    Plain,
+    /// The segments of the path separated by arrows, e.g. `~ › work › api`
+    Breadcrumb,
+}
+
+/// The variable disabling the colors of the terminal applications, see https://no-color.org
+const NO_COLOR_VAR: &str = "NO_COLOR";
+
+/// The rendering of the GUI for the terminals with limited colors or for visually impaired users
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Accessibility {
+    /// The colors of the theme
+    #[default]
+    None,
+    /// No color, the whole text being bold and the headers and selections in reverse video
+    HighContrast,
+    /// No color, the headers and selections being in reverse video
+    Monochrome,
+}
+
+impl Accessibility {
+    /// The mode applied given the value of the `NO_COLOR` variable: a non empty value
+    /// disables the colors of the themes.
+    pub fn resolve(self, no_color: Option<&str>) -> Accessibility {
+        match (self, no_color) {
+            (Accessibility::None, Some(no_color)) if !no_color.is_empty() => {
+                Accessibility::Monochrome
+            }
+            _ => self,
+        }
+    }
+}
+
+/// How the paths too long for their column are shortened
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PathShortening {
+    /// The start of the path is cut, e.g. `~/*api/src`
+    #[default]
+    Left,
+    /// The middle segments are abbreviated first, e.g. `~/w…/api/src`
+    Middle,
+}
+
+/// The format of the lines of the log file
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// e.g. `2024-01-02 14:05:12.345  INFO cdir_core::store: Adding path /tmp`
+    #[default]
+    Text,
+    /// One JSON object per line, with the fields and the spans of the event
+    Json,
+}
+
+/// The fuzzy search matcher and the bonuses added to its scores, 0 disabling a bonus.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct FuzzyConfig {
+    #[serde(default)]
+    pub algorithm: FuzzyAlgorithm,
+
+    /// Added for each word of the search starting a segment of the path
+    #[serde(default)]
+    pub segment_boundary_bonus: u32,
+
+    /// Added when the search matches the name of a shortcut of the path
+    #[serde(default)]
+    pub shortcut_name_bonus: u32,
+
+    /// Added to the most recent path among the matches, and proportionally to the date of the
+    /// others, the oldest getting nothing
+    #[serde(default = "DEFAULT_FUZZY_RECENCY_BOOST")]
+    pub recency_boost: u32,
+
+    /// Added to the most visited path among the matches, and proportionally to the number of
+    /// visits of the others
+    #[serde(default = "DEFAULT_FUZZY_FREQUENCY_BOOST")]
+    pub frequency_boost: u32,
+}
+
+impl Default for FuzzyConfig {
+    fn default() -> Self {
+        FuzzyConfig {
+            algorithm: FuzzyAlgorithm::default(),
+            segment_boundary_bonus: 0,
+            shortcut_name_bonus: 0,
+            recency_boost: DEFAULT_FUZZY_RECENCY_BOOST(),
+            frequency_boost: DEFAULT_FUZZY_FREQUENCY_BOOST(),
+        }
+    }
+}
+
+/// The journal mode of the SQLite database
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum JournalMode {
+    /// A rollback journal deleted at the end of each transaction, the SQLite default
+    Delete,
+    /// A write-ahead log, the readers not blocking the writer
+    #[default]
+    Wal,
+}
+
+/// How often SQLite waits for the writes to reach the disk
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Synchronous {
+    Off,
+    /// Safe in WAL mode, a power loss possibly losing the last transactions
+    #[default]
+    Normal,
+    Full,
+}
+
+/// The tuning of the connection to the database, applied when it is opened
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct DatabaseConfig {
+    #[serde(default)]
+    pub journal_mode: JournalMode,
+
+    #[serde(default)]
+    pub synchronous: Synchronous,
+
+    /// How long a write waits for the lock of another process, e.g. a shell hook adding a
+    /// path, before failing
+    #[serde(default = "DEFAULT_BUSY_TIMEOUT_MS")]
+    pub busy_timeout_ms: u64,
+}
+
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        DatabaseConfig {
+            journal_mode: JournalMode::default(),
+            synchronous: Synchronous::default(),
+            busy_timeout_ms: DEFAULT_BUSY_TIMEOUT_MS(),
+        }
+    }
+}
+
 /// Application configuration structure.
 /// The configuration can be loaded from a YAML file.
 #[derive(Serialize, Deserialize)]
@@ -69,8 +461,25 @@ pub struct Config {
     #[serde(default = "DEFAULT_DB_PATH")]
     pub db_path: Option<PathBuf>,
 
-    #[serde(default = "DEFAULT_LOG_CONFIG_PATH")]
-    pub log_config_path: Option<PathBuf>,
+    /// The file the logs are written to, rolled when it grows too large, nothing being logged
+    /// to a file when None
+    #[serde(default = "DEFAULT_LOG_FILE")]
+    pub log_file: Option<PathBuf>,
+
+    /// The levels of the log file, e.g. `info,cdir_core::store=debug`, replaced by the
+    /// `CDIR_LOG` environment variable
+    #[serde(default = "DEFAULT_LOG_LEVEL")]
+    pub log_level: String,
+
+    #[serde(default)]
+    pub log_format: LogFormat,
+
+    /// Mirror the warnings and the errors of the commands to the standard error
+    #[serde(default = "DEFAULT_TRUE")]
+    pub log_to_stderr: bool,
+
+    #[serde(default)]
+    pub database: DatabaseConfig,
 
     #[serde(default = "DEFAULT_TRUE")]
     pub path_search_include_shortcuts: bool,
@@ -84,21 +493,65 @@ pub struct Config {
     #[serde(default = "PATH_VIEW_DESCRIPTION_COLUMN_WEIGHT")]
     pub path_view_description_column_weight: usize,
 
+    /// Group the rows of the history under day section headers, see `date_formatter::day_section`
+    #[serde(default = "DEFAULT_FALSE")]
+    pub path_view_day_sections: bool,
+
+    /// Show under the rows of the history the last command run in each directory, see `add-cmd`
+    #[serde(default = "DEFAULT_TRUE")]
+    pub path_view_last_commands: bool,
+
     #[serde(default = "DEFAULT_TRUE")]
     pub smart_suggestions_active: bool,
 
+    /// Show the smart suggestions in a panel on top of the history view instead of inside it
+    #[serde(default = "DEFAULT_FALSE")]
+    pub smart_suggestions_panel: bool,
+
+    /// The percentage of the history view the suggestions panel takes at most, adjusted with
+    /// ctrl+up and ctrl+down
+    #[serde(default = "DEFAULT_SUGGESTIONS_PANEL_RATIO")]
+    pub suggestions_panel_ratio: u16,
+
+    #[serde(default)]
+    pub smart_suggestions_strategy: SmartSuggestionsStrategy,
+
     #[serde(default = "DEFAULT_SMART_SUGGESTIONS_DEPTH")]
     pub smart_suggestions_depth: usize,
 
     #[serde(default = "DEFAULT_SMART_SUGGESTIONS_COUNT")]
     pub smart_suggestions_count: usize,
 
+    /// The number of days after which a visit counts half in the smart suggestions, 0 for no
+    /// decay (`window` strategy)
+    #[serde(default = "DEFAULT_SMART_SUGGESTIONS_HALF_LIFE_DAYS")]
+    pub smart_suggestions_half_life_days: u32,
+
+    /// The weight of the closeness of a path to the visits of the current directory in the
+    /// smart suggestions (`window` strategy)
+    #[serde(default = "DEFAULT_SMART_SUGGESTIONS_ADJACENCY_WEIGHT")]
+    pub smart_suggestions_adjacency_weight: u32,
+
+    /// The weight of the recency of the visits of the current directory a path followed
+    /// (`window` strategy)
+    #[serde(default = "DEFAULT_SMART_SUGGESTIONS_RECENCY_WEIGHT")]
+    pub smart_suggestions_recency_weight: u32,
+
+    /// The weight of the number of visits of the current directory a path followed
+    /// (`window` strategy)
+    #[serde(default = "DEFAULT_SMART_SUGGESTIONS_FREQUENCY_WEIGHT")]
+    pub smart_suggestions_frequency_weight: u32,
+
     #[serde(default = "DEFAULT_THEMES_DIRECTORY_PATH")]
     pub themes_directory_path: Option<PathBuf>,
 
     #[serde(default = "DEFAULT_DATE_FORMAT")]
     pub date_format: String,
 
+    /// The rendering of the dates of each view, `date_format` being used by the `format` style
+    #[serde(default)]
+    pub dates: DatesConfig,
+
     #[serde(default = "DEFAULT_THEME")]
     pub theme: Option<String>,
 
@@ -117,12 +570,141 @@ pub struct Config {
     #[serde(default = "DEFAULT_COLORS")]
     pub inline_theme_light: Theme,
 
+    #[serde(default = "DEFAULT_FALSE")]
+    pub reduced_motion: bool,
+
+    /// Render the GUI without colors, see `Accessibility::resolve`
+    #[serde(default)]
+    pub accessibility: Accessibility,
+
+    /// Render the tables as plain lines for the screen readers, see `TableView::render_lines`
+    #[serde(default = "DEFAULT_FALSE")]
+    pub screen_reader: bool,
+
+    /// Ring the bell of the terminal when the selection moves in the screen reader mode
+    #[serde(default = "DEFAULT_FALSE")]
+    pub screen_reader_bell: bool,
+
+    /// The minimal time between two frames of the GUI in milliseconds, the changes made
+    /// meanwhile (e.g. while typing a search) being drawn at once; 0 draws after each event
+    #[serde(default = "DEFAULT_FRAME_BUDGET_MS")]
+    pub frame_budget_ms: u64,
+
+    /// Show the search mode, the filters, the number of results and the main keys on the last row
+    /// of the GUI
+    #[serde(default = "DEFAULT_TRUE")]
+    pub status_bar: bool,
+
+    /// Give ctrl+a, ctrl+e and ctrl+u to the search, as in readline, instead of the actions of
+    /// the tables
+    #[serde(default = "DEFAULT_FALSE")]
+    pub readline_keys: bool,
+
+    /// Show the File, Edit, View and Help menus on the first row of the GUI
+    #[serde(default = "DEFAULT_FALSE")]
+    pub menu_bar: bool,
+
+    /// Show a third view listing the files opened recently, recorded by `cdir add-file`
+    #[serde(default = "DEFAULT_FALSE")]
+    pub files_view: bool,
+
+    /// Return the only match of the GUI initial query without displaying the GUI
+    #[serde(default = "DEFAULT_FALSE")]
+    pub auto_select_single_result: bool,
+
+    /// Reopen the GUI with the search, view and selection it was left with
+    #[serde(default = "DEFAULT_FALSE")]
+    pub restore_session: bool,
+
+    /// Only list the paths visited from the current host (and the ones of an unknown host)
+    #[serde(default = "DEFAULT_FALSE")]
+    pub current_host_only: bool,
+
+    /// List and suggest the paths of the host and of all the containers together
+    #[serde(default = "DEFAULT_FALSE")]
+    pub mix_container_history: bool,
+
+    /// Record the paths in their canonical form, see `Store::add_path_from_pane`
+    #[serde(default = "DEFAULT_FALSE")]
+    pub canonicalize_paths: bool,
+
+    /// Create a shortcut for the root of the git repository of a recorded path, see
+    /// `Store::add_git_root_shortcut`
+    #[serde(default = "DEFAULT_FALSE")]
+    pub auto_shortcut_git_roots: bool,
+
+    /// The duration after which the size of a directory is computed again, in seconds
+    #[serde(default = "DEFAULT_DIR_SIZE_TTL")]
+    pub dir_size_ttl: u64,
+
+    /// The command line opening a shell into a remote `[user@]host:/path` entry
+    #[serde(default = "DEFAULT_REMOTE_COMMAND_TEMPLATE")]
+    pub remote_command_template: String,
+
+    /// Ask before applying the environment hints of a shortcut after jumping into it
+    #[serde(default = "DEFAULT_FALSE")]
+    pub confirm_shortcut_hints: bool,
+
+    /// The duration after which the path of a shortcut is checked again, in seconds
+    #[serde(default = "DEFAULT_SHORTCUT_CHECK_INTERVAL")]
+    pub shortcut_check_interval: u64,
+
+    /// The number of days the deleted paths stay in the trash before being purged
+    #[serde(default = "DEFAULT_TRASH_RETENTION_DAYS")]
+    pub trash_retention_days: u64,
+
+    /// The gap between two visits, in minutes, after which a new session of the history starts
+    #[serde(default = "DEFAULT_SESSION_IDLE_MINUTES")]
+    pub session_idle_minutes: u64,
+
+    /// The number of visits from which a path without shortcut is suggested as a shortcut
+    #[serde(default = "DEFAULT_SHORTCUT_SUGGESTIONS_MIN_VISITS")]
+    pub shortcut_suggestions_min_visits: usize,
+
+    /// The number of components from which a path is deep enough to be suggested as a shortcut
+    #[serde(default = "DEFAULT_SHORTCUT_SUGGESTIONS_MIN_DEPTH")]
+    pub shortcut_suggestions_min_depth: usize,
+
+    #[serde(default)]
+    pub columns: ColumnsConfig,
+
+    /// The "open with" commands, run on the selected path with alt+key
+    #[serde(default)]
+    pub commands: BTreeMap<String, String>,
+
+    /// The public keys, in base64, of which one must sign the bundles installed, see
+    /// `bundle::verify`; the bundles are installed without a signature when empty
+    #[serde(default)]
+    pub bundle_public_keys: Vec<String>,
+
+    #[serde(default)]
+    pub hooks: HooksConfig,
+
+    #[serde(default)]
+    pub fuzzy: FuzzyConfig,
+
+    /// The case sensitivity the searches start with, toggled in the GUI
+    #[serde(default)]
+    pub case_sensitivity: CaseSensitivity,
+
+    /// The icons displayed before the paths, see `icons::resolve`
+    #[serde(default)]
+    pub icons: Icons,
+
+    /// How the paths are rendered, see `Gui::breadcrumb`
+    #[serde(default)]
+    pub path_display: PathDisplay,
+
+    /// How the paths too long are shortened, see `Gui::abbreviate_path`
+    #[serde(default)]
+    pub path_shortening: PathShortening,
+
     #[serde(skip_serializing)]
     #[serde(skip_deserializing)]
     pub styles: ThemeStyles,
 
-    #[serde(skip, default = "DEFAULT_DATE_FORMATER")]
-    pub date_formater: Arc<dyn Fn(i64) -> String + Send + Sync>,
+    #[serde(skip, default = "DEFAULT_DATE_FORMATTERS")]
+    pub date_formatters: DateFormatters,
 }
 
 impl Config {
@@ -160,26 +742,98 @@ impl Config {
     pub fn load(path: PathBuf) -> Result<Config, String> {
         let file = std::fs::File::open(path.clone());
 
-        match serde_yaml::from_reader(file.unwrap()) {
-            Ok(c) => Ok(c),
-            Err(e) => Err(format!("Failed to parse config file {:?}: {}", path, e)),
+        let config: Config = match serde_yaml::from_reader(file.unwrap()) {
+            Ok(c) => c,
+            Err(e) => return Err(format!("Failed to parse config file {:?}: {}", path, e)),
+        };
+        config
+            .validate()
+            .map_err(|e| format!("Invalid config file {:?}: {}", path, e))?;
+        Ok(config)
+    }
+
+    /// Check the consistency of the values which cannot be enforced by the deserialization.
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(columns) = &self.columns.history {
+            ColumnConfig::validate("history", columns, &HISTORY_COLUMNS)?;
+        }
+        if let Some(columns) = &self.columns.shortcuts {
+            ColumnConfig::validate("shortcuts", columns, &SHORTCUTS_COLUMNS)?;
+        }
+        for key in self.commands.keys() {
+            let mut chars = key.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) if !RESERVED_COMMAND_KEYS.contains(&c) => {}
+                (Some(c), None) => {
+                    return Err(format!("commands: the key '{}' is reserved", c));
+                }
+                _ => {
+                    return Err(format!(
+                        "commands: the key '{}' must be a single character",
+                        key
+                    ));
+                }
+            }
         }
+        for key in &self.bundle_public_keys {
+            bundle::validate_public_key(key).map_err(|e| format!("bundle_public_keys: {}", e))?;
+        }
+        remote::validate_template(&self.remote_command_template)?;
+        Ok(())
+    }
+
+    /// The columns of the history table: the configured ones if any, else the date, path and
+    /// description columns laid out from the `path_view_*` parameters.
+    /// The description column is left out when `path_view_show_shortcut_description_column` is off.
+    pub fn history_columns(&self) -> Vec<ColumnConfig> {
+        let columns = self.columns.history.clone().unwrap_or_else(|| {
+            vec![
+                ColumnConfig::fixed("date", DEFAULT_FIXED_COLUMN_WIDTH),
+                ColumnConfig::weighted("path", self.path_view_path_column_weight as u16),
+                ColumnConfig::weighted(
+                    "description",
+                    self.path_view_description_column_weight as u16,
+                ),
+            ]
+        });
+        columns
+            .into_iter()
+            .filter(|c| self.path_view_show_shortcut_description_column || c.name != "description")
+            .collect()
+    }
+
+    /// The columns of the shortcuts table: the configured ones if any, else the default ones.
+    pub fn shortcuts_columns(&self) -> Vec<ColumnConfig> {
+        self.columns.shortcuts.clone().unwrap_or_else(|| {
+            vec![
+                ColumnConfig::fixed("shortcut", DEFAULT_FIXED_COLUMN_WIDTH),
+                ColumnConfig::weighted("path", 1),
+                ColumnConfig::weighted("description", 1),
+            ]
+        })
+    }
+
+    /// The columns of the recent files table
+    pub fn files_columns(&self) -> Vec<ColumnConfig> {
+        vec![
+            ColumnConfig::fixed("date", DEFAULT_FIXED_COLUMN_WIDTH),
+            ColumnConfig::weighted("file", 1),
+        ]
     }
 
     pub fn process(self: &mut Config) -> &Config {
         let actual_theme = Self::process_themes(self);
 
-        // compute the styles fom the current inline_theme
-        self.styles = ThemeStyles::from(&actual_theme);
+        // compute the styles fom the current inline_theme, unless an accessible mode bypasses it
+        let no_color = env::var(NO_COLOR_VAR).ok();
+        self.styles = match self.accessibility.resolve(no_color.as_deref()) {
+            Accessibility::None => ThemeStyles::from(&actual_theme),
+            Accessibility::HighContrast => ThemeStyles::accessible(true),
+            Accessibility::Monochrome => ThemeStyles::accessible(false),
+        };
 
-        let date_format = self.date_format.clone();
-        self.date_formater = Arc::from(move |s: i64| {
-            DateTime::from_timestamp(s, 0)
-                .unwrap()
-                .with_timezone(&Local::now().timezone())
-                .format(date_format.as_str())
-                .to_string()
-        });
+        self.date_formatters =
+            DateFormatters::new(self.dates.history, self.dates.details, &self.date_format);
 
         self
     }
@@ -189,7 +843,7 @@ impl Config {
         let mut external_theme = Theme::default();
 
         // Process the dark/light config
-        if config.theme_dark.is_some() && config.theme_light.is_some() {
+        if let (Some(theme_dark), Some(theme_light)) = (&config.theme_dark, &config.theme_light) {
             let dl = match dark_light::detect() {
                 Ok(dl) => dl,
                 Err(err) => {
@@ -200,14 +854,14 @@ impl Config {
             match dl {
                 dark_light::Mode::Dark => {
                     trace!("dark mode detected");
-                    if let Some(theme) = config.load_theme(config.theme_dark.as_ref().unwrap()) {
+                    if let Some(theme) = config.load_theme(theme_dark) {
                         external_theme = theme.merge(&external_theme);
                     }
                     actual_theme = config.inline_theme_dark.merge(&external_theme);
                 }
                 dark_light::Mode::Light => {
                     trace!("light mode detected");
-                    if let Some(theme) = config.load_theme(config.theme_light.as_ref().unwrap()) {
+                    if let Some(theme) = config.load_theme(theme_light) {
                         external_theme = theme.merge(&external_theme);
                     }
                     actual_theme = config.inline_theme_light.merge(&external_theme);
@@ -269,23 +923,6 @@ impl Config {
         fs::write(&config_file_path, config)
             .unwrap_or_else(|_| panic!("Failed to write config file {:?}", config_file_path));
 
-        // de-templatize and write the log4rs file if it doesn't exist
-        let log4rs_config_path = config_dir.join("log4rs.yaml");
-        if !log4rs_config_path.exists() {
-            let log4rs_template = include_str!("../templates/log4rs.yaml");
-            let log4rs_config = log4rs_template
-                .replace("__CONFIG_PATH__", config_dir.to_str().unwrap())
-                .replace("__DATA_PATH__", data_dir.to_str().unwrap());
-
-            println!("→ Creating the log4rs config file {:?}", log4rs_config_path);
-            fs::write(&log4rs_config_path, log4rs_config).unwrap_or_else(|_| {
-                panic!(
-                    "Failed to write log4rs config file {:?}",
-                    log4rs_config_path
-                )
-            });
-        }
-
         // create the .cdirsh file in the home directory
         let cdirsh_path = dirs::home_dir().unwrap().join(".cdirsh");
         let mut cdirsh_content =
@@ -421,7 +1058,7 @@ impl Config {
     }
 
     // Save the configuration by applying a patch to the existing config file, to preserve comments and formatting as much as possible
-    pub(crate) fn save(&self) -> Result<(), String> {
+    pub fn save(&self) -> Result<(), String> {
         info!("Saving configuration to {:?}", CONFIG_FILE_PATH.get());
 
         let config_path = CONFIG_FILE_PATH
@@ -481,6 +1118,42 @@ impl Config {
             );
         }
 
+        for (key, from_file, value) in [
+            (
+                "smart_suggestions_half_life_days",
+                config_from_file.smart_suggestions_half_life_days,
+                self.smart_suggestions_half_life_days,
+            ),
+            (
+                "smart_suggestions_adjacency_weight",
+                config_from_file.smart_suggestions_adjacency_weight,
+                self.smart_suggestions_adjacency_weight,
+            ),
+            (
+                "smart_suggestions_recency_weight",
+                config_from_file.smart_suggestions_recency_weight,
+                self.smart_suggestions_recency_weight,
+            ),
+            (
+                "smart_suggestions_frequency_weight",
+                config_from_file.smart_suggestions_frequency_weight,
+                self.smart_suggestions_frequency_weight,
+            ),
+        ] {
+            if from_file != value {
+                add_or_replace(key, Value::Number(serde_yaml::Number::from(value as u64)));
+            }
+        }
+
+        if config_from_file.suggestions_panel_ratio != self.suggestions_panel_ratio {
+            add_or_replace(
+                "suggestions_panel_ratio",
+                Value::Number(serde_yaml::Number::from(
+                    self.suggestions_panel_ratio as u64,
+                )),
+            );
+        }
+
         if config_from_file.path_search_include_shortcuts != self.path_search_include_shortcuts {
             add_or_replace(
                 "path_search_include_shortcuts",
@@ -525,15 +1198,61 @@ impl Default for Config {
             path_view_show_shortcut_description_column: true,
             path_view_path_column_weight: PATH_VIEW_PATH_COLUMN_WEIGHT(),
             path_view_description_column_weight: PATH_VIEW_DESCRIPTION_COLUMN_WEIGHT(),
+            path_view_day_sections: false,
+            path_view_last_commands: true,
             smart_suggestions_active: true,
+            smart_suggestions_panel: false,
+            suggestions_panel_ratio: DEFAULT_SUGGESTIONS_PANEL_RATIO(),
+            smart_suggestions_strategy: SmartSuggestionsStrategy::Window,
             smart_suggestions_depth: DEFAULT_SMART_SUGGESTIONS_DEPTH(),
             smart_suggestions_count: DEFAULT_SMART_SUGGESTIONS_COUNT(),
+            smart_suggestions_half_life_days: DEFAULT_SMART_SUGGESTIONS_HALF_LIFE_DAYS(),
+            smart_suggestions_adjacency_weight: DEFAULT_SMART_SUGGESTIONS_ADJACENCY_WEIGHT(),
+            smart_suggestions_recency_weight: DEFAULT_SMART_SUGGESTIONS_RECENCY_WEIGHT(),
+            smart_suggestions_frequency_weight: DEFAULT_SMART_SUGGESTIONS_FREQUENCY_WEIGHT(),
             themes_directory_path: Default::default(),
-            date_formater: Arc::new(|date| date.to_string()),
+            date_formatters: DEFAULT_DATE_FORMATTERS(),
             db_path: Default::default(),
-            log_config_path: Default::default(),
+            log_file: Default::default(),
+            log_level: DEFAULT_LOG_LEVEL(),
+            log_format: Default::default(),
+            log_to_stderr: true,
+            database: Default::default(),
             path_search_include_shortcuts: true,
             date_format: Default::default(),
+            dates: Default::default(),
+            reduced_motion: false,
+            accessibility: Default::default(),
+            screen_reader: false,
+            screen_reader_bell: false,
+            frame_budget_ms: DEFAULT_FRAME_BUDGET_MS(),
+            status_bar: true,
+            readline_keys: false,
+            menu_bar: false,
+            files_view: false,
+            auto_select_single_result: false,
+            restore_session: false,
+            current_host_only: false,
+            mix_container_history: false,
+            canonicalize_paths: false,
+            auto_shortcut_git_roots: false,
+            dir_size_ttl: DEFAULT_DIR_SIZE_TTL(),
+            remote_command_template: DEFAULT_REMOTE_COMMAND_TEMPLATE(),
+            confirm_shortcut_hints: false,
+            shortcut_check_interval: DEFAULT_SHORTCUT_CHECK_INTERVAL(),
+            trash_retention_days: DEFAULT_TRASH_RETENTION_DAYS(),
+            session_idle_minutes: DEFAULT_SESSION_IDLE_MINUTES(),
+            shortcut_suggestions_min_visits: DEFAULT_SHORTCUT_SUGGESTIONS_MIN_VISITS(),
+            shortcut_suggestions_min_depth: DEFAULT_SHORTCUT_SUGGESTIONS_MIN_DEPTH(),
+            columns: Default::default(),
+            commands: Default::default(),
+            bundle_public_keys: Default::default(),
+            hooks: Default::default(),
+            fuzzy: Default::default(),
+            case_sensitivity: Default::default(),
+            icons: Default::default(),
+            path_display: Default::default(),
+            path_shortening: Default::default(),
         }
     }
 }
@@ -553,16 +1272,61 @@ impl Clone for Config {
                 .path_view_show_shortcut_description_column,
             path_view_path_column_weight: self.path_view_path_column_weight,
             path_view_description_column_weight: self.path_view_description_column_weight,
+            path_view_day_sections: self.path_view_day_sections,
+            path_view_last_commands: self.path_view_last_commands,
             smart_suggestions_active: self.smart_suggestions_active,
+            smart_suggestions_panel: self.smart_suggestions_panel,
+            suggestions_panel_ratio: self.suggestions_panel_ratio,
+            smart_suggestions_strategy: self.smart_suggestions_strategy,
             smart_suggestions_depth: self.smart_suggestions_depth,
             smart_suggestions_count: self.smart_suggestions_count,
+            smart_suggestions_half_life_days: self.smart_suggestions_half_life_days,
+            smart_suggestions_adjacency_weight: self.smart_suggestions_adjacency_weight,
+            smart_suggestions_recency_weight: self.smart_suggestions_recency_weight,
+            smart_suggestions_frequency_weight: self.smart_suggestions_frequency_weight,
             themes_directory_path: self.themes_directory_path.clone(),
             db_path: self.db_path.clone(),
-            log_config_path: self.log_config_path.clone(),
+            log_file: self.log_file.clone(),
+            log_level: self.log_level.clone(),
+            log_format: self.log_format,
+            log_to_stderr: self.log_to_stderr,
+            database: self.database.clone(),
             path_search_include_shortcuts: self.path_search_include_shortcuts,
             date_format: self.date_format.clone(),
-            // Provide a new default closure for date_formater
-            date_formater: Arc::new(|date| date.to_string()),
+            dates: self.dates.clone(),
+            reduced_motion: self.reduced_motion,
+            accessibility: self.accessibility,
+            screen_reader: self.screen_reader,
+            screen_reader_bell: self.screen_reader_bell,
+            frame_budget_ms: self.frame_budget_ms,
+            status_bar: self.status_bar,
+            readline_keys: self.readline_keys,
+            menu_bar: self.menu_bar,
+            files_view: self.files_view,
+            auto_select_single_result: self.auto_select_single_result,
+            restore_session: self.restore_session,
+            current_host_only: self.current_host_only,
+            mix_container_history: self.mix_container_history,
+            canonicalize_paths: self.canonicalize_paths,
+            auto_shortcut_git_roots: self.auto_shortcut_git_roots,
+            dir_size_ttl: self.dir_size_ttl,
+            remote_command_template: self.remote_command_template.clone(),
+            confirm_shortcut_hints: self.confirm_shortcut_hints,
+            shortcut_check_interval: self.shortcut_check_interval,
+            trash_retention_days: self.trash_retention_days,
+            session_idle_minutes: self.session_idle_minutes,
+            shortcut_suggestions_min_visits: self.shortcut_suggestions_min_visits,
+            shortcut_suggestions_min_depth: self.shortcut_suggestions_min_depth,
+            columns: self.columns.clone(),
+            commands: self.commands.clone(),
+            bundle_public_keys: self.bundle_public_keys.clone(),
+            hooks: self.hooks.clone(),
+            fuzzy: self.fuzzy.clone(),
+            case_sensitivity: self.case_sensitivity,
+            icons: self.icons,
+            path_display: self.path_display,
+            path_shortening: self.path_shortening,
+            date_formatters: self.date_formatters.clone(),
         }
     }
 }
@@ -37,11 +37,7 @@ impl View for ConfigButton {
         }
 
         let pa = Paragraph::new("F12: Config")
-            .style(
-                Style::default()
-                    .bg(config_lock.styles.header_bg_color.unwrap())
-                    .fg(config_lock.styles.header_fg_color.unwrap()),
-            )
+            .style(config_lock.styles.header_style())
             .alignment(Alignment::Center);
         frame.render_widget(pa, area);
     }
@@ -4,13 +4,13 @@ use std::{
 };
 
 use crossterm::event::{KeyCode, KeyEvent};
-use log::{error, info};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     widgets::{Block, Borders, Clear, Paragraph},
 };
 use tokio::sync::broadcast::Sender;
+use tracing::{error, info};
 use tui_textarea::{Input, TextArea};
 
 use crate::{
@@ -21,12 +21,23 @@ use crate::{
     },
 };
 
+/// The labels of the scoring parameters of the smart suggestions: the half-life in days, then
+/// the adjacency, recency and frequency weights
+const SCORING_LABELS: [&str; 4] = [
+    "Smart suggestions half-life (days)",
+    "Smart suggestions adjacency weight",
+    "Smart suggestions recency weight",
+    "Smart suggestions frequency weight",
+];
+
 #[derive(Copy, Clone, PartialEq)]
 enum ConfigField {
     SmartSuggestionActiveCheckbox,
     PathSearchCheckbox,
     PathViewDescriptionCheckbox,
     SmartSuggestionCountField,
+    /// The nth scoring parameter of the smart suggestions, see `SCORING_LABELS`
+    SmartSuggestionScoringField(usize),
     YesButton,
     CancelButton,
 }
@@ -39,6 +50,7 @@ pub struct ConfigView {
     path_search_include_shortcuts: bool,
     path_view_show_description_column: bool,
     count_textarea: Option<TextArea<'static>>,
+    scoring_textareas: Vec<TextArea<'static>>,
 }
 
 impl ConfigView {
@@ -51,6 +63,7 @@ impl ConfigView {
             path_search_include_shortcuts: false,
             path_view_show_description_column: false,
             count_textarea: None,
+            scoring_textareas: vec![],
         }))
     }
 
@@ -77,6 +90,30 @@ impl ConfigView {
                     error!("Invalid count value '{}', keeping current value", count_str);
                 }
             }
+            // Save the scoring parameters from their TextAreas
+            let mut scoring = [
+                config.smart_suggestions_half_life_days,
+                config.smart_suggestions_adjacency_weight,
+                config.smart_suggestions_recency_weight,
+                config.smart_suggestions_frequency_weight,
+            ];
+            for (value, textarea) in scoring.iter_mut().zip(&self.scoring_textareas) {
+                let value_str = textarea.lines().first().map(String::as_str).unwrap_or("");
+                match value_str.parse::<u32>() {
+                    Ok(parsed) => *value = parsed,
+                    Err(_) => error!(
+                        "Invalid scoring value '{}', keeping current value",
+                        value_str
+                    ),
+                }
+            }
+            info!("Saving smart suggestions scoring={:?}", scoring);
+            [
+                config.smart_suggestions_half_life_days,
+                config.smart_suggestions_adjacency_weight,
+                config.smart_suggestions_recency_weight,
+                config.smart_suggestions_frequency_weight,
+            ] = scoring;
             if let Err(e) = config.save() {
                 error!("Failed to save config: {}", e);
             }
@@ -122,6 +159,22 @@ impl View for ConfigView {
             count_textarea.set_cursor_line_style(config_lock.styles.text_style);
             count_textarea.insert_str(config_lock.smart_suggestions_count.to_string());
             self.count_textarea = Some(count_textarea);
+
+            // Initialize the scoring textareas, in the order of SCORING_LABELS
+            self.scoring_textareas = [
+                config_lock.smart_suggestions_half_life_days,
+                config_lock.smart_suggestions_adjacency_weight,
+                config_lock.smart_suggestions_recency_weight,
+                config_lock.smart_suggestions_frequency_weight,
+            ]
+            .iter()
+            .map(|value| {
+                let mut textarea = TextArea::default();
+                textarea.set_cursor_line_style(config_lock.styles.text_style);
+                textarea.insert_str(value.to_string());
+                textarea
+            })
+            .collect();
         } else {
             error!("Failed to lock config to get initial state, using defaults");
             self.smart_suggestions_active = false;
@@ -153,6 +206,14 @@ impl View for ConfigView {
                         ConfigField::SmartSuggestionCountField
                     }
                     ConfigField::SmartSuggestionCountField => {
+                        ConfigField::SmartSuggestionScoringField(0)
+                    }
+                    ConfigField::SmartSuggestionScoringField(index)
+                        if index + 1 < SCORING_LABELS.len() =>
+                    {
+                        ConfigField::SmartSuggestionScoringField(index + 1)
+                    }
+                    ConfigField::SmartSuggestionScoringField(_) => {
                         ConfigField::PathViewDescriptionCheckbox
                     }
                     ConfigField::PathViewDescriptionCheckbox => ConfigField::PathSearchCheckbox,
@@ -168,9 +229,15 @@ impl View for ConfigView {
                     ConfigField::SmartSuggestionCountField => {
                         ConfigField::SmartSuggestionActiveCheckbox
                     }
-                    ConfigField::PathViewDescriptionCheckbox => {
+                    ConfigField::SmartSuggestionScoringField(0) => {
                         ConfigField::SmartSuggestionCountField
                     }
+                    ConfigField::SmartSuggestionScoringField(index) => {
+                        ConfigField::SmartSuggestionScoringField(index - 1)
+                    }
+                    ConfigField::PathViewDescriptionCheckbox => {
+                        ConfigField::SmartSuggestionScoringField(SCORING_LABELS.len() - 1)
+                    }
                     ConfigField::PathSearchCheckbox => ConfigField::PathViewDescriptionCheckbox,
                     ConfigField::YesButton => ConfigField::PathSearchCheckbox,
                     ConfigField::CancelButton => ConfigField::YesButton,
@@ -223,6 +290,10 @@ impl View for ConfigView {
                         // Move to next field (Yes button) when Enter is pressed on TextArea
                         self.smart_suggestions_field = ConfigField::YesButton;
                     }
+                    ConfigField::SmartSuggestionScoringField(_) => {
+                        // Move to next field (Yes button) when Enter is pressed on TextArea
+                        self.smart_suggestions_field = ConfigField::YesButton;
+                    }
                     ConfigField::YesButton => {
                         // Save and close
                         self.save_config();
@@ -236,19 +307,25 @@ impl View for ConfigView {
                 }
             }
             _ => {
-                // Handle input for the count textarea - only allow numeric input and navigation
-                if self.smart_suggestions_field == ConfigField::SmartSuggestionCountField
-                    && let Some(textarea) = self.count_textarea.as_mut()
-                {
+                // Handle input for the count and scoring textareas - only allow numeric input
+                // and navigation, up to 2 characters for the count and 3 for the scoring
+                let (textarea, max_len) = match self.smart_suggestions_field {
+                    ConfigField::SmartSuggestionCountField => (self.count_textarea.as_mut(), 2),
+                    ConfigField::SmartSuggestionScoringField(index) => {
+                        (self.scoring_textareas.get_mut(index), 3)
+                    }
+                    _ => (None, 0),
+                };
+                if let Some(textarea) = textarea {
                     match key_event.code {
-                        // Allow numeric keys (but limit to 3 characters)
+                        // Allow numeric keys (but limit the length)
                         KeyCode::Char(c) if c.is_ascii_digit() => {
                             let current_text = if textarea.lines().is_empty() {
                                 String::new()
                             } else {
                                 textarea.lines()[0].clone()
                             };
-                            if current_text.len() < 2 {
+                            if current_text.len() < max_len {
                                 textarea.input(Input::from(key_event));
                             }
                         }
@@ -284,7 +361,7 @@ impl View for ConfigView {
     fn draw(&mut self, frame: &mut ratatui::Frame, modal_area: Rect, _active: bool) {
         let layout = Layout::vertical([
             Constraint::Fill(1),
-            Constraint::Length(10),
+            Constraint::Length(14),
             Constraint::Fill(1),
         ]);
         let chunks: [Rect; 3] = layout.areas(modal_area);
@@ -332,23 +409,25 @@ impl View for ConfigView {
         let checkbox_text = format!("{}  Smart suggestions", checkbox_symbol);
 
         let content_layout = Layout::vertical([
-            Constraint::Length(1), // Empty line at top
-            Constraint::Length(1), // Smart suggestions active checkbox
-            Constraint::Length(1), // Smart suggestions count field
-            Constraint::Length(1), // Path view description checkbox
-            Constraint::Length(1), // Path search checkbox
-            Constraint::Length(1), // Empty line before buttons
-            Constraint::Length(1), // Buttons
+            Constraint::Length(1),                           // Empty line at top
+            Constraint::Length(1),                           // Smart suggestions active checkbox
+            Constraint::Length(1),                           // Smart suggestions count field
+            Constraint::Length(SCORING_LABELS.len() as u16), // Smart suggestions scoring fields
+            Constraint::Length(1),                           // Path view description checkbox
+            Constraint::Length(1),                           // Path search checkbox
+            Constraint::Length(1),                           // Empty line before buttons
+            Constraint::Length(1),                           // Buttons
         ]);
         let [
             _top_spacer,
             suggestions_active_area,
             suggestions_count_area,
+            suggestions_scoring_area,
             path_view_desc_area,
             path_search_area,
             _spacer,
             buttons_area,
-        ]: [Rect; 7] = content_layout.areas(inner_area);
+        ]: [Rect; 8] = content_layout.areas(inner_area);
 
         // Render checkbox with highlighting if selected
         let checkbox_style =
@@ -389,6 +468,31 @@ impl View for ConfigView {
             frame.render_widget(count_paragraph, suggestions_count_area);
         }
 
+        // Render the scoring fields as formatted text [XXX] message
+        let scoring_areas = Layout::vertical(vec![Constraint::Length(1); SCORING_LABELS.len()])
+            .split(suggestions_scoring_area);
+        for (index, (textarea, label)) in self
+            .scoring_textareas
+            .iter()
+            .zip(SCORING_LABELS)
+            .enumerate()
+        {
+            let value = textarea.lines().first().cloned().unwrap_or_default();
+            let scoring_style = if self.smart_suggestions_field
+                == ConfigField::SmartSuggestionScoringField(index)
+            {
+                config_lock
+                    .styles
+                    .text_style
+                    .add_modifier(Modifier::REVERSED)
+            } else {
+                config_lock.styles.text_style
+            };
+            let scoring_paragraph =
+                Paragraph::new(format!("[{:>3}] {}", value, label)).style(scoring_style);
+            frame.render_widget(scoring_paragraph, scoring_areas[index]);
+        }
+
         // Render path search checkbox
         let path_checkbox_symbol = if self.path_search_include_shortcuts {
             "[X]"
@@ -442,22 +546,14 @@ impl View for ConfigView {
             ])
             .areas(buttons_area);
 
-        let yes_style = if self.smart_suggestions_field == ConfigField::YesButton {
-            Style::default()
-                .fg(Color::Black)
-                .bg(Color::Green)
-                .add_modifier(Modifier::BOLD)
-        } else {
-            Style::default().fg(Color::Green)
-        };
-        let cancel_style = if self.smart_suggestions_field == ConfigField::CancelButton {
-            Style::default()
-                .fg(Color::Black)
-                .bg(Color::Red)
-                .add_modifier(Modifier::BOLD)
-        } else {
-            Style::default().fg(Color::Red)
-        };
+        let yes_style = config_lock.styles.button_style(
+            Color::Green,
+            self.smart_suggestions_field == ConfigField::YesButton,
+        );
+        let cancel_style = config_lock.styles.button_style(
+            Color::Red,
+            self.smart_suggestions_field == ConfigField::CancelButton,
+        );
         let yes = Paragraph::new(" Yes ")
             .style(yes_style)
             .alignment(ratatui::layout::Alignment::Center);
@@ -1,10 +1,10 @@
 use crossterm::event::{KeyCode, KeyEvent};
-use log::debug;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Color, Style},
     widgets::{Block, Borders, Clear, Paragraph},
 };
+use tracing::debug;
 
 use crate::{
     theme::ThemeStyles,
@@ -106,22 +106,12 @@ impl View for Confirmation {
                 Constraint::Length(10),
             ])
             .areas(vchunks[2]);
-        let yes_style = if self.selected == ConfirmationButton::Yes {
-            Style::default()
-                .fg(Color::Black)
-                .bg(Color::Green)
-                .add_modifier(Modifier::BOLD)
-        } else {
-            Style::default().fg(Color::Green)
-        };
-        let cancel_style = if self.selected == ConfirmationButton::Cancel {
-            Style::default()
-                .fg(Color::Black)
-                .bg(Color::Red)
-                .add_modifier(Modifier::BOLD)
-        } else {
-            Style::default().fg(Color::Red)
-        };
+        let yes_style = self
+            .styles
+            .button_style(Color::Green, self.selected == ConfirmationButton::Yes);
+        let cancel_style = self
+            .styles
+            .button_style(Color::Red, self.selected == ConfirmationButton::Cancel);
         let yes = Paragraph::new(" Yes ")
             .style(yes_style)
             .alignment(ratatui::layout::Alignment::Center);
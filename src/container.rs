@@ -0,0 +1,48 @@
+use std::{fs, path::Path};
+
+#[cfg(test)]
+#[path = "container_tests.rs"]
+mod container_tests;
+
+/// The file podman creates in its containers, holding the container name
+const CONTAINERENV_PATH: &str = "/run/.containerenv";
+
+/// The file docker creates in its containers
+const DOCKERENV_PATH: &str = "/.dockerenv";
+
+/// The name of the container cdir runs in, None when running on the host.
+///
+/// The name is found, in order, from:
+/// - `$CONTAINER_ID`, set by distrobox and toolbox;
+/// - the `name` entry of `/run/.containerenv` (podman);
+/// - the hostname, defaulting to the container id, when `/.dockerenv` exists (docker) or when
+///   `/run/.containerenv` has no name.
+pub fn current_container() -> Option<String> {
+    if let Ok(id) = std::env::var("CONTAINER_ID")
+        && !id.is_empty()
+    {
+        return Some(id);
+    }
+    let containerenv = fs::read_to_string(CONTAINERENV_PATH).ok();
+    if let Some(name) = containerenv.as_deref().and_then(parse_containerenv) {
+        return Some(name);
+    }
+    if containerenv.is_some() || Path::new(DOCKERENV_PATH).exists() {
+        return fs::read_to_string("/etc/hostname")
+            .ok()
+            .map(|hostname| hostname.trim().to_string())
+            .filter(|hostname| !hostname.is_empty())
+            .or_else(|| Some(String::from("container")));
+    }
+    None
+}
+
+/// Extract the container name of a `/run/.containerenv` file, made of `key="value"` lines.
+fn parse_containerenv(content: &str) -> Option<String> {
+    content
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .find(|(key, _)| key.trim() == "name")
+        .map(|(_, value)| value.trim().trim_matches('"').to_string())
+        .filter(|name| !name.is_empty())
+}
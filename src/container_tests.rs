@@ -0,0 +1,14 @@
+use super::*;
+
+#[test]
+fn test_parse_containerenv() {
+    let content =
+        "engine=\"podman-4.9.3\"\nname=\"fedora-toolbox\"\nid=\"4f2c\"\nimage=\"fedora\"\n";
+    assert_eq!(
+        parse_containerenv(content),
+        Some(String::from("fedora-toolbox"))
+    );
+    assert_eq!(parse_containerenv("engine=\"podman-4.9.3\"\n"), None);
+    assert_eq!(parse_containerenv("name=\"\"\n"), None);
+    assert_eq!(parse_containerenv(""), None);
+}
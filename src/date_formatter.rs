@@ -0,0 +1,167 @@
+use std::{env, sync::Arc};
+
+use chrono::{DateTime, Local, TimeZone};
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+#[path = "date_formatter_tests.rs"]
+mod date_formatter_tests;
+
+/// The variables giving the locale of the dates, by priority
+const LOCALE_VARS: [&str; 3] = ["LC_ALL", "LC_TIME", "LANG"];
+
+/// Formats the dates of the entries, given as seconds since the epoch
+pub trait DateFormatter: Send + Sync {
+    fn format(&self, epoch: i64) -> String;
+}
+
+/// How the dates of a view are rendered
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DateStyle {
+    /// The `date_format` pattern, e.g. `05-Mar-25 14:02:10`
+    #[default]
+    Format,
+    /// The time elapsed since the date, e.g. `2h ago` or `yesterday`
+    Relative,
+    /// The usual layout of the locale of the user, e.g. `03/05/2025 2:02 PM` in `en_US`
+    Locale,
+}
+
+impl DateStyle {
+    /// The formatter of the style, `date_format` being the pattern of `DateStyle::Format`
+    pub fn formatter(self, date_format: &str) -> Arc<dyn DateFormatter> {
+        match self {
+            DateStyle::Format => Arc::new(PatternFormatter::new(date_format)),
+            DateStyle::Relative => Arc::new(RelativeFormatter),
+            DateStyle::Locale => Arc::new(PatternFormatter::new(locale_pattern(&locale()))),
+        }
+    }
+}
+
+/// The formatters of the dates of each view
+#[derive(Clone)]
+pub struct DateFormatters {
+    /// The date column of the history table
+    pub history: Arc<dyn DateFormatter>,
+    /// The details of a path
+    pub details: Arc<dyn DateFormatter>,
+    /// The `date_format` pattern, for the command line outputs read by scripts
+    pub pattern: Arc<dyn DateFormatter>,
+}
+
+impl DateFormatters {
+    pub fn new(history: DateStyle, details: DateStyle, date_format: &str) -> Self {
+        DateFormatters {
+            history: history.formatter(date_format),
+            details: details.formatter(date_format),
+            pattern: DateStyle::Format.formatter(date_format),
+        }
+    }
+}
+
+/// The date at `epoch` in the local time zone
+fn local_date(epoch: i64) -> Option<DateTime<Local>> { Local.timestamp_opt(epoch, 0).single() }
+
+/// Formats the dates with a strftime-like pattern
+pub struct PatternFormatter {
+    pattern: String,
+}
+
+impl PatternFormatter {
+    pub fn new(pattern: &str) -> Self {
+        PatternFormatter {
+            pattern: pattern.to_string(),
+        }
+    }
+}
+
+impl DateFormatter for PatternFormatter {
+    fn format(&self, epoch: i64) -> String {
+        local_date(epoch)
+            .map(|date| date.format(&self.pattern).to_string())
+            .unwrap_or_default()
+    }
+}
+
+/// Formats the dates as the time elapsed until now, see `relative`
+pub struct RelativeFormatter;
+
+impl DateFormatter for RelativeFormatter {
+    fn format(&self, epoch: i64) -> String {
+        local_date(epoch)
+            .map(|date| relative(date, Local::now()))
+            .unwrap_or_default()
+    }
+}
+
+/// The time elapsed between `date` and `now`: the minutes or the hours within the same day,
+/// then `yesterday`, then the days, weeks, months and years.
+pub fn relative<Tz: TimeZone>(date: DateTime<Tz>, now: DateTime<Tz>) -> String {
+    let elapsed = now.clone() - date.clone();
+    let days = (now.date_naive() - date.date_naive()).num_days();
+    let plural = |count: i64, unit: &str| {
+        if count == 1 {
+            format!("1 {} ago", unit)
+        } else {
+            format!("{} {}s ago", count, unit)
+        }
+    };
+    match elapsed.num_minutes() {
+        minutes if minutes < 1 => String::from("just now"),
+        minutes if minutes < 60 => format!("{}m ago", minutes),
+        _ if days == 0 => format!("{}h ago", elapsed.num_hours()),
+        _ if days == 1 => String::from("yesterday"),
+        _ if days < 7 => plural(days, "day"),
+        _ if days < 31 => plural(days / 7, "week"),
+        _ if days < 365 => plural(days / 30, "month"),
+        _ => plural(days / 365, "year"),
+    }
+}
+
+/// The section of the history grouping the entries of `date`, relative to `now`: `Today`,
+/// `Yesterday`, `Last week` and `Last month` within 7 and 31 days, then `Older`
+pub fn day_section<Tz: TimeZone>(date: DateTime<Tz>, now: DateTime<Tz>) -> &'static str {
+    match (now.date_naive() - date.date_naive()).num_days() {
+        ..=0 => "Today",
+        1 => "Yesterday",
+        2..7 => "Last week",
+        7..31 => "Last month",
+        _ => "Older",
+    }
+}
+
+/// The section of the history grouping the entries of `epoch`, see `day_section`
+pub fn local_day_section(epoch: i64) -> Option<&'static str> {
+    local_date(epoch).map(|date| day_section(date, Local::now()))
+}
+
+/// The locale of the user, e.g. `fr_FR.UTF-8`, empty when unknown
+fn locale() -> String {
+    LOCALE_VARS
+        .iter()
+        .filter_map(|var| env::var(var).ok())
+        .find(|value| !value.is_empty())
+        .unwrap_or_default()
+}
+
+/// The usual date pattern of a locale, e.g. `en_US.UTF-8` or `de_DE`, the ISO 8601 layout
+/// being used for the unknown locales and for `C`/`POSIX`
+pub fn locale_pattern(locale: &str) -> &'static str {
+    let locale = locale.split(['.', '@']).next().unwrap_or_default();
+    let mut parts = locale.split(['_', '-']);
+    let language = parts.next().unwrap_or_default();
+    let region = parts.next().unwrap_or_default();
+    match (language, region) {
+        ("en", "US" | "PH") => "%m/%d/%Y %I:%M %p",
+        ("ja" | "zh", _) => "%Y/%m/%d %H:%M",
+        ("ko", _) => "%Y. %m. %d. %H:%M",
+        ("sv" | "lt" | "hu", _) | ("en", "CA") => "%Y-%m-%d %H:%M",
+        ("de" | "fi" | "nb" | "no" | "da" | "ru" | "pl" | "cs" | "sk" | "tr" | "uk", _) => {
+            "%d.%m.%Y %H:%M"
+        }
+        ("nl", _) => "%d-%m-%Y %H:%M",
+        ("en" | "fr" | "es" | "it" | "pt" | "el" | "ca", _) => "%d/%m/%Y %H:%M",
+        _ => "%Y-%m-%d %H:%M",
+    }
+}
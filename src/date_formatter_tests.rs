@@ -0,0 +1,68 @@
+use chrono::{TimeZone, Utc};
+
+use crate::date_formatter::{
+    DateFormatter, PatternFormatter, day_section, locale_pattern, relative,
+};
+
+#[test]
+fn test_relative() {
+    let now = Utc.with_ymd_and_hms(2025, 3, 5, 14, 30, 0).unwrap();
+    let ago = |day, hour, min| {
+        relative(
+            Utc.with_ymd_and_hms(2025, 3, day, hour, min, 0).unwrap(),
+            now,
+        )
+    };
+    assert_eq!(ago(5, 14, 30), "just now");
+    assert_eq!(ago(5, 14, 5), "25m ago");
+    assert_eq!(ago(5, 2, 0), "12h ago");
+    assert_eq!(ago(4, 23, 0), "yesterday");
+    // less than an hour before, the day before
+    let midnight = Utc.with_ymd_and_hms(2025, 3, 5, 0, 10, 0).unwrap();
+    assert_eq!(
+        relative(
+            Utc.with_ymd_and_hms(2025, 3, 4, 23, 50, 0).unwrap(),
+            midnight
+        ),
+        "20m ago"
+    );
+    assert_eq!(ago(2, 14, 0), "3 days ago");
+    let date = |year, month, day| Utc.with_ymd_and_hms(year, month, day, 12, 0, 0).unwrap();
+    assert_eq!(relative(date(2025, 2, 26), now), "1 week ago");
+    assert_eq!(relative(date(2024, 12, 1), now), "3 months ago");
+    assert_eq!(relative(date(2022, 3, 1), now), "3 years ago");
+}
+
+#[test]
+fn test_locale_pattern() {
+    assert_eq!(locale_pattern("en_US.UTF-8"), "%m/%d/%Y %I:%M %p");
+    assert_eq!(locale_pattern("en_GB.UTF-8"), "%d/%m/%Y %H:%M");
+    assert_eq!(locale_pattern("de_DE@euro"), "%d.%m.%Y %H:%M");
+    assert_eq!(locale_pattern("ja_JP"), "%Y/%m/%d %H:%M");
+    assert_eq!(locale_pattern("C"), "%Y-%m-%d %H:%M");
+    assert_eq!(locale_pattern(""), "%Y-%m-%d %H:%M");
+}
+
+#[test]
+fn test_pattern_formatter() {
+    // the year of the date, whatever the time zone
+    let formatter = PatternFormatter::new("%Y");
+    assert_eq!(formatter.format(1_741_185_000), "2025");
+}
+
+#[test]
+fn test_day_section() {
+    let now = Utc.with_ymd_and_hms(2025, 3, 5, 0, 30, 0).unwrap();
+    let section = |month, day, hour| {
+        day_section(
+            Utc.with_ymd_and_hms(2025, month, day, hour, 0, 0).unwrap(),
+            now,
+        )
+    };
+    assert_eq!(section(3, 5, 0), "Today");
+    // less than an hour before, the day before
+    assert_eq!(section(3, 4, 23), "Yesterday");
+    assert_eq!(section(2, 27, 12), "Last week");
+    assert_eq!(section(2, 26, 12), "Last month");
+    assert_eq!(section(1, 1, 12), "Older");
+}
@@ -0,0 +1,157 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Padding, Paragraph, Sparkline, Wrap},
+};
+use tracing::debug;
+
+use crate::{
+    theme::ThemeStyles,
+    tui::{EventCaptured, ManagerAction, View, ViewBuilder, ViewManager},
+};
+
+/// The number of weeks of the activity of a path
+pub(crate) const ACTIVITY_WEEKS: usize = 8;
+
+/// The height of the activity sparkline, in rows
+const ACTIVITY_HEIGHT: u16 = 3;
+
+/// What the details modal shows about an entry
+pub struct Details {
+    /// The fields of the entry, as (name, value)
+    pub fields: Vec<(String, String)>,
+    /// The visits of a path in each of the last `ACTIVITY_WEEKS` weeks, the oldest first
+    pub activity: Option<Vec<u64>>,
+}
+
+impl From<Vec<(String, String)>> for Details {
+    fn from(fields: Vec<(String, String)>) -> Self {
+        Details {
+            fields,
+            activity: None,
+        }
+    }
+}
+
+/// A modal showing all the fields of an entry without any truncation,
+/// long values being wrapped over several lines, then the activity of a path as a sparkline.
+pub struct DetailsView {
+    styles: ThemeStyles,
+    title: String,
+    details: Details,
+}
+
+impl DetailsView {
+    pub fn builder(title: String, details: Details, styles: ThemeStyles) -> ViewBuilder {
+        ViewBuilder::from(Box::new(Self {
+            styles,
+            title,
+            details,
+        }))
+    }
+
+    /// The bars of the sparkline for a width, each week being as wide as possible
+    fn activity_bars(activity: &[u64], width: u16) -> Vec<u64> {
+        let week_width = (width as usize / activity.len().max(1)).max(1);
+        activity
+            .iter()
+            .flat_map(|visits| std::iter::repeat_n(*visits, week_width))
+            .collect()
+    }
+
+    fn build_lines(&self) -> Vec<Line<'static>> {
+        let mut lines = vec![];
+        for (index, (name, value)) in self.details.fields.iter().enumerate() {
+            if index > 0 {
+                lines.push(Line::from(""));
+            }
+            lines.push(Line::from(Span::styled(
+                format!("{}:", name),
+                self.styles.text_em_style,
+            )));
+            lines.push(Line::from(Span::styled(
+                value.clone(),
+                self.styles.text_style,
+            )));
+        }
+        if let Some(activity) = &self.details.activity {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "Visits, past {} weeks: {}",
+                    ACTIVITY_WEEKS,
+                    activity.iter().sum::<u64>()
+                ),
+                self.styles.text_em_style,
+            )));
+        }
+        lines
+    }
+}
+
+impl View for DetailsView {
+    fn draw(&mut self, frame: &mut ratatui::Frame, _area: Rect, _active: bool) {
+        debug!("Drawing details");
+        let area = frame.area();
+        let lines = self.build_lines();
+
+        // Use most of the screen width, and enough height for the wrapped values
+        let modal_width = (area.width.saturating_mul(4) / 5).max(20).min(area.width);
+        let text_width = modal_width.saturating_sub(4).max(1) as usize;
+        let text_height: usize = lines
+            .iter()
+            .map(|l| l.width().max(1).div_ceil(text_width))
+            .sum();
+        let activity_height = if self.details.activity.is_some() {
+            ACTIVITY_HEIGHT
+        } else {
+            0
+        };
+        let modal_height = (text_height as u16)
+            .saturating_add(4 + activity_height)
+            .min(area.height);
+
+        let modal_area = ViewManager::centered_rect(area, modal_width, modal_height);
+        frame.render_widget(Clear, modal_area);
+        if let Some(bg_color) = &self.styles.background_color {
+            let background = Paragraph::new("").style(Style::default().bg(*bg_color));
+            frame.render_widget(background, modal_area);
+        }
+
+        let mut block = Block::default()
+            .title(Span::styled(
+                format!(" {} ", self.title),
+                self.styles.title_style,
+            ))
+            .borders(Borders::ALL)
+            .padding(Padding::new(1, 1, 1, 1));
+        if let Some(border_color) = self.styles.border_color {
+            block = block.border_style(Style::default().fg(border_color));
+        }
+
+        let inner_area = block.inner(modal_area);
+        frame.render_widget(block, modal_area);
+        let [text_area, activity_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(activity_height)])
+                .areas(inner_area);
+
+        let paragraph = Paragraph::new(lines).wrap(Wrap { trim: false });
+        frame.render_widget(paragraph, text_area);
+        if let Some(activity) = &self.details.activity {
+            let sparkline = Sparkline::default()
+                .data(Self::activity_bars(activity, activity_area.width))
+                .style(self.styles.heat_style);
+            frame.render_widget(sparkline, activity_area);
+        }
+    }
+
+    fn handle_key_event(&mut self, key_event: KeyEvent) -> (EventCaptured, ManagerAction) {
+        let close = matches!(key_event.code, KeyCode::Enter | KeyCode::Char('q'));
+        (
+            EventCaptured::Yes,
+            ManagerAction::new(false).with_close(close),
+        )
+    }
+}
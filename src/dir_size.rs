@@ -0,0 +1,156 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::Path,
+    sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use tokio::sync::broadcast;
+use tracing::{debug, error};
+
+use crate::tui::{
+    GenericEvent,
+    event::{ApplicationEvent, ViewManagerEvent},
+};
+
+#[cfg(test)]
+#[path = "dir_size_tests.rs"]
+mod dir_size_tests;
+
+/// The id of the application event published when the size of a directory has been computed,
+/// its payload being a `DirSizePayload`
+pub const DIR_SIZE_COMPUTED_EVENT: &str = "dir_size.computed";
+
+/// The size of a directory
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DirSize {
+    /// The total size of the files of the directory and its sub directories, in bytes
+    pub size: u64,
+    /// When the size was computed, in seconds since the epoch
+    pub date: i64,
+}
+
+/// The payload of the `DIR_SIZE_COMPUTED_EVENT` event
+#[derive(Debug)]
+pub struct DirSizePayload {
+    pub path: String,
+    pub size: DirSize,
+}
+
+/// The sizes of the directories displayed in the history, computed lazily in background tasks.
+#[derive(Clone)]
+pub struct DirSizeCache {
+    sizes: Arc<Mutex<HashMap<String, DirSize>>>,
+    /// The directories whose size is being computed
+    computing: Arc<Mutex<HashSet<String>>>,
+    /// The duration after which a size is computed again, in seconds
+    ttl: i64,
+    tx: broadcast::Sender<GenericEvent>,
+}
+
+impl DirSizeCache {
+    /// Build the cache from the sizes already computed, e.g. by a previous session.
+    pub fn new(
+        sizes: Vec<(String, DirSize)>,
+        ttl: u64,
+        tx: broadcast::Sender<GenericEvent>,
+    ) -> DirSizeCache {
+        DirSizeCache {
+            sizes: Arc::new(Mutex::new(sizes.into_iter().collect())),
+            computing: Arc::new(Mutex::new(HashSet::new())),
+            ttl: ttl as i64,
+            tx,
+        }
+    }
+
+    /// The size of a directory, None while it was never computed.
+    /// When the size is unknown or older than the TTL, it is computed in a background task which
+    /// publishes a `DIR_SIZE_COMPUTED_EVENT` event and requests a redraw once done.
+    pub fn get(&self, path: &str) -> Option<u64> {
+        let cached = self.sizes.lock().unwrap().get(path).copied();
+        if cached.is_none_or(|size| now() - size.date > self.ttl) {
+            self.compute(path);
+        }
+        cached.map(|size| size.size)
+    }
+
+    fn compute(&self, path: &str) {
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        if !Path::new(path).is_dir() || !self.computing.lock().unwrap().insert(path.to_string()) {
+            return;
+        }
+        let path = path.to_string();
+        let cache = self.clone();
+        handle.spawn_blocking(move || {
+            let size = DirSize {
+                size: dir_size(Path::new(&path)),
+                date: now(),
+            };
+            debug!("dir_size {} {:?}", path, size);
+            cache.sizes.lock().unwrap().insert(path.clone(), size);
+            cache.computing.lock().unwrap().remove(&path);
+            let event = GenericEvent::ApplicationEvent(ApplicationEvent {
+                id: String::from(DIR_SIZE_COMPUTED_EVENT),
+                payload: Some(Arc::new(DirSizePayload { path, size })),
+            });
+            if let Err(e) = cache.tx.send(event) {
+                error!("Failed to send '{}' event: {}", DIR_SIZE_COMPUTED_EVENT, e);
+            }
+            let _ = cache
+                .tx
+                .send(GenericEvent::ViewManagerEvent(ViewManagerEvent::Redraw));
+        });
+    }
+}
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+}
+
+/// The total size of the files below a directory. Symbolic links are not followed and the
+/// entries which cannot be read are skipped, making the size approximate.
+fn dir_size(path: &Path) -> u64 {
+    let mut size = 0;
+    let mut dirs = vec![path.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            match entry.metadata() {
+                Ok(metadata) if metadata.is_dir() => dirs.push(entry.path()),
+                Ok(metadata) => size += metadata.len(),
+                Err(_) => {}
+            }
+        }
+    }
+    size
+}
+
+/// Format a size in bytes with a binary unit, e.g. `1.5K` or `12G`.
+pub fn format_size(size: u64) -> String {
+    const UNITS: [&str; 5] = ["K", "M", "G", "T", "P"];
+    if size < 1024 {
+        return format!("{}B", size);
+    }
+    let mut value = size as f64;
+    let mut unit = "B";
+    for u in UNITS {
+        if value < 1024.0 {
+            break;
+        }
+        value /= 1024.0;
+        unit = u;
+    }
+    if value < 10.0 {
+        format!("{:.1}{}", value, unit)
+    } else {
+        format!("{:.0}{}", value, unit)
+    }
+}
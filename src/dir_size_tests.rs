@@ -0,0 +1,63 @@
+use super::*;
+
+#[test]
+fn test_format_size() {
+    assert_eq!(format_size(0), "0B");
+    assert_eq!(format_size(1023), "1023B");
+    assert_eq!(format_size(1536), "1.5K");
+    assert_eq!(format_size(20 * 1024 * 1024), "20M");
+    assert_eq!(format_size(3 * 1024 * 1024 * 1024), "3.0G");
+}
+
+#[test]
+fn test_dir_size() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("a"), vec![0u8; 100]).unwrap();
+    fs::create_dir(dir.path().join("sub")).unwrap();
+    fs::write(dir.path().join("sub").join("b"), vec![0u8; 50]).unwrap();
+    assert_eq!(dir_size(dir.path()), 150);
+}
+
+#[test]
+fn test_cache_get() {
+    let (tx, _rx) = broadcast::channel(10);
+    let fresh = DirSize {
+        size: 42,
+        date: now(),
+    };
+    let stale = DirSize { size: 7, date: 0 };
+    let cache = DirSizeCache::new(
+        vec![
+            (String::from("/fresh"), fresh),
+            (String::from("/stale"), stale),
+        ],
+        60,
+        tx,
+    );
+    assert_eq!(cache.get("/fresh"), Some(42));
+    // the stale size is still returned while it is computed again
+    assert_eq!(cache.get("/stale"), Some(7));
+    assert_eq!(cache.get("/unknown"), None);
+}
+
+#[tokio::test]
+async fn test_cache_compute() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("a"), vec![0u8; 10]).unwrap();
+    let path = dir.path().to_str().unwrap().to_string();
+    let (tx, mut rx) = broadcast::channel(10);
+    let cache = DirSizeCache::new(vec![], 60, tx);
+
+    assert_eq!(cache.get(&path), None);
+    match rx.recv().await.unwrap() {
+        GenericEvent::ApplicationEvent(ae) => {
+            assert_eq!(ae.id, DIR_SIZE_COMPUTED_EVENT);
+            let payload = ae.payload.unwrap();
+            let payload = payload.downcast_ref::<DirSizePayload>().unwrap();
+            assert_eq!(payload.path, path);
+            assert_eq!(payload.size.size, 10);
+        }
+        _ => panic!("expected the computed size event"),
+    }
+    assert_eq!(cache.get(&path), Some(10));
+}
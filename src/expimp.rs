@@ -1,98 +1,523 @@
-use std::{fs, path::PathBuf};
+use std::{collections::HashMap, fmt, fs, str::FromStr};
 
-use log::error;
 use serde::{Deserialize, Serialize};
+use tracing::warn;
 
-use crate::store::Store;
+use crate::{fetch, remote::RemotePath, store::Store};
 
 #[cfg(test)]
 #[path = "expimp_tests.rs"]
 mod expimp_tests;
 
+/// The number of entries between two progress reports
+const PROGRESS_STEP: usize = 1000;
+
+/// The name of the list of the paths, in the formats whose documents are tables
+const PATHS_KEY: &str = "paths";
+
+/// The name of the list of the shortcuts, in the formats whose documents are tables
+const SHORTCUTS_KEY: &str = "shortcuts";
+
+/// Called during an import with the number of entries processed and the total number of entries
+pub type ProgressFn<'a> = &'a mut dyn FnMut(usize, usize);
+
+/// A file format of the imports and the exports, whose documents hold a list of entries
+pub trait Format {
+    /// Parse the list of entries of a document, `key` naming the list in the formats whose
+    /// documents are tables, e.g. `shortcuts`.
+    fn parse(&self, contents: &str, key: &str) -> Result<Vec<serde_yaml::Value>, String>;
+
+    /// Write a list of entries, see `parse`.
+    fn write<T: Serialize>(&self, entries: &[T], key: &str) -> Result<String, String>;
+
+    /// The lines, from 1, of the entries of a document, empty when they are not known.
+    fn entry_lines(&self, _contents: &str) -> Vec<usize> { vec![] }
+}
+
+/// A YAML list
+pub struct Yaml;
+
+impl Format for Yaml {
+    fn parse(&self, contents: &str, _key: &str) -> Result<Vec<serde_yaml::Value>, String> {
+        serde_yaml::from_str(contents).map_err(|e| e.to_string())
+    }
+
+    fn write<T: Serialize>(&self, entries: &[T], _key: &str) -> Result<String, String> {
+        serde_yaml::to_string(entries).map_err(|e| e.to_string())
+    }
+
+    /// The items of the top level list written in the block style, e.g. `- name: w`
+    fn entry_lines(&self, contents: &str) -> Vec<usize> {
+        contents
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| *line == "-" || line.starts_with("- "))
+            .map(|(idx, _)| idx + 1)
+            .collect()
+    }
+}
+
+/// A JSON array
+pub struct Json;
+
+impl Format for Json {
+    fn parse(&self, contents: &str, _key: &str) -> Result<Vec<serde_yaml::Value>, String> {
+        let values: Vec<serde_json::Value> =
+            serde_json::from_str(contents).map_err(|e| e.to_string())?;
+        values
+            .iter()
+            .map(|value| serde_yaml::to_value(value).map_err(|e| e.to_string()))
+            .collect()
+    }
+
+    fn write<T: Serialize>(&self, entries: &[T], _key: &str) -> Result<String, String> {
+        serde_json::to_string_pretty(entries)
+            .map(|json| json + "\n")
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// A TOML array of tables, e.g. `[[shortcuts]]`
+pub struct Toml;
+
+impl Format for Toml {
+    fn parse(&self, contents: &str, key: &str) -> Result<Vec<serde_yaml::Value>, String> {
+        let table: toml::Table = toml::from_str(contents).map_err(|e| e.to_string())?;
+        match table.get(key) {
+            None => Ok(vec![]),
+            Some(toml::Value::Array(values)) => values
+                .iter()
+                .map(|value| serde_yaml::to_value(value).map_err(|e| e.to_string()))
+                .collect(),
+            Some(_) => Err(format!("`{}` is not an array of tables", key)),
+        }
+    }
+
+    fn write<T: Serialize>(&self, entries: &[T], key: &str) -> Result<String, String> {
+        toml::to_string(&HashMap::from([(key, entries)])).map_err(|e| e.to_string())
+    }
+
+    /// The headers of the tables of the array, e.g. `[[shortcuts]]`
+    fn entry_lines(&self, contents: &str) -> Vec<usize> {
+        contents
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| line.trim_start().starts_with("[["))
+            .map(|(idx, _)| idx + 1)
+            .collect()
+    }
+}
+
+/// The formats of the imported and exported files
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum FileFormat {
+    #[default]
+    Yaml,
+    Json,
+    Toml,
+}
+
+impl FileFormat {
+    /// The format of a file from the extension of its name, YAML when it is not known.
+    pub fn detect(location: &str) -> FileFormat {
+        let extension = location
+            .rsplit_once('.')
+            .map(|(_, extension)| extension.to_ascii_lowercase())
+            .unwrap_or_default();
+        extension.parse().unwrap_or_default()
+    }
+}
+
+impl Format for FileFormat {
+    fn parse(&self, contents: &str, key: &str) -> Result<Vec<serde_yaml::Value>, String> {
+        match self {
+            FileFormat::Yaml => Yaml.parse(contents, key),
+            FileFormat::Json => Json.parse(contents, key),
+            FileFormat::Toml => Toml.parse(contents, key),
+        }
+    }
+
+    fn write<T: Serialize>(&self, entries: &[T], key: &str) -> Result<String, String> {
+        match self {
+            FileFormat::Yaml => Yaml.write(entries, key),
+            FileFormat::Json => Json.write(entries, key),
+            FileFormat::Toml => Toml.write(entries, key),
+        }
+    }
+
+    fn entry_lines(&self, contents: &str) -> Vec<usize> {
+        match self {
+            FileFormat::Yaml => Yaml.entry_lines(contents),
+            FileFormat::Json => Json.entry_lines(contents),
+            FileFormat::Toml => Toml.entry_lines(contents),
+        }
+    }
+}
+
+impl FromStr for FileFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "yaml" | "yml" => Ok(FileFormat::Yaml),
+            "json" => Ok(FileFormat::Json),
+            "toml" => Ok(FileFormat::Toml),
+            _ => Err(format!(
+                "unknown format '{}', expected yaml, json or toml",
+                s
+            )),
+        }
+    }
+}
+
+/// How an import file is read and imported
+#[derive(Debug, Default, Clone)]
+pub struct ImportOptions {
+    /// The format of the file, detected from its extension when None
+    pub format: Option<FileFormat>,
+    /// The SHA-256 the file must have, in hexadecimal
+    pub sha256: Option<String>,
+    /// Check the entries without importing them
+    pub validate_only: bool,
+    /// Import the entries then roll back, to report what the import would change
+    pub dry_run: bool,
+}
+
+impl ImportOptions {
+    /// Whether the import is rolled back rather than committed
+    fn rolls_back(&self) -> bool { self.validate_only || self.dry_run }
+}
+
+/// The position of an entry in an import file
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Position {
+    /// The rank of the entry in the list, from 1
+    pub index: usize,
+    /// The line the entry starts at, from 1, when it is known
+    pub line: Option<usize>,
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "entry {} (line {})", self.index, line),
+            None => write!(f, "entry {}", self.index),
+        }
+    }
+}
+
+/// An entry of an import file which is not imported, quarantined with the reason why
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rejected {
+    pub position: Position,
+    pub reason: String,
+    /// The entry, as written in the file
+    pub entry: serde_yaml::Value,
+}
+
+impl Rejected {
+    fn new<T: Serialize>(position: Position, reason: String, entry: &T) -> Rejected {
+        warn!("Rejecting {}: {}", position, reason);
+        Rejected {
+            position,
+            reason,
+            entry: serde_yaml::to_value(entry).unwrap_or_default(),
+        }
+    }
+}
+
+impl fmt::Display for Rejected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.position, self.reason)
+    }
+}
+
+/// The outcome of an import
+#[derive(Debug, Default, PartialEq)]
+pub struct ImportSummary {
+    /// The entries added to the database
+    pub imported: usize,
+    /// The entries already in the database, or earlier in the file
+    pub duplicates: usize,
+    /// The shortcuts replaced by an entry of another path or description, with their previous
+    /// path
+    pub replaced: Vec<(String, String)>,
+    /// The entries rejected because they do not match the schema or have an invalid field,
+    /// in the order of the file
+    pub rejected: Vec<Rejected>,
+}
+
+impl fmt::Display for ImportSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} imported, {} duplicates skipped, {} invalid",
+            self.imported,
+            self.duplicates,
+            self.rejected.len()
+        )
+    }
+}
+
+/// The entries of an import file matching the schema of `T`, with their position
+type Entries<T> = Vec<(Position, T)>;
+
+/// Read a file holding a list of entries named `key`, from a local path or an https:// URL,
+/// see `fetch::read_location`. The entries are checked one by one against the schema of `T`,
+/// the ones which do not match it being rejected rather than failing the whole file.
+///
+/// ### Returns
+/// The entries matching the schema and the rejected ones, or an error, with its line and
+/// column, if the file cannot be read or is not a list.
+fn read_entries<T: for<'de> Deserialize<'de>>(
+    location: &str,
+    key: &str,
+    options: &ImportOptions,
+) -> Result<(Entries<T>, Vec<Rejected>), String> {
+    let contents = fetch::read_location(location, options.sha256.as_deref())?;
+    let format = options
+        .format
+        .unwrap_or_else(|| FileFormat::detect(location));
+    let values = format
+        .parse(&contents, key)
+        .map_err(|e| format!("Failed to parse the file {}: {}", location, e))?;
+    // the lines are only known when each entry starts a line
+    let lines = Some(format.entry_lines(&contents)).filter(|lines| lines.len() == values.len());
+    let mut entries = vec![];
+    let mut rejected = vec![];
+    for (idx, value) in values.into_iter().enumerate() {
+        let position = Position {
+            index: idx + 1,
+            line: lines.as_ref().map(|lines| lines[idx]),
+        };
+        match serde_yaml::from_value::<T>(value.clone()) {
+            Ok(entry) => entries.push((position, entry)),
+            Err(e) => rejected.push(Rejected::new(position, e.to_string(), &value)),
+        }
+    }
+    Ok((entries, rejected))
+}
+
+/// Write the rejected entries of an import to a YAML file, whatever the format of the import, each one preceded by a comment
+/// with its position and the reason it was rejected, so that it can be fixed and imported
+/// again.
+pub fn write_rejects(rejected: &[Rejected], file: &std::path::Path) -> Result<(), String> {
+    let mut contents = String::new();
+    for rejected in rejected {
+        contents.push_str(&format!("# {}\n", rejected));
+        contents.push_str(
+            &serde_yaml::to_string(&[&rejected.entry])
+                .map_err(|e| format!("Failed to write {}: {}", rejected.position, e))?,
+        );
+    }
+    fs::write(file, contents).map_err(|e| format!("Failed to write {}: {}", file.display(), e))
+}
+
 #[derive(Serialize, Deserialize, PartialEq, Debug)]
+#[serde(deny_unknown_fields)]
 struct Path {
     date: String,
     path: String,
 }
 
-/// Load paths from a YAML file and add them to the store.
-/// The YAML file should contain a list of objects with `date` and `path` fields.
+/// Load paths from a YAML, JSON or TOML file, a local path or an https:// URL, and add them to
+/// the store, in a single transaction, rolled back when `options.validate_only` or
+/// `options.dry_run` is set.
+/// The file should contain a list of objects with `date` and `path` fields, named `paths` in
+/// TOML.
 /// The `date` field should be a string representing a UNIX timestamp in seconds.
-pub(crate) fn load_paths_from_yaml(store: Store, yaml_file: PathBuf) {
-    if !yaml_file.exists() {
-        error!("File {} does not exist", yaml_file.display());
-        return;
-    }
-    match fs::read_to_string(&yaml_file) {
-        Ok(contents) => {
-            let new_paths_res: Result<Vec<Path>, serde_yaml::Error> =
-                serde_yaml::from_str(contents.as_str());
-            match new_paths_res {
-                Ok(new_paths) => {
-                    load_paths(store, new_paths);
-                }
-                Err(e) => {
-                    error!("Failed to parse the file {}: {}", yaml_file.display(), e);
-                }
-            }
-        }
-        Err(e) => {
-            error!("Failed to read file {}: {}", yaml_file.display(), e);
-        }
-    }
+pub fn load_paths_from_file(
+    store: Store,
+    location: &str,
+    options: &ImportOptions,
+    progress: ProgressFn,
+) -> Result<ImportSummary, String> {
+    let (new_paths, rejected) = read_entries::<Path>(location, PATHS_KEY, options)?;
+    let mut summary = load_paths(store, new_paths, options.rolls_back(), progress)
+        .map_err(|e| format!("Failed to import the paths of {}: {}", location, e))?;
+    summary.rejected.extend(rejected);
+    summary
+        .rejected
+        .sort_by_key(|rejected| rejected.position.index);
+    Ok(summary)
 }
 
-fn load_paths(store: Store, new_paths: Vec<Path>) {
-    for entry in new_paths {
+fn load_paths(
+    store: Store,
+    new_paths: Entries<Path>,
+    roll_back: bool,
+    progress: ProgressFn,
+) -> Result<ImportSummary, rusqlite::Error> {
+    let transaction = store.transaction()?;
+    let mut summary = ImportSummary::default();
+    let mut seen = HashMap::new();
+    let total = new_paths.len();
+    for (idx, (position, entry)) in new_paths.into_iter().enumerate() {
         match entry.date.parse::<u64>() {
+            Ok(_) if entry.path.is_empty() => {
+                summary.rejected.push(Rejected::new(
+                    position,
+                    String::from("the path is empty"),
+                    &entry,
+                ));
+            }
+            Ok(_) if RemotePath::validate(&entry.path).is_err() => {
+                summary.rejected.push(Rejected::new(
+                    position,
+                    format!("invalid remote entry '{}'", entry.path),
+                    &entry,
+                ));
+            }
             Ok(sec) => {
-                let _ = store
-                    .add_path_with_time(&entry.path, sec)
-                    .map_err(|e| error!("{}", e));
+                if let Some(first) = seen.get(&(entry.path.clone(), sec)) {
+                    warn!("Skipping {}, duplicate of {}", position, first);
+                    summary.duplicates += 1;
+                } else if store.has_path_history(&entry.path, sec)? {
+                    summary.duplicates += 1;
+                } else {
+                    store.add_path_with_time(&entry.path, sec)?;
+                    summary.imported += 1;
+                }
+                seen.entry((entry.path, sec)).or_insert(position);
             }
             Err(e) => {
-                error!("{}", e);
+                summary.rejected.push(Rejected::new(
+                    position,
+                    format!("invalid date '{}': {}", entry.date, e),
+                    &entry,
+                ));
             }
         }
+        if (idx + 1) % PROGRESS_STEP == 0 {
+            progress(idx + 1, total);
+        }
     }
+    if !roll_back {
+        transaction.commit()?;
+    }
+    Ok(summary)
 }
 
 #[derive(Serialize, Deserialize, PartialEq, Debug)]
+#[serde(deny_unknown_fields)]
 struct Shortcut {
     name: String,
     path: String,
+    // TOML has no null value
+    #[serde(skip_serializing_if = "Option::is_none")]
     description: Option<String>,
 }
 
-pub(crate) fn load_shortcuts_from_yaml(store: Store, yaml_file: PathBuf) {
-    if !yaml_file.exists() {
-        error!("File {} does not exist", yaml_file.display());
-        return;
-    }
-    match fs::read_to_string(&yaml_file) {
-        Ok(contents) => {
-            let new_shortcuts_res: Result<Vec<Shortcut>, serde_yaml::Error> =
-                serde_yaml::from_str(contents.as_str());
-            match new_shortcuts_res {
-                Ok(shortcuts) => {
-                    load_shortcuts(store, shortcuts);
-                }
-                Err(e) => {
-                    error!("Failed to parse the file {}: {}", yaml_file.display(), e);
+/// Load shortcuts from a file, like `load_paths_from_file`, and add them to the store, in a
+/// single transaction. The list of the shortcuts is named `shortcuts` in TOML.
+/// The shortcuts replace the existing ones having the same name, a shortcut defined again
+/// later in the file being rejected.
+pub fn load_shortcuts_from_file(
+    store: Store,
+    location: &str,
+    options: &ImportOptions,
+    progress: ProgressFn,
+) -> Result<ImportSummary, String> {
+    let (shortcuts, rejected) = read_entries::<Shortcut>(location, SHORTCUTS_KEY, options)?;
+    let mut summary = load_shortcuts(store, shortcuts, options.rolls_back(), progress)
+        .map_err(|e| format!("Failed to import the shortcuts of {}: {}", location, e))?;
+    summary.rejected.extend(rejected);
+    summary
+        .rejected
+        .sort_by_key(|rejected| rejected.position.index);
+    Ok(summary)
+}
+
+fn load_shortcuts(
+    store: Store,
+    new_shortcuts: Entries<Shortcut>,
+    roll_back: bool,
+    progress: ProgressFn,
+) -> Result<ImportSummary, rusqlite::Error> {
+    let transaction = store.transaction()?;
+    let mut summary = ImportSummary::default();
+    let mut seen: HashMap<String, Position> = HashMap::new();
+    let total = new_shortcuts.len();
+    for (idx, (position, entry)) in new_shortcuts.into_iter().enumerate() {
+        if entry.name.is_empty() || entry.path.is_empty() {
+            summary.rejected.push(Rejected::new(
+                position,
+                String::from("the shortcut has no name or no path"),
+                &entry,
+            ));
+        } else if let Some(first) = seen.get(&entry.name) {
+            summary.rejected.push(Rejected::new(
+                position,
+                format!(
+                    "the shortcut '{}' is already defined by {}",
+                    entry.name, first
+                ),
+                &entry,
+            ));
+        } else if RemotePath::validate(&entry.path).is_err() {
+            summary.rejected.push(Rejected::new(
+                position,
+                format!("invalid remote entry '{}'", entry.path),
+                &entry,
+            ));
+        } else {
+            seen.insert(entry.name.clone(), position);
+            let existing = store.find_shortcut(&entry.name);
+            if existing
+                .as_ref()
+                .is_some_and(|s| s.path == entry.path && s.description == entry.description)
+            {
+                summary.duplicates += 1;
+            } else {
+                if let Some(existing) = existing {
+                    summary.replaced.push((entry.name.clone(), existing.path));
                 }
+                store.upsert_shortcut(&entry.name, &entry.path, entry.description.as_deref())?;
+                summary.imported += 1;
             }
         }
-        Err(e) => {
-            error!("Failed to read file {}: {}", yaml_file.display(), e);
+        if (idx + 1) % PROGRESS_STEP == 0 {
+            progress(idx + 1, total);
         }
     }
+    if !roll_back {
+        transaction.commit()?;
+    }
+    Ok(summary)
 }
 
-fn load_shortcuts(store: Store, new_paths: Vec<Shortcut>) {
-    for entry in new_paths {
-        let _ = store.delete_shortcut(&entry.name);
-        let _ = store
-            .add_shortcut(&entry.name, &entry.path, entry.description.as_deref())
-            .map_err(|e| error!("{}", e));
-    }
+/// The shortcuts of the store, by name, as a document of `format` in the format of the
+/// imports.
+pub fn export_shortcuts(store: &Store, format: FileFormat) -> Result<String, String> {
+    let shortcuts: Vec<Shortcut> = store
+        .list_all_shortcuts()
+        .map_err(|e| format!("Failed to list the shortcuts: {}", e))?
+        .into_iter()
+        .map(|shortcut| Shortcut {
+            name: shortcut.name,
+            path: shortcut.path,
+            description: shortcut.description,
+        })
+        .collect();
+    format
+        .write(&shortcuts, SHORTCUTS_KEY)
+        .map_err(|e| format!("Failed to write the shortcuts: {}", e))
+}
+
+/// The visits of the history, the oldest first, as a document of `format` in the format of
+/// the imports. The paths in the trash are left out, and the sensitive ones unless the store
+/// reveals them.
+pub fn export_paths(store: &Store, format: FileFormat) -> Result<String, String> {
+    let paths: Vec<Path> = store
+        .list_history()
+        .map_err(|e| format!("Failed to list the history: {}", e))?
+        .into_iter()
+        .map(|(path, date)| Path {
+            date: date.to_string(),
+            path,
+        })
+        .collect();
+    format
+        .write(&paths, PATHS_KEY)
+        .map_err(|e| format!("Failed to write the paths: {}", e))
 }
@@ -1,5 +1,22 @@
 use super::*;
 
+/// The entries in the order of a file, without their lines
+fn positioned<T>(entries: Vec<T>) -> Entries<T> {
+    entries
+        .into_iter()
+        .enumerate()
+        .map(|(idx, entry)| {
+            (
+                Position {
+                    index: idx + 1,
+                    line: None,
+                },
+                entry,
+            )
+        })
+        .collect()
+}
+
 #[test]
 fn test_serde_path() {
     let paths = [Path {
@@ -47,7 +64,7 @@ fn test_load_shortcuts() {
         path: String::from("b"),
         description: Some(String::from("c")),
     }];
-    load_shortcuts(store.clone(), shortcuts);
+    load_shortcuts(store.clone(), positioned(shortcuts), false, &mut |_, _| {}).unwrap();
     let rs = store.list_all_shortcuts();
     assert!(rs.is_ok());
     let list = rs.unwrap();
@@ -69,7 +86,7 @@ fn test_load_shortcuts() {
             description: Some(String::from("cc")),
         },
     ];
-    load_shortcuts(store.clone(), shortcuts);
+    load_shortcuts(store.clone(), positioned(shortcuts), false, &mut |_, _| {}).unwrap();
     let rs = store.list_all_shortcuts();
     assert!(rs.is_ok());
     let list = rs.unwrap();
@@ -82,3 +99,360 @@ fn test_load_shortcuts() {
     assert_eq!(shortcut_x.path, "y");
     assert_eq!(shortcut_x.description, Some(String::from("z")));
 }
+
+#[test]
+fn test_load_shortcuts_summary() {
+    use crate::store::Store;
+    let store = Store::setup_test_store();
+    store.upsert_shortcut("a", "/a", None).unwrap();
+
+    let shortcuts = vec![
+        Shortcut {
+            name: String::from("a"),
+            path: String::from("/a"),
+            description: None,
+        },
+        Shortcut {
+            name: String::from("b"),
+            path: String::from("/b"),
+            description: None,
+        },
+        Shortcut {
+            name: String::new(),
+            path: String::from("/c"),
+            description: None,
+        },
+        Shortcut {
+            name: String::from("d"),
+            path: String::from("me@:/d"),
+            description: None,
+        },
+    ];
+    let summary =
+        load_shortcuts(store.clone(), positioned(shortcuts), false, &mut |_, _| {}).unwrap();
+    assert_eq!((summary.imported, summary.duplicates), (1, 1));
+    assert_eq!(
+        summary
+            .rejected
+            .iter()
+            .map(|rejected| rejected.position.index)
+            .collect::<Vec<_>>(),
+        vec![3, 4]
+    );
+    assert_eq!(store.list_all_shortcuts().unwrap().len(), 2);
+}
+
+#[test]
+fn test_load_shortcuts_replaced_rolled_back() {
+    use crate::store::Store;
+    let store = Store::setup_test_store();
+    store.upsert_shortcut("a", "/old", None).unwrap();
+
+    let shortcuts = vec![
+        Shortcut {
+            name: String::from("a"),
+            path: String::from("/new"),
+            description: None,
+        },
+        Shortcut {
+            name: String::from("b"),
+            path: String::from("/b"),
+            description: None,
+        },
+    ];
+    let summary =
+        load_shortcuts(store.clone(), positioned(shortcuts), true, &mut |_, _| {}).unwrap();
+    assert_eq!(summary.imported, 2);
+    assert_eq!(
+        summary.replaced,
+        vec![(String::from("a"), String::from("/old"))]
+    );
+    assert_eq!(store.find_shortcut("a").unwrap().path, "/old");
+    assert!(store.find_shortcut("b").is_none());
+}
+
+#[test]
+fn test_load_paths() {
+    use crate::store::Store;
+    let store = Store::setup_test_store();
+    store.add_path_with_time("/a", 10).unwrap();
+
+    let mut paths: Vec<Path> = (0..PROGRESS_STEP as u64 * 2)
+        .map(|i| Path {
+            date: (100 + i).to_string(),
+            path: format!("/p{}", i),
+        })
+        .collect();
+    paths.push(Path {
+        date: String::from("10"),
+        path: String::from("/a"),
+    });
+    paths.push(Path {
+        date: String::from("100"),
+        path: String::from("/p0"),
+    });
+    paths.push(Path {
+        date: String::from("yesterday"),
+        path: String::from("/b"),
+    });
+    paths.push(Path {
+        date: String::from("20"),
+        path: String::new(),
+    });
+
+    let mut reports = vec![];
+    let summary = load_paths(
+        store.clone(),
+        positioned(paths),
+        false,
+        &mut |done, total| reports.push((done, total)),
+    )
+    .unwrap();
+    assert_eq!(
+        (summary.imported, summary.duplicates, summary.rejected.len()),
+        (PROGRESS_STEP * 2, 2, 2)
+    );
+    let total = PROGRESS_STEP * 2 + 4;
+    assert_eq!(
+        reports,
+        vec![(PROGRESS_STEP, total), (PROGRESS_STEP * 2, total)]
+    );
+    assert_eq!(
+        store.list_paths(0, 10000, "", false).unwrap().len(),
+        PROGRESS_STEP * 2 + 1
+    );
+}
+
+#[test]
+fn test_export_shortcuts() {
+    use crate::store::Store;
+    let store = Store::setup_test_store();
+    store.upsert_shortcut("b", "/b", None).unwrap();
+    store.upsert_shortcut("a", "/a", Some("first")).unwrap();
+
+    for format in [FileFormat::Yaml, FileFormat::Json, FileFormat::Toml] {
+        let contents = export_shortcuts(&store, format).unwrap();
+        let shortcuts: Vec<Shortcut> = format
+            .parse(&contents, SHORTCUTS_KEY)
+            .unwrap()
+            .into_iter()
+            .map(|value| serde_yaml::from_value(value).unwrap())
+            .collect();
+        assert_eq!(
+            shortcuts,
+            vec![
+                Shortcut {
+                    name: String::from("a"),
+                    path: String::from("/a"),
+                    description: Some(String::from("first")),
+                },
+                Shortcut {
+                    name: String::from("b"),
+                    path: String::from("/b"),
+                    description: None,
+                },
+            ],
+            "{:?}",
+            format
+        );
+    }
+}
+
+#[test]
+fn test_export_paths() {
+    use crate::store::Store;
+    let store = Store::setup_test_store();
+    store.add_path_with_time("/b", 20).unwrap();
+    store.add_path_with_time("/a", 10).unwrap();
+
+    let json = export_paths(&store, FileFormat::Json).unwrap();
+    let paths: Vec<Path> = serde_json::from_str(&json).unwrap();
+    assert_eq!(
+        paths
+            .iter()
+            .map(|path| (path.date.as_str(), path.path.as_str()))
+            .collect::<Vec<_>>(),
+        vec![("10", "/a"), ("20", "/b")]
+    );
+
+    // the trash is left out, the sensitive paths too unless they are revealed
+    store.add_path_with_time("/c", 30).unwrap();
+    store.set_path_sensitive("/b", true).unwrap();
+    let trashed = store.list_paths(0, 10, "/c", false).unwrap()[0].id;
+    store.delete_path_by_id(trashed).unwrap();
+    let exported = |store: &Store| -> Vec<String> {
+        let json = export_paths(store, FileFormat::Json).unwrap();
+        serde_json::from_str::<Vec<Path>>(&json)
+            .unwrap()
+            .into_iter()
+            .map(|path| path.path)
+            .collect()
+    };
+    assert_eq!(exported(&store), vec!["/a"]);
+    store.set_reveal_sensitive(true);
+    assert_eq!(exported(&store), vec!["/a", "/b"]);
+}
+
+#[test]
+fn test_file_format() {
+    assert_eq!(FileFormat::detect("shortcuts.json"), FileFormat::Json);
+    assert_eq!(
+        FileFormat::detect("/tmp/a.b/shortcuts.TOML"),
+        FileFormat::Toml
+    );
+    assert_eq!(FileFormat::detect("shortcuts.yml"), FileFormat::Yaml);
+    assert_eq!(FileFormat::detect("shortcuts"), FileFormat::Yaml);
+    assert_eq!("toml".parse::<FileFormat>(), Ok(FileFormat::Toml));
+    assert!("xml".parse::<FileFormat>().is_err());
+}
+
+#[test]
+fn test_entry_lines() {
+    assert_eq!(
+        Yaml.entry_lines("# shortcuts\n- name: a\n  path: /a\n-\n  name: b\n"),
+        vec![2, 4]
+    );
+    assert!(Yaml.entry_lines("[{name: a, path: /a}]").is_empty());
+    assert_eq!(
+        Toml.entry_lines("[[shortcuts]]\nname = \"a\"\n\n[[shortcuts]]\nname = \"b\"\n"),
+        vec![1, 4]
+    );
+}
+
+#[test]
+fn test_read_entries() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("shortcuts.yaml");
+    fs::write(
+        &file,
+        "- name: a\n  path: /a\n- name: b\n  pth: /b\n- name: c\n  path: /c\n",
+    )
+    .unwrap();
+
+    let (entries, rejected) = read_entries::<Shortcut>(
+        file.to_str().unwrap(),
+        SHORTCUTS_KEY,
+        &ImportOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(
+        entries
+            .iter()
+            .map(|(_, entry)| &entry.name)
+            .collect::<Vec<_>>(),
+        vec!["a", "c"]
+    );
+    assert_eq!(
+        entries[1].0,
+        Position {
+            index: 3,
+            line: Some(5)
+        }
+    );
+    assert_eq!(rejected.len(), 1);
+    assert_eq!(
+        rejected[0].position,
+        Position {
+            index: 2,
+            line: Some(3)
+        }
+    );
+    assert!(rejected[0].reason.contains("pth"));
+
+    fs::write(&file, "- name: a\n  path: [/a\n").unwrap();
+    assert!(
+        read_entries::<Shortcut>(
+            file.to_str().unwrap(),
+            SHORTCUTS_KEY,
+            &ImportOptions::default()
+        )
+        .unwrap_err()
+        .contains("line")
+    );
+}
+
+#[test]
+fn test_read_entries_json_toml() {
+    let dir = tempfile::tempdir().unwrap();
+    let json = dir.path().join("shortcuts.json");
+    fs::write(&json, r#"[{"name": "a", "path": "/a"}, {"name": "b"}]"#).unwrap();
+    let (entries, rejected) = read_entries::<Shortcut>(
+        json.to_str().unwrap(),
+        SHORTCUTS_KEY,
+        &ImportOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(rejected[0].position.index, 2);
+
+    let toml = dir.path().join("shortcuts.toml");
+    fs::write(
+        &toml,
+        "[[shortcuts]]\nname = \"a\"\npath = \"/a\"\n\n[[shortcuts]]\nname = \"b\"\npath = \"/b\"\ndescription = \"B\"\n",
+    )
+    .unwrap();
+    let (entries, rejected) = read_entries::<Shortcut>(
+        toml.to_str().unwrap(),
+        SHORTCUTS_KEY,
+        &ImportOptions::default(),
+    )
+    .unwrap();
+    assert!(rejected.is_empty());
+    assert_eq!(entries[1].0.line, Some(5));
+    assert_eq!(entries[1].1.description, Some(String::from("B")));
+
+    // the format given in the options wins over the extension
+    let options = ImportOptions {
+        format: Some(FileFormat::Yaml),
+        ..Default::default()
+    };
+    assert!(read_entries::<Shortcut>(toml.to_str().unwrap(), SHORTCUTS_KEY, &options).is_err());
+}
+
+#[test]
+fn test_load_shortcuts_rejects_duplicates() {
+    use crate::store::Store;
+    let store = Store::setup_test_store();
+    let shortcuts = vec![
+        Shortcut {
+            name: String::from("a"),
+            path: String::from("/a"),
+            description: None,
+        },
+        Shortcut {
+            name: String::from("a"),
+            path: String::from("/other"),
+            description: None,
+        },
+    ];
+
+    let summary =
+        load_shortcuts(store.clone(), positioned(shortcuts), true, &mut |_, _| {}).unwrap();
+    assert_eq!(summary.imported, 1);
+    assert_eq!(
+        summary.rejected[0].reason,
+        "the shortcut 'a' is already defined by entry 1"
+    );
+    // nothing is imported when only validating
+    assert!(store.list_all_shortcuts().unwrap().is_empty());
+}
+
+#[test]
+fn test_write_rejects() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("rejects.yaml");
+    let rejected = vec![Rejected {
+        position: Position {
+            index: 2,
+            line: Some(3),
+        },
+        reason: String::from("the path is empty"),
+        entry: serde_yaml::from_str("{date: '1', path: ''}").unwrap(),
+    }];
+
+    write_rejects(&rejected, &file).unwrap();
+    let contents = fs::read_to_string(&file).unwrap();
+    assert!(contents.starts_with("# entry 2 (line 3): the path is empty\n- "));
+    let entries: Vec<Path> = serde_yaml::from_str(&contents).unwrap();
+    assert_eq!(entries[0].date, "1");
+}
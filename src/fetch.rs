@@ -0,0 +1,68 @@
+use std::{fs, path::Path, time::Duration};
+
+use sha2::{Digest, Sha256};
+use tracing::{debug, info};
+
+#[cfg(test)]
+#[path = "fetch_tests.rs"]
+mod fetch_tests;
+
+/// The prefix of the files downloaded rather than read from the disk
+const HTTPS_PREFIX: &str = "https://";
+
+/// The time allowed to download a file
+const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Whether `location` is the URL of a file to download, rather than a local path.
+pub fn is_url(location: &str) -> bool { location.starts_with(HTTPS_PREFIX) }
+
+/// The SHA-256 of `contents`, in lower case hexadecimal.
+pub fn sha256_hex(contents: &[u8]) -> String { format!("{:x}", Sha256::digest(contents)) }
+
+/// Check that the SHA-256 of `contents` is `expected`, in hexadecimal.
+fn verify_sha256(location: &str, contents: &str, expected: &str) -> Result<(), String> {
+    let actual = sha256_hex(contents.as_bytes());
+    if !actual.eq_ignore_ascii_case(expected.trim()) {
+        return Err(format!(
+            "The SHA-256 of {} is {}, expected {}",
+            location, actual, expected
+        ));
+    }
+    Ok(())
+}
+
+/// Download a text file over https.
+fn download(url: &str) -> Result<String, String> {
+    info!("Downloading {}", url);
+    let agent = ureq::AgentBuilder::new().timeout(DOWNLOAD_TIMEOUT).build();
+    agent
+        .get(url)
+        .call()
+        .map_err(|e| format!("Failed to download {}: {}", url, e))?
+        .into_string()
+        .map_err(|e| format!("Failed to download {}: {}", url, e))
+}
+
+/// Read the contents of a file, `location` being a local path or an https:// URL.
+/// When `sha256` is set, the contents are only returned if their SHA-256 is this one.
+///
+/// ### Returns
+/// The contents of the file, or an error message if it cannot be read or its checksum does
+/// not match.
+pub fn read_location(location: &str, sha256: Option<&str>) -> Result<String, String> {
+    debug!("read_location {} sha256={:?}", location, sha256);
+    let contents = if is_url(location) {
+        download(location)?
+    } else {
+        let path = Path::new(location);
+        if !path.exists() {
+            return Err(format!("File {} does not exist", path.display()));
+        }
+        fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read file {}: {}", path.display(), e))?
+    };
+    if let Some(expected) = sha256 {
+        verify_sha256(location, &contents, expected)?;
+    }
+    Ok(contents)
+}
@@ -0,0 +1,40 @@
+use super::*;
+
+#[test]
+fn test_is_url() {
+    assert!(is_url("https://example.com/shortcuts.yaml"));
+    assert!(!is_url("http://example.com/shortcuts.yaml"));
+    assert!(!is_url("/home/me/shortcuts.yaml"));
+}
+
+#[test]
+fn test_sha256_hex() {
+    assert_eq!(
+        sha256_hex(b"abc"),
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+}
+
+#[test]
+fn test_read_location() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("shortcuts.yaml");
+    fs::write(&file, "abc").unwrap();
+    let location = file.to_str().unwrap();
+
+    assert_eq!(read_location(location, None).unwrap(), "abc");
+    assert_eq!(
+        read_location(
+            location,
+            Some("BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD")
+        )
+        .unwrap(),
+        "abc"
+    );
+    assert!(
+        read_location(location, Some("0000"))
+            .unwrap_err()
+            .starts_with("The SHA-256 of")
+    );
+    assert!(read_location("/nonexistent.yaml", None).is_err());
+}
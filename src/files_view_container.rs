@@ -0,0 +1,149 @@
+use std::{
+    rc::Rc,
+    sync::{Arc, Mutex},
+};
+
+use crossterm::event::KeyEvent;
+use ratatui::layout::{Constraint, Layout, Rect};
+use tracing::debug;
+
+use crate::{
+    config::Config,
+    config_button::ConfigButton,
+    list_indicator_view::ListIndicatorView,
+    model::{CountFunction, ListFunction},
+    search_text_view::{SearchTextState, SearchTextView},
+    store::{RecentFile, SortColumn, Store},
+    tableview::{
+        ColumnConstraintsFn, ColumnNamesFn, DeleteFn, DetailsFn, RowifyFn, TableView,
+        TableViewParams, TableViewState,
+    },
+    tui::{EventCaptured, ManagerAction, View, ViewBuilder, ViewManager},
+};
+
+const FILES_VIEW_ID: u16 = 0;
+const SEARCH_TEXT_VIEW_1: u16 = 1;
+const CONFIGURATION_VIEW: u16 = 2;
+const LIST_INDICATOR_VIEW: u16 = 3;
+
+/// The parts of the files view, given to `FilesViewContainer::builder`, most of
+/// them making its table
+pub struct FilesViewParams {
+    pub column_names_fn: ColumnNamesFn,
+    pub column_constraints_fn: ColumnConstraintsFn,
+    pub list_fn: Box<ListFunction<RecentFile>>,
+    pub count_fn: Box<CountFunction>,
+    pub rowify: RowifyFn<RecentFile>,
+    pub config: Arc<Mutex<Config>>,
+    pub view_state: Arc<Mutex<TableViewState>>,
+    pub delete_fn: DeleteFn<RecentFile>,
+    pub details_fn: DetailsFn<RecentFile>,
+    /// The state of the search of the view
+    pub search_text_state: Arc<Mutex<SearchTextState>>,
+    pub store: Store,
+}
+
+/// The view of the files opened recently, recorded by `cdir add-file`
+pub struct FilesViewContainer {}
+
+impl FilesViewContainer {
+    /// Create a ViewBuilder for the files view, made of `params`.
+    pub fn builder(vm: Rc<ViewManager>, params: FilesViewParams) -> ViewBuilder {
+        let FilesViewParams {
+            column_names_fn,
+            column_constraints_fn,
+            list_fn,
+            count_fn,
+            rowify,
+            config,
+            view_state,
+            delete_fn,
+            details_fn,
+            search_text_state,
+            store,
+        } = params;
+        ViewBuilder::from(Box::new(Self {}))
+            .child(
+                FILES_VIEW_ID,
+                TableView::builder(
+                    vm.clone(),
+                    TableViewParams {
+                        objects_type: "file".to_string(),
+                        column_names_fn,
+                        column_constraints_fn,
+                        list_fn,
+                        count_fn,
+                        rowify,
+                        stringify: |file: &RecentFile| file.path.clone(),
+                        config: config.clone(),
+                        view_state,
+                        delete_fn,
+                        restore_fn: None,
+                        editor_modal_view_builder: None,
+                        relocate_modal_view_builder: None,
+                        find_focus_fn: Box::new(|_| 0),
+                        details_fn,
+                        shortcut_name_fn: Box::new(|_| None),
+                        sort_columns: vec![("date", SortColumn::Date), ("file", SortColumn::Path)],
+                        quick_select: false,
+                        section_fn: None,
+                        subtitle_fn: None,
+                        popover_fn: None,
+                        warm_start: None,
+                    },
+                )
+                .with_publish_events(true),
+            )
+            .child(
+                SEARCH_TEXT_VIEW_1,
+                SearchTextView::builder(
+                    vm.clone(),
+                    config.clone(),
+                    search_text_state,
+                    store,
+                    "file".to_string(),
+                ),
+            )
+            .child(
+                CONFIGURATION_VIEW,
+                ConfigButton::builder(vm.clone(), config.clone()),
+            )
+            .child(
+                LIST_INDICATOR_VIEW,
+                ListIndicatorView::builder(vm, config, "file".to_string()),
+            )
+    }
+}
+
+impl View for FilesViewContainer {
+    fn capture_focus(&self) -> bool { true }
+    fn broadcast_keyboard_events(&self) -> bool { true }
+    fn resize(&mut self, area: Rect) -> Vec<(u16, Rect)> {
+        let vertical = Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).spacing(0);
+        let [main, bottom] = vertical.areas(area);
+
+        let horizontal = Layout::horizontal([
+            Constraint::Fill(1),
+            Constraint::Length(14),
+            Constraint::Length(1),
+            Constraint::Length(12),
+        ])
+        .spacing(0);
+        let [search_text_area, list_indicator_rect, _, config_rect] = horizontal.areas(bottom);
+
+        vec![
+            (FILES_VIEW_ID, main),
+            (SEARCH_TEXT_VIEW_1, search_text_area),
+            (LIST_INDICATOR_VIEW, list_indicator_rect),
+            (CONFIGURATION_VIEW, config_rect),
+        ]
+    }
+    fn draw(&mut self, _frame: &mut ratatui::Frame, area: ratatui::prelude::Rect, active: bool) {
+        // nothing to draw
+        debug!("draw area='{}' active='{}", area, active);
+    }
+
+    fn handle_key_event(&mut self, _key_event: KeyEvent) -> (EventCaptured, ManagerAction) {
+        (EventCaptured::No, ManagerAction::new(false))
+    }
+}
@@ -1,28 +1,45 @@
 use std::{
+    collections::{HashMap, HashSet},
     env,
     rc::Rc,
-    sync::{Arc, Mutex},
+    sync::{Arc, Mutex, Once},
+    time::Duration,
 };
 
-use log::debug;
 use ratatui::{
-    layout::Constraint,
-    style::{Modifier, Style},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::Row,
 };
+use tracing::{Instrument, debug, error, info, info_span};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 use crate::{
-    config::Config,
+    command_palette::{self, CommandPalette, EMPTY_TRASH_EVENT, PaletteCommand},
+    config::{Config, Icons, PathDisplay, PathShortening},
     config_view::ConfigView,
+    details_view::{ACTIVITY_WEEKS, Details},
+    dir_size::{self, DirSizeCache},
+    files_view_container::{FilesViewContainer, FilesViewParams},
     help::Help,
-    history_view_container::HistoryViewContainer,
+    history_view_container::{HistoryViewContainer, HistoryViewParams},
+    hooks::{self, Hook},
+    icons::{self, IconKind},
+    keybindings::{self, Action, Scope},
+    log_view::LogView,
+    logging,
+    menu_bar::{self, MenuBar},
+    path_picker::PathPicker,
+    project_type::ProjectType,
+    remote::RemotePath,
     search_text_view::SearchTextState,
     shortcut_editor::ShortcutEditor,
-    shortcut_view_container::ShortcutViewContainer,
-    store::{self, Path, Shortcut, Store},
+    shortcut_view_container::{ShortcutViewContainer, ShortcutViewParams},
+    status_bar::{StatusBar, StatusBarState, StatusBarView},
+    store::{self, Filter, GuiSession, Path, RecentFile, Shortcut, SortOrder, Store, VisitCounts},
+    suggestions_view::{SuggestionsState, SuggestionsView},
     tableview::{RowifyFn, TableViewState},
-    tui::{ViewBuilder, ViewManager},
+    theme::ThemeStyles,
+    tui::{Notification, ViewBuilder, ViewManager, notify},
 };
 
 #[cfg(test)]
@@ -31,12 +48,129 @@ mod gui_tests;
 
 const HISTORY_VIEW_CONTAINER: u16 = 0;
 const SHORTCUT_VIEW_ID: u16 = 1;
+const FILES_VIEW_ID: u16 = 2;
+
+/// The objects type of each top level view, by view id
+const TOP_LEVEL_VIEW_OBJECTS_TYPES: [&str; 3] = ["path", "shortcut", "file"];
+
+/// The scope of the key bindings of each top level view, by view id, the files view having
+/// only the bindings of the tables
+const TOP_LEVEL_VIEW_SCOPES: [Scope; 3] = [Scope::History, Scope::Shortcuts, Scope::Tables];
+
+/// The actions hinted by the status bar for each top level view, by view id
+const TOP_LEVEL_VIEW_HINTS: [&[(Action, &str)]; 3] = [
+    &[
+        (Action::Choose, "choose"),
+        (Action::Delete, "delete"),
+        (Action::ToggleFuzzy, "fuzzy"),
+        (Action::ToggleTrash, "trash"),
+        (Action::Help, "help"),
+    ],
+    &[
+        (Action::Choose, "choose"),
+        (Action::Edit, "edit"),
+        (Action::Delete, "delete"),
+        (Action::SuggestShortcuts, "suggest"),
+        (Action::Help, "help"),
+    ],
+    &[
+        (Action::Choose, "open"),
+        (Action::Delete, "forget"),
+        (Action::ToggleFuzzy, "fuzzy"),
+        (Action::Help, "help"),
+    ],
+];
+
+/// The actions hinted by the status bar in select only mode
+const SELECT_ONLY_HINTS: &[(Action, &str)] = &[
+    (Action::Choose, "choose"),
+    (Action::ToggleFuzzy, "fuzzy"),
+    (Action::Help, "help"),
+];
+
+/// The mark ending an abbreviated segment of a path
+const ABBREVIATION_MARK: &str = "…";
+
+/// The separator of the segments of a path rendered as a breadcrumb
+const BREADCRUMB_SEPARATOR: &str = " › ";
+
+/// The filled and the empty parts of the visit frequency bars
+const HEAT_BAR_FILLED: char = '█';
+const HEAT_BAR_EMPTY: char = '░';
+
+/// The mark before the name of a shortcut whose directory vanished
+const BROKEN_SHORTCUT_MARK: &str = "⚠ ";
+/// The style of the path of a shortcut whose directory vanished
+const BROKEN_SHORTCUT_STYLE: Style = Style::new()
+    .fg(Color::Red)
+    .add_modifier(Modifier::CROSSED_OUT);
+
+/// The view the GUI opens on
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub(crate) enum StartView {
+    History,
+    Shortcuts,
+    /// The history view, listing only the smart suggestions for the current directory
+    Suggestions,
+    /// The files opened recently, shown even when the `files_view` parameter is not set
+    Files,
+}
+
+impl StartView {
+    /// The id of the top level view showing this view
+    fn view_id(&self) -> u16 {
+        match self {
+            StartView::History | StartView::Suggestions => HISTORY_VIEW_CONTAINER,
+            StartView::Shortcuts => SHORTCUT_VIEW_ID,
+            StartView::Files => FILES_VIEW_ID,
+        }
+    }
+}
+
+/// The options the GUI is launched with
+#[derive(Debug, Default)]
+pub(crate) struct GuiOptions {
+    /// The text the search field is pre-populated with
+    pub(crate) query: Option<String>,
+    /// Whether the query is searched with a fuzzy match
+    pub(crate) fuzzy: bool,
+    /// Whether the only path matching the query is returned without displaying the GUI
+    pub(crate) auto_select: bool,
+    /// The tmux pane the GUI runs in, allowing to list only the paths visited from it
+    pub(crate) tmux_pane: Option<String>,
+    /// The view to open on, instead of the one of the restored session
+    pub(crate) view: Option<StartView>,
+    /// Whether only the view opened on is shown, its entries being chosen but not changed
+    pub(crate) select_only: bool,
+    /// Whether the GUI starts without the session and the first page of the previous run, e.g.
+    /// when restoring them crashes it
+    pub(crate) safe_mode: bool,
+}
+
+/// The path chosen in the GUI, with the search it was chosen from
+#[derive(Debug, PartialEq)]
+pub(crate) struct Selection {
+    pub(crate) path: String,
+    pub(crate) search: String,
+    pub(crate) fuzzy: bool,
+    /// Whether the path is a recent file, to be opened rather than changed to
+    pub(crate) file: bool,
+}
 
 /// The main application structure
 pub(crate) struct Gui {
     table_view_state: Arc<Mutex<TableViewState>>,
     history_view_container: Option<ViewBuilder>,
     shortcut_view_container: Option<ViewBuilder>,
+    /// The recent files view, only built when it is shown
+    files_view_container: Option<ViewBuilder>,
+    search_text_state: Arc<Mutex<SearchTextState>>,
+    store: Store,
+    config: Arc<Mutex<Config>>,
+    restore_session: bool,
+    /// The top level view shown first, the first one when None
+    initial_view_idx: Option<usize>,
+    select_only: bool,
 }
 
 // Blends two colors with the given weight (0.0 to 1.0)
@@ -62,6 +196,16 @@ pub(crate) struct Gui {
 // }
 
 impl Gui {
+    /// The notification of a failed write to the database, `action` being e.g. `delete`
+    pub(crate) fn failure_notification(action: &str, e: &rusqlite::Error) -> Notification {
+        error!("Failed to {}: {}", action, e);
+        if store::is_busy(e) {
+            Notification::warning(format!("database locked, try to {} again", action))
+        } else {
+            Notification::error(format!("failed to {}: {}", action, e))
+        }
+    }
+
     /// Return a Line with where HOME is replaced by '~'
     pub(crate) fn reduce_path(path: String, size: u16, home_tild_style: Style) -> Line<'static> {
         if size == 0 {
@@ -82,15 +226,139 @@ impl Gui {
         }
     }
 
+    /// Return a Line showing a remote `[user@]host:/path` entry: the `[user@]host:` prefix in
+    /// italics, then the path reduced to the remaining size.
+    fn reduce_remote_path(remote: &RemotePath, size: u16, styles: &ThemeStyles) -> Line<'static> {
+        let target = format!("{}:", remote.target);
+        let target_width = target.width();
+        if target_width >= size as usize {
+            return Self::reduce_string(&format!("{}{}", target, remote.path), size as usize);
+        }
+        let mut line = Self::reduce_string(&remote.path, size as usize - target_width);
+        line.spans.insert(
+            0,
+            Span::from(target).style(styles.description_style.add_modifier(Modifier::ITALIC)),
+        );
+        line
+    }
+
+    /// Return a Line showing a path reduced to `size`, remote entries being rendered distinctly
+    fn reduce_any_path(path: &str, remote: bool, size: u16, config: &Config) -> Line<'static> {
+        match RemotePath::parse(path) {
+            Some(remote_path) if remote && size > 0 => {
+                Self::reduce_remote_path(&remote_path, size, &config.styles)
+            }
+            _ => Self::reduce_local_path(config, path, size),
+        }
+    }
+
+    /// Return a Line showing a local path reduced to `size` as configured by `path_shortening`
+    pub(crate) fn reduce_local_path(config: &Config, path: &str, size: u16) -> Line<'static> {
+        let home_tilde_style = config.styles.home_tilde_style;
+        if config.path_shortening == PathShortening::Middle
+            && let Some(line) = Self::abbreviate_path(path, size, home_tilde_style)
+        {
+            return line;
+        }
+        Self::reduce_path(path.to_string(), size, home_tilde_style)
+    }
+
+    /// Shorten a path too long for `size` by abbreviating its middle segments to their first
+    /// letter, from the first one, e.g. `~/w…/api/src`, then by eliding them all, e.g.
+    /// `~/…/src`. The last segment is never cut: None is returned when the path fits as is or
+    /// when its last segment does not fit, the path being then reduced by `reduce_path`.
+    pub(crate) fn abbreviate_path(
+        path: &str,
+        size: u16,
+        home_tilde_style: Style,
+    ) -> Option<Line<'static>> {
+        let home = env::var("HOME").ok().filter(|home| !home.is_empty());
+        let (head, rest) = match home {
+            Some(home)
+                if path
+                    .strip_prefix(&home)
+                    .is_some_and(|rest| rest.starts_with('/')) =>
+            {
+                ("~", &path[home.len()..])
+            }
+            _ => ("", path),
+        };
+        let lead = if rest.starts_with('/') { "/" } else { "" };
+        let mut segments: Vec<String> = rest
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(String::from)
+            .collect();
+        let width = |segments: &[String]| head.width() + lead.len() + segments.join("/").width();
+        let size = size as usize;
+        if segments.len() < 2 || width(&segments) <= size {
+            return None;
+        }
+
+        let line = |segments: &[String]| {
+            let tail = Span::from(format!("{}{}", lead, segments.join("/")));
+            match head {
+                "" => Line::from(tail),
+                _ => Span::from(head).style(home_tilde_style) + tail,
+            }
+        };
+        let last = segments.len() - 1;
+        for idx in 0..last {
+            let first = segments[idx].chars().next().unwrap_or_default();
+            let abbreviation = format!("{}{}", first, ABBREVIATION_MARK);
+            // an abbreviation not shorter is not worth it
+            if abbreviation.width() < segments[idx].width() {
+                segments[idx] = abbreviation;
+                if width(&segments) <= size {
+                    return Some(line(&segments));
+                }
+            }
+        }
+        let elided = [ABBREVIATION_MARK.to_string(), segments[last].clone()];
+        if width(&elided) <= size {
+            return Some(line(&elided));
+        }
+        None
+    }
+
     fn reduce_string(path: &str, size: usize) -> Line<'static> {
-        if path.len() <= size {
+        if path.width() <= size {
             return Line::from(Span::from(path.to_string()));
         }
-        let remaining_size = size - 1;
-        let path_suffix = &path[path.len() - remaining_size..];
+        let path_suffix = Self::tail_within(path, size - 1);
         Line::from(Span::from(format!("*{}", path_suffix)))
     }
 
+    /// The longest end of `text` taking at most `width` columns, not starting in the middle
+    /// of a character or with a combining mark cut from its base character
+    pub(crate) fn tail_within(text: &str, width: usize) -> &str {
+        let mut start = text.len();
+        let mut taken = 0;
+        for (idx, c) in text.char_indices().rev() {
+            taken += c.width().unwrap_or(0);
+            if taken > width {
+                break;
+            }
+            start = idx;
+        }
+        let tail = &text[start..];
+        tail.trim_start_matches(|c: char| c.width() == Some(0))
+    }
+
+    /// The longest start of `text` taking at most `width` columns
+    pub(crate) fn head_within(text: &str, width: usize) -> &str {
+        let mut end = 0;
+        let mut taken = 0;
+        for (idx, c) in text.char_indices() {
+            taken += c.width().unwrap_or(0);
+            if taken > width {
+                break;
+            }
+            end = idx + c.len_utf8();
+        }
+        &text[..end]
+    }
+
     fn do_reduce_path(
         path: &String,
         home: String,
@@ -111,9 +379,8 @@ impl Gui {
 
         let path_suffix = &path[home.len() + 1..];
         let remaining_size = size as usize - 2; // for '~' and '/'
-        if path_suffix.len() > remaining_size {
-            let start_index = path_suffix.len() - remaining_size + 1;
-            let path_suffix = format!("*{}", &path_suffix[start_index..]);
+        if path_suffix.width() > remaining_size {
+            let path_suffix = format!("*{}", Self::tail_within(path_suffix, remaining_size - 1));
             return Span::from("~").style(home_tild_style)
                 + Span::from("/")
                 + Span::from(path_suffix);
@@ -189,12 +456,13 @@ impl Gui {
         shortcut: &Shortcut,
         size: u16,
     ) -> Line<'static> {
-        if shortcut.name.len() + 3 == size as usize {
+        let name_width = shortcut.name.width();
+        if name_width + 3 == size as usize {
             return Span::from("[").style(*style)
                 + Span::from(shortcut.name.clone()).style(*style)
                 + Span::from("]").style(*style)
                 + Span::from("*");
-        } else if shortcut.name.len() + 3 > size as usize {
+        } else if name_width + 3 > size as usize {
             return Line::from("*");
         }
         let mut result_path = Span::from("[").style(*style)
@@ -211,14 +479,16 @@ impl Gui {
         // We want to keep the / after the shortcut name
         result_path += Span::from("/");
 
-        let remaining_size = size as usize - (shortcut.name.len() + 3);
+        let remaining_size = size as usize - (name_width + 3);
 
         // take the suffix of the path after the shortcut path and after '/'
         let path_suffix = &path[shortcut.path.len() + 1..];
 
-        if path_suffix.len() > remaining_size {
-            let start_index = path_suffix.len() - remaining_size + 1;
-            let path_suffix = format!("*{}", &path_suffix[start_index..]);
+        if path_suffix.width() > remaining_size {
+            let path_suffix = format!(
+                "*{}",
+                Self::tail_within(path_suffix, remaining_size.saturating_sub(1))
+            );
             result_path += Span::from(path_suffix);
             return result_path;
         }
@@ -228,88 +498,342 @@ impl Gui {
         result_path
     }
 
+    /// Format the date cell of a history row
+    fn format_history_date(config: &Config, path: &Path) -> Line<'static> {
+        if path.is_in_history() {
+            Line::from(
+                Span::from(config.date_formatters.history.format(path.date))
+                    .style(config.styles.date_style),
+            )
+        } else if !path.smart_path {
+            // a directory of a shortcut never visited
+            Line::default()
+        } else {
+            Line::from(
+                Span::from("                 @ ").style(config.styles.date_style /*.bg(bgc)*/),
+            )
+        }
+    }
+
+    /// Format the path cell of a history row, using the embedded shortcut if requested
+    fn format_history_path(
+        config: &Config,
+        path: &Path,
+        size: u16,
+        display_with_shortcuts: bool,
+    ) -> Line<'static> {
+        let shortened_line = match display_with_shortcuts {
+            true => Self::shorten_path_for_path(config, path, size),
+            false => None,
+        };
+        let line = shortened_line
+            .unwrap_or_else(|| Self::reduce_any_path(&path.path, path.remote, size, config))
+            .style(config.styles.path_style);
+        if path.smart_path {
+            line.style(Style::default().add_modifier(Modifier::ITALIC)) //.bg(bgc))
+        } else {
+            line
+        }
+    }
+
+    /// Format the description cell of a history row, from the embedded shortcut
+    fn format_history_description(config: &Config, path: &Path, size: u16) -> Line<'static> {
+        path.shortcut
+            .as_ref()
+            .and_then(|s| s.description.as_ref())
+            .map(|d| {
+                let desc_str = if d.width() > size as usize {
+                    format!(
+                        "{}...",
+                        Self::head_within(d, (size as usize).saturating_sub(3))
+                    )
+                } else {
+                    d.clone()
+                };
+                Line::from(Span::from(desc_str).style(config.styles.description_style))
+            })
+            .unwrap_or_else(|| Line::from(""))
+    }
+
+    /// Format the host cell of a history row
+    fn format_history_host(config: &Config, path: &Path, size: u16) -> Line<'static> {
+        let host: String = path
+            .hostname
+            .as_deref()
+            .unwrap_or_default()
+            .chars()
+            .take(size as usize)
+            .collect();
+        Line::from(Span::from(host).style(config.styles.date_style))
+    }
+
+    /// Format the size cell of a history row, empty until the size is computed
+    fn format_history_size(
+        config: &Config,
+        path: &Path,
+        dir_sizes: &DirSizeCache,
+    ) -> Line<'static> {
+        if path.remote {
+            return Line::from("");
+        }
+        let size = dir_sizes
+            .get(&path.path)
+            .map(dir_size::format_size)
+            .unwrap_or_default();
+        Line::from(Span::from(size).style(config.styles.date_style)).right_aligned()
+    }
+
+    /// Format the visits cell of a history row: a bar as long as the visits of the path are
+    /// frequent relatively to the most visited path, followed by their count
+    fn format_history_visits(
+        config: &Config,
+        path: &Path,
+        visit_counts: &VisitCounts,
+        size: u16,
+    ) -> Line<'static> {
+        let count = visit_counts.count(&path.path);
+        if count == 0 {
+            return Line::from("");
+        }
+        let count = format!(" {}", count);
+        let bar_width = (size as usize).saturating_sub(count.len());
+        let (filled, empty) = Self::heat_bar(visit_counts.heat(&path.path), bar_width);
+        Line::from(vec![
+            Span::from(filled).style(config.styles.heat_style),
+            Span::from(empty).style(config.styles.date_style),
+            Span::from(count).style(config.styles.date_style),
+        ])
+    }
+
+    /// The filled and the empty parts of a bar of `width` characters showing `heat`, from 0 to
+    /// 1, any visited path having at least one filled character
+    fn heat_bar(heat: f64, width: usize) -> (String, String) {
+        let filled = ((heat * width as f64).round() as usize).clamp(1.min(width), width);
+        (
+            HEAT_BAR_FILLED.to_string().repeat(filled),
+            HEAT_BAR_EMPTY.to_string().repeat(width - filled),
+        )
+    }
+
+    /// Format the project type cell of a row, empty if the path is not a project
+    fn format_project_type(
+        config: &Config,
+        path: &str,
+        project_types: &HashMap<String, ProjectType>,
+    ) -> Line<'static> {
+        let name = project_types
+            .get(path)
+            .map(ProjectType::name)
+            .unwrap_or_default();
+        Line::from(Span::from(name).style(config.styles.date_style))
+    }
+
+    /// Prefix the path cell `build` makes for a width with the icon of the path, the width
+    /// being reduced by the one of the icon, if the icons are enabled. The path is rendered as
+    /// configured by `path_display`.
+    fn format_path_with_icon(
+        config: &Config,
+        path: &str,
+        remote: bool,
+        project_types: &HashMap<String, ProjectType>,
+        size: u16,
+        build: impl Fn(u16) -> Line<'static>,
+    ) -> Line<'static> {
+        if config.icons == Icons::None {
+            return Self::format_path_display(config, size, build);
+        }
+        let kind = IconKind::of(path, remote, project_types.get(path).copied());
+        let Some(icon) = icons::resolve(config.icons, kind) else {
+            return Self::format_path_display(config, size, build);
+        };
+        let line =
+            Self::format_path_display(config, size.saturating_sub(icons::width(icon)), build);
+        let mut spans = vec![Span::from(icon).style(config.styles.date_style)];
+        spans.extend(line.spans);
+        Line { spans, ..line }
+    }
+
+    /// Render the path cell `build` makes for a width as a breadcrumb when configured and it fits
+    /// in `size`, else as is, shortened by `build` if needed.
+    fn format_path_display(
+        config: &Config,
+        size: u16,
+        build: impl Fn(u16) -> Line<'static>,
+    ) -> Line<'static> {
+        if config.path_display == PathDisplay::Breadcrumb {
+            let breadcrumb = Self::breadcrumb(build(u16::MAX), &config.styles);
+            if breadcrumb.width() <= size as usize {
+                return breadcrumb;
+            }
+        }
+        build(size)
+    }
+
+    /// Replace the slashes of a path line by arrows, e.g. `~ › work › api`, the styles of the
+    /// spans being kept and the last segment being bold.
+    pub(crate) fn breadcrumb(line: Line<'static>, styles: &ThemeStyles) -> Line<'static> {
+        let mut spans: Vec<Span<'static>> = vec![];
+        let mut last_segment = None;
+        for span in &line.spans {
+            for (idx, segment) in span.content.split('/').enumerate() {
+                if idx > 0 {
+                    if spans.is_empty() {
+                        // the root of an absolute path
+                        spans.push(Span::from("/").style(span.style));
+                    }
+                    spans.push(Span::from(BREADCRUMB_SEPARATOR).style(styles.date_style));
+                }
+                if !segment.is_empty() {
+                    last_segment = Some(spans.len());
+                    spans.push(Span::from(segment.to_string()).style(span.style));
+                }
+            }
+        }
+        // a trailing slash leaves a separator without segment
+        if spans
+            .last()
+            .is_some_and(|span| span.content == BREADCRUMB_SEPARATOR)
+        {
+            spans.pop();
+        }
+        if let Some(idx) = last_segment
+            && idx < spans.len()
+        {
+            spans[idx].style = spans[idx].style.add_modifier(Modifier::BOLD);
+        }
+        Line { spans, ..line }
+    }
+
+    /// The project types of the paths of the rows, if the type column or the icons are displayed
+    fn row_project_types(
+        store: &Store,
+        paths: &[&str],
+        displayed: bool,
+    ) -> HashMap<String, ProjectType> {
+        if !displayed {
+            return HashMap::new();
+        }
+        store.project_types(paths).unwrap_or_else(|e| {
+            error!("Failed to detect the project types: {}", e);
+            HashMap::new()
+        })
+    }
+
+    /// Return the fields describing a path in full: the path, its date and its shortcut
+    pub(crate) fn path_details(config: &Config, path: &Path) -> Vec<(String, String)> {
+        let mut fields = vec![(String::from("Path"), path.path.clone())];
+        if path.is_in_history() {
+            fields.push((
+                String::from("Date"),
+                config.date_formatters.details.format(path.date),
+            ));
+        }
+        if let Some(hostname) = &path.hostname {
+            fields.push((String::from("Host"), hostname.clone()));
+        }
+        if let Some(shortcut) = &path.shortcut {
+            fields.push((
+                String::from("Shortcut"),
+                format!("{} ({})", shortcut.name, shortcut.path),
+            ));
+            if let Some(description) = &shortcut.description {
+                fields.push((String::from("Description"), description.clone()));
+            }
+        }
+        fields
+    }
+
+    /// Return the fields describing a shortcut in full
+    pub(crate) fn shortcut_details(shortcut: &Shortcut) -> Vec<(String, String)> {
+        vec![
+            (String::from("Shortcut"), shortcut.name.clone()),
+            (String::from("Path"), shortcut.path.clone()),
+            (
+                String::from("Description"),
+                shortcut.description.clone().unwrap_or_default(),
+            ),
+        ]
+    }
+
     /// Return a function that formats a row for the history view
     fn build_format_history_row_builder(
+        store: Store,
         config: Arc<Mutex<Config>>,
         table_view_state: Arc<Mutex<TableViewState>>,
+        dir_sizes: DirSizeCache,
     ) -> RowifyFn<store::Path> {
         let table_view_state = table_view_state.clone();
         Box::new(move |paths: &[Path], size: &[u16]| {
             let table_view_state = table_view_state.clone();
             let config = config.clone();
+            let dir_sizes = dir_sizes.clone();
+            let type_displayed = {
+                let config_lock = config.lock().unwrap();
+                config_lock.icons != Icons::None
+                    || config_lock
+                        .history_columns()
+                        .iter()
+                        .any(|column| column.name == "type")
+            };
+            let project_types = Self::row_project_types(
+                &store,
+                &paths.iter().map(|p| p.path.as_str()).collect::<Vec<&str>>(),
+                type_displayed,
+            );
+            let visit_counts = store.visit_counts();
             paths
                 .iter()
                 .map(move |path| {
                     let path_init = path.clone();
                     let config_lock = config.lock().unwrap();
-                    let show_description = config_lock.path_view_show_shortcut_description_column;
-
-                    // Determine column indices based on whether description column is shown
-                    let path_col_idx = 1;
-                    let desc_col_idx = 2;
-
-                    // format the date
-                    let date: Line = if !path_init.smart_path {
-                        Line::from(
-                            Span::from((config_lock.date_formater)(path_init.date))
-                                .style(config_lock.styles.date_style),
-                        )
-                    } else {
-                        Line::from(
-                            Span::from("                 @ ")
-                                .style(config_lock.styles.date_style /*.bg(bgc)*/),
-                        )
-                    };
-
-                    // format the path using the embedded shortcut
-                    let shortened_line =
-                        match table_view_state.lock().unwrap().display_with_shortcuts {
-                            true => Self::shorten_path_for_path(
+
+                    // Build the cells of the configured columns, in order
+                    let columns = config_lock.history_columns();
+                    columns
+                        .iter()
+                        .enumerate()
+                        .map(|(col_idx, column)| match column.name.as_str() {
+                            "date" => Self::format_history_date(&config_lock, &path_init),
+                            "path" => Self::format_path_with_icon(
+                                &config_lock,
+                                &path_init.path,
+                                path_init.remote,
+                                &project_types,
+                                size[col_idx],
+                                |size| {
+                                    Self::format_history_path(
+                                        &config_lock,
+                                        &path_init,
+                                        size,
+                                        table_view_state.lock().unwrap().display_with_shortcuts,
+                                    )
+                                },
+                            ),
+                            "host" => {
+                                Self::format_history_host(&config_lock, &path_init, size[col_idx])
+                            }
+                            "size" => {
+                                Self::format_history_size(&config_lock, &path_init, &dir_sizes)
+                            }
+                            "type" => Self::format_project_type(
+                                &config_lock,
+                                &path_init.path,
+                                &project_types,
+                            ),
+                            "visits" => Self::format_history_visits(
                                 &config_lock,
                                 &path_init,
-                                size[path_col_idx],
+                                &visit_counts,
+                                size[col_idx],
+                            ),
+                            _ => Self::format_history_description(
+                                &config_lock,
+                                &path_init,
+                                size[col_idx],
                             ),
-                            false => None,
-                        };
-                    let path = shortened_line
-                        .unwrap_or_else(|| {
-                            Self::reduce_path(
-                                path_init.path.clone(),
-                                size[path_col_idx],
-                                config_lock.styles.home_tilde_style,
-                            )
                         })
-                        .style(config_lock.styles.path_style);
-                    let path = if path_init.smart_path {
-                        path.style(Style::default().add_modifier(Modifier::ITALIC)) //.bg(bgc))
-                    } else {
-                        path
-                    };
-
-                    // Build the row based on whether description column is enabled
-                    if show_description {
-                        let description = path_init
-                            .shortcut
-                            .as_ref()
-                            .and_then(|s| s.description.as_ref())
-                            .map(|d| {
-                                let desc_str = if d.len() > size[desc_col_idx] as usize {
-                                    format!("{}...", &d[..size[desc_col_idx] as usize - 3])
-                                } else {
-                                    d.clone()
-                                };
-                                Line::from(
-                                    Span::from(desc_str)
-                                        .style(config_lock.styles.description_style),
-                                )
-                            })
-                            .unwrap_or_else(|| Line::from(""));
-                        vec![date, path, description]
-                    } else {
-                        vec![date, path]
-                    }
+                        .collect::<Vec<Line>>()
                 })
-                .map(Row::new)
                 .collect()
         })
     }
@@ -321,52 +845,127 @@ impl Gui {
         store: Store,
         config: Arc<Mutex<Config>>,
         search_text_state: Arc<Mutex<SearchTextState>>,
+        tmux_pane: Option<String>,
     ) {
+        let suggestions_state = Arc::new(Mutex::new(SuggestionsState::new(
+            config.lock().unwrap().suggestions_panel_ratio,
+        )));
+        let dir_sizes = DirSizeCache::new(
+            store.list_dir_sizes().unwrap_or_else(|e| {
+                error!("Failed to load the directory sizes: {}", e);
+                vec![]
+            }),
+            config.lock().unwrap().dir_size_ttl,
+            view_manager.tx(),
+        );
         self.history_view_container = Some(HistoryViewContainer::builder(
             view_manager.clone(),
-            Box::new(|config| {
-                if config.path_view_show_shortcut_description_column {
-                    vec![
-                        "date".to_string(),
-                        "path".to_string(),
-                        "description".to_string(),
-                    ]
-                } else {
-                    vec!["date".to_string(), "path".to_string()]
-                }
-            }),
-            Box::new(|config| {
-                if config.path_view_show_shortcut_description_column {
-                    vec![
-                        Constraint::Length(20),
-                        Constraint::Fill(config.path_view_path_column_weight as u16),
-                        Constraint::Fill(config.path_view_description_column_weight as u16),
-                    ]
-                } else {
-                    vec![Constraint::Length(20), Constraint::Fill(1)]
-                }
-            }),
-            {
-                let store = store.clone();
-                Box::new(move |pos, len, text, fuzzy| store.list_paths(pos, len, text, fuzzy))
-            },
-            Box::new(Gui::build_format_history_row_builder(
-                config.clone(),
-                self.table_view_state.clone(),
-            )),
-            |path: &Path| path.path.clone(),
-            config.clone(),
-            self.table_view_state.clone(),
-            {
-                let store = store.clone();
-                Box::new(move |path| {
-                    debug!("delete path: {}", path.path);
-                    store.delete_path_by_id(path.id).unwrap();
-                })
+            HistoryViewParams {
+                column_names_fn: Box::new(|config| {
+                    config
+                        .history_columns()
+                        .into_iter()
+                        .map(|c| c.name)
+                        .collect()
+                }),
+                column_constraints_fn: Box::new(|config| {
+                    config
+                        .history_columns()
+                        .iter()
+                        .map(|c| c.constraint())
+                        .collect()
+                }),
+                list_fn: {
+                    let store = store.clone();
+                    Box::new(move |pos, len, filters, sort| {
+                        store.list_paths_filtered(pos, len, filters, sort)
+                    })
+                },
+                count_fn: {
+                    let store = store.clone();
+                    Box::new(move |filters| store.count_paths_filtered(filters))
+                },
+                rowify: Box::new(Gui::build_format_history_row_builder(
+                    store.clone(),
+                    config.clone(),
+                    self.table_view_state.clone(),
+                    dir_sizes,
+                )),
+                stringify: |path: &Path| path.path.clone(),
+                config: config.clone(),
+                view_state: self.table_view_state.clone(),
+                delete_fn: {
+                    let store = store.clone();
+                    let tx = view_manager.tx();
+                    Box::new(move |path| {
+                        let (result, done) = if store.show_trash() {
+                            debug!("purge path: {}", path.path);
+                            (store.purge_path_by_id(path.id), "deleted for good")
+                        } else {
+                            debug!("delete path: {}", path.path);
+                            (store.delete_path_by_id(path.id), "moved to the trash")
+                        };
+                        notify(
+                            &tx,
+                            match result {
+                                Ok(()) => Notification::info(done),
+                                Err(e) => Gui::failure_notification("delete", &e),
+                            },
+                        );
+                    })
+                },
+                restore_fn: {
+                    let store = store.clone();
+                    let tx = view_manager.tx();
+                    Box::new(move |path| {
+                        if !store.show_trash() {
+                            return false;
+                        }
+                        debug!("restore path: {}", path.path);
+                        match store.restore_path_by_id(path.id) {
+                            Ok(()) => true,
+                            Err(e) => {
+                                notify(&tx, Gui::failure_notification("restore", &e));
+                                false
+                            }
+                        }
+                    })
+                },
+                editor_modal_view_builder: None,
+                details_fn: {
+                    let config = config.clone();
+                    let store = store.clone();
+                    Box::new(move |path: &Path| Details {
+                        fields: Gui::path_details(&config.lock().unwrap(), path),
+                        activity: store
+                            .weekly_visits(&path.path, ACTIVITY_WEEKS)
+                            .inspect_err(|e| error!("Failed to count the weekly visits: {}", e))
+                            .ok(),
+                    })
+                },
+                search_text_state,
+                suggestions_view: SuggestionsView::builder(
+                    &view_manager,
+                    store.clone(),
+                    config.clone(),
+                    suggestions_state.clone(),
+                    {
+                        let config = config.clone();
+                        let table_view_state = self.table_view_state.clone();
+                        Box::new(move |path: &Path, size: u16| {
+                            Gui::format_history_path(
+                                &config.lock().unwrap(),
+                                path,
+                                size,
+                                table_view_state.lock().unwrap().display_with_shortcuts,
+                            )
+                        })
+                    },
+                ),
+                suggestions_state,
+                store: store.clone(),
+                tmux_pane,
             },
-            //search_string,
-            None,
-            search_text_state,
         ));
     }
 
@@ -380,50 +979,105 @@ impl Gui {
         let store = store.clone();
         let config = config.clone();
         Box::new(move |shortcuts: &[Shortcut], size: &[u16]| {
+            let broken = store.broken_shortcut_ids().unwrap_or_else(|e| {
+                error!("Failed to list the broken shortcuts: {}", e);
+                HashSet::new()
+            });
+            let type_displayed = {
+                let config_lock = config.lock().unwrap();
+                config_lock.icons != Icons::None
+                    || config_lock
+                        .shortcuts_columns()
+                        .iter()
+                        .any(|column| column.name == "type")
+            };
+            let project_types = Self::row_project_types(
+                &store,
+                &shortcuts
+                    .iter()
+                    .map(|s| s.path.as_str())
+                    .collect::<Vec<&str>>(),
+                type_displayed,
+            );
             shortcuts
                 .iter()
                 .map(|shortcut| {
                     // format the path
                     let shortcut = shortcut.clone();
                     let config_lock = config.lock().unwrap();
-                    let shortened_line =
-                        match table_view_state.lock().unwrap().display_with_shortcuts {
-                            true => {
-                                let all_shortcuts: Vec<Shortcut> =
-                                    store.list_all_shortcuts().unwrap();
-                                Self::shorten_path_for_shortcut(
-                                    &config_lock,
-                                    &all_shortcuts,
-                                    &shortcut.path,
-                                    size[1],
-                                )
-                            }
-                            false => None,
-                        };
-                    let path = shortened_line
-                        .unwrap_or_else(|| {
-                            Self::reduce_path(
-                                shortcut.path,
-                                size[1],
-                                config_lock.styles.home_tilde_style,
+                    let columns = config_lock.shortcuts_columns();
+                    let path_size = columns
+                        .iter()
+                        .position(|c| c.name == "path")
+                        .map_or(0, |i| size[i]);
+                    let is_broken = broken.contains(&shortcut.id);
+                    let path = Self::format_path_with_icon(
+                        &config_lock,
+                        &shortcut.path,
+                        shortcut.remote,
+                        &project_types,
+                        path_size,
+                        |path_size| {
+                            let shortened_line =
+                                match table_view_state.lock().unwrap().display_with_shortcuts {
+                                    true => {
+                                        let all_shortcuts: Vec<Shortcut> =
+                                            store.list_all_shortcuts().unwrap();
+                                        Self::shorten_path_for_shortcut(
+                                            &config_lock,
+                                            &all_shortcuts,
+                                            &shortcut.path,
+                                            path_size,
+                                        )
+                                    }
+                                    false => None,
+                                };
+                            shortened_line
+                                .unwrap_or_else(|| {
+                                    Self::reduce_any_path(
+                                        &shortcut.path,
+                                        shortcut.remote,
+                                        path_size,
+                                        &config_lock,
+                                    )
+                                })
+                                .style(if is_broken {
+                                    BROKEN_SHORTCUT_STYLE
+                                } else {
+                                    config_lock.styles.path_style
+                                })
+                        },
+                    );
+
+                    // Build the cells of the configured columns, in order
+                    let cells: Vec<Line> = columns
+                        .iter()
+                        .map(|column| match column.name.as_str() {
+                            "shortcut" if is_broken => Line::from(vec![
+                                Span::from(BROKEN_SHORTCUT_MARK).style(Style::new().fg(Color::Red)),
+                                Span::from(shortcut.name.clone())
+                                    .style(config_lock.styles.shortcut_name_style),
+                            ]),
+                            "shortcut" => Line::from(
+                                Span::from(shortcut.name.clone())
+                                    .style(config_lock.styles.shortcut_name_style),
+                            ),
+                            "path" => path.clone(),
+                            "type" => Self::format_project_type(
+                                &config_lock,
+                                &shortcut.path,
+                                &project_types,
+                            ),
+                            _ => Line::from(
+                                shortcut
+                                    .description
+                                    .clone()
+                                    .unwrap_or_else(|| "".to_string()),
                             )
+                            .style(config_lock.styles.description_style),
                         })
-                        .style(config_lock.styles.path_style);
-
-                    Row::new(vec![
-                        Line::from(
-                            Span::from(shortcut.name.clone())
-                                .style(config_lock.styles.shortcut_name_style),
-                        ),
-                        path,
-                        Line::from(
-                            shortcut
-                                .description
-                                .clone()
-                                .unwrap_or_else(|| "".to_string()),
-                        )
-                        .style(config_lock.styles.description_style),
-                    ])
+                        .collect();
+                    cells
                 })
                 .collect()
         })
@@ -439,68 +1093,279 @@ impl Gui {
     ) {
         let modal_store = store.clone();
         let modal_config = config.clone();
+        let modal_tx = view_manager.tx();
         let editor_modal_view_builder = Box::new(move |shortcut: Shortcut| {
             Box::new(ShortcutEditor::builder(
                 modal_store.clone(),
                 modal_config.clone(),
+                modal_tx.clone(),
                 shortcut.clone(),
             ))
         });
+        let relocate_store = store.clone();
+        let relocate_tx = view_manager.tx();
+        let styles = config.lock().unwrap().styles.clone();
+        let relocate_modal_view_builder = Box::new(move |shortcut: Shortcut| {
+            // start from the closest ancestor of the vanished path still existing
+            let start = std::path::Path::new(&shortcut.path)
+                .ancestors()
+                .find(|ancestor| ancestor.is_dir())
+                .and_then(|ancestor| ancestor.to_str())
+                .map(|ancestor| format!("{}/", ancestor.trim_end_matches('/')))
+                .unwrap_or_else(|| String::from("/"));
+            let store = relocate_store.clone();
+            let tx = relocate_tx.clone();
+            Box::new(PathPicker::builder(
+                format!("Relocate {}", shortcut.name),
+                &start,
+                styles.clone(),
+                Box::new(move |path| {
+                    notify(
+                        &tx,
+                        match store.relocate_shortcut(shortcut.id, path) {
+                            Ok(()) => Notification::info(format!("{} relocated", shortcut.name)),
+                            Err(e) => Gui::failure_notification("relocate the shortcut", &e),
+                        },
+                    );
+                }),
+            ))
+        });
 
         self.shortcut_view_container = Some(ShortcutViewContainer::builder(
             view_manager.clone(),
-            Box::new(|_config| {
-                vec![
-                    "shortcut".to_string(),
-                    "path".to_string(),
-                    "description".to_string(),
-                ]
-            }),
-            Box::new(|_config| {
-                vec![
-                    Constraint::Length(20),
-                    Constraint::Fill(1),
-                    Constraint::Fill(1),
-                ]
-            }),
-            {
-                let store = store.clone();
-                Box::new(move |pos, len, text, fuzzy| store.list_shortcuts(pos, len, text, fuzzy))
+            ShortcutViewParams {
+                column_names_fn: Box::new(|config| {
+                    config
+                        .shortcuts_columns()
+                        .into_iter()
+                        .map(|c| c.name)
+                        .collect()
+                }),
+                column_constraints_fn: Box::new(|config| {
+                    config
+                        .shortcuts_columns()
+                        .iter()
+                        .map(|c| c.constraint())
+                        .collect()
+                }),
+                list_fn: {
+                    let store = store.clone();
+                    Box::new(move |pos, len, filters, sort| {
+                        store.list_shortcuts_filtered(pos, len, filters, sort)
+                    })
+                },
+                count_fn: {
+                    let store = store.clone();
+                    Box::new(move |filters| store.count_shortcuts_filtered(filters))
+                },
+                rowify: Box::new(Gui::build_format_shortcut_row_builder(
+                    store.clone(),
+                    config.clone(),
+                    self.table_view_state.clone(),
+                )),
+                stringify: |shortcut: &store::Shortcut| shortcut.path.clone(),
+                config: config.clone(),
+                view_state: self.table_view_state.clone(),
+                delete_fn: {
+                    let store = store.clone();
+                    let config = config.clone();
+                    let tx = view_manager.tx();
+                    Box::new(move |shortcut| {
+                        debug!("delete shortcut: {}", shortcut.path);
+                        if let Err(e) = store.delete_shortcut_by_id(shortcut.id) {
+                            notify(&tx, Gui::failure_notification("delete", &e));
+                            return;
+                        }
+                        notify(
+                            &tx,
+                            Notification::info(format!("{} deleted", shortcut.name)),
+                        );
+                        hooks::run_hook(
+                            &config,
+                            Hook::DeleteShortcut,
+                            &[
+                                ("CDIR_SHORTCUT", &shortcut.name),
+                                ("CDIR_PATH", &shortcut.path),
+                            ],
+                        );
+                    })
+                },
+                editor_modal_view_builder: Some(editor_modal_view_builder),
+                relocate_modal_view_builder: Some(relocate_modal_view_builder),
+                details_fn: Box::new(|shortcut: &Shortcut| Gui::shortcut_details(shortcut).into()),
+                search_text_state,
+                store: store.clone(),
             },
-            Box::new(Gui::build_format_shortcut_row_builder(
-                store.clone(),
-                config.clone(),
-                self.table_view_state.clone(),
-            )),
-            |shortcut: &store::Shortcut| shortcut.path.clone(),
-            config.clone(),
-            self.table_view_state.clone(),
-            {
-                let store = store.clone();
-                Box::new(move |path| {
-                    debug!("delete shortcut: {}", path.path);
-                    store.delete_shortcut_by_id(path.id).unwrap();
+        ));
+    }
+
+    /// Return a function that formats a row for the recent files view
+    fn build_format_file_row_builder(config: Arc<Mutex<Config>>) -> RowifyFn<RecentFile> {
+        Box::new(move |files: &[RecentFile], size: &[u16]| {
+            let config_lock = config.lock().unwrap();
+            let columns = config_lock.files_columns();
+            files
+                .iter()
+                .map(|file| {
+                    columns
+                        .iter()
+                        .zip(size)
+                        .map(|(column, size)| match column.name.as_str() {
+                            "date" => Line::from(
+                                Span::from(config_lock.date_formatters.history.format(file.date))
+                                    .style(config_lock.styles.date_style),
+                            ),
+                            _ => Self::reduce_local_path(&config_lock, &file.path, *size)
+                                .style(config_lock.styles.path_style),
+                        })
+                        .collect()
                 })
+                .collect()
+        })
+    }
+
+    /// Build the recent files view
+    fn build_files_view(
+        &mut self,
+        view_manager: Rc<ViewManager>,
+        store: Store,
+        config: Arc<Mutex<Config>>,
+        search_text_state: Arc<Mutex<SearchTextState>>,
+    ) {
+        self.files_view_container = Some(FilesViewContainer::builder(
+            view_manager.clone(),
+            FilesViewParams {
+                column_names_fn: Box::new(|config| {
+                    config.files_columns().into_iter().map(|c| c.name).collect()
+                }),
+                column_constraints_fn: Box::new(|config| {
+                    config
+                        .files_columns()
+                        .iter()
+                        .map(|c| c.constraint())
+                        .collect()
+                }),
+                list_fn: {
+                    let store = store.clone();
+                    Box::new(move |pos, len, filters, sort| {
+                        store.list_files_filtered(pos, len, filters, sort)
+                    })
+                },
+                count_fn: {
+                    let store = store.clone();
+                    Box::new(move |filters| store.count_files_filtered(filters))
+                },
+                rowify: Gui::build_format_file_row_builder(config.clone()),
+                config: config.clone(),
+                view_state: self.table_view_state.clone(),
+                delete_fn: {
+                    let store = store.clone();
+                    let tx = view_manager.tx();
+                    Box::new(move |file: &RecentFile| {
+                        debug!("forget file: {}", file.path);
+                        match store.delete_file_by_id(file.id) {
+                            Ok(()) => {
+                                notify(&tx, Notification::info(format!("{} forgotten", file.path)))
+                            }
+                            Err(e) => notify(&tx, Gui::failure_notification("forget", &e)),
+                        }
+                    })
+                },
+                details_fn: {
+                    let config = config.clone();
+                    Box::new(move |file: &RecentFile| {
+                        vec![
+                            (String::from("File"), file.path.clone()),
+                            (
+                                String::from("Date"),
+                                config
+                                    .lock()
+                                    .unwrap()
+                                    .date_formatters
+                                    .details
+                                    .format(file.date),
+                            ),
+                        ]
+                        .into()
+                    })
+                },
+                search_text_state,
+                store,
             },
-            //search_string,
-            Some(editor_modal_view_builder),
-            search_text_state,
         ));
     }
 
+    /// Return the path matching the initial query if it is the only one, the shortcuts or the
+    /// files being searched when opening on their view.
+    pub(crate) fn single_result(store: &Store, options: &GuiOptions) -> Option<String> {
+        let query = options.query.as_ref().filter(|q| !q.is_empty())?;
+        let paths = match options.view {
+            Some(StartView::Shortcuts) => store
+                .list_shortcuts(0, 2, query, options.fuzzy)
+                .map(|shortcuts| shortcuts.into_iter().map(|s| s.path).collect::<Vec<_>>()),
+            Some(StartView::Files) => store
+                .list_files_filtered(
+                    0,
+                    2,
+                    &[Filter::new(query, options.fuzzy)],
+                    SortOrder::Default,
+                )
+                .map(|files| files.into_iter().map(|f| f.path).collect()),
+            _ => store
+                .list_paths(0, 2, query, options.fuzzy)
+                .map(|paths| paths.into_iter().map(|p| p.path).collect()),
+        };
+        match paths {
+            Ok(paths) if paths.len() == 1 => Some(paths[0].clone()),
+            Ok(_) => None,
+            Err(e) => {
+                error!("Failed to search the paths matching '{}': {}", query, e);
+                None
+            }
+        }
+    }
+
     /// Instantiate the application GUI
-    fn new(view_manager: Rc<ViewManager>, store: store::Store, config: Arc<Mutex<Config>>) -> Gui {
+    fn new(
+        view_manager: Rc<ViewManager>,
+        store: store::Store,
+        config: Arc<Mutex<Config>>,
+        options: GuiOptions,
+    ) -> Gui {
+        let search_text_state = Arc::new(Mutex::new(SearchTextState::new(view_manager.clone())));
+        // a picker started by a script leaves the session of the user untouched
+        let restore_session = config.lock().unwrap().restore_session && !options.select_only;
+        let mut table_view_state = TableViewState::new();
+        table_view_state.select_only = options.select_only;
+        table_view_state.safe_mode = options.safe_mode;
         let mut gui = Gui {
-            table_view_state: Arc::new(Mutex::new(TableViewState::new())),
+            table_view_state: Arc::new(Mutex::new(table_view_state)),
             history_view_container: None,
             shortcut_view_container: None,
+            files_view_container: None,
+            search_text_state: search_text_state.clone(),
+            store: store.clone(),
+            config: config.clone(),
+            restore_session,
+            initial_view_idx: None,
+            select_only: options.select_only,
         };
-        let search_text_state = Arc::new(Mutex::new(SearchTextState::new(view_manager.clone())));
+        // the session left by the run is still saved, replacing the one skipped
+        if restore_session && !options.safe_mode {
+            gui.restore_session();
+        }
+        if let Some(view) = options.view {
+            gui.initial_view_idx = Some(view.view_id() as usize);
+        }
+        if let Some(query) = options.query {
+            gui.set_initial_search(query, options.fuzzy);
+        }
         gui.build_history_view(
             view_manager.clone(),
             store.clone(),
             config.clone(),
             search_text_state.clone(),
+            options.tmux_pane,
         );
         gui.build_shortcut_view(
             view_manager.clone(),
@@ -508,31 +1373,292 @@ impl Gui {
             config.clone(),
             search_text_state.clone(),
         );
+        if config.lock().unwrap().files_view || options.view == Some(StartView::Files) {
+            gui.build_files_view(
+                view_manager.clone(),
+                store.clone(),
+                config.clone(),
+                search_text_state.clone(),
+            );
+        } else if gui.initial_view_idx == Some(FILES_VIEW_ID as usize) {
+            // the session was saved on the files view, hidden since
+            gui.initial_view_idx = None;
+        }
 
         gui
     }
 
-    /// Run the application GUI loop
-    async fn run(&mut self, view_manager: Rc<ViewManager>) -> Option<String> {
-        let vb = self.history_view_container.take().unwrap();
-        view_manager.add_view(
-            HISTORY_VIEW_CONTAINER,
-            vb,
-            &[HISTORY_VIEW_CONTAINER as usize],
+    /// Restore the last saved session: the views are built from the restored state.
+    fn restore_session(&mut self) {
+        let session = match self.store.load_gui_session() {
+            Ok(Some(session)) => session,
+            Ok(None) => return,
+            Err(e) => {
+                error!("Failed to load the GUI session: {}", e);
+                return;
+            }
+        };
+        info!("restoring session {:?}", session);
+        self.search_text_state
+            .lock()
+            .unwrap()
+            .restore(&session.search, session.fuzzy_match);
+        let mut table_view_state = self.table_view_state.lock().unwrap();
+        table_view_state.initial_search = Some((session.search, session.fuzzy_match));
+        table_view_state
+            .initial_rows
+            .insert(session.view.clone(), session.selected_row);
+        self.initial_view_idx = TOP_LEVEL_VIEW_OBJECTS_TYPES
+            .iter()
+            .position(|objects_type| *objects_type == session.view);
+    }
+
+    /// Start with the results already filtered by a search.
+    /// Any restored selection is dropped, as it belongs to another search.
+    fn set_initial_search(&mut self, search_string: String, fuzzy_match: bool) {
+        info!("initial search '{}' fuzzy={}", search_string, fuzzy_match);
+        self.search_text_state
+            .lock()
+            .unwrap()
+            .restore(&search_string, fuzzy_match);
+        let mut table_view_state = self.table_view_state.lock().unwrap();
+        table_view_state.initial_search = Some((search_string, fuzzy_match));
+        table_view_state.initial_rows.clear();
+    }
+
+    /// Save the current search, view and selection, to be restored on the next launch.
+    fn save_session(&self, view_manager: &ViewManager) {
+        let Some(view) = TOP_LEVEL_VIEW_OBJECTS_TYPES.get(view_manager.top_level_view_idx()) else {
+            return;
+        };
+        let search_text_state = self.search_text_state.lock().unwrap();
+        let session = GuiSession {
+            search: search_text_state.search_string().to_string(),
+            fuzzy_match: search_text_state.fuzzy_match(),
+            view: view.to_string(),
+            selected_row: self
+                .table_view_state
+                .lock()
+                .unwrap()
+                .selected_rows
+                .get(*view)
+                .copied()
+                .unwrap_or(0),
+        };
+        if let Err(e) = self.store.save_gui_session(&session) {
+            error!("Failed to save the GUI session: {}", e);
+        }
+    }
+
+    /// Add the search made when leaving the GUI to the history of the searches of the view
+    fn record_search(&self, view_manager: &ViewManager) {
+        let Some(view) = TOP_LEVEL_VIEW_OBJECTS_TYPES.get(view_manager.top_level_view_idx()) else {
+            return;
+        };
+        let search_text_state = self.search_text_state.lock().unwrap();
+        if let Err(e) = self.store.add_search(
+            view,
+            search_text_state.search_string(),
+            search_text_state.fuzzy_match(),
+        ) {
+            error!("Failed to record the search: {}", e);
+        }
+    }
+
+    /// The status bar describing the top level views of `ids`, in this order
+    fn status_bar(&self, view_manager: Rc<ViewManager>, ids: &[u16]) -> ViewBuilder {
+        let views = ids
+            .iter()
+            .map(|id| StatusBarView {
+                objects_type: TOP_LEVEL_VIEW_OBJECTS_TYPES[*id as usize].to_string(),
+                hints: if self.select_only {
+                    SELECT_ONLY_HINTS
+                } else {
+                    TOP_LEVEL_VIEW_HINTS[*id as usize]
+                },
+            })
+            .collect();
+        let db_path = self
+            .config
+            .lock()
+            .unwrap()
+            .db_path
+            .as_ref()
+            .map(|path| path.to_string_lossy().into_owned());
+        let fuzzy_match = self.search_text_state.lock().unwrap().fuzzy_match();
+        let mut state = StatusBarState::new(views, fuzzy_match, db_path);
+        state.set_segment(
+            TOP_LEVEL_VIEW_OBJECTS_TYPES[HISTORY_VIEW_CONTAINER as usize],
+            "filters",
+            HistoryViewContainer::filters_description(&self.store),
         );
+        StatusBar::builder(view_manager, self.config.clone(), state)
+    }
+
+    /// Open the command palette on the commands of the displayed view, `ids` being the ids of
+    /// the top level views in the order they were added
+    fn set_command_palette(&self, view_manager: Rc<ViewManager>, ids: Vec<u16>) {
+        let config = self.config.clone();
+        let select_only = self.select_only;
+        let vm = view_manager.clone();
+        view_manager.set_global_command_palette(Box::new(move || {
+            let id = ids[vm.top_level_view_idx()];
+            let mut commands = command_palette::key_commands(TOP_LEVEL_VIEW_SCOPES[id as usize]);
+            if id == HISTORY_VIEW_CONTAINER && !select_only {
+                commands.push(PaletteCommand::event(
+                    "empty the trash, deleting its paths for good",
+                    EMPTY_TRASH_EVENT,
+                ));
+            }
+            let config = config.lock().unwrap();
+            CommandPalette::builder(
+                vm.tx(),
+                commands,
+                config.fuzzy.clone(),
+                config.styles.clone(),
+            )
+        }));
+    }
 
-        let vb = self.shortcut_view_container.take().unwrap();
-        view_manager.add_view(SHORTCUT_VIEW_ID, vb, &[SHORTCUT_VIEW_ID as usize]);
+    /// The menu bar listing the actions of the displayed view, `ids` being the ids of the top
+    /// level views in the order they were added
+    fn menu_bar(&self, view_manager: Rc<ViewManager>, ids: Vec<u16>) -> ViewBuilder {
+        let select_only = self.select_only;
+        let vm = view_manager.clone();
+        let menus = Box::new(move || {
+            let id = ids[vm.top_level_view_idx()];
+            let mut menus = menu_bar::menus(TOP_LEVEL_VIEW_SCOPES[id as usize]);
+            if id == HISTORY_VIEW_CONTAINER
+                && !select_only
+                && let Some(edit) = menus.iter_mut().find(|menu| menu.title == "Edit")
+            {
+                edit.items
+                    .push(PaletteCommand::event("Empty the trash", EMPTY_TRASH_EVENT));
+            }
+            menus
+        });
+        MenuBar::builder(view_manager, self.config.clone(), menus)
+    }
 
-        view_manager.event_loop().await
+    /// Run the application GUI loop
+    async fn run(&mut self, view_manager: Rc<ViewManager>) -> Option<Selection> {
+        let initial_view_id = self.initial_view_idx.unwrap_or_default() as u16;
+        let views = [
+            (HISTORY_VIEW_CONTAINER, self.history_view_container.take()),
+            (SHORTCUT_VIEW_ID, self.shortcut_view_container.take()),
+            (FILES_VIEW_ID, self.files_view_container.take()),
+        ];
+        let mut ids = vec![];
+        for (id, vb) in views {
+            // in select only mode, the other views cannot be switched to
+            if self.select_only && id != initial_view_id {
+                continue;
+            }
+            let Some(vb) = vb else {
+                continue;
+            };
+            view_manager.add_view(id, vb, &[id as usize]);
+            ids.push(id);
+        }
+        if self.config.lock().unwrap().status_bar {
+            view_manager.set_status_bar(self.status_bar(view_manager.clone(), &ids));
+        }
+        if self.config.lock().unwrap().menu_bar {
+            view_manager.set_menu_bar(self.menu_bar(view_manager.clone(), ids.clone()));
+        }
+        self.set_command_palette(view_manager.clone(), ids.clone());
+        if !self.select_only
+            && let Some(idx) = self.initial_view_idx
+        {
+            view_manager.set_top_level_view_idx(idx);
+        }
+
+        let path = view_manager
+            .event_loop()
+            .instrument(info_span!("event_loop"))
+            .await;
+        let file = ids.get(view_manager.top_level_view_idx()) == Some(&FILES_VIEW_ID);
+        if !self.select_only {
+            self.record_search(&view_manager);
+        }
+        if self.restore_session {
+            self.save_session(&view_manager);
+        }
+        let search_text_state = self.search_text_state.lock().unwrap();
+        path.map(|path| Selection {
+            path,
+            search: search_text_state.search_string().to_string(),
+            fuzzy: search_text_state.fuzzy_match(),
+            file,
+        })
     }
 }
 
+/// After the panic is printed, suggest starting again without the previous session, in case
+/// restoring it is what crashed the GUI. Installed once per process.
+fn suggest_safe_mode_on_panic() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            previous(info);
+            eprintln!("cdir crashed: run 'cdir gui --safe-mode' to start without the last session");
+        }));
+    });
+}
+
 /// Launch the GUI. Returns the selected path or None if the user quit.
-pub(crate) async fn gui(store: store::Store, config: Arc<Mutex<Config>>) -> Option<String> {
+pub(crate) async fn gui(
+    store: store::Store,
+    config: Arc<Mutex<Config>>,
+    options: GuiOptions,
+) -> Option<Selection> {
     debug!("gui");
 
+    store.set_suggestions_only(options.view == Some(StartView::Suggestions));
+    if options.auto_select
+        && let Some(path) = Gui::single_result(&store, &options)
+    {
+        info!(
+            "single result '{}' selected without displaying the GUI",
+            path
+        );
+        return Some(Selection {
+            path,
+            search: options.query.unwrap_or_default(),
+            fuzzy: options.fuzzy,
+            file: options.view == Some(StartView::Files),
+        });
+    }
+
+    let (shortcut_check_interval, trash_retention_days) = {
+        let config_lock = config.lock().unwrap();
+        (
+            config_lock.shortcut_check_interval,
+            config_lock.trash_retention_days,
+        )
+    };
+    if let Err(e) = store.check_shortcuts(shortcut_check_interval) {
+        error!("Failed to check the shortcuts: {}", e);
+    }
+    if let Err(e) = store.purge_trash(trash_retention_days) {
+        error!("Failed to purge the trash: {}", e);
+    }
+
+    if !options.safe_mode {
+        suggest_safe_mode_on_panic();
+    }
+
     let view_manager: Rc<ViewManager> = Rc::new(ViewManager::new());
+    {
+        let config_lock = config.lock().unwrap();
+        view_manager.set_frame_budget(Duration::from_millis(config_lock.frame_budget_ms));
+        view_manager.set_reduced_motion(config_lock.reduced_motion);
+        view_manager.set_notification_styles(config_lock.styles.notification_styles());
+        keybindings::set_readline_keys(config_lock.readline_keys);
+    }
+    // the search is shared by the views, and the reloads read the whole data again
+    view_manager.set_coalesced_events(&["search.description", "data.reload"]);
 
     {
         // Set the global help view
@@ -550,9 +1676,20 @@ pub(crate) async fn gui(store: store::Store, config: Arc<Mutex<Config>>) -> Opti
         view_manager.set_global_config_view(config_builder);
     }
 
+    {
+        // Set the global log viewer
+        let config3 = config.clone();
+        let tx = view_manager.tx();
+        let log_builder = Box::new(move || {
+            let styles = config3.lock().unwrap().styles.clone();
+            LogView::builder(tx.clone(), logging::log_buffer(), styles)
+        });
+        view_manager.set_global_log_view(log_builder);
+    }
+
     {
         // Launch the GUI
-        let mut gui = Gui::new(view_manager.clone(), store, config);
+        let mut gui = Gui::new(view_manager.clone(), store, config, options);
         gui.run(view_manager).await
     }
 }
@@ -6,6 +6,7 @@ use super::*;
 use crate::{
     config::Config,
     store::{Path, Shortcut},
+    tui::notification::NotificationLevel,
 };
 
 #[test]
@@ -16,6 +17,7 @@ fn test_shorten_path_basic() {
         name: "docs".to_string(),
         path: "/home/user/docs".to_string(),
         description: None,
+        remote: false,
     }];
     let path = Path {
         id: 1,
@@ -23,6 +25,8 @@ fn test_shorten_path_basic() {
         date: 0,
         shortcut: None,
         smart_path: false,
+        hostname: None,
+        remote: false,
     };
     let result = Gui::shorten_path_for_shortcut(&config, &shortcuts, &path.path, 80);
     assert!(result.is_some());
@@ -39,6 +43,7 @@ fn test_shorten_path_no_match() {
         name: "docs".to_string(),
         path: "/home/user/docs".to_string(),
         description: None,
+        remote: false,
     }];
     let path = Path {
         id: 1,
@@ -46,6 +51,8 @@ fn test_shorten_path_no_match() {
         date: 0,
         shortcut: None,
         smart_path: false,
+        hostname: None,
+        remote: false,
     };
     let result = Gui::shorten_path_for_shortcut(&config, &shortcuts, &path.path, 80);
     assert!(result.is_none());
@@ -60,12 +67,14 @@ fn test_shorten_path_exact_match() {
             name: "docs".to_string(),
             path: "/home/user/docs".to_string(),
             description: None,
+            remote: false,
         },
         Shortcut {
             id: 2,
             name: "work".to_string(),
             path: "/home/user/docs/work".to_string(),
             description: None,
+            remote: false,
         },
     ];
     let path = Path::new(1, "/home/user/docs/work".to_string(), 0, &shortcuts);
@@ -85,12 +94,14 @@ fn test_shorten_path_longest_match() {
             name: "docs".to_string(),
             path: "/home/user/docs".to_string(),
             description: None,
+            remote: false,
         },
         Shortcut {
             id: 2,
             name: "work".to_string(),
             path: "/home/user/docs/work".to_string(),
             description: None,
+            remote: false,
         },
     ];
     let path = Path {
@@ -99,6 +110,8 @@ fn test_shorten_path_longest_match() {
         date: 0,
         shortcut: None,
         smart_path: false,
+        hostname: None,
+        remote: false,
     };
     let result = Gui::shorten_path_for_shortcut(&config, &shortcuts, &path.path, 80);
     assert!(result.is_some());
@@ -115,6 +128,7 @@ fn test_shorten_path_limited_size() {
         name: "docs".to_string(),
         path: "/home/user/docs".to_string(),
         description: None,
+        remote: false,
     }];
     let path = Path {
         id: 1,
@@ -122,6 +136,8 @@ fn test_shorten_path_limited_size() {
         date: 0,
         shortcut: None,
         smart_path: false,
+        hostname: None,
+        remote: false,
     };
     let result = Gui::shorten_path_for_shortcut(&config, &shortcuts, &path.path, 14);
     assert!(result.is_some());
@@ -173,6 +189,8 @@ fn test_reduce_path_home_replacement() {
         date: 0,
         shortcut: None,
         smart_path: false,
+        hostname: None,
+        remote: false,
     };
     let line = Gui::reduce_path(path.path, 80, Style::new());
     let line_str = line.to_string();
@@ -191,6 +209,8 @@ fn test_reduce_path_exact_home() {
         date: 0,
         shortcut: None,
         smart_path: false,
+        hostname: None,
+        remote: false,
     };
     let line = Gui::reduce_path(path.path, 80, Style::new());
     let line_str = line.to_string();
@@ -208,6 +228,8 @@ fn test_reduce_path_no_home_match() {
         date: 0,
         shortcut: None,
         smart_path: false,
+        hostname: None,
+        remote: false,
     };
     let line = Gui::reduce_path(path.path, 80, Style::new());
     let line_str = line.to_string();
@@ -226,6 +248,8 @@ fn test_reduce_path_with_home_limited_size() {
         date: 0,
         shortcut: None,
         smart_path: false,
+        hostname: None,
+        remote: false,
     };
 
     let line = Gui::reduce_path(path.path.clone(), 9, Style::new());
@@ -265,6 +289,8 @@ fn test_reduce_path_with_home_exact_limited_size() {
         date: 0,
         shortcut: None,
         smart_path: false,
+        hostname: None,
+        remote: false,
     };
 
     let line = Gui::reduce_path(path.path.clone(), 2, Style::new());
@@ -288,6 +314,8 @@ fn test_reduce_path_without_home_limited_size() {
         date: 0,
         shortcut: None,
         smart_path: false,
+        hostname: None,
+        remote: false,
     };
 
     let line = Gui::reduce_path(path.path.clone(), 19, Style::new());
@@ -298,3 +326,374 @@ fn test_reduce_path_without_home_limited_size() {
     let line_str = line.to_string();
     assert_eq!(line_str, "*ther/path/project");
 }
+
+#[test]
+fn test_reduce_path_multibyte() {
+    let home = "/home/testuser";
+    unsafe {
+        env::set_var("HOME", home);
+    }
+    // the CJK characters take two columns each
+    let path = format!("{}/文档/项目", home);
+    let line = Gui::reduce_path(path.clone(), 80, Style::new());
+    assert_eq!(line.to_string(), "~/文档/项目");
+    assert_eq!(line.width(), 11);
+
+    let line = Gui::reduce_path(path.clone(), 8, Style::new());
+    assert_eq!(line.to_string(), "~/*/项目");
+    let line = Gui::reduce_path(path.clone(), 7, Style::new());
+    assert_eq!(line.to_string(), "~/*项目");
+    // a wide character is not cut in half
+    let line = Gui::reduce_path(path.clone(), 6, Style::new());
+    assert_eq!(line.to_string(), "~/*目");
+    assert!(line.width() <= 6);
+
+    let line = Gui::reduce_path("/données/café/🦀crab".to_string(), 10, Style::new());
+    assert_eq!(line.to_string(), "*fé/🦀crab");
+    assert!(line.width() <= 10);
+}
+
+#[test]
+fn test_shorten_path_multibyte() {
+    let config = Config::default();
+    let shortcuts = vec![Shortcut {
+        id: 1,
+        name: "文档".to_string(),
+        path: "/home/user/docs".to_string(),
+        description: Some("éèàù documents".to_string()),
+        remote: false,
+    }];
+    let path = Path::new(1, "/home/user/docs/données/été".to_string(), 0, &shortcuts);
+    let line = Gui::shorten_path_for_path(&config, &path, 80).unwrap();
+    assert_eq!(line.to_string(), "[文档]/données/été");
+
+    let line = Gui::shorten_path_for_path(&config, &path, 12).unwrap();
+    assert_eq!(line.to_string(), "[文档]/*/été");
+    assert!(line.width() <= 12);
+
+    let line = Gui::format_history_description(&config, &path, 7);
+    assert_eq!(line.to_string(), "éèàù...");
+}
+
+#[test]
+fn test_width_within() {
+    assert_eq!(Gui::tail_within("abc", 5), "abc");
+    assert_eq!(Gui::tail_within("日本語", 5), "本語");
+    assert_eq!(Gui::tail_within("日本語", 1), "");
+    // a combining accent is kept with its letter, or dropped with it
+    assert_eq!(Gui::tail_within("xe\u{301}", 1), "e\u{301}");
+    assert_eq!(Gui::tail_within("x本\u{302}", 1), "");
+
+    assert_eq!(Gui::head_within("日本語", 5), "日本");
+    assert_eq!(Gui::head_within("café", 3), "caf");
+    assert_eq!(Gui::head_within("abc", 0), "");
+}
+
+#[test]
+fn test_reduce_remote_path() {
+    let config = Config::default();
+    let path = Path::new(1, String::from("me@server:/srv/app"), 0, &[]);
+    assert!(path.remote);
+
+    let line = Gui::reduce_any_path(&path.path, path.remote, 80, &config);
+    assert_eq!(line.spans.len(), 2);
+    assert_eq!(line.spans[0].content, "me@server:");
+    assert_eq!(line.to_string(), "me@server:/srv/app");
+
+    let line = Gui::reduce_any_path(&path.path, path.remote, 15, &config);
+    assert_eq!(line.to_string(), "me@server:*/app");
+
+    let line = Gui::reduce_any_path(&path.path, path.remote, 8, &config);
+    assert_eq!(line.to_string(), "*srv/app");
+}
+
+#[test]
+fn test_abbreviate_path_with_home() {
+    let home = "/home/testuser";
+    unsafe {
+        env::set_var("HOME", home);
+    }
+    let path = format!("{}/work/api/src", home);
+
+    // a path which fits is left to reduce_path
+    assert_eq!(Gui::abbreviate_path(&path, 14, Style::new()), None);
+
+    let line = Gui::abbreviate_path(&path, 13, Style::new()).unwrap();
+    assert_eq!(line.to_string(), "~/w…/api/src");
+    assert_eq!(line.spans[0].content, "~");
+
+    let line = Gui::abbreviate_path(&path, 11, Style::new()).unwrap();
+    assert_eq!(line.to_string(), "~/w…/a…/src");
+
+    let line = Gui::abbreviate_path(&path, 7, Style::new()).unwrap();
+    assert_eq!(line.to_string(), "~/…/src");
+
+    // the last segment is never cut
+    assert_eq!(Gui::abbreviate_path(&path, 6, Style::new()), None);
+}
+
+#[test]
+fn test_abbreviate_path_without_home() {
+    unsafe {
+        env::set_var("HOME", "/home/testuser");
+    }
+    let path = "/other/path/project";
+
+    let line = Gui::abbreviate_path(path, 18, Style::new()).unwrap();
+    assert_eq!(line.to_string(), "/o…/path/project");
+    assert_eq!(line.spans.len(), 1);
+
+    let line = Gui::abbreviate_path(path, 14, Style::new()).unwrap();
+    assert_eq!(line.to_string(), "/o…/p…/project");
+
+    let line = Gui::abbreviate_path(path, 10, Style::new()).unwrap();
+    assert_eq!(line.to_string(), "/…/project");
+
+    // short segments are kept as their abbreviation would not be shorter
+    let line = Gui::abbreviate_path("/a/bc/project", 11, Style::new()).unwrap();
+    assert_eq!(line.to_string(), "/…/project");
+
+    // a single segment can only be cut
+    assert_eq!(
+        Gui::abbreviate_path("/a-long-project", 8, Style::new()),
+        None
+    );
+}
+
+#[test]
+fn test_reduce_local_path_middle() {
+    unsafe {
+        env::set_var("HOME", "/home/testuser");
+    }
+    let mut config = Config::default();
+    let line = Gui::reduce_local_path(&config, "/other/path/project", 14);
+    assert_eq!(line.to_string(), "*/path/project");
+
+    config.path_shortening = PathShortening::Middle;
+    let line = Gui::reduce_local_path(&config, "/other/path/project", 14);
+    assert_eq!(line.to_string(), "/o…/p…/project");
+    let line = Gui::reduce_local_path(&config, "/other/path/project", 80);
+    assert_eq!(line.to_string(), "/other/path/project");
+
+    // falls back to the left truncation when the last segment does not fit
+    let line = Gui::reduce_local_path(&config, "/other/path/project", 6);
+    assert_eq!(line.to_string(), "*oject");
+}
+
+#[test]
+fn test_path_with_icon() {
+    let mut config = Config::default();
+    let project_types = HashMap::from([(String::from("/srv/cdir"), ProjectType::Rust)]);
+    let build = |size: u16| Gui::reduce_any_path("/srv/cdir/src", false, size, &Config::default());
+
+    let line = Gui::format_path_with_icon(&config, "/srv/cdir", false, &project_types, 10, build);
+    assert_eq!(line.to_string(), build(10).to_string());
+
+    // the icon takes a part of the width of the column
+    config.icons = Icons::Ascii;
+    let line = Gui::format_path_with_icon(&config, "/srv/cdir", false, &project_types, 10, build);
+    assert_eq!(line.to_string(), format!("rs {}", build(7)));
+    assert!(line.width() <= 10);
+}
+
+#[test]
+fn test_breadcrumb() {
+    let styles = ThemeStyles::default();
+    let line = Gui::breadcrumb(Line::from("/usr/lib/"), &styles);
+    assert_eq!(line.to_string(), "/ › usr › lib");
+    assert!(
+        line.spans
+            .last()
+            .unwrap()
+            .style
+            .add_modifier
+            .contains(Modifier::BOLD)
+    );
+
+    let line = Gui::breadcrumb(
+        Line::from(vec![Span::from("~"), Span::from("/work")]),
+        &styles,
+    );
+    assert_eq!(line.to_string(), "~ › work");
+
+    // the shortened path is kept when the breadcrumb does not fit
+    let config = Config {
+        path_display: PathDisplay::Breadcrumb,
+        ..Default::default()
+    };
+    let build = |size: u16| Gui::reduce_any_path("/srv/cdir/src", false, size, &Config::default());
+    let line = Gui::format_path_with_icon(&config, "/srv", false, &HashMap::new(), 40, build);
+    assert_eq!(line.to_string(), "/ › srv › cdir › src");
+    let line = Gui::format_path_with_icon(&config, "/srv", false, &HashMap::new(), 12, build);
+    assert_eq!(line.to_string(), build(12).to_string());
+}
+
+#[test]
+fn test_path_details() {
+    let config = Config::default();
+    let shortcuts = vec![Shortcut {
+        id: 1,
+        name: "docs".to_string(),
+        path: "/home/user/docs".to_string(),
+        description: Some("My documents".to_string()),
+        remote: false,
+    }];
+    let path = Path::new(
+        1,
+        "/home/user/docs/a/very/long/project".to_string(),
+        42,
+        &shortcuts,
+    );
+    let details = Gui::path_details(&config, &path);
+    assert_eq!(
+        details,
+        vec![
+            (
+                "Path".to_string(),
+                "/home/user/docs/a/very/long/project".to_string()
+            ),
+            ("Date".to_string(), "42".to_string()),
+            ("Shortcut".to_string(), "docs (/home/user/docs)".to_string()),
+            ("Description".to_string(), "My documents".to_string()),
+        ]
+    );
+
+    // no shortcut and smart suggestion: only the path
+    let mut path = Path::new(2, "/tmp".to_string(), 42, &[]);
+    path.smart_path = true;
+    assert_eq!(
+        Gui::path_details(&config, &path),
+        vec![("Path".to_string(), "/tmp".to_string())]
+    );
+}
+
+#[test]
+fn test_single_result() {
+    let store = Store::setup_test_store();
+    store.add_path("/home/user/project").unwrap();
+    store.add_path("/home/user/projects/web").unwrap();
+
+    let options = |query: &str, fuzzy: bool| GuiOptions {
+        query: Some(query.to_string()),
+        fuzzy,
+        auto_select: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        Gui::single_result(&store, &options("web", false)),
+        Some(String::from("/home/user/projects/web"))
+    );
+    assert_eq!(Gui::single_result(&store, &options("project", false)), None);
+    assert_eq!(Gui::single_result(&store, &options("nomatch", false)), None);
+    assert_eq!(Gui::single_result(&store, &options("", false)), None);
+    assert_eq!(Gui::single_result(&store, &GuiOptions::default()), None);
+}
+
+#[test]
+fn test_single_result_shortcuts_view() {
+    let store = Store::setup_test_store();
+    store.add_path("/home/user/projects/web").unwrap();
+    store.upsert_shortcut("w", "/srv/www", None).unwrap();
+
+    let options = |query: &str| GuiOptions {
+        query: Some(query.to_string()),
+        auto_select: true,
+        view: Some(StartView::Shortcuts),
+        ..Default::default()
+    };
+    assert_eq!(
+        Gui::single_result(&store, &options("www")),
+        Some(String::from("/srv/www"))
+    );
+    // the history is not searched
+    assert_eq!(Gui::single_result(&store, &options("web")), None);
+}
+
+#[test]
+fn test_single_result_files_view() {
+    let store = Store::setup_test_store();
+    store.add_path("/home/user/notes").unwrap();
+    store.add_file("/home/user/notes.md").unwrap();
+    store.add_file("/etc/hosts").unwrap();
+
+    let options = |query: &str| GuiOptions {
+        query: Some(query.to_string()),
+        auto_select: true,
+        view: Some(StartView::Files),
+        ..Default::default()
+    };
+    assert_eq!(
+        Gui::single_result(&store, &options("notes")),
+        Some(String::from("/home/user/notes.md"))
+    );
+    assert_eq!(Gui::single_result(&store, &options("/")), None);
+}
+
+#[test]
+fn test_failure_notification() {
+    let busy = rusqlite::Error::SqliteFailure(rusqlite::ffi::Error::new(5), None);
+    let notification = Gui::failure_notification("delete", &busy);
+    assert_eq!(notification.level, NotificationLevel::Warning);
+    assert_eq!(notification.message, "database locked, try to delete again");
+
+    let notification = Gui::failure_notification("delete", &rusqlite::Error::InvalidQuery);
+    assert_eq!(notification.level, NotificationLevel::Error);
+    assert!(notification.message.starts_with("failed to delete: "));
+}
+
+#[test]
+fn test_heat_bar() {
+    assert_eq!(Gui::heat_bar(1.0, 4), (String::from("████"), String::new()));
+    assert_eq!(
+        Gui::heat_bar(0.5, 4),
+        (String::from("██"), String::from("░░"))
+    );
+    // a visited path is never shown as not visited
+    assert_eq!(
+        Gui::heat_bar(0.01, 4),
+        (String::from("█"), String::from("░░░"))
+    );
+    assert_eq!(Gui::heat_bar(0.5, 0), (String::new(), String::new()));
+}
+
+#[test]
+fn test_safe_mode_skips_the_session() {
+    let store = Store::setup_test_store();
+    store
+        .save_gui_session(&GuiSession {
+            search: String::from("proj"),
+            fuzzy_match: true,
+            view: String::from("shortcuts"),
+            selected_row: 3,
+        })
+        .unwrap();
+    let config = Arc::new(Mutex::new(Config {
+        restore_session: true,
+        ..Default::default()
+    }));
+    let gui = |safe_mode: bool| {
+        Gui::new(
+            Rc::new(ViewManager::new()),
+            store.clone(),
+            config.clone(),
+            GuiOptions {
+                safe_mode,
+                ..Default::default()
+            },
+        )
+    };
+
+    let restored = gui(false);
+    assert_eq!(
+        restored.table_view_state.lock().unwrap().initial_search,
+        Some((String::from("proj"), true))
+    );
+
+    let safe = gui(true);
+    let table_view_state = safe.table_view_state.lock().unwrap();
+    assert!(table_view_state.safe_mode);
+    assert_eq!(table_view_state.initial_search, None);
+    assert!(table_view_state.initial_rows.is_empty());
+    // the session is still saved on exit
+    assert!(safe.restore_session);
+}
@@ -1,26 +1,74 @@
 use std::sync::{Arc, Mutex};
 
 use crossterm::event::{KeyCode, KeyEvent};
-use log::debug;
 use ratatui::{
     layout::{Constraint, Layout, Rect},
     style::Style,
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Padding, Paragraph},
 };
+use tracing::debug;
 
 use crate::{
     config::Config,
+    keybindings::{self, bindings_by_scope},
     tui::{EventCaptured, ManagerAction, View, ViewBuilder},
 };
 
 pub struct Help {
     config: Arc<Mutex<Config>>,
+    /// The first line of the help displayed
+    scroll: u16,
+    /// The number of lines of the help which are not visible, updated at each draw
+    hidden_lines: u16,
+    /// The number of lines visible in the help window, updated at each draw
+    page: u16,
 }
 
 impl Help {
     pub fn builder(config: Arc<Mutex<Config>>) -> ViewBuilder {
-        ViewBuilder::from(Box::new(Self { config }))
+        ViewBuilder::from(Box::new(Self {
+            config,
+            scroll: 0,
+            hidden_lines: 0,
+            page: 1,
+        }))
+    }
+
+    fn lines(&self) -> Vec<Line<'static>> {
+        let styles = self.config.lock().unwrap().styles.clone();
+        let ts = styles.text_style;
+        let es = styles.text_em_style;
+
+        let mut lines = vec![];
+        for (scope, bindings) in bindings_by_scope() {
+            if !lines.is_empty() {
+                lines.push(Line::from(""));
+            }
+            lines.push(Line::from(Span::styled(
+                format!("{}:", scope.title()),
+                styles.title_style,
+            )));
+            for binding in bindings {
+                lines.push(Line::from(vec![
+                    Span::styled("  ", ts),
+                    Span::styled(keybindings::keys_label(binding), es),
+                    Span::styled(format!(": {}", binding.description), ts),
+                ]));
+            }
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![Span::styled(
+            "Enter a text to filter.",
+            ts,
+        )]));
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::styled("Use ", ts),
+            Span::styled("esc", es),
+            Span::styled(" to close this window.", ts),
+        ]));
+        lines
     }
 }
 
@@ -31,6 +79,16 @@ impl View for Help {
                 EventCaptured::Yes,
                 ManagerAction::new(false).with_close(true),
             ),
+            KeyCode::Up | KeyCode::Down | KeyCode::PageUp | KeyCode::PageDown => {
+                self.scroll = match key_event.code {
+                    KeyCode::Up => self.scroll.saturating_sub(1),
+                    KeyCode::Down => self.scroll.saturating_add(1),
+                    KeyCode::PageUp => self.scroll.saturating_sub(self.page),
+                    _ => self.scroll.saturating_add(self.page),
+                }
+                .min(self.hidden_lines);
+                (EventCaptured::Yes, ManagerAction::new(true))
+            }
             _ => (EventCaptured::Yes, ManagerAction::new(false)),
         }
     }
@@ -38,9 +96,12 @@ impl View for Help {
     fn draw(&mut self, frame: &mut ratatui::Frame, modal_area: Rect, _active: bool) {
         debug!("Drawing help active");
 
+        let lines = self.lines();
+        // the borders and the padding take 4 lines
+        let height = (lines.len() as u16 + 4).min(modal_area.height);
         let layout = Layout::vertical([
             Constraint::Fill(1),
-            Constraint::Length(19),
+            Constraint::Length(height),
             Constraint::Fill(1),
         ]);
         let chunks: [Rect; 3] = layout.areas(modal_area);
@@ -52,97 +113,19 @@ impl View for Help {
         let chunks: [Rect; 3] = center_layout.areas(chunks[1]);
         let modal_area = chunks[1];
 
+        self.page = height.saturating_sub(4).max(1);
+        self.hidden_lines = (lines.len() as u16).saturating_sub(self.page);
+        self.scroll = self.scroll.min(self.hidden_lines);
+
         frame.render_widget(Clear, modal_area);
 
         let styles = self.config.lock().unwrap().styles.clone();
-        let ts = styles.text_style;
-        let es = styles.text_em_style;
-
-        let message = Paragraph::new(vec![
-        Line::from(vec![
-            Span::styled("Use ", ts),
-            Span::styled("tab", es),
-            Span::styled(" to switch between the views.", ts),
-        ]),
-        Line::from(vec![
-            Span::styled("Use ", ts),
-            Span::styled("enter", es),
-            Span::styled(" to exit the GUI and go into the selected directory;", ts),
-        ]),
-        Line::from(vec![
-            Span::styled("Use ", ts),
-            Span::styled("esc or ctrl+q", es),
-            Span::styled(" to simply exit and stay in the current directory.", ts),
-        ]),
-        Line::from(vec![
-            Span::styled("Use the ", ts),
-            Span::styled("up", es),
-            Span::styled(" and ", ts),
-            Span::styled("down", es),
-            Span::styled(" arrow keys to select a directory (", ts),
-            Span::styled("shift", es),
-            Span::styled(" for bigger jumps);", ts),
-        ]),
-        Line::from(vec![
-            Span::styled("Use ", ts),
-            Span::styled("page up", es),
-            Span::styled(" and ", ts),
-            Span::styled("page down", es),
-            Span::styled(" to scroll through the list by page;", ts),
-        ]),
-        Line::from(vec![
-            Span::styled("Use ", ts),
-            Span::styled("home", es),
-            Span::styled(
-                " to go to the most recent directory in the history (the top);",
-                ts,
-            ),
-        ]),
-        Line::from(vec![
-            Span::styled("Use ", ts),
-            Span::styled("ctrl+a", es),
-            Span::styled(" to see the full directory path without shortcuts, or switch back to shortcut usage.", ts),
-        ]),
-        Line::from(vec![
-            Span::styled("Use ", ts),
-            Span::styled("ctrl+d", es),
-            Span::styled(" to delete the selected entry.", ts),
-        ]),
-        Line::from(vec![
-            Span::styled("Use ", ts),
-            Span::styled("ctrl+e", es),
-            Span::styled(" to edit a shortcut description.", ts),
-        ]),
-        Line::from(vec![
-            Span::styled("Use ", ts),
-            Span::styled("ctrl+f", es),
-            Span::styled(" to switch between exact and fuzzy search.", ts),
-        ]),
-        Line::from(vec![
-            Span::styled("Use ", ts),
-            Span::styled("ctrl+h", es),
-            Span::styled(" for the help screen.", ts),
-        ]),
-        Line::from(vec![
-            Span::styled("Use ", ts),
-            Span::styled("F12", es),
-            Span::styled(" to open the configuration view.", ts),
-        ]),
-        Line::from(""),
-        Line::from(vec![Span::styled("Enter a text to filter.", ts)]),
-        Line::from(""),
-        Line::from(vec![
-            Span::styled("Use ", ts),
-            Span::styled("esc", es),
-            Span::styled(" to close this window.", ts),
-        ]),
-    ])
-    .block(
-        Block::default()
-            .padding(Padding::new(1, 1, 1, 1))
-            .title(Span::styled(" cdir help ", styles.title_style))
-            .borders(Borders::ALL)
-    );
+        let message = Paragraph::new(lines).scroll((self.scroll, 0)).block(
+            Block::default()
+                .padding(Padding::new(1, 1, 1, 1))
+                .title(Span::styled(" cdir help ", styles.title_style))
+                .borders(Borders::ALL),
+        );
 
         // Fill the frame with the background color if defined
         if let Some(bg_color) = &styles.background_color {
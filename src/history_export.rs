@@ -0,0 +1,114 @@
+use cdir_core::store::Path;
+use chrono::{Local, NaiveDate, TimeZone};
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::report::parse_period;
+
+#[cfg(test)]
+#[path = "history_export_tests.rs"]
+mod history_export_tests;
+
+/// The formats of the exported history
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) enum HistoryFormat {
+    /// Comma-separated values, with a header line
+    #[default]
+    Csv,
+    /// A JSON array of objects
+    Json,
+}
+
+/// The columns of the exported history
+const COLUMNS: [&str; 4] = ["timestamp", "path", "shortcut", "hostname"];
+
+/// A visit of the exported history
+#[derive(Serialize, Debug, PartialEq)]
+struct Row<'a> {
+    timestamp: String,
+    path: &'a str,
+    shortcut: Option<&'a str>,
+    hostname: Option<&'a str>,
+}
+
+/// Parse the start of the exported history: a local day like `2026-10-01`, or a period
+/// before now like `30d`, see `parse_period`.
+///
+/// ### Returns
+/// The start in seconds since EPOCH, or an error message if it cannot be parsed.
+pub(crate) fn parse_since(text: &str) -> Result<i64, String> {
+    if let Ok(day) = NaiveDate::parse_from_str(text, "%Y-%m-%d") {
+        return Local
+            .from_local_datetime(&day.and_time(Default::default()))
+            .earliest()
+            .map(|start| start.timestamp())
+            .ok_or_else(|| format!("invalid date '{}'", text));
+    }
+    parse_period(text)
+        .map(|period| Local::now().timestamp() - period)
+        .map_err(|_| {
+            format!(
+                "invalid date '{}', expected e.g. 2026-10-01 or a period like 30d",
+                text
+            )
+        })
+}
+
+/// The local date and time of `epoch`, in a form the spreadsheets recognize
+fn timestamp(epoch: i64) -> String {
+    Local
+        .timestamp_opt(epoch, 0)
+        .single()
+        .map(|date| date.format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_default()
+}
+
+/// Quote a CSV field when it holds a separator, a quote or a line break, doubling its quotes
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+fn rows(visits: &[Path]) -> Vec<Row<'_>> {
+    visits
+        .iter()
+        .map(|visit| Row {
+            timestamp: timestamp(visit.date),
+            path: &visit.path,
+            shortcut: visit
+                .shortcut
+                .as_ref()
+                .map(|shortcut| shortcut.name.as_str()),
+            hostname: visit.hostname.as_deref(),
+        })
+        .collect()
+}
+
+fn render_csv(visits: &[Path]) -> String {
+    let mut csv = COLUMNS.join(",") + "\r\n";
+    for row in rows(visits) {
+        let fields = [
+            row.timestamp.as_str(),
+            row.path,
+            row.shortcut.unwrap_or_default(),
+            row.hostname.unwrap_or_default(),
+        ];
+        let fields: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        csv.push_str(&fields.join(","));
+        csv.push_str("\r\n");
+    }
+    csv
+}
+
+/// Render the visits of the history, the oldest first, in `format`.
+pub(crate) fn render(visits: &[Path], format: HistoryFormat) -> Result<String, String> {
+    match format {
+        HistoryFormat::Csv => Ok(render_csv(visits)),
+        HistoryFormat::Json => serde_json::to_string_pretty(&rows(visits))
+            .map(|json| json + "\n")
+            .map_err(|e| e.to_string()),
+    }
+}
@@ -0,0 +1,58 @@
+use cdir_core::store::Shortcut;
+
+use super::*;
+
+fn visits() -> Vec<Path> {
+    let shortcuts = [Shortcut {
+        id: 1,
+        name: String::from("w"),
+        path: String::from("/work"),
+        description: None,
+        remote: false,
+    }];
+    vec![
+        Path::new(1, String::from("/work/api"), 0, &shortcuts)
+            .with_hostname(Some(String::from("laptop"))),
+        Path::new(2, String::from("/tmp/a,\"b\""), 60, &shortcuts),
+    ]
+}
+
+#[test]
+fn test_parse_since() {
+    let start = parse_since("2026-10-01").unwrap();
+    assert_eq!(timestamp(start), "2026-10-01 00:00:00");
+    let now = Local::now().timestamp();
+    assert!((parse_since("1d").unwrap() - (now - 24 * 3600)).abs() < 60);
+    assert!(parse_since("2026-13-01").is_err());
+    assert!(parse_since("yesterday").is_err());
+}
+
+#[test]
+fn test_csv_field() {
+    assert_eq!(csv_field("/work/api"), "/work/api");
+    assert_eq!(csv_field("a,b"), "\"a,b\"");
+    assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+}
+
+#[test]
+fn test_render_csv() {
+    let csv = render(&visits(), HistoryFormat::Csv).unwrap();
+    assert_eq!(
+        csv,
+        format!(
+            "timestamp,path,shortcut,hostname\r\n{},/work/api,w,laptop\r\n{},\"/tmp/a,\"\"b\"\"\",,\r\n",
+            timestamp(0),
+            timestamp(60)
+        )
+    );
+}
+
+#[test]
+fn test_render_json() {
+    let json = render(&visits(), HistoryFormat::Json).unwrap();
+    let rows: Vec<serde_json::Value> = serde_json::from_str(&json).unwrap();
+    assert_eq!(rows[0]["shortcut"], "w");
+    assert_eq!(rows[0]["hostname"], "laptop");
+    assert!(rows[1]["shortcut"].is_null());
+}
@@ -3,76 +3,397 @@ use std::{
     sync::{Arc, Mutex},
 };
 
+use chrono::{DateTime, Local};
 use crossterm::event::KeyEvent;
-use log::debug;
 use ratatui::layout::{Constraint, Layout, Rect};
+use tracing::{debug, error};
 
 use crate::{
+    command_palette::EMPTY_TRASH_EVENT,
     config::Config,
     config_button::ConfigButton,
-    list_indicator_view::ListIndicatorView,
-    model::ListFunction,
+    date_formatter::local_day_section,
+    dir_size::{DIR_SIZE_COMPUTED_EVENT, DirSizePayload},
+    gui::Gui,
+    keybindings::{self, Action, Scope},
+    list_indicator_view::{ListIndicatorView, publish_status_message},
+    model::{CountFunction, ListFunction, WarmStart},
     search_text_view::{SearchTextState, SearchTextView},
-    store::Path,
+    sessions_view::SessionsView,
+    status_bar::publish_status_segment,
+    store::{Path, SortColumn, Store},
+    suggestions_view::SuggestionsState,
     tableview::{
-        ColumnConstraintsFn, ColumnNamesFn, DeleteFn, EditorViewBuilder, RowifyFn, TableView,
-        TableViewState,
+        ColumnConstraintsFn, ColumnNamesFn, DeleteFn, DetailsFn, EditorViewBuilder, PopoverFn,
+        RestoreFn, RowifyFn, SubtitleFn, TableView, TableViewParams, TableViewState,
+    },
+    tui::{
+        EventCaptured, GenericEvent, ManagerAction, Notification, View, ViewBuilder, ViewManager,
+        event::{ApplicationEvent, ViewManagerEvent},
     },
-    tui::{EventCaptured, ManagerAction, View, ViewBuilder, ViewManager},
 };
 
 const PATH_HISTORY_VIEW_ID: u16 = 0;
 const SEARCH_TEXT_VIEW_1: u16 = 1;
 const CONFIGURATION_VIEW: u16 = 2;
 const LIST_INDICATOR_VIEW: u16 = 3;
+const SUGGESTIONS_VIEW: u16 = 4;
+
+/// The maximum number of sessions browsed
+const MAX_SESSIONS: usize = 200;
+
+/// The maximum number of visits listed by the explanation of a smart suggestion
+const MAX_EXPLAINED_SEQUENCES: usize = 3;
 
-pub struct HistoryViewContainer {}
+/// The parts of the directory history view, given to `HistoryViewContainer::builder`, most of
+/// them making its table
+pub struct HistoryViewParams {
+    pub column_names_fn: ColumnNamesFn,
+    pub column_constraints_fn: ColumnConstraintsFn,
+    pub list_fn: Box<ListFunction<Path>>,
+    pub count_fn: Box<CountFunction>,
+    pub rowify: RowifyFn<Path>,
+    pub stringify: fn(&Path) -> String,
+    pub config: Arc<Mutex<Config>>,
+    pub view_state: Arc<Mutex<TableViewState>>,
+    pub delete_fn: DeleteFn<Path>,
+    pub restore_fn: RestoreFn<Path>,
+    pub editor_modal_view_builder: Option<EditorViewBuilder<Path>>,
+    pub details_fn: DetailsFn<Path>,
+    /// The state of the search of the view
+    pub search_text_state: Arc<Mutex<SearchTextState>>,
+    /// The panel of the smart suggestions, shown along the table
+    pub suggestions_view: ViewBuilder,
+    pub suggestions_state: Arc<Mutex<SuggestionsState>>,
+    pub store: Store,
+    /// The tmux pane the GUI runs in, None outside of tmux
+    pub tmux_pane: Option<String>,
+}
+
+pub struct HistoryViewContainer {
+    vm: Rc<ViewManager>,
+    config: Arc<Mutex<Config>>,
+    suggestions_state: Arc<Mutex<SuggestionsState>>,
+    store: Store,
+    /// The tmux pane the GUI runs in, None outside of tmux
+    tmux_pane: Option<String>,
+}
 
 impl HistoryViewContainer {
-    pub fn builder(
-        vm: Rc<ViewManager>,
-        column_names_fn: ColumnNamesFn,
-        column_constraints_fn: ColumnConstraintsFn,
-        list_fn: Box<ListFunction<Path>>,
-        rowify: RowifyFn<Path>,
-        stringify: fn(&Path) -> String,
-        config: Arc<Mutex<Config>>,
-        view_state: Arc<Mutex<TableViewState>>,
-        delete_fn: DeleteFn<Path>,
-        editor_modal_view_builder: Option<EditorViewBuilder<Path>>,
-        search_text_state: Arc<Mutex<SearchTextState>>,
-    ) -> ViewBuilder {
-        ViewBuilder::from(Box::new(Self {}))
-            .child(
-                PATH_HISTORY_VIEW_ID,
-                TableView::builder(
-                    vm.clone(),
-                    "path".to_string(),
+    /// Create a ViewBuilder for the directory history view, made of `params`.
+    pub fn builder(vm: Rc<ViewManager>, params: HistoryViewParams) -> ViewBuilder {
+        let HistoryViewParams {
+            column_names_fn,
+            column_constraints_fn,
+            list_fn,
+            count_fn,
+            rowify,
+            stringify,
+            config,
+            view_state,
+            delete_fn,
+            restore_fn,
+            editor_modal_view_builder,
+            details_fn,
+            search_text_state,
+            suggestions_view,
+            suggestions_state,
+            store,
+            tmux_pane,
+        } = params;
+        let safe_mode = view_state.lock().unwrap().safe_mode;
+        ViewBuilder::from(Box::new(Self {
+            vm: vm.clone(),
+            config: config.clone(),
+            suggestions_state,
+            store: store.clone(),
+            tmux_pane,
+        }))
+        .with_publish_events(true)
+        .child(
+            PATH_HISTORY_VIEW_ID,
+            TableView::builder(
+                vm.clone(),
+                TableViewParams {
+                    objects_type: "path".to_string(),
                     column_names_fn,
                     column_constraints_fn,
                     list_fn,
+                    count_fn,
                     rowify,
                     stringify,
-                    config.clone(),
+                    config: config.clone(),
                     view_state,
                     delete_fn,
+                    restore_fn: Some(restore_fn),
                     editor_modal_view_builder,
-                    Box::new(|paths| paths.iter().position(|p| !p.smart_path).unwrap_or(0)),
-                )
-                .with_publish_events(true),
-            )
-            .child(
-                SEARCH_TEXT_VIEW_1,
-                SearchTextView::builder(config.clone(), search_text_state.clone()),
-            )
-            .child(
-                CONFIGURATION_VIEW,
-                ConfigButton::builder(vm.clone(), config.clone()),
-            )
-            .child(
-                LIST_INDICATOR_VIEW,
-                ListIndicatorView::builder(vm.clone(), config.clone(), "path".to_string()),
+                    relocate_modal_view_builder: None,
+                    find_focus_fn: Box::new(|paths| {
+                        paths.iter().position(|p| !p.smart_path).unwrap_or(0)
+                    }),
+                    details_fn,
+                    shortcut_name_fn: Box::new(|path: &Path| {
+                        path.shortcut.as_ref().map(|s| s.name.clone())
+                    }),
+                    sort_columns: vec![("date", SortColumn::Date), ("path", SortColumn::Path)],
+                    quick_select: true,
+                    section_fn: Some(Box::new(|config: &Config, path: &Path| {
+                        (config.path_view_day_sections && !path.smart_path)
+                            .then(|| local_day_section(path.date))
+                            .flatten()
+                            .map(String::from)
+                    })),
+                    subtitle_fn: Some(Self::last_commands_fn(store.clone())),
+                    popover_fn: Some(Self::explanation_fn(store.clone())),
+                    warm_start: (!safe_mode).then(|| Self::warm_start(store.clone())),
+                },
             )
+            .with_publish_events(true),
+        )
+        .child(
+            SEARCH_TEXT_VIEW_1,
+            SearchTextView::builder(
+                vm.clone(),
+                config.clone(),
+                search_text_state.clone(),
+                store.clone(),
+                "path".to_string(),
+            ),
+        )
+        .child(
+            CONFIGURATION_VIEW,
+            ConfigButton::builder(vm.clone(), config.clone()),
+        )
+        .child(
+            LIST_INDICATOR_VIEW,
+            ListIndicatorView::builder(vm.clone(), config.clone(), "path".to_string()),
+        )
+        .child(SUGGESTIONS_VIEW, suggestions_view.with_publish_events(true))
+    }
+}
+
+impl HistoryViewContainer {
+    /// The last commands run in the directories of the history, drawn under their rows
+    fn last_commands_fn(store: Store) -> SubtitleFn<Path> {
+        Box::new(move |config: &Config, paths: &[Path]| {
+            if !config.path_view_last_commands {
+                return vec![None; paths.len()];
+            }
+            let names: Vec<&str> = paths.iter().map(|p| p.path.as_str()).collect();
+            let mut commands = store.last_commands(&names).unwrap_or_else(|e| {
+                error!("Failed to read the last commands: {}", e);
+                Default::default()
+            });
+            paths.iter().map(|p| commands.remove(&p.path)).collect()
+        })
+    }
+
+    /// The first page of the history of the previous session, shown while the history is
+    /// queried at startup; the smart suggestions are not persisted, being computed anew
+    fn warm_start(store: Store) -> WarmStart<Path> {
+        let save_store = store.clone();
+        WarmStart {
+            load_fn: Box::new(move || {
+                store.warm_start_page().unwrap_or_else(|e| {
+                    error!("Failed to load the first page of the history: {}", e);
+                    vec![]
+                })
+            }),
+            save_fn: Box::new(move |paths: &[Path]| {
+                let ids: Vec<i64> = paths
+                    .iter()
+                    .filter(|path| path.is_in_history())
+                    .map(|path| path.id)
+                    .collect();
+                if let Err(e) = save_store.save_warm_start_page(&ids) {
+                    error!("Failed to save the first page of the history: {}", e);
+                }
+            }),
+        }
+    }
+
+    /// Why the selected smart suggestion is suggested, drawn over the table
+    fn explanation_fn(store: Store) -> PopoverFn<Path> {
+        Box::new(move |_config: &Config, path: &Path| {
+            if !path.smart_path {
+                return None;
+            }
+            let explanation = store.suggestion_explanation(&path.path)?;
+            let mut lines = vec![format!(
+                "followed the current directory {} time{}",
+                explanation.followed,
+                if explanation.followed == 1 { "" } else { "s" }
+            )];
+            let sequences: Vec<String> = explanation
+                .sequences
+                .iter()
+                .take(MAX_EXPLAINED_SEQUENCES)
+                .filter_map(|date| DateTime::from_timestamp(*date, 0))
+                .map(|date| {
+                    date.with_timezone(&Local)
+                        .format("%a %m-%d %H:%M")
+                        .to_string()
+                })
+                .collect();
+            if !sequences.is_empty() {
+                let more = explanation.sequences.len() - sequences.len();
+                lines.push(format!(
+                    "after the visits of {}{}",
+                    sequences.join(", "),
+                    match more {
+                        0 => String::new(),
+                        more => format!(" and {} more", more),
+                    }
+                ));
+            }
+            lines.push(format!("score {}", explanation.score));
+            Some((String::from("Why suggested"), lines))
+        })
+    }
+
+    /// The filters applied to the history besides the search, e.g. `this pane, trash`, None
+    /// when the whole history is listed
+    pub(crate) fn filters_description(store: &Store) -> Option<String> {
+        let filters: Vec<&str> = [
+            (store.pane_filter().is_some(), "this pane"),
+            (store.show_trash(), "trash"),
+            (store.reveal_sensitive(), "sensitive shown"),
+            (store.suggestions_only(), "suggestions"),
+        ]
+        .into_iter()
+        .filter_map(|(active, name)| active.then_some(name))
+        .collect();
+        Some(filters.join(", ")).filter(|filters| !filters.is_empty())
+    }
+
+    /// Update the filters shown by the status bar.
+    fn publish_filters(&self) {
+        publish_status_segment(
+            &self.vm,
+            "path",
+            "filters",
+            Self::filters_description(&self.store),
+        );
+    }
+
+    /// Switch between the paths visited from the current tmux pane and all the paths.
+    fn toggle_pane_filter(&self) {
+        let Some(tmux_pane) = &self.tmux_pane else {
+            publish_status_message(&self.vm, "not in tmux");
+            return;
+        };
+        if self.store.pane_filter().is_some() {
+            self.store.set_pane_filter(None);
+            self.publish_filters();
+            publish_status_message(&self.vm, "all panes");
+        } else {
+            self.store.set_pane_filter(Some(tmux_pane.clone()));
+            self.publish_filters();
+            publish_status_message(&self.vm, "this pane only");
+        }
+        let event = GenericEvent::ApplicationEvent(ApplicationEvent {
+            id: String::from("data.reload"),
+            payload: None,
+        });
+        if let Err(e) = self.vm.tx().send(event) {
+            error!("Failed to send 'data.reload' event: {}", e);
+        }
+    }
+}
+
+impl HistoryViewContainer {
+    /// Show the paths marked as sensitive, or hide them again.
+    fn toggle_reveal_sensitive(&self) {
+        let reveal = !self.store.reveal_sensitive();
+        self.store.set_reveal_sensitive(reveal);
+        self.publish_filters();
+        publish_status_message(
+            &self.vm,
+            if reveal {
+                "sensitive shown"
+            } else {
+                "sensitive hidden"
+            },
+        );
+        let event = GenericEvent::ApplicationEvent(ApplicationEvent {
+            id: String::from("data.reload"),
+            payload: None,
+        });
+        if let Err(e) = self.vm.tx().send(event) {
+            error!("Failed to send 'data.reload' event: {}", e);
+        }
+    }
+}
+
+impl HistoryViewContainer {
+    /// List the deleted paths, which can be restored or purged, or the history again.
+    fn toggle_trash(&self) {
+        let show = !self.store.show_trash();
+        self.store.set_show_trash(show);
+        self.publish_filters();
+        publish_status_message(&self.vm, if show { "trash" } else { "history" });
+        let event = GenericEvent::ApplicationEvent(ApplicationEvent {
+            id: String::from("data.reload"),
+            payload: None,
+        });
+        if let Err(e) = self.vm.tx().send(event) {
+            error!("Failed to send 'data.reload' event: {}", e);
+        }
+    }
+}
+
+impl HistoryViewContainer {
+    /// Browse the sessions of the history, the directory chosen in one of them being jumped to.
+    fn show_sessions(&self) {
+        let (idle_minutes, styles) = {
+            let config_lock = self.config.lock().unwrap();
+            (config_lock.session_idle_minutes, config_lock.styles.clone())
+        };
+        match self
+            .store
+            .list_sessions(idle_minutes as i64 * 60, MAX_SESSIONS)
+        {
+            Ok(sessions) => {
+                let vb = SessionsView::builder(sessions, styles);
+                self.vm.show_modal(vb, Some(Self::sessions_callback));
+            }
+            Err(e) => self
+                .vm
+                .notify(Gui::failure_notification("list the sessions", &e)),
+        }
+    }
+
+    fn sessions_callback(&mut self, view: &SessionsView) -> ManagerAction {
+        if let Some(path) = view.chosen() {
+            debug!("path '{}' chosen in the sessions", path);
+            let event =
+                GenericEvent::ViewManagerEvent(ViewManagerEvent::Exit(Some(path.to_string())));
+            if let Err(e) = self.vm.tx().send(event) {
+                error!("Failed to send the exit event: {}", e);
+            }
+        }
+        ManagerAction::new(true)
+    }
+}
+
+impl HistoryViewContainer {
+    /// Delete for good every path of the trash.
+    fn empty_trash(&self) {
+        match self.store.purge_trash(0) {
+            Ok(purged) => self
+                .vm
+                .notify(Notification::info(format!("{} paths purged", purged))),
+            Err(e) => self
+                .vm
+                .notify(Gui::failure_notification("empty the trash", &e)),
+        }
+        let event = GenericEvent::ApplicationEvent(ApplicationEvent {
+            id: String::from("data.reload"),
+            payload: None,
+        });
+        if let Err(e) = self.vm.tx().send(event) {
+            error!("Failed to send 'data.reload' event: {}", e);
+        }
     }
 }
 
@@ -80,8 +401,19 @@ impl View for HistoryViewContainer {
     fn capture_focus(&self) -> bool { true }
     fn broadcast_keyboard_events(&self) -> bool { true }
     fn resize(&mut self, area: Rect) -> Vec<(u16, Rect)> {
-        let vertical = Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).spacing(0);
-        let [main, bottom] = vertical.areas(area);
+        // the bottom row is left to the search
+        let suggestions_height = self
+            .suggestions_state
+            .lock()
+            .unwrap()
+            .height(area.height.saturating_sub(1));
+        let vertical = Layout::vertical([
+            Constraint::Length(suggestions_height),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+        ])
+        .spacing(0);
+        let [suggestions, main, bottom] = vertical.areas(area);
 
         let horizontal = Layout::horizontal([
             Constraint::Fill(1),
@@ -97,6 +429,7 @@ impl View for HistoryViewContainer {
             (SEARCH_TEXT_VIEW_1, search_text_area),
             (LIST_INDICATOR_VIEW, list_indicator_rect),
             (CONFIGURATION_VIEW, config_rect),
+            (SUGGESTIONS_VIEW, suggestions),
         ]
     }
     fn draw(&mut self, _frame: &mut ratatui::Frame, area: ratatui::prelude::Rect, active: bool) {
@@ -105,8 +438,32 @@ impl View for HistoryViewContainer {
     }
 
     fn handle_key_event(&mut self, key_event: KeyEvent) -> (EventCaptured, ManagerAction) {
-        let _ = key_event;
         debug!("handle_key_event");
+        match keybindings::action(Scope::History, &key_event) {
+            Some(Action::TogglePaneFilter) => self.toggle_pane_filter(),
+            Some(Action::RevealSensitive) => self.toggle_reveal_sensitive(),
+            Some(Action::ToggleTrash) => self.toggle_trash(),
+            Some(Action::Sessions) => self.show_sessions(),
+            _ => {}
+        }
         (EventCaptured::No, ManagerAction::new(false))
     }
+
+    fn handle_application_event(&mut self, ae: &ApplicationEvent) {
+        if ae.id == "data.reload" {
+            self.store.clear_fuzzy_cache();
+            self.store.clear_visit_counts();
+        }
+        if ae.id == EMPTY_TRASH_EVENT {
+            self.empty_trash();
+        }
+        // keep the computed directory sizes for the next sessions
+        if ae.id == DIR_SIZE_COMPUTED_EVENT
+            && let Some(payload) = &ae.payload
+            && let Some(payload) = payload.downcast_ref::<DirSizePayload>()
+            && let Err(e) = self.store.set_dir_size(&payload.path, &payload.size)
+        {
+            error!("Failed to save the size of '{}': {}", payload.path, e);
+        }
+    }
 }
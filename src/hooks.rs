@@ -0,0 +1,67 @@
+use std::{
+    process::{Command, Stdio},
+    sync::{Arc, Mutex},
+};
+
+use tracing::{debug, error};
+
+use crate::config::Config;
+
+#[cfg(test)]
+#[path = "hooks_tests.rs"]
+mod hooks_tests;
+
+/// The events user commands can be hooked on
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Hook {
+    /// A path was added to the history
+    AddPath,
+    /// A directory was chosen, from the GUI or by shortcut name
+    Select,
+    /// A shortcut was deleted
+    DeleteShortcut,
+}
+
+impl Hook {
+    /// The name of the hook, as found in the configuration
+    pub fn name(&self) -> &'static str {
+        match self {
+            Hook::AddPath => "on_add_path",
+            Hook::Select => "on_select",
+            Hook::DeleteShortcut => "on_delete_shortcut",
+        }
+    }
+
+    fn command(&self, config: &Config) -> Option<String> {
+        match self {
+            Hook::AddPath => config.hooks.on_add_path.clone(),
+            Hook::Select => config.hooks.on_select.clone(),
+            Hook::DeleteShortcut => config.hooks.on_delete_shortcut.clone(),
+        }
+    }
+}
+
+/// Run the command configured for `hook`, if any, with `sh -c`.
+/// The parameters of the event are passed as environment variables, along with `CDIR_HOOK`
+/// holding the name of the hook. The output of the command is discarded so that it never
+/// mixes with the output of cdir, and a failure is only logged.
+pub fn run_hook(config: &Arc<Mutex<Config>>, hook: Hook, env: &[(&str, &str)]) {
+    let Some(command) = hook.command(&config.lock().unwrap()) else {
+        return;
+    };
+    debug!("run_hook {} '{}' {:?}", hook.name(), command, env);
+    let status = Command::new("sh")
+        .arg("-c")
+        .arg(&command)
+        .env("CDIR_HOOK", hook.name())
+        .envs(env.iter().copied())
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+    match status {
+        Ok(status) if status.success() => {}
+        Ok(status) => error!("Hook {} '{}' failed: {}", hook.name(), command, status),
+        Err(e) => error!("Failed to run hook {} '{}': {}", hook.name(), command, e),
+    }
+}
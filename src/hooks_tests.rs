@@ -0,0 +1,39 @@
+use std::fs;
+
+use super::*;
+use crate::config::HooksConfig;
+
+fn config_with_hooks(hooks: HooksConfig) -> Arc<Mutex<Config>> {
+    Arc::new(Mutex::new(Config {
+        hooks,
+        ..Default::default()
+    }))
+}
+
+#[test]
+fn test_run_hook_passes_the_parameters() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("output");
+    let config = config_with_hooks(HooksConfig {
+        on_select: Some(format!(
+            "echo \"$CDIR_HOOK $CDIR_PATH\" > '{}'",
+            output.display()
+        )),
+        ..Default::default()
+    });
+
+    run_hook(&config, Hook::AddPath, &[("CDIR_PATH", "/add")]);
+    assert!(!output.exists());
+
+    run_hook(&config, Hook::Select, &[("CDIR_PATH", "/home/me")]);
+    assert_eq!(fs::read_to_string(&output).unwrap(), "on_select /home/me\n");
+}
+
+#[test]
+fn test_run_hook_failure_is_ignored() {
+    let config = config_with_hooks(HooksConfig {
+        on_delete_shortcut: Some(String::from("exit 3")),
+        ..Default::default()
+    });
+    run_hook(&config, Hook::DeleteShortcut, &[]);
+}
@@ -0,0 +1,62 @@
+use unicode_width::UnicodeWidthStr;
+
+use crate::{config::Icons, project_type::ProjectType};
+
+#[cfg(test)]
+#[path = "icons_tests.rs"]
+mod icons_tests;
+
+/// What an icon depicts, from the most specific to the least
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum IconKind {
+    Project(ProjectType),
+    Git,
+    Remote,
+    Folder,
+}
+
+impl IconKind {
+    /// The kind of icon of a path: its project type, a git repository root, a remote path,
+    /// else a plain folder.
+    pub(crate) fn of(path: &str, remote: bool, project_type: Option<ProjectType>) -> IconKind {
+        if remote {
+            return IconKind::Remote;
+        }
+        if let Some(project_type) = project_type {
+            return IconKind::Project(project_type);
+        }
+        if std::path::Path::new(path).join(".git").exists() {
+            return IconKind::Git;
+        }
+        IconKind::Folder
+    }
+}
+
+/// The icon of a kind of path in a set, followed by a space, None without icons
+pub(crate) fn resolve(icons: Icons, kind: IconKind) -> Option<&'static str> {
+    match icons {
+        Icons::None => None,
+        Icons::Nerdfont => Some(match kind {
+            IconKind::Project(ProjectType::Rust) => "\u{e7a8} ",
+            IconKind::Project(ProjectType::Node) => "\u{e718} ",
+            IconKind::Project(ProjectType::Python) => "\u{e73c} ",
+            IconKind::Project(ProjectType::Go) => "\u{e627} ",
+            IconKind::Git => "\u{e702} ",
+            IconKind::Remote => "\u{f233} ",
+            IconKind::Folder => "\u{f07b} ",
+        }),
+        // the same width for all of them, so that the paths stay aligned
+        Icons::Ascii => Some(match kind {
+            IconKind::Project(ProjectType::Rust) => "rs ",
+            IconKind::Project(ProjectType::Node) => "js ",
+            IconKind::Project(ProjectType::Python) => "py ",
+            IconKind::Project(ProjectType::Go) => "go ",
+            IconKind::Git => "gt ",
+            IconKind::Remote => "@  ",
+            IconKind::Folder => "/  ",
+        }),
+    }
+}
+
+/// The number of columns an icon takes, the Nerd Font glyphs being one column wide
+pub(crate) fn width(icon: &str) -> u16 { icon.width() as u16 }
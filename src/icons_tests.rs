@@ -0,0 +1,46 @@
+use std::fs;
+
+use super::*;
+
+#[test]
+fn test_kind() {
+    let root = tempfile::tempdir().unwrap();
+    fs::create_dir_all(root.path().join("repo/.git")).unwrap();
+    let repo = root.path().join("repo").to_str().unwrap().to_string();
+    let dir = root.path().to_str().unwrap();
+
+    assert_eq!(IconKind::of(dir, false, None), IconKind::Folder);
+    assert_eq!(IconKind::of(&repo, false, None), IconKind::Git);
+    assert_eq!(
+        IconKind::of(&repo, false, Some(ProjectType::Rust)),
+        IconKind::Project(ProjectType::Rust)
+    );
+    assert_eq!(IconKind::of("me@server:/srv", true, None), IconKind::Remote);
+}
+
+#[test]
+fn test_resolve() {
+    assert_eq!(resolve(Icons::None, IconKind::Git), None);
+    assert_eq!(
+        resolve(Icons::Ascii, IconKind::Project(ProjectType::Python)),
+        Some("py ")
+    );
+
+    // the icons of a set have the same width
+    let kinds = [
+        IconKind::Project(ProjectType::Rust),
+        IconKind::Project(ProjectType::Node),
+        IconKind::Project(ProjectType::Python),
+        IconKind::Project(ProjectType::Go),
+        IconKind::Git,
+        IconKind::Remote,
+        IconKind::Folder,
+    ];
+    for icons in [Icons::Nerdfont, Icons::Ascii] {
+        let widths: Vec<u16> = kinds
+            .iter()
+            .map(|kind| width(resolve(icons, *kind).unwrap()))
+            .collect();
+        assert!(widths.iter().all(|w| *w == widths[0]), "{:?}", icons);
+    }
+}
@@ -0,0 +1,612 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+#[cfg(test)]
+#[path = "keybindings_tests.rs"]
+mod keybindings_tests;
+
+/// The views the key bindings apply to, in the order of the help screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Scope {
+    /// Handled by the view manager, whatever the displayed view
+    Global,
+    /// The history and shortcuts tables
+    Tables,
+    /// The directory history view
+    History,
+    /// The shortcuts view
+    Shortcuts,
+    /// The suggestions panel of the directory history view
+    Suggestions,
+    /// The search field
+    Search,
+}
+
+impl Scope {
+    /// The title of the group of bindings in the help
+    pub(crate) fn title(&self) -> &'static str {
+        match self {
+            Scope::Global => "All views",
+            Scope::Tables => "History, shortcuts and files tables",
+            Scope::History => "Directory history",
+            Scope::Shortcuts => "Shortcuts",
+            Scope::Suggestions => "Suggestions panel",
+            Scope::Search => "Search",
+        }
+    }
+
+    const ALL: [Scope; 6] = [
+        Scope::Global,
+        Scope::Tables,
+        Scope::History,
+        Scope::Shortcuts,
+        Scope::Suggestions,
+        Scope::Search,
+    ];
+}
+
+/// A key, or a family of keys, triggering an action
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Key {
+    /// A key pressed without control nor alt, shift being allowed
+    Code(KeyCode),
+    Ctrl(char),
+    /// A letter pressed with control and shift
+    CtrlShift(char),
+    Alt(char),
+    /// A key which is not a character, pressed with control
+    CtrlCode(KeyCode),
+    /// A key which is not a character, pressed with alt
+    AltCode(KeyCode),
+    /// alt+1 to alt+9
+    AltDigit,
+    /// Any alt+character combination
+    AltAny,
+    /// F1 to F9
+    Function,
+}
+
+impl Key {
+    fn matches(&self, key_event: &KeyEvent) -> bool {
+        let ctrl_or_alt = key_event
+            .modifiers
+            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT);
+        match (self, key_event.code) {
+            (Key::Code(code), _) => !ctrl_or_alt && key_event.code == *code,
+            (Key::Ctrl(c), KeyCode::Char(k)) => {
+                key_event.modifiers == KeyModifiers::CONTROL && k == *c
+            }
+            (Key::CtrlShift(c), KeyCode::Char(k)) => {
+                key_event.modifiers == KeyModifiers::CONTROL | KeyModifiers::SHIFT
+                    && k.eq_ignore_ascii_case(c)
+            }
+            (Key::Alt(c), KeyCode::Char(k)) => key_event.modifiers == KeyModifiers::ALT && k == *c,
+            (Key::CtrlCode(code), _) => {
+                key_event.modifiers == KeyModifiers::CONTROL && key_event.code == *code
+            }
+            (Key::AltCode(code), _) => {
+                key_event.modifiers == KeyModifiers::ALT && key_event.code == *code
+            }
+            (Key::AltDigit, KeyCode::Char('1'..='9')) => key_event.modifiers == KeyModifiers::ALT,
+            (Key::AltAny, KeyCode::Char(_)) => key_event.modifiers == KeyModifiers::ALT,
+            (Key::Function, KeyCode::F(1..=9)) => !ctrl_or_alt,
+            _ => false,
+        }
+    }
+
+    /// The name of the key as displayed in the help
+    pub(crate) fn label(&self) -> String {
+        match self {
+            Key::Code(KeyCode::Tab) => String::from("tab"),
+            Key::Code(KeyCode::Esc) => String::from("esc"),
+            Key::Code(KeyCode::Enter) => String::from("enter"),
+            Key::Code(KeyCode::Home) => String::from("home"),
+            Key::Code(KeyCode::Up) => String::from("up"),
+            Key::Code(KeyCode::Down) => String::from("down"),
+            Key::Code(KeyCode::PageUp) => String::from("page up"),
+            Key::Code(KeyCode::PageDown) => String::from("page down"),
+            Key::Code(KeyCode::F(n)) => format!("F{}", n),
+            Key::Code(code) => code.to_string().to_lowercase(),
+            Key::Ctrl(' ') => String::from("ctrl+space"),
+            Key::Ctrl(c) => format!("ctrl+{}", c),
+            Key::CtrlShift(c) => format!("ctrl+shift+{}", c),
+            Key::Alt(c) => format!("alt+{}", c),
+            Key::CtrlCode(code) => format!("ctrl+{}", Key::Code(*code).label()),
+            Key::AltCode(code) => format!("alt+{}", Key::Code(*code).label()),
+            Key::AltDigit => String::from("alt+1..9"),
+            Key::AltAny => String::from("alt+key"),
+            Key::Function => String::from("F1..F9"),
+        }
+    }
+
+    /// The event of the key, None for a family of keys
+    pub(crate) fn event(&self) -> Option<KeyEvent> {
+        match self {
+            Key::Code(code) => Some(KeyEvent::new(*code, KeyModifiers::NONE)),
+            Key::Ctrl(c) => Some(KeyEvent::new(KeyCode::Char(*c), KeyModifiers::CONTROL)),
+            Key::CtrlShift(c) => Some(KeyEvent::new(
+                KeyCode::Char(c.to_ascii_uppercase()),
+                KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+            )),
+            Key::Alt(c) => Some(KeyEvent::new(KeyCode::Char(*c), KeyModifiers::ALT)),
+            Key::CtrlCode(code) => Some(KeyEvent::new(*code, KeyModifiers::CONTROL)),
+            Key::AltCode(code) => Some(KeyEvent::new(*code, KeyModifiers::ALT)),
+            Key::AltDigit | Key::AltAny | Key::Function => None,
+        }
+    }
+}
+
+/// The actions of the GUI bound to keys
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Action {
+    SwitchView,
+    Exit,
+    Help,
+    Configuration,
+    CommandPalette,
+    Logs,
+    Choose,
+    Top,
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+    ToggleShortcuts,
+    Delete,
+    Edit,
+    Relocate,
+    Restore,
+    Sort,
+    Details,
+    RunCommand,
+    Copy,
+    CopyShortcutName,
+    CopyLastCommand,
+    QuickSelect,
+    OpenWith,
+    TogglePaneFilter,
+    RevealSensitive,
+    ToggleTrash,
+    Sessions,
+    SuggestShortcuts,
+    ChooseSuggestion,
+    CollapseSuggestions,
+    GrowSuggestions,
+    ShrinkSuggestions,
+    ToggleFuzzy,
+    ToggleCase,
+    Narrow,
+    Widen,
+    PreviousSearch,
+    NextSearch,
+    SearchHistory,
+    WordLeft,
+    WordRight,
+    DeleteWord,
+    LineStart,
+    LineEnd,
+    ClearLine,
+}
+
+/// An action, the keys triggering it and the views it applies to
+pub(crate) struct KeyBinding {
+    pub(crate) action: Action,
+    pub(crate) scope: Scope,
+    pub(crate) keys: &'static [Key],
+    pub(crate) description: &'static str,
+}
+
+/// All the key bindings of the GUI. The first binding matching a key in a scope wins, so the
+/// families of keys (e.g. `AltAny`) come after the specific keys.
+pub(crate) const KEY_BINDINGS: &[KeyBinding] = &[
+    KeyBinding {
+        action: Action::SwitchView,
+        scope: Scope::Global,
+        keys: &[Key::Code(KeyCode::Tab)],
+        description: "switch between the views",
+    },
+    KeyBinding {
+        action: Action::Exit,
+        scope: Scope::Global,
+        keys: &[Key::Code(KeyCode::Esc), Key::Ctrl('q')],
+        description: "exit and stay in the current directory, or close a window",
+    },
+    KeyBinding {
+        action: Action::Help,
+        scope: Scope::Global,
+        keys: &[Key::Ctrl('h')],
+        description: "show this help",
+    },
+    KeyBinding {
+        action: Action::Configuration,
+        scope: Scope::Global,
+        keys: &[Key::Code(KeyCode::F(12))],
+        description: "open the configuration view",
+    },
+    KeyBinding {
+        action: Action::CommandPalette,
+        scope: Scope::Global,
+        keys: &[Key::CtrlShift('p'), Key::Ctrl(' ')],
+        description: "search the actions and run one",
+    },
+    KeyBinding {
+        action: Action::Logs,
+        scope: Scope::Global,
+        keys: &[Key::CtrlShift('l'), Key::Code(KeyCode::F(11))],
+        description: "show the last logs of the application",
+    },
+    KeyBinding {
+        action: Action::Choose,
+        scope: Scope::Tables,
+        keys: &[Key::Code(KeyCode::Enter)],
+        description: "exit the GUI and go into the selected directory",
+    },
+    KeyBinding {
+        action: Action::Top,
+        scope: Scope::Tables,
+        keys: &[Key::Code(KeyCode::Home)],
+        description: "go to the most recent directory (the top)",
+    },
+    KeyBinding {
+        action: Action::Up,
+        scope: Scope::Tables,
+        keys: &[Key::Code(KeyCode::Up)],
+        description: "select the previous entry (with shift for bigger jumps)",
+    },
+    KeyBinding {
+        action: Action::Down,
+        scope: Scope::Tables,
+        keys: &[Key::Code(KeyCode::Down)],
+        description: "select the next entry (with shift for bigger jumps)",
+    },
+    KeyBinding {
+        action: Action::PageUp,
+        scope: Scope::Tables,
+        keys: &[Key::Code(KeyCode::PageUp)],
+        description: "scroll up by page",
+    },
+    KeyBinding {
+        action: Action::PageDown,
+        scope: Scope::Tables,
+        keys: &[Key::Code(KeyCode::PageDown)],
+        description: "scroll down by page",
+    },
+    KeyBinding {
+        action: Action::ToggleShortcuts,
+        scope: Scope::Tables,
+        keys: &[Key::Ctrl('a')],
+        description: "show the full paths without shortcuts, or switch back to shortcuts",
+    },
+    KeyBinding {
+        action: Action::Delete,
+        scope: Scope::Tables,
+        keys: &[Key::Ctrl('d')],
+        description: "delete the selected entry",
+    },
+    KeyBinding {
+        action: Action::Edit,
+        scope: Scope::Tables,
+        keys: &[Key::Ctrl('e')],
+        description: "edit the selected shortcut",
+    },
+    KeyBinding {
+        action: Action::Relocate,
+        scope: Scope::Tables,
+        keys: &[Key::Ctrl('o')],
+        description: "relocate the selected shortcut to another directory",
+    },
+    KeyBinding {
+        action: Action::Restore,
+        scope: Scope::Tables,
+        keys: &[Key::Ctrl('z')],
+        description: "restore the selected path of the trash",
+    },
+    KeyBinding {
+        action: Action::Sort,
+        scope: Scope::Tables,
+        keys: &[Key::Ctrl('s')],
+        description: "change the sort column and direction",
+    },
+    KeyBinding {
+        action: Action::Details,
+        scope: Scope::Tables,
+        keys: &[Key::Ctrl('v')],
+        description: "view the selected entry in full",
+    },
+    KeyBinding {
+        action: Action::RunCommand,
+        scope: Scope::Tables,
+        keys: &[Key::Ctrl('x')],
+        description: "run a command in the selected directory and see its output",
+    },
+    KeyBinding {
+        action: Action::Copy,
+        scope: Scope::Tables,
+        keys: &[Key::Ctrl('y')],
+        description: "copy the selected path to the clipboard",
+    },
+    KeyBinding {
+        action: Action::CopyShortcutName,
+        scope: Scope::Tables,
+        keys: &[Key::Alt('y')],
+        description: "copy the shortcut name of the selected entry to the clipboard",
+    },
+    KeyBinding {
+        action: Action::CopyLastCommand,
+        scope: Scope::Tables,
+        keys: &[Key::Ctrl('k')],
+        description: "copy the last command run in the selected directory to the clipboard",
+    },
+    KeyBinding {
+        action: Action::QuickSelect,
+        scope: Scope::Tables,
+        keys: &[Key::AltDigit],
+        description: "choose one of the numbered paths of the history",
+    },
+    KeyBinding {
+        action: Action::OpenWith,
+        scope: Scope::Tables,
+        keys: &[Key::AltAny],
+        description: "run the command configured for the key on the selected directory",
+    },
+    KeyBinding {
+        action: Action::TogglePaneFilter,
+        scope: Scope::History,
+        keys: &[Key::Ctrl('p')],
+        description: "only list the paths visited from the current tmux pane, or all of them",
+    },
+    KeyBinding {
+        action: Action::RevealSensitive,
+        scope: Scope::History,
+        keys: &[Key::Ctrl('u')],
+        description: "reveal the paths marked as sensitive, or hide them again",
+    },
+    KeyBinding {
+        action: Action::ToggleTrash,
+        scope: Scope::History,
+        keys: &[Key::Ctrl('b')],
+        description: "list the deleted paths (the trash), or the history again",
+    },
+    KeyBinding {
+        action: Action::Sessions,
+        scope: Scope::History,
+        keys: &[Key::Ctrl('j')],
+        description: "browse the sessions of the history and the directories visited in each one",
+    },
+    KeyBinding {
+        action: Action::SuggestShortcuts,
+        scope: Scope::Shortcuts,
+        keys: &[Key::Ctrl('l')],
+        description: "suggest shortcuts for the directories visited often",
+    },
+    KeyBinding {
+        action: Action::ChooseSuggestion,
+        scope: Scope::Suggestions,
+        keys: &[Key::Function],
+        description: "choose one of the suggestions",
+    },
+    KeyBinding {
+        action: Action::CollapseSuggestions,
+        scope: Scope::Suggestions,
+        keys: &[Key::Ctrl('g')],
+        description: "collapse or expand the suggestions",
+    },
+    KeyBinding {
+        action: Action::GrowSuggestions,
+        scope: Scope::Suggestions,
+        keys: &[Key::CtrlCode(KeyCode::Down)],
+        description: "move the split down, growing the suggestions panel",
+    },
+    KeyBinding {
+        action: Action::ShrinkSuggestions,
+        scope: Scope::Suggestions,
+        keys: &[Key::CtrlCode(KeyCode::Up)],
+        description: "move the split up, shrinking the suggestions panel",
+    },
+    KeyBinding {
+        action: Action::ToggleFuzzy,
+        scope: Scope::Search,
+        keys: &[Key::Ctrl('f')],
+        description: "switch between exact and fuzzy search",
+    },
+    KeyBinding {
+        action: Action::ToggleCase,
+        scope: Scope::Search,
+        keys: &[Key::Ctrl('t')],
+        description: "cycle the case sensitivity: ignore, smart and respect",
+    },
+    KeyBinding {
+        action: Action::Narrow,
+        scope: Scope::Search,
+        keys: &[Key::Ctrl('n')],
+        description: "freeze the results and search within them",
+    },
+    KeyBinding {
+        action: Action::Widen,
+        scope: Scope::Search,
+        keys: &[Key::Code(KeyCode::Esc)],
+        description: "drop the last frozen search, once the results are narrowed",
+    },
+    KeyBinding {
+        action: Action::PreviousSearch,
+        scope: Scope::Search,
+        keys: &[Key::AltCode(KeyCode::Up)],
+        description: "recall the previous search of the view",
+    },
+    KeyBinding {
+        action: Action::NextSearch,
+        scope: Scope::Search,
+        keys: &[Key::AltCode(KeyCode::Down)],
+        description: "recall the next search of the view, back to the one being typed",
+    },
+    KeyBinding {
+        action: Action::SearchHistory,
+        scope: Scope::Search,
+        keys: &[Key::Ctrl('r')],
+        description: "search the past searches of the view",
+    },
+    KeyBinding {
+        action: Action::WordLeft,
+        scope: Scope::Search,
+        keys: &[Key::CtrlCode(KeyCode::Left), Key::Alt('b')],
+        description: "move the cursor to the start of the previous word",
+    },
+    KeyBinding {
+        action: Action::WordRight,
+        scope: Scope::Search,
+        keys: &[Key::CtrlCode(KeyCode::Right), Key::Alt('f')],
+        description: "move the cursor to the end of the next word",
+    },
+    KeyBinding {
+        action: Action::DeleteWord,
+        scope: Scope::Search,
+        keys: &[Key::Ctrl('w')],
+        description: "delete the word before the cursor, up to a space",
+    },
+    KeyBinding {
+        action: Action::LineEnd,
+        scope: Scope::Search,
+        keys: &[Key::Code(KeyCode::End)],
+        description: "move the cursor to the end of the search",
+    },
+];
+
+/// The readline keys of the search, taken from the other views when `readline_keys` is set
+pub(crate) const READLINE_BINDINGS: &[KeyBinding] = &[
+    KeyBinding {
+        action: Action::LineStart,
+        scope: Scope::Search,
+        keys: &[Key::Ctrl('a')],
+        description: "move the cursor to the start of the search",
+    },
+    KeyBinding {
+        action: Action::LineEnd,
+        scope: Scope::Search,
+        keys: &[Key::Ctrl('e')],
+        description: "move the cursor to the end of the search",
+    },
+    KeyBinding {
+        action: Action::ClearLine,
+        scope: Scope::Search,
+        keys: &[Key::Ctrl('u')],
+        description: "clear the search",
+    },
+];
+
+/// Whether the readline bindings are active, set once from the configuration
+static READLINE_KEYS: AtomicBool = AtomicBool::new(false);
+
+/// Give the readline keys (ctrl+a, ctrl+e and ctrl+u) to the search, instead of the actions
+/// of the tables they are bound to.
+pub(crate) fn set_readline_keys(enabled: bool) { READLINE_KEYS.store(enabled, Ordering::Relaxed); }
+
+/// The readline binding of a key, if the readline keys are active
+fn readline_binding(key_event: &KeyEvent, readline_keys: bool) -> Option<&'static KeyBinding> {
+    if !readline_keys {
+        return None;
+    }
+    READLINE_BINDINGS
+        .iter()
+        .find(|binding| binding.keys.iter().any(|key| key.matches(key_event)))
+}
+
+/// Whether a key is taken by the search from its binding in another view
+pub(crate) fn is_shadowed(key_event: &KeyEvent) -> bool {
+    readline_binding(key_event, READLINE_KEYS.load(Ordering::Relaxed)).is_some()
+}
+
+/// The action bound to a key in a scope, if any.
+pub(crate) fn action(scope: Scope, key_event: &KeyEvent) -> Option<Action> {
+    action_with(scope, key_event, READLINE_KEYS.load(Ordering::Relaxed))
+}
+
+fn action_with(scope: Scope, key_event: &KeyEvent, readline_keys: bool) -> Option<Action> {
+    if let Some(binding) = readline_binding(key_event, readline_keys) {
+        // the other views do not see the key
+        return (binding.scope == scope).then_some(binding.action);
+    }
+    // the views shown along the search receive its keys too, e.g. alt+b would also run the
+    // command of the tables bound to alt+key
+    if scope != Scope::Global
+        && scope != Scope::Search
+        && binding(Scope::Search, key_event).is_some()
+    {
+        return None;
+    }
+    binding(scope, key_event).map(|binding| binding.action)
+}
+
+/// The binding of a key in a scope, if any
+fn binding(scope: Scope, key_event: &KeyEvent) -> Option<&'static KeyBinding> {
+    KEY_BINDINGS
+        .iter()
+        .filter(|binding| binding.scope == scope)
+        .find(|binding| binding.keys.iter().any(|key| key.matches(key_event)))
+}
+
+/// The keys of a binding as displayed in the help, e.g. `esc or ctrl+q`
+pub(crate) fn keys_label(binding: &KeyBinding) -> String {
+    binding
+        .keys
+        .iter()
+        .map(Key::label)
+        .collect::<Vec<String>>()
+        .join(" or ")
+}
+
+/// The first key bound to an action, e.g. `ctrl+d`, None for an unbound action
+pub(crate) fn key_label(action: Action) -> Option<String> {
+    KEY_BINDINGS
+        .iter()
+        .find(|binding| binding.action == action)
+        .and_then(|binding| binding.keys.first())
+        .map(Key::label)
+}
+
+/// The bindings grouped by scope, in the order of the help screen, with the readline ones if
+/// they are active
+pub(crate) fn bindings_by_scope() -> Vec<(Scope, Vec<&'static KeyBinding>)> {
+    let readline_bindings = match READLINE_KEYS.load(Ordering::Relaxed) {
+        true => READLINE_BINDINGS,
+        false => &[],
+    };
+    Scope::ALL
+        .iter()
+        .map(|scope| {
+            (
+                *scope,
+                KEY_BINDINGS
+                    .iter()
+                    .chain(readline_bindings)
+                    .filter(|binding| binding.scope == *scope)
+                    .collect(),
+            )
+        })
+        .collect()
+}
+
+/// The key bindings as a plain text table, one group per scope.
+pub(crate) fn help_text() -> String {
+    let width = KEY_BINDINGS
+        .iter()
+        .map(|binding| keys_label(binding).len())
+        .max()
+        .unwrap_or_default();
+    let mut text = String::new();
+    for (idx, (scope, bindings)) in bindings_by_scope().iter().enumerate() {
+        if idx > 0 {
+            text.push('\n');
+        }
+        text.push_str(&format!("{}:\n", scope.title()));
+        for binding in bindings {
+            text.push_str(&format!(
+                "  {:width$}  {}\n",
+                keys_label(binding),
+                binding.description,
+                width = width
+            ));
+        }
+    }
+    text
+}
@@ -0,0 +1,238 @@
+use super::*;
+
+fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent { KeyEvent::new(code, modifiers) }
+
+#[test]
+fn test_action() {
+    assert_eq!(
+        action(
+            Scope::Tables,
+            &key(KeyCode::Char('d'), KeyModifiers::CONTROL)
+        ),
+        Some(Action::Delete)
+    );
+    assert_eq!(
+        action(Scope::Tables, &key(KeyCode::Char('3'), KeyModifiers::ALT)),
+        Some(Action::QuickSelect)
+    );
+    assert_eq!(
+        action(Scope::Tables, &key(KeyCode::Char('z'), KeyModifiers::ALT)),
+        Some(Action::OpenWith)
+    );
+    assert_eq!(
+        action(Scope::Tables, &key(KeyCode::Down, KeyModifiers::SHIFT)),
+        Some(Action::Down)
+    );
+    assert_eq!(
+        action(Scope::Suggestions, &key(KeyCode::F(2), KeyModifiers::NONE)),
+        Some(Action::ChooseSuggestion)
+    );
+    assert_eq!(
+        action(
+            Scope::Search,
+            &key(KeyCode::Char('f'), KeyModifiers::CONTROL)
+        ),
+        Some(Action::ToggleFuzzy)
+    );
+    assert_eq!(
+        action(
+            Scope::Search,
+            &key(KeyCode::Char('n'), KeyModifiers::CONTROL)
+        ),
+        Some(Action::Narrow)
+    );
+    assert_eq!(
+        action(Scope::Search, &key(KeyCode::Up, KeyModifiers::ALT)),
+        Some(Action::PreviousSearch)
+    );
+    // alt+up does not move the selection of the tables
+    assert_eq!(
+        action(Scope::Tables, &key(KeyCode::Up, KeyModifiers::ALT)),
+        None
+    );
+    // a plain character is typed in the search field, not bound
+    assert_eq!(
+        action(Scope::Tables, &key(KeyCode::Char('d'), KeyModifiers::NONE)),
+        None
+    );
+    // the bindings of a scope do not apply to the others
+    assert_eq!(
+        action(
+            Scope::Tables,
+            &key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        ),
+        None
+    );
+}
+
+#[test]
+fn test_labels() {
+    assert_eq!(Key::Code(KeyCode::PageDown).label(), "page down");
+    assert_eq!(Key::Code(KeyCode::F(12)).label(), "F12");
+    assert_eq!(Key::Ctrl('h').label(), "ctrl+h");
+    assert_eq!(Key::AltCode(KeyCode::Down).label(), "alt+down");
+    let exit = KEY_BINDINGS
+        .iter()
+        .find(|binding| binding.action == Action::Exit)
+        .unwrap();
+    assert_eq!(keys_label(exit), "esc or ctrl+q");
+    assert_eq!(key_label(Action::Exit).as_deref(), Some("esc"));
+    assert_eq!(key_label(Action::Delete).as_deref(), Some("ctrl+d"));
+}
+
+#[test]
+fn test_help_text() {
+    let text = help_text();
+    assert!(text.starts_with("All views:\n"));
+    assert!(text.contains("\nSuggestions panel:\n"));
+    assert!(text.contains("ctrl+d"));
+    assert!(text.contains("delete the selected entry"));
+    // every binding is listed
+    assert_eq!(
+        text.lines().filter(|line| line.starts_with("  ")).count(),
+        KEY_BINDINGS.len()
+    );
+}
+
+#[test]
+fn test_command_palette_keys() {
+    let ctrl_shift = KeyModifiers::CONTROL | KeyModifiers::SHIFT;
+    assert_eq!(
+        action(Scope::Global, &key(KeyCode::Char('P'), ctrl_shift)),
+        Some(Action::CommandPalette)
+    );
+    assert_eq!(
+        action(
+            Scope::Global,
+            &key(KeyCode::Char(' '), KeyModifiers::CONTROL)
+        ),
+        Some(Action::CommandPalette)
+    );
+    assert_eq!(
+        key_label(Action::CommandPalette).as_deref(),
+        Some("ctrl+shift+p")
+    );
+    assert_eq!(Key::Ctrl(' ').label(), "ctrl+space");
+
+    // the logs have a key for the terminals not reporting ctrl+shift
+    assert_eq!(
+        action(Scope::Global, &key(KeyCode::Char('L'), ctrl_shift)),
+        Some(Action::Logs)
+    );
+    assert_eq!(
+        action(Scope::Global, &key(KeyCode::F(11), KeyModifiers::NONE)),
+        Some(Action::Logs)
+    );
+
+    // the events replayed by the palette match their bindings
+    for binding in KEY_BINDINGS {
+        for key_event in binding.keys.iter().filter_map(Key::event) {
+            assert!(binding.keys.iter().any(|key| key.matches(&key_event)));
+        }
+    }
+    assert_eq!(Key::AltDigit.event(), None);
+}
+
+#[test]
+fn test_ctrl_code() {
+    let ctrl_down = key(KeyCode::Down, KeyModifiers::CONTROL);
+    assert_eq!(
+        action(Scope::Suggestions, &ctrl_down),
+        Some(Action::GrowSuggestions)
+    );
+    // the table still moves with the arrow alone
+    assert_eq!(
+        action(Scope::Tables, &key(KeyCode::Down, KeyModifiers::NONE)),
+        Some(Action::Down)
+    );
+    assert_eq!(action(Scope::Tables, &ctrl_down), None);
+    assert_eq!(Key::CtrlCode(KeyCode::Up).label(), "ctrl+up");
+    assert_eq!(Key::CtrlCode(KeyCode::Down).event(), Some(ctrl_down));
+}
+
+#[test]
+fn test_readline_keys() {
+    let ctrl_a = key(KeyCode::Char('a'), KeyModifiers::CONTROL);
+    assert_eq!(
+        action_with(Scope::Tables, &ctrl_a, false),
+        Some(Action::ToggleShortcuts)
+    );
+    assert_eq!(action_with(Scope::Search, &ctrl_a, false), None);
+    // the search takes the key from the tables
+    assert_eq!(action_with(Scope::Tables, &ctrl_a, true), None);
+    assert_eq!(
+        action_with(Scope::Search, &ctrl_a, true),
+        Some(Action::LineStart)
+    );
+    assert_eq!(
+        action_with(
+            Scope::Search,
+            &key(KeyCode::Char('e'), KeyModifiers::CONTROL),
+            true
+        ),
+        Some(Action::LineEnd)
+    );
+
+    // the word moves are always active
+    assert_eq!(
+        action(Scope::Search, &key(KeyCode::Char('f'), KeyModifiers::ALT)),
+        Some(Action::WordRight)
+    );
+    assert_eq!(
+        action(Scope::Search, &key(KeyCode::Left, KeyModifiers::CONTROL)),
+        Some(Action::WordLeft)
+    );
+}
+
+/// The events of a key, the families of keys being expanded
+fn key_events(key: &Key) -> Vec<KeyEvent> {
+    match key {
+        Key::AltDigit => ('1'..='9')
+            .map(|c| self::key(KeyCode::Char(c), KeyModifiers::ALT))
+            .collect(),
+        Key::AltAny => ('a'..='z')
+            .chain('0'..='9')
+            .map(|c| self::key(KeyCode::Char(c), KeyModifiers::ALT))
+            .collect(),
+        Key::Function => (1..=9)
+            .map(|n| self::key(KeyCode::F(n), KeyModifiers::NONE))
+            .collect(),
+        key => key.event().into_iter().collect(),
+    }
+}
+
+#[test]
+fn test_no_key_shared_by_the_views_shown_together() {
+    // the views of the history and the shortcuts all receive the keys
+    let groups = [
+        [
+            Scope::Tables,
+            Scope::History,
+            Scope::Suggestions,
+            Scope::Search,
+        ]
+        .as_slice(),
+        [Scope::Tables, Scope::Shortcuts, Scope::Search].as_slice(),
+    ];
+    for readline_keys in [false, true] {
+        for key_event in KEY_BINDINGS
+            .iter()
+            .chain(READLINE_BINDINGS)
+            .flat_map(|binding| binding.keys.iter().flat_map(key_events))
+        {
+            for group in groups {
+                let scopes: Vec<&Scope> = group
+                    .iter()
+                    .filter(|scope| action_with(**scope, &key_event, readline_keys).is_some())
+                    .collect();
+                assert!(
+                    scopes.len() <= 1,
+                    "{:?} (readline keys: {}) is bound in {:?}",
+                    key_event,
+                    readline_keys,
+                    scopes
+                );
+            }
+        }
+    }
+}
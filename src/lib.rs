@@ -0,0 +1,67 @@
+//! The engine of cdir: the database of the visited directories and of the shortcuts, the
+//! searches, and the smart suggestions, shared by the `cdir` command and the tools embedding
+//! it, like editor plugins.
+//!
+//! A [`store::Store`] is opened on a database file with a [`config::Config`], failing when
+//! the database cannot be opened, created or upgraded. A store is used from the thread which
+//! opened it, each thread opening its own one on the database:
+//!
+//! ```no_run
+//! use std::sync::{Arc, Mutex};
+//!
+//! use cdir_core::{config::Config, store::Store};
+//!
+//! let config = Arc::new(Mutex::new(Config::default()));
+//! let store = Store::new(std::path::Path::new("/tmp/cdir.db"), config).unwrap();
+//! store.add_path("/home/me/work").unwrap();
+//! for path in store.list_paths(0, 10, "work", false).unwrap() {
+//!     println!("{}", path.path);
+//! }
+//!
+//! std::thread::spawn(|| {
+//!     let config = Arc::new(Mutex::new(Config::default()));
+//!     let store = Store::new(std::path::Path::new("/tmp/cdir.db"), config).unwrap();
+//!     store.add_path("/home/me/notes").unwrap();
+//! })
+//! .join()
+//! .unwrap();
+//! ```
+
+/// The batch files of store operations run by `cdir exec`
+pub mod batch;
+/// The bundles of shortcuts shared by a team
+pub mod bundle;
+/// The configuration file and its defaults
+pub mod config;
+/// The detection of the container cdir runs in
+pub mod container;
+/// The formatting of the dates of the entries
+pub mod date_formatter;
+/// The sizes of the directories, computed in background tasks
+pub mod dir_size;
+/// The import of paths and shortcuts from YAML files, and the export of the shortcuts
+pub mod expimp;
+/// The reading of the imported files, from the disk or downloaded
+pub mod fetch;
+/// The user commands run on the cdir events
+pub mod hooks;
+/// The fuzzy matching of the searches
+pub mod matcher;
+/// The external commands run on a path
+pub mod open_with;
+/// The detection of the kind of project of a directory
+pub mod project_type;
+/// The `[user@]host:/path` entries
+pub mod remote;
+/// The environment variables and commands applied when jumping to a shortcut
+pub mod shortcut_hints;
+/// The shortcuts suggested for the directories visited often
+pub mod shortcut_suggestions;
+/// The aggregation of the history into usage statistics
+pub mod stats;
+/// The database of the paths, their history and the shortcuts
+pub mod store;
+/// The colors of the GUI
+pub mod theme;
+/// The event loop and the views the GUI is built on
+pub mod tui;
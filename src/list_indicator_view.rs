@@ -1,26 +1,59 @@
 use std::{
     rc::Rc,
     sync::{Arc, Mutex},
+    time::{Duration, Instant},
 };
 
-use log::debug;
 use ratatui::{
     layout::{Alignment, Position, Rect},
     prelude::Style,
     style::{Color, Stylize},
     widgets::Paragraph,
 };
+use tracing::{debug, error};
 
 use crate::{
     config::Config,
     help::Help,
     model::DataStatePayload,
-    tui::{ManagerAction, View, ViewBuilder, ViewManager, event::ApplicationEvent},
+    tui::{
+        GenericEvent, ManagerAction, View, ViewBuilder, ViewManager,
+        event::{ApplicationEvent, ViewManagerEvent},
+    },
 };
 
+/// How long a status message stays displayed
+const STATUS_MESSAGE_DURATION: Duration = Duration::from_secs(2);
+
+// "status.message"
+pub struct StatusMessagePayload {
+    pub message: String,
+}
+
+/// Publish a short message to be displayed for a while in place of the list indicator.
+pub fn publish_status_message(vm: &ViewManager, message: &str) {
+    let event = GenericEvent::ApplicationEvent(ApplicationEvent {
+        id: String::from("status.message"),
+        payload: Some(Arc::new(StatusMessagePayload {
+            message: String::from(message),
+        })),
+    });
+    if let Err(e) = vm.tx().send(event) {
+        error!("Failed to send 'status.message' event: {}", e);
+    }
+}
+
+/// Format the rows displayed in a table, e.g. `rows 1–20 of 345 `
+fn format_position((first, last, total): (usize, usize, usize)) -> String {
+    format!("rows {}\u{2013}{} of {} ", first, last, total)
+}
+
 pub struct ListIndicatorState {
     objects_type: String,
     is_empty: bool,
+    /// The (first, last, total) rows displayed, the first row being 1
+    position: Option<(usize, usize, usize)>,
+    status_message: Option<(String, Instant)>, // (message, expiration)
 }
 
 impl ListIndicatorState {
@@ -28,6 +61,8 @@ impl ListIndicatorState {
         Self {
             objects_type,
             is_empty: false,
+            position: None,
+            status_message: None,
         }
     }
 }
@@ -63,25 +98,47 @@ impl View for ListIndicatorView {
             frame.render_widget(background, area);
         }
 
-        let pa = if self.state.is_empty {
-            Paragraph::new("no entry")
-                .style(
-                    Style::default()
-                        .fg(Color::Black)
-                        .bg(config_lock.styles.free_text_area_bg_color.unwrap()),
-                )
-                .bg(Color::Red)
-                .alignment(Alignment::Center)
-        } else {
-            Paragraph::new("ctrl+h: help")
-                .style(
-                    Style::default()
-                        .bg(config_lock.styles.header_bg_color.unwrap())
-                        .fg(config_lock.styles.header_fg_color.unwrap()),
-                )
-                .alignment(Alignment::Center)
-        };
+        let status_message = self
+            .state
+            .status_message
+            .as_ref()
+            .filter(|(_, expiration)| Instant::now() < *expiration)
+            .map(|(message, _)| message.clone());
+        let show_position = status_message.is_none() && !self.state.is_empty;
+
+        let pa =
+            if let Some(message) = status_message {
+                Paragraph::new(message)
+                    .style(config_lock.styles.highlight(
+                        Style::default().fg(config_lock.styles.header_fg_color.unwrap()),
+                    ))
+                    .alignment(Alignment::Center)
+            } else if self.state.is_empty && config_lock.styles.accessible {
+                Paragraph::new("no entry")
+                    .style(config_lock.styles.header_style())
+                    .alignment(Alignment::Center)
+            } else if self.state.is_empty {
+                Paragraph::new("no entry")
+                    .style(
+                        Style::default()
+                            .fg(Color::Black)
+                            .bg(config_lock.styles.free_text_area_bg_color.unwrap()),
+                    )
+                    .bg(Color::Red)
+                    .alignment(Alignment::Center)
+            } else {
+                Paragraph::new("ctrl+h: help")
+                    .style(config_lock.styles.header_style())
+                    .alignment(Alignment::Center)
+            };
         frame.render_widget(pa, area);
+
+        if show_position && let Some(position) = self.state.position {
+            let indicator = Paragraph::new(format_position(position))
+                .style(Style::default().fg(config_lock.styles.header_fg_color.unwrap()))
+                .alignment(Alignment::Right);
+            frame.render_widget(indicator, area);
+        }
     }
     fn handle_mouse_event(
         &mut self,
@@ -108,10 +165,37 @@ impl View for ListIndicatorView {
         {
             debug!("data.payload is_empty={}", payload.is_empty);
             self.state.is_empty = payload.is_empty;
+            self.state.position = payload.total.map(|total| {
+                (
+                    payload.first + 1,
+                    payload.first + payload.length as usize,
+                    total,
+                )
+            });
 
             // let _ = self
             //     .tx
             //     .send(GenericEvent::ViewManagerEvent(ViewManagerEvent::Redraw));
+        } else if ae.id == "status.message"
+            && let Some(payload) = &ae.payload
+            && let Some(payload) = payload.downcast_ref::<StatusMessagePayload>()
+        {
+            debug!("status.message message={}", payload.message);
+            self.state.status_message = Some((
+                payload.message.clone(),
+                Instant::now() + STATUS_MESSAGE_DURATION,
+            ));
+
+            let tx = self.vm.tx();
+            let _ = tx.send(GenericEvent::ViewManagerEvent(ViewManagerEvent::Redraw));
+
+            // redraw once the message expired to remove it
+            if let Ok(handle) = tokio::runtime::Handle::try_current() {
+                handle.spawn(async move {
+                    tokio::time::sleep(STATUS_MESSAGE_DURATION).await;
+                    let _ = tx.send(GenericEvent::ViewManagerEvent(ViewManagerEvent::Redraw));
+                });
+            }
         }
     }
 }
@@ -0,0 +1,236 @@
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
+};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+use tokio::sync::broadcast;
+use tracing::{debug, level_filters::LevelFilter};
+
+use crate::{
+    logging::{LogBuffer, LogRecord},
+    theme::ThemeStyles,
+    tui::{
+        EventCaptured, GenericEvent, ManagerAction, View, ViewBuilder, ViewManager,
+        event::ViewManagerEvent,
+    },
+};
+
+#[cfg(test)]
+#[path = "log_view_tests.rs"]
+mod log_view_tests;
+
+/// How often the logs are checked for new ones while the window is open
+const TAIL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// A modal tailing the last logs of the application, filtered by their level (tab) and by the
+/// text typed, scrollable with the arrow and page keys.
+pub(crate) struct LogView {
+    styles: ThemeStyles,
+    buffer: LogBuffer,
+    /// The most verbose level shown
+    level: LevelFilter,
+    query: String,
+    /// The number of logs between the last displayed one and the last one, 0 following the
+    /// new logs
+    from_end: usize,
+    /// Number of logs displayed by the last draw
+    page_height: usize,
+    /// Whether the window is open, the tailing task stopping once it is closed
+    open: Arc<AtomicBool>,
+}
+
+impl LogView {
+    pub(crate) fn builder(
+        tx: broadcast::Sender<GenericEvent>,
+        buffer: LogBuffer,
+        styles: ThemeStyles,
+    ) -> ViewBuilder {
+        let view = Self::new(buffer, styles);
+        view.tail(tx);
+        ViewBuilder::from(Box::new(view))
+    }
+
+    fn new(buffer: LogBuffer, styles: ThemeStyles) -> Self {
+        Self {
+            styles,
+            buffer,
+            level: LevelFilter::TRACE,
+            query: String::new(),
+            from_end: 0,
+            page_height: 1,
+            open: Arc::new(AtomicBool::new(true)),
+        }
+    }
+
+    /// Redraw the window when logs are added while it is open, the redraws being requested
+    /// from the event loop as the views are not shared with the runtime.
+    fn tail(&self, tx: broadcast::Sender<GenericEvent>) {
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        let (buffer, open) = (self.buffer.clone(), self.open.clone());
+        handle.spawn(async move {
+            let mut pushed = buffer.pushed();
+            while open.load(Ordering::Relaxed) {
+                tokio::time::sleep(TAIL_INTERVAL).await;
+                if buffer.pushed() != pushed {
+                    pushed = buffer.pushed();
+                    let _ = tx.send(GenericEvent::ViewManagerEvent(ViewManagerEvent::Redraw));
+                }
+            }
+        });
+    }
+
+    /// The logs of the level shown and matching the query, ignoring the case, the oldest first
+    fn filtered(&self) -> Vec<LogRecord> {
+        let query = self.query.to_lowercase();
+        self.buffer
+            .records()
+            .into_iter()
+            .filter(|record| record.level <= self.level)
+            .filter(|record| {
+                query.is_empty()
+                    || record.message.to_lowercase().contains(&query)
+                    || record.target.to_lowercase().contains(&query)
+            })
+            .collect()
+    }
+
+    /// The next level shown: all, debug, info, warn, error, then all again
+    fn next_level(level: LevelFilter) -> LevelFilter {
+        match level {
+            LevelFilter::TRACE => LevelFilter::DEBUG,
+            LevelFilter::DEBUG => LevelFilter::INFO,
+            LevelFilter::INFO => LevelFilter::WARN,
+            LevelFilter::WARN => LevelFilter::ERROR,
+            _ => LevelFilter::TRACE,
+        }
+    }
+
+    fn level_label(&self) -> String {
+        match self.level {
+            LevelFilter::TRACE => String::from("all levels"),
+            LevelFilter::ERROR => String::from("errors"),
+            level => format!("{} and above", level.to_string().to_lowercase()),
+        }
+    }
+
+    fn scroll_by(&mut self, delta: isize, count: usize) {
+        let max = count.saturating_sub(self.page_height);
+        self.from_end = self.from_end.saturating_add_signed(-delta).min(max);
+    }
+
+    fn record_line(&self, record: &LogRecord) -> Line<'static> {
+        Line::from(vec![
+            Span::styled(
+                record.time.format("%H:%M:%S%.3f ").to_string(),
+                self.styles.date_style,
+            ),
+            Span::styled(format!("{:>5} ", record.level), self.styles.text_em_style),
+            Span::styled(format!("{}: ", record.target), self.styles.date_style),
+            Span::styled(record.message.clone(), self.styles.text_style),
+        ])
+    }
+}
+
+impl Drop for LogView {
+    fn drop(&mut self) { self.open.store(false, Ordering::Relaxed); }
+}
+
+impl View for LogView {
+    fn draw(&mut self, frame: &mut ratatui::Frame, _area: Rect, _active: bool) {
+        debug!("Drawing the logs");
+        let area = frame.area();
+        let width = (area.width.saturating_mul(9) / 10).max(20).min(area.width);
+        let height = (area.height.saturating_mul(4) / 5).max(5).min(area.height);
+        let modal_area = ViewManager::centered_rect(area, width, height);
+        // the borders and the query take 3 lines
+        self.page_height = (height.saturating_sub(3) as usize).max(1);
+
+        frame.render_widget(Clear, modal_area);
+        if let Some(bg_color) = &self.styles.background_color {
+            let background = Paragraph::new("").style(Style::default().bg(*bg_color));
+            frame.render_widget(background, modal_area);
+        }
+
+        let records = self.filtered();
+        self.from_end = self
+            .from_end
+            .min(records.len().saturating_sub(self.page_height));
+        let end = records.len() - self.from_end;
+        let start = end.saturating_sub(self.page_height);
+
+        let mut lines = vec![Line::from(vec![
+            Span::styled("> ", self.styles.date_style),
+            Span::styled(self.query.clone(), self.styles.text_em_style),
+        ])];
+        lines.extend(
+            records[start..end]
+                .iter()
+                .map(|record| self.record_line(record)),
+        );
+
+        let mut block = Block::default()
+            .title(Span::styled(" Logs ", self.styles.title_style))
+            .title_bottom(Span::styled(
+                format!(" {} (tab) · {}/{} ", self.level_label(), end, records.len()),
+                self.styles.text_style,
+            ))
+            .borders(Borders::ALL);
+        if let Some(border_color) = self.styles.border_color {
+            block = block.border_style(Style::default().fg(border_color));
+        }
+        frame.render_widget(Paragraph::new(lines).block(block), modal_area);
+        frame.set_cursor_position((
+            modal_area.x + 3 + self.query.chars().count() as u16,
+            modal_area.y + 1,
+        ));
+    }
+
+    fn handle_key_event(&mut self, key_event: KeyEvent) -> (EventCaptured, ManagerAction) {
+        let page = self.page_height as isize;
+        let count = self.filtered().len();
+        match key_event.code {
+            KeyCode::Enter => {
+                return (
+                    EventCaptured::Yes,
+                    ManagerAction::new(false).with_close(true),
+                );
+            }
+            KeyCode::Tab => {
+                self.level = Self::next_level(self.level);
+                self.from_end = 0;
+            }
+            KeyCode::Down => self.scroll_by(1, count),
+            KeyCode::Up => self.scroll_by(-1, count),
+            KeyCode::PageDown => self.scroll_by(page, count),
+            KeyCode::PageUp => self.scroll_by(-page, count),
+            KeyCode::Home => self.from_end = count.saturating_sub(self.page_height),
+            KeyCode::End => self.from_end = 0,
+            KeyCode::Backspace => {
+                self.query.pop();
+                self.from_end = 0;
+            }
+            KeyCode::Char(c)
+                if !key_event
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                self.query.push(c);
+                self.from_end = 0;
+            }
+            _ => return (EventCaptured::Yes, ManagerAction::new(false)),
+        }
+        (EventCaptured::Yes, ManagerAction::new(true))
+    }
+}
@@ -0,0 +1,91 @@
+use chrono::Local;
+use tracing::Level;
+
+use super::*;
+
+fn key(code: KeyCode) -> KeyEvent { KeyEvent::new(code, KeyModifiers::NONE) }
+
+fn record(level: Level, message: &str) -> LogRecord {
+    LogRecord {
+        time: Local::now(),
+        level,
+        target: String::from("cdir_core::store"),
+        message: String::from(message),
+    }
+}
+
+fn view() -> LogView {
+    let buffer = LogBuffer::new(10);
+    buffer.push(record(Level::INFO, "Starting"));
+    buffer.push(record(Level::DEBUG, "Listing paths pos=0"));
+    buffer.push(record(Level::WARN, "Database busy"));
+    buffer.push(record(Level::ERROR, "Failed to save the path"));
+    LogView::new(buffer, ThemeStyles::default())
+}
+
+fn messages(view: &LogView) -> Vec<String> {
+    view.filtered()
+        .into_iter()
+        .map(|record| record.message)
+        .collect()
+}
+
+#[test]
+fn test_level_filter() {
+    let mut view = view();
+    assert_eq!(messages(&view).len(), 4);
+    view.handle_key_event(key(KeyCode::Tab));
+    assert_eq!(messages(&view).len(), 4);
+    view.handle_key_event(key(KeyCode::Tab));
+    assert_eq!(
+        messages(&view),
+        ["Starting", "Database busy", "Failed to save the path"]
+    );
+    view.handle_key_event(key(KeyCode::Tab));
+    view.handle_key_event(key(KeyCode::Tab));
+    assert_eq!(view.level_label(), "errors");
+    assert_eq!(messages(&view), ["Failed to save the path"]);
+    // back to all the levels
+    view.handle_key_event(key(KeyCode::Tab));
+    assert_eq!(view.level_label(), "all levels");
+    assert_eq!(messages(&view).len(), 4);
+}
+
+#[test]
+fn test_search() {
+    let mut view = view();
+    for c in "PATH".chars() {
+        view.handle_key_event(key(KeyCode::Char(c)));
+    }
+    assert_eq!(
+        messages(&view),
+        ["Listing paths pos=0", "Failed to save the path"]
+    );
+    // the target matches too
+    view.query = String::from("store");
+    assert_eq!(messages(&view).len(), 4);
+    view.handle_key_event(key(KeyCode::Backspace));
+    assert_eq!(view.query, "stor");
+}
+
+#[test]
+fn test_scroll() {
+    let mut view = view();
+    view.page_height = 2;
+    view.handle_key_event(key(KeyCode::Up));
+    assert_eq!(view.from_end, 1);
+    view.handle_key_event(key(KeyCode::PageUp));
+    assert_eq!(view.from_end, 2);
+    view.handle_key_event(key(KeyCode::End));
+    assert_eq!(view.from_end, 0);
+    view.handle_key_event(key(KeyCode::Down));
+    assert_eq!(view.from_end, 0);
+    view.handle_key_event(key(KeyCode::Home));
+    assert_eq!(view.from_end, 2);
+    // a search follows the new logs again
+    view.handle_key_event(key(KeyCode::Char('a')));
+    assert_eq!(view.from_end, 0);
+
+    let (_, action) = view.handle_key_event(key(KeyCode::Enter));
+    assert!(action.close());
+}
@@ -0,0 +1,318 @@
+use std::{
+    collections::VecDeque,
+    fmt::{self, Write as _},
+    fs::{self, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::{
+        Arc, LazyLock, Mutex,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
+};
+
+use cdir_core::config::{Config, LogFormat};
+use chrono::{DateTime, Local};
+use tracing::{
+    Event, Level, Subscriber,
+    field::{Field, Visit},
+    level_filters::LevelFilter,
+};
+use tracing_subscriber::{
+    EnvFilter, Layer, Registry,
+    fmt::{FmtContext, FormatEvent, FormatFields, format::Writer, time::FormatTime},
+    layer::{Context, SubscriberExt},
+    registry::LookupSpan,
+    util::SubscriberInitExt,
+};
+
+use crate::profile::ProfileLayer;
+
+#[cfg(test)]
+#[path = "logging_tests.rs"]
+mod logging_tests;
+
+/// The environment variable replacing the levels of the configuration, e.g.
+/// `CDIR_LOG=info,cdir_core::store=debug`
+pub(crate) const LOG_ENV: &str = "CDIR_LOG";
+
+/// The size from which the log file is rolled
+const LOG_FILE_LIMIT: u64 = 100 * 1024;
+
+/// The number of rolled log files kept, e.g. `cdir_1.log` to `cdir_5.log`
+const LOG_FILE_COUNT: usize = 5;
+
+/// The number of the last logs kept in memory for the log viewer of the GUI
+const LOG_BUFFER_SIZE: usize = 2000;
+
+/// Whether the errors are mirrored to the standard error
+static STDERR_MIRRORED: AtomicBool = AtomicBool::new(false);
+
+/// The last logs of the process, see `log_buffer`
+static LOG_BUFFER: LazyLock<LogBuffer> = LazyLock::new(|| LogBuffer::new(LOG_BUFFER_SIZE));
+
+/// A layer of the subscriber of the process
+type BoxedLayer = Box<dyn Layer<Registry> + Send + Sync>;
+
+/// How the logs of a run differ from the levels of the configuration
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub(crate) struct LogOptions {
+    /// The level of all the targets, the ones of the configuration when None
+    pub level: Option<LevelFilter>,
+    /// The lowest level mirrored to the standard error, nothing being mirrored when None
+    pub stderr_level: Option<LevelFilter>,
+}
+
+impl LogOptions {
+    /// The options of the `-v`/`-vv` and `-q` flags, the warnings and the errors being
+    /// mirrored to the standard error when `stderr` is set.
+    pub(crate) fn new(verbose: u8, quiet: bool, stderr: bool) -> LogOptions {
+        let level = match (quiet, verbose) {
+            (true, _) => Some(LevelFilter::ERROR),
+            (false, 0) => None,
+            (false, 1) => Some(LevelFilter::DEBUG),
+            (false, _) => Some(LevelFilter::TRACE),
+        };
+        LogOptions {
+            level,
+            stderr_level: stderr.then(|| level.unwrap_or(LevelFilter::WARN)),
+        }
+    }
+}
+
+/// The filter of the log file: the level of the flags, else the directives of `CDIR_LOG`,
+/// else the levels of the configuration, the invalid directives being ignored.
+fn build_filter(levels: &str, env: Option<&str>, options: LogOptions) -> EnvFilter {
+    let directives = match (options.level, env) {
+        (Some(level), _) => level.to_string(),
+        (None, Some(env)) if !env.trim().is_empty() => env.to_string(),
+        (None, _) => levels.to_string(),
+    };
+    EnvFilter::builder()
+        .with_default_directive(LevelFilter::INFO.into())
+        .parse_lossy(directives)
+}
+
+/// The path of the `index`th rolled log file, e.g. `cdir_2.log` for `cdir.log`
+fn rolled_path(path: &Path, index: usize) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match path.extension() {
+        Some(extension) => format!("{}_{}.{}", stem, index, extension.to_string_lossy()),
+        None => format!("{}_{}", stem, index),
+    };
+    path.with_file_name(name)
+}
+
+/// The log file, rolled to `cdir_1.log` once it reaches `LOG_FILE_LIMIT`, the previous rolled
+/// files being shifted and the oldest one removed, so that the space taken is limited.
+struct RollingFile {
+    path: PathBuf,
+    file: File,
+    size: u64,
+}
+
+impl RollingFile {
+    fn open(path: &Path) -> io::Result<RollingFile> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let file = File::options().create(true).append(true).open(path)?;
+        let size = file.metadata()?.len();
+        Ok(RollingFile {
+            path: path.to_path_buf(),
+            file,
+            size,
+        })
+    }
+
+    fn roll(&mut self) -> io::Result<()> {
+        for index in (1..LOG_FILE_COUNT).rev() {
+            let rolled = rolled_path(&self.path, index);
+            if rolled.exists() {
+                fs::rename(&rolled, rolled_path(&self.path, index + 1))?;
+            }
+        }
+        fs::rename(&self.path, rolled_path(&self.path, 1))?;
+        *self = RollingFile::open(&self.path)?;
+        Ok(())
+    }
+}
+
+impl Write for RollingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.size > 0 && self.size + buf.len() as u64 > LOG_FILE_LIMIT {
+            self.roll()?;
+        }
+        let written = self.file.write(buf)?;
+        self.size += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> { self.file.flush() }
+}
+
+/// The local time of the lines of the log file, e.g. `2024-01-02 14:05:12.345`
+struct LocalTime;
+
+impl FormatTime for LocalTime {
+    fn format_time(&self, w: &mut Writer<'_>) -> fmt::Result {
+        write!(w, "{}", Local::now().format("%Y-%m-%d %H:%M:%S%.3f"))
+    }
+}
+
+/// The format of the logs mirrored to the standard error, e.g. `WARN: Skipping entry 2`
+struct StderrFormat;
+
+impl<S, N> FormatEvent<S, N> for StderrFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        write!(writer, "{}: ", event.metadata().level())?;
+        ctx.field_format().format_fields(writer.by_ref(), event)?;
+        writeln!(writer)
+    }
+}
+
+/// A log kept in memory, see `LogBuffer`
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct LogRecord {
+    pub time: DateTime<Local>,
+    pub level: Level,
+    pub target: String,
+    /// The message followed by the other fields of the event, e.g. `Saving path=/tmp`
+    pub message: String,
+}
+
+/// Writes the message of an event, then its other fields
+struct MessageVisitor<'a> {
+    message: &'a mut String,
+}
+
+impl Visit for MessageVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        match field.name() {
+            "message" => {
+                let _ = write!(self.message, "{:?}", value);
+            }
+            // the metadata of the events of the `log` crate
+            name if name.starts_with("log.") => {}
+            name => {
+                let _ = write!(self.message, " {}={:?}", name, value);
+            }
+        }
+    }
+}
+
+/// The last logs, the oldest being dropped beyond the capacity, shown by the log viewer of the
+/// GUI. The buffer is shared by its clones.
+#[derive(Debug, Clone)]
+pub(crate) struct LogBuffer {
+    records: Arc<Mutex<VecDeque<LogRecord>>>,
+    capacity: usize,
+    /// The number of logs pushed since the start, including the dropped ones
+    pushed: Arc<AtomicUsize>,
+}
+
+impl LogBuffer {
+    pub(crate) fn new(capacity: usize) -> LogBuffer {
+        LogBuffer {
+            records: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+            capacity,
+            pushed: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    pub(crate) fn push(&self, record: LogRecord) {
+        let mut records = self.records.lock().unwrap();
+        if records.len() == self.capacity {
+            records.pop_front();
+        }
+        records.push_back(record);
+        self.pushed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// The number of logs pushed since the start, changing whenever a log is pushed
+    pub(crate) fn pushed(&self) -> usize { self.pushed.load(Ordering::Relaxed) }
+
+    /// The logs kept, the oldest first
+    pub(crate) fn records(&self) -> Vec<LogRecord> {
+        self.records.lock().unwrap().iter().cloned().collect()
+    }
+}
+
+impl<S: Subscriber> Layer<S> for LogBuffer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut message = String::new();
+        event.record(&mut MessageVisitor {
+            message: &mut message,
+        });
+        let metadata = event.metadata();
+        self.push(LogRecord {
+            time: Local::now(),
+            level: *metadata.level(),
+            target: metadata.target().to_string(),
+            message,
+        });
+    }
+}
+
+/// The last logs of the process, with the levels of the log file even when there is none
+pub(crate) fn log_buffer() -> LogBuffer { LOG_BUFFER.clone() }
+
+/// The layer writing the log file in `format`, filtered by `filter`
+fn file_layer(file: RollingFile, format: LogFormat, filter: EnvFilter) -> BoxedLayer {
+    let layer = tracing_subscriber::fmt::layer()
+        .with_writer(Mutex::new(file))
+        .with_ansi(false)
+        .with_thread_ids(true)
+        .with_file(true)
+        .with_line_number(true);
+    match format {
+        LogFormat::Text => layer.with_timer(LocalTime).with_filter(filter).boxed(),
+        LogFormat::Json => layer.json().with_filter(filter).boxed(),
+    }
+}
+
+/// Initialize the logs from the log options of the configuration and `options`, the last ones
+/// being kept in memory for the log viewer, and the spans being also recorded by `profile`
+/// when given.
+pub(crate) fn initialize(config: &Config, options: LogOptions, profile: Option<ProfileLayer>) {
+    let env = std::env::var(LOG_ENV).ok();
+    let filter = || build_filter(&config.log_level, env.as_deref(), options);
+    let mut layers: Vec<BoxedLayer> = vec![log_buffer().with_filter(filter()).boxed()];
+    if let Some(path) = &config.log_file {
+        match RollingFile::open(path) {
+            Ok(file) => layers.push(file_layer(file, config.log_format, filter())),
+            Err(e) => eprintln!("warning: cannot open the log file {:?}: {}", path, e),
+        }
+    }
+    if let Some(stderr_level) = options.stderr_level {
+        layers.push(
+            tracing_subscriber::fmt::layer()
+                .with_writer(io::stderr)
+                .event_format(StderrFormat)
+                .with_filter(stderr_level)
+                .boxed(),
+        );
+    }
+    if let Some(profile) = profile {
+        // the steps of the run, not the detailed spans of the debug logs
+        layers.push(profile.with_filter(LevelFilter::INFO).boxed());
+    }
+    if tracing_subscriber::registry()
+        .with(layers)
+        .try_init()
+        .is_ok()
+    {
+        STDERR_MIRRORED.store(options.stderr_level.is_some(), Ordering::Relaxed);
+    }
+}
+
+/// Whether the errors logged are also written to the standard error, see `initialize`.
+pub(crate) fn mirrors_to_stderr() -> bool { STDERR_MIRRORED.load(Ordering::Relaxed) }
@@ -0,0 +1,111 @@
+use super::*;
+
+#[test]
+fn test_log_options() {
+    assert_eq!(
+        LogOptions::new(0, false, true),
+        LogOptions {
+            level: None,
+            stderr_level: Some(LevelFilter::WARN),
+        }
+    );
+    assert_eq!(
+        LogOptions::new(2, false, false),
+        LogOptions {
+            level: Some(LevelFilter::TRACE),
+            stderr_level: None,
+        }
+    );
+    assert_eq!(
+        LogOptions::new(0, true, true).stderr_level,
+        Some(LevelFilter::ERROR)
+    );
+    assert_eq!(
+        LogOptions::new(1, false, true).stderr_level,
+        Some(LevelFilter::DEBUG)
+    );
+}
+
+#[test]
+fn test_build_filter_keeps_the_configuration() {
+    let filter = build_filter("warn,cdir_core::store=debug", None, LogOptions::default());
+    assert_eq!(filter.max_level_hint(), Some(LevelFilter::DEBUG));
+    assert!(filter.to_string().contains("cdir_core::store=debug"));
+
+    // the invalid directives are ignored
+    let filter = build_filter("warn,=nope", Some(" "), LogOptions::default());
+    assert_eq!(filter.max_level_hint(), Some(LevelFilter::WARN));
+}
+
+#[test]
+fn test_build_filter_overrides_the_levels() {
+    let filter = build_filter(
+        "warn",
+        Some("cdir_core::store=trace"),
+        LogOptions::default(),
+    );
+    assert_eq!(filter.max_level_hint(), Some(LevelFilter::TRACE));
+    assert!(!filter.to_string().contains("warn"));
+
+    // the flags win over the environment
+    let filter = build_filter("warn", Some("trace"), LogOptions::new(0, true, false));
+    assert_eq!(filter.max_level_hint(), Some(LevelFilter::ERROR));
+}
+
+#[test]
+fn test_rolled_path() {
+    assert_eq!(
+        rolled_path(Path::new("/data/cdir.log"), 2),
+        PathBuf::from("/data/cdir_2.log")
+    );
+    assert_eq!(
+        rolled_path(Path::new("/data/cdir"), 1),
+        PathBuf::from("/data/cdir_1")
+    );
+}
+
+#[test]
+fn test_rolling_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("logs/cdir.log");
+    let line = vec![b'x'; LOG_FILE_LIMIT as usize / 2];
+
+    let mut file = RollingFile::open(&path).unwrap();
+    for _ in 0..(LOG_FILE_COUNT + 1) * 2 {
+        file.write_all(&line).unwrap();
+    }
+    file.write_all(&line).unwrap();
+
+    assert_eq!(fs::metadata(&path).unwrap().len(), line.len() as u64);
+    for index in 1..=LOG_FILE_COUNT {
+        assert_eq!(
+            fs::metadata(rolled_path(&path, index)).unwrap().len(),
+            2 * line.len() as u64
+        );
+    }
+    assert!(!rolled_path(&path, LOG_FILE_COUNT + 1).exists());
+
+    // the size of the file already written counts
+    let file = RollingFile::open(&path).unwrap();
+    assert_eq!(file.size, line.len() as u64);
+}
+
+#[test]
+fn test_log_buffer() {
+    let buffer = LogBuffer::new(2);
+    let subscriber = tracing_subscriber::registry().with(buffer.clone());
+    tracing::subscriber::with_default(subscriber, || {
+        tracing::info!("Starting");
+        tracing::debug!(pos = 3, "Listing {}", "paths");
+        tracing::warn!("Database busy");
+    });
+
+    // the oldest log is dropped
+    let records = buffer.records();
+    assert_eq!(buffer.pushed(), 3);
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].level, Level::DEBUG);
+    assert_eq!(records[0].message, "Listing paths pos=3");
+    assert_eq!(records[1].message, "Database busy");
+    assert!(records[1].target.ends_with("logging_tests"));
+}
@@ -1,38 +1,73 @@
-mod config;
+mod cli_error;
+mod clipboard;
+mod command_output_view;
+mod command_palette;
+mod command_prompt;
 mod config_button;
 mod config_view;
 mod confirmation;
-mod expimp;
+mod details_view;
+mod files_view_container;
 mod gui;
 mod help;
+mod history_export;
 mod history_view_container;
+mod icons;
+mod keybindings;
 mod list_indicator_view;
+mod log_view;
+mod logging;
+mod menu_bar;
 mod model;
+mod path_picker;
+mod picker;
+mod profile;
+mod prompt;
+mod report;
+mod search_history_view;
 mod search_text_view;
+mod serve;
+mod sessions_view;
 mod shortcut_editor;
+mod shortcut_suggestions_view;
 mod shortcut_view_container;
-mod store;
+mod status_bar;
+mod suggestions_view;
 mod tableview;
 mod text_to_ansi;
-mod theme;
-mod tui;
 
 use std::{
-    error::Error,
-    fs::File,
-    io::Write,
+    fs::{self, File},
+    io::{BufRead, Write},
     path::{Path, PathBuf},
+    process::ExitCode,
+    str::FromStr,
     sync::{Arc, Mutex},
 };
 
-use clap::{Parser, Subcommand};
+use cdir_core::{
+    batch, bundle, config, date_formatter, dir_size, expimp, fetch, hooks, matcher, open_with,
+    project_type, remote, shortcut_hints, shortcut_suggestions, stats, store, theme, tui,
+};
+use clap::{ArgAction, Parser, Subcommand};
 use config::Config;
-use expimp::load_paths_from_yaml;
-use log::{debug, error, info};
+use expimp::load_paths_from_file;
 use ratatui::text::Text;
 use store::Store;
+use tracing::{debug, error, info};
+
+use crate::{
+    cli_error::{CliError, ErrorKind},
+    expimp::{FileFormat, ImportOptions, ImportSummary, load_shortcuts_from_file},
+    hooks::Hook,
+    picker::{Candidate, OutputFormat},
+    remote::RemotePath,
+    store::Shortcut,
+    text_to_ansi::text_to_ansi,
+};
 
-use crate::{expimp::load_shortcuts_from_yaml, store::Shortcut, text_to_ansi::text_to_ansi};
+/// The environment variable identifying the tmux pane
+const TMUX_PANE_VARIABLE: &str = "TMUX_PANE";
 
 /// cdir helps you to switch quickly and easily between directories
 #[derive(Parser, Debug)]
@@ -42,6 +77,28 @@ struct Args {
     #[arg(short, long)]
     config_file: Option<std::path::PathBuf>,
 
+    /// Print what the command would change, without changing the database
+    #[arg(long, global = true)]
+    dry_run: bool,
+
+    /// Log the debug messages, or all of them when repeated, to the log file and the standard
+    /// error
+    #[arg(short, long, action = ArgAction::Count, conflicts_with = "quiet")]
+    verbose: u8,
+
+    /// Only log the errors
+    #[arg(short, long)]
+    quiet: bool,
+
+    /// Do not ask for the confirmation of the commands deleting entries
+    #[arg(short, long, global = true)]
+    yes: bool,
+
+    /// Print how long the steps of the run took on exit, e.g. the opening of the database or
+    /// the first draw of the GUI
+    #[arg(long, global = true)]
+    profile: bool,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
@@ -49,27 +106,203 @@ struct Args {
 #[derive(Subcommand, Debug)]
 enum Commands {
     /// Launch the GUI
-    Gui { filename: Option<String> },
+    Gui {
+        filename: Option<String>,
+        /// Open the GUI with the search field pre-populated with this text
+        #[arg(short, long)]
+        query: Option<String>,
+        /// Search the query with a fuzzy match instead of an exact one
+        #[arg(short, long, requires = "query")]
+        fuzzy: bool,
+        /// Return the match immediately, without displaying the GUI, if the query matches a single path
+        #[arg(short, long, requires = "query")]
+        auto: bool,
+        /// Output a shell script changing to the selected directory and applying its shortcut
+        /// hints, or opening the selected file in $EDITOR
+        #[arg(long)]
+        eval: bool,
+        /// The format of the selected path, `vim` and `json` adding its shortcut and score
+        #[arg(long, value_enum, default_value_t, conflicts_with = "eval")]
+        format: OutputFormat,
+        /// Open the GUI on this view instead of the one of the last session
+        #[arg(long, value_enum)]
+        view: Option<gui::StartView>,
+        /// Only show the view opened on, whose entries can be chosen but not changed
+        #[arg(long)]
+        select_only: bool,
+        /// Start without restoring the session and the first page of the previous run, e.g.
+        /// after a crash
+        #[arg(long)]
+        safe_mode: bool,
+    },
     /// Print the path to the configuration file
     ConfigFile,
     /// Add a directory path
-    AddPath { path: String },
-    /// Import a path file
-    ImportPaths { filename: String },
+    AddPath {
+        #[arg(required_unless_present = "batch")]
+        path: Option<String>,
+        /// Read newline-separated paths from the standard input and add them at once
+        #[arg(long, conflicts_with = "path")]
+        batch: bool,
+    },
+    /// Record a file opened now, for the recent files view
+    AddFile { path: String },
+    /// Record the last command run in a directory, shown under its row in the history
+    AddCmd { path: String, command: String },
+    /// Import a path file, YAML, JSON or TOML, a local path or an https:// URL
+    ImportPaths {
+        filename: String,
+        /// The format of the file, detected from its extension when not set
+        #[arg(long, value_parser = FileFormat::from_str)]
+        format: Option<FileFormat>,
+        /// Only import the file if its SHA-256 is this one
+        #[arg(long, value_name = "CHECKSUM")]
+        sha256: Option<String>,
+        /// Check the entries of the file without importing them
+        #[arg(long)]
+        validate_only: bool,
+        /// Write the invalid entries to this file, to fix and import them again
+        #[arg(long, value_name = "FILE")]
+        rejects: Option<PathBuf>,
+    },
+    /// Export the history as a path file, printed when no file is given
+    ExportPaths {
+        filename: Option<String>,
+        /// The format of the file, detected from its extension when not set, YAML by default
+        #[arg(long, value_parser = FileFormat::from_str)]
+        format: Option<FileFormat>,
+        /// Also export the visits of the sensitive paths
+        #[arg(long)]
+        include_sensitive: bool,
+    },
+    /// Merge the paths, the history and the shortcuts of another cdir database
+    Merge {
+        /// The database file to merge, left unchanged
+        #[arg(long)]
+        from: String,
+    },
+    /// Mark a path as sensitive, hiding it from the listings until they are revealed
+    MarkSensitive {
+        path: String,
+        /// Clear the mark instead
+        #[arg(long)]
+        clear: bool,
+    },
+    /// Declare two paths as the same directory, grouping them in the history
+    LinkPaths {
+        /// the path the alias is equivalent to
+        path: String,
+        /// the other form of the path
+        alias: String,
+    },
     /// Add a shortcut
     AddShortcut {
         name: String,
         path: String,
         description: Option<String>,
+        /// An environment variable exported after jumping into the shortcut, as NAME=value
+        #[arg(long = "env", value_name = "NAME=value")]
+        env: Vec<String>,
+        /// A command run after jumping into the shortcut
+        #[arg(long, value_name = "COMMAND")]
+        post_cd: Option<String>,
+        /// Overwrite the shortcut of the same name, if any
+        #[arg(long)]
+        force: bool,
+        /// Add the shortcut even if its directory does not exist
+        #[arg(long)]
+        allow_missing: bool,
     },
     /// Delete a shortcut
     DeleteShortcut { name: String },
+    /// Rename a shortcut, keeping its path, description and hints
+    RenameShortcut {
+        /// The current name of the shortcut
+        old: String,
+        /// The new name, which must not be the one of another shortcut
+        new: String,
+    },
+    /// Change the path or the description of a shortcut
+    #[command(group = clap::ArgGroup::new("changes").required(true).multiple(true))]
+    UpdateShortcut {
+        name: String,
+        /// The new path of the shortcut
+        #[arg(long, group = "changes")]
+        path: Option<String>,
+        /// The new description of the shortcut, an empty one removing it
+        #[arg(long, group = "changes")]
+        description: Option<String>,
+        /// Change the path even if its directory does not exist
+        #[arg(long, requires = "path")]
+        allow_missing: bool,
+    },
+    /// Check the directories of the shortcuts and print the broken shortcuts
+    CheckShortcuts,
+    /// Suggest shortcuts for the directories visited often, printing them unless accepted
+    SuggestShortcuts {
+        /// Add the suggested shortcut of this name, can be repeated
+        #[arg(long, value_name = "NAME")]
+        accept: Vec<String>,
+        /// Add all the suggested shortcuts
+        #[arg(long, conflicts_with = "accept")]
+        all: bool,
+    },
     /// Print a shortcut
-    PrintShortcut { name: String },
-    /// Import a shortcuts file
-    ImportShortcuts { filename: String },
+    PrintShortcut {
+        name: String,
+        /// Output a shell script changing to the shortcut and applying its hints
+        #[arg(long)]
+        eval: bool,
+    },
+    /// Print the directory of a shortcut followed by a relative path, e.g. docs/reports/2024
+    Expand {
+        /// A shortcut name, optionally followed by a `/` and a path inside its directory
+        text: String,
+        /// Output a shell script changing to the directory and applying the shortcut hints
+        #[arg(long)]
+        eval: bool,
+    },
+    /// Import a shortcuts file, YAML, JSON or TOML, a local path or an https:// URL
+    ImportShortcuts {
+        filename: String,
+        /// The format of the file, detected from its extension when not set
+        #[arg(long, value_parser = FileFormat::from_str)]
+        format: Option<FileFormat>,
+        /// Only import the file if its SHA-256 is this one
+        #[arg(long, value_name = "CHECKSUM")]
+        sha256: Option<String>,
+        /// Check the entries of the file without importing them
+        #[arg(long)]
+        validate_only: bool,
+        /// Write the invalid entries to this file, to fix and import them again
+        #[arg(long, value_name = "FILE")]
+        rejects: Option<PathBuf>,
+    },
+    /// Export the shortcuts as a shortcuts file, printed when no file is given
+    ExportShortcuts {
+        filename: Option<String>,
+        /// The format of the file, detected from its extension when not set, YAML by default
+        #[arg(long, value_parser = FileFormat::from_str)]
+        format: Option<FileFormat>,
+    },
     /// Print last paths
-    Lasts,
+    Lasts {
+        /// Only print the paths visited from the current tmux pane
+        #[arg(long)]
+        pane: bool,
+        /// Only print the paths visited from this host
+        #[arg(long)]
+        host: Option<String>,
+        /// Also print the paths marked as sensitive
+        #[arg(long)]
+        include_sensitive: bool,
+        /// Print each visit rather than each path
+        #[arg(long)]
+        visits: bool,
+        /// Group the consecutive visits to the same path, with their count
+        #[arg(long, requires = "visits")]
+        collapse_repeats: bool,
+    },
     /// Pretty print a path using shortcuts
     PrettyPrintPath {
         /// the path to pretty print
@@ -79,54 +312,434 @@ enum Commands {
         /// if set, the maximum width of the string
         max_width: Option<u16>,
     },
+    /// Print the paths matching a search, for an editor to offer them
+    Picker {
+        /// The search, all the paths being printed when empty
+        #[arg(short, long, default_value = "")]
+        query: String,
+        /// Search the query with a fuzzy match instead of an exact one
+        #[arg(short, long)]
+        fuzzy: bool,
+        /// The maximum number of paths printed
+        #[arg(short, long, default_value_t = 100)]
+        limit: usize,
+        /// The format of the paths, one per line
+        #[arg(long, value_enum, default_value_t)]
+        format: OutputFormat,
+    },
+    /// List the paths of the history matching a search, best first
+    ListPaths {
+        #[command(flatten)]
+        search: ListSearch,
+        /// The format of the paths, one per line
+        #[arg(long, value_enum, default_value_t)]
+        format: OutputFormat,
+    },
+    /// List the shortcuts matching a search
+    ListShortcuts {
+        #[command(flatten)]
+        search: ListSearch,
+        /// The format of the shortcuts, one per line: the name and the path in the plain format
+        #[arg(long, value_enum, default_value_t)]
+        format: OutputFormat,
+    },
+    /// Print all the paths by frecency, annotated with their shortcut, to be piped into fzf
+    Fzf,
+    /// Print the path of a line chosen in fzf and record the visit
+    Resolve { line: String },
+    /// Print a usage report: the top directories, the daily activity, the new directories and
+    /// the new shortcuts
+    Report {
+        /// The format of the report
+        #[arg(long, value_enum, default_value_t)]
+        format: report::ReportFormat,
+        /// The period of the report, as a number of hours, days or weeks, e.g. 12h, 90d or 8w
+        #[arg(long, default_value = "90d", value_parser = report::parse_period)]
+        since: i64,
+        /// The number of top directories
+        #[arg(long, default_value_t = 10)]
+        top: usize,
+    },
+    /// Print the visits of the history, the oldest first, for a spreadsheet or other analytics
+    ExportHistory {
+        /// The format of the history
+        #[arg(long, value_enum, default_value_t)]
+        format: history_export::HistoryFormat,
+        /// Only export the visits since this day, e.g. 2026-10-01, or period, e.g. 30d
+        #[arg(long, value_parser = history_export::parse_since)]
+        since: Option<i64>,
+        /// Also export the visits of the sensitive paths
+        #[arg(long)]
+        include_sensitive: bool,
+    },
+    /// Run the store operations of a YAML batch file, all of them or none
+    Exec { filename: String },
+    /// Install, update or remove the bundles of shortcuts shared by a team
+    Bundle {
+        #[command(subcommand)]
+        command: BundleCommand,
+    },
+    /// Print the key bindings of the GUI
+    HelpKeys,
+    /// Answer the JSON-RPC requests of an editor plugin, one per line
+    Serve {
+        /// Read the requests from the standard input and write the responses to the standard
+        /// output
+        #[arg(long, required = true)]
+        stdio: bool,
+    },
+}
+
+/// The search of the list commands, everything being listed without one
+#[derive(clap::Args, Debug)]
+struct ListSearch {
+    /// Only list the entries containing this text
+    #[arg(long, value_name = "TEXT")]
+    filter: Option<String>,
+    /// Only list the entries matching this text with a fuzzy match
+    #[arg(long, value_name = "TEXT", conflicts_with = "filter")]
+    fuzzy: Option<String>,
+    /// The maximum number of entries listed
+    #[arg(long, default_value_t = 100)]
+    limit: usize,
+}
+
+impl ListSearch {
+    /// The text searched and whether the match is fuzzy
+    fn query(&self) -> (&str, bool) {
+        match (&self.filter, &self.fuzzy) {
+            (_, Some(fuzzy)) => (fuzzy, true),
+            (filter, None) => (filter.as_deref().unwrap_or_default(), false),
+        }
+    }
+}
+
+#[derive(Subcommand, Debug)]
+enum BundleCommand {
+    /// Install a bundle file, a local path or an https:// URL, or update the installed bundle
+    /// of the same name
+    Install {
+        filename: String,
+        /// Only install the file if its SHA-256 is this one
+        #[arg(long, value_name = "CHECKSUM")]
+        sha256: Option<String>,
+        /// Install the bundle even if a newer version is installed
+        #[arg(long)]
+        force: bool,
+        /// Only install the bundle if it is signed by this public key, or by one of the
+        /// `bundle_public_keys` of the configuration
+        #[arg(long = "public-key", value_name = "KEY")]
+        public_keys: Vec<String>,
+    },
+    /// Remove a bundle and its shortcuts
+    Remove { name: String },
+    /// Print the installed bundles
+    List,
+    /// Generate a key pair signing bundles: the secret key is written to a new file, and the
+    /// public key printed
+    Keygen { key_file: String },
+    /// Sign a bundle file with a secret key, the signature being written next to it with the
+    /// .sig extension
+    Sign {
+        filename: String,
+        /// The file of the secret key written by `bundle keygen`
+        #[arg(long, value_name = "KEY_FILE")]
+        key: String,
+    },
+}
+
+/// The shell script the wrapper evaluates to jump into `path`: an ssh session for a remote
+/// entry, else a `cd` applying the hints of the shortcut `name`.
+fn jump_script(
+    config: &Arc<Mutex<Config>>,
+    path: &str,
+    name: &str,
+    hints: &store::ShortcutHints,
+) -> String {
+    let config_lock = config.lock().unwrap();
+    match RemotePath::parse(path) {
+        Some(remote) => format!(
+            "{}\n",
+            remote.command_line(&config_lock.remote_command_template)
+        ),
+        None => shortcut_hints::shell_script(path, name, hints, config_lock.confirm_shortcut_hints),
+    }
+}
+
+/// Report the progress of an import
+fn print_import_progress(done: usize, total: usize) {
+    println!("{}/{} entries processed", done, total);
+}
+
+impl Commands {
+    /// Whether the command honors `--dry-run`: the commands deleting or overwriting entries
+    /// in bulk
+    fn supports_dry_run(&self) -> bool {
+        matches!(
+            self,
+            Commands::ImportPaths { .. }
+                | Commands::ImportShortcuts { .. }
+                | Commands::Merge { .. }
+                | Commands::AddShortcut { force: true, .. }
+                | Commands::DeleteShortcut { .. }
+                | Commands::RenameShortcut { .. }
+                | Commands::UpdateShortcut { .. }
+                | Commands::Exec { .. }
+                | Commands::Bundle {
+                    command: BundleCommand::Install { .. } | BundleCommand::Remove { .. },
+                }
+        )
+    }
+}
+
+/// The format of an exported file: the one given, else the one of its extension.
+fn format_of(filename: Option<&str>, format: Option<FileFormat>) -> FileFormat {
+    format.unwrap_or_else(|| filename.map(FileFormat::detect).unwrap_or_default())
 }
 
-fn initialize_logs(config_path: &Option<PathBuf>) {
-    if let Some(config_path) = config_path.as_ref()
-        && config_path.exists()
+/// Write an exported document to `filename`, or to the standard output when not set.
+fn write_export(contents: &str, filename: Option<&str>) -> Result<(), CliError> {
+    match filename {
+        Some(filename) => fs::write(filename, contents)
+            .map_err(|e| CliError::io(format!("Failed to write {}: {}", filename, e)))?,
+        None => print!("{}", contents),
+    }
+    Ok(())
+}
+
+/// Print the outcome of an import and its rejected entries, writing them to `rejects` if set.
+/// When only validating, the rejected entries make the command fail.
+fn report_import(
+    summary: &ImportSummary,
+    options: &ImportOptions,
+    rejects: Option<&Path>,
+) -> Result<(), CliError> {
+    for rejected in &summary.rejected {
+        eprintln!("rejected {}", rejected);
+    }
+    if let Some(rejects) = rejects
+        && !summary.rejected.is_empty()
     {
-        let _ = log4rs::init_file(config_path, Default::default());
-    };
+        expimp::write_rejects(&summary.rejected, rejects).map_err(CliError::io)?;
+    }
+    if options.dry_run {
+        for (name, path) in &summary.replaced {
+            println!("would replace the shortcut {} ({})", name, path);
+        }
+        println!("{} (dry run, nothing imported)", summary);
+    }
+    if options.validate_only {
+        println!("{} (validation only, nothing imported)", summary);
+        if !summary.rejected.is_empty() {
+            let message = format!("{} invalid entries", summary.rejected.len());
+            return Err(CliError::invalid(message));
+        }
+    } else if !options.dry_run {
+        println!("{}", summary);
+    }
+    Ok(())
+}
+
+/// Check the path of a shortcut: a valid remote path or, unless `allow_missing`, an existing
+/// directory.
+fn check_shortcut_path(path: &str, allow_missing: bool) -> Result<(), CliError> {
+    RemotePath::validate(path).map_err(CliError::invalid)?;
+    if !allow_missing && RemotePath::parse(path).is_none() && !Path::new(path).is_dir() {
+        let e = format!(
+            "the directory '{}' does not exist, use --allow-missing to use it anyway",
+            path
+        );
+        return Err(CliError::not_found(e));
+    }
+    Ok(())
+}
+
+/// Warn when shortcuts other than `name` already point at `path`.
+fn warn_duplicate_shortcuts(store: &Store, name: &str, path: &str) {
+    let duplicates: Vec<String> = store
+        .list_shortcuts_by_path(path)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|shortcut| shortcut.name != name)
+        .map(|shortcut| shortcut.name)
+        .collect();
+    if !duplicates.is_empty() {
+        eprintln!(
+            "warning: '{}' is also the path of the shortcuts {}, \
+            'cdir delete-shortcut <name>' removes the ones no longer needed",
+            path,
+            duplicates.join(", ")
+        );
+    }
+}
+
+/// Fail when a local file, the argument of a command, does not exist, the URLs being
+/// checked when downloaded.
+fn check_exists(location: &str) -> Result<(), CliError> {
+    if !fetch::is_url(location) && !Path::new(location).exists() {
+        return Err(CliError::not_found(format!(
+            "File {} does not exist",
+            location
+        )));
+    }
+    Ok(())
+}
+
+/// Write a secret key to the new file `path`, only readable by its owner.
+fn write_secret(path: &str, secret: &str) -> Result<(), CliError> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options
+        .open(path)
+        .map_err(|e| CliError::io(format!("Failed to create {}: {}", path, e)))?;
+    writeln!(file, "{}", secret)
+        .map_err(|e| CliError::io(format!("Failed to write {}: {}", path, e)))
+}
+
+/// Confirm a destructive command, see `prompt::confirm`, failing when it is declined.
+fn confirm(question: &str, yes: bool) -> Result<(), CliError> {
+    match prompt::confirm(question, yes) {
+        Ok(true) => Ok(()),
+        Ok(false) => Err(CliError::new(ErrorKind::Cancelled, "cancelled")),
+        Err(e) => Err(CliError::new(ErrorKind::Cancelled, e)),
+    }
 }
 
 #[tokio::main]
-async fn main() -> Result<(), Box<dyn Error>> {
-    color_eyre::install()?;
-    let args = Args::parse();
-    let mut config = match Config::initialize_and_load(args.config_file.clone()) {
-        Ok(config) => config,
+async fn main() -> ExitCode {
+    let args = match Args::try_parse() {
+        Ok(args) => args,
+        Err(e) => {
+            let _ = e.print();
+            return match e.use_stderr() {
+                true => ExitCode::from(ErrorKind::Usage.exit_code()),
+                // --help and --version
+                false => ExitCode::SUCCESS,
+            };
+        }
+    };
+    let profile = args.profile.then(profile::Profile::new);
+    let result = run(args, profile.as_ref()).await;
+    if let Some(profile) = profile {
+        eprint!("{}", profile.report());
+    }
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
         Err(e) => {
             error!("{}", e);
-            return Err(Box::<dyn Error>::from(e));
+            if !logging::mirrors_to_stderr() {
+                eprintln!("error: {}", e);
+            }
+            e.exit_code()
         }
+    }
+}
+
+/// Run the command of `args`, its steps being recorded by `profile` when given.
+async fn run(args: Args, profile: Option<&profile::Profile>) -> Result<(), CliError> {
+    color_eyre::install().map_err(|e| CliError::new(ErrorKind::Internal, e))?;
+    let load = || {
+        tracing::info_span!("config_load")
+            .in_scope(|| Config::initialize_and_load(args.config_file.clone()))
     };
-    initialize_logs(&config.log_config_path);
+    // the logs are initialized from the configuration, the profile records its loading alone
+    let mut config = match profile {
+        Some(profile) => profile.in_scope(load),
+        None => load(),
+    }
+    .map_err(CliError::config)?;
+    // the logs would garble the GUI
+    let log_to_stderr =
+        config.log_to_stderr && !matches!(args.command, None | Some(Commands::Gui { .. }));
+    logging::initialize(
+        &config,
+        logging::LogOptions::new(args.verbose, args.quiet, log_to_stderr),
+        profile.map(profile::Profile::layer),
+    );
     config.process();
 
     info!("Starting with args={args:?}");
+    if args.dry_run
+        && let Some(command) = &args.command
+        && !command.supports_dry_run()
+    {
+        return Err(CliError::new(
+            ErrorKind::Usage,
+            "--dry-run is not supported by this command",
+        ));
+    }
 
     let config = Arc::new(Mutex::new(config));
 
-    let store = Store::new(
-        config
-            .lock()
-            .unwrap()
-            .db_path
-            .as_ref()
-            .expect("missing db_path into the configuration"),
-        config.clone(),
-    );
+    let db_path = config
+        .lock()
+        .unwrap()
+        .db_path
+        .clone()
+        .ok_or_else(|| CliError::config("db_path is missing from the configuration"))?;
+    let store = Store::new(&db_path, config.clone()).map_err(|e| {
+        CliError::database(format!(
+            "Failed to open the database {}: {}",
+            db_path.display(),
+            e
+        ))
+    })?;
     match &args.command {
-        Some(Commands::Gui { filename }) => {
-            if let Some(s) = gui::gui(store, config.clone()).await {
+        Some(Commands::Gui {
+            filename,
+            query,
+            fuzzy,
+            auto,
+            eval,
+            format,
+            view,
+            select_only,
+            safe_mode,
+        }) => {
+            let options = gui::GuiOptions {
+                query: query.clone(),
+                fuzzy: *fuzzy,
+                auto_select: *auto || config.lock().unwrap().auto_select_single_result,
+                tmux_pane: std::env::var(TMUX_PANE_VARIABLE).ok(),
+                view: *view,
+                select_only: *select_only,
+                safe_mode: *safe_mode,
+            };
+            let hints_store = store.clone();
+            if let Some(selection) = gui::gui(store, config.clone(), options).await {
+                let mut s = selection.path.clone();
+                hooks::run_hook(&config, Hook::Select, &[("CDIR_PATH", &s)]);
+                if selection.file {
+                    if *eval {
+                        s = open_with::editor_script(&s);
+                    }
+                } else if *format != OutputFormat::Plain {
+                    s = Candidate::selected(
+                        &hints_store,
+                        &selection.path,
+                        &selection.search,
+                        selection.fuzzy,
+                    )
+                    .format(*format);
+                }
+                if *eval && !selection.file {
+                    let (name, hints) = hints_store
+                        .find_shortcut_hints_by_path(&s)?
+                        .unwrap_or_default();
+                    s = jump_script(&config, &s, &name, &hints);
+                }
                 match filename {
                     None => {
                         println!("{}", s);
                     }
                     Some(filename) => {
-                        let path = Path::new(filename);
-                        let mut file = File::create(path).unwrap();
-                        file.write_all(s.as_bytes()).unwrap();
+                        File::create(filename)
+                            .and_then(|mut file| file.write_all(s.as_bytes()))
+                            .map_err(|e| {
+                                CliError::io(format!("Failed to write {}: {}", filename, e))
+                            })?;
                     }
                 }
             };
@@ -137,50 +750,363 @@ async fn main() -> Result<(), Box<dyn Error>> {
             } else if let Ok(config_file) = std::env::var(config::CDIR_CONFIG_VAR) {
                 println!("{}", config_file);
             } else {
-                let mut cpath = dirs::home_dir().unwrap();
+                let mut cpath = dirs::home_dir()
+                    .ok_or_else(|| CliError::config("the home directory is not known"))?;
                 cpath.push(".config");
                 cpath.push("cdir");
                 cpath.push("config.yaml");
                 println!("{}", cpath.display());
             }
         }
-        Some(Commands::AddPath { path }) => {
-            store.add_path(path).unwrap();
+        Some(Commands::AddPath { path, batch }) => {
+            let paths = match path {
+                Some(path) => vec![path.clone()],
+                None => {
+                    debug!("AddPath batch={}", batch);
+                    std::io::stdin()
+                        .lock()
+                        .lines()
+                        .map_while(Result::ok)
+                        .filter(|line| !line.trim().is_empty())
+                        .collect()
+                }
+            };
+            for path in &paths {
+                RemotePath::validate(path).map_err(CliError::invalid)?;
+            }
+            let tmux_pane = std::env::var(TMUX_PANE_VARIABLE).ok();
+            store.add_paths_from_pane(&paths, tmux_pane.as_deref())?;
+            for path in &paths {
+                hooks::run_hook(&config, Hook::AddPath, &[("CDIR_PATH", path)]);
+            }
+        }
+        Some(Commands::AddFile { path }) => {
+            let path = std::path::absolute(path)
+                .map_err(|e| CliError::invalid(format!("Invalid path '{}': {}", path, e)))?;
+            store.add_file(&path.to_string_lossy())?;
+        }
+        Some(Commands::AddCmd { path, command }) => {
+            // e.g. a hook run for an empty command line
+            if !command.trim().is_empty() {
+                store.add_command(path, command.trim())?;
+            }
+        }
+        Some(Commands::ImportPaths {
+            filename,
+            format,
+            sha256,
+            validate_only,
+            rejects,
+        }) => {
+            let options = ImportOptions {
+                format: *format,
+                sha256: sha256.clone(),
+                validate_only: *validate_only,
+                dry_run: args.dry_run,
+            };
+            check_exists(filename)?;
+            let summary =
+                load_paths_from_file(store, filename, &options, &mut print_import_progress)
+                    .map_err(CliError::invalid)?;
+            report_import(&summary, &options, rejects.as_deref())?;
+        }
+        Some(Commands::ExportPaths {
+            filename,
+            format,
+            include_sensitive,
+        }) => {
+            store.set_reveal_sensitive(*include_sensitive);
+            let format = format_of(filename.as_deref(), *format);
+            let contents = expimp::export_paths(&store, format).map_err(CliError::database)?;
+            write_export(&contents, filename.as_deref())?;
         }
-        Some(Commands::ImportPaths { filename }) => {
-            load_paths_from_yaml(store, PathBuf::from(filename));
+        Some(Commands::Merge { from }) => {
+            check_exists(from)?;
+            let summary = store
+                .merge_database(&PathBuf::from(from), args.dry_run)
+                .map_err(|e| CliError::database(format!("Failed to merge {}: {}", from, e)))?;
+            println!("{}", summary);
+            if args.dry_run {
+                println!("dry run, nothing merged");
+            }
+        }
+        Some(Commands::MarkSensitive { path, clear }) => {
+            debug!("MarkSensitive {} clear={}", path, clear);
+            if !store.set_path_sensitive(path, !clear)? {
+                let message = format!("'{}' is not in the history", path);
+                return Err(CliError::not_found(message));
+            }
+        }
+        Some(Commands::LinkPaths { path, alias }) => {
+            debug!("LinkPaths {} {}", path, alias);
+            store.link_paths(path, alias).map_err(|e| {
+                CliError::database(format!("Failed to link '{}' to '{}': {}", alias, path, e))
+            })?;
         }
         Some(Commands::AddShortcut {
             name,
             path,
             description,
+            env,
+            post_cd,
+            force,
+            allow_missing,
         }) => {
-            debug!("AddShortcut {} {} {:?}", name, path, description);
-            store
-                .add_shortcut(name, path, description.as_ref().map(|s| s.as_str()))
-                .unwrap()
+            debug!(
+                "AddShortcut {} {} {:?} {:?} {:?} force={} allow_missing={}",
+                name, path, description, env, post_cd, force, allow_missing
+            );
+            check_shortcut_path(path, *allow_missing)?;
+            let existing = store.find_shortcut(name);
+            if !force && let Some(existing) = &existing {
+                let e = format!(
+                    "the shortcut '{}' already exists for '{}', use --force to overwrite it",
+                    name, existing.path
+                );
+                return Err(CliError::invalid(e));
+            }
+            let hints = store::ShortcutHints {
+                env: shortcut_hints::parse_env(env).map_err(CliError::invalid)?,
+                post_cd: post_cd.clone(),
+            };
+            if args.dry_run {
+                match existing {
+                    Some(existing) => {
+                        println!("would replace the shortcut {} ({})", name, existing.path)
+                    }
+                    None => println!("would add the shortcut {} ({})", name, path),
+                }
+                return Ok(());
+            }
+            store.upsert_shortcut(name, path, description.as_ref().map(|s| s.as_str()))?;
+            if !hints.is_empty() {
+                store.set_shortcut_hints(name, &hints)?;
+            }
+            warn_duplicate_shortcuts(&store, name, path);
         }
         Some(Commands::DeleteShortcut { name }) => {
             debug!("DeleteShortcut {}", name);
-            store.delete_shortcut(name).unwrap();
-        }
-        Some(Commands::PrintShortcut { name }) => {
-            debug!("PrintShortcut {}", name);
             match store.find_shortcut(name) {
-                None => {}
+                Some(shortcut) if args.dry_run => {
+                    println!("would delete the shortcut {} ({})", name, shortcut.path)
+                }
+                Some(shortcut) => {
+                    let question = format!("Delete the shortcut {} ({})?", name, shortcut.path);
+                    confirm(&question, args.yes)?;
+                    store.delete_shortcut(name)?;
+                    hooks::run_hook(
+                        &config,
+                        Hook::DeleteShortcut,
+                        &[("CDIR_SHORTCUT", name), ("CDIR_PATH", &shortcut.path)],
+                    );
+                }
+                None => return Err(CliError::not_found(format!("no shortcut '{}'", name))),
+            }
+        }
+        Some(Commands::RenameShortcut { old, new }) => {
+            debug!("RenameShortcut {} {}", old, new);
+            if store.find_shortcut(old).is_none() {
+                return Err(CliError::not_found(format!("no shortcut '{}'", old)));
+            }
+            if let Some(existing) = store.find_shortcut(new) {
+                let e = format!(
+                    "the shortcut '{}' already exists for '{}'",
+                    new, existing.path
+                );
+                return Err(CliError::invalid(e));
+            }
+            if args.dry_run {
+                println!("would rename the shortcut {} to {}", old, new);
+                return Ok(());
+            }
+            store.rename_shortcut(old, new)?;
+        }
+        Some(Commands::UpdateShortcut {
+            name,
+            path,
+            description,
+            allow_missing,
+        }) => {
+            debug!(
+                "UpdateShortcut {} {:?} {:?} allow_missing={}",
+                name, path, description, allow_missing
+            );
+            let Some(shortcut) = store.find_shortcut(name) else {
+                return Err(CliError::not_found(format!("no shortcut '{}'", name)));
+            };
+            if let Some(path) = path {
+                check_shortcut_path(path, *allow_missing)?;
+            }
+            let path = path.as_deref().unwrap_or(&shortcut.path);
+            let description = match description {
+                Some(description) => Some(description.as_str()).filter(|d| !d.is_empty()),
+                None => shortcut.description.as_deref(),
+            };
+            if args.dry_run {
+                println!("would update the shortcut {} ({})", name, path);
+                return Ok(());
+            }
+            store.update_shortcut(shortcut.id, name, path, description)?;
+            if path != shortcut.path {
+                warn_duplicate_shortcuts(&store, name, path);
+            }
+        }
+        Some(Commands::CheckShortcuts) => {
+            debug!("CheckShortcuts");
+            store.check_shortcuts(0)?;
+            let broken = store.broken_shortcut_ids()?;
+            for shortcut in store.list_all_shortcuts()? {
+                if broken.contains(&shortcut.id) {
+                    println!("{} {}", shortcut.name, shortcut.path);
+                }
+            }
+        }
+        Some(Commands::SuggestShortcuts { accept, all }) => {
+            debug!("SuggestShortcuts accept={:?} all={}", accept, all);
+            let suggestions = shortcut_suggestions::suggest(&store, &config.lock().unwrap())?;
+            if !*all && accept.is_empty() {
+                for suggestion in &suggestions {
+                    println!(
+                        "{} {} ({} visits)",
+                        suggestion.name, suggestion.path, suggestion.visits
+                    );
+                }
+                return Ok(());
+            }
+            if let Some(unknown) = accept
+                .iter()
+                .find(|name| !suggestions.iter().any(|s| s.name == **name))
+            {
+                let e = format!("'{}' is not a suggested shortcut", unknown);
+                return Err(CliError::not_found(e));
+            }
+            for suggestion in suggestions
+                .iter()
+                .filter(|s| *all || accept.contains(&s.name))
+            {
+                store.upsert_shortcut(&suggestion.name, &suggestion.path, None)?;
+                println!("added {} {}", suggestion.name, suggestion.path);
+            }
+        }
+        Some(Commands::PrintShortcut { name, eval }) => {
+            debug!("PrintShortcut {} eval={}", name, eval);
+            let shortcut = store.find_shortcut(name);
+            if let Some(s) = &shortcut {
+                hooks::run_hook(
+                    &config,
+                    Hook::Select,
+                    &[("CDIR_SHORTCUT", name), ("CDIR_PATH", &s.path)],
+                );
+            }
+            match shortcut {
+                None => return Err(CliError::not_found(format!("no shortcut '{}'", name))),
+                Some(s) if *eval => {
+                    let hints = store.find_shortcut_hints(name)?.unwrap_or_default();
+                    print!("{}", jump_script(&config, &s.path, name, &hints))
+                }
                 Some(s) => {
                     print!("{}", s.path)
                 }
             };
         }
-        Some(Commands::ImportShortcuts { filename }) => {
-            load_shortcuts_from_yaml(store, PathBuf::from(filename));
+        Some(Commands::Expand { text, eval }) => {
+            debug!("Expand {} eval={}", text, eval);
+            let Some((shortcut, path)) = store.expand_shortcut(text) else {
+                let name = text.split('/').next().unwrap_or_default();
+                return Err(CliError::not_found(format!("no shortcut '{}'", name)));
+            };
+            if !shortcut.remote && !Path::new(&path).is_dir() {
+                let e = format!("the directory '{}' does not exist", path);
+                return Err(CliError::not_found(e));
+            }
+            hooks::run_hook(
+                &config,
+                Hook::Select,
+                &[("CDIR_SHORTCUT", &shortcut.name), ("CDIR_PATH", &path)],
+            );
+            match eval {
+                true => {
+                    let hints = store
+                        .find_shortcut_hints(&shortcut.name)?
+                        .unwrap_or_default();
+                    print!("{}", jump_script(&config, &path, &shortcut.name, &hints))
+                }
+                false => println!("{}", path),
+            }
+        }
+        Some(Commands::ImportShortcuts {
+            filename,
+            format,
+            sha256,
+            validate_only,
+            rejects,
+        }) => {
+            let options = ImportOptions {
+                format: *format,
+                sha256: sha256.clone(),
+                validate_only: *validate_only,
+                dry_run: args.dry_run,
+            };
+            check_exists(filename)?;
+            let summary =
+                load_shortcuts_from_file(store, filename, &options, &mut print_import_progress)
+                    .map_err(CliError::invalid)?;
+            report_import(&summary, &options, rejects.as_deref())?;
+        }
+        Some(Commands::ExportShortcuts { filename, format }) => {
+            let format = format_of(filename.as_deref(), *format);
+            let contents = expimp::export_shortcuts(&store, format).map_err(CliError::database)?;
+            write_export(&contents, filename.as_deref())?;
         }
-        Some(Commands::Lasts) => {
-            let list = store.list_paths(0, 10, "", false).unwrap();
+        Some(Commands::Lasts {
+            pane,
+            host,
+            include_sensitive,
+            visits,
+            collapse_repeats,
+        }) => {
+            store.set_reveal_sensitive(*include_sensitive);
+            if *pane {
+                match std::env::var(TMUX_PANE_VARIABLE) {
+                    Ok(tmux_pane) => store.set_pane_filter(Some(tmux_pane)),
+                    Err(_) => {
+                        let message = "--pane requires to run inside tmux ($TMUX_PANE is not set)";
+                        return Err(CliError::invalid(message));
+                    }
+                }
+            }
+            if *visits {
+                let list = store.list_path_visits(0, 10, host.as_deref(), *collapse_repeats)?;
+                let config_lock = config.lock().unwrap();
+                list.iter().for_each(|visit| {
+                    let badge = if visit.count > 1 {
+                        format!(" ×{}", visit.count)
+                    } else {
+                        String::new()
+                    };
+                    println!(
+                        "{} {}{}",
+                        config_lock.date_formatters.pattern.format(visit.path.date),
+                        visit.path.path,
+                        badge
+                    )
+                });
+                return Ok(());
+            }
+            // the host filter is the one of the search syntax
+            let like_text = host
+                .as_ref()
+                .map(|host| format!("host:{}", host))
+                .unwrap_or_default();
+            let list = store.list_paths(0, 10, &like_text, false)?;
             let config_lock = config.lock().unwrap();
-            list.iter()
-                .for_each(|s| println!("{} {}", (config_lock.date_formater)(s.date), s.path));
+            list.iter().for_each(|s| {
+                println!(
+                    "{} {}",
+                    config_lock.date_formatters.pattern.format(s.date),
+                    s.path
+                )
+            });
         }
         Some(Commands::PrettyPrintPath {
             path,
@@ -188,19 +1114,13 @@ async fn main() -> Result<(), Box<dyn Error>> {
             max_width,
         }) => {
             let max_width = max_width.unwrap_or(u16::MAX);
-            let shortcuts: Vec<Shortcut> = store.list_all_shortcuts().unwrap();
+            let shortcuts: Vec<Shortcut> = store.list_all_shortcuts()?;
             let config_lock = config.lock().unwrap();
             let path_entry = store::Path::new(0, path.clone(), 0, &shortcuts);
             let shortened_line =
                 gui::Gui::shorten_path_for_path(&config_lock, &path_entry, max_width);
             let shortened_line = shortened_line
-                .unwrap_or_else(|| {
-                    gui::Gui::reduce_path(
-                        path.clone(),
-                        max_width,
-                        config_lock.styles.home_tilde_style,
-                    )
-                })
+                .unwrap_or_else(|| gui::Gui::reduce_local_path(&config_lock, path, max_width))
                 .style(config_lock.styles.path_style);
             if style.is_none_or(|s| s) {
                 print!("{}", text_to_ansi(&Text::from(shortened_line)));
@@ -208,6 +1128,167 @@ async fn main() -> Result<(), Box<dyn Error>> {
                 print!("{}", shortened_line);
             }
         }
+        Some(Commands::Picker {
+            query,
+            fuzzy,
+            limit,
+            format,
+        }) => {
+            let candidates = picker::candidates(&store, query, *fuzzy, *limit)
+                .map_err(|e| CliError::database(format!("Failed to search '{}': {}", query, e)))?;
+            for candidate in candidates {
+                println!("{}", candidate.format(*format));
+            }
+        }
+        Some(Commands::ListPaths { search, format }) => {
+            let (query, fuzzy) = search.query();
+            let candidates = picker::candidates(&store, query, fuzzy, search.limit)
+                .map_err(|e| CliError::database(format!("Failed to list the paths: {}", e)))?;
+            for candidate in candidates {
+                println!("{}", candidate.format(*format));
+            }
+        }
+        Some(Commands::ListShortcuts { search, format }) => {
+            let (query, fuzzy) = search.query();
+            let entries = picker::shortcut_entries(&store, query, fuzzy, search.limit)
+                .map_err(|e| CliError::database(format!("Failed to list the shortcuts: {}", e)))?;
+            for entry in entries {
+                println!("{}", entry.format(*format));
+            }
+        }
+        Some(Commands::Fzf) => {
+            let paths = store
+                .list_paths_by_frecency()
+                .map_err(|e| CliError::database(format!("Failed to list the paths: {}", e)))?;
+            let mut stdout = std::io::stdout().lock();
+            for path in &paths {
+                // fzf closes the pipe once a line is chosen
+                if writeln!(stdout, "{}", picker::fzf_line(path)).is_err() {
+                    break;
+                }
+            }
+        }
+        Some(Commands::Resolve { line }) => {
+            let Some(path) = picker::resolve_fzf_line(line) else {
+                return Err(CliError::invalid("no path chosen"));
+            };
+            RemotePath::validate(path).map_err(CliError::invalid)?;
+            let tmux_pane = std::env::var(TMUX_PANE_VARIABLE).ok();
+            store.add_path_from_pane(path, tmux_pane.as_deref())?;
+            hooks::run_hook(&config, Hook::AddPath, &[("CDIR_PATH", path)]);
+            hooks::run_hook(&config, Hook::Select, &[("CDIR_PATH", path)]);
+            println!("{}", path);
+        }
+        Some(Commands::Report { format, since, top }) => {
+            let now = chrono::Local::now().timestamp();
+            let stats = stats::collect(&store, now - since, *top)
+                .map_err(|e| CliError::database(format!("Failed to aggregate the usage: {}", e)))?;
+            print!("{}", report::render(&stats, *format));
+        }
+        Some(Commands::ExportHistory {
+            format,
+            since,
+            include_sensitive,
+        }) => {
+            store.set_reveal_sensitive(*include_sensitive);
+            let visits = store
+                .list_history_since(since.unwrap_or(0))
+                .map_err(|e| CliError::database(format!("Failed to list the history: {}", e)))?;
+            let rendered = history_export::render(&visits, *format)
+                .map_err(|e| CliError::new(ErrorKind::Internal, e))?;
+            print!("{}", rendered);
+        }
+        Some(Commands::Exec { filename }) => {
+            let dry_run = args.dry_run;
+            debug!("Exec {} dry_run={}", filename, dry_run);
+            check_exists(filename)?;
+            let operations =
+                batch::read_batch_file(Path::new(filename)).map_err(CliError::invalid)?;
+            batch::run(&store, &operations, dry_run, &mut |operation| {
+                println!("{}", operation)
+            })
+            .map_err(CliError::invalid)?;
+            if dry_run {
+                println!("{} operations checked, nothing applied", operations.len());
+            } else {
+                println!("{} operations applied", operations.len());
+            }
+        }
+        Some(Commands::Bundle { command }) => match command {
+            BundleCommand::Install {
+                filename,
+                sha256,
+                force,
+                public_keys,
+            } => {
+                debug!("Bundle install {} force={}", filename, force);
+                check_exists(filename)?;
+                for key in public_keys {
+                    bundle::validate_public_key(key).map_err(CliError::invalid)?;
+                }
+                let mut public_keys = public_keys.clone();
+                public_keys.extend(config.lock().unwrap().bundle_public_keys.iter().cloned());
+                let bundle = bundle::read_bundle_file(filename, sha256.as_deref(), &public_keys)
+                    .map_err(CliError::invalid)?;
+                let summary = bundle::install(&store, &bundle, *force, args.dry_run)
+                    .map_err(CliError::invalid)?;
+                println!("{}", summary);
+                if args.dry_run {
+                    println!("dry run, nothing installed");
+                }
+            }
+            BundleCommand::Remove { name } => {
+                debug!("Bundle remove {}", name);
+                if store.find_bundle_version(name)?.is_none() {
+                    let message = format!("the bundle '{}' is not installed", name);
+                    return Err(CliError::not_found(message));
+                }
+                if !args.dry_run {
+                    let question = format!("Remove the bundle {} and its shortcuts?", name);
+                    confirm(&question, args.yes)?;
+                }
+                let removed =
+                    bundle::remove(&store, name, args.dry_run).map_err(CliError::database)?;
+                if args.dry_run {
+                    println!("{} would be removed with its {} shortcuts", name, removed);
+                } else {
+                    println!("{} removed with its {} shortcuts", name, removed);
+                }
+            }
+            BundleCommand::List => {
+                for (name, version, shortcuts) in store.list_bundles()? {
+                    println!("{} {} ({} shortcuts)", name, version, shortcuts);
+                }
+            }
+            BundleCommand::Keygen { key_file } => {
+                debug!("Bundle keygen {}", key_file);
+                let (secret_key, public_key) = bundle::generate_key();
+                write_secret(key_file, &secret_key)?;
+                println!("{}", public_key);
+            }
+            BundleCommand::Sign { filename, key } => {
+                debug!("Bundle sign {}", filename);
+                let read = |path: &str| {
+                    fs::read_to_string(path)
+                        .map_err(|e| CliError::io(format!("Failed to read {}: {}", path, e)))
+                };
+                check_exists(filename)?;
+                check_exists(key)?;
+                let signature =
+                    bundle::sign(&read(filename)?, &read(key)?).map_err(CliError::invalid)?;
+                let signature_file = format!("{}{}", filename, bundle::SIGNATURE_EXTENSION);
+                fs::write(&signature_file, format!("{}\n", signature)).map_err(|e| {
+                    CliError::io(format!("Failed to write {}: {}", signature_file, e))
+                })?;
+                println!("{} signed, see {}", filename, signature_file);
+            }
+        },
+        Some(Commands::HelpKeys) => {
+            print!("{}", keybindings::help_text());
+        }
+        Some(Commands::Serve { .. }) => {
+            serve::serve(&store, &config, std::io::stdin().lock(), std::io::stdout())?;
+        }
         None => {
             println!("Use the 'c' shell alias to launch the GUI.");
             println!("Use --help to see available commands.");
@@ -0,0 +1,147 @@
+use nucleo_matcher::{
+    Utf32Str,
+    pattern::{AtomKind, CaseMatching, Normalization, Pattern},
+};
+
+use crate::config::{FuzzyAlgorithm, FuzzyConfig};
+
+#[cfg(test)]
+#[path = "matcher_tests.rs"]
+mod matcher_tests;
+
+/// Scores texts against the text of a fuzzy search
+pub trait Matcher {
+    /// The score of `haystack`, the higher the better, None when it does not match.
+    fn score(&mut self, haystack: &str) -> Option<u32>;
+}
+
+/// Build the matcher of the algorithm configured for the fuzzy search of `needle`, respecting
+/// the case of the letters when `case_sensitive` is set.
+pub fn build(config: &FuzzyConfig, needle: &str, case_sensitive: bool) -> Box<dyn Matcher> {
+    let case_matching = if case_sensitive {
+        CaseMatching::Respect
+    } else {
+        CaseMatching::Ignore
+    };
+    let matcher: Box<dyn Matcher> = match config.algorithm {
+        FuzzyAlgorithm::FzfV2 => Box::new(FzfMatcher::new(needle, case_matching)),
+        FuzzyAlgorithm::Skim => Box::new(SkimMatcher::new(needle, case_matching)),
+        FuzzyAlgorithm::Substring => Box::new(SubstringMatcher::new(needle, case_matching)),
+    };
+    if config.segment_boundary_bonus == 0 {
+        return matcher;
+    }
+    Box::new(SegmentBoundaryBonus {
+        matcher,
+        words: needle.split_whitespace().map(str::to_lowercase).collect(),
+        bonus: config.segment_boundary_bonus,
+    })
+}
+
+fn nucleo_matcher() -> nucleo_matcher::Matcher {
+    nucleo_matcher::Matcher::new(nucleo_matcher::Config::DEFAULT.match_paths())
+}
+
+/// The optimal matching of fzf (its v2 algorithm), supporting its syntax: `'` for an exact
+/// match, `^` for a prefix, `$` for a suffix and `!` for a negation
+struct FzfMatcher {
+    matcher: nucleo_matcher::Matcher,
+    pattern: Pattern,
+    buf: Vec<char>,
+}
+
+impl FzfMatcher {
+    fn new(needle: &str, case_matching: CaseMatching) -> FzfMatcher {
+        FzfMatcher {
+            matcher: nucleo_matcher(),
+            pattern: Pattern::parse(needle, case_matching, Normalization::Smart),
+            buf: Vec::new(),
+        }
+    }
+}
+
+impl Matcher for FzfMatcher {
+    fn score(&mut self, haystack: &str) -> Option<u32> {
+        let haystack = Utf32Str::new(haystack, &mut self.buf);
+        self.pattern.score(haystack, &mut self.matcher)
+    }
+}
+
+/// The greedy matching of skim, faster than fzf's one but whose scores are not always the best.
+/// Each word must match.
+struct SkimMatcher {
+    matcher: nucleo_matcher::Matcher,
+    pattern: Pattern,
+    buf: Vec<char>,
+}
+
+impl SkimMatcher {
+    fn new(needle: &str, case_matching: CaseMatching) -> SkimMatcher {
+        SkimMatcher {
+            matcher: nucleo_matcher(),
+            pattern: Pattern::new(needle, case_matching, Normalization::Smart, AtomKind::Fuzzy),
+            buf: Vec::new(),
+        }
+    }
+}
+
+impl Matcher for SkimMatcher {
+    fn score(&mut self, haystack: &str) -> Option<u32> {
+        let haystack = Utf32Str::new(haystack, &mut self.buf);
+        self.pattern.atoms.iter().try_fold(0, |score, atom| {
+            self.matcher
+                .fuzzy_match_greedy(haystack, atom.needle_text())
+                .map(|s| score + s as u32)
+        })
+    }
+}
+
+/// Each word must be found as is
+struct SubstringMatcher {
+    matcher: nucleo_matcher::Matcher,
+    pattern: Pattern,
+    buf: Vec<char>,
+}
+
+impl SubstringMatcher {
+    fn new(needle: &str, case_matching: CaseMatching) -> SubstringMatcher {
+        SubstringMatcher {
+            matcher: nucleo_matcher(),
+            pattern: Pattern::new(
+                needle,
+                case_matching,
+                Normalization::Smart,
+                AtomKind::Substring,
+            ),
+            buf: Vec::new(),
+        }
+    }
+}
+
+impl Matcher for SubstringMatcher {
+    fn score(&mut self, haystack: &str) -> Option<u32> {
+        let haystack = Utf32Str::new(haystack, &mut self.buf);
+        self.pattern.score(haystack, &mut self.matcher)
+    }
+}
+
+/// Adds a bonus to the score of a match for each word of the search starting a segment of the
+/// path, e.g. `src` in `/home/user/src/cdir`
+struct SegmentBoundaryBonus {
+    matcher: Box<dyn Matcher>,
+    words: Vec<String>,
+    bonus: u32,
+}
+
+impl Matcher for SegmentBoundaryBonus {
+    fn score(&mut self, haystack: &str) -> Option<u32> {
+        let score = self.matcher.score(haystack)?;
+        let haystack = haystack.to_lowercase();
+        let segments = self
+            .words
+            .iter()
+            .filter(|word| haystack.contains(&format!("/{}", word)))
+            .count() as u32;
+        Some(score + segments * self.bonus)
+    }
+}
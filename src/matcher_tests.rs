@@ -0,0 +1,75 @@
+use super::*;
+
+fn config(algorithm: FuzzyAlgorithm) -> FuzzyConfig {
+    FuzzyConfig {
+        algorithm,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_fzf_v2() {
+    let mut matcher = build(&config(FuzzyAlgorithm::FzfV2), "prj cdr", false);
+    assert!(matcher.score("/home/me/projects/cdir").is_some());
+    assert!(matcher.score("/home/me/projects").is_none());
+
+    // the fzf syntax is supported
+    let mut matcher = build(&config(FuzzyAlgorithm::FzfV2), "^/home !tmp", false);
+    assert!(matcher.score("/home/me/src").is_some());
+    assert!(matcher.score("/home/me/tmp").is_none());
+    assert!(matcher.score("/var/home").is_none());
+}
+
+#[test]
+fn test_default_is_fzf_v2() {
+    let mut default = build(&FuzzyConfig::default(), "prj cdr", false);
+    let mut fzf = FzfMatcher::new("prj cdr", CaseMatching::Ignore);
+    for haystack in ["/home/me/projects/cdir", "/prj/cdr", "/tmp"] {
+        assert_eq!(default.score(haystack), fzf.score(haystack));
+    }
+}
+
+#[test]
+fn test_skim() {
+    let mut matcher = build(&config(FuzzyAlgorithm::Skim), "PRJ cdr", false);
+    assert!(matcher.score("/home/me/projects/cdir").is_some());
+    assert!(matcher.score("/home/me/projects").is_none());
+}
+
+#[test]
+fn test_substring() {
+    let mut matcher = build(&config(FuzzyAlgorithm::Substring), "proj CDIR", false);
+    assert!(matcher.score("/home/me/projects/cdir").is_some());
+    assert!(matcher.score("/home/me/p/r/o/j/cdir").is_none());
+}
+
+#[test]
+fn test_segment_boundary_bonus() {
+    let fuzzy_config = FuzzyConfig {
+        segment_boundary_bonus: 100,
+        ..Default::default()
+    };
+    let mut plain = build(&FuzzyConfig::default(), "src", false);
+    let mut matcher = build(&fuzzy_config, "src", false);
+    assert_eq!(
+        matcher.score("/home/src"),
+        plain.score("/home/src").map(|score| score + 100)
+    );
+    assert_eq!(matcher.score("/home/mysrc"), plain.score("/home/mysrc"));
+    assert_eq!(matcher.score("/tmp"), None);
+}
+
+#[test]
+fn test_case_sensitive() {
+    for algorithm in [
+        FuzzyAlgorithm::FzfV2,
+        FuzzyAlgorithm::Skim,
+        FuzzyAlgorithm::Substring,
+    ] {
+        let mut matcher = build(&config(algorithm), "Proj", true);
+        assert!(matcher.score("/home/me/Projects").is_some());
+        assert!(matcher.score("/home/me/projects").is_none());
+        let mut matcher = build(&config(algorithm), "Proj", false);
+        assert!(matcher.score("/home/me/projects").is_some());
+    }
+}
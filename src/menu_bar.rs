@@ -0,0 +1,357 @@
+use std::{
+    rc::Rc,
+    sync::{Arc, Mutex},
+};
+
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::{
+    layout::{Position, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+use tokio::sync::broadcast;
+use tracing::debug;
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    command_palette::{self, PaletteCommand},
+    config::Config,
+    keybindings::{Action, KEY_BINDINGS, Scope},
+    theme::ThemeStyles,
+    tui::{EventCaptured, GenericEvent, ManagerAction, View, ViewBuilder, ViewManager},
+};
+
+#[cfg(test)]
+#[path = "menu_bar_tests.rs"]
+mod menu_bar_tests;
+
+/// The menus of the menu bar, with the actions they list and their titles in the menu
+const MENUS: &[(&str, &[(Action, &str)])] = &[
+    (
+        "File",
+        &[
+            (Action::Choose, "Go to the directory"),
+            (Action::RunCommand, "Run a command..."),
+            (Action::Copy, "Copy the path"),
+            (Action::CopyShortcutName, "Copy the shortcut name"),
+            (Action::CopyLastCommand, "Copy the last command"),
+            (Action::Exit, "Quit"),
+        ],
+    ),
+    (
+        "Edit",
+        &[
+            (Action::Edit, "Edit..."),
+            (Action::Delete, "Delete..."),
+            (Action::Restore, "Restore"),
+            (Action::Relocate, "Relocate..."),
+            (Action::SuggestShortcuts, "Suggest shortcuts..."),
+        ],
+    ),
+    (
+        "View",
+        &[
+            (Action::SwitchView, "Switch the view"),
+            (Action::ToggleShortcuts, "Full paths"),
+            (Action::ToggleTrash, "Trash"),
+            (Action::Sessions, "Sessions..."),
+            (Action::TogglePaneFilter, "This pane only"),
+            (Action::RevealSensitive, "Sensitive directories"),
+            (Action::Sort, "Sort order"),
+            (Action::Details, "Details..."),
+            (Action::ToggleFuzzy, "Fuzzy search"),
+            (Action::ToggleCase, "Case sensitivity"),
+            (Action::Narrow, "Narrow the search"),
+            (Action::SearchHistory, "Search history..."),
+            (Action::CommandPalette, "Command palette..."),
+        ],
+    ),
+    (
+        "Help",
+        &[
+            (Action::Help, "Key bindings..."),
+            (Action::Configuration, "Configuration..."),
+        ],
+    ),
+];
+
+/// The key opening the menus
+const MENU_KEY: KeyCode = KeyCode::F(10);
+
+/// A menu of the menu bar
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Menu {
+    pub(crate) title: &'static str,
+    pub(crate) items: Vec<PaletteCommand>,
+}
+
+/// The menus listing the actions applying to the view of `scope`
+pub(crate) fn menus(scope: Scope) -> Vec<Menu> {
+    let scopes = command_palette::view_scopes(scope);
+    MENUS
+        .iter()
+        .map(|(title, actions)| Menu {
+            title,
+            items: actions
+                .iter()
+                .filter_map(|(action, item_title)| {
+                    let binding = KEY_BINDINGS.iter().find(|binding| {
+                        binding.action == *action && scopes.contains(&binding.scope)
+                    })?;
+                    PaletteCommand::binding(binding, item_title)
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+/// The columns and widths of the titles of `menus` on the menu bar
+fn title_areas(menus: &[Menu]) -> Vec<(u16, u16)> {
+    let mut x = 1;
+    menus
+        .iter()
+        .map(|menu| {
+            let width = menu.title.width() as u16 + 2;
+            let area = (x, width);
+            x += width;
+            area
+        })
+        .collect()
+}
+
+/// The index of the menu whose title is at `column`
+fn menu_at(menus: &[Menu], column: u16) -> Option<usize> {
+    title_areas(menus)
+        .iter()
+        .position(|(x, width)| (*x..x + width).contains(&column))
+}
+
+/// Draw the titles of the menus on the first row of `area`, the one at `open` being highlighted.
+fn draw_titles(
+    frame: &mut ratatui::Frame,
+    area: Rect,
+    menus: &[Menu],
+    open: Option<usize>,
+    styles: &ThemeStyles,
+) {
+    let style = styles.header_style();
+    let mut spans = vec![Span::styled(" ", style)];
+    for (idx, menu) in menus.iter().enumerate() {
+        let title_style = if open == Some(idx) {
+            styles.highlight(style)
+        } else {
+            style
+        };
+        spans.push(Span::styled(format!(" {} ", menu.title), title_style));
+    }
+    let hint = String::from("F10 menus ");
+    let used: usize = spans.iter().map(|span| span.width()).sum();
+    let padding = (area.width as usize).saturating_sub(used + hint.width());
+    spans.push(Span::styled(" ".repeat(padding), style));
+    spans.push(Span::styled(hint, style));
+    let row = Rect::new(area.x, area.y, area.width, 1);
+    frame.render_widget(Paragraph::new(Line::from(spans)).style(style), row);
+}
+
+/// The first row of the GUI, listing the menus opened with F10 or a click
+pub(crate) struct MenuBar {
+    vm: Rc<ViewManager>,
+    config: Arc<Mutex<Config>>,
+    /// The menus applying to the displayed view
+    menus: Box<dyn Fn() -> Vec<Menu>>,
+}
+
+impl MenuBar {
+    pub(crate) fn builder(
+        vm: Rc<ViewManager>,
+        config: Arc<Mutex<Config>>,
+        menus: Box<dyn Fn() -> Vec<Menu>>,
+    ) -> ViewBuilder {
+        ViewBuilder::from(Box::new(MenuBar { vm, config, menus }))
+    }
+
+    fn open(&self, open: usize) -> ManagerAction {
+        debug!("opening the menu {}", open);
+        let styles = self.config.lock().unwrap().styles.clone();
+        self.vm.show_modal_generic(
+            MenuView::builder(self.vm.tx(), (self.menus)(), open, styles),
+            None,
+        );
+        ManagerAction::new(true)
+    }
+}
+
+impl View for MenuBar {
+    fn draw(&mut self, frame: &mut ratatui::Frame, area: Rect, _active: bool) {
+        let styles = &self.config.lock().unwrap().styles;
+        draw_titles(frame, area, &(self.menus)(), None, styles);
+    }
+
+    fn handle_key_event(&mut self, key_event: KeyEvent) -> (EventCaptured, ManagerAction) {
+        if key_event.code == MENU_KEY {
+            return (EventCaptured::Yes, self.open(0));
+        }
+        (EventCaptured::No, ManagerAction::new(false))
+    }
+
+    fn handle_mouse_event(&mut self, _area: Rect, mouse_event: MouseEvent) -> ManagerAction {
+        if mouse_event.kind == MouseEventKind::Down(MouseButton::Left)
+            && let Some(idx) = menu_at(&(self.menus)(), mouse_event.column)
+        {
+            return self.open(idx);
+        }
+        ManagerAction::new(false)
+    }
+}
+
+/// A menu opened from the menu bar, drawn over it; the chosen item is run once it is closed.
+pub(crate) struct MenuView {
+    tx: broadcast::Sender<GenericEvent>,
+    menus: Vec<Menu>,
+    styles: ThemeStyles,
+    /// The index of the open menu
+    open: usize,
+    /// The index of the selected item of the open menu
+    selected: usize,
+}
+
+impl MenuView {
+    pub(crate) fn builder(
+        tx: broadcast::Sender<GenericEvent>,
+        menus: Vec<Menu>,
+        open: usize,
+        styles: ThemeStyles,
+    ) -> ViewBuilder {
+        ViewBuilder::from(Box::new(Self::new(tx, menus, open, styles)))
+    }
+
+    fn new(
+        tx: broadcast::Sender<GenericEvent>,
+        menus: Vec<Menu>,
+        open: usize,
+        styles: ThemeStyles,
+    ) -> Self {
+        Self {
+            tx,
+            menus,
+            styles,
+            open,
+            selected: 0,
+        }
+    }
+
+    fn items(&self) -> &[PaletteCommand] { &self.menus[self.open].items }
+
+    /// Open the menu `offset` menus away from the open one, around the menu bar.
+    fn move_open(&mut self, offset: isize) {
+        let len = self.menus.len() as isize;
+        self.open = (self.open as isize + offset).rem_euclid(len) as usize;
+        self.selected = 0;
+    }
+
+    /// The area of the open menu below the menu bar, within `area`
+    fn dropdown_area(&self, area: Rect) -> Rect {
+        let x = title_areas(&self.menus)[self.open].0;
+        let width = self
+            .items()
+            .iter()
+            .map(|item| item.title.width() + item.keys.width() + 3)
+            .max()
+            .unwrap_or_default() as u16
+            + 2;
+        let height = self.items().len() as u16 + 2;
+        let x = x.min(area.right().saturating_sub(width));
+        Rect::new(x, area.y + 1, width, height).intersection(area)
+    }
+
+    fn run_selected(&self) -> ManagerAction {
+        if let Some(item) = self.items().get(self.selected) {
+            item.run(&self.tx);
+        }
+        ManagerAction::new(true).with_close(true)
+    }
+}
+
+impl View for MenuView {
+    fn draw(&mut self, frame: &mut ratatui::Frame, _area: Rect, _active: bool) {
+        let area = frame.area();
+        draw_titles(frame, area, &self.menus, Some(self.open), &self.styles);
+
+        let dropdown_area = self.dropdown_area(area);
+        frame.render_widget(Clear, dropdown_area);
+        if let Some(bg_color) = &self.styles.background_color {
+            let background = Paragraph::new("").style(Style::default().bg(*bg_color));
+            frame.render_widget(background, dropdown_area);
+        }
+        // the keys are aligned on the right of the menu
+        let inner_width = dropdown_area.width.saturating_sub(2) as usize;
+        let lines: Vec<Line> = self
+            .items()
+            .iter()
+            .enumerate()
+            .map(|(idx, item)| {
+                let mut style = self.styles.path_style;
+                if idx == self.selected {
+                    style = self.styles.highlight(style);
+                }
+                let padding = inner_width.saturating_sub(item.title.width() + item.keys.width());
+                Line::from(vec![
+                    Span::styled(item.title.clone(), style),
+                    Span::styled(" ".repeat(padding), style),
+                    Span::styled(item.keys.clone(), self.styles.date_style),
+                ])
+            })
+            .collect();
+        let mut block = Block::default().borders(Borders::ALL);
+        if let Some(border_color) = self.styles.border_color {
+            block = block.border_style(Style::default().fg(border_color));
+        }
+        frame.render_widget(Paragraph::new(lines).block(block), dropdown_area);
+    }
+
+    fn handle_key_event(&mut self, key_event: KeyEvent) -> (EventCaptured, ManagerAction) {
+        match key_event.code {
+            KeyCode::Enter => return (EventCaptured::Yes, self.run_selected()),
+            MENU_KEY => {
+                return (
+                    EventCaptured::Yes,
+                    ManagerAction::new(true).with_close(true),
+                );
+            }
+            KeyCode::Left => self.move_open(-1),
+            KeyCode::Right => self.move_open(1),
+            KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down => {
+                self.selected = (self.selected + 1).min(self.items().len().saturating_sub(1))
+            }
+            _ => return (EventCaptured::Yes, ManagerAction::new(false)),
+        }
+        (EventCaptured::Yes, ManagerAction::new(true))
+    }
+
+    fn handle_mouse_event(&mut self, area: Rect, mouse_event: MouseEvent) -> ManagerAction {
+        if mouse_event.kind != MouseEventKind::Down(MouseButton::Left) {
+            return ManagerAction::new(false);
+        }
+        let position = Position::new(mouse_event.column, mouse_event.row);
+        // a click on a title opens its menu
+        if position.y == area.y
+            && let Some(idx) = menu_at(&self.menus, position.x)
+        {
+            self.open = idx;
+            self.selected = 0;
+            return ManagerAction::new(true);
+        }
+        // a click on an item runs it, a click elsewhere closes the menu
+        let dropdown_area = self.dropdown_area(area);
+        if dropdown_area.contains(position)
+            && let Some(row) = position.y.checked_sub(dropdown_area.y + 1)
+            && (row as usize) < self.items().len()
+        {
+            self.selected = row as usize;
+            return self.run_selected();
+        }
+        ManagerAction::new(true).with_close(true)
+    }
+}
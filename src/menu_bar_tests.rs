@@ -0,0 +1,90 @@
+use crossterm::event::KeyModifiers;
+
+use super::*;
+use crate::{command_palette::PaletteAction, tui::event::ViewManagerEvent};
+
+fn titles(menu: &Menu) -> Vec<&str> { menu.items.iter().map(|item| item.title.as_str()).collect() }
+
+#[test]
+fn test_menus() {
+    let history = menus(Scope::History);
+    let menu_titles: Vec<&str> = history.iter().map(|menu| menu.title).collect();
+    assert_eq!(menu_titles, vec!["File", "Edit", "View", "Help"]);
+    assert!(titles(&history[2]).contains(&"Trash"));
+    assert_eq!(
+        titles(&history[3]),
+        vec!["Key bindings...", "Configuration..."]
+    );
+    assert_eq!(history[3].items[1].keys, "F12");
+
+    // the items of the other view are left out
+    let shortcuts = menus(Scope::Shortcuts);
+    assert!(!titles(&shortcuts[2]).contains(&"Trash"));
+    assert!(titles(&shortcuts[1]).contains(&"Suggest shortcuts..."));
+    assert!(!titles(&history[1]).contains(&"Suggest shortcuts..."));
+}
+
+#[test]
+fn test_menu_at() {
+    let menus = menus(Scope::History);
+    // " File  Edit  View  Help"
+    assert_eq!(menu_at(&menus, 0), None);
+    assert_eq!(menu_at(&menus, 1), Some(0));
+    assert_eq!(menu_at(&menus, 6), Some(0));
+    assert_eq!(menu_at(&menus, 7), Some(1));
+    assert_eq!(menu_at(&menus, 19), Some(3));
+    assert_eq!(menu_at(&menus, 25), None);
+}
+
+#[test]
+fn test_navigate_and_run() {
+    let vm = ViewManager::new();
+    let mut rx = vm.tx().subscribe();
+    let mut view = MenuView::new(vm.tx(), menus(Scope::History), 0, ThemeStyles::default());
+    let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+
+    // the menus wrap around the menu bar
+    view.handle_key_event(key(KeyCode::Left));
+    assert_eq!(view.open, 3);
+    view.handle_key_event(key(KeyCode::Down));
+    view.handle_key_event(key(KeyCode::Down));
+    assert_eq!(view.selected, 1);
+    view.handle_key_event(key(KeyCode::Right));
+    assert_eq!((view.open, view.selected), (0, 0));
+
+    view.move_open(-1);
+    view.handle_key_event(key(KeyCode::Down));
+    let (_, action) = view.handle_key_event(key(KeyCode::Enter));
+    assert!(action.close());
+    match rx.try_recv() {
+        Ok(GenericEvent::ViewManagerEvent(ViewManagerEvent::Key(key_event))) => {
+            assert_eq!(key_event.code, KeyCode::F(12))
+        }
+        _ => panic!("the configuration key was not replayed"),
+    }
+
+    // a click on an item runs it
+    let area = Rect::new(0, 0, 80, 24);
+    let click = |column, row| MouseEvent {
+        kind: MouseEventKind::Down(MouseButton::Left),
+        column,
+        row,
+        modifiers: KeyModifiers::NONE,
+    };
+    assert!(!view.handle_mouse_event(area, click(8, 0)).close());
+    assert_eq!(view.open, 1);
+    let dropdown_area = view.dropdown_area(area);
+    assert_eq!(dropdown_area.x, 7);
+    let action = view.handle_mouse_event(area, click(8, 2));
+    assert!(action.close());
+    assert_eq!(view.selected, 0);
+    assert!(matches!(
+        &view.items()[0].action,
+        PaletteAction::Key(key_event) if key_event.code == KeyCode::Char('e')
+    ));
+    assert!(rx.try_recv().is_ok());
+
+    // a click elsewhere closes the menu without running anything
+    assert!(view.handle_mouse_event(area, click(70, 20)).close());
+    assert!(rx.try_recv().is_err());
+}
@@ -1,9 +1,12 @@
 use std::sync::Arc;
 
-use log::{debug, error, trace};
 use tokio::sync::broadcast;
+use tracing::{debug, error, trace};
 
-use crate::tui::{GenericEvent, event::ApplicationEvent};
+use crate::{
+    store::{Filter, SortOrder},
+    tui::{GenericEvent, event::ApplicationEvent},
+};
 
 #[cfg(test)]
 #[path = "model_tests.rs"]
@@ -13,6 +16,127 @@ mod model_tests;
 pub struct DataStatePayload {
     pub objects_type: String,
     pub is_empty: bool,
+    /// The index of the first entry of the view
+    pub first: usize,
+    /// The number of entries of the view
+    pub length: u16,
+    /// The number of entries matching the filter, None when unknown
+    pub total: Option<usize>,
+}
+
+/// The id of the application event requesting the model of `objects_type` to prefetch the
+/// entries around its view, its payload being a `DataPrefetchPayload`
+pub(crate) const DATA_PREFETCH_EVENT: &str = "data.prefetch";
+
+/// The number of pages of entries prefetched on each side of the view
+const PREFETCH_PAGES: usize = 3;
+
+/// The number of pages from the edge of the prefetched entries under which the entries are
+/// prefetched again. Being lower than `PREFETCH_PAGES`, the view moves by a few pages between
+/// two prefetches.
+const PREFETCH_MARGIN_PAGES: usize = 1;
+
+// "data.prefetch"
+pub struct DataPrefetchPayload {
+    pub objects_type: String,
+}
+
+/// The id of the application event requesting the model of `objects_type` to query the
+/// entries shown from the previous session at startup, its payload being a
+/// `DataReconcilePayload`
+pub(crate) const DATA_RECONCILE_EVENT: &str = "data.reconcile";
+
+// "data.reconcile"
+pub struct DataReconcilePayload {
+    pub objects_type: String,
+}
+
+/// A type alias for a function loading the entries of the first page persisted by the previous
+/// session, empty when there are none.
+pub(crate) type WarmLoadFunction<T> = dyn Fn() -> Vec<T>;
+
+/// A type alias for a function persisting the entries of the first page once queried, for the
+/// next session.
+pub(crate) type WarmSaveFunction<T> = dyn Fn(&[T]);
+
+/// The first page of entries of the previous session, shown at startup until the entries are
+/// queried, so that the view is drawn without waiting for a long query
+pub struct WarmStart<T> {
+    pub load_fn: Box<WarmLoadFunction<T>>,
+    pub save_fn: Box<WarmSaveFunction<T>>,
+}
+
+/// Entries fetched beyond the view, serving the next updates without querying
+struct PrefetchedEntries<T> {
+    first: usize,
+    entries: Vec<T>,
+    /// Whether the entries reach the end of the data
+    complete: bool,
+}
+
+impl<T: Clone> PrefetchedEntries<T> {
+    /// The entries of the range, when known: within the prefetched entries, or after them
+    /// when they reach the end of the data.
+    fn get(&self, first: usize, length: u16) -> Option<Vec<T>> {
+        let end = self.first + self.entries.len();
+        if first < self.first || (first + length as usize > end && !self.complete) {
+            return None;
+        }
+        let start = (first - self.first).min(self.entries.len());
+        let stop = (start + length as usize).min(self.entries.len());
+        Some(self.entries[start..stop].to_vec())
+    }
+}
+
+/// The separator of the path segments completed by `completion`
+const PATH_SEPARATOR: char = '/';
+
+/// The byte index of the last occurrence of `needle` in `haystack`, ignoring the case
+fn rfind_ignore_case(haystack: &str, needle: &str) -> Option<usize> {
+    let needle: Vec<char> = needle.chars().flat_map(char::to_lowercase).collect();
+    haystack.char_indices().rev().find_map(|(idx, _)| {
+        let mut rest = haystack[idx..].chars().flat_map(char::to_lowercase);
+        needle
+            .iter()
+            .all(|c| rest.next() == Some(*c))
+            .then_some(idx)
+    })
+}
+
+/// The completion of the search `text` offered by `paths`, like a shell: the longest text
+/// following the last occurrence of the search in all the paths containing it, up to the end
+/// of a path segment. The paths not containing the search are ignored, e.g. a shortcut matched
+/// by its name.
+///
+/// Returns None when there is nothing to complete.
+pub(crate) fn completion<I: IntoIterator<Item = String>>(text: &str, paths: I) -> Option<String> {
+    if text.is_empty() {
+        return None;
+    }
+    let mut common: Option<String> = None;
+    for path in paths {
+        let Some(idx) = rfind_ignore_case(&path, text) else {
+            continue;
+        };
+        // the match is followed by the same number of characters, whatever their case
+        let continuation: String = path[idx..].chars().skip(text.chars().count()).collect();
+        common = Some(match common {
+            None => continuation,
+            Some(common) => common
+                .chars()
+                .zip(continuation.chars())
+                .take_while(|(a, b)| a == b)
+                .map(|(a, _)| a)
+                .collect(),
+        });
+    }
+    let common = common?;
+    // a single segment at once, with its separator
+    let segment = match common.find(PATH_SEPARATOR) {
+        Some(idx) => common[..=idx].to_string(),
+        None => common,
+    };
+    (!segment.is_empty()).then_some(segment)
 }
 
 /// A type alias for a function that retrieves a list of data entries based on the given parameters.
@@ -24,14 +148,19 @@ pub struct DataStatePayload {
 /// # Parameters
 /// - `start`: The starting index of the data entries to retrieve.
 /// - `count`: The maximum number of data entries to retrieve.
-/// - `filter`: A string used as a filter or search term for the data entries.
-/// - `fuzzy`: If true, perform a fuzzy search ; else perform an exact search
+/// - `filters`: The stack of filters the data entries must match, the last one being the
+///   current search (its text, and whether the search is fuzzy).
+/// - `sort`: The ordering of the data entries
 ///
 /// # Returns
 /// - `Result<Vec<T>, rusqlite::Error>`: A `Result` containing either a vector of data entries
 ///   (`Vec<T>`) on success or a `rusqlite::Error` on failure.
 pub(crate) type ListFunction<T> =
-    dyn Fn(usize, usize, &str, bool) -> Result<Vec<T>, rusqlite::Error>;
+    dyn Fn(usize, usize, &[Filter], SortOrder) -> Result<Vec<T>, rusqlite::Error>;
+
+/// A type alias for a function that counts the data entries matching a stack of filters, as
+/// listed by the `ListFunction`.
+pub(crate) type CountFunction = dyn Fn(&[Filter]) -> Result<usize, rusqlite::Error>;
 
 /// A model representing a view of data, typically used for managing and displaying
 /// a subset of entries with filtering and pagination capabilities.
@@ -45,7 +174,15 @@ pub(crate) type ListFunction<T> =
 /// - `list_fn`: A boxed function or closure responsible for fetching or generating the list of entries.
 /// - `first`: The index of the first entry in the current view.
 /// - `length`: The number of entries to display in the current view.
-/// - `filter`: A string used to filter the entries based on some criteria.
+/// - `filters`: The stack of filters of the entries: the frozen searches, then the current one.
+/// - `sort`: The ordering of the entries.
+/// - `count_fn`: An optional function counting the entries matching the filter.
+/// - `total`: The number of entries matching the filter, computed when first needed.
+/// - `prefetched`: The entries around the view, fetched ahead of the scrolling.
+/// - `prefetch_requested`: Whether a `DATA_PREFETCH_EVENT` is pending.
+/// - `warm_start`: The first page of the previous session, shown at startup.
+/// - `warm_started`: Whether the first page of the previous session was looked for.
+/// - `warm`: Whether the entries are the ones of the previous session, not queried yet.
 pub(crate) struct DataViewModel<T> {
     objects_type: String,
     tx: broadcast::Sender<GenericEvent>,
@@ -53,8 +190,15 @@ pub(crate) struct DataViewModel<T> {
     pub(crate) list_fn: Box<ListFunction<T>>,
     pub(crate) first: usize,
     pub(crate) length: u16,
-    filter: String,
-    fuzzy_match: bool,
+    filters: Vec<Filter>,
+    sort: SortOrder,
+    count_fn: Option<Box<CountFunction>>,
+    total: Option<usize>,
+    prefetched: Option<PrefetchedEntries<T>>,
+    prefetch_requested: bool,
+    warm_start: Option<WarmStart<T>>,
+    warm_started: bool,
+    warm: bool,
 }
 
 impl<T: Clone> DataViewModel<T> {
@@ -79,17 +223,59 @@ impl<T: Clone> DataViewModel<T> {
             list_fn,
             first: 0,
             length: 0,
-            filter: String::new(),
-            fuzzy_match,
+            filters: vec![Filter::new("", fuzzy_match)],
+            sort: SortOrder::Default,
+            count_fn: None,
+            total: None,
+            prefetched: None,
+            prefetch_requested: false,
+            warm_start: None,
+            warm_started: false,
+            warm: false,
+        }
+    }
+
+    /// Sets the first page of the previous session, shown by the first update of the first
+    /// page instead of querying it.
+    pub(crate) fn with_warm_start(mut self, warm_start: Option<WarmStart<T>>) -> Self {
+        self.warm_start = warm_start;
+        self
+    }
+
+    /// Sets the function counting the entries matching the filter, making `total` available.
+    pub(crate) fn with_count_fn(mut self, count_fn: Box<CountFunction>) -> Self {
+        self.count_fn = Some(count_fn);
+        self
+    }
+
+    /// The number of entries matching the filter, None without a count function.
+    /// The count is cached until the filter changes or the data is reloaded.
+    pub(crate) fn total(&mut self) -> Option<usize> {
+        if self.total.is_none()
+            && let Some(count_fn) = &self.count_fn
+        {
+            match count_fn(&self.filters) {
+                Ok(total) => self.total = Some(total),
+                Err(err) => error!("count failed: {}", err),
+            }
         }
+        self.total
     }
 
-    fn publish(&self) {
+    fn publish(&mut self) {
+        // counting would query the entries the previous session spares
+        let total = match self.warm {
+            true => None,
+            false => self.total(),
+        };
         let event = GenericEvent::ApplicationEvent(ApplicationEvent {
             id: String::from("data.payload"),
             payload: Some(Arc::new(DataStatePayload {
                 objects_type: self.objects_type.clone(),
                 is_empty: self.length == 0,
+                first: self.first,
+                length: self.length,
+                total,
             })),
         });
         debug!("model sending event={:?}", event);
@@ -138,18 +324,73 @@ impl<T: Clone> DataViewModel<T> {
         true
     }
 
+    /// The current filter, the last of the stack
+    fn current_filter(&mut self) -> &mut Filter {
+        // the stack always holds the current filter
+        self.filters.last_mut().unwrap()
+    }
+
+    fn fuzzy_match(&self) -> bool { self.filters.last().is_some_and(|filter| filter.fuzzy) }
+
     pub(crate) fn set_fuzzy_match(&mut self, fuzzy_match: bool) {
         debug!("fuzzy_match={}", fuzzy_match);
-        if self.fuzzy_match == fuzzy_match {
+        if self.fuzzy_match() == fuzzy_match {
             return;
         }
-        self.fuzzy_match = fuzzy_match;
+        self.current_filter().fuzzy = fuzzy_match;
+        self.total = None;
+        self.prefetched = None;
         self.update(self.first, self.length, true);
     }
 
+    pub(crate) fn sort(&self) -> SortOrder { self.sort }
+
+    /// Changes the ordering of the entries and reloads the view from its beginning.
+    pub(crate) fn set_sort(&mut self, length: u16, sort: SortOrder) {
+        debug!("sort={:?}", sort);
+        self.sort = sort;
+        self.prefetched = None;
+        self.update(0, length, true);
+    }
+
+    /// Sets the current filter without loading the entries: it applies from the next update.
+    pub(crate) fn set_filter(&mut self, filter: &str, fuzzy: bool) {
+        *self.current_filter() = Filter::new(filter, fuzzy);
+        self.total = None;
+        self.prefetched = None;
+    }
+
+    /// Sets the frozen filters narrowed by the current one, without loading the entries.
+    pub(crate) fn set_frozen_filters(&mut self, frozen_filters: &[Filter]) {
+        if self.filters[..self.filters.len() - 1] == *frozen_filters {
+            return;
+        }
+        let current = self.current_filter().clone();
+        self.filters = frozen_filters.to_vec();
+        self.filters.push(current);
+        self.total = None;
+        self.prefetched = None;
+    }
+
+    /// The text of the current search
+    pub(crate) fn search_string(&self) -> &str {
+        self.filters
+            .last()
+            .map_or("", |filter| filter.text.as_str())
+    }
+
+    /// The completion of the current search by the paths of the entries of the view, given by
+    /// `path_fn`, see `completion`. The fuzzy searches are not completed.
+    pub(crate) fn completion(&self, path_fn: fn(&T) -> String) -> Option<String> {
+        let filter = self.filters.last()?;
+        if filter.fuzzy {
+            return None;
+        }
+        completion(&filter.text, self.entries.iter().flatten().map(path_fn))
+    }
+
     pub(crate) fn update_filter(&mut self, length: u16, filter: &str, fuzzy: bool) {
-        self.filter = String::from(filter);
-        self.fuzzy_match = fuzzy;
+        self.set_filter(filter, fuzzy);
         self.update(0, length, true);
     }
 
@@ -169,14 +410,21 @@ impl<T: Clone> DataViewModel<T> {
     /// `true` if the data view was updated; otherwise, `false`.
     pub(crate) fn update(&mut self, first: usize, length: u16, force: bool) -> bool {
         trace!("update first={} length={} force={}", first, length, force);
-        if !force && !self.fuzzy_match && self.update_into_subset(first, length) {
+        if self.show_warm_start(first, length) {
+            return true;
+        }
+        if !force && !self.fuzzy_match() && self.update_into_subset(first, length) {
             trace!("subset found");
             return false;
         }
-        let new_entries: Result<Vec<T>, rusqlite::Error> =
-            (self.list_fn)(first, length as usize, &self.filter, self.fuzzy_match);
+        let new_entries = self.fetch(first, length);
+        self.request_prefetch(first, length);
         match new_entries {
             Ok(new_entries) => {
+                self.warm = false;
+                if first == 0 {
+                    self.save_first_page(&new_entries);
+                }
                 let new_length = new_entries.len();
                 if !force && (new_length != length as usize) {
                     // If we have less data than requested and it is a subset, we don't update
@@ -214,6 +462,142 @@ impl<T: Clone> DataViewModel<T> {
         }
     }
 
+    /// Whether the entries are listed without a search, in the default order: the first page
+    /// of the previous session only applies to them
+    fn is_default_view(&self) -> bool {
+        self.sort == SortOrder::Default && self.filters.iter().all(|filter| filter.text.is_empty())
+    }
+
+    /// Shows the first page of the previous session instead of querying the first page, once,
+    /// and publishes a `DATA_RECONCILE_EVENT` so that the entries are queried once it is
+    /// displayed, see `reconcile`.
+    ///
+    /// ### Returns
+    /// `true` if the entries of the previous session are shown; otherwise, `false`.
+    fn show_warm_start(&mut self, first: usize, length: u16) -> bool {
+        let Some(warm_start) = self.warm_start.as_ref().filter(|_| !self.warm_started) else {
+            return false;
+        };
+        self.warm_started = true;
+        if first != 0 || length == 0 || !self.is_default_view() {
+            return false;
+        }
+        let mut entries = (warm_start.load_fn)();
+        if entries.is_empty() {
+            return false;
+        }
+        entries.truncate(length as usize);
+        debug!("warm start with {} entries", entries.len());
+        self.first = 0;
+        self.length = entries.len() as u16;
+        self.entries = Some(entries);
+        self.warm = true;
+        self.publish();
+        let event = GenericEvent::ApplicationEvent(ApplicationEvent {
+            id: String::from(DATA_RECONCILE_EVENT),
+            payload: Some(Arc::new(DataReconcilePayload {
+                objects_type: self.objects_type.clone(),
+            })),
+        });
+        if let Err(e) = self.tx.send(event) {
+            error!("Failed to send '{}' event: {}", DATA_RECONCILE_EVENT, e);
+        }
+        true
+    }
+
+    /// Replaces the entries of the previous session by the queried ones, unless they were
+    /// already replaced, e.g. by a search.
+    ///
+    /// ### Returns
+    /// `true` if the data view was updated; otherwise, `false`.
+    pub(crate) fn reconcile(&mut self, length: u16) -> bool {
+        if !self.warm {
+            return false;
+        }
+        debug!("reconcile length={}", length);
+        self.update(self.first, length, true)
+    }
+
+    /// Persists the entries of the first page for the next session, when listed by default
+    fn save_first_page(&self, entries: &[T]) {
+        if let Some(warm_start) = &self.warm_start
+            && self.is_default_view()
+        {
+            (warm_start.save_fn)(entries);
+        }
+    }
+
+    /// The entries of a range, from the prefetched entries if they hold them, else from the
+    /// list function.
+    fn fetch(&mut self, first: usize, length: u16) -> Result<Vec<T>, rusqlite::Error> {
+        if let Some(entries) = self
+            .prefetched
+            .as_ref()
+            .and_then(|prefetched| prefetched.get(first, length))
+        {
+            trace!(
+                "fetch first={} length={} from the prefetched entries",
+                first, length
+            );
+            return Ok(entries);
+        }
+        let entries = tracing::info_span!("query", objects_type = %self.objects_type)
+            .in_scope(|| (self.list_fn)(first, length as usize, &self.filters, self.sort))?;
+        self.prefetched = Some(PrefetchedEntries {
+            first,
+            complete: entries.len() < length as usize,
+            entries: entries.clone(),
+        });
+        Ok(entries)
+    }
+
+    /// Publishes a `DATA_PREFETCH_EVENT` when the range gets within `PREFETCH_MARGIN_PAGES` of
+    /// an edge of the prefetched entries, so that the next pages are fetched once the current
+    /// one is displayed.
+    fn request_prefetch(&mut self, first: usize, length: u16) {
+        let Some(prefetched) = &self.prefetched else {
+            return;
+        };
+        let margin = PREFETCH_MARGIN_PAGES * length.max(1) as usize;
+        let end = prefetched.first + prefetched.entries.len();
+        let near_end = !prefetched.complete && first + length as usize + margin > end;
+        let near_start = prefetched.first > 0 && first < prefetched.first + margin;
+        if self.prefetch_requested || !(near_end || near_start) {
+            return;
+        }
+        trace!("request_prefetch first={} length={}", first, length);
+        self.prefetch_requested = true;
+        let event = GenericEvent::ApplicationEvent(ApplicationEvent {
+            id: String::from(DATA_PREFETCH_EVENT),
+            payload: Some(Arc::new(DataPrefetchPayload {
+                objects_type: self.objects_type.clone(),
+            })),
+        });
+        if let Err(e) = self.tx.send(event) {
+            error!("Failed to send '{}' event: {}", DATA_PREFETCH_EVENT, e);
+        }
+    }
+
+    /// Fetches `PREFETCH_PAGES` pages of entries on each side of the view, `length` being the
+    /// size of a page.
+    pub(crate) fn prefetch(&mut self, length: u16) {
+        self.prefetch_requested = false;
+        let page = length.max(1) as usize;
+        let first = self.first.saturating_sub(PREFETCH_PAGES * page);
+        let count = self.first - first + page * (PREFETCH_PAGES + 1);
+        debug!("prefetch first={} count={}", first, count);
+        match (self.list_fn)(first, count, &self.filters, self.sort) {
+            Ok(entries) => {
+                self.prefetched = Some(PrefetchedEntries {
+                    first,
+                    complete: entries.len() < count,
+                    entries,
+                })
+            }
+            Err(err) => error!("prefetch failed: {}", err),
+        }
+    }
+
     /// Updates the data view by applying an offset to the current starting index and
     /// adjusting the range accordingly.
     ///
@@ -237,16 +621,18 @@ impl<T: Clone> DataViewModel<T> {
     /// Reloads the current data view by fetching new entries based on the existing
     /// starting index, length, and filter.
     pub(crate) fn reload(&mut self) {
-        let new_entries: Result<Vec<T>, rusqlite::Error> = (self.list_fn)(
-            self.first,
-            self.length as usize,
-            self.filter.as_str(),
-            self.fuzzy_match,
-        );
+        self.warm = false;
+        self.total = None;
+        self.prefetched = None;
+        let new_entries: Result<Vec<T>, rusqlite::Error> =
+            (self.list_fn)(self.first, self.length as usize, &self.filters, self.sort);
         match new_entries {
             Ok(new_entries) => {
                 let new_length = new_entries.len();
                 if new_length > 0 {
+                    if self.first == 0 {
+                        self.save_first_page(&new_entries);
+                    }
                     self.entries = Some(new_entries);
                     self.length = new_length as u16;
                     trace!("Updated");
@@ -1,6 +1,15 @@
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
 use tokio::sync::broadcast;
 
-use crate::{model::DataViewModel, store::Store, tui::GenericEvent};
+use crate::{
+    model::{DATA_PREFETCH_EVENT, DATA_RECONCILE_EVENT, DataViewModel, WarmStart, completion},
+    store::{Filter, Path, Store},
+    tui::GenericEvent,
+};
 
 #[test]
 fn test_scroll() {
@@ -15,7 +24,7 @@ fn test_scroll() {
     let mut model = DataViewModel::new(
         "test".to_string(),
         tx,
-        Box::new(move |pos, len, text, fuzzy| store.list_paths(pos, len, text, fuzzy)),
+        Box::new(move |pos, len, filters, sort| store.list_paths_filtered(pos, len, filters, sort)),
         false,
     );
     assert!(model.entries.is_none());
@@ -76,3 +85,248 @@ fn test_scroll() {
     assert_eq!(model.entries.as_ref().unwrap().len(), 1);
     assert_eq!(model.entries.as_ref().unwrap()[0].path, "/5");
 }
+
+#[test]
+fn test_total() {
+    let tx = broadcast::channel::<GenericEvent>(16).0;
+    let store = Store::setup_test_store();
+    store.add_path("/home/a").unwrap();
+    store.add_path("/home/b").unwrap();
+    store.add_path("/var").unwrap();
+
+    let list_store = store.clone();
+    let mut model = DataViewModel::new(
+        "test".to_string(),
+        tx,
+        Box::new(move |pos, len, filters, sort| {
+            list_store.list_paths_filtered(pos, len, filters, sort)
+        }),
+        false,
+    )
+    .with_count_fn(Box::new(move |filters| store.count_paths_filtered(filters)));
+
+    model.update(0, 2, false);
+    assert_eq!(model.entries.as_ref().unwrap().len(), 2);
+    assert_eq!(model.total(), Some(3));
+
+    model.update_filter(2, "home", false);
+    assert_eq!(model.total(), Some(2));
+}
+
+#[test]
+fn test_prefetch() {
+    let (tx, mut rx) = broadcast::channel::<GenericEvent>(64);
+    let store = Store::setup_test_store();
+    for i in 0..50 {
+        store
+            .add_path_with_time(&format!("/{}", i), 1000 - i)
+            .unwrap();
+    }
+    let queries = Rc::new(Cell::new(0));
+    let list_queries = queries.clone();
+    let mut model = DataViewModel::new(
+        "test".to_string(),
+        tx,
+        Box::new(move |pos, len, filters, sort| {
+            list_queries.set(list_queries.get() + 1);
+            store.list_paths_filtered(pos, len, filters, sort)
+        }),
+        false,
+    );
+    let mut prefetch_requested = || {
+        let mut requested = false;
+        while let Ok(event) = rx.try_recv() {
+            if let GenericEvent::ApplicationEvent(ae) = event {
+                requested |= ae.id == DATA_PREFETCH_EVENT;
+            }
+        }
+        requested
+    };
+
+    model.update(0, 5, false);
+    assert_eq!(queries.get(), 1);
+    assert!(prefetch_requested());
+
+    // the next 3 pages are prefetched
+    model.prefetch(5);
+    assert_eq!(queries.get(), 2);
+    for first in 1..=10 {
+        model.update(first, 5, false);
+        assert_eq!(
+            model.entries.as_ref().unwrap()[0].path,
+            format!("/{}", first)
+        );
+    }
+    assert_eq!(queries.get(), 2);
+    assert!(!prefetch_requested());
+
+    // within a page of the end of the prefetched entries
+    model.update(11, 5, false);
+    assert_eq!(queries.get(), 2);
+    assert!(prefetch_requested());
+    // only requested once
+    model.update(12, 5, false);
+    assert!(!prefetch_requested());
+
+    model.prefetch(5);
+    assert_eq!(queries.get(), 3);
+    model.update(25, 5, false);
+    assert_eq!(model.entries.as_ref().unwrap()[0].path, "/25");
+    assert_eq!(queries.get(), 3);
+
+    // the filter drops the prefetched entries
+    model.update_filter(5, "4", false);
+    assert_eq!(queries.get(), 4);
+    assert_eq!(model.entries.as_ref().unwrap()[0].path, "/4");
+}
+
+#[test]
+fn test_frozen_filters() {
+    let tx = broadcast::channel::<GenericEvent>(16).0;
+    let store = Store::setup_test_store();
+    store.add_path("/home/src/a").unwrap();
+    store.add_path("/home/doc/b").unwrap();
+    store.add_path("/var/src/c").unwrap();
+
+    let list_store = store.clone();
+    let mut model = DataViewModel::new(
+        "test".to_string(),
+        tx,
+        Box::new(move |pos, len, filters, sort| {
+            list_store.list_paths_filtered(pos, len, filters, sort)
+        }),
+        false,
+    )
+    .with_count_fn(Box::new(move |filters| store.count_paths_filtered(filters)));
+    let paths = |model: &DataViewModel<_>| {
+        model
+            .entries
+            .as_ref()
+            .map(|entries: &Vec<crate::store::Path>| {
+                entries.iter().map(|p| p.path.clone()).collect::<Vec<_>>()
+            })
+            .unwrap_or_default()
+    };
+
+    model.update_filter(10, "home", false);
+    assert_eq!(paths(&model), vec!["/home/doc/b", "/home/src/a"]);
+
+    // the search narrows the frozen one
+    model.set_frozen_filters(&[Filter::new("home", false)]);
+    model.update_filter(10, "src", true);
+    assert_eq!(paths(&model), vec!["/home/src/a"]);
+    assert_eq!(model.total(), Some(1));
+
+    model.set_frozen_filters(&[]);
+    model.update_filter(10, "src", true);
+    assert_eq!(paths(&model).len(), 2);
+    assert_eq!(model.total(), Some(2));
+}
+
+#[test]
+fn test_completion() {
+    let paths = || {
+        vec![
+            String::from("/home/me/Projects/cdir/src"),
+            String::from("/home/me/projects/cdir/docs"),
+            String::from("/tmp"),
+        ]
+    };
+
+    // the common text after the search, up to the end of the segment
+    assert_eq!(completion("proj", paths()), Some(String::from("ects/")));
+    assert_eq!(
+        completion("projects/", paths()),
+        Some(String::from("cdir/"))
+    );
+    // then the paths differ
+    assert_eq!(completion("projects/cdir/", paths()), None);
+    // the last occurrence of the search in the path is completed
+    assert_eq!(completion("me", paths()), Some(String::from("/")));
+    assert_eq!(completion("", paths()), None);
+    assert_eq!(completion("nothing", paths()), None);
+}
+
+#[test]
+fn test_model_completion() {
+    let tx = broadcast::channel::<GenericEvent>(16).0;
+    let store = Store::setup_test_store();
+    store.add_path("/home/me/work/a").unwrap();
+    store.add_path("/home/me/work/b").unwrap();
+    let mut model = DataViewModel::new(
+        "test".to_string(),
+        tx,
+        Box::new(move |pos, len, filters, sort| store.list_paths_filtered(pos, len, filters, sort)),
+        false,
+    );
+
+    model.update_filter(10, "wo", false);
+    assert_eq!(model.search_string(), "wo");
+    assert_eq!(
+        model.completion(|path| path.path.clone()),
+        Some(String::from("rk/"))
+    );
+
+    // the fuzzy searches are not completed
+    model.update_filter(10, "wo", true);
+    assert_eq!(model.completion(|path| path.path.clone()), None);
+}
+
+#[test]
+fn test_warm_start() {
+    let (tx, mut rx) = broadcast::channel::<GenericEvent>(64);
+    let store = Store::setup_test_store();
+    for i in 0..5 {
+        store
+            .add_path_with_time(&format!("/{}", i), 100 - i)
+            .unwrap();
+    }
+    let queries = Rc::new(Cell::new(0));
+    let list_queries = queries.clone();
+    let saved = Rc::new(RefCell::new(vec![]));
+    let save_saved = saved.clone();
+    let mut model = DataViewModel::new(
+        "test".to_string(),
+        tx,
+        Box::new(move |pos, len, filters, sort| {
+            list_queries.set(list_queries.get() + 1);
+            store.list_paths_filtered(pos, len, filters, sort)
+        }),
+        false,
+    )
+    .with_warm_start(Some(WarmStart {
+        load_fn: Box::new(|| vec![Path::new(1, String::from("/warm"), 0, &[])]),
+        save_fn: Box::new(move |paths| {
+            *save_saved.borrow_mut() = paths.iter().map(|path| path.path.clone()).collect()
+        }),
+    }));
+    let mut reconcile_requested = || {
+        let mut requested = false;
+        while let Ok(event) = rx.try_recv() {
+            if let GenericEvent::ApplicationEvent(ae) = event {
+                requested |= ae.id == DATA_RECONCILE_EVENT;
+            }
+        }
+        requested
+    };
+
+    // the first page of the previous session, without querying
+    model.update(0, 3, true);
+    assert_eq!(queries.get(), 0);
+    assert_eq!(model.entries.as_ref().unwrap()[0].path, "/warm");
+    assert!(reconcile_requested());
+
+    // then queried, and persisted for the next session
+    assert!(model.reconcile(3));
+    assert_eq!(queries.get(), 1);
+    assert_eq!(model.entries.as_ref().unwrap()[0].path, "/0");
+    assert_eq!(*saved.borrow(), vec!["/0", "/1", "/2"]);
+    assert!(!model.reconcile(3));
+
+    // only once, and only the first page without a search is persisted
+    model.update(0, 3, true);
+    assert_eq!(model.entries.as_ref().unwrap()[0].path, "/0");
+    assert!(!reconcile_requested());
+    model.update_filter(3, "3", false);
+    assert_eq!(*saved.borrow(), vec!["/0", "/1", "/2"]);
+}
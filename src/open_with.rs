@@ -0,0 +1,50 @@
+use crate::tui::event::ExternalCommand;
+
+#[cfg(test)]
+#[path = "open_with_tests.rs"]
+mod open_with_tests;
+
+/// The placeholder replaced by the selected path in the "open with" commands
+pub const PATH_PLACEHOLDER: &str = "{path}";
+
+/// Quote a string so that the shell takes it as a single word.
+pub fn shell_quote(s: &str) -> String { format!("'{}'", s.replace('\'', r"'\''")) }
+
+/// Build the shell script opening the file `path` in `$EDITOR`, vi when it is not set, to be
+/// evaluated by the shell wrapper.
+pub fn editor_script(path: &str) -> String {
+    format!("\"${{EDITOR:-vi}}\" {}\n", shell_quote(path))
+}
+
+/// Build the command running an "open with" command line on a path.
+/// Each `{path}` of the command line is replaced by the quoted path; when there is none,
+/// the path is appended as the last argument.
+pub fn build_open_command(command_line: &str, path: &str) -> ExternalCommand {
+    let quoted_path = shell_quote(path);
+    let command_line = if command_line.contains(PATH_PLACEHOLDER) {
+        command_line.replace(PATH_PLACEHOLDER, &quoted_path)
+    } else {
+        format!("{} {}", command_line, quoted_path)
+    };
+    ExternalCommand {
+        program: String::from("sh"),
+        args: vec![String::from("-c"), command_line],
+        cwd: None,
+        output_event_id: None,
+    }
+}
+
+/// Build the command running a one-off command line in a directory, its output being
+/// captured and published with the `output_event_id` application event.
+pub fn build_run_command(
+    command_line: &str,
+    directory: &str,
+    output_event_id: &str,
+) -> ExternalCommand {
+    ExternalCommand {
+        program: String::from("sh"),
+        args: vec![String::from("-c"), command_line.to_string()],
+        cwd: Some(directory.to_string()),
+        output_event_id: Some(output_event_id.to_string()),
+    }
+}
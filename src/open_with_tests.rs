@@ -0,0 +1,36 @@
+use super::*;
+
+#[test]
+fn test_shell_quote() {
+    assert_eq!(shell_quote("/tmp/a b"), "'/tmp/a b'");
+    assert_eq!(shell_quote("/tmp/it's"), r"'/tmp/it'\''s'");
+}
+
+#[test]
+fn test_build_open_command_with_placeholder() {
+    let command = build_open_command("code -n {path}", "/home/user/my project");
+    assert_eq!(command.program, "sh");
+    assert_eq!(command.args, vec!["-c", "code -n '/home/user/my project'"]);
+}
+
+#[test]
+fn test_build_open_command_without_placeholder() {
+    let command = build_open_command("xdg-open", "/tmp");
+    assert_eq!(command.args, vec!["-c", "xdg-open '/tmp'"]);
+}
+
+#[test]
+fn test_build_run_command() {
+    let command = build_run_command("git status", "/home/user/my project", "command.output");
+    assert_eq!(command.args, vec!["-c", "git status"]);
+    assert_eq!(command.cwd.as_deref(), Some("/home/user/my project"));
+    assert_eq!(command.output_event_id.as_deref(), Some("command.output"));
+}
+
+#[test]
+fn test_editor_script() {
+    assert_eq!(
+        editor_script("/home/user/it's.md"),
+        "\"${EDITOR:-vi}\" '/home/user/it'\\''s.md'\n"
+    );
+}
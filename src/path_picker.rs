@@ -0,0 +1,191 @@
+use std::{fs, path::Path};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+use tracing::{debug, info};
+
+use crate::{
+    theme::ThemeStyles,
+    tui::{EventCaptured, ManagerAction, View, ViewBuilder, ViewManager},
+};
+
+#[cfg(test)]
+#[path = "path_picker_tests.rs"]
+mod path_picker_tests;
+
+/// Called with the directory picked
+pub type PickFn = Box<dyn Fn(&str)>;
+
+/// The maximum number of sub directories listed below the typed path
+const MAX_CANDIDATES: usize = 50;
+
+/// A modal typing the path of a directory, the sub directories matching the last segment of the
+/// typed path being listed to complete it.
+pub struct PathPicker {
+    styles: ThemeStyles,
+    title: String,
+    input: String,
+    /// The sub directories of the parent of the input starting like its last segment
+    candidates: Vec<String>,
+    selected: usize,
+    on_pick: PickFn,
+}
+
+impl PathPicker {
+    pub fn builder(title: String, path: &str, styles: ThemeStyles, on_pick: PickFn) -> ViewBuilder {
+        ViewBuilder::from(Box::new(Self::new(title, path, styles, on_pick)))
+    }
+
+    fn new(title: String, path: &str, styles: ThemeStyles, on_pick: PickFn) -> Self {
+        let mut picker = Self {
+            styles,
+            title,
+            input: path.to_string(),
+            candidates: vec![],
+            selected: 0,
+            on_pick,
+        };
+        picker.update_candidates();
+        picker
+    }
+
+    /// List the sub directories completing the input, the hidden ones only when the last
+    /// segment starts with a dot.
+    fn update_candidates(&mut self) {
+        self.selected = 0;
+        self.candidates.clear();
+        let Some(idx) = self.input.rfind('/') else {
+            return;
+        };
+        let (dir, prefix) = (&self.input[..=idx], &self.input[idx + 1..]);
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+        let mut candidates: Vec<String> = entries
+            .flatten()
+            .filter(|entry| entry.path().is_dir())
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .filter(|name| {
+                name.starts_with(prefix) && (prefix.starts_with('.') || !name.starts_with('.'))
+            })
+            .map(|name| format!("{}{}", dir, name))
+            .collect();
+        candidates.sort();
+        candidates.truncate(MAX_CANDIDATES);
+        self.candidates = candidates;
+    }
+
+    /// Replace the input by the selected sub directory, listing its own sub directories
+    fn complete(&mut self) {
+        if let Some(candidate) = self.candidates.get(self.selected) {
+            self.input = format!("{}/", candidate);
+            self.update_candidates();
+        }
+    }
+
+    /// The typed path without its trailing slashes, if it is an existing directory
+    fn picked(&self) -> Option<&str> {
+        let path = match self.input.trim_end_matches('/') {
+            "" => "/",
+            path => path,
+        };
+        Path::new(path).is_dir().then_some(path)
+    }
+}
+
+impl View for PathPicker {
+    fn draw(&mut self, frame: &mut ratatui::Frame, _area: Rect, _active: bool) {
+        debug!("Drawing the path picker");
+        let area = frame.area();
+        // the borders and the input take 3 lines
+        let height = (self.candidates.len() as u16 + 3)
+            .clamp(4, 20)
+            .min(area.height);
+        let width = 80.min(area.width);
+        let modal_area = ViewManager::centered_rect(area, width, height);
+
+        frame.render_widget(Clear, modal_area);
+        if let Some(bg_color) = &self.styles.background_color {
+            let background = Paragraph::new("").style(Style::default().bg(*bg_color));
+            frame.render_widget(background, modal_area);
+        }
+
+        let input_style = if self.picked().is_some() {
+            self.styles.path_style
+        } else {
+            self.styles.text_em_style
+        };
+        let visible = height.saturating_sub(3) as usize;
+        let first = self.selected.saturating_sub(visible.saturating_sub(1));
+        let mut lines = vec![Line::from(vec![
+            Span::styled("> ", self.styles.date_style),
+            Span::styled(self.input.clone(), input_style),
+        ])];
+        for (row, candidate) in self.candidates.iter().enumerate().skip(first).take(visible) {
+            let mut style = self.styles.path_style;
+            if row == self.selected {
+                style = self.styles.highlight(style);
+            }
+            lines.push(Line::from(Span::styled(candidate.clone(), style)));
+        }
+
+        let mut block = Block::default()
+            .title(Span::styled(
+                format!(" {} ", self.title),
+                self.styles.title_style,
+            ))
+            .title_bottom(Span::styled(
+                " tab: complete, enter: choose ",
+                self.styles.date_style,
+            ))
+            .borders(Borders::ALL);
+        if let Some(border_color) = self.styles.border_color {
+            block = block.border_style(Style::default().fg(border_color));
+        }
+        frame.render_widget(Paragraph::new(lines).block(block), modal_area);
+        frame.set_cursor_position((
+            modal_area.x + 3 + self.input.chars().count() as u16,
+            modal_area.y + 1,
+        ));
+    }
+
+    fn handle_key_event(&mut self, key_event: KeyEvent) -> (EventCaptured, ManagerAction) {
+        match key_event.code {
+            KeyCode::Enter => {
+                if let Some(path) = self.picked() {
+                    info!("directory '{}' picked", path);
+                    (self.on_pick)(path);
+                    return (
+                        EventCaptured::Yes,
+                        ManagerAction::new(false).with_close(true),
+                    );
+                }
+                self.complete();
+            }
+            KeyCode::Tab => self.complete(),
+            KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down => {
+                self.selected = (self.selected + 1).min(self.candidates.len().saturating_sub(1))
+            }
+            KeyCode::Backspace => {
+                self.input.pop();
+                self.update_candidates();
+            }
+            KeyCode::Char(c)
+                if !key_event
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                self.input.push(c);
+                self.update_candidates();
+            }
+            _ => return (EventCaptured::Yes, ManagerAction::new(false)),
+        }
+        (EventCaptured::Yes, ManagerAction::new(true))
+    }
+}
@@ -0,0 +1,62 @@
+use std::{cell::RefCell, rc::Rc};
+
+use super::*;
+
+fn key(code: KeyCode) -> KeyEvent { KeyEvent::new(code, KeyModifiers::NONE) }
+
+fn picker(path: &str) -> (PathPicker, Rc<RefCell<Option<String>>>) {
+    let picked = Rc::new(RefCell::new(None));
+    let on_pick = {
+        let picked = picked.clone();
+        Box::new(move |path: &str| *picked.borrow_mut() = Some(path.to_string()))
+    };
+    (
+        PathPicker::new(
+            String::from("Relocate"),
+            path,
+            ThemeStyles::default(),
+            on_pick,
+        ),
+        picked,
+    )
+}
+
+#[test]
+fn test_candidates() {
+    let root = tempfile::tempdir().unwrap();
+    for dir in ["api", "app", "doc", ".hidden"] {
+        fs::create_dir(root.path().join(dir)).unwrap();
+    }
+    fs::write(root.path().join("apple"), "").unwrap();
+    let root = root.path().to_str().unwrap().to_string();
+
+    let (mut picker, _) = picker(&format!("{}/a", root));
+    assert_eq!(
+        picker.candidates,
+        vec![format!("{}/api", root), format!("{}/app", root)]
+    );
+
+    picker.handle_key_event(key(KeyCode::Backspace));
+    assert_eq!(picker.candidates.len(), 3);
+    picker.handle_key_event(key(KeyCode::Char('.')));
+    assert_eq!(picker.candidates, vec![format!("{}/.hidden", root)]);
+}
+
+#[test]
+fn test_pick() {
+    let root = tempfile::tempdir().unwrap();
+    fs::create_dir_all(root.path().join("work/api")).unwrap();
+    let root = root.path().to_str().unwrap().to_string();
+
+    // a missing directory is not picked, enter completes it instead
+    let (mut picker, picked) = picker(&format!("{}/wo", root));
+    let (_, action) = picker.handle_key_event(key(KeyCode::Enter));
+    assert!(!action.close());
+    assert_eq!(picker.input, format!("{}/work/", root));
+    assert_eq!(picker.candidates, vec![format!("{}/work/api", root)]);
+
+    picker.handle_key_event(key(KeyCode::Tab));
+    let (_, action) = picker.handle_key_event(key(KeyCode::Enter));
+    assert!(action.close());
+    assert_eq!(*picked.borrow(), Some(format!("{}/work/api", root)));
+}
@@ -0,0 +1,163 @@
+use clap::ValueEnum;
+use serde::Serialize;
+use tracing::error;
+
+use crate::store::{self, SortOrder, Store};
+
+#[cfg(test)]
+#[path = "picker_tests.rs"]
+mod picker_tests;
+
+/// The separator of a path and of its annotation in the lines streamed to fzf
+const FZF_ANNOTATION_SEPARATOR: char = '\t';
+
+/// How the paths offered to, or chosen by, an editor are printed
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) enum OutputFormat {
+    /// The path alone
+    #[default]
+    Plain,
+    /// `path<TAB>shortcut<TAB>score`, the fields being empty when unknown, for vim's
+    /// `systemlist()`
+    Vim,
+    /// A JSON object with the `path`, `shortcut` and `score` members
+    Json,
+}
+
+/// A path offered to an editor, or chosen in the GUI
+#[derive(Serialize, Debug, PartialEq)]
+pub(crate) struct Candidate {
+    pub(crate) path: String,
+    /// The name of the shortcut of the deepest directory containing the path
+    pub(crate) shortcut: Option<String>,
+    /// The score of the path for the fuzzy search it was found with
+    pub(crate) score: Option<u32>,
+}
+
+impl Candidate {
+    /// The candidate of a path of the history, `search` being the fuzzy search it was found
+    /// with, if any
+    pub(crate) fn new(store: &Store, path: store::Path, search: Option<&str>) -> Candidate {
+        let score = search.and_then(|search| {
+            store
+                .fuzzy_score(&path.path, search)
+                .inspect_err(|e| error!("Failed to score '{}': {}", path.path, e))
+                .ok()
+                .flatten()
+        });
+        Candidate {
+            shortcut: path.shortcut.map(|shortcut| shortcut.name),
+            path: path.path,
+            score,
+        }
+    }
+
+    /// The candidate of a path chosen in the GUI, with the search it was chosen from
+    pub(crate) fn selected(store: &Store, path: &str, search: &str, fuzzy: bool) -> Candidate {
+        let shortcuts = store.list_all_shortcuts().unwrap_or_else(|e| {
+            error!("Failed to list the shortcuts: {}", e);
+            vec![]
+        });
+        let path = store::Path::new(0, path.to_string(), 0, &shortcuts);
+        Candidate::new(store, path, Some(search).filter(|_| fuzzy))
+    }
+
+    /// The candidate as a line of output, without the line break
+    pub(crate) fn format(&self, format: OutputFormat) -> String {
+        match format {
+            OutputFormat::Plain => self.path.clone(),
+            OutputFormat::Vim => format!(
+                "{}\t{}\t{}",
+                self.path,
+                self.shortcut.as_deref().unwrap_or_default(),
+                self.score
+                    .map(|score| score.to_string())
+                    .unwrap_or_default()
+            ),
+            // the fields are plain strings and numbers, which always serialize
+            OutputFormat::Json => serde_json::to_string(self).unwrap(),
+        }
+    }
+}
+
+/// The paths of the history matching `query`, best first, as offered by the GUI for the same
+/// search, without the smart suggestions of the current directory.
+pub(crate) fn candidates(
+    store: &Store,
+    query: &str,
+    fuzzy: bool,
+    limit: usize,
+) -> Result<Vec<Candidate>, rusqlite::Error> {
+    let search = Some(query).filter(|_| fuzzy);
+    // the smart suggestions are paths of the history, which would be listed twice
+    Ok(store
+        .list_paths_sorted(0, limit, query, fuzzy, SortOrder::Default, false)?
+        .into_iter()
+        .map(|path| Candidate::new(store, path, search))
+        .collect())
+}
+
+/// A shortcut listed for a script or an editor
+#[derive(Serialize, Debug, PartialEq)]
+pub(crate) struct ShortcutEntry {
+    pub(crate) name: String,
+    pub(crate) path: String,
+    pub(crate) description: Option<String>,
+}
+
+impl ShortcutEntry {
+    /// The shortcut as a line of output, without the line break: its name and its path in the
+    /// plain format, `name<TAB>path<TAB>description` in the vim one
+    pub(crate) fn format(&self, format: OutputFormat) -> String {
+        match format {
+            OutputFormat::Plain => format!("{} {}", self.name, self.path),
+            OutputFormat::Vim => format!(
+                "{}\t{}\t{}",
+                self.name,
+                self.path,
+                self.description.as_deref().unwrap_or_default()
+            ),
+            // the fields are plain strings, which always serialize
+            OutputFormat::Json => serde_json::to_string(self).unwrap(),
+        }
+    }
+}
+
+/// The shortcuts matching `query`, as listed by the GUI for the same search.
+pub(crate) fn shortcut_entries(
+    store: &Store,
+    query: &str,
+    fuzzy: bool,
+    limit: usize,
+) -> Result<Vec<ShortcutEntry>, rusqlite::Error> {
+    Ok(store
+        .list_shortcuts(0, limit, query, fuzzy)?
+        .into_iter()
+        .map(|shortcut| ShortcutEntry {
+            name: shortcut.name,
+            path: shortcut.path,
+            description: shortcut.description,
+        })
+        .collect())
+}
+
+/// The line of a path streamed to fzf: the path, followed by the name of its shortcut between
+/// brackets if it has one.
+pub(crate) fn fzf_line(path: &store::Path) -> String {
+    match &path.shortcut {
+        Some(shortcut) => format!(
+            "{}{}[{}]",
+            path.path, FZF_ANNOTATION_SEPARATOR, shortcut.name
+        ),
+        None => path.path.clone(),
+    }
+}
+
+/// The path of a line chosen in fzf, without its annotation, None for an empty line
+pub(crate) fn resolve_fzf_line(line: &str) -> Option<&str> {
+    let line = line.trim_end_matches(['\n', '\r']);
+    let path = line
+        .split_once(FZF_ANNOTATION_SEPARATOR)
+        .map_or(line, |(path, _)| path);
+    Some(path).filter(|path| !path.trim().is_empty())
+}
@@ -0,0 +1,107 @@
+use super::*;
+
+#[test]
+fn test_format() {
+    let candidate = Candidate {
+        path: "/home/me/work/api".to_string(),
+        shortcut: Some("w".to_string()),
+        score: Some(42),
+    };
+    assert_eq!(candidate.format(OutputFormat::Plain), "/home/me/work/api");
+    assert_eq!(
+        candidate.format(OutputFormat::Vim),
+        "/home/me/work/api\tw\t42"
+    );
+    assert_eq!(
+        candidate.format(OutputFormat::Json),
+        r#"{"path":"/home/me/work/api","shortcut":"w","score":42}"#
+    );
+
+    let candidate = Candidate {
+        path: "/tmp".to_string(),
+        shortcut: None,
+        score: None,
+    };
+    assert_eq!(candidate.format(OutputFormat::Vim), "/tmp\t\t");
+    assert_eq!(
+        candidate.format(OutputFormat::Json),
+        r#"{"path":"/tmp","shortcut":null,"score":null}"#
+    );
+}
+
+#[test]
+fn test_candidates() {
+    let store = Store::setup_test_store();
+    store.upsert_shortcut("w", "/home/me/work", None).unwrap();
+    store.add_path_with_time("/home/me/work/api", 100).unwrap();
+    store.add_path_with_time("/tmp/apis", 200).unwrap();
+    store.add_path_with_time("/tmp/other", 300).unwrap();
+
+    let exact = candidates(&store, "api", false, 10).unwrap();
+    assert_eq!(
+        exact
+            .iter()
+            .map(|candidate| candidate.path.as_str())
+            .collect::<Vec<_>>(),
+        vec!["/tmp/apis", "/home/me/work/api"]
+    );
+    assert_eq!(exact[1].shortcut.as_deref(), Some("w"));
+    assert!(exact.iter().all(|candidate| candidate.score.is_none()));
+
+    let fuzzy = candidates(&store, "api", true, 10).unwrap();
+    assert!(fuzzy.iter().all(|candidate| candidate.score.is_some()));
+    assert_eq!(candidates(&store, "", false, 2).unwrap().len(), 2);
+
+    let selected = Candidate::selected(&store, "/home/me/work/api", "wrk api", true);
+    assert_eq!(selected.shortcut.as_deref(), Some("w"));
+    assert!(selected.score.is_some());
+    assert_eq!(
+        Candidate::selected(&store, "/tmp/other", "api", true).score,
+        None
+    );
+}
+
+#[test]
+fn test_fzf_lines() {
+    let shortcuts = [store::Shortcut {
+        id: 1,
+        name: "w".to_string(),
+        path: "/home/me/work".to_string(),
+        description: None,
+        remote: false,
+    }];
+    let annotated = store::Path::new(1, "/home/me/work/api".to_string(), 0, &shortcuts);
+    let plain = store::Path::new(2, "/tmp/my dir".to_string(), 0, &shortcuts);
+    assert_eq!(fzf_line(&annotated), "/home/me/work/api\t[w]");
+    assert_eq!(fzf_line(&plain), "/tmp/my dir");
+
+    assert_eq!(
+        resolve_fzf_line(&fzf_line(&annotated)),
+        Some("/home/me/work/api")
+    );
+    assert_eq!(resolve_fzf_line("/tmp/my dir\n"), Some("/tmp/my dir"));
+    assert_eq!(resolve_fzf_line("\n"), None);
+}
+
+#[test]
+fn test_shortcut_entries() {
+    let store = Store::setup_test_store();
+    store
+        .upsert_shortcut("w", "/home/me/work", Some("Work"))
+        .unwrap();
+    store
+        .upsert_shortcut("api", "/home/me/work/api", None)
+        .unwrap();
+    store.upsert_shortcut("t", "/tmp", None).unwrap();
+
+    let entries = shortcut_entries(&store, "work", false, 10).unwrap();
+    assert_eq!(entries.len(), 2);
+    let work = entries.iter().find(|entry| entry.name == "w").unwrap();
+    assert_eq!(work.format(OutputFormat::Plain), "w /home/me/work");
+    assert_eq!(work.format(OutputFormat::Vim), "w\t/home/me/work\tWork");
+    assert_eq!(
+        work.format(OutputFormat::Json),
+        r#"{"name":"w","path":"/home/me/work","description":"Work"}"#
+    );
+    assert_eq!(shortcut_entries(&store, "", false, 2).unwrap().len(), 2);
+}
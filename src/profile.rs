@@ -0,0 +1,139 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use tracing::{Subscriber, span};
+use tracing_subscriber::{
+    Layer,
+    layer::{Context, SubscriberExt},
+    registry::LookupSpan,
+};
+
+#[cfg(test)]
+#[path = "profile_tests.rs"]
+mod profile_tests;
+
+/// The timings of the spans of a name, e.g. `db_open`
+#[derive(Debug, Clone, PartialEq)]
+struct Step {
+    name: String,
+    calls: usize,
+    /// The duration of the first span
+    first: Duration,
+    total: Duration,
+    /// When the first span closed, since the start of the profile
+    done_at: Duration,
+}
+
+/// The timings of the steps of a run, in the order they first completed, printed on exit by
+/// `--profile`
+#[derive(Debug, Clone)]
+pub(crate) struct Profile {
+    start: Instant,
+    steps: Arc<Mutex<Vec<Step>>>,
+}
+
+impl Profile {
+    pub(crate) fn new() -> Profile {
+        Profile {
+            start: Instant::now(),
+            steps: Arc::new(Mutex::new(vec![])),
+        }
+    }
+
+    /// The layer of the tracing subscriber recording the duration of the spans into the
+    /// profile, from their creation to their closing
+    pub(crate) fn layer(&self) -> ProfileLayer {
+        ProfileLayer {
+            profile: self.clone(),
+        }
+    }
+
+    /// Runs `f`, its spans being recorded into the profile, before the subscriber of the
+    /// process is initialized, see `logging::initialize`
+    pub(crate) fn in_scope<T>(&self, f: impl FnOnce() -> T) -> T {
+        let subscriber = tracing_subscriber::registry().with(self.layer());
+        tracing::subscriber::with_default(subscriber, f)
+    }
+
+    /// Records that a span named `name` lasted `duration`, closing at `closed_at`.
+    fn record(&self, name: &str, duration: Duration, closed_at: Instant) {
+        let mut steps = self.steps.lock().unwrap();
+        match steps.iter_mut().find(|step| step.name == name) {
+            Some(step) => {
+                step.calls += 1;
+                step.total += duration;
+            }
+            None => steps.push(Step {
+                name: name.to_string(),
+                calls: 1,
+                first: duration,
+                total: duration,
+                done_at: closed_at.saturating_duration_since(self.start),
+            }),
+        }
+    }
+
+    /// The timing breakdown, one line per step, followed by the duration of the whole run
+    pub(crate) fn report(&self) -> String {
+        let steps = self.steps.lock().unwrap();
+        let mut report = format!(
+            "{:<14} {:>6} {:>10} {:>10} {:>10}\n",
+            "step", "calls", "first", "total", "done at"
+        );
+        for step in steps.iter() {
+            report.push_str(&format!(
+                "{:<14} {:>6} {:>10} {:>10} {:>10}\n",
+                step.name,
+                step.calls,
+                format_duration(step.first),
+                format_duration(step.total),
+                format_duration(step.done_at)
+            ));
+        }
+        report.push_str(&format!(
+            "{:<14} {:>6} {:>10} {:>10}\n",
+            "run",
+            "",
+            "",
+            format_duration(self.start.elapsed())
+        ));
+        report
+    }
+}
+
+/// A duration in milliseconds, e.g. `12.34ms`
+fn format_duration(duration: Duration) -> String {
+    format!("{:.2}ms", duration.as_secs_f64() * 1000.0)
+}
+
+/// When a span was created
+struct SpanStart(Instant);
+
+/// Records the spans into a `Profile`, see `Profile::layer`
+pub(crate) struct ProfileLayer {
+    profile: Profile,
+}
+
+impl<S> Layer<S> for ProfileLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, _attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(SpanStart(Instant::now()));
+        }
+    }
+
+    fn on_close(&self, id: span::Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        if let Some(SpanStart(start)) = span.extensions().get::<SpanStart>() {
+            let now = Instant::now();
+            self.profile
+                .record(span.name(), now.saturating_duration_since(*start), now);
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use super::*;
+
+#[test]
+fn test_profile_records_the_spans() {
+    let profile = Profile::new();
+    let subscriber = tracing_subscriber::registry().with(profile.layer());
+    tracing::subscriber::with_default(subscriber, || {
+        tracing::info_span!("db_open").in_scope(|| {});
+        for _ in 0..3 {
+            tracing::info_span!("query").in_scope(|| {});
+        }
+    });
+
+    let steps = profile.steps.lock().unwrap().clone();
+    assert_eq!(
+        steps
+            .iter()
+            .map(|step| (step.name.as_str(), step.calls))
+            .collect::<Vec<_>>(),
+        vec![("db_open", 1), ("query", 3)]
+    );
+    assert!(steps[1].first <= steps[1].total);
+    assert!(steps[0].done_at <= steps[1].done_at);
+}
+
+#[test]
+fn test_report() {
+    let profile = Profile::new();
+    profile.record(
+        "config_load",
+        Duration::from_micros(1500),
+        profile.start + Duration::from_millis(2),
+    );
+    profile.record(
+        "draw",
+        Duration::from_millis(4),
+        profile.start + Duration::from_millis(10),
+    );
+    profile.record(
+        "draw",
+        Duration::from_millis(2),
+        profile.start + Duration::from_millis(20),
+    );
+
+    let report = profile.report();
+    let lines: Vec<&str> = report.lines().collect();
+    assert_eq!(lines.len(), 4);
+    assert!(lines[0].starts_with("step"));
+    assert_eq!(
+        lines[1].split_whitespace().collect::<Vec<_>>(),
+        vec!["config_load", "1", "1.50ms", "1.50ms", "2.00ms"]
+    );
+    assert_eq!(
+        lines[2].split_whitespace().collect::<Vec<_>>(),
+        vec!["draw", "2", "4.00ms", "6.00ms", "10.00ms"]
+    );
+    assert!(lines[3].starts_with("run"));
+}
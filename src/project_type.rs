@@ -0,0 +1,59 @@
+use std::path::Path;
+
+#[cfg(test)]
+#[path = "project_type_tests.rs"]
+mod project_type_tests;
+
+/// The kind of project a directory holds, detected from the marker file at its root
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProjectType {
+    Rust,
+    Node,
+    Python,
+    Go,
+}
+
+/// The marker files of the project types, in detection order
+const MARKERS: [(&str, ProjectType); 4] = [
+    ("Cargo.toml", ProjectType::Rust),
+    ("package.json", ProjectType::Node),
+    ("pyproject.toml", ProjectType::Python),
+    ("go.mod", ProjectType::Go),
+];
+
+impl ProjectType {
+    /// All the project types, in detection order
+    pub const ALL: [ProjectType; 4] = [
+        ProjectType::Rust,
+        ProjectType::Node,
+        ProjectType::Python,
+        ProjectType::Go,
+    ];
+
+    /// The name of the type, as stored and typed in the `type:` search token
+    pub fn name(&self) -> &'static str {
+        match self {
+            ProjectType::Rust => "rust",
+            ProjectType::Node => "node",
+            ProjectType::Python => "python",
+            ProjectType::Go => "go",
+        }
+    }
+
+    /// The type named `name`, ignoring the case
+    pub fn from_name(name: &str) -> Option<ProjectType> {
+        Self::ALL
+            .into_iter()
+            .find(|project_type| project_type.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Detect the type of the project at the root of `path`, None if it is not a project or
+    /// not a local directory.
+    pub fn detect(path: &str) -> Option<ProjectType> {
+        let dir = Path::new(path);
+        MARKERS
+            .iter()
+            .find(|(marker, _)| dir.join(marker).is_file())
+            .map(|(_, project_type)| *project_type)
+    }
+}
@@ -0,0 +1,42 @@
+use std::fs;
+
+use super::*;
+
+#[test]
+fn test_detect() {
+    let root = tempfile::tempdir().unwrap();
+    let path = |dir: &str| root.path().join(dir).to_str().unwrap().to_string();
+    for (dir, marker) in [
+        ("cdir", "Cargo.toml"),
+        ("web", "package.json"),
+        ("tool", "pyproject.toml"),
+        ("svc", "go.mod"),
+    ] {
+        fs::create_dir(root.path().join(dir)).unwrap();
+        fs::write(root.path().join(dir).join(marker), "").unwrap();
+    }
+    fs::create_dir_all(root.path().join("docs/go.mod")).unwrap();
+
+    assert_eq!(ProjectType::detect(&path("cdir")), Some(ProjectType::Rust));
+    assert_eq!(ProjectType::detect(&path("web")), Some(ProjectType::Node));
+    assert_eq!(
+        ProjectType::detect(&path("tool")),
+        Some(ProjectType::Python)
+    );
+    assert_eq!(ProjectType::detect(&path("svc")), Some(ProjectType::Go));
+    // a directory named like a marker is not a marker
+    assert_eq!(ProjectType::detect(&path("docs")), None);
+    assert_eq!(ProjectType::detect(&path("missing")), None);
+}
+
+#[test]
+fn test_names() {
+    for project_type in ProjectType::ALL {
+        assert_eq!(
+            ProjectType::from_name(project_type.name()),
+            Some(project_type)
+        );
+    }
+    assert_eq!(ProjectType::from_name("Rust"), Some(ProjectType::Rust));
+    assert_eq!(ProjectType::from_name("java"), None);
+}
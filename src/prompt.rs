@@ -0,0 +1,41 @@
+use std::io::{self, BufRead, IsTerminal, Write};
+
+use tracing::debug;
+
+#[cfg(test)]
+#[path = "prompt_tests.rs"]
+mod prompt_tests;
+
+/// Ask `question` on `output` and read the answer from `input`, only a `y` or a `yes`,
+/// whatever the case, confirming.
+fn ask(question: &str, input: &mut impl BufRead, output: &mut impl Write) -> io::Result<bool> {
+    write!(output, "{} [y/N] ", question)?;
+    output.flush()?;
+    let mut answer = String::new();
+    input.read_line(&mut answer)?;
+    let answer = answer.trim().to_ascii_lowercase();
+    Ok(answer == "y" || answer == "yes")
+}
+
+/// Confirm a destructive operation: granted by `yes`, else asked on the terminal. When the
+/// standard input is not a terminal, e.g. in a script, nobody can answer and `--yes` is
+/// required.
+///
+/// ### Returns
+/// Whether the operation is confirmed, or an error message if it cannot be asked.
+pub(crate) fn confirm(question: &str, yes: bool) -> Result<bool, String> {
+    if yes {
+        return Ok(true);
+    }
+    let stdin = io::stdin();
+    if !stdin.is_terminal() {
+        return Err(format!(
+            "{} The standard input is not a terminal, use --yes to confirm",
+            question
+        ));
+    }
+    let confirmed = ask(question, &mut stdin.lock(), &mut io::stderr())
+        .map_err(|e| format!("Failed to read the answer: {}", e))?;
+    debug!("confirm {} confirmed={}", question, confirmed);
+    Ok(confirmed)
+}
@@ -0,0 +1,22 @@
+use super::*;
+
+fn answer(input: &str) -> (bool, String) {
+    let mut output = Vec::new();
+    let confirmed = ask("Delete?", &mut input.as_bytes(), &mut output).unwrap();
+    (confirmed, String::from_utf8(output).unwrap())
+}
+
+#[test]
+fn test_ask() {
+    assert_eq!(answer("y\n"), (true, String::from("Delete? [y/N] ")));
+    assert!(answer("YES\n").0);
+    assert!(answer(" yes ").0);
+    assert!(!answer("n\n").0);
+    assert!(!answer("\n").0);
+    assert!(!answer("").0);
+}
+
+#[test]
+fn test_confirm_yes() {
+    assert_eq!(confirm("Delete?", true), Ok(true));
+}
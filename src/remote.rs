@@ -0,0 +1,119 @@
+use crate::open_with::shell_quote;
+
+#[cfg(test)]
+#[path = "remote_tests.rs"]
+mod remote_tests;
+
+/// The placeholder of the remote command template replaced by `[user@]host`
+const TARGET_PLACEHOLDER: &str = "{target}";
+
+/// The placeholder of the remote command template replaced by the quoted remote path
+const PATH_PLACEHOLDER: &str = "{path}";
+
+/// The placeholder of the remote command template replaced by the quoted command changing to
+/// the remote path and starting a shell
+const COMMAND_PLACEHOLDER: &str = "{command}";
+
+/// A directory on another machine, written `[user@]host:/path`
+#[derive(Debug, Clone, PartialEq)]
+pub struct RemotePath {
+    /// `[user@]host`
+    pub target: String,
+    /// The path on the remote host, absolute or relative to a home directory (`~`)
+    pub path: String,
+}
+
+impl RemotePath {
+    /// Parse a `[user@]host:/path` entry.
+    ///
+    /// ### Returns
+    /// Some(remote path) if the entry is a valid remote entry, otherwise None.
+    pub fn parse(entry: &str) -> Option<RemotePath> {
+        if !Self::looks_remote(entry) {
+            return None;
+        }
+        let (target, path) = entry.split_once(':')?;
+        let host = match target.split_once('@') {
+            Some((user, host)) if Self::is_valid_name(user) => host,
+            Some(_) => return None,
+            None => target,
+        };
+        if !Self::is_valid_name(host) || !(path.starts_with('/') || path.starts_with('~')) {
+            return None;
+        }
+        // the `~user` part is left unquoted for the shell to expand it, see `quoted_path`
+        if let Some(home) = path.strip_prefix('~') {
+            let user = home.split('/').next().unwrap_or_default();
+            if !user.is_empty() && !Self::is_valid_name(user) {
+                return None;
+            }
+        }
+        Some(RemotePath {
+            target: target.to_string(),
+            path: path.to_string(),
+        })
+    }
+
+    /// Whether an entry is meant to be remote: not an absolute local path, and having a colon.
+    pub fn looks_remote(entry: &str) -> bool { !entry.starts_with('/') && entry.contains(':') }
+
+    /// Check that an entry meant to be remote is a valid `[user@]host:/path` entry.
+    pub fn validate(entry: &str) -> Result<(), String> {
+        if Self::looks_remote(entry) && Self::parse(entry).is_none() {
+            return Err(format!(
+                "Invalid remote entry '{}', expected [user@]host:/path",
+                entry
+            ));
+        }
+        Ok(())
+    }
+
+    fn is_valid_name(name: &str) -> bool {
+        !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'))
+    }
+
+    /// The path quoted for the remote shell, leaving a leading `~` or `~user` unquoted so
+    /// that it is still expanded.
+    fn quoted_path(&self) -> String {
+        let (home, rest) = match self.path.strip_prefix('~') {
+            Some(_) => match self.path.find('/') {
+                Some(idx) => self.path.split_at(idx),
+                None => (self.path.as_str(), ""),
+            },
+            None => ("", self.path.as_str()),
+        };
+        let safe = rest
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '/' | '.' | '-' | '_'));
+        if safe {
+            format!("{}{}", home, rest)
+        } else {
+            format!("{}{}", home, shell_quote(rest))
+        }
+    }
+
+    /// Build the command line opening a shell in the remote directory from a template, e.g.
+    /// `ssh -t {target} {command}` gives `ssh -t user@host 'cd /path && exec $SHELL'`.
+    pub fn command_line(&self, template: &str) -> String {
+        let command = format!("cd {} && exec $SHELL", self.quoted_path());
+        template
+            // the target only holds safe characters, see `parse`
+            .replace(TARGET_PLACEHOLDER, &self.target)
+            .replace(PATH_PLACEHOLDER, &self.quoted_path())
+            .replace(COMMAND_PLACEHOLDER, &shell_quote(&command))
+    }
+}
+
+/// Check that a remote command template connects to the remote host.
+pub fn validate_template(template: &str) -> Result<(), String> {
+    if !template.contains(TARGET_PLACEHOLDER) {
+        return Err(format!(
+            "remote_command_template: '{}' must contain {}",
+            template, TARGET_PLACEHOLDER
+        ));
+    }
+    Ok(())
+}
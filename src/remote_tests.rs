@@ -0,0 +1,70 @@
+use super::*;
+
+#[test]
+fn test_parse() {
+    assert_eq!(
+        RemotePath::parse("me@server.example.com:/srv/app"),
+        Some(RemotePath {
+            target: String::from("me@server.example.com"),
+            path: String::from("/srv/app"),
+        })
+    );
+    assert_eq!(
+        RemotePath::parse("server:~/work"),
+        Some(RemotePath {
+            target: String::from("server"),
+            path: String::from("~/work"),
+        })
+    );
+
+    assert_eq!(RemotePath::parse("/home/me"), None);
+    assert_eq!(RemotePath::parse("/home/me:x"), None);
+    assert_eq!(RemotePath::parse("server:relative"), None);
+    assert_eq!(RemotePath::parse("@server:/srv"), None);
+    assert_eq!(RemotePath::parse("my server:/srv"), None);
+    assert_eq!(RemotePath::parse(":/srv"), None);
+
+    assert_eq!(
+        RemotePath::parse("server:~me/work").map(|remote| remote.path),
+        Some(String::from("~me/work"))
+    );
+    // the user of a home directory is not quoted, no command may hide in it
+    assert_eq!(RemotePath::parse("server:~$(touch /tmp/PWNED)/x"), None);
+    assert_eq!(RemotePath::parse("server:~`touch /tmp/PWNED`/x"), None);
+    assert_eq!(RemotePath::parse("server:~me;reboot"), None);
+    assert!(RemotePath::validate("server:~$(id)").is_err());
+}
+
+#[test]
+fn test_validate() {
+    assert!(RemotePath::validate("/home/me").is_ok());
+    assert!(RemotePath::validate("relative").is_ok());
+    assert!(RemotePath::validate("me@server:/srv").is_ok());
+    assert!(RemotePath::validate("me@server:srv").is_err());
+    assert!(RemotePath::validate("c:\\windows").is_err());
+}
+
+#[test]
+fn test_command_line() {
+    let remote = RemotePath::parse("me@server:/srv/app").unwrap();
+    assert_eq!(
+        remote.command_line("ssh -t {target} {command}"),
+        "ssh -t me@server 'cd /srv/app && exec $SHELL'"
+    );
+    assert_eq!(
+        remote.command_line("mosh {target} -- sh -c \"cd {path}; exec bash\""),
+        "mosh me@server -- sh -c \"cd /srv/app; exec bash\""
+    );
+
+    let remote = RemotePath::parse("server:~/my project").unwrap();
+    assert_eq!(
+        remote.command_line("ssh -t {target} {command}"),
+        "ssh -t server 'cd ~'\\''/my project'\\'' && exec $SHELL'"
+    );
+}
+
+#[test]
+fn test_validate_template() {
+    assert!(validate_template("ssh -t {target} {command}").is_ok());
+    assert!(validate_template("ssh -t server").is_err());
+}
@@ -0,0 +1,204 @@
+use cdir_core::stats::UsageStats;
+use chrono::{Local, TimeZone};
+use clap::ValueEnum;
+
+#[cfg(test)]
+#[path = "report_tests.rs"]
+mod report_tests;
+
+/// The width of the bar of the busiest day of the daily activity
+const ACTIVITY_BAR_WIDTH: usize = 30;
+
+/// The formats of the usage report
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) enum ReportFormat {
+    /// A Markdown document
+    #[default]
+    Md,
+    /// A standalone HTML page
+    Html,
+}
+
+/// Parse a period like `90d`: a number followed by `h` (hours), `d` (days) or `w` (weeks).
+///
+/// ### Returns
+/// The duration of the period in seconds, or an error message if it cannot be parsed.
+pub(crate) fn parse_period(text: &str) -> Result<i64, String> {
+    let error = || format!("invalid period '{}', expected e.g. 12h, 90d or 8w", text);
+    let unit = match text.chars().last() {
+        Some('h') => 3600,
+        Some('d') => 24 * 3600,
+        Some('w') => 7 * 24 * 3600,
+        _ => return Err(error()),
+    };
+    let count: i64 = text[..text.len() - 1].parse().map_err(|_| error())?;
+    if count <= 0 {
+        return Err(error());
+    }
+    Ok(count * unit)
+}
+
+/// The local day of `epoch`, e.g. `2026-10-16`
+fn local_day(epoch: i64) -> String {
+    Local
+        .timestamp_opt(epoch, 0)
+        .single()
+        .map(|date| date.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+/// The bar of a day with `visits`, `max` being the visits of the busiest day
+fn activity_bar(visits: usize, max: usize) -> String {
+    "█".repeat((visits * ACTIVITY_BAR_WIDTH).div_ceil(max.max(1)))
+}
+
+/// A section of the report: a title and a table, `None` being shown when it has no rows
+struct Section {
+    title: &'static str,
+    headers: &'static [&'static str],
+    rows: Vec<Vec<String>>,
+}
+
+fn sections(stats: &UsageStats) -> Vec<Section> {
+    let busiest = stats
+        .daily_visits
+        .iter()
+        .map(|(_, visits)| *visits)
+        .max()
+        .unwrap_or_default();
+    vec![
+        Section {
+            title: "Top directories",
+            headers: &["Directory", "Visits"],
+            rows: stats
+                .top_paths
+                .iter()
+                .map(|(path, visits)| vec![path.clone(), visits.to_string()])
+                .collect(),
+        },
+        Section {
+            title: "Daily activity",
+            headers: &["Day", "Visits", ""],
+            rows: stats
+                .daily_visits
+                .iter()
+                .map(|(day, visits)| {
+                    vec![
+                        day.format("%Y-%m-%d").to_string(),
+                        visits.to_string(),
+                        activity_bar(*visits, busiest),
+                    ]
+                })
+                .collect(),
+        },
+        Section {
+            title: "Newly discovered directories",
+            headers: &["Directory", "First visit"],
+            rows: stats
+                .discovered_paths
+                .iter()
+                .map(|(path, date)| vec![path.clone(), local_day(*date)])
+                .collect(),
+        },
+        Section {
+            title: "Shortcuts created",
+            headers: &["Shortcut", "Directory", "Created"],
+            rows: stats
+                .created_shortcuts
+                .iter()
+                .map(|(shortcut, date)| {
+                    vec![
+                        shortcut.name.clone(),
+                        shortcut.path.clone(),
+                        local_day(*date),
+                    ]
+                })
+                .collect(),
+        },
+    ]
+}
+
+/// The summary line of the report
+fn summary(stats: &UsageStats) -> String {
+    format!(
+        "Since {}: {} visits, {} new directories and {} new shortcuts.",
+        local_day(stats.since),
+        stats.visits,
+        stats.discovered_paths.len(),
+        stats.created_shortcuts.len()
+    )
+}
+
+/// Escape the characters of a Markdown table cell
+fn escape_md(text: &str) -> String { text.replace('\\', "\\\\").replace('|', "\\|") }
+
+/// Escape the characters of an HTML text
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn render_md(stats: &UsageStats) -> String {
+    let mut md = format!("# cdir usage report\n\n{}\n", summary(stats));
+    for section in sections(stats) {
+        md.push_str(&format!("\n## {}\n\n", section.title));
+        if section.rows.is_empty() {
+            md.push_str("None.\n");
+            continue;
+        }
+        md.push_str(&format!("| {} |\n", section.headers.join(" | ")));
+        md.push_str(&format!(
+            "|{}\n",
+            section.headers.iter().map(|_| " --- |").collect::<String>()
+        ));
+        for row in &section.rows {
+            let cells: Vec<String> = row.iter().map(|cell| escape_md(cell)).collect();
+            md.push_str(&format!("| {} |\n", cells.join(" | ")));
+        }
+    }
+    md
+}
+
+fn render_html(stats: &UsageStats) -> String {
+    let mut html = String::from(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>cdir usage report</title>\n<style>\n\
+         body { font-family: sans-serif; margin: 2em; }\n\
+         table { border-collapse: collapse; }\n\
+         th, td { padding: 0.2em 1em; text-align: left; border-bottom: 1px solid #ddd; }\n\
+         </style>\n</head>\n<body>\n<h1>cdir usage report</h1>\n",
+    );
+    html.push_str(&format!("<p>{}</p>\n", escape_html(&summary(stats))));
+    for section in sections(stats) {
+        html.push_str(&format!("<h2>{}</h2>\n", section.title));
+        if section.rows.is_empty() {
+            html.push_str("<p>None.</p>\n");
+            continue;
+        }
+        html.push_str("<table>\n<tr>");
+        for header in section.headers {
+            html.push_str(&format!("<th>{}</th>", header));
+        }
+        html.push_str("</tr>\n");
+        for row in &section.rows {
+            html.push_str("<tr>");
+            for cell in row {
+                html.push_str(&format!("<td>{}</td>", escape_html(cell)));
+            }
+            html.push_str("</tr>\n");
+        }
+        html.push_str("</table>\n");
+    }
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// Render the usage report of `stats` in `format`.
+pub(crate) fn render(stats: &UsageStats, format: ReportFormat) -> String {
+    match format {
+        ReportFormat::Md => render_md(stats),
+        ReportFormat::Html => render_html(stats),
+    }
+}
@@ -0,0 +1,58 @@
+use cdir_core::store::Shortcut;
+use chrono::NaiveDate;
+
+use super::*;
+
+fn stats() -> UsageStats {
+    UsageStats {
+        since: 0,
+        visits: 3,
+        top_paths: vec![(String::from("/work|api"), 3)],
+        daily_visits: vec![
+            (NaiveDate::from_ymd_opt(2026, 10, 14).unwrap(), 2),
+            (NaiveDate::from_ymd_opt(2026, 10, 15).unwrap(), 1),
+        ],
+        discovered_paths: vec![],
+        created_shortcuts: vec![(
+            Shortcut {
+                id: 1,
+                name: String::from("w"),
+                path: String::from("/work/<api>"),
+                description: None,
+                remote: false,
+            },
+            0,
+        )],
+    }
+}
+
+#[test]
+fn test_parse_period() {
+    assert_eq!(parse_period("12h"), Ok(12 * 3600));
+    assert_eq!(parse_period("90d"), Ok(90 * 24 * 3600));
+    assert_eq!(parse_period("2w"), Ok(14 * 24 * 3600));
+    assert!(parse_period("90").is_err());
+    assert!(parse_period("d").is_err());
+    assert!(parse_period("0d").is_err());
+    assert!(parse_period("-3d").is_err());
+}
+
+#[test]
+fn test_render_md() {
+    let md = render(&stats(), ReportFormat::Md);
+    assert!(md.starts_with("# cdir usage report\n"));
+    assert!(md.contains("3 visits, 0 new directories and 1 new shortcuts"));
+    assert!(md.contains("| /work\\|api | 3 |\n"));
+    assert!(md.contains(&format!("| 2026-10-14 | 2 | {} |\n", "█".repeat(30))));
+    assert!(md.contains(&format!("| 2026-10-15 | 1 | {} |\n", "█".repeat(15))));
+    assert!(md.contains("## Newly discovered directories\n\nNone.\n"));
+}
+
+#[test]
+fn test_render_html() {
+    let html = render(&stats(), ReportFormat::Html);
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<td>w</td><td>/work/&lt;api&gt;</td>"));
+    assert!(html.contains("<h2>Newly discovered directories</h2>\n<p>None.</p>"));
+    assert!(html.ends_with("</html>\n"));
+}
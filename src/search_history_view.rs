@@ -0,0 +1,180 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+use tracing::debug;
+
+use crate::{
+    config::{CaseSensitivity, FuzzyConfig},
+    matcher,
+    store::Filter,
+    theme::ThemeStyles,
+    tui::{EventCaptured, ManagerAction, View, ViewBuilder, ViewManager},
+};
+
+#[cfg(test)]
+#[path = "search_history_view_tests.rs"]
+mod search_history_view_tests;
+
+/// A modal searching the past searches of a view, the chosen one becoming the current search.
+pub struct SearchHistoryView {
+    styles: ThemeStyles,
+    fuzzy_config: FuzzyConfig,
+    case_sensitivity: CaseSensitivity,
+    /// The past searches, the most recent first
+    searches: Vec<Filter>,
+    query: String,
+    /// The indexes of the searches matching the query, the best match first
+    matches: Vec<usize>,
+    selected: usize,
+    chosen: Option<Filter>,
+}
+
+impl SearchHistoryView {
+    pub fn builder(
+        searches: Vec<Filter>,
+        fuzzy_config: FuzzyConfig,
+        case_sensitivity: CaseSensitivity,
+        styles: ThemeStyles,
+    ) -> ViewBuilder {
+        ViewBuilder::from(Box::new(Self::new(
+            searches,
+            fuzzy_config,
+            case_sensitivity,
+            styles,
+        )))
+    }
+
+    fn new(
+        searches: Vec<Filter>,
+        fuzzy_config: FuzzyConfig,
+        case_sensitivity: CaseSensitivity,
+        styles: ThemeStyles,
+    ) -> Self {
+        let mut view = Self {
+            styles,
+            fuzzy_config,
+            case_sensitivity,
+            searches,
+            query: String::new(),
+            matches: vec![],
+            selected: 0,
+            chosen: None,
+        };
+        view.update_matches();
+        view
+    }
+
+    /// The chosen search, None if the window was closed without choosing one
+    pub fn chosen(&self) -> Option<&Filter> { self.chosen.as_ref() }
+
+    /// Match the searches against the query, the most recent being first among equal matches.
+    fn update_matches(&mut self) {
+        self.selected = 0;
+        if self.query.is_empty() {
+            self.matches = (0..self.searches.len()).collect();
+            return;
+        }
+        let mut matcher = matcher::build(
+            &self.fuzzy_config,
+            &self.query,
+            self.case_sensitivity.is_case_sensitive(&self.query),
+        );
+        let mut scored: Vec<(usize, u32)> = self
+            .searches
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, search)| matcher.score(&search.text).map(|score| (idx, score)))
+            .collect();
+        scored.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+        self.matches = scored.into_iter().map(|(idx, _)| idx).collect();
+    }
+}
+
+impl View for SearchHistoryView {
+    fn draw(&mut self, frame: &mut ratatui::Frame, _area: Rect, _active: bool) {
+        debug!("Drawing the search history");
+        let area = frame.area();
+        // the borders and the query take 3 lines
+        let height = (self.matches.len() as u16 + 3)
+            .clamp(4, 20)
+            .min(area.height);
+        let width = 60.min(area.width);
+        let modal_area = ViewManager::centered_rect(area, width, height);
+
+        frame.render_widget(Clear, modal_area);
+        if let Some(bg_color) = &self.styles.background_color {
+            let background = Paragraph::new("").style(Style::default().bg(*bg_color));
+            frame.render_widget(background, modal_area);
+        }
+
+        let visible = height.saturating_sub(3) as usize;
+        let first = self.selected.saturating_sub(visible.saturating_sub(1));
+        let mut lines = vec![Line::from(vec![
+            Span::styled("> ", self.styles.date_style),
+            Span::styled(self.query.clone(), self.styles.text_em_style),
+        ])];
+        for (row, idx) in self.matches.iter().enumerate().skip(first).take(visible) {
+            let search = &self.searches[*idx];
+            let mut style = self.styles.path_style;
+            if row == self.selected {
+                style = self.styles.highlight(style);
+            }
+            lines.push(Line::from(vec![
+                Span::styled(
+                    if search.fuzzy { "[f] " } else { "[e] " },
+                    self.styles.date_style,
+                ),
+                Span::styled(search.text.clone(), style),
+            ]));
+        }
+
+        let mut block = Block::default()
+            .title(Span::styled(" Past searches ", self.styles.title_style))
+            .borders(Borders::ALL);
+        if let Some(border_color) = self.styles.border_color {
+            block = block.border_style(Style::default().fg(border_color));
+        }
+        frame.render_widget(Paragraph::new(lines).block(block), modal_area);
+        frame.set_cursor_position((
+            modal_area.x + 3 + self.query.chars().count() as u16,
+            modal_area.y + 1,
+        ));
+    }
+
+    fn handle_key_event(&mut self, key_event: KeyEvent) -> (EventCaptured, ManagerAction) {
+        match key_event.code {
+            KeyCode::Enter => {
+                self.chosen = self
+                    .matches
+                    .get(self.selected)
+                    .map(|idx| self.searches[*idx].clone());
+                return (
+                    EventCaptured::Yes,
+                    ManagerAction::new(false).with_close(true),
+                );
+            }
+            KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down => {
+                self.selected = (self.selected + 1).min(self.matches.len().saturating_sub(1))
+            }
+            KeyCode::Backspace => {
+                self.query.pop();
+                self.update_matches();
+            }
+            KeyCode::Char(c)
+                if !key_event
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                self.query.push(c);
+                self.update_matches();
+            }
+            _ => return (EventCaptured::Yes, ManagerAction::new(false)),
+        }
+        (EventCaptured::Yes, ManagerAction::new(true))
+    }
+}
@@ -0,0 +1,43 @@
+use super::*;
+
+fn key(code: KeyCode) -> KeyEvent { KeyEvent::new(code, KeyModifiers::NONE) }
+
+fn view() -> SearchHistoryView {
+    SearchHistoryView::new(
+        vec![
+            Filter::new("src", false),
+            Filter::new("doc", true),
+            Filter::new("cdir/src", false),
+        ],
+        FuzzyConfig::default(),
+        CaseSensitivity::default(),
+        ThemeStyles::default(),
+    )
+}
+
+#[test]
+fn test_matches() {
+    let mut view = view();
+    assert_eq!(view.matches, vec![0, 1, 2]);
+
+    view.handle_key_event(key(KeyCode::Char('s')));
+    view.handle_key_event(key(KeyCode::Char('r')));
+    assert_eq!(view.matches.len(), 2);
+    assert!(!view.matches.contains(&1));
+
+    view.handle_key_event(key(KeyCode::Backspace));
+    view.handle_key_event(key(KeyCode::Backspace));
+    assert_eq!(view.matches, vec![0, 1, 2]);
+}
+
+#[test]
+fn test_choose() {
+    let mut view = view();
+    view.handle_key_event(key(KeyCode::Down));
+    view.handle_key_event(key(KeyCode::Down));
+    view.handle_key_event(key(KeyCode::Down));
+    view.handle_key_event(key(KeyCode::Up));
+    let (_, action) = view.handle_key_event(key(KeyCode::Enter));
+    assert!(action.close());
+    assert_eq!(view.chosen(), Some(&Filter::new("doc", true)));
+}
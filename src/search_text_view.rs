@@ -4,35 +4,63 @@ use std::{
 };
 
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent};
-use log::{debug, error, warn};
 use ratatui::{
     layout::{Constraint, Layout, Position, Rect},
     style::Style,
+    text::{Line, Span},
     widgets::Paragraph,
 };
 use tokio::sync::broadcast::Sender;
+use tracing::{debug, error, info, warn};
+use unicode_width::UnicodeWidthStr;
 
 use crate::{
-    config::Config,
+    config::{CaseSensitivity, Config},
+    keybindings::{self, Action, Scope},
+    search_history_view::SearchHistoryView,
+    store::{Filter, Store},
     tui::{
         EventCaptured, GenericEvent, ManagerAction, View, ViewBuilder, ViewManager,
         event::ApplicationEvent,
     },
 };
 
+#[cfg(test)]
+#[path = "search_text_view_tests.rs"]
+mod search_text_view_tests;
+
 const SEARCH_PROMPT: &str = "> ";
 
+/// The separator of the frozen searches in the breadcrumb
+const BREADCRUMB_SEPARATOR: &str = " \u{203a} ";
+
 // "search.description"
 pub struct SearchTextState {
     tx: Sender<GenericEvent>,
     search_string: String,
+    /// The byte index of the cursor in the search string, always on a character boundary
     search_string_cursor_index: usize,
     fuzzy_match: bool,
+    /// The searches whose results are narrowed by the current one, from the first
+    frozen_filters: Vec<Filter>,
 }
 
 pub struct SearchDescriptionPayload {
     pub search_string: String,
     pub fuzzy_match: bool,
+    pub frozen_filters: Vec<Filter>,
+}
+
+/// The id of the application event publishing the completion of the search offered by the
+/// entries of a view, its payload being a `SearchCompletionPayload`
+pub(crate) const SEARCH_COMPLETION_EVENT: &str = "search.completion";
+
+pub(crate) struct SearchCompletionPayload {
+    pub(crate) objects_type: String,
+    /// The search completed
+    pub(crate) search_string: String,
+    /// The text to append to the search, None when there is nothing to complete
+    pub(crate) completion: Option<String>,
 }
 
 impl SearchTextState {
@@ -42,15 +70,169 @@ impl SearchTextState {
             search_string: String::new(),
             search_string_cursor_index: 0,
             fuzzy_match: false,
+            frozen_filters: vec![],
         }
     }
 
+    pub fn search_string(&self) -> &str { &self.search_string }
+
+    pub fn fuzzy_match(&self) -> bool { self.fuzzy_match }
+
+    /// Restore a previous search, the cursor being put at its end.
+    pub fn restore(&mut self, search_string: &str, fuzzy_match: bool) {
+        self.search_string = search_string.to_string();
+        self.search_string_cursor_index = self.search_string.len();
+        self.fuzzy_match = fuzzy_match;
+    }
+
+    /// Insert a text at the cursor, e.g. a pasted path, the line breaks and the other control
+    /// characters being dropped; the search is published once.
+    pub fn insert(&mut self, text: &str) {
+        let text: String = text.chars().filter(|c| !c.is_control()).collect();
+        if text.is_empty() {
+            return;
+        }
+        self.search_string
+            .insert_str(self.search_string_cursor_index, &text);
+        self.search_string_cursor_index += text.len();
+        self.publish();
+    }
+
+    /// Insert a character at the cursor, the cursor moving after it.
+    fn insert_char(&mut self, c: char) {
+        self.search_string
+            .insert(self.search_string_cursor_index, c);
+        self.search_string_cursor_index += c.len_utf8();
+        self.publish();
+    }
+
+    /// The byte index of the character before the cursor, None at the start of the search
+    fn previous_boundary(&self) -> Option<usize> {
+        self.search_string[..self.search_string_cursor_index]
+            .char_indices()
+            .next_back()
+            .map(|(idx, _)| idx)
+    }
+
+    /// The byte index following the character after the cursor, None at the end of the search
+    fn next_boundary(&self) -> Option<usize> {
+        self.search_string[self.search_string_cursor_index..]
+            .chars()
+            .next()
+            .map(|c| self.search_string_cursor_index + c.len_utf8())
+    }
+
+    /// The byte index of the start of the word before the cursor, the characters between the
+    /// word and the cursor being skipped
+    fn word_start(&self, is_word: fn(char) -> bool) -> usize {
+        self.search_string[..self.search_string_cursor_index]
+            .trim_end_matches(|c| !is_word(c))
+            .trim_end_matches(is_word)
+            .len()
+    }
+
+    /// The byte index of the end of the word after the cursor, the characters between the
+    /// cursor and the word being skipped
+    fn word_end(&self, is_word: fn(char) -> bool) -> usize {
+        let rest = self.search_string[self.search_string_cursor_index..]
+            .trim_start_matches(|c| !is_word(c))
+            .trim_start_matches(is_word);
+        self.search_string.len() - rest.len()
+    }
+
+    /// Delete the characters between the cursor and `idx`, the cursor moving to the start of
+    /// the deleted range.
+    fn delete_to(&mut self, idx: usize) {
+        let range =
+            idx.min(self.search_string_cursor_index)..idx.max(self.search_string_cursor_index);
+        if range.is_empty() {
+            return;
+        }
+        self.search_string_cursor_index = range.start;
+        self.search_string.replace_range(range, "");
+        self.publish();
+    }
+
+    /// Apply an editing action of the search, returning false for the other actions
+    fn edit(&mut self, action: Action) -> bool {
+        match action {
+            Action::WordLeft => {
+                self.search_string_cursor_index = self.word_start(char::is_alphanumeric)
+            }
+            Action::WordRight => {
+                self.search_string_cursor_index = self.word_end(char::is_alphanumeric)
+            }
+            Action::DeleteWord => self.delete_to(self.word_start(|c| !c.is_whitespace())),
+            Action::LineStart => self.search_string_cursor_index = 0,
+            Action::LineEnd => self.search_string_cursor_index = self.search_string.len(),
+            Action::ClearLine => {
+                self.search_string_cursor_index = self.search_string.len();
+                self.delete_to(0);
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// The column of the cursor from the start of the search string
+    fn cursor_column(&self) -> usize {
+        self.search_string[..self.search_string_cursor_index].width()
+    }
+
+    /// The completion of the search, `completion` being offered for `search_string`: only
+    /// a search matching it and whose cursor is at its end is completed.
+    fn completion<'a>(&self, search_string: &str, completion: &'a str) -> Option<&'a str> {
+        (self.search_string == search_string
+            && self.search_string_cursor_index == self.search_string.len())
+        .then_some(completion)
+    }
+
+    /// Append a completion to the search, the cursor moving to its end.
+    fn complete(&mut self, completion: &str) {
+        self.search_string.push_str(completion);
+        self.search_string_cursor_index = self.search_string.len();
+        self.publish();
+    }
+
+    /// Freeze the current search: the next searches are made within its results.
+    pub fn narrow(&mut self) {
+        if self.search_string.is_empty() {
+            return;
+        }
+        self.frozen_filters
+            .push(Filter::new(&self.search_string, self.fuzzy_match));
+        self.search_string.clear();
+        self.search_string_cursor_index = 0;
+        self.publish();
+    }
+
+    /// Drop the last frozen search, which becomes the current search again.
+    ///
+    /// Returns false when no search is frozen.
+    pub fn widen(&mut self) -> bool {
+        let Some(filter) = self.frozen_filters.pop() else {
+            return false;
+        };
+        self.restore(&filter.text, filter.fuzzy);
+        self.publish();
+        true
+    }
+
+    /// The frozen searches, e.g. `home › src › `, empty when none
+    fn breadcrumb(&self) -> String {
+        self.frozen_filters
+            .iter()
+            .map(|filter| format!("{}{}", filter.text, BREADCRUMB_SEPARATOR))
+            .collect()
+    }
+
     fn publish(&self) {
         let event = GenericEvent::ApplicationEvent(ApplicationEvent {
             id: String::from("search.description"),
             payload: Some(Arc::new(SearchDescriptionPayload {
                 search_string: self.search_string.clone(),
                 fuzzy_match: self.fuzzy_match,
+                frozen_filters: self.frozen_filters.clone(),
             })),
         });
         let result = self.tx.send(event);
@@ -61,13 +243,94 @@ impl SearchTextState {
 }
 
 pub struct SearchTextView {
+    vm: Rc<ViewManager>,
     config: Arc<Mutex<Config>>,
     state: Arc<Mutex<SearchTextState>>,
+    store: Store,
+    /// The objects type of the view the searches are made in
+    objects_type: String,
+    /// The past searches of the view, the most recent first
+    searches: Vec<Filter>,
+    /// The index in `searches` of the recalled search, None while typing
+    recalled: Option<usize>,
+    /// The search being typed before recalling the past ones
+    draft: Option<Filter>,
+    /// The last completion offered by the entries of the view: the search completed and its
+    /// completion
+    completion: Option<(String, String)>,
 }
 
 impl SearchTextView {
-    pub fn builder(config: Arc<Mutex<Config>>, state: Arc<Mutex<SearchTextState>>) -> ViewBuilder {
-        ViewBuilder::from(Box::new(SearchTextView { config, state }))
+    pub fn builder(
+        vm: Rc<ViewManager>,
+        config: Arc<Mutex<Config>>,
+        state: Arc<Mutex<SearchTextState>>,
+        store: Store,
+        objects_type: String,
+    ) -> ViewBuilder {
+        ViewBuilder::from(Box::new(SearchTextView {
+            vm,
+            config,
+            state,
+            store,
+            objects_type,
+            searches: vec![],
+            recalled: None,
+            draft: None,
+            completion: None,
+        }))
+    }
+
+    /// Replace the current search by an older or a newer one of the history of the view, the
+    /// newest one being followed by the search which was being typed.
+    fn recall(&mut self, older: bool) {
+        let mut state_lock = self.state.lock().unwrap();
+        let recalled = match (self.recalled, older) {
+            (None, true) if !self.searches.is_empty() => {
+                self.draft = Some(Filter::new(
+                    state_lock.search_string(),
+                    state_lock.fuzzy_match(),
+                ));
+                Some(0)
+            }
+            (Some(idx), true) => Some((idx + 1).min(self.searches.len() - 1)),
+            (Some(0), false) => None,
+            (Some(idx), false) => Some(idx - 1),
+            (None, _) => return,
+        };
+        let search = match recalled {
+            Some(idx) => self.searches[idx].clone(),
+            None => self.draft.take().unwrap_or_default(),
+        };
+        self.recalled = recalled;
+        state_lock.restore(&search.text, search.fuzzy);
+        state_lock.publish();
+    }
+
+    /// Open the search over the history of the view
+    fn show_search_history(&self) {
+        let (fuzzy_config, styles) = {
+            let config_lock = self.config.lock().unwrap();
+            (config_lock.fuzzy.clone(), config_lock.styles.clone())
+        };
+        let vb = SearchHistoryView::builder(
+            self.searches.clone(),
+            fuzzy_config,
+            self.store.case_sensitivity(),
+            styles,
+        );
+        self.vm.show_modal(vb, Some(Self::search_history_callback));
+    }
+
+    fn search_history_callback(&mut self, view: &SearchHistoryView) -> ManagerAction {
+        if let Some(search) = view.chosen() {
+            info!("search '{}' chosen in the history", search.text);
+            self.recalled = None;
+            let mut state_lock = self.state.lock().unwrap();
+            state_lock.restore(&search.text, search.fuzzy);
+            state_lock.publish();
+        }
+        ManagerAction::new(true)
     }
 
     pub fn toggle_fuzzy_match(&mut self) {
@@ -75,9 +338,26 @@ impl SearchTextView {
         state_lock.fuzzy_match = !state_lock.fuzzy_match;
         state_lock.publish();
     }
+
+    /// Cycle the case sensitivity of the searches and search again
+    fn toggle_case_sensitivity(&mut self) {
+        let case_sensitivity = self.store.case_sensitivity().next();
+        self.store.set_case_sensitivity(case_sensitivity);
+        self.state.lock().unwrap().publish();
+    }
 }
 
 impl View for SearchTextView {
+    fn init(&mut self) {
+        self.searches = self
+            .store
+            .list_searches(&self.objects_type)
+            .unwrap_or_else(|e| {
+                error!("Failed to load the past searches: {}", e);
+                vec![]
+            });
+    }
+
     fn draw(&mut self, frame: &mut ratatui::Frame, area: Rect, active: bool) {
         debug!("draw area='{}' active='{}", area, active);
         let config_lock = self.config.lock().unwrap();
@@ -92,22 +372,33 @@ impl View for SearchTextView {
 
         let state_lock = self.state.lock().unwrap();
         let search_string = state_lock.search_string.clone();
+        let breadcrumb = state_lock.breadcrumb();
+        let completion = self
+            .completion
+            .as_ref()
+            .and_then(|(completed, completion)| state_lock.completion(completed, completion))
+            .filter(|_| active)
+            .unwrap_or_default();
 
         let search_text_area: Rect;
         {
             // bottom line
             let horizontal =
-                Layout::horizontal([Constraint::Length(4), Constraint::Percentage(100)]).spacing(0);
+                Layout::horizontal([Constraint::Length(5), Constraint::Percentage(100)]).spacing(0);
             let left: Rect;
             [left, search_text_area] = horizontal.areas(input);
 
-            // The left exact/fuzzy indicator
+            // The left exact/fuzzy and case sensitivity indicator
 
-            let mut pa = if state_lock.fuzzy_match {
-                Paragraph::new("[f]")
-            } else {
-                Paragraph::new("[e]")
-            };
+            let mut pa = Paragraph::new(format!(
+                "[{}{}]",
+                if state_lock.fuzzy_match { 'f' } else { 'e' },
+                match self.store.case_sensitivity() {
+                    CaseSensitivity::Ignore => 'i',
+                    CaseSensitivity::Smart => 's',
+                    CaseSensitivity::Respect => 'c',
+                }
+            ));
             pa = pa.style(
                 config_lock
                     .styles
@@ -116,76 +407,105 @@ impl View for SearchTextView {
             );
             frame.render_widget(pa, left);
 
-            // Draw the free text area
+            // Draw the frozen searches and the free text area
 
-            let pa = Paragraph::new(format!("{}{}", SEARCH_PROMPT, search_string.as_str())).style(
-                config_lock
-                    .styles
-                    .path_style
-                    .bg(config_lock.styles.free_text_area_bg_color.unwrap()),
-            );
+            let bg_color = config_lock.styles.free_text_area_bg_color.unwrap();
+            let pa = Paragraph::new(Line::from(vec![
+                Span::styled(
+                    breadcrumb.as_str(),
+                    config_lock.styles.date_style.bg(bg_color),
+                ),
+                Span::styled(
+                    format!("{}{}", SEARCH_PROMPT, search_string.as_str()),
+                    config_lock.styles.path_style.bg(bg_color),
+                ),
+                Span::styled(completion, config_lock.styles.date_style.bg(bg_color)),
+            ]))
+            .style(Style::default().bg(bg_color));
             frame.render_widget(pa, search_text_area);
         }
 
         if active {
             // Don't activate the cursor if not active...
-            let search_string_cursor_index = state_lock.search_string_cursor_index;
             frame.set_cursor_position(Position::new(
-                search_text_area.x + search_string_cursor_index as u16 + SEARCH_PROMPT.len() as u16,
+                search_text_area.x
+                    + breadcrumb.width() as u16
+                    + state_lock.cursor_column() as u16
+                    + SEARCH_PROMPT.len() as u16,
                 search_text_area.y,
             ));
         }
     }
 
     fn handle_key_event(&mut self, key_event: KeyEvent) -> (EventCaptured, ManagerAction) {
-        let _ = key_event;
         debug!("handle_key_event");
 
+        if matches!(
+            key_event.code,
+            KeyCode::Backspace | KeyCode::Delete | KeyCode::Char(_)
+        ) && key_event.modifiers != KeyModifiers::CONTROL
+            && key_event.modifiers != KeyModifiers::ALT
+        {
+            // editing the search stops the recall
+            self.recalled = None;
+        }
+
+        // the readline-like editing keys
+        if let Some(action) = keybindings::action(Scope::Search, &key_event)
+            && self.state.lock().unwrap().edit(action)
+        {
+            if matches!(action, Action::DeleteWord | Action::ClearLine) {
+                self.recalled = None;
+            }
+            return (EventCaptured::Yes, ManagerAction::new(true));
+        }
+
         match key_event.code {
             KeyCode::Backspace => {
                 let mut state_lock = self.state.lock().unwrap();
-                if state_lock.search_string_cursor_index != 0 {
-                    let search_string_cursor_index = state_lock.search_string_cursor_index;
-                    state_lock.search_string_cursor_index -= 1;
-                    state_lock
-                        .search_string
-                        .remove(search_string_cursor_index - 1);
-                    state_lock.publish();
+                if let Some(idx) = state_lock.previous_boundary() {
+                    state_lock.delete_to(idx);
                 }
             }
             KeyCode::Delete => {
                 let mut state_lock = self.state.lock().unwrap();
-                if state_lock.search_string_cursor_index < state_lock.search_string.len() {
-                    let search_string_cursor_index = state_lock.search_string_cursor_index;
-                    state_lock.search_string.remove(search_string_cursor_index);
-                    state_lock.publish();
+                if let Some(idx) = state_lock.next_boundary() {
+                    state_lock.delete_to(idx);
                 }
             }
             KeyCode::Left => {
                 let mut state_lock = self.state.lock().unwrap();
-                if state_lock.search_string_cursor_index != 0 {
-                    state_lock.search_string_cursor_index -= 1;
+                if let Some(idx) = state_lock.previous_boundary() {
+                    state_lock.search_string_cursor_index = idx;
                 }
             }
             KeyCode::Right => {
                 let mut state_lock = self.state.lock().unwrap();
-                if state_lock.search_string_cursor_index < state_lock.search_string.len() {
-                    state_lock.search_string_cursor_index += 1;
+                if let Some(idx) = state_lock.next_boundary() {
+                    state_lock.search_string_cursor_index = idx;
                 }
             }
             KeyCode::Char(c) => {
-                if key_event.modifiers != KeyModifiers::CONTROL {
-                    let mut state_lock = self.state.lock().unwrap();
-                    let search_string_cursor_index = state_lock.search_string_cursor_index;
-                    state_lock
-                        .search_string
-                        .insert(search_string_cursor_index, c);
-                    state_lock.search_string_cursor_index += 1;
-                    state_lock.publish();
-                } else if c == 'f' {
-                    self.toggle_fuzzy_match();
+                // control and alt combinations are shortcuts, not text
+                if key_event.modifiers != KeyModifiers::CONTROL
+                    && key_event.modifiers != KeyModifiers::ALT
+                {
+                    self.state.lock().unwrap().insert_char(c);
+                } else {
+                    match keybindings::action(Scope::Search, &key_event) {
+                        Some(Action::ToggleFuzzy) => self.toggle_fuzzy_match(),
+                        Some(Action::ToggleCase) => self.toggle_case_sensitivity(),
+                        Some(Action::Narrow) => self.state.lock().unwrap().narrow(),
+                        Some(Action::SearchHistory) => self.show_search_history(),
+                        _ => {}
+                    }
                 }
             }
+            KeyCode::Up | KeyCode::Down => match keybindings::action(Scope::Search, &key_event) {
+                Some(Action::PreviousSearch) => self.recall(true),
+                Some(Action::NextSearch) => self.recall(false),
+                _ => {}
+            },
             _ => {
                 warn!("Unknown action key={}", key_event.code);
             }
@@ -194,6 +514,44 @@ impl View for SearchTextView {
         (EventCaptured::No, ManagerAction::new(false))
     }
 
+    fn handle_escape(&mut self) -> bool { self.state.lock().unwrap().widen() }
+
+    fn handle_tab(&mut self) -> bool {
+        let Some((completed, completion)) = &self.completion else {
+            return false;
+        };
+        let mut state_lock = self.state.lock().unwrap();
+        let Some(completion) = state_lock.completion(completed, completion) else {
+            return false;
+        };
+        info!("completing the search with '{}'", completion);
+        // editing the search stops the recall
+        self.recalled = None;
+        state_lock.complete(completion);
+        true
+    }
+
+    fn handle_application_event(&mut self, ae: &ApplicationEvent) {
+        if ae.id == SEARCH_COMPLETION_EVENT
+            && let Some(payload) = &ae.payload
+            && let Some(payload) = payload.downcast_ref::<SearchCompletionPayload>()
+            && payload.objects_type == self.objects_type
+        {
+            self.completion = payload
+                .completion
+                .clone()
+                .map(|completion| (payload.search_string.clone(), completion));
+        }
+    }
+
+    fn handle_paste(&mut self, text: &str) -> (EventCaptured, ManagerAction) {
+        debug!("pasting {} chars", text.chars().count());
+        // editing the search stops the recall
+        self.recalled = None;
+        self.state.lock().unwrap().insert(text);
+        (EventCaptured::Yes, ManagerAction::new(true))
+    }
+
     fn handle_mouse_event(&mut self, area: Rect, mouse_event: MouseEvent) -> ManagerAction {
         let mut ma = ManagerAction::new(false);
 
@@ -0,0 +1,94 @@
+use super::*;
+
+#[test]
+fn test_insert() {
+    let vm = Rc::new(ViewManager::new());
+    let mut rx = vm.tx().subscribe();
+    let mut state = SearchTextState::new(vm);
+    state.restore("/home/proj", false);
+    state.search_string_cursor_index = "/home".len();
+
+    // a pasted path is inserted at the cursor, without its line break
+    state.insert("/me/work\n");
+    assert_eq!(state.search_string(), "/home/me/work/proj");
+    assert_eq!(state.search_string_cursor_index, "/home/me/work".len());
+
+    // the search is published once
+    let Ok(GenericEvent::ApplicationEvent(ae)) = rx.try_recv() else {
+        panic!("the search was not published");
+    };
+    let payload = ae.payload.unwrap();
+    let payload = payload.downcast_ref::<SearchDescriptionPayload>().unwrap();
+    assert_eq!(payload.search_string, "/home/me/work/proj");
+    assert!(rx.try_recv().is_err());
+
+    state.insert("\r\n");
+    assert_eq!(state.search_string(), "/home/me/work/proj");
+    assert!(rx.try_recv().is_err());
+}
+
+fn state(search_string: &str, cursor: usize) -> SearchTextState {
+    let mut state = SearchTextState::new(Rc::new(ViewManager::new()));
+    state.restore(search_string, false);
+    state.search_string_cursor_index = cursor;
+    state
+}
+
+#[test]
+fn test_edit_words() {
+    let text = "~/src/ma proj";
+    let mut state = state(text, text.len());
+    assert!(state.edit(Action::WordLeft));
+    assert_eq!(state.search_string_cursor_index, "~/src/ma ".len());
+    state.edit(Action::WordLeft);
+    assert_eq!(state.search_string_cursor_index, "~/src/".len());
+    state.edit(Action::WordRight);
+    assert_eq!(state.search_string_cursor_index, "~/src/ma".len());
+
+    // ctrl+w deletes up to a space
+    state.edit(Action::LineEnd);
+    state.edit(Action::DeleteWord);
+    assert_eq!(state.search_string(), "~/src/ma ");
+    state.edit(Action::DeleteWord);
+    assert_eq!(state.search_string(), "");
+    assert!(!state.edit(Action::Narrow));
+}
+
+#[test]
+fn test_edit_multibyte() {
+    let text = "caf\u{e9} d\u{e9}j\u{e0}";
+    let mut state = state(text, text.len());
+    assert_eq!(state.cursor_column(), 9);
+    state.edit(Action::WordLeft);
+    assert_eq!(state.search_string_cursor_index, "caf\u{e9} ".len());
+    assert_eq!(state.cursor_column(), 5);
+
+    // the cursor stays on the character boundaries
+    let idx = state.previous_boundary().unwrap();
+    state.delete_to(idx);
+    state.insert_char('\u{e8}');
+    assert_eq!(state.search_string(), "caf\u{e9}\u{e8}d\u{e9}j\u{e0}");
+    state.search_string_cursor_index = state.next_boundary().unwrap();
+    assert_eq!(state.cursor_column(), 6);
+
+    state.edit(Action::LineStart);
+    assert_eq!(state.search_string_cursor_index, 0);
+    assert_eq!(state.previous_boundary(), None);
+    state.edit(Action::ClearLine);
+    assert_eq!(state.search_string(), "");
+}
+
+#[test]
+fn test_complete() {
+    let mut state = state("proj", 2);
+    // the cursor is not at the end of the search
+    assert_eq!(state.completion("proj", "ects/"), None);
+
+    state.edit(Action::LineEnd);
+    // the completion of another search
+    assert_eq!(state.completion("pro", "jects/"), None);
+    let completion = state.completion("proj", "ects/").unwrap();
+    state.complete(completion);
+    assert_eq!(state.search_string(), "projects/");
+    assert_eq!(state.search_string_cursor_index, "projects/".len());
+}
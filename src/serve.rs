@@ -0,0 +1,273 @@
+use std::{
+    io::{self, BufRead, Write},
+    sync::{Arc, Mutex},
+};
+
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::{Value, json};
+use tracing::{debug, error, info};
+
+use crate::{
+    config::Config,
+    hooks::{self, Hook},
+    remote::RemotePath,
+    store::{self, SortOrder, Store},
+};
+
+#[cfg(test)]
+#[path = "serve_tests.rs"]
+mod serve_tests;
+
+/// The number of entries returned by `query` and `listShortcuts` when no limit is given
+const DEFAULT_LIMIT: usize = 50;
+
+/// The JSON-RPC error codes
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+/// An error of the database, or a rejected entry
+const SERVER_ERROR: i64 = -32000;
+
+#[derive(Deserialize)]
+struct Request {
+    jsonrpc: Option<String>,
+    /// None for a notification, which gets no response
+    id: Option<Value>,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+/// A JSON-RPC error, sent back as the `error` member of the response
+#[derive(Debug, PartialEq)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl ToString) -> RpcError {
+        RpcError {
+            code,
+            message: message.to_string(),
+        }
+    }
+}
+
+impl From<rusqlite::Error> for RpcError {
+    fn from(e: rusqlite::Error) -> RpcError { RpcError::new(SERVER_ERROR, e) }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct QueryParams {
+    #[serde(default)]
+    search: String,
+    #[serde(default)]
+    fuzzy: bool,
+    #[serde(default)]
+    offset: usize,
+    #[serde(default = "default_limit")]
+    limit: usize,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct AddPathParams {
+    path: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct SmartSuggestionsParams {
+    /// The directory the suggestions are made for, typically the one of the edited file
+    path: String,
+    limit: Option<usize>,
+}
+
+fn default_limit() -> usize { DEFAULT_LIMIT }
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PathResult {
+    path: String,
+    date: i64,
+    shortcut: Option<String>,
+    host: Option<String>,
+    /// Whether the path is a smart suggestion rather than an entry of the history
+    smart: bool,
+    /// The score of a smart suggestion, the highest first
+    #[serde(skip_serializing_if = "Option::is_none")]
+    score: Option<f64>,
+}
+
+impl From<store::Path> for PathResult {
+    fn from(path: store::Path) -> PathResult {
+        PathResult {
+            path: path.path,
+            date: path.date,
+            shortcut: path.shortcut.map(|shortcut| shortcut.name),
+            host: path.hostname,
+            smart: path.smart_path,
+            score: None,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ShortcutResult {
+    name: String,
+    path: String,
+    description: Option<String>,
+}
+
+/// Answers the JSON-RPC requests of an editor plugin, one request per line of `input` and one
+/// response per line of `output`, until `input` is closed.
+///
+/// The methods are:
+/// - `query {search, fuzzy, offset, limit}`: the paths of the history matching a search;
+/// - `addPath {path}`: records a visit of a directory, running the `on_add_path` hook;
+/// - `listShortcuts {search, fuzzy, offset, limit}`: the shortcuts matching a search;
+/// - `smartSuggestions {path, limit}`: the directories usually visited after `path`.
+pub fn serve(
+    store: &Store,
+    config: &Arc<Mutex<Config>>,
+    input: impl BufRead,
+    mut output: impl Write,
+) -> io::Result<()> {
+    info!("serve: waiting for requests");
+    for line in input.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(response) = handle_line(store, config, &line) {
+            writeln!(output, "{}", response)?;
+            output.flush()?;
+        }
+    }
+    info!("serve: input closed");
+    Ok(())
+}
+
+/// The response to a line of input, None for a notification
+fn handle_line(store: &Store, config: &Arc<Mutex<Config>>, line: &str) -> Option<Value> {
+    let request = match serde_json::from_str::<Value>(line) {
+        Ok(request) => request,
+        Err(e) => return Some(error_response(Value::Null, RpcError::new(PARSE_ERROR, e))),
+    };
+    let id = request.get("id").cloned();
+    let request = match serde_json::from_value::<Request>(request) {
+        Ok(request) if request.jsonrpc.as_deref() == Some("2.0") => request,
+        Ok(_) => {
+            let e = RpcError::new(INVALID_REQUEST, "jsonrpc must be \"2.0\"");
+            return Some(error_response(id.unwrap_or_default(), e));
+        }
+        Err(e) => {
+            let e = RpcError::new(INVALID_REQUEST, e);
+            return Some(error_response(id.unwrap_or_default(), e));
+        }
+    };
+    debug!("serve: {} {}", request.method, request.params);
+    let result = dispatch(store, config, &request.method, request.params);
+    if let Err(e) = &result {
+        error!("serve: {} failed: {}", request.method, e.message);
+    }
+    let id = request.id?;
+    Some(match result {
+        Ok(result) => json!({"jsonrpc": "2.0", "id": id, "result": result}),
+        Err(e) => error_response(id, e),
+    })
+}
+
+fn error_response(id: Value, e: RpcError) -> Value {
+    json!({"jsonrpc": "2.0", "id": id, "error": {"code": e.code, "message": e.message}})
+}
+
+/// The parameters of a method, none being given standing for the default ones
+fn params<T: DeserializeOwned>(params: Value) -> Result<T, RpcError> {
+    let params = if params.is_null() { json!({}) } else { params };
+    serde_json::from_value(params).map_err(|e| RpcError::new(INVALID_PARAMS, e))
+}
+
+fn dispatch(
+    store: &Store,
+    config: &Arc<Mutex<Config>>,
+    method: &str,
+    raw_params: Value,
+) -> Result<Value, RpcError> {
+    match method {
+        "query" => {
+            let p: QueryParams = params(raw_params)?;
+            // the smart suggestions are paths of the history, which would be listed twice
+            let paths = store.list_paths_sorted(
+                p.offset,
+                p.limit,
+                &p.search,
+                p.fuzzy,
+                SortOrder::Default,
+                false,
+            )?;
+            Ok(to_value(
+                paths.into_iter().map(PathResult::from).collect::<Vec<_>>(),
+            ))
+        }
+        "addPath" => {
+            let p: AddPathParams = params(raw_params)?;
+            RemotePath::validate(&p.path).map_err(|e| RpcError::new(SERVER_ERROR, e))?;
+            store.add_path(&p.path)?;
+            hooks::run_hook(config, Hook::AddPath, &[("CDIR_PATH", &p.path)]);
+            Ok(Value::Null)
+        }
+        "listShortcuts" => {
+            let p: QueryParams = params(raw_params)?;
+            let shortcuts = store.list_shortcuts(p.offset, p.limit, &p.search, p.fuzzy)?;
+            Ok(to_value(
+                shortcuts
+                    .into_iter()
+                    .map(|shortcut| ShortcutResult {
+                        name: shortcut.name,
+                        path: shortcut.path,
+                        description: shortcut.description,
+                    })
+                    .collect::<Vec<_>>(),
+            ))
+        }
+        "smartSuggestions" => {
+            let p: SmartSuggestionsParams = params(raw_params)?;
+            let (depth, count) = {
+                let config = config.lock().unwrap();
+                (
+                    config.smart_suggestions_depth,
+                    config.smart_suggestions_count,
+                )
+            };
+            let shortcuts = store.list_all_shortcuts()?;
+            let paths = store.list_path_history_smart_suggestions_scored(
+                &p.path,
+                depth,
+                p.limit.unwrap_or(count),
+                &shortcuts,
+            )?;
+            Ok(to_value(
+                paths
+                    .into_iter()
+                    .map(|(path, explanation)| PathResult {
+                        score: Some(explanation.score),
+                        ..PathResult::from(path)
+                    })
+                    .collect::<Vec<_>>(),
+            ))
+        }
+        _ => Err(RpcError::new(
+            METHOD_NOT_FOUND,
+            format!("unknown method '{}'", method),
+        )),
+    }
+}
+
+fn to_value(result: impl Serialize) -> Value {
+    // the results are plain structures, which always serialize
+    serde_json::to_value(result).unwrap()
+}
@@ -0,0 +1,123 @@
+use super::*;
+
+/// Run the server on the requests, returning the responses
+fn exchange(store: &Store, requests: &[Value]) -> Vec<Value> {
+    let config = Arc::new(Mutex::new(Config::default()));
+    let input: String = requests.iter().map(|r| format!("{}\n", r)).collect();
+    let mut output = Vec::new();
+    serve(store, &config, input.as_bytes(), &mut output).unwrap();
+    String::from_utf8(output)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_methods() {
+    let store = Store::setup_test_store();
+    store
+        .upsert_shortcut("w", "/home/me/work", Some("work"))
+        .unwrap();
+    let responses = exchange(
+        &store,
+        &[
+            json!({"jsonrpc": "2.0", "id": 1, "method": "addPath", "params": {"path": "/home/me/work/cdir"}}),
+            json!({"jsonrpc": "2.0", "method": "addPath", "params": {"path": "/tmp"}}),
+            json!({"jsonrpc": "2.0", "id": 2, "method": "query", "params": {"search": "cdir"}}),
+            json!({"jsonrpc": "2.0", "id": "s", "method": "listShortcuts"}),
+        ],
+    );
+    // the notification gets no response
+    assert_eq!(responses.len(), 3);
+    assert_eq!(
+        responses[0],
+        json!({"jsonrpc": "2.0", "id": 1, "result": null})
+    );
+    assert_eq!(responses[1]["id"], 2);
+    let paths = responses[1]["result"].as_array().unwrap();
+    assert_eq!(paths.len(), 1);
+    assert_eq!(paths[0]["path"], "/home/me/work/cdir");
+    assert_eq!(paths[0]["shortcut"], "w");
+    assert_eq!(paths[0]["smart"], false);
+    assert_eq!(
+        responses[2]["result"],
+        json!([{"name": "w", "path": "/home/me/work", "description": "work"}])
+    );
+    assert_eq!(store.list_paths(0, 10, "/tmp", false).unwrap().len(), 1);
+}
+
+#[test]
+fn test_smart_suggestions() {
+    let store = Store::setup_test_store();
+    for (path, date) in [("/a", 100), ("/b", 101), ("/a", 102), ("/b", 103)] {
+        store.add_path_with_time(path, date).unwrap();
+    }
+    let responses = exchange(
+        &store,
+        &[
+            json!({"jsonrpc": "2.0", "id": 1, "method": "smartSuggestions", "params": {"path": "/a"}}),
+        ],
+    );
+    let paths = responses[0]["result"].as_array().unwrap();
+    assert_eq!(paths[0]["path"], "/b");
+    assert!(paths[0]["score"].as_f64().unwrap() > 0.0);
+}
+
+#[test]
+fn test_query_without_smart_suggestions() {
+    let store = Store::setup_test_store();
+    let cwd = std::env::current_dir().unwrap();
+    let cwd = cwd.to_str().unwrap();
+    for (path, date) in [(cwd, 100), ("/next", 101), (cwd, 102), ("/after", 103)] {
+        store.add_path_with_time(path, date).unwrap();
+    }
+    // the smart suggestions of the current directory are paths of the history already
+    let responses = exchange(
+        &store,
+        &[json!({"jsonrpc": "2.0", "id": 1, "method": "query"})],
+    );
+    let paths: Vec<&str> = responses[0]["result"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|path| path["path"].as_str().unwrap())
+        .collect();
+    assert_eq!(paths, vec!["/after", cwd, "/next"]);
+}
+
+#[test]
+fn test_errors() {
+    let store = Store::setup_test_store();
+    let config = Arc::new(Mutex::new(Config::default()));
+    let code = |line: &str| handle_line(&store, &config, line).unwrap()["error"]["code"].clone();
+    assert_eq!(code("{not json"), PARSE_ERROR);
+    assert_eq!(code(r#"{"id": 1, "method": "query"}"#), INVALID_REQUEST);
+    assert_eq!(
+        code(r#"{"jsonrpc": "2.0", "id": 1, "method": "jump"}"#),
+        METHOD_NOT_FOUND
+    );
+    assert_eq!(
+        code(r#"{"jsonrpc": "2.0", "id": 1, "method": "addPath", "params": {}}"#),
+        INVALID_PARAMS
+    );
+    assert_eq!(
+        code(r#"{"jsonrpc": "2.0", "id": 1, "method": "query", "params": {"serch": "x"}}"#),
+        INVALID_PARAMS
+    );
+    assert_eq!(
+        code(
+            r#"{"jsonrpc": "2.0", "id": 1, "method": "addPath", "params": {"path": "host:relative"}}"#
+        ),
+        SERVER_ERROR
+    );
+    // a failed notification gets no response either
+    assert!(
+        handle_line(
+            &store,
+            &config,
+            r#"{"jsonrpc": "2.0", "method": "addPath", "params": {}}"#
+        )
+        .is_none()
+    );
+}
@@ -0,0 +1,210 @@
+use chrono::{DateTime, Local};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+use tracing::debug;
+
+use crate::{
+    store::Session,
+    theme::ThemeStyles,
+    tui::{EventCaptured, ManagerAction, View, ViewBuilder, ViewManager},
+};
+
+#[cfg(test)]
+#[path = "sessions_view_tests.rs"]
+mod sessions_view_tests;
+
+/// The width of the list of the sessions
+const SESSIONS_WIDTH: u16 = 34;
+
+/// A modal browsing the sessions of the history: the sessions on the left, the directories
+/// visited during the selected one on the right, the chosen directory being jumped to.
+pub struct SessionsView {
+    styles: ThemeStyles,
+    /// The sessions, the most recent first
+    sessions: Vec<Session>,
+    selected_session: usize,
+    selected_path: usize,
+    /// Whether the keys move in the directories of the selected session
+    in_paths: bool,
+    chosen: Option<String>,
+}
+
+impl SessionsView {
+    pub fn builder(sessions: Vec<Session>, styles: ThemeStyles) -> ViewBuilder {
+        ViewBuilder::from(Box::new(Self::new(sessions, styles)))
+    }
+
+    fn new(sessions: Vec<Session>, styles: ThemeStyles) -> Self {
+        Self {
+            styles,
+            sessions,
+            selected_session: 0,
+            selected_path: 0,
+            in_paths: false,
+            chosen: None,
+        }
+    }
+
+    /// The chosen directory, None if the window was closed without choosing one
+    pub fn chosen(&self) -> Option<&str> { self.chosen.as_deref() }
+
+    /// The start and the end of a session, e.g. `Tue 2024-01-02 14:05-16:40`, the day of the
+    /// end being added when it is not the one of the start
+    fn session_label(session: &Session) -> String {
+        let (Some(start), Some(end)) = (
+            DateTime::from_timestamp(session.start, 0),
+            DateTime::from_timestamp(session.end, 0),
+        ) else {
+            return String::new();
+        };
+        let (start, end) = (start.with_timezone(&Local), end.with_timezone(&Local));
+        let end_format = match start.date_naive() == end.date_naive() {
+            true => "%H:%M",
+            false => "%a %H:%M",
+        };
+        format!(
+            "{}-{}",
+            start.format("%a %Y-%m-%d %H:%M"),
+            end.format(end_format)
+        )
+    }
+
+    /// The directories of the selected session
+    fn paths(&self) -> &[String] {
+        self.sessions
+            .get(self.selected_session)
+            .map(|session| session.paths.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// The lines of a list, scrolled to keep the selected one visible: only the `visible`
+    /// items drawn are turned into spans, however long the list is.
+    fn list_lines<'a, T>(
+        &self,
+        items: &'a [T],
+        selected: Option<usize>,
+        visible: usize,
+        spans: impl Fn(&'a T) -> Vec<Span<'a>>,
+    ) -> Vec<Line<'a>> {
+        let first = selected
+            .unwrap_or_default()
+            .saturating_sub(visible.saturating_sub(1))
+            .min(items.len());
+        let last = (first + visible).min(items.len());
+        items[first..last]
+            .iter()
+            .enumerate()
+            .map(|(row, item)| match selected == Some(first + row) {
+                true => Line::from(spans(item)).style(self.styles.highlight(Style::default())),
+                false => Line::from(spans(item)),
+            })
+            .collect()
+    }
+
+    fn block<'a>(&self, title: &'a str) -> Block<'a> {
+        let mut block = Block::default()
+            .title(Span::styled(title, self.styles.title_style))
+            .borders(Borders::ALL);
+        if let Some(border_color) = self.styles.border_color {
+            block = block.border_style(Style::default().fg(border_color));
+        }
+        block
+    }
+}
+
+impl View for SessionsView {
+    fn draw(&mut self, frame: &mut ratatui::Frame, _area: Rect, _active: bool) {
+        debug!("Drawing the sessions");
+        let area = frame.area();
+        let height = 22.min(area.height);
+        let width = 100.min(area.width);
+        let modal_area = ViewManager::centered_rect(area, width, height);
+
+        frame.render_widget(Clear, modal_area);
+        if let Some(bg_color) = &self.styles.background_color {
+            let background = Paragraph::new("").style(Style::default().bg(*bg_color));
+            frame.render_widget(background, modal_area);
+        }
+
+        let [sessions_area, paths_area] = Layout::horizontal([
+            Constraint::Length(SESSIONS_WIDTH.min(width / 2)),
+            Constraint::Fill(1),
+        ])
+        .areas(modal_area);
+        // the borders take 2 lines
+        let visible = height.saturating_sub(2) as usize;
+
+        let session_spans = |session: &Session| {
+            vec![
+                Span::styled(Self::session_label(session), self.styles.date_style),
+                Span::styled(
+                    format!(" ({})", session.paths.len()),
+                    self.styles.text_style,
+                ),
+            ]
+        };
+        let lines = match self.sessions.is_empty() {
+            true => vec![Line::from(Span::styled(
+                "no history",
+                self.styles.text_style,
+            ))],
+            false => self.list_lines(
+                &self.sessions,
+                (!self.in_paths).then_some(self.selected_session),
+                visible,
+                session_spans,
+            ),
+        };
+        frame.render_widget(
+            Paragraph::new(lines).block(self.block(" Sessions ")),
+            sessions_area,
+        );
+
+        let lines = self.list_lines(
+            self.paths(),
+            self.in_paths.then_some(self.selected_path),
+            visible,
+            |path| vec![Span::styled(path.as_str(), self.styles.path_style)],
+        );
+        frame.render_widget(
+            Paragraph::new(lines).block(self.block(" Directories ")),
+            paths_area,
+        );
+    }
+
+    fn handle_key_event(&mut self, key_event: KeyEvent) -> (EventCaptured, ManagerAction) {
+        match (key_event.code, self.in_paths) {
+            (KeyCode::Up, false) => {
+                self.selected_session = self.selected_session.saturating_sub(1);
+                self.selected_path = 0;
+            }
+            (KeyCode::Down, false) => {
+                self.selected_session =
+                    (self.selected_session + 1).min(self.sessions.len().saturating_sub(1));
+                self.selected_path = 0;
+            }
+            (KeyCode::Enter | KeyCode::Right | KeyCode::Tab, false) => {
+                self.in_paths = !self.paths().is_empty();
+            }
+            (KeyCode::Up, true) => self.selected_path = self.selected_path.saturating_sub(1),
+            (KeyCode::Down, true) => {
+                self.selected_path = (self.selected_path + 1).min(self.paths().len() - 1)
+            }
+            (KeyCode::Left | KeyCode::Tab | KeyCode::Backspace, true) => self.in_paths = false,
+            (KeyCode::Enter, true) => {
+                self.chosen = self.paths().get(self.selected_path).cloned();
+                return (
+                    EventCaptured::Yes,
+                    ManagerAction::new(false).with_close(true),
+                );
+            }
+            _ => return (EventCaptured::Yes, ManagerAction::new(false)),
+        }
+        (EventCaptured::Yes, ManagerAction::new(true))
+    }
+}
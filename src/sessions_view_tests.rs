@@ -0,0 +1,109 @@
+use crossterm::event::KeyModifiers;
+
+use super::*;
+
+fn key(code: KeyCode) -> KeyEvent { KeyEvent::new(code, KeyModifiers::NONE) }
+
+fn view() -> SessionsView {
+    SessionsView::new(
+        vec![
+            Session {
+                start: 5000,
+                end: 6000,
+                paths: vec![String::from("/home/me/docs"), String::from("/tmp")],
+            },
+            Session {
+                start: 1000,
+                end: 1700,
+                paths: vec![
+                    String::from("/home/me/api"),
+                    String::from("/home/me/api/src"),
+                ],
+            },
+        ],
+        ThemeStyles::default(),
+    )
+}
+
+#[test]
+fn test_choose() {
+    let mut view = view();
+    // the second session, then its second directory
+    view.handle_key_event(key(KeyCode::Down));
+    view.handle_key_event(key(KeyCode::Down));
+    view.handle_key_event(key(KeyCode::Enter));
+    assert!(view.in_paths);
+    view.handle_key_event(key(KeyCode::Down));
+    view.handle_key_event(key(KeyCode::Down));
+    let (_, action) = view.handle_key_event(key(KeyCode::Enter));
+    assert!(action.close());
+    assert_eq!(view.chosen(), Some("/home/me/api/src"));
+}
+
+#[test]
+fn test_back_to_sessions() {
+    let mut view = view();
+    view.handle_key_event(key(KeyCode::Right));
+    view.handle_key_event(key(KeyCode::Down));
+    view.handle_key_event(key(KeyCode::Left));
+    assert!(!view.in_paths);
+    // another session starts at its first directory
+    view.handle_key_event(key(KeyCode::Down));
+    assert_eq!(view.selected_session, 1);
+    assert_eq!(view.selected_path, 0);
+    assert_eq!(view.chosen(), None);
+}
+
+#[test]
+fn test_no_sessions() {
+    let mut view = SessionsView::new(vec![], ThemeStyles::default());
+    view.handle_key_event(key(KeyCode::Down));
+    view.handle_key_event(key(KeyCode::Enter));
+    assert!(!view.in_paths);
+    assert_eq!(view.chosen(), None);
+}
+
+#[test]
+fn test_list_lines_builds_the_visible_items() {
+    let view = view();
+    let items: Vec<usize> = (0..10_000).collect();
+    let built = std::cell::Cell::new(0);
+    let spans = |item: &usize| {
+        built.set(built.get() + 1);
+        vec![Span::raw(item.to_string())]
+    };
+
+    let lines = view.list_lines(&items, Some(5_000), 20, spans);
+    assert_eq!(built.get(), 20);
+    assert_eq!(lines.first().unwrap().to_string(), "4981");
+    assert_eq!(lines.last().unwrap().to_string(), "5000");
+
+    built.set(0);
+    let lines = view.list_lines(&items[..3], None, 20, spans);
+    assert_eq!((built.get(), lines.len()), (3, 3));
+}
+
+#[test]
+fn test_draw_large_history() {
+    let sessions = (0..100_000)
+        .map(|idx| Session {
+            start: idx * 10_000,
+            end: idx * 10_000 + 600,
+            paths: vec![String::from("/tmp")],
+        })
+        .collect();
+    let mut view = SessionsView::new(sessions, ThemeStyles::default());
+    view.selected_session = 50_000;
+    let mut term = ratatui::Terminal::new(ratatui::backend::TestBackend::new(100, 22)).unwrap();
+    term.draw(|frame| view.draw(frame, frame.area(), true))
+        .unwrap();
+
+    // the sessions scrolled out are not formatted: only the 20 rows inside the borders are
+    let built = std::cell::Cell::new(0);
+    let lines = view.list_lines(&view.sessions, Some(view.selected_session), 20, |session| {
+        built.set(built.get() + 1);
+        vec![Span::raw(SessionsView::session_label(session))]
+    });
+    assert_eq!(lines.len(), 20);
+    assert_eq!(built.get(), 20);
+}
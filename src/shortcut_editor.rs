@@ -1,66 +1,184 @@
 use std::sync::{Arc, Mutex};
 
 use crossterm::event::{KeyCode, KeyEvent};
-use log::{debug, error};
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
+    text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph},
 };
+use tokio::sync::broadcast;
+use tracing::{debug, info};
 use tui_textarea::{Input, TextArea};
 
 use crate::{
     config::Config,
+    gui::Gui,
     store,
-    store::Shortcut,
-    tui::{EventCaptured, ManagerAction, View, ViewBuilder},
+    store::{Shortcut, SuggestionSettings},
+    tui::{EventCaptured, GenericEvent, ManagerAction, Notification, View, ViewBuilder, notify},
 };
 
 #[derive(Copy, Clone, PartialEq)]
 enum EditorField {
     Name,
     Description,
+    SuggestionsDepth,
+    SuggestionsCount,
     YesButton,
     CancelButton,
 }
 
+/// The resolutions of a name already used by another shortcut
+#[derive(Copy, Clone, PartialEq)]
+enum Resolution {
+    Rename,
+    Overwrite,
+    Cancel,
+}
+
+/// The shortcut whose name is wanted, and the resolution selected
+struct Conflict {
+    shortcut: Shortcut,
+    selected: Resolution,
+}
+
 pub struct ShortcutEditor {
     store: store::Store,
     config: Arc<Mutex<Config>>,
+    tx: broadcast::Sender<GenericEvent>,
     shortcut: Option<Shortcut>,
     name_textarea: Option<TextArea<'static>>,
     description_textarea: Option<TextArea<'static>>,
+    /// The smart suggestions parameters of the directory of the shortcut, blank for the
+    /// configured ones
+    depth_textarea: Option<TextArea<'static>>,
+    count_textarea: Option<TextArea<'static>>,
     selected_field: EditorField,
+    /// The other shortcuts of the same path
+    duplicates: Vec<String>,
+    conflict: Option<Conflict>,
 }
 
 impl ShortcutEditor {
     pub fn builder(
         store: store::Store,
         config: Arc<Mutex<Config>>,
+        tx: broadcast::Sender<GenericEvent>,
         shortcut: Shortcut,
     ) -> ViewBuilder {
         ViewBuilder::from(Box::new(Self {
             store,
             config,
+            tx,
             shortcut: Some(shortcut),
             name_textarea: None,
             description_textarea: None,
+            depth_textarea: None,
+            count_textarea: None,
             selected_field: EditorField::Name,
+            duplicates: vec![],
+            conflict: None,
         }))
     }
 
+    /// The name typed in the editor
+    fn name(&self) -> &str {
+        match self.name_textarea.as_ref() {
+            Some(name_textarea) if !name_textarea.lines().is_empty() => {
+                name_textarea.lines()[0].as_str()
+            }
+            _ => "",
+        }
+    }
+
+    /// The number typed in a field of the smart suggestions parameters, None when blank
+    fn parameter(textarea: Option<&TextArea<'static>>) -> Result<Option<usize>, String> {
+        let text = textarea
+            .and_then(|textarea| textarea.lines().first())
+            .map(|line| line.trim())
+            .unwrap_or_default();
+        match text {
+            "" => Ok(None),
+            text => match text.parse::<usize>() {
+                Ok(value) if value > 0 => Ok(Some(value)),
+                _ => Err(format!("'{}' is not a positive number", text)),
+            },
+        }
+    }
+
+    /// Save the smart suggestions parameters of the directory of the shortcut.
+    fn save_suggestion_settings(&self) {
+        let Some(shortcut) = self.shortcut.as_ref() else {
+            return;
+        };
+        let settings = match (
+            Self::parameter(self.depth_textarea.as_ref()),
+            Self::parameter(self.count_textarea.as_ref()),
+        ) {
+            (Ok(depth), Ok(count)) => SuggestionSettings { depth, count },
+            (Err(e), _) | (_, Err(e)) => {
+                notify(
+                    &self.tx,
+                    Notification::warning(format!("Suggestions not saved: {}", e)),
+                );
+                return;
+            }
+        };
+        if let Err(err) = self.store.set_suggestion_settings(&shortcut.path, settings) {
+            notify(
+                &self.tx,
+                Gui::failure_notification("save the suggestions settings", &err),
+            );
+        }
+    }
+
+    /// A field of the editor, its border showing whether it is selected
+    fn field_block(title: &str, config: &Config) -> Block<'static> {
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title.to_string())
+            .title_style(config.styles.text_style)
+            .border_style(Style::default().fg(config.styles.border_color.unwrap()))
+    }
+
+    /// The other shortcut named like the name typed in the editor, if any
+    fn conflicting_shortcut(&self) -> Option<Shortcut> {
+        let id = self.shortcut.as_ref()?.id;
+        self.store
+            .find_shortcut(self.name())
+            .filter(|other| other.id != id)
+    }
+
+    /// Apply the resolution of a conflict.
+    ///
+    /// ### Returns
+    /// Whether the editor is closed.
+    fn resolve_conflict(&mut self, conflict: Conflict) -> bool {
+        match conflict.selected {
+            Resolution::Rename => {
+                self.selected_field = EditorField::Name;
+                false
+            }
+            Resolution::Overwrite => {
+                info!("overwriting the shortcut '{}'", conflict.shortcut.name);
+                if let Err(err) = self.store.delete_shortcut_by_id(conflict.shortcut.id) {
+                    notify(&self.tx, Gui::failure_notification("overwrite", &err));
+                    return true;
+                }
+                self.save_shortcut();
+                true
+            }
+            Resolution::Cancel => true,
+        }
+    }
+
     fn save_shortcut(&mut self) {
         // Save the shortcut
         debug!("Saving shortcut");
-        if let Some(name_textarea) = self.name_textarea.as_ref()
-            && let Some(description_textarea) = self.description_textarea.as_ref()
-        {
-            let name = if name_textarea.lines().is_empty() {
-                ""
-            } else {
-                name_textarea.lines()[0].as_str()
-            };
+        if let Some(description_textarea) = self.description_textarea.as_ref() {
+            let name = self.name();
 
             let description = if description_textarea.lines().is_empty() {
                 None
@@ -69,17 +187,20 @@ impl ShortcutEditor {
             };
 
             debug!("Saving name: {:?}, description: {:?}", name, description);
-            if let Some(shortcut) = self.shortcut.as_ref()
-                && let Err(err) = self.store.update_shortcut(
+            if let Some(shortcut) = self.shortcut.as_ref() {
+                let notification = match self.store.update_shortcut(
                     shortcut.id,
                     name,
                     shortcut.path.as_str(),
                     description,
-                )
-            {
-                error!("Error updating shortcut: {}", err);
+                ) {
+                    Ok(()) => Notification::info(format!("{} saved", name)),
+                    Err(err) => Gui::failure_notification("save the shortcut", &err),
+                };
+                notify(&self.tx, notification);
             }
         }
+        self.save_suggestion_settings();
     }
 }
 
@@ -118,6 +239,45 @@ impl View for ShortcutEditor {
             description_textarea.insert_str(description.as_str());
         }
         self.description_textarea = Some(description_textarea);
+
+        // Initialize the smart suggestions parameters
+        let settings = self
+            .shortcut
+            .as_ref()
+            .and_then(|shortcut| self.store.suggestion_settings(&shortcut.path).ok())
+            .unwrap_or_default();
+        for (value, title, textarea) in [
+            (
+                settings.depth,
+                "Suggestions depth",
+                &mut self.depth_textarea,
+            ),
+            (
+                settings.count,
+                "Suggestions count",
+                &mut self.count_textarea,
+            ),
+        ] {
+            let mut field = TextArea::default();
+            field.set_block(Self::field_block(title, &config_lock));
+            field.set_cursor_line_style(config_lock.styles.text_style);
+            field.set_placeholder_text("default");
+            if let Some(value) = value {
+                field.insert_str(value.to_string());
+            }
+            *textarea = Some(field);
+        }
+
+        if let Some(shortcut) = self.shortcut.as_ref() {
+            self.duplicates = self
+                .store
+                .list_shortcuts_by_path(&shortcut.path)
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|other| other.id != shortcut.id)
+                .map(|other| other.name)
+                .collect();
+        }
     }
 
     fn draw(&mut self, frame: &mut Frame, modal_area: Rect, _active: bool) {
@@ -133,7 +293,7 @@ impl View for ShortcutEditor {
         // Create a modal that's centered on the screen
         let layout = Layout::vertical([
             Constraint::Fill(1),
-            Constraint::Length(12),
+            Constraint::Length(15),
             Constraint::Fill(1),
         ]);
         let chunks: [Rect; 3] = layout.areas(modal_area);
@@ -168,16 +328,38 @@ impl View for ShortcutEditor {
             width: modal_area.width - 2,
             height: modal_area.height - 2,
         };
-        let vchunks: [Rect; 4] = Layout::default()
+        let vchunks: [Rect; 5] = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
                 Constraint::Length(3), // name textarea
                 Constraint::Length(3), // description textarea
-                Constraint::Fill(1),   // spacing
+                Constraint::Length(3), // smart suggestions parameters
+                Constraint::Fill(1),   // duplicates and conflict
                 Constraint::Length(1), // buttons
             ])
             .areas(inner);
 
+        let mut messages = vec![];
+        if !self.duplicates.is_empty() {
+            messages.push(Line::from(Span::styled(
+                format!(
+                    "Same directory as: {} (delete the duplicates no longer needed)",
+                    self.duplicates.join(", ")
+                ),
+                config_lock.styles.date_style,
+            )));
+        }
+        if let Some(conflict) = self.conflict.as_ref() {
+            messages.push(Line::from(Span::styled(
+                format!(
+                    "'{}' is already the shortcut of {}",
+                    conflict.shortcut.name, conflict.shortcut.path
+                ),
+                config_lock.styles.text_em_style,
+            )));
+        }
+        frame.render_widget(Paragraph::new(messages), vchunks[3]);
+
         // Update border styles and cursor visibility based on selected field
         if let Some(name_textarea) = self.name_textarea.as_mut() {
             name_textarea.set_block(
@@ -218,6 +400,35 @@ impl View for ShortcutEditor {
             frame.render_widget(description_textarea, vchunks[1]);
         }
 
+        let parameter_areas: [Rect; 2] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)]).areas(vchunks[2]);
+        for (field, title, textarea, area) in [
+            (
+                EditorField::SuggestionsDepth,
+                "Suggestions depth",
+                self.depth_textarea.as_mut(),
+                parameter_areas[0],
+            ),
+            (
+                EditorField::SuggestionsCount,
+                "Suggestions count",
+                self.count_textarea.as_mut(),
+                parameter_areas[1],
+            ),
+        ] {
+            let Some(textarea) = textarea else {
+                continue;
+            };
+            textarea.set_block(Self::field_block(title, &config_lock));
+            // Show cursor only if this field is selected
+            if self.selected_field == field {
+                textarea.set_cursor_style(Style::default().add_modifier(Modifier::REVERSED));
+            } else {
+                textarea.set_cursor_style(Style::default());
+            }
+            frame.render_widget(&*textarea, area);
+        }
+
         // Buttons at the bottom
         let button_layout: [Rect; 3] = Layout::default()
             .direction(Direction::Horizontal)
@@ -226,24 +437,52 @@ impl View for ShortcutEditor {
                 Constraint::Fill(1),
                 Constraint::Length(10),
             ])
-            .areas(vchunks[3]);
-
-        let yes_style = if self.selected_field == EditorField::YesButton {
-            Style::default()
-                .fg(Color::Black)
-                .bg(Color::Green)
-                .add_modifier(Modifier::BOLD)
-        } else {
-            Style::default().fg(Color::Green)
-        };
-        let cancel_style = if self.selected_field == EditorField::CancelButton {
-            Style::default()
-                .fg(Color::Black)
-                .bg(Color::Red)
-                .add_modifier(Modifier::BOLD)
-        } else {
-            Style::default().fg(Color::Red)
-        };
+            .areas(vchunks[4]);
+
+        let yes_style = config_lock
+            .styles
+            .button_style(Color::Green, self.selected_field == EditorField::YesButton);
+        let cancel_style = config_lock
+            .styles
+            .button_style(Color::Red, self.selected_field == EditorField::CancelButton);
+        if let Some(conflict) = self.conflict.as_ref() {
+            let button_layout: [Rect; 5] = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([
+                    Constraint::Length(10),
+                    Constraint::Fill(1),
+                    Constraint::Length(13),
+                    Constraint::Fill(1),
+                    Constraint::Length(10),
+                ])
+                .areas(vchunks[4]);
+            let buttons = [
+                (
+                    Resolution::Rename,
+                    " Rename ",
+                    Color::Green,
+                    button_layout[0],
+                ),
+                (
+                    Resolution::Overwrite,
+                    " Overwrite ",
+                    Color::Yellow,
+                    button_layout[2],
+                ),
+                (Resolution::Cancel, " Cancel ", Color::Red, button_layout[4]),
+            ];
+            for (resolution, label, color, area) in buttons {
+                let style = config_lock
+                    .styles
+                    .button_style(color, conflict.selected == resolution);
+                let button = Paragraph::new(label)
+                    .style(style)
+                    .alignment(ratatui::layout::Alignment::Center);
+                frame.render_widget(button, area);
+            }
+            return;
+        }
+
         let yes = Paragraph::new(" Yes ")
             .style(yes_style)
             .alignment(ratatui::layout::Alignment::Center);
@@ -259,6 +498,39 @@ impl View for ShortcutEditor {
         let mut close = false;
         let mut redraw = false;
 
+        if let Some(conflict) = self.conflict.as_mut() {
+            match key_event.code {
+                KeyCode::Left | KeyCode::BackTab => {
+                    conflict.selected = match conflict.selected {
+                        Resolution::Rename => Resolution::Cancel,
+                        Resolution::Overwrite => Resolution::Rename,
+                        Resolution::Cancel => Resolution::Overwrite,
+                    };
+                }
+                KeyCode::Right | KeyCode::Tab => {
+                    conflict.selected = match conflict.selected {
+                        Resolution::Rename => Resolution::Overwrite,
+                        Resolution::Overwrite => Resolution::Cancel,
+                        Resolution::Cancel => Resolution::Rename,
+                    };
+                }
+                KeyCode::Enter => {
+                    let conflict = self.conflict.take().unwrap();
+                    close = self.resolve_conflict(conflict);
+                }
+                KeyCode::Esc => {
+                    // Back to the editing of the name
+                    self.conflict = None;
+                    self.selected_field = EditorField::Name;
+                }
+                _ => {}
+            }
+            return (
+                EventCaptured::Yes,
+                ManagerAction::new(true).with_close(close),
+            );
+        }
+
         match key_event.code {
             KeyCode::Esc => {
                 // Cancel - close without saving
@@ -271,7 +543,9 @@ impl View for ShortcutEditor {
                 // Navigate between fields
                 self.selected_field = match self.selected_field {
                     EditorField::Name => EditorField::Description,
-                    EditorField::Description => EditorField::YesButton,
+                    EditorField::Description => EditorField::SuggestionsDepth,
+                    EditorField::SuggestionsDepth => EditorField::SuggestionsCount,
+                    EditorField::SuggestionsCount => EditorField::YesButton,
                     EditorField::YesButton => EditorField::CancelButton,
                     EditorField::CancelButton => EditorField::Name,
                 };
@@ -282,7 +556,9 @@ impl View for ShortcutEditor {
                 self.selected_field = match self.selected_field {
                     EditorField::Name => EditorField::CancelButton,
                     EditorField::Description => EditorField::Name,
-                    EditorField::YesButton => EditorField::Description,
+                    EditorField::SuggestionsDepth => EditorField::Description,
+                    EditorField::SuggestionsCount => EditorField::SuggestionsDepth,
+                    EditorField::YesButton => EditorField::SuggestionsCount,
                     EditorField::CancelButton => EditorField::YesButton,
                 };
                 redraw = true;
@@ -304,6 +580,14 @@ impl View for ShortcutEditor {
             KeyCode::Enter => {
                 // Handle button press
                 if self.selected_field != EditorField::CancelButton {
+                    if let Some(other) = self.conflicting_shortcut() {
+                        // Ask how to resolve the conflict before saving
+                        self.conflict = Some(Conflict {
+                            shortcut: other,
+                            selected: Resolution::Rename,
+                        });
+                        return (EventCaptured::Yes, ManagerAction::new(true));
+                    }
                     self.save_shortcut();
                 }
                 // Close regardless of Yes or Cancel
@@ -325,6 +609,16 @@ impl View for ShortcutEditor {
                             textarea.input(Input::from(key_event));
                         }
                     }
+                    EditorField::SuggestionsDepth => {
+                        if let Some(textarea) = self.depth_textarea.as_mut() {
+                            textarea.input(Input::from(key_event));
+                        }
+                    }
+                    EditorField::SuggestionsCount => {
+                        if let Some(textarea) = self.count_textarea.as_mut() {
+                            textarea.input(Input::from(key_event));
+                        }
+                    }
                     _ => {}
                 }
                 redraw = true;
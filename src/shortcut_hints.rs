@@ -0,0 +1,67 @@
+use crate::{open_with::shell_quote, store::ShortcutHints};
+
+#[cfg(test)]
+#[path = "shortcut_hints_tests.rs"]
+mod shortcut_hints_tests;
+
+/// Whether `name` is a valid environment variable name: letters, digits and underscores,
+/// not starting with a digit.
+fn is_valid_env_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Parse `NAME=value` definitions of environment variables.
+///
+/// ### Returns
+/// The (name, value) pairs, or an error message naming the first invalid definition.
+pub fn parse_env(definitions: &[String]) -> Result<Vec<(String, String)>, String> {
+    definitions
+        .iter()
+        .map(|definition| match definition.split_once('=') {
+            Some((name, value)) if is_valid_env_name(name) && !value.contains('\n') => {
+                Ok((name.to_string(), value.to_string()))
+            }
+            _ => Err(format!(
+                "Invalid environment definition '{}', expected NAME=value",
+                definition
+            )),
+        })
+        .collect()
+}
+
+/// Build the shell script changing the directory to `path` then applying the hints of the
+/// shortcut `name`, to be evaluated by the shell wrapper.
+/// When `confirm` is set, the hints are only applied once the user answered `y`.
+pub fn shell_script(path: &str, name: &str, hints: &ShortcutHints, confirm: bool) -> String {
+    let mut script = format!("cd {}\n", shell_quote(path));
+    if hints.is_empty() {
+        return script;
+    }
+
+    let mut commands: Vec<String> = hints
+        .env
+        .iter()
+        .map(|(name, value)| format!("export {}={}", name, shell_quote(value)))
+        .collect();
+    if let Some(post_cd) = &hints.post_cd {
+        commands.push(post_cd.clone());
+    }
+
+    if confirm {
+        let question = format!("Apply the environment of shortcut {}? [y/N] ", name);
+        script.push_str(&format!("printf '%s' {}\n", shell_quote(&question)));
+        script.push_str("read -r REPLY\n");
+        script.push_str("if [ \"$REPLY\" = y ]; then\n");
+        commands
+            .iter()
+            .for_each(|command| script.push_str(&format!("  {}\n", command)));
+        script.push_str("fi\n");
+    } else {
+        commands
+            .iter()
+            .for_each(|command| script.push_str(&format!("{}\n", command)));
+    }
+    script
+}
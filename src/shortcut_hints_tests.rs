@@ -0,0 +1,59 @@
+use super::*;
+
+#[test]
+fn test_parse_env() {
+    let env = parse_env(&[
+        String::from("VIRTUAL_ENV=/home/me/venv"),
+        String::from("_EMPTY="),
+        String::from("OPTS=a=b"),
+    ])
+    .unwrap();
+    assert_eq!(
+        env,
+        vec![
+            (String::from("VIRTUAL_ENV"), String::from("/home/me/venv")),
+            (String::from("_EMPTY"), String::new()),
+            (String::from("OPTS"), String::from("a=b")),
+        ]
+    );
+
+    assert!(parse_env(&[String::from("NOVALUE")]).is_err());
+    assert!(parse_env(&[String::from("1ABC=x")]).is_err());
+    assert!(parse_env(&[String::from("A B=x")]).is_err());
+    assert!(parse_env(&[String::from("=x")]).is_err());
+}
+
+#[test]
+fn test_shell_script_without_hints() {
+    let script = shell_script("/tmp/it's", "tmp", &ShortcutHints::default(), true);
+    assert_eq!(script, "cd '/tmp/it'\\''s'\n");
+}
+
+#[test]
+fn test_shell_script() {
+    let hints = ShortcutHints {
+        env: vec![(String::from("NAME"), String::from("a b"))],
+        post_cd: Some(String::from("source venv/bin/activate")),
+    };
+    let script = shell_script("/work", "work", &hints, false);
+    assert_eq!(
+        script,
+        "cd '/work'\nexport NAME='a b'\nsource venv/bin/activate\n"
+    );
+}
+
+#[test]
+fn test_shell_script_with_confirmation() {
+    let hints = ShortcutHints {
+        env: vec![(String::from("NAME"), String::from("value"))],
+        post_cd: None,
+    };
+    let script = shell_script("/work", "work", &hints, true);
+    assert_eq!(
+        script,
+        "cd '/work'\n\
+         printf '%s' 'Apply the environment of shortcut work? [y/N] '\n\
+         read -r REPLY\n\
+         if [ \"$REPLY\" = y ]; then\n  export NAME='value'\nfi\n"
+    );
+}
@@ -0,0 +1,86 @@
+use std::collections::HashSet;
+
+use crate::{config::Config, store::Store};
+
+#[cfg(test)]
+#[path = "shortcut_suggestions_tests.rs"]
+mod shortcut_suggestions_tests;
+
+/// A path visited often, proposed as a shortcut
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShortcutSuggestion {
+    pub name: String,
+    pub path: String,
+    pub visits: usize,
+}
+
+/// Make a shortcut name of a path segment: lower case, the characters other than letters,
+/// digits, '-' and '_' becoming '-'.
+fn slug(segment: &str) -> String {
+    segment
+        .trim_start_matches('.')
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+/// Derive a shortcut name from the trailing segments of a path, adding the parent segments
+/// until the name is not taken, then a number.
+pub fn derive_name(path: &str, taken: &HashSet<String>) -> String {
+    let segments: Vec<String> = path
+        .rsplit('/')
+        .map(slug)
+        .filter(|segment| !segment.is_empty())
+        .collect();
+    let mut name = String::new();
+    for segment in &segments {
+        name = if name.is_empty() {
+            segment.clone()
+        } else {
+            format!("{}-{}", segment, name)
+        };
+        if !taken.contains(&name) {
+            return name;
+        }
+    }
+    if name.is_empty() {
+        name = String::from("dir");
+    }
+    (2..)
+        .map(|n| format!("{}-{}", name, n))
+        .find(|numbered| !taken.contains(numbered))
+        .unwrap()
+}
+
+/// The number of components of a path, e.g. 3 for /home/me/work
+fn depth(path: &str) -> usize { path.split('/').filter(|s| !s.is_empty()).count() }
+
+/// Suggest shortcuts for the deep paths visited often which are not the path of a shortcut,
+/// the most visited first.
+///
+/// ### Returns
+/// The suggestions, their names being unique and not used by the existing shortcuts, if the
+/// operation was successful, otherwise an error.
+pub fn suggest(store: &Store, config: &Config) -> Result<Vec<ShortcutSuggestion>, rusqlite::Error> {
+    let mut taken: HashSet<String> = store
+        .list_all_shortcuts()?
+        .into_iter()
+        .map(|shortcut| shortcut.name)
+        .collect();
+    Ok(store
+        .list_frequent_paths_without_shortcut(config.shortcut_suggestions_min_visits)?
+        .into_iter()
+        .filter(|(path, _)| depth(path) >= config.shortcut_suggestions_min_depth)
+        .map(|(path, visits)| {
+            let name = derive_name(&path, &taken);
+            taken.insert(name.clone());
+            ShortcutSuggestion { name, path, visits }
+        })
+        .collect())
+}
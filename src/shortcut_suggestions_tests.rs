@@ -0,0 +1,61 @@
+use super::*;
+
+#[test]
+fn test_derive_name() {
+    let mut taken = HashSet::new();
+    assert_eq!(
+        derive_name("/home/me/work/API Server", &taken),
+        "api-server"
+    );
+    assert_eq!(derive_name("/home/me/.config/", &taken), "config");
+
+    taken.insert(String::from("api"));
+    assert_eq!(derive_name("/home/me/work/api", &taken), "work-api");
+    taken.insert(String::from("work-api"));
+    taken.insert(String::from("me-work-api"));
+    taken.insert(String::from("home-me-work-api"));
+    assert_eq!(
+        derive_name("/home/me/work/api", &taken),
+        "home-me-work-api-2"
+    );
+}
+
+#[test]
+fn test_suggest() {
+    let store = Store::setup_test_store();
+    let config = Config {
+        shortcut_suggestions_min_visits: 2,
+        ..Default::default()
+    };
+    for (path, visits) in [
+        ("/home/me/work/api", 3),
+        ("/home/me/old/api", 2),
+        ("/home/me", 5),
+        ("/home/me/work/web", 1),
+        ("/home/me/work/docs", 4),
+    ] {
+        for _ in 0..visits {
+            store.add_path(path).unwrap();
+        }
+    }
+    store
+        .upsert_shortcut("docs", "/home/me/work/docs", None)
+        .unwrap();
+
+    let suggestions = suggest(&store, &config).unwrap();
+    assert_eq!(
+        suggestions,
+        vec![
+            ShortcutSuggestion {
+                name: String::from("api"),
+                path: String::from("/home/me/work/api"),
+                visits: 3
+            },
+            ShortcutSuggestion {
+                name: String::from("old-api"),
+                path: String::from("/home/me/old/api"),
+                visits: 2
+            },
+        ]
+    );
+}
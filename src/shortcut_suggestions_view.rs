@@ -0,0 +1,175 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+use tracing::{debug, error, info};
+
+use crate::{
+    shortcut_suggestions::ShortcutSuggestion,
+    store::Store,
+    theme::ThemeStyles,
+    tui::{EventCaptured, ManagerAction, View, ViewBuilder, ViewManager},
+};
+
+#[cfg(test)]
+#[path = "shortcut_suggestions_view_tests.rs"]
+mod shortcut_suggestions_view_tests;
+
+/// A modal listing the shortcuts suggested for the directories visited often, each one being
+/// accepted with enter, or all of them with ctrl+a.
+pub struct ShortcutSuggestionsView {
+    styles: ThemeStyles,
+    store: Store,
+    /// The suggestions not accepted yet, the most visited first
+    suggestions: Vec<ShortcutSuggestion>,
+    selected: usize,
+    /// The number of shortcuts added
+    accepted: usize,
+}
+
+impl ShortcutSuggestionsView {
+    pub fn builder(
+        suggestions: Vec<ShortcutSuggestion>,
+        store: Store,
+        styles: ThemeStyles,
+    ) -> ViewBuilder {
+        ViewBuilder::from(Box::new(Self::new(suggestions, store, styles)))
+    }
+
+    fn new(suggestions: Vec<ShortcutSuggestion>, store: Store, styles: ThemeStyles) -> Self {
+        Self {
+            styles,
+            store,
+            suggestions,
+            selected: 0,
+            accepted: 0,
+        }
+    }
+
+    /// The number of shortcuts added
+    pub fn accepted(&self) -> usize { self.accepted }
+
+    /// Add the shortcut of the suggestion at `idx`, removing it from the list
+    fn accept(&mut self, idx: usize) {
+        let suggestion = self.suggestions.remove(idx);
+        match self
+            .store
+            .upsert_shortcut(&suggestion.name, &suggestion.path, None)
+        {
+            Ok(()) => {
+                info!(
+                    "shortcut {} added for '{}'",
+                    suggestion.name, suggestion.path
+                );
+                self.accepted += 1;
+            }
+            Err(e) => error!("Failed to add the shortcut {}: {}", suggestion.name, e),
+        }
+        self.selected = self.selected.min(self.suggestions.len().saturating_sub(1));
+    }
+}
+
+impl View for ShortcutSuggestionsView {
+    fn draw(&mut self, frame: &mut ratatui::Frame, _area: Rect, _active: bool) {
+        debug!("Drawing the shortcut suggestions");
+        let area = frame.area();
+        // the borders take 2 lines
+        let height = (self.suggestions.len() as u16 + 2)
+            .clamp(3, 20)
+            .min(area.height);
+        let width = 80.min(area.width);
+        let modal_area = ViewManager::centered_rect(area, width, height);
+
+        frame.render_widget(Clear, modal_area);
+        if let Some(bg_color) = &self.styles.background_color {
+            let background = Paragraph::new("").style(Style::default().bg(*bg_color));
+            frame.render_widget(background, modal_area);
+        }
+
+        let name_width = self
+            .suggestions
+            .iter()
+            .map(|suggestion| suggestion.name.chars().count())
+            .max()
+            .unwrap_or_default();
+        let visible = height.saturating_sub(2) as usize;
+        let first = self.selected.saturating_sub(visible.saturating_sub(1));
+        let mut lines = vec![];
+        if self.suggestions.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "no directory to suggest",
+                self.styles.date_style,
+            )));
+        }
+        for (row, suggestion) in self
+            .suggestions
+            .iter()
+            .enumerate()
+            .skip(first)
+            .take(visible)
+        {
+            let mut style = self.styles.path_style;
+            if row == self.selected {
+                style = self.styles.highlight(style);
+            }
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("{:width$} ", suggestion.name, width = name_width),
+                    self.styles.shortcut_name_style,
+                ),
+                Span::styled(suggestion.path.clone(), style),
+                Span::styled(
+                    format!(" ({} visits)", suggestion.visits),
+                    self.styles.date_style,
+                ),
+            ]));
+        }
+
+        let mut block = Block::default()
+            .title(Span::styled(
+                " Suggested shortcuts ",
+                self.styles.title_style,
+            ))
+            .title_bottom(Span::styled(
+                " enter: add, ctrl+a: add all ",
+                self.styles.date_style,
+            ))
+            .borders(Borders::ALL);
+        if let Some(border_color) = self.styles.border_color {
+            block = block.border_style(Style::default().fg(border_color));
+        }
+        frame.render_widget(Paragraph::new(lines).block(block), modal_area);
+    }
+
+    fn handle_key_event(&mut self, key_event: KeyEvent) -> (EventCaptured, ManagerAction) {
+        match key_event.code {
+            KeyCode::Enter if !self.suggestions.is_empty() => {
+                self.accept(self.selected);
+                if self.suggestions.is_empty() {
+                    return (
+                        EventCaptured::Yes,
+                        ManagerAction::new(false).with_close(true),
+                    );
+                }
+            }
+            KeyCode::Char('a') if key_event.modifiers == KeyModifiers::CONTROL => {
+                while !self.suggestions.is_empty() {
+                    self.accept(0);
+                }
+                return (
+                    EventCaptured::Yes,
+                    ManagerAction::new(false).with_close(true),
+                );
+            }
+            KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down => {
+                self.selected = (self.selected + 1).min(self.suggestions.len().saturating_sub(1))
+            }
+            _ => return (EventCaptured::Yes, ManagerAction::new(false)),
+        }
+        (EventCaptured::Yes, ManagerAction::new(true))
+    }
+}
@@ -0,0 +1,56 @@
+use super::*;
+
+fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent { KeyEvent::new(code, modifiers) }
+
+fn suggestion(name: &str, path: &str) -> ShortcutSuggestion {
+    ShortcutSuggestion {
+        name: name.to_string(),
+        path: path.to_string(),
+        visits: 10,
+    }
+}
+
+fn view(store: &Store) -> ShortcutSuggestionsView {
+    ShortcutSuggestionsView::new(
+        vec![
+            suggestion("api", "/home/me/work/api"),
+            suggestion("web", "/home/me/work/web"),
+            suggestion("docs", "/home/me/work/docs"),
+        ],
+        store.clone(),
+        ThemeStyles::default(),
+    )
+}
+
+#[test]
+fn test_accept_one_by_one() {
+    let store = Store::setup_test_store();
+    let mut view = view(&store);
+
+    view.handle_key_event(key(KeyCode::Down, KeyModifiers::NONE));
+    let (_, action) = view.handle_key_event(key(KeyCode::Enter, KeyModifiers::NONE));
+    assert!(!action.close());
+    assert_eq!(view.accepted(), 1);
+    assert_eq!(
+        store.find_shortcut("web").unwrap().path,
+        "/home/me/work/web"
+    );
+    assert_eq!(view.suggestions.len(), 2);
+    assert_eq!(view.suggestions[view.selected].name, "docs");
+
+    view.handle_key_event(key(KeyCode::Enter, KeyModifiers::NONE));
+    let (_, action) = view.handle_key_event(key(KeyCode::Enter, KeyModifiers::NONE));
+    assert!(action.close());
+    assert_eq!(view.accepted(), 3);
+}
+
+#[test]
+fn test_accept_all() {
+    let store = Store::setup_test_store();
+    let mut view = view(&store);
+
+    let (_, action) = view.handle_key_event(key(KeyCode::Char('a'), KeyModifiers::CONTROL));
+    assert!(action.close());
+    assert_eq!(view.accepted(), 3);
+    assert_eq!(store.list_all_shortcuts().unwrap().len(), 3);
+}
@@ -4,21 +4,28 @@ use std::{
 };
 
 use crossterm::event::KeyEvent;
-use log::debug;
 use ratatui::layout::{Constraint, Layout, Rect};
+use tracing::{debug, error};
 
 use crate::{
     config::Config,
     config_button::ConfigButton,
-    list_indicator_view::ListIndicatorView,
-    model::ListFunction,
+    gui::Gui,
+    keybindings::{self, Action, Scope},
+    list_indicator_view::{ListIndicatorView, publish_status_message},
+    model::{CountFunction, ListFunction},
     search_text_view::{SearchTextState, SearchTextView},
-    store::Shortcut,
+    shortcut_suggestions,
+    shortcut_suggestions_view::ShortcutSuggestionsView,
+    store::{Shortcut, SortColumn, Store},
     tableview::{
-        ColumnConstraintsFn, ColumnNamesFn, DeleteFn, EditorViewBuilder, RowifyFn, TableView,
-        TableViewState,
+        ColumnConstraintsFn, ColumnNamesFn, DeleteFn, DetailsFn, EditorViewBuilder, RowifyFn,
+        TableView, TableViewParams, TableViewState,
+    },
+    tui::{
+        EventCaptured, GenericEvent, ManagerAction, View, ViewBuilder, ViewManager,
+        event::ApplicationEvent,
     },
-    tui::{EventCaptured, ManagerAction, View, ViewBuilder, ViewManager},
 };
 
 const SHORTCUT_VIEW_ID: u16 = 0;
@@ -26,53 +33,143 @@ const SEARCH_TEXT_VIEW_1: u16 = 1;
 const CONFIGURATION_VIEW: u16 = 2;
 const LIST_INDICATOR_VIEW: u16 = 3;
 
-pub struct ShortcutViewContainer {}
+/// The parts of the shortcuts view, given to `ShortcutViewContainer::builder`, most of
+/// them making its table
+pub struct ShortcutViewParams {
+    pub column_names_fn: ColumnNamesFn,
+    pub column_constraints_fn: ColumnConstraintsFn,
+    pub list_fn: Box<ListFunction<Shortcut>>,
+    pub count_fn: Box<CountFunction>,
+    pub rowify: RowifyFn<Shortcut>,
+    pub stringify: fn(&Shortcut) -> String,
+    pub config: Arc<Mutex<Config>>,
+    pub view_state: Arc<Mutex<TableViewState>>,
+    pub delete_fn: DeleteFn<Shortcut>,
+    pub editor_modal_view_builder: Option<EditorViewBuilder<Shortcut>>,
+    pub relocate_modal_view_builder: Option<EditorViewBuilder<Shortcut>>,
+    pub details_fn: DetailsFn<Shortcut>,
+    /// The state of the search of the view
+    pub search_text_state: Arc<Mutex<SearchTextState>>,
+    pub store: Store,
+}
+
+pub struct ShortcutViewContainer {
+    vm: Rc<ViewManager>,
+    config: Arc<Mutex<Config>>,
+    store: Store,
+    view_state: Arc<Mutex<TableViewState>>,
+}
 
 impl ShortcutViewContainer {
-    pub fn builder(
-        vm: Rc<ViewManager>,
-        column_names_fn: ColumnNamesFn,
-        column_constraints_fn: ColumnConstraintsFn,
-        list_fn: Box<ListFunction<Shortcut>>,
-        rowify: RowifyFn<Shortcut>,
-        stringify: fn(&Shortcut) -> String,
-        config: Arc<Mutex<Config>>,
-        view_state: Arc<Mutex<TableViewState>>,
-        delete_fn: DeleteFn<Shortcut>,
-        editor_modal_view_builder: Option<EditorViewBuilder<Shortcut>>,
-        search_text_state: Arc<Mutex<SearchTextState>>,
-    ) -> ViewBuilder {
-        ViewBuilder::from(Box::new(Self {}))
-            .child(
-                SHORTCUT_VIEW_ID,
-                TableView::builder(
-                    vm.clone(),
-                    "shortcut".to_string(),
+    /// Create a ViewBuilder for the shortcuts view, made of `params`.
+    pub fn builder(vm: Rc<ViewManager>, params: ShortcutViewParams) -> ViewBuilder {
+        let ShortcutViewParams {
+            column_names_fn,
+            column_constraints_fn,
+            list_fn,
+            count_fn,
+            rowify,
+            stringify,
+            config,
+            view_state,
+            delete_fn,
+            editor_modal_view_builder,
+            relocate_modal_view_builder,
+            details_fn,
+            search_text_state,
+            store,
+        } = params;
+        ViewBuilder::from(Box::new(Self {
+            vm: vm.clone(),
+            config: config.clone(),
+            store: store.clone(),
+            view_state: view_state.clone(),
+        }))
+        .child(
+            SHORTCUT_VIEW_ID,
+            TableView::builder(
+                vm.clone(),
+                TableViewParams {
+                    objects_type: "shortcut".to_string(),
                     column_names_fn,
                     column_constraints_fn,
                     list_fn,
+                    count_fn,
                     rowify,
                     stringify,
-                    config.clone(),
+                    config: config.clone(),
                     view_state,
                     delete_fn,
+                    restore_fn: None,
                     editor_modal_view_builder,
-                    Box::new(|_| 0),
-                )
-                .with_publish_events(true),
-            )
-            .child(
-                SEARCH_TEXT_VIEW_1,
-                SearchTextView::builder(config.clone(), search_text_state.clone()),
-            )
-            .child(
-                CONFIGURATION_VIEW,
-                ConfigButton::builder(vm.clone(), config.clone()),
+                    relocate_modal_view_builder,
+                    find_focus_fn: Box::new(|_| 0),
+                    details_fn,
+                    shortcut_name_fn: Box::new(|shortcut: &Shortcut| Some(shortcut.name.clone())),
+                    sort_columns: vec![("shortcut", SortColumn::Name), ("path", SortColumn::Path)],
+                    quick_select: false,
+                    section_fn: None,
+                    subtitle_fn: None,
+                    popover_fn: None,
+                    warm_start: None,
+                },
             )
-            .child(
-                LIST_INDICATOR_VIEW,
-                ListIndicatorView::builder(vm.clone(), config.clone(), "shortcut".to_string()),
+            .with_publish_events(true),
+        )
+        .child(
+            SEARCH_TEXT_VIEW_1,
+            SearchTextView::builder(
+                vm.clone(),
+                config.clone(),
+                search_text_state.clone(),
+                store.clone(),
+                "shortcut".to_string(),
+            ),
+        )
+        .child(
+            CONFIGURATION_VIEW,
+            ConfigButton::builder(vm.clone(), config.clone()),
+        )
+        .child(
+            LIST_INDICATOR_VIEW,
+            ListIndicatorView::builder(vm.clone(), config.clone(), "shortcut".to_string()),
+        )
+    }
+}
+
+impl ShortcutViewContainer {
+    /// Show the shortcuts suggested for the directories visited often
+    fn show_suggestions(&self) {
+        let (suggestions, styles) = {
+            let config_lock = self.config.lock().unwrap();
+            (
+                shortcut_suggestions::suggest(&self.store, &config_lock),
+                config_lock.styles.clone(),
             )
+        };
+        match suggestions {
+            Ok(suggestions) => {
+                let vb = ShortcutSuggestionsView::builder(suggestions, self.store.clone(), styles);
+                self.vm.show_modal(vb, Some(Self::suggestions_callback));
+            }
+            Err(e) => self
+                .vm
+                .notify(Gui::failure_notification("suggest shortcuts", &e)),
+        }
+    }
+
+    fn suggestions_callback(&mut self, view: &ShortcutSuggestionsView) -> ManagerAction {
+        if view.accepted() > 0 {
+            publish_status_message(&self.vm, &format!("{} added", view.accepted()));
+            let event = GenericEvent::ApplicationEvent(ApplicationEvent {
+                id: String::from("data.reload"),
+                payload: None,
+            });
+            if let Err(e) = self.vm.tx().send(event) {
+                error!("Failed to send 'data.reload' event: {}", e);
+            }
+        }
+        ManagerAction::new(true)
     }
 }
 
@@ -105,8 +202,15 @@ impl View for ShortcutViewContainer {
     }
 
     fn handle_key_event(&mut self, key_event: KeyEvent) -> (EventCaptured, ManagerAction) {
-        let _ = key_event;
         debug!("handle_key_event");
+        if keybindings::action(Scope::Shortcuts, &key_event) == Some(Action::SuggestShortcuts) {
+            if self.view_state.lock().unwrap().select_only {
+                publish_status_message(&self.vm, "select only");
+            } else {
+                self.show_suggestions();
+            }
+            return (EventCaptured::Yes, ManagerAction::new(true));
+        }
         (EventCaptured::No, ManagerAction::new(false))
     }
 }
@@ -0,0 +1,60 @@
+use chrono::{Local, NaiveDate, TimeZone};
+
+use crate::store::{Shortcut, Store};
+
+#[cfg(test)]
+#[path = "stats_tests.rs"]
+mod stats_tests;
+
+/// The usage of cdir over a period, aggregated from the history and the shortcuts
+#[derive(Debug, Clone, Default)]
+pub struct UsageStats {
+    /// The start of the period, in seconds since EPOCH
+    pub since: i64,
+    /// The number of visits of the period
+    pub visits: usize,
+    /// The paths visited the most and their number of visits, the most visited first
+    pub top_paths: Vec<(String, usize)>,
+    /// The number of visits of each local day with visits, the oldest day first
+    pub daily_visits: Vec<(NaiveDate, usize)>,
+    /// The paths visited for the first time and the date of this visit, the oldest first
+    pub discovered_paths: Vec<(String, i64)>,
+    /// The shortcuts created and their creation date, the oldest first
+    pub created_shortcuts: Vec<(Shortcut, i64)>,
+}
+
+/// Count the visits of `dates` (in seconds since EPOCH, sorted) by local day.
+pub fn count_by_day(dates: &[i64]) -> Vec<(NaiveDate, usize)> {
+    let mut days: Vec<(NaiveDate, usize)> = vec![];
+    for date in dates {
+        let Some(day) = Local
+            .timestamp_opt(*date, 0)
+            .single()
+            .map(|date| date.date_naive())
+        else {
+            continue;
+        };
+        match days.last_mut() {
+            Some((last, count)) if *last == day => *count += 1,
+            _ => days.push((day, 1)),
+        }
+    }
+    days
+}
+
+/// Aggregate the usage since `since` (in seconds since EPOCH), keeping the `top` paths
+/// visited the most. The sensitive paths are left out.
+///
+/// ### Returns
+/// The statistics of the period if the operation was successful, otherwise an error.
+pub fn collect(store: &Store, since: i64, top: usize) -> Result<UsageStats, rusqlite::Error> {
+    let dates = store.list_visit_dates_since(since)?;
+    Ok(UsageStats {
+        since,
+        visits: dates.len(),
+        top_paths: store.list_top_paths_since(since, top)?,
+        daily_visits: count_by_day(&dates),
+        discovered_paths: store.list_paths_discovered_since(since)?,
+        created_shortcuts: store.list_shortcuts_created_since(since)?,
+    })
+}
@@ -0,0 +1,37 @@
+use super::*;
+
+#[test]
+fn test_count_by_day() {
+    let noon = Local
+        .with_ymd_and_hms(2026, 10, 14, 12, 0, 0)
+        .unwrap()
+        .timestamp();
+    let day = 24 * 3600;
+    assert_eq!(
+        count_by_day(&[noon, noon + 60, noon + day, noon + 3 * day]),
+        vec![
+            (NaiveDate::from_ymd_opt(2026, 10, 14).unwrap(), 2),
+            (NaiveDate::from_ymd_opt(2026, 10, 15).unwrap(), 1),
+            (NaiveDate::from_ymd_opt(2026, 10, 17).unwrap(), 1),
+        ]
+    );
+    assert!(count_by_day(&[]).is_empty());
+}
+
+#[test]
+fn test_collect() {
+    let store = Store::setup_test_store();
+    for (path, date) in [("/old", 100), ("/work", 1000), ("/work", 1100)] {
+        store.add_path_with_time(path, date).unwrap();
+    }
+    store.upsert_shortcut("w", "/work", None).unwrap();
+
+    let stats = collect(&store, 1000, 5).unwrap();
+    assert_eq!(stats.since, 1000);
+    assert_eq!(stats.visits, 2);
+    assert_eq!(stats.top_paths, vec![(String::from("/work"), 2)]);
+    assert_eq!(stats.daily_visits.iter().map(|(_, n)| n).sum::<usize>(), 2);
+    assert_eq!(stats.discovered_paths, vec![(String::from("/work"), 1000)]);
+    assert_eq!(stats.created_shortcuts.len(), 1);
+    assert_eq!(stats.created_shortcuts[0].0.name, "w");
+}
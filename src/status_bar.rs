@@ -0,0 +1,251 @@
+use std::{
+    collections::HashMap,
+    rc::Rc,
+    sync::{Arc, Mutex},
+};
+
+use ratatui::{
+    layout::{Alignment, Rect},
+    widgets::Paragraph,
+};
+use tracing::{debug, error};
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    config::Config,
+    gui::Gui,
+    keybindings::{self, Action},
+    model::DataStatePayload,
+    search_text_view::SearchDescriptionPayload,
+    tui::{GenericEvent, View, ViewBuilder, ViewManager, event::ApplicationEvent},
+};
+
+#[cfg(test)]
+#[path = "status_bar_tests.rs"]
+mod status_bar_tests;
+
+/// The id of the application event setting a segment of the status bar, its payload being a
+/// `StatusSegmentPayload`
+pub(crate) const STATUS_SEGMENT_EVENT: &str = "status.segment";
+
+/// The separator of the segments of the status bar
+const SEGMENT_SEPARATOR: &str = " \u{2502} ";
+
+/// The separator of the key hints
+const HINT_SEPARATOR: &str = "  ";
+
+// "status.segment"
+pub(crate) struct StatusSegmentPayload {
+    /// The objects type of the view the segment is shown with
+    pub(crate) objects_type: String,
+    pub(crate) name: String,
+    /// The text of the segment, None removing it
+    pub(crate) text: Option<String>,
+}
+
+/// Set a segment of the status bar shown with the view of `objects_type`, or remove it.
+pub(crate) fn publish_status_segment(
+    vm: &ViewManager,
+    objects_type: &str,
+    name: &str,
+    text: Option<String>,
+) {
+    let event = GenericEvent::ApplicationEvent(ApplicationEvent {
+        id: String::from(STATUS_SEGMENT_EVENT),
+        payload: Some(Arc::new(StatusSegmentPayload {
+            objects_type: String::from(objects_type),
+            name: String::from(name),
+            text,
+        })),
+    });
+    if let Err(e) = vm.tx().send(event) {
+        error!("Failed to send '{}' event: {}", STATUS_SEGMENT_EVENT, e);
+    }
+}
+
+/// A top level view, as described by the status bar
+pub(crate) struct StatusBarView {
+    /// The objects type of the view
+    pub(crate) objects_type: String,
+    /// The actions whose keys are hinted while the view is displayed
+    pub(crate) hints: &'static [(Action, &'static str)],
+}
+
+pub(crate) struct StatusBarState {
+    /// The top level views, in the order they were added to the view manager
+    views: Vec<StatusBarView>,
+    fuzzy_match: bool,
+    /// The number of frozen searches the results are narrowed by
+    frozen_filters: usize,
+    /// The number of entries matching the search, by objects type
+    totals: HashMap<String, usize>,
+    /// The segments published by the views as (objects type, name, text), in the order they
+    /// were first published
+    segments: Vec<(String, String, String)>,
+    db_path: Option<String>,
+}
+
+impl StatusBarState {
+    pub(crate) fn new(
+        views: Vec<StatusBarView>,
+        fuzzy_match: bool,
+        db_path: Option<String>,
+    ) -> StatusBarState {
+        StatusBarState {
+            views,
+            fuzzy_match,
+            frozen_filters: 0,
+            totals: HashMap::new(),
+            segments: vec![],
+            db_path,
+        }
+    }
+
+    /// Set the segment `name` of the view of `objects_type`, or remove it.
+    pub(crate) fn set_segment(&mut self, objects_type: &str, name: &str, text: Option<String>) {
+        let position = self
+            .segments
+            .iter()
+            .position(|(o, n, _)| o == objects_type && n == name);
+        match (position, text) {
+            (Some(idx), Some(text)) => self.segments[idx].2 = text,
+            (Some(idx), None) => {
+                self.segments.remove(idx);
+            }
+            (None, Some(text)) => {
+                self.segments
+                    .push((objects_type.to_string(), name.to_string(), text))
+            }
+            (None, None) => {}
+        }
+    }
+
+    /// The segments shown with the view at `view_idx`: the search mode, the filters and the
+    /// number of results
+    fn segments(&self, view_idx: usize) -> Vec<String> {
+        let mut segments = vec![String::from(if self.fuzzy_match {
+            "fuzzy"
+        } else {
+            "exact"
+        })];
+        let Some(view) = self.views.get(view_idx) else {
+            return segments;
+        };
+        if self.frozen_filters > 0 {
+            segments.push(format!("narrowed \u{00d7}{}", self.frozen_filters));
+        }
+        segments.extend(
+            self.segments
+                .iter()
+                .filter(|(objects_type, _, _)| *objects_type == view.objects_type)
+                .map(|(_, _, text)| text.clone()),
+        );
+        if let Some(total) = self.totals.get(&view.objects_type) {
+            segments.push(match total {
+                1 => String::from("1 result"),
+                _ => format!("{} results", total),
+            });
+        }
+        segments
+    }
+
+    /// The keys of the main actions of the view at `view_idx`, e.g. `enter choose  ctrl+h help`
+    fn hints(&self, view_idx: usize) -> String {
+        self.views
+            .get(view_idx)
+            .map(|view| view.hints)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|(action, label)| {
+                keybindings::key_label(*action).map(|key| format!("{} {}", key, label))
+            })
+            .collect::<Vec<_>>()
+            .join(HINT_SEPARATOR)
+    }
+
+    /// The text of the left part of the status bar for the view at `view_idx`, the key hints
+    /// being dropped when the text does not fit within `width`
+    pub(crate) fn left_text(&self, view_idx: usize, width: usize) -> String {
+        let segments = format!(" {}", self.segments(view_idx).join(SEGMENT_SEPARATOR));
+        let hints = self.hints(view_idx);
+        let text = format!("{}{}{}", segments, SEGMENT_SEPARATOR, hints);
+        if hints.is_empty() || text.width() > width {
+            segments
+        } else {
+            text
+        }
+    }
+}
+
+/// The last row of the GUI: the search mode, the filters and the number of results of the
+/// displayed view, the keys of its main actions and the path of the database.
+pub(crate) struct StatusBar {
+    vm: Rc<ViewManager>,
+    config: Arc<Mutex<Config>>,
+    state: StatusBarState,
+}
+
+impl StatusBar {
+    pub(crate) fn builder(
+        vm: Rc<ViewManager>,
+        config: Arc<Mutex<Config>>,
+        state: StatusBarState,
+    ) -> ViewBuilder {
+        ViewBuilder::from(Box::new(StatusBar { vm, config, state })).with_publish_events(true)
+    }
+}
+
+impl View for StatusBar {
+    fn draw(&mut self, frame: &mut ratatui::Frame, area: Rect, _active: bool) {
+        let config_lock = self.config.lock().unwrap();
+        let style = config_lock.styles.header_style();
+
+        let left = self
+            .state
+            .left_text(self.vm.top_level_view_idx(), area.width as usize);
+        frame.render_widget(Paragraph::new(left.clone()).style(style), area);
+
+        // the path of the database takes the room left, if any
+        let room = (area.width as usize).saturating_sub(left.width() + SEGMENT_SEPARATOR.width());
+        if let Some(db_path) = &self.state.db_path
+            && room > 1
+        {
+            let mut db_path = Gui::reduce_path(
+                db_path.clone(),
+                room as u16 - 1,
+                config_lock.styles.home_tilde_style,
+            );
+            db_path.push_span(" ");
+            let paragraph = Paragraph::new(db_path).alignment(Alignment::Right);
+            frame.render_widget(paragraph, area);
+        }
+    }
+
+    fn handle_application_event(&mut self, ae: &ApplicationEvent) {
+        if ae.id == "search.description"
+            && let Some(payload) = &ae.payload
+            && let Some(payload) = payload.downcast_ref::<SearchDescriptionPayload>()
+        {
+            self.state.fuzzy_match = payload.fuzzy_match;
+            self.state.frozen_filters = payload.frozen_filters.len();
+        } else if ae.id == "data.payload"
+            && let Some(payload) = &ae.payload
+            && let Some(payload) = payload.downcast_ref::<DataStatePayload>()
+            && let Some(total) = payload.total
+        {
+            self.state
+                .totals
+                .insert(payload.objects_type.clone(), total);
+        } else if ae.id == STATUS_SEGMENT_EVENT
+            && let Some(payload) = &ae.payload
+            && let Some(payload) = payload.downcast_ref::<StatusSegmentPayload>()
+        {
+            debug!(
+                "status segment {}/{}: {:?}",
+                payload.objects_type, payload.name, payload.text
+            );
+            self.state
+                .set_segment(&payload.objects_type, &payload.name, payload.text.clone());
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use super::*;
+
+fn state() -> StatusBarState {
+    StatusBarState::new(
+        vec![
+            StatusBarView {
+                objects_type: String::from("path"),
+                hints: &[(Action::Choose, "choose"), (Action::Delete, "delete")],
+            },
+            StatusBarView {
+                objects_type: String::from("shortcut"),
+                hints: &[],
+            },
+        ],
+        false,
+        Some(String::from("/tmp/cdir.db")),
+    )
+}
+
+#[test]
+fn test_segments() {
+    let mut state = state();
+    assert_eq!(state.segments(0), vec!["exact"]);
+
+    state.fuzzy_match = true;
+    state.frozen_filters = 2;
+    state.totals.insert(String::from("path"), 345);
+    state.totals.insert(String::from("shortcut"), 1);
+    state.set_segment("path", "filters", Some(String::from("trash")));
+    assert_eq!(
+        state.segments(0),
+        vec!["fuzzy", "narrowed \u{00d7}2", "trash", "345 results"]
+    );
+    // the segments of a view are not shown with the other ones
+    assert_eq!(
+        state.segments(1),
+        vec!["fuzzy", "narrowed \u{00d7}2", "1 result"]
+    );
+
+    state.set_segment("path", "filters", Some(String::from("this pane")));
+    assert_eq!(state.segments(0)[2], "this pane");
+    state.set_segment("path", "filters", None);
+    assert_eq!(state.segments(0).len(), 3);
+}
+
+#[test]
+fn test_left_text() {
+    let state = state();
+    let text = state.left_text(0, 80);
+    assert_eq!(text, " exact \u{2502} enter choose  ctrl+d delete");
+    // the hints are dropped when they do not fit
+    assert_eq!(state.left_text(0, 20), " exact");
+    // a view without hints
+    assert_eq!(state.left_text(1, 80), " exact");
+}
@@ -1,18 +1,29 @@
 use std::{
+    cell::{Cell, RefCell},
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
     fmt, fs,
     rc::Rc,
     sync::{Arc, Mutex},
-    time::{SystemTime, UNIX_EPOCH},
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
-use log::{debug, error, info, trace, warn};
-use nucleo_matcher::{
-    Matcher, Utf32Str,
-    pattern::{CaseMatching, Normalization, Pattern},
-};
-use rusqlite::{Connection, Result, params};
+use chrono::{Datelike, Local, TimeZone, Timelike};
+use rusqlite::{Connection, ErrorCode, Result, TransactionBehavior, params};
+use tracing::{debug, error, info, instrument, trace, warn};
 
-use crate::config::Config;
+use crate::{
+    config::{
+        CaseSensitivity, Config, DatabaseConfig, FuzzyConfig, JournalMode,
+        SmartSuggestionsStrategy, Synchronous,
+    },
+    container,
+    dir_size::DirSize,
+    matcher::{self, Matcher},
+    project_type::ProjectType,
+    remote::RemotePath,
+    shortcut_suggestions,
+};
 
 #[cfg(test)]
 #[path = "store_tests.rs"]
@@ -20,35 +31,67 @@ mod store_tests;
 
 // Update this when the database schema changes with the max value value of the sql
 // files in ../dbschema (e.g. if 1.sql is the latest, this should be 1)
-const CURRENT_SCHEMA_VERSION: i64 = 3;
+const CURRENT_SCHEMA_VERSION: i64 = 25;
+
+/// The schema version from which the names of the shortcuts are unique, see 17.sql
+const UNIQUE_SHORTCUT_NAMES_VERSION: i64 = 17;
+
+/// The number of prepared statements kept by the connection, enough for the queries of a
+/// GUI session
+const STATEMENT_CACHE_CAPACITY: usize = 64;
+
+/// The number of times a write is attempted while the database is locked
+const BUSY_RETRY_ATTEMPTS: u32 = 5;
+
+/// The delay before the first retry of a write, doubled at each retry
+const BUSY_RETRY_INITIAL_DELAY: Duration = Duration::from_millis(50);
 
 /// Represents a path entry in the database
 /// id: auto increment primary key
 /// path: the file path
 /// date: the timestamp when the path was added (in seconds since EPOCH)
 /// shortcut: the optional shortcut associated with this path
+/// hostname: the host the path was last visited from, if known
+/// remote: whether the path is a `[user@]host:/path` entry
 #[derive(Debug, Clone)]
-pub(crate) struct Path {
-    pub(crate) id: i64,
-    pub(crate) date: i64,
-    pub(crate) path: String,
-    pub(crate) shortcut: Option<Shortcut>,
-    pub(crate) smart_path: bool,
+pub struct Path {
+    pub id: i64,
+    pub date: i64,
+    pub path: String,
+    pub shortcut: Option<Shortcut>,
+    pub smart_path: bool,
+    pub hostname: Option<String>,
+    pub remote: bool,
 }
 
 impl Path {
+    /// Build a path entry, with the most specific of `shortcuts` containing it
     pub fn new(id: i64, path: String, date: i64, shortcuts: &[Shortcut]) -> Self {
+        let remote = RemotePath::parse(&path).is_some();
         let mut path = Path {
             id,
             path,
             date,
             shortcut: None,
             smart_path: false,
+            hostname: None,
+            remote,
         };
         path.assign_shortcut(shortcuts);
         path
     }
 
+    /// Whether the path is an entry of the history, rather than a smart suggestion or a
+    /// directory found on the disk
+    pub fn is_in_history(&self) -> bool { self.id != 0 && !self.smart_path }
+
+    /// Set the host the path was last visited from
+    pub fn with_hostname(mut self, hostname: Option<String>) -> Self {
+        self.hostname = hostname;
+        self
+    }
+
+    /// Assign the shortcut of the deepest directory containing the path, if any
     pub fn assign_shortcut(&mut self, shortcuts: &[Shortcut]) {
         for shortcut in shortcuts {
             if !Self::is_subpath(&shortcut.path, &self.path) {
@@ -64,6 +107,7 @@ impl Path {
         }
     }
 
+    /// Whether `sub_path` is `base_path` or one of its sub directories
     pub fn is_subpath(base_path: &str, sub_path: &str) -> bool {
         if !sub_path.starts_with(base_path) {
             return false;
@@ -79,12 +123,14 @@ impl Path {
 /// id: auto increment primary key
 /// name: the name of the shortcut
 /// path: the file path associated with the shortcut
+/// remote: whether the path is a `[user@]host:/path` entry
 #[derive(Debug, Clone)]
-pub(crate) struct Shortcut {
-    pub(crate) id: i64,
-    pub(crate) name: String,
-    pub(crate) path: String,
-    pub(crate) description: Option<String>,
+pub struct Shortcut {
+    pub id: i64,
+    pub name: String,
+    pub path: String,
+    pub description: Option<String>,
+    pub remote: bool,
 }
 
 impl fmt::Display for Shortcut {
@@ -97,10 +143,285 @@ impl fmt::Display for Shortcut {
     }
 }
 
+/// A file opened recently, recorded with `add_file`
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecentFile {
+    pub id: i64,
+    pub path: String,
+    /// When the file was last opened
+    pub date: i64,
+}
+
+/// A run of consecutive visits to the same path, from the paths_history table
+/// path: the path with the date and the id of its latest visit in the run
+/// count: the number of visits in the run, 1 unless the repeats are collapsed
+#[derive(Debug, Clone)]
+pub struct Visit {
+    pub path: Path,
+    pub count: usize,
+}
+
+/// The smart suggestions parameters of a directory and of its sub directories, the configured
+/// ones applying to the parameters not set
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SuggestionSettings {
+    /// The number of past visits looked at, see `smart_suggestions_depth`
+    pub depth: Option<usize>,
+    /// The number of suggestions, see `smart_suggestions_count`
+    pub count: Option<usize>,
+}
+
+/// A run of visits without a gap longer than the idle threshold of the sessions
+/// start, end: the dates of the first and of the last visits, in seconds since EPOCH
+/// paths: the paths in the order of their visits, the consecutive visits of a path collapsed
+#[derive(Debug, Clone, PartialEq)]
+pub struct Session {
+    pub start: i64,
+    pub end: i64,
+    pub paths: Vec<String>,
+}
+
+/// The number of visits of each path of the history, and the one of the most visited path
+#[derive(Debug, Clone, Default)]
+pub struct VisitCounts {
+    counts: HashMap<String, usize>,
+    max: usize,
+}
+
+impl VisitCounts {
+    pub fn new(counts: HashMap<String, usize>) -> Self {
+        let max = counts.values().copied().max().unwrap_or_default();
+        VisitCounts { counts, max }
+    }
+
+    /// The number of visits of `path`, 0 if it was never visited
+    pub fn count(&self, path: &str) -> usize { self.counts.get(path).copied().unwrap_or_default() }
+
+    /// The visits of `path` relative to the ones of the most visited path, from 0 to 1
+    pub fn heat(&self, path: &str) -> f64 {
+        if self.max == 0 {
+            return 0.0;
+        }
+        self.count(path) as f64 / self.max as f64
+    }
+}
+
+/// The environment hints of a shortcut, applied by the shell after jumping into it
+/// env: the environment variables to export, as (name, value)
+/// post_cd: a command to run once in the directory
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ShortcutHints {
+    pub env: Vec<(String, String)>,
+    pub post_cd: Option<String>,
+}
+
+impl ShortcutHints {
+    /// Whether there is nothing to apply when jumping to the shortcut
+    pub fn is_empty(&self) -> bool { self.env.is_empty() && self.post_cd.is_none() }
+
+    /// The environment variables stored one `NAME=value` per line
+    fn env_to_column(&self) -> Option<String> {
+        if self.env.is_empty() {
+            return None;
+        }
+        Some(
+            self.env
+                .iter()
+                .map(|(name, value)| format!("{}={}", name, value))
+                .collect::<Vec<String>>()
+                .join("\n"),
+        )
+    }
+
+    fn from_columns(env: Option<String>, post_cd: Option<String>) -> ShortcutHints {
+        let env = env
+            .unwrap_or_default()
+            .lines()
+            .filter_map(|line| line.split_once('='))
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        ShortcutHints { env, post_cd }
+    }
+}
+
+/// The state of the GUI when it was left, restored on the next launch
+/// search: the search text
+/// fuzzy_match: whether the search was fuzzy or exact
+/// view: the objects type of the active view (e.g. "path" or "shortcut")
+/// selected_row: the index of the selected row in the active view
+#[derive(Debug, Clone, PartialEq)]
+pub struct GuiSession {
+    pub search: String,
+    pub fuzzy_match: bool,
+    pub view: String,
+    pub selected_row: usize,
+}
+
+/// A column the paths or shortcuts lists can be sorted on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortColumn {
+    Date,
+    Path,
+    Name,
+}
+
+impl SortColumn {
+    fn sql_column(&self) -> &'static str {
+        match self {
+            SortColumn::Date => "date",
+            SortColumn::Path => "path",
+            SortColumn::Name => "name",
+        }
+    }
+}
+
+/// The ordering requested for a list of paths or shortcuts.
+/// `Default` keeps the natural ordering of the list (most recent first for the paths,
+/// by name for the shortcuts, by score for fuzzy searches).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortOrder {
+    #[default]
+    Default,
+    Ascending(SortColumn),
+    Descending(SortColumn),
+}
+
+impl SortOrder {
+    /// Build the content of the ORDER BY clause, falling back on `default` for the ties.
+    fn order_by(&self, default: &str) -> String {
+        match self {
+            SortOrder::Default => String::from(default),
+            SortOrder::Ascending(column) => format!("{} asc, {}", column.sql_column(), default),
+            SortOrder::Descending(column) => format!("{} desc, {}", column.sql_column(), default),
+        }
+    }
+}
+
+/// A level of a stack of filters, the entries listed having to match all the levels: the last
+/// level is the search being typed, the previous ones are frozen searches it narrows.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Filter {
+    pub text: String,
+    pub fuzzy: bool,
+}
+
+impl Filter {
+    pub fn new(text: &str, fuzzy: bool) -> Filter {
+        Filter {
+            text: String::from(text),
+            fuzzy,
+        }
+    }
+}
+
+/// The number of searches kept in the history of each view
+const SEARCH_HISTORY_SIZE: usize = 100;
+
+/// The number of recent files kept, the least recently opened ones being forgotten
+const FILES_SIZE: usize = 1000;
+
+/// The length passed to the listing functions to get all the matching entries
+const ALL_ENTRIES: usize = i64::MAX as usize;
+
+/// The time slot of a timestamp, used to weight the transitions between paths:
+/// (day of the week from monday) * 4 + (quarter of the day)
+fn time_slot(epoc: i64) -> i64 {
+    match Local.timestamp_opt(epoc, 0).single() {
+        Some(date) => date.weekday().num_days_from_monday() as i64 * 4 + date.hour() as i64 / 6,
+        None => 0,
+    }
+}
+
+/// The weight of the transitions made during `slot` when suggesting paths during `now_slot`:
+/// the transitions made at the same time of the day, and on the same kind of day
+/// (weekday or weekend) count more.
+fn time_slot_weight(slot: i64, now_slot: i64) -> u64 {
+    let is_weekend = |slot: i64| slot / 4 >= 5;
+    let mut weight = 1;
+    if slot % 4 == now_slot % 4 {
+        weight += 2;
+    }
+    if is_weekend(slot) == is_weekend(now_slot) {
+        weight += 1;
+    }
+    if slot == now_slot {
+        weight += 4;
+    }
+    weight
+}
+
+/// Why a path is suggested by the smart suggestions
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SuggestionExplanation {
+    /// The score ranking the suggestions, the highest first
+    pub score: f64,
+    /// The number of times the path followed the current directory
+    pub followed: usize,
+    /// The dates of the visits of the current directory the path followed, the most recent
+    /// first, in seconds since EPOCH; empty for the `transitions` strategy
+    pub sequences: Vec<i64>,
+}
+
+/// How the SmartRanker scores the paths visited after the current directory, see the
+/// `smart_suggestions_*_weight` and `smart_suggestions_half_life_days` parameters
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SmartScoring {
+    /// The number of days after which a visit counts half, 0 for no decay
+    pub half_life_days: u32,
+    /// The weight of the closeness of a path to the visit of the current directory
+    pub adjacency_weight: u32,
+    /// The weight of the visits of the current directory being recent among the last ones
+    pub recency_weight: u32,
+    /// The weight of the number of visits of the current directory a path followed
+    pub frequency_weight: u32,
+}
+
+impl SmartScoring {
+    pub fn from_config(config: &Config) -> Self {
+        SmartScoring {
+            half_life_days: config.smart_suggestions_half_life_days,
+            adjacency_weight: config.smart_suggestions_adjacency_weight,
+            recency_weight: config.smart_suggestions_recency_weight,
+            frequency_weight: config.smart_suggestions_frequency_weight,
+        }
+    }
+
+    /// The factor applied to a visit made `age_days` ago
+    fn decay(&self, age_days: f64) -> f64 {
+        match self.half_life_days {
+            0 => 1.0,
+            half_life_days => 0.5f64.powf(age_days.max(0.0) / half_life_days as f64),
+        }
+    }
+}
+
+impl Default for SmartScoring {
+    fn default() -> Self { SmartScoring::from_config(&Config::default()) }
+}
+
+/// A path ranked by the SmartRanker, with the breakdown of its score
+#[derive(Debug, Clone, PartialEq)]
+struct RankedPath {
+    path: String,
+    score: f64,
+    /// The indexes of the visits of the current directory the path followed, 0 being the last
+    /// one
+    depths: Vec<usize>,
+}
+
+/// Ranks the paths visited after the last `depth` visits of the current directory, within a
+/// window of `context_values_count` paths after each visit. Each time a path follows a visit,
+/// it scores its adjacency (halved at each step away from the visit) and the recency of the
+/// visit among the last ones, decayed with the age of the visit; each visit it follows adds
+/// to its frequency.
 struct SmartRanker {
     depth: usize,
     context_values_count: usize,
-    seen_paths: std::collections::HashMap<String, u64>,
+    scoring: SmartScoring,
+    /// The age, in days, of each of the visits of the current directory, none being decayed
+    /// when unknown
+    ages: Vec<f64>,
+    seen_paths: std::collections::HashMap<String, RankedPath>,
 }
 
 impl SmartRanker {
@@ -112,10 +433,26 @@ impl SmartRanker {
         SmartRanker {
             depth,
             context_values_count,
+            scoring: SmartScoring::default(),
+            ages: vec![],
             seen_paths: std::collections::HashMap::new(),
         }
     }
 
+    fn with_scoring(mut self, scoring: SmartScoring, ages: Vec<f64>) -> SmartRanker {
+        self.scoring = scoring;
+        self.ages = ages;
+        self
+    }
+
+    /// The factor applied to the scores of the visit at `depth`
+    fn decay(&self, depth: usize) -> f64 {
+        self.ages
+            .get(depth)
+            .map(|age| self.scoring.decay(*age))
+            .unwrap_or(1.0)
+    }
+
     fn add_path(&mut self, depth: usize, path: String, distance: usize) {
         debug!(
             "SmartRanker add_path depth={} path='{}' distance={}",
@@ -128,46 +465,290 @@ impl SmartRanker {
             );
             return;
         }
-        let reverse_idx: u32 = self.context_values_count as u32 - 1 - distance as u32;
-        let score = 2u64.pow(reverse_idx);
-        // adjust the score with the depth:
-        // 2u64.pow((self.depth - depth - 1) as u32) is too much, let's have the depth score 4 time lower than the distance score
-        // so that we privilegiate the closest paths, and only use the depth as a tie breaker
-        let score = (score << 2) + (self.depth - depth - 1) as u64;
+        // both in (0, 1], the closest paths and the most recent visits scoring 1
+        let adjacency = 0.5f64.powi(distance as i32);
+        let recency = (self.depth - depth) as f64 / self.depth as f64;
+        let decay = self.decay(depth);
+        let score = decay
+            * (self.scoring.adjacency_weight as f64 * adjacency
+                + self.scoring.recency_weight as f64 * recency);
         trace!(
             "SmartRanker adding path: {:?} distance={} score={}",
             path, distance, score
         );
-        if let Some(existing_path_score) = self.seen_paths.get(&path) {
-            let new_score = score + existing_path_score;
+        let frequency = self.frequency_score(depth);
+        if let Some(existing_path) = self.seen_paths.get_mut(&path) {
+            let new_score = score + existing_path.score;
             trace!(
                 "SmartRanker updating path: {:?} existing_score={} new_score={}",
-                path, existing_path_score, new_score
+                path, existing_path.score, new_score
             );
-            self.seen_paths.insert(path.clone(), new_score);
+            existing_path.score = new_score;
+            if !existing_path.depths.contains(&depth) {
+                existing_path.depths.push(depth);
+                existing_path.score += frequency;
+            }
         } else {
-            self.seen_paths.insert(path.clone(), score);
+            let score = score + frequency;
+            self.seen_paths.insert(
+                path.clone(),
+                RankedPath {
+                    path,
+                    score,
+                    depths: vec![depth],
+                },
+            );
         }
     }
 
-    fn collect_rows(&self) -> Vec<String> {
-        let rows = self.seen_paths.iter().map(|(k, v)| (k.clone(), *v));
-        let mut rows: Vec<(String, u64)> = rows.collect();
-        // let mut rows: Vec<(u64, String)> = self.seen_paths.clone().into_iter().collect();
+    /// The score of a path following one more of the visits, the one at `depth`
+    fn frequency_score(&self, depth: usize) -> f64 {
+        self.scoring.frequency_weight as f64 * self.decay(depth) / self.depth as f64
+    }
+
+    /// The ranked paths, the highest score first
+    fn collect_rows(&self) -> Vec<RankedPath> {
+        let mut rows: Vec<RankedPath> = self.seen_paths.values().cloned().collect();
         // sort by score descending
-        rows.sort_by(|pws1, pws2| pws2.1.cmp(&pws1.1));
-        rows.into_iter()
-            .take(self.context_values_count)
-            .map(|pws| pws.0)
-            .collect::<Vec<String>>()
+        rows.sort_by(|a, b| b.score.total_cmp(&a.score));
+        rows.truncate(self.context_values_count);
+        rows
     }
 }
 
 /// Store struct to manage database connection and operations
 /// db_conn: the SQLite database connection
-pub(crate) struct Store {
+///
+/// A store and its clones share their connection and state, and stay on the thread which
+/// opened them: a `Store` is neither `Send` nor `Sync`. Another thread opens its own store on
+/// the same database file, SQLite serializing the writes of the connections.
+pub struct Store {
     db_conn: Rc<Connection>,
     config: Arc<Mutex<Config>>,
+    /// When set, only the paths visited from this tmux pane are listed; shared by the clones
+    pane_filter: Rc<RefCell<Option<String>>>,
+    /// Whether the sensitive paths are listed; shared by the clones
+    reveal_sensitive: Rc<Cell<bool>>,
+    /// Whether the deleted paths are listed instead of the others; shared by the clones
+    show_trash: Rc<Cell<bool>>,
+    /// Whether only the smart suggestions for the current directory are listed; shared by the
+    /// clones
+    suggestions_only: Rc<Cell<bool>>,
+    /// Whether the searches respect the case; shared by the clones
+    case_sensitivity: Rc<Cell<CaseSensitivity>>,
+    /// The host the paths are visited from
+    hostname: Option<String>,
+    /// The container the paths are visited from, None on the host
+    container: Option<String>,
+    /// The results of the last fuzzy search of each table, reused while paging through them;
+    /// shared by the clones
+    fuzzy_cache: Rc<RefCell<HashMap<&'static str, FuzzySearchCache>>>,
+    /// The visit counts of the paths, loaded once until the next reload; shared by the clones
+    visit_counts: Rc<RefCell<Option<Rc<VisitCounts>>>>,
+    /// Why the last smart suggestions were made, by path; shared by the clones
+    suggestion_explanations: Rc<RefCell<HashMap<String, SuggestionExplanation>>>,
+}
+
+/// What the results of a fuzzy search depend on
+#[derive(Debug, Clone, PartialEq)]
+struct FuzzySearchKey {
+    like_text: String,
+    tokens: SearchTokens,
+    sort: SortOrder,
+    pane_filter: Option<String>,
+    reveal_sensitive: bool,
+    show_trash: bool,
+    suggestions_only: bool,
+    case_sensitive: bool,
+    /// The settings changing the matches: `path_search_include_shortcuts`, `current_host_only`
+    /// and `mix_container_history`
+    settings: (bool, bool, bool),
+    fuzzy_config: FuzzyConfig,
+    /// The rows changed through the connection of the store
+    total_changes: u64,
+    /// Changed by the commits of the other connections
+    data_version: i64,
+}
+
+/// A shortcut of a merged database whose name is the one of a shortcut of another directory
+#[derive(Debug, PartialEq)]
+pub struct ShortcutConflict {
+    pub name: String,
+    /// The path of the shortcut kept in the database
+    pub path: String,
+    /// The path of the shortcut of the merged database, which is dropped
+    pub other_path: String,
+}
+
+/// The outcome of the merge of another database
+#[derive(Debug, Default, PartialEq)]
+pub struct MergeSummary {
+    /// The paths which were not in the database
+    pub paths_added: usize,
+    /// The paths visited last in the merged database, whose date is updated
+    pub paths_updated: usize,
+    /// The visits which were not in the history
+    pub history_added: usize,
+    /// The visits already in the history, at the same date
+    pub history_duplicates: usize,
+    pub shortcuts_added: usize,
+    /// The shortcuts already in the database with the same name and path
+    pub shortcuts_duplicates: usize,
+    pub shortcut_conflicts: Vec<ShortcutConflict>,
+}
+
+impl fmt::Display for MergeSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "paths: {} added, {} updated",
+            self.paths_added, self.paths_updated
+        )?;
+        writeln!(
+            f,
+            "history: {} added, {} duplicates skipped",
+            self.history_added, self.history_duplicates
+        )?;
+        write!(
+            f,
+            "shortcuts: {} added, {} duplicates skipped, {} conflicts",
+            self.shortcuts_added,
+            self.shortcuts_duplicates,
+            self.shortcut_conflicts.len()
+        )?;
+        for conflict in &self.shortcut_conflicts {
+            write!(
+                f,
+                "\n  {}: kept {}, dropped {}",
+                conflict.name, conflict.path, conflict.other_path
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// A path matching a fuzzy search
+struct ScoredPath {
+    path: Path,
+    score: u32,
+    /// The number of visits of the path
+    visits: i64,
+}
+
+impl ScoredPath {
+    /// The order of the results: the best scores first, then the most recent, the most visited
+    /// and the last added paths, so that the order does not depend on the one of the rows
+    fn compare(a: &ScoredPath, b: &ScoredPath) -> std::cmp::Ordering {
+        b.score
+            .cmp(&a.score)
+            .then(b.path.date.cmp(&a.path.date))
+            .then(b.visits.cmp(&a.visits))
+            .then(b.path.id.cmp(&a.path.id))
+    }
+}
+
+/// The ids of the rows matching a fuzzy search, in the order of the results
+struct FuzzySearchCache {
+    key: FuzzySearchKey,
+    ids: Rc<Vec<i64>>,
+}
+
+/// The prefix of the search token restricting the listed paths to a host
+const HOST_FILTER_PREFIX: &str = "host:";
+
+/// The prefix of the search token restricting the listed paths to a project type
+const TYPE_FILTER_PREFIX: &str = "type:";
+
+/// The maximum number of GLOB clauses of a glob search, see `Store::glob_patterns`
+const MAX_GLOB_PATTERNS: usize = 16;
+
+/// The duration after which the type of a project is detected again, in seconds
+const PROJECT_TYPE_TTL: i64 = 24 * 3600;
+
+/// The duration after which `add_path` computes the cached smart suggestions of a directory
+/// again, in seconds
+const SUGGESTIONS_CACHE_TTL: i64 = 10 * 60;
+
+/// The duration of a week of the visits histograms, in seconds
+const WEEK: i64 = 7 * 24 * 3600;
+
+/// The tokens of a search text restricting the listed paths, e.g. `host:laptop type:rust`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchTokens {
+    pub host: Option<String>,
+    pub project_type: Option<ProjectType>,
+}
+
+impl SearchTokens {
+    fn is_empty(&self) -> bool { self.host.is_none() && self.project_type.is_none() }
+}
+
+/// The paths equivalent to the path parameter `?1`, including itself
+const EQUIVALENT_PATHS_SQL: &str = "SELECT (?1) UNION SELECT path FROM path_links \
+     WHERE canonical = (SELECT canonical FROM path_links WHERE path = (?1))";
+
+/// The condition keeping, among the listed paths of a group of equivalent paths, the one
+/// visited last
+const LATEST_EQUIVALENT_PATH_CONDITION: &str = "NOT EXISTS (SELECT 1 FROM path_links AS mine \
+     JOIN path_links AS theirs ON theirs.canonical = mine.canonical \
+     JOIN paths AS other ON other.path = theirs.path \
+     WHERE mine.path = paths.path AND other.deleted_at IS NULL \
+     AND (other.date > paths.date OR (other.date = paths.date AND other.id > paths.id)))";
+
+/// The name of the current host, None if it cannot be found
+fn current_hostname() -> Option<String> {
+    std::env::var("HOSTNAME")
+        .ok()
+        .or_else(|| fs::read_to_string("/proc/sys/kernel/hostname").ok())
+        .or_else(|| fs::read_to_string("/etc/hostname").ok())
+        .or_else(|| {
+            std::process::Command::new("uname")
+                .arg("-n")
+                .output()
+                .ok()
+                .and_then(|output| String::from_utf8(output.stdout).ok())
+        })
+        .map(|hostname| hostname.trim().to_string())
+        .filter(|hostname| !hostname.is_empty())
+}
+
+/// Whether an error tells that another connection holds the lock of the database
+pub fn is_busy(err: &rusqlite::Error) -> bool {
+    matches!(
+        err,
+        rusqlite::Error::SqliteFailure(failure, _)
+            if matches!(failure.code, ErrorCode::DatabaseBusy | ErrorCode::DatabaseLocked)
+    )
+}
+
+/// The canonical form of a local path: `~` expanded, `.` and `..` resolved, trailing slashes
+/// removed and, when the directory exists, symbolic links resolved.
+/// Remote entries and relative paths are returned unchanged.
+fn canonical_path(path: &str) -> String {
+    let expanded = match path.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with('/') => match std::env::var("HOME") {
+            Ok(home) => format!("{}{}", home, rest),
+            Err(_) => return path.to_string(),
+        },
+        _ => path.to_string(),
+    };
+    if !expanded.starts_with('/') {
+        return path.to_string();
+    }
+    let mut normalized = std::path::PathBuf::from("/");
+    for component in std::path::Path::new(&expanded).components() {
+        match component {
+            std::path::Component::ParentDir => {
+                normalized.pop();
+            }
+            std::path::Component::Normal(name) => normalized.push(name),
+            _ => {}
+        }
+    }
+    fs::canonicalize(&normalized)
+        .unwrap_or(normalized)
+        .to_string_lossy()
+        .into_owned()
 }
 
 impl Store {
@@ -177,41 +758,88 @@ impl Store {
     /// dir_path: the path to the SQLite database file
     ///
     /// ### Returns
-    /// a new Store instance
-    pub(crate) fn new(dir_path: &std::path::Path, config: Arc<Mutex<Config>>) -> Store {
+    /// a new Store instance, or the error of the database which cannot be opened, created or
+    /// upgraded
+    pub fn new(
+        dir_path: &std::path::Path,
+        config: Arc<Mutex<Config>>,
+    ) -> Result<Store, rusqlite::Error> {
         info!("db file={}", dir_path.display());
 
         if !dir_path.exists()
             && let Some(parent) = dir_path.parent()
             && let Err(e) = fs::create_dir_all(parent)
         {
+            // opening the database fails just below
             error!("Failed to create directory '{}': {}", parent.display(), e);
-            panic!("Directory creation failed");
         }
         let db_exists = dir_path.exists();
+        let case_sensitivity = config.lock().unwrap().case_sensitivity;
+        let database_config = config.lock().unwrap().database.clone();
 
+        let db_open = tracing::info_span!("db_open").entered();
+        let db_conn = Connection::open(dir_path).inspect_err(|err| {
+            error!(
+                "Failed to open connection to database '{}': {}",
+                dir_path.display(),
+                err
+            )
+        })?;
+        Self::tune_connection(&db_conn, &database_config);
         let store = Store {
-            db_conn: match Connection::open(dir_path) {
-                Ok(conn) => Rc::new(conn),
-                Err(err) => {
-                    error!(
-                        "Failed to open connection to database '{}': {}",
-                        dir_path.display(),
-                        err
-                    );
-                    panic!("Failed to open connection to database")
-                }
-            },
+            db_conn: Rc::new(db_conn),
             config,
+            pane_filter: Rc::new(RefCell::new(None)),
+            reveal_sensitive: Rc::new(Cell::new(false)),
+            show_trash: Rc::new(Cell::new(false)),
+            suggestions_only: Rc::new(Cell::new(false)),
+            case_sensitivity: Rc::new(Cell::new(case_sensitivity)),
+            hostname: current_hostname(),
+            container: container::current_container(),
+            fuzzy_cache: Rc::new(RefCell::new(HashMap::new())),
+            visit_counts: Rc::new(RefCell::new(None)),
+            suggestion_explanations: Rc::new(RefCell::new(HashMap::new())),
         };
+        db_open.exit();
 
+        let _schema_check = tracing::info_span!("schema_check").entered();
         if !db_exists {
-            store.init_schema();
+            store.init_schema()?;
         } else {
-            store.upgrade_schema();
+            store.upgrade_schema()?;
         }
 
-        store
+        Ok(store)
+    }
+
+    /// Apply the configured pragmas to a new connection, and size the cache of the prepared
+    /// statements for the queries of the store. A pragma which fails is only logged, the
+    /// database remaining usable with the SQLite defaults.
+    fn tune_connection(conn: &Connection, database_config: &DatabaseConfig) {
+        conn.set_prepared_statement_cache_capacity(STATEMENT_CACHE_CAPACITY);
+        if let Err(e) = conn.busy_timeout(Duration::from_millis(database_config.busy_timeout_ms)) {
+            warn!("Failed to set the busy timeout: {}", e);
+        }
+        let journal_mode = match database_config.journal_mode {
+            JournalMode::Delete => "DELETE",
+            JournalMode::Wal => "WAL",
+        };
+        // the journal mode pragma returns the mode in effect
+        if let Err(e) = conn.query_row(
+            &format!("PRAGMA journal_mode = {}", journal_mode),
+            [],
+            |row| row.get::<_, String>(0),
+        ) {
+            warn!("Failed to set the journal mode {}: {}", journal_mode, e);
+        }
+        let synchronous = match database_config.synchronous {
+            Synchronous::Off => "OFF",
+            Synchronous::Normal => "NORMAL",
+            Synchronous::Full => "FULL",
+        };
+        if let Err(e) = conn.pragma_update(None, "synchronous", synchronous) {
+            warn!("Failed to set the synchronous mode {}: {}", synchronous, e);
+        }
     }
 
     fn set_schema_version(&self, version: i64) {
@@ -234,19 +862,20 @@ impl Store {
 
     /// Initializes the database schema by creating necessary tables and indexes.
     /// If the tables already exist, this function does nothing.
-    fn init_schema(&self) {
+    fn init_schema(&self) -> Result<(), rusqlite::Error> {
         info!("Initializing the database schema");
 
         let script = include_str!("../dbschema/current.sql");
         debug!("Schema initialization");
         if let Err(err) = self.db_conn.execute_batch(script) {
             error!("init_schema: {}", err);
-            panic!("init_schema")
+            return Err(err);
         }
         self.set_schema_version(CURRENT_SCHEMA_VERSION);
+        Ok(())
     }
 
-    fn upgrade_schema(&self) {
+    fn upgrade_schema(&self) -> Result<(), rusqlite::Error> {
         info!("Upgrading the database schema if necessary");
 
         // Find the current version of the schema
@@ -258,7 +887,7 @@ impl Store {
 
         if version >= CURRENT_SCHEMA_VERSION {
             info!("Database schema is up to date");
-            return;
+            return Ok(());
         }
 
         // embed the sql upgrade scripts
@@ -266,28 +895,81 @@ impl Store {
             include_str!("../dbschema/1.sql"),
             include_str!("../dbschema/2.sql"),
             include_str!("../dbschema/3.sql"),
+            include_str!("../dbschema/4.sql"),
+            include_str!("../dbschema/5.sql"),
+            include_str!("../dbschema/6.sql"),
+            include_str!("../dbschema/7.sql"),
+            include_str!("../dbschema/8.sql"),
+            include_str!("../dbschema/9.sql"),
+            include_str!("../dbschema/10.sql"),
+            include_str!("../dbschema/11.sql"),
+            include_str!("../dbschema/12.sql"),
+            include_str!("../dbschema/13.sql"),
+            include_str!("../dbschema/14.sql"),
+            include_str!("../dbschema/15.sql"),
+            include_str!("../dbschema/16.sql"),
+            include_str!("../dbschema/17.sql"),
+            include_str!("../dbschema/18.sql"),
+            include_str!("../dbschema/19.sql"),
+            include_str!("../dbschema/20.sql"),
+            include_str!("../dbschema/21.sql"),
+            include_str!("../dbschema/22.sql"),
+            include_str!("../dbschema/23.sql"),
+            include_str!("../dbschema/24.sql"),
+            include_str!("../dbschema/25.sql"),
             // add other upgrade scripts here
         ];
 
         for v in version..CURRENT_SCHEMA_VERSION {
+            if v + 1 == UNIQUE_SHORTCUT_NAMES_VERSION {
+                self.report_duplicate_shortcuts();
+            }
             let script = u[v as usize];
             info!("Upgrading schema from version {} to {}", v, v + 1);
             debug!("Upgrade script:\n{}", script);
             if let Err(err) = self.db_conn.execute_batch(script) {
                 error!("upgrade_schema from {} to {}: {}", v, v + 1, err);
-                panic!("upgrade_schema")
+                return Err(err);
             } else {
                 info!("Successfully upgraded schema to version {}", v + 1);
             }
         }
 
         self.set_schema_version(CURRENT_SCHEMA_VERSION);
+        Ok(())
+    }
+
+    /// The shortcuts renamed `name~id` by the upgrade making the names unique, as (id, name,
+    /// path): all the shortcuts sharing a name but the latest one
+    fn list_duplicate_shortcuts(&self) -> Result<Vec<(i64, String, String)>, rusqlite::Error> {
+        let mut stmt = self.db_conn.prepare(
+            "SELECT id, name, path FROM shortcuts \
+             WHERE id NOT IN (SELECT MAX(id) FROM shortcuts GROUP BY name) ORDER BY name, id",
+        )?;
+        stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+            .collect()
+    }
+
+    /// Warn about each shortcut renamed by the upgrade making the names unique, the user
+    /// finding it under its new name
+    fn report_duplicate_shortcuts(&self) {
+        match self.list_duplicate_shortcuts() {
+            Ok(duplicates) => {
+                for (id, name, path) in duplicates {
+                    warn!(
+                        "The shortcut '{}' to {} is renamed '{}~{}', another shortcut having the same name",
+                        name, path, name, id
+                    );
+                }
+            }
+            Err(e) => error!("Failed to list the shortcuts sharing a name: {}", e),
+        }
     }
 
     fn find_schema_version(&self) -> i64 {
         let version: i64;
 
-        let mut stmt = match self.db_conn.prepare("SELECT version FROM version") {
+        let mut stmt = match self.db_conn.prepare_cached("SELECT version FROM version") {
             Ok(stmt) => stmt,
             Err(e) => {
                 error!("find_schema_version failed in prepare: {}", e);
@@ -330,145 +1012,697 @@ impl Store {
     ///
     /// ### Returns
     /// Ok(()) if the operation was successful, otherwise an error
-    pub(crate) fn add_path(&self, path: &str) -> Result<(), rusqlite::Error> {
-        debug!("add_path path={}", path);
-        self.add_path_with_time(
-            path,
-            SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap()
-                .as_secs(),
-        )
+    #[allow(dead_code)]
+    pub fn add_path(&self, path: &str) -> Result<(), rusqlite::Error> {
+        self.add_path_from_pane(path, None)
     }
 
-    /// Adds a new path to the database with a specified timestamp.
-    /// If the path already exists, it is updated with the new timestamp.
+    /// Adds a new path to the database with the current timestamp, like `add_path`, recording
+    /// the tmux pane and the host it was visited from.
     ///
     /// ### Parameters
     /// path: the file path to add
-    /// epoc: the timestamp to associate with the path (in seconds since EPOCH)
+    /// tmux_pane: the tmux pane identifier ($TMUX_PANE), if any
     ///
     /// ### Returns
     /// Ok(()) if the operation was successful, otherwise an error
-    pub(crate) fn add_path_with_time(&self, path: &str, epoc: u64) -> Result<(), rusqlite::Error> {
-        debug!("add_path_with_time path={} epoch={}", path, epoc);
-        {
-            let mut stmt = self.db_conn.prepare("DELETE FROM paths WHERE path=(?1)")?;
-            if let Err(err) = stmt.execute([path]) {
-                error!("Failed to delete path '{}': {}", path, err);
-                return Err(err);
-            }
+    pub fn add_path_from_pane(
+        &self,
+        path: &str,
+        tmux_pane: Option<&str>,
+    ) -> Result<(), rusqlite::Error> {
+        debug!("add_path path={} tmux_pane={:?}", path, tmux_pane);
+        let path = if self.config.lock().unwrap().canonicalize_paths {
+            &canonical_path(path)
+        } else {
+            path
+        };
+        self.add_path_entry(
+            path,
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+            tmux_pane,
+            self.hostname.as_deref(),
+        )?;
+        if let Err(err) = self.link_resolved_path(path) {
+            // the links only group the equivalent paths, the path is recorded anyway
+            warn!("Failed to link '{}' to its resolved path: {}", path, err);
         }
-        let result1;
-        let result2;
+        if self.config.lock().unwrap().auto_shortcut_git_roots
+            && let Err(err) = self.add_git_root_shortcut(path)
         {
-            // add into paths
-            let mut stmt = self
-                .db_conn
-                .prepare("INSERT INTO paths (path, date) VALUES ((?1),(?2))")?;
-            result1 = stmt
-                .execute([path, &format!("{}", epoc)])
-                .map_err(|e| {
-                    error!("Failed to insert path '{}' time' {}: {}", path, epoc, e);
-                    e
-                })
-                .map(|_l| ());
-            let _ = result1
-                .as_ref()
-                .map_err(|e| error!("Error inserting into paths: {}", e));
+            warn!(
+                "Failed to add the shortcut of the repository of '{}': {}",
+                path, err
+            );
         }
+        if self.config.lock().unwrap().smart_suggestions_active
+            && let Err(err) = self.refresh_stale_suggestions_cache(path)
         {
-            // add into paths_history
-            let mut stmt = self
-                .db_conn
-                .prepare("INSERT INTO paths_history (path, date) VALUES ((?1),(?2))")?;
-            result2 = stmt
-                .execute([path, &format!("{}", epoc)])
-                .map_err(|e| {
-                    error!("Failed to insert path '{}' time' {}: {}", path, epoc, e);
-                    e
-                })
-                .map(|_l| ());
-            let _ = result2
-                .as_ref()
-                .map_err(|e| error!("Error inserting into paths_history: {}", e));
+            // the GUI computes the suggestions anyway
+            warn!("Failed to cache the suggestions of '{}': {}", path, err);
         }
-        result1.and(result2)
+        Ok(())
     }
 
-    /// Deletes a path from the database by its ID.
-    ///
-    /// ### Parameters
-    /// id: the ID of the path to delete
+    /// Adds a shortcut for the root of the git repository `path` belongs to, unless the root
+    /// already has one. The shortcut is named after the root directory, see
+    /// `shortcut_suggestions::derive_name`.
     ///
     /// ### Returns
-    /// Ok(()) if the operation was successful, otherwise an error
-    pub(crate) fn delete_path_by_id(&self, id: i64) -> Result<(), rusqlite::Error> {
-        let mut stmt = self.db_conn.prepare("DELETE FROM paths WHERE id=(?1)")?;
-        stmt.execute([id])
-            .map_err(|e| {
-                error!("Failed to delete path by id '{}',{}", id, e);
-                e
-            })
-            .map(|_l: usize| ())
+    /// The name of the shortcut added, if any, if the operation was successful, otherwise an
+    /// error.
+    pub fn add_git_root_shortcut(&self, path: &str) -> Result<Option<String>, rusqlite::Error> {
+        if RemotePath::parse(path).is_some() {
+            return Ok(None);
+        }
+        // a worktree or a submodule has a .git file instead of a directory
+        let Some(root) = std::path::Path::new(path)
+            .ancestors()
+            .find(|ancestor| ancestor.join(".git").exists())
+            .and_then(|root| root.to_str())
+        else {
+            return Ok(None);
+        };
+        if !self.list_shortcuts_by_path(root)?.is_empty() {
+            return Ok(None);
+        }
+        let taken: HashSet<String> = self
+            .list_all_shortcuts()?
+            .into_iter()
+            .map(|shortcut| shortcut.name)
+            .collect();
+        let name = shortcut_suggestions::derive_name(root, &taken);
+        info!(
+            "adding the shortcut {} of the git repository '{}'",
+            name, root
+        );
+        self.upsert_shortcut(&name, root, None)?;
+        Ok(Some(name))
     }
 
-    /// Lists paths from the database with pagination and optional filtering.
-    /// The results are ordered by date (descending) and ID (descending).
-    /// If `like_text` is provided, only paths containing the text are returned.
-    ///
-    /// ### Parameters
-    /// pos: the starting position (offset) for pagination
-    /// len: the number of paths to return
-    /// like_text: optional text to filter paths (if empty, no filtering is applied)
+    /// Groups a path reached through symbolic links with its resolved path, unless it already
+    /// belongs to a group.
+    fn link_resolved_path(&self, path: &str) -> Result<(), rusqlite::Error> {
+        if RemotePath::parse(path).is_some() || self.path_group(path)?.is_some() {
+            return Ok(());
+        }
+        let Some(resolved) = fs::canonicalize(path)
+            .ok()
+            .and_then(|p| p.to_str().map(String::from))
+            .filter(|resolved| resolved != path)
+        else {
+            return Ok(());
+        };
+        self.link_paths(&resolved, path)
+    }
+
+    /// The canonical path of the group of equivalent paths `path` belongs to, if any.
+    fn path_group(&self, path: &str) -> Result<Option<String>, rusqlite::Error> {
+        let mut stmt = self
+            .db_conn
+            .prepare_cached("SELECT canonical FROM path_links WHERE path = (?1)")?;
+        stmt.query_map([path], |row| row.get(0))?.next().transpose()
+    }
+
+    /// Declares `alias` as equivalent to `path`: the group of `alias`, if any, joins the group
+    /// of `path`, which is created if needed.
+    pub fn link_paths(&self, path: &str, alias: &str) -> Result<(), rusqlite::Error> {
+        self.retry_busy(|| {
+            debug!("link_paths path={} alias={}", path, alias);
+            // the links may be made within the transaction of a batch
+            let transaction = match self.db_conn.is_autocommit() {
+                true => Some(self.transaction()?),
+                false => None,
+            };
+            let canonical = self.path_group(path)?.unwrap_or_else(|| path.to_string());
+            self.db_conn.execute(
+                "INSERT OR IGNORE INTO path_links (path, canonical) VALUES (?1, ?1)",
+                [&canonical],
+            )?;
+            self.db_conn.execute(
+                "INSERT OR IGNORE INTO path_links (path, canonical) VALUES (?1, ?2)",
+                [path, &canonical],
+            )?;
+            if let Some(alias_group) = self.path_group(alias)? {
+                self.db_conn.execute(
+                    "UPDATE path_links SET canonical = (?1) WHERE canonical = (?2)",
+                    [&canonical, &alias_group],
+                )?;
+            }
+            self.db_conn.execute(
+                "INSERT OR REPLACE INTO path_links (path, canonical) VALUES (?1, ?2)",
+                [alias, &canonical],
+            )?;
+            match transaction {
+                Some(transaction) => transaction.commit(),
+                None => Ok(()),
+            }
+        })
+    }
+
+    /// The path of the group of `path` visited last, `path` itself if it has no equivalent.
+    fn latest_equivalent_path(&self, path: &str) -> Result<String, rusqlite::Error> {
+        let sql = format!(
+            "SELECT path FROM paths WHERE path IN ({}) AND deleted_at IS NULL \
+             ORDER BY date desc, id desc LIMIT 1",
+            EQUIVALENT_PATHS_SQL
+        );
+        let latest: Option<String> = self
+            .db_conn
+            .prepare_cached(&sql)?
+            .query_map([path], |row| row.get(0))?
+            .next()
+            .transpose()?;
+        Ok(latest.unwrap_or_else(|| path.to_string()))
+    }
+
+    /// The key grouping the equivalent paths when ranking suggestions.
+    fn group_key(&self, path: &str) -> Result<String, rusqlite::Error> {
+        Ok(self.path_group(path)?.unwrap_or_else(|| path.to_string()))
+    }
+
+    /// Adds several paths, in order, like `add_path_from_pane` but in a single transaction.
     ///
     /// ### Returns
-    /// A vector of Path entries if the operation was successful, otherwise an error.
-    pub(crate) fn list_paths(
+    /// Ok(()) if all the paths were added, otherwise an error and none of them is added
+    pub fn add_paths_from_pane(
         &self,
-        pos: usize,
-        len: usize,
-        like_text: &str,
-        fuzzy: bool,
-    ) -> Result<Vec<Path>, rusqlite::Error> {
-        debug!(
-            "list_paths pos={} len={} like_text={} fuzzy={}",
-            pos, len, like_text, fuzzy
-        );
-        // Retrieve all shortcuts to associate with paths
-        let shortcuts = self.list_all_shortcuts().unwrap_or_default();
-        if like_text.is_empty() || !fuzzy {
-            self.list_path_exact(pos, len, like_text, &shortcuts)
-        } else {
-            self.list_path_fuzzy(pos, len, like_text, &shortcuts)
-        }
+        paths: &[String],
+        tmux_pane: Option<&str>,
+    ) -> Result<(), rusqlite::Error> {
+        self.retry_busy(|| {
+            debug!("add_paths_from_pane count={}", paths.len());
+            let transaction = self.transaction()?;
+            for path in paths {
+                self.add_path_from_pane(path, tmux_pane)?;
+            }
+            transaction.commit()
+        })
     }
 
-    /// Scores a path for fuzzy search based on the provided pattern and matcher.
+    /// Starts a transaction, committed with `Transaction::commit` and rolled back when dropped.
+    /// The operations of the store made while it is alive belong to it.
+    ///
+    /// The write lock is taken at once, waiting up to the busy timeout for the other writers:
+    /// a transaction reading first could not take it later while another process commits.
+    pub fn transaction(&self) -> Result<rusqlite::Transaction<'_>, rusqlite::Error> {
+        rusqlite::Transaction::new_unchecked(&self.db_conn, TransactionBehavior::Immediate)
+    }
+
+    /// Runs a write, run again with a doubling delay while another process keeps the database
+    /// locked beyond the busy timeout, or invalidated the snapshot of a transaction. A write
+    /// made within a transaction is not retried on its own: the whole transaction is, by the
+    /// write which started it.
+    #[instrument(name = "write", level = "debug", skip_all)]
+    fn retry_busy<T>(
+        &self,
+        mut write: impl FnMut() -> Result<T, rusqlite::Error>,
+    ) -> Result<T, rusqlite::Error> {
+        let retriable = self.db_conn.is_autocommit();
+        let mut delay = BUSY_RETRY_INITIAL_DELAY;
+        let mut attempt = 1;
+        loop {
+            match write() {
+                Err(err) if retriable && is_busy(&err) && attempt < BUSY_RETRY_ATTEMPTS => {
+                    warn!(
+                        "Database busy (attempt {}/{}), retrying in {:?}: {}",
+                        attempt, BUSY_RETRY_ATTEMPTS, delay, err
+                    );
+                    std::thread::sleep(delay);
+                    delay *= 2;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Whether the history holds a visit of `path` at `epoc`.
+    pub fn has_path_history(&self, path: &str, epoc: u64) -> Result<bool, rusqlite::Error> {
+        self.db_conn
+            .prepare_cached("SELECT COUNT(*) FROM paths_history WHERE path = (?1) AND date = (?2)")?
+            .query_row(params![path, epoc as i64], |row| row.get::<_, i64>(0))
+            .map(|count| count > 0)
+    }
+
+    /// Adds a new path to the database with a specified timestamp.
+    /// If the path already exists, it is updated with the new timestamp.
+    ///
+    /// ### Parameters
+    /// path: the file path to add
+    /// epoc: the timestamp to associate with the path (in seconds since EPOCH)
+    ///
+    /// ### Returns
+    /// Ok(()) if the operation was successful, otherwise an error
+    pub fn add_path_with_time(&self, path: &str, epoc: u64) -> Result<(), rusqlite::Error> {
+        self.add_path_entry(path, epoc, None, None)
+    }
+
+    fn add_path_entry(
+        &self,
+        path: &str,
+        epoc: u64,
+        tmux_pane: Option<&str>,
+        hostname: Option<&str>,
+    ) -> Result<(), rusqlite::Error> {
+        self.retry_busy(|| {
+            debug!(
+                "add_path_entry path={} epoch={} tmux_pane={:?} hostname={:?}",
+                path, epoc, tmux_pane, hostname
+            );
+            // the path keeps its sensitive flag when visited again
+            let sensitive: bool = self
+                .db_conn
+                .prepare_cached("SELECT COALESCE(MAX(sensitive), 0) FROM paths WHERE path=(?1)")?
+                .query_row([path], |row| row.get(0))?;
+            {
+                let mut stmt = self
+                    .db_conn
+                    .prepare_cached("DELETE FROM paths WHERE path=(?1)")?;
+                if let Err(err) = stmt.execute([path]) {
+                    error!("Failed to delete path '{}': {}", path, err);
+                    return Err(err);
+                }
+            }
+            if let Err(err) = self.add_path_transition(path, epoc) {
+                // the transitions only feed the suggestions, the path is recorded anyway
+                warn!("Failed to record the transition to '{}': {}", path, err);
+            }
+            let result1;
+            let result2;
+            {
+                // add into paths
+                let mut stmt = self.db_conn.prepare_cached(
+                    "INSERT INTO paths (path, date, hostname, remote, sensitive) VALUES ((?1),(?2),(?3),(?4),(?5))",
+                )?;
+                result1 = stmt
+                    .execute(params![
+                        path,
+                        epoc as i64,
+                        hostname,
+                        RemotePath::parse(path).is_some(),
+                        sensitive
+                    ])
+                    .map_err(|e| {
+                        error!("Failed to insert path '{}' time' {}: {}", path, epoc, e);
+                        e
+                    })
+                    .map(|_l| ());
+                let _ = result1
+                    .as_ref()
+                    .map_err(|e| error!("Error inserting into paths: {}", e));
+            }
+            {
+                // add into paths_history
+                let mut stmt = self.db_conn.prepare_cached(
+                    "INSERT INTO paths_history (path, date, tmux_pane, hostname, container) VALUES ((?1),(?2),(?3),(?4),(?5))",
+                )?;
+                result2 = stmt
+                    .execute(params![
+                        path,
+                        epoc as i64,
+                        tmux_pane,
+                        hostname,
+                        self.container
+                    ])
+                    .map_err(|e| {
+                        error!("Failed to insert path '{}' time' {}: {}", path, epoc, e);
+                        e
+                    })
+                    .map(|_l| ());
+                let _ = result2
+                    .as_ref()
+                    .map_err(|e| error!("Error inserting into paths_history: {}", e));
+            }
+            result1.and(result2)
+        })
+    }
+
+    /// Counts the transition from the last path of the history to `path`, in the time slot
+    /// of `epoc`.
+    fn add_path_transition(&self, path: &str, epoc: u64) -> Result<(), rusqlite::Error> {
+        let previous_path: Option<String> = self
+            .db_conn
+            .prepare_cached("SELECT path FROM paths_history ORDER BY id DESC LIMIT 1")?
+            .query_map([], |row| row.get(0))?
+            .next()
+            .transpose()?;
+        let Some(previous_path) = previous_path.filter(|p| p != path) else {
+            return Ok(());
+        };
+        trace!("transition '{}' -> '{}'", previous_path, path);
+        self.db_conn
+            .prepare_cached(
+                "INSERT INTO path_transitions (from_path, to_path, time_slot, count) VALUES (?1, ?2, ?3, 1) \
+                 ON CONFLICT (from_path, to_path, time_slot) DO UPDATE SET count = count + 1",
+            )?
+            .execute(params![previous_path, path, time_slot(epoc as i64)])?;
+        Ok(())
+    }
+
+    /// Moves a path to the trash by its ID: it is no longer listed, except in the trash, until
+    /// it is restored, visited again or purged.
+    ///
+    /// ### Parameters
+    /// id: the ID of the path to delete
+    ///
+    /// ### Returns
+    /// Ok(()) if the operation was successful, otherwise an error
+    pub fn delete_path_by_id(&self, id: i64) -> Result<(), rusqlite::Error> {
+        self.retry_busy(|| {
+            let mut stmt = self
+                .db_conn
+                .prepare_cached("UPDATE paths SET deleted_at = (?1) WHERE id = (?2)")?;
+            stmt.execute(params![Local::now().timestamp(), id])
+                .map_err(|e| {
+                    error!("Failed to delete path by id '{}',{}", id, e);
+                    e
+                })
+                .map(|_l: usize| ())
+        })
+    }
+
+    /// Takes a path out of the trash by its ID.
+    pub fn restore_path_by_id(&self, id: i64) -> Result<(), rusqlite::Error> {
+        self.retry_busy(|| {
+            debug!("restore_path_by_id {}", id);
+            self.db_conn
+                .prepare_cached("UPDATE paths SET deleted_at = NULL WHERE id = (?1)")?
+                .execute([id])
+                .map(|_l| ())
+        })
+    }
+
+    /// Deletes for good a path of the trash by its ID.
+    pub fn purge_path_by_id(&self, id: i64) -> Result<(), rusqlite::Error> {
+        self.retry_busy(|| {
+            debug!("purge_path_by_id {}", id);
+            self.db_conn
+                .prepare_cached("DELETE FROM paths WHERE id = (?1) AND deleted_at IS NOT NULL")?
+                .execute([id])
+                .map(|_l| ())
+        })
+    }
+
+    /// Deletes for good the paths in the trash for more than `retention_days` days.
+    ///
+    /// ### Returns
+    /// The number of paths purged
+    pub fn purge_trash(&self, retention_days: u64) -> Result<usize, rusqlite::Error> {
+        self.retry_busy(|| {
+            let before = Local::now().timestamp() - (retention_days * 24 * 3600) as i64;
+            let purged = self
+                .db_conn
+                .prepare_cached("DELETE FROM paths WHERE deleted_at < (?1)")?
+                .execute([before])?;
+            info!("{} paths purged from the trash", purged);
+            Ok(purged)
+        })
+    }
+
+    /// Lists paths from the database with pagination and optional filtering.
+    /// The results are ordered by date (descending) and ID (descending).
+    /// If `like_text` is provided, only paths containing the text are returned.
+    ///
+    /// ### Parameters
+    /// pos: the starting position (offset) for pagination
+    /// len: the number of paths to return
+    /// like_text: optional text to filter paths (if empty, no filtering is applied)
+    ///
+    /// ### Returns
+    /// A vector of Path entries if the operation was successful, otherwise an error.
+    pub fn list_paths(
+        &self,
+        pos: usize,
+        len: usize,
+        like_text: &str,
+        fuzzy: bool,
+    ) -> Result<Vec<Path>, rusqlite::Error> {
+        self.list_paths_sorted(pos, len, like_text, fuzzy, SortOrder::Default, true)
+    }
+
+    /// Lists paths from the database like `list_paths`, with a specific ordering.
+    /// The smart suggestions are only included when asked, with the default ordering.
+    ///
+    /// ### Parameters
+    /// pos: the starting position (offset) for pagination
+    /// len: the number of paths to return
+    /// like_text: optional text to filter paths (if empty, no filtering is applied)
+    /// fuzzy: if true, perform a fuzzy search
+    /// sort: the ordering of the results (only the date and path columns apply)
+    /// include_suggestions: list the smart suggestions of the current directory on top of the
+    /// history, when they apply; they are paths of the history, listed twice then
+    ///
+    /// ### Returns
+    /// A vector of Path entries if the operation was successful, otherwise an error.
+    pub fn list_paths_sorted(
+        &self,
+        pos: usize,
+        len: usize,
+        like_text: &str,
+        fuzzy: bool,
+        sort: SortOrder,
+        include_suggestions: bool,
+    ) -> Result<Vec<Path>, rusqlite::Error> {
+        debug!(
+            "list_paths pos={} len={} like_text={} fuzzy={} sort={:?} include_suggestions={}",
+            pos, len, like_text, fuzzy, sort, include_suggestions
+        );
+        // Retrieve all shortcuts to associate with paths
+        let shortcuts = self.list_all_shortcuts().unwrap_or_default();
+        let (like_text, tokens) = self.split_search_tokens(like_text)?;
+        let like_text = like_text.as_str();
+        if let Some((parent, filter)) = Self::shortcut_relative_search(like_text, &shortcuts) {
+            let paths = self.list_shortcut_relative(&parent, &filter, &tokens, &shortcuts, sort)?;
+            return Ok(paths.into_iter().skip(pos).take(len).collect());
+        }
+        if like_text.is_empty() || !fuzzy {
+            let smart_rows = match include_suggestions {
+                true => self.list_smart_rows(like_text, &tokens, &shortcuts, sort),
+                false => vec![],
+            };
+            let (mut paths, pos, len) = Self::page_smart_rows(smart_rows, pos, len);
+            paths.extend(self.list_path_exact(pos, len, like_text, &tokens, &shortcuts, sort)?);
+            debug!("final_row len={}", paths.len());
+            Ok(paths)
+        } else {
+            self.list_path_fuzzy(pos, len, like_text, &tokens, &shortcuts, sort)
+        }
+    }
+
+    /// Lists the paths matching a stack of filters, ordered like the results of the last one.
+    /// With several levels, the smart suggestions are not listed.
+    ///
+    /// ### Parameters
+    /// pos: the starting position (offset) for pagination
+    /// len: the number of paths to return
+    /// filters: the filters the paths must all match, the last one being the current search
+    /// sort: the ordering of the results
+    #[instrument(level = "debug", skip(self, filters), fields(levels = filters.len()))]
+    pub fn list_paths_filtered(
+        &self,
+        pos: usize,
+        len: usize,
+        filters: &[Filter],
+        sort: SortOrder,
+    ) -> Result<Vec<Path>, rusqlite::Error> {
+        let [frozen @ .., current] = filters else {
+            return self.list_paths_sorted(pos, len, "", false, sort, true);
+        };
+        if frozen.is_empty() {
+            return self.list_paths_sorted(pos, len, &current.text, current.fuzzy, sort, true);
+        }
+        let ids = self.narrowed_path_ids(filters, sort)?;
+        let ids: Vec<i64> = ids.into_iter().skip(pos).take(len).collect();
+        let shortcuts = self.list_all_shortcuts().unwrap_or_default();
+        self.list_paths_by_ids(&ids, &shortcuts)
+    }
+
+    /// Counts the paths `list_paths_filtered` returns.
+    #[instrument(level = "debug", skip_all, fields(levels = filters.len()))]
+    pub fn count_paths_filtered(&self, filters: &[Filter]) -> Result<usize, rusqlite::Error> {
+        match filters {
+            [] => self.count_paths("", false),
+            [current] => self.count_paths(&current.text, current.fuzzy),
+            _ => self
+                .narrowed_path_ids(filters, SortOrder::Default)
+                .map(|ids| ids.len()),
+        }
+    }
+
+    /// The ids of the paths of the history matching all the filters, in the order of the results
+    /// of the last one.
+    fn narrowed_path_ids(
+        &self,
+        filters: &[Filter],
+        sort: SortOrder,
+    ) -> Result<Vec<i64>, rusqlite::Error> {
+        let path_ids = |filter: &Filter, sort| -> Result<Vec<i64>, rusqlite::Error> {
+            Ok(self
+                .list_paths_sorted(0, ALL_ENTRIES, &filter.text, filter.fuzzy, sort, false)?
+                .into_iter()
+                .map(|path| path.id)
+                .collect())
+        };
+        let mut ids = match filters.last() {
+            Some(current) => path_ids(current, sort)?,
+            None => return Ok(vec![]),
+        };
+        for filter in &filters[..filters.len() - 1] {
+            let allowed: HashSet<i64> = path_ids(filter, SortOrder::Default)?.into_iter().collect();
+            ids.retain(|id| allowed.contains(id));
+        }
+        Ok(ids)
+    }
+
+    /// Extracts the `host:<name>` token from a search text.
+    ///
+    /// ### Returns
+    /// The search text without the token, and the host name if any.
+    pub fn split_host_filter(like_text: &str) -> (String, Option<String>) {
+        let mut host = None;
+        let mut words = vec![];
+        for word in like_text.split(' ') {
+            match word.strip_prefix(HOST_FILTER_PREFIX) {
+                Some(name) if !name.is_empty() => host = Some(name.to_string()),
+                _ => words.push(word),
+            }
+        }
+        if host.is_none() {
+            return (like_text.to_string(), None);
+        }
+        (words.join(" ").trim().to_string(), host)
+    }
+
+    /// Extracts the `host:<name>` and `type:<name>` tokens from a search text, the types of the
+    /// projects being detected again when a type is searched, see `refresh_project_types`.
+    ///
+    /// ### Returns
+    /// The search text without the tokens, and the tokens.
+    fn split_search_tokens(
+        &self,
+        like_text: &str,
+    ) -> Result<(String, SearchTokens), rusqlite::Error> {
+        let (like_text, host) = Self::split_host_filter(like_text);
+        let mut project_type = None;
+        let mut words = vec![];
+        for word in like_text.split(' ') {
+            match word
+                .strip_prefix(TYPE_FILTER_PREFIX)
+                .and_then(ProjectType::from_name)
+            {
+                Some(found) => project_type = Some(found),
+                None => words.push(word),
+            }
+        }
+        if project_type.is_some() {
+            self.refresh_project_types()?;
+        }
+        Ok((
+            words.join(" ").trim().to_string(),
+            SearchTokens { host, project_type },
+        ))
+    }
+
+    /// Translates a search text containing a `*` into the patterns of SQL GLOB clauses, a path
+    /// matching if it matches any of them. Only `*` makes a glob pattern, `?` and `[...]` being
+    /// wildcards within it, so that a search for e.g. `[draft]` stays a substring search.
+    /// `~` is expanded to the home directory, `**/` matches any number of directories (each
+    /// occurrence doubling the patterns, with and without a directory, up to
+    /// `MAX_GLOB_PATTERNS`), `*` matches any characters including `/`, and a pattern which does
+    /// not start with `/` matches from the start of any segment of the path, e.g. `api*` matches
+    /// `/home/user/work/api-server`.
+    ///
+    /// ### Returns
+    /// The GLOB patterns, empty if the text is not a glob pattern.
+    pub fn glob_patterns(like_text: &str) -> Vec<String> {
+        if !like_text.contains('*') {
+            return vec![];
+        }
+        let pattern = match like_text.strip_prefix('~') {
+            Some(rest) if rest.is_empty() || rest.starts_with('/') => match dirs::home_dir() {
+                Some(home) => format!("{}{}", home.to_string_lossy(), rest),
+                None => like_text.to_string(),
+            },
+            _ => like_text.to_string(),
+        };
+        let mut pattern = if pattern.starts_with('/') {
+            pattern
+        } else {
+            format!("*/{}", pattern.trim_start_matches("**/"))
+        };
+        // `**/**/` matches the same directories as `**/`
+        while pattern.contains("/**/**/") {
+            pattern = pattern.replace("/**/**/", "/**/");
+        }
+        let mut patterns = vec![pattern];
+        while patterns[0].contains("/**/") && patterns.len() * 2 <= MAX_GLOB_PATTERNS {
+            patterns = patterns
+                .iter()
+                .flat_map(|p| [p.replacen("/**/", "/", 1), p.replacen("/**/", "/*/", 1)])
+                .collect();
+        }
+        let mut globs: Vec<String> = vec![];
+        for mut p in patterns {
+            // past the limit, the `**/` left match any directories or the rest of a name
+            p = p.replace("/**/", "/*");
+            while p.contains("**") {
+                p = p.replace("**", "*");
+            }
+            if !globs.contains(&p) {
+                globs.push(p);
+            }
+        }
+        globs
+    }
+
+    /// The SQL condition of a search of the paths by the GLOB patterns of `glob_patterns`, the
+    /// patterns being appended to `params`. GLOB respecting the case, both sides are lowercased
+    /// unless `case_sensitive` is set.
+    fn glob_condition(
+        patterns: Vec<String>,
+        case_sensitive: bool,
+        params: &mut Vec<String>,
+    ) -> String {
+        let mut clauses = vec![];
+        for pattern in patterns {
+            if case_sensitive {
+                params.push(pattern);
+                clauses.push(format!("path GLOB (?{})", params.len()));
+            } else {
+                params.push(pattern.to_lowercase());
+                clauses.push(format!("lower(path) GLOB (?{})", params.len()));
+            }
+        }
+        format!("({})", clauses.join(" OR "))
+    }
+
+    /// Scores a path for fuzzy search with the provided matcher.
     /// The score is calculated as the maximum of:
     /// - The path itself
-    /// - For each parent shortcut: a concatenation of the path, shortcut name, and description
+    /// - For each parent shortcut: a concatenation of the path, shortcut name, and description,
+    ///   plus the `shortcut_name_bonus` when the shortcut name matches on its own
     ///
     /// ### Parameters
     /// path: the path to score
-    /// matches: the pattern to match against
-    /// matcher: the matcher to use for matching
-    /// buf: a buffer to use for UTF-32 conversion
+    /// matcher: the matcher of the search text
     /// shortcuts: all available shortcuts for scoring parent shortcuts
+    /// fuzzy_config: the bonuses to apply
     ///
     /// ### Returns
     /// Some(score) if there is a match, otherwise None.
     fn score_path_for_fuzzy_search(
         &self,
         path: &Path,
-        matches: &Pattern,
-        matcher: &mut Matcher,
-        buf: &mut Vec<char>,
+        matcher: &mut dyn Matcher,
         shortcuts: &[Shortcut],
+        fuzzy_config: &FuzzyConfig,
     ) -> Option<u32> {
         // Score the path itself
-        let path_str = Utf32Str::new(path.path.as_str(), buf);
-        let mut max_score = matches.score(path_str, matcher);
+        let mut max_score = matcher.score(&path.path);
 
         trace!("Scoring path '{}' initial score={:?}", path.path, max_score);
 
@@ -493,8 +1727,8 @@ impl Store {
             }
 
             // Score the combined string
-            let combined_str = Utf32Str::new(&combined, buf);
-            if let Some(score) = matches.score(combined_str, matcher) {
+            if let Some(score) = matcher.score(&combined) {
+                let score = score + Self::shortcut_name_bonus(matcher, shortcut, fuzzy_config);
                 trace!("Scoring combined '{}' score={:?}", combined, score);
                 max_score = Some(max_score.map_or(score, |m| m.max(score)));
             }
@@ -503,48 +1737,117 @@ impl Store {
         max_score
     }
 
+    /// The score of a path for a fuzzy search, as used to rank the results of the search,
+    /// the `host:` and `type:` tokens of the search being ignored.
+    ///
+    /// ### Returns
+    /// Some(score) if the path matches the search, None otherwise or when the search is empty.
+    pub fn fuzzy_score(&self, path: &str, search: &str) -> Result<Option<u32>, rusqlite::Error> {
+        let (like_text, _) = self.split_search_tokens(search)?;
+        if like_text.is_empty() {
+            return Ok(None);
+        }
+        let shortcuts = self.list_all_shortcuts()?;
+        let fuzzy_config = self.config.lock().unwrap().fuzzy.clone();
+        let mut matcher = matcher::build(
+            &fuzzy_config,
+            &like_text,
+            self.is_case_sensitive(&like_text),
+        );
+        let path = Path::new(0, path.to_string(), 0, &shortcuts);
+        Ok(self.score_path_for_fuzzy_search(&path, matcher.as_mut(), &shortcuts, &fuzzy_config))
+    }
+
     fn list_path_fuzzy(
         &self,
         pos: usize,
         len: usize,
         like_text: &str,
+        tokens: &SearchTokens,
         shortcuts: &[Shortcut],
+        sort: SortOrder,
     ) -> Result<Vec<Path>, rusqlite::Error> {
         debug!(
             "list_path_fuzzy pos={} len={} like_text={}",
             pos, len, like_text
         );
+        let ids = self.fuzzy_path_ids(like_text, tokens, shortcuts, sort)?;
+        // Paginate: skip `pos`, take `len`
+        let ids: Vec<i64> = ids.iter().skip(pos).take(len).copied().collect();
+        self.list_paths_by_ids(&ids, shortcuts)
+    }
+
+    /// The ids of the paths matching a fuzzy search, in the order of the results. They are
+    /// cached until the search or the data changes, so that paging does not score the paths
+    /// again.
+    fn fuzzy_path_ids(
+        &self,
+        like_text: &str,
+        tokens: &SearchTokens,
+        shortcuts: &[Shortcut],
+        sort: SortOrder,
+    ) -> Result<Rc<Vec<i64>>, rusqlite::Error> {
+        let key = self.fuzzy_search_key(like_text, tokens, sort)?;
+        self.cached_fuzzy_ids("paths", key, || {
+            self.score_paths(like_text, tokens, shortcuts, sort)
+        })
+    }
 
-        let sql = String::from("SELECT id, path, date FROM paths ORDER BY date desc, id desc");
-        let mut stmt = match self.db_conn.prepare(sql.as_str()) {
+    /// Scores all the paths against a fuzzy search.
+    ///
+    /// ### Returns
+    /// The ids of the matching paths, in the order of the results.
+    fn score_paths(
+        &self,
+        like_text: &str,
+        tokens: &SearchTokens,
+        shortcuts: &[Shortcut],
+        sort: SortOrder,
+    ) -> Result<Vec<i64>, rusqlite::Error> {
+        debug!("score_paths like_text={}", like_text);
+        let mut sql = String::from(
+            "SELECT id, path, date, hostname, \
+            (SELECT COUNT(*) FROM paths_history WHERE paths_history.path = paths.path) FROM paths",
+        );
+        let mut params: Vec<String> = vec![];
+        let conditions = self.history_conditions(tokens, &mut params);
+        if !conditions.is_empty() {
+            sql.push_str(&format!(" WHERE {}", conditions.join(" AND ")));
+        }
+        sql.push_str(" ORDER BY date desc, id desc");
+        let mut stmt = match self.db_conn.prepare_cached(sql.as_str()) {
             Ok(stmt) => stmt,
             Err(e) => {
                 error!("list_paths failed in prepare {}: {}", sql, e);
                 return Err(e);
             }
         };
-        let params: Vec<String> = vec![];
-        let mut matcher = Matcher::new(nucleo_matcher::Config::DEFAULT.match_paths());
-        let matches = Pattern::parse(like_text, CaseMatching::Ignore, Normalization::Smart);
-        let mut buf = Vec::new();
+        let fuzzy_config = self.config.lock().unwrap().fuzzy.clone();
+        let mut matcher =
+            matcher::build(&fuzzy_config, like_text, self.is_case_sensitive(like_text));
 
         let rows = stmt.query_map(rusqlite::params_from_iter(params), |row| {
             let path_str: String = row.get(1)?;
-            Ok(Path::new(row.get(0)?, path_str, row.get(2)?, shortcuts))
+            Ok((
+                Path::new(row.get(0)?, path_str, row.get(2)?, shortcuts).with_hostname(row.get(3)?),
+                row.get(4)?,
+            ))
         });
 
-        // Build the (Path, score) pairs
-        let mut scored_paths: Vec<(Path, u32)> = match rows {
+        let mut scored_paths: Vec<ScoredPath> = match rows {
             Ok(rows) => rows.filter_map(|row| {
-                if let Ok(path) = row {
+                if let Ok((path, visits)) = row {
                     self.score_path_for_fuzzy_search(
                         &path,
-                        &matches,
-                        &mut matcher,
-                        &mut buf,
+                        matcher.as_mut(),
                         shortcuts,
+                        &fuzzy_config,
                     )
-                    .map(|score| (path, score))
+                    .map(|score| ScoredPath {
+                        path,
+                        score,
+                        visits,
+                    })
                 } else {
                     None
                 }
@@ -555,109 +1858,832 @@ impl Store {
             }
         }
         .collect();
+        Self::blend_recency_and_frequency(&mut scored_paths, &fuzzy_config);
 
-        // Sort by descending score
-        scored_paths.sort_by(|a, b| b.1.cmp(&a.1));
+        match sort {
+            SortOrder::Default => scored_paths.sort_by(ScoredPath::compare),
+            SortOrder::Ascending(column) => {
+                scored_paths.sort_by(|a, b| Self::compare_paths(column, &a.path, &b.path))
+            }
+            SortOrder::Descending(column) => {
+                scored_paths.sort_by(|a, b| Self::compare_paths(column, &b.path, &a.path))
+            }
+        }
 
-        // Paginate: skip `pos`, take `len`
-        let paginated = scored_paths
+        Ok(scored_paths
             .into_iter()
-            .skip(pos)
-            .take(len)
-            .map(|(path, _)| path)
-            .collect();
-
-        Ok(paginated)
+            .map(|scored| scored.path.id)
+            .collect())
     }
 
-    fn build_list_path_exact_sql_statement(
-        &self,
-        pos: usize,
-        len: usize,
-        like_text: &str,
-        shortcuts: &[Shortcut],
-    ) -> (String, Vec<String>) {
-        let mut params: Vec<String> = vec![];
-        let mut sql = String::from("SELECT id, path, date FROM paths");
-        if !like_text.is_empty() {
-            // Find shortcuts where name or description matches the like_text
-            let like_lower = like_text.to_lowercase();
-
-            if self.config.lock().unwrap().path_search_include_shortcuts {
-                let matching_shortcut_paths: Vec<&str> = shortcuts
-                    .iter()
-                    .filter(|s| {
-                        s.name.to_lowercase().contains(&like_lower)
-                            || s.description
-                                .as_ref()
-                                .is_some_and(|d| d.to_lowercase().contains(&like_lower))
-                    })
-                    .map(|s| s.path.as_str())
-                    .collect();
-
-                // Build WHERE clause: path matches like_text OR path starts with any matching shortcut path
-                sql.push_str(" WHERE (path LIKE '%' || (?1) || '%'");
-                params.push(like_text.to_string());
-
-                // Add OR conditions for each matching shortcut's path
-                for (i, shortcut_path) in matching_shortcut_paths.iter().enumerate() {
-                    let param_idx = i + 2; // +2 because ?1 is like_text
-                    sql.push_str(&format!(" OR path == (?{})", param_idx));
-                    sql.push_str(&format!(" OR path LIKE (?{}) || '/' || '%'", param_idx));
-                    params.push(shortcut_path.to_string());
-                }
-                sql.push(')');
-            } else {
-                sql.push_str(" WHERE path LIKE '%' || (?1) || '%'");
-                params.push(like_text.to_string());
+    /// Adds to the match scores the configured boosts, shared between the paths proportionally
+    /// to their date and to their number of visits: the most recent path gets all of the
+    /// recency boost and the oldest nothing, the most visited path gets all of the frequency
+    /// boost.
+    fn blend_recency_and_frequency(scored_paths: &mut [ScoredPath], fuzzy_config: &FuzzyConfig) {
+        let dates = scored_paths.iter().map(|scored| scored.path.date);
+        let (Some(oldest), Some(newest)) = (dates.clone().min(), dates.max()) else {
+            return;
+        };
+        let most_visits = scored_paths
+            .iter()
+            .map(|scored| scored.visits)
+            .max()
+            .unwrap_or_default();
+        for scored in scored_paths.iter_mut() {
+            if newest > oldest {
+                scored.score += (fuzzy_config.recency_boost as i64 * (scored.path.date - oldest)
+                    / (newest - oldest)) as u32;
             }
+            if most_visits > 0 {
+                scored.score +=
+                    (fuzzy_config.frequency_boost as i64 * scored.visits / most_visits) as u32;
+            }
+        }
+    }
 
-            let limit_idx = params.len() + 1;
-            let offset_idx = params.len() + 2;
-            sql.push_str(&format!(
-                " ORDER BY date desc, id desc LIMIT (?{}) OFFSET (?{})",
-                limit_idx, offset_idx
-            ));
+    /// The bonus of a shortcut whose name matches the search on its own
+    fn shortcut_name_bonus(
+        matcher: &mut dyn Matcher,
+        shortcut: &Shortcut,
+        fuzzy_config: &FuzzyConfig,
+    ) -> u32 {
+        if fuzzy_config.shortcut_name_bonus > 0 && matcher.score(&shortcut.name).is_some() {
+            fuzzy_config.shortcut_name_bonus
         } else {
-            sql.push_str(" ORDER BY date desc, id desc LIMIT (?1) OFFSET (?2)");
+            0
         }
-        params.push(format!("{}", len));
-        params.push(format!("{}", pos));
-
-        debug!("list_path_exact sql={} params={:?}", sql, params);
-        (sql, params)
     }
 
-    fn list_path_exact(
+    /// The paths of the given ids, in the same order
+    fn list_paths_by_ids(
         &self,
-        pos: usize,
-        len: usize,
-        like_text: &str,
+        ids: &[i64],
         shortcuts: &[Shortcut],
     ) -> Result<Vec<Path>, rusqlite::Error> {
-        debug!(
-            "list_path_exact pos={} len={} like_text={}",
-            pos, len, like_text
+        if ids.is_empty() {
+            return Ok(vec![]);
+        }
+        let sql = format!(
+            "SELECT id, path, date, hostname FROM paths WHERE id IN ({})",
+            vec!["?"; ids.len()].join(", ")
         );
-        let mut pos = pos;
-        let mut len = len;
+        let mut stmt = self.db_conn.prepare_cached(&sql)?;
+        let mut paths: HashMap<i64, Path> = stmt
+            .query_map(rusqlite::params_from_iter(ids), |row| {
+                let path_str: String = row.get(1)?;
+                Ok(Path::new(row.get(0)?, path_str, row.get(2)?, shortcuts)
+                    .with_hostname(row.get(3)?))
+            })?
+            .map(|path| path.map(|path| (path.id, path)))
+            .collect::<Result<_, _>>()?;
+        Ok(ids.iter().filter_map(|id| paths.remove(id)).collect())
+    }
+
+    /// Whether the paths are listed without any of the filters toggled from the GUI: the pane
+    /// filter, the sensitive paths, the trash and the suggestions only
+    fn is_default_listing(&self) -> bool {
+        self.pane_filter().is_none()
+            && !self.reveal_sensitive()
+            && !self.show_trash()
+            && !self.suggestions_only()
+    }
+
+    /// Persists the ids of the paths of the first page of the history, replacing the previous
+    /// ones, so that the next start of the GUI shows them at once, see `warm_start_page`.
+    /// Nothing is persisted while a filter is toggled.
+    pub fn save_warm_start_page(&self, ids: &[i64]) -> Result<(), rusqlite::Error> {
+        if !self.is_default_listing() {
+            return Ok(());
+        }
+        let saved: Vec<i64> = self
+            .db_conn
+            .prepare_cached("SELECT id FROM warm_start_page ORDER BY position")?
+            .query_map([], |row| row.get(0))?
+            .collect::<Result<_, _>>()?;
+        if saved == ids {
+            return Ok(());
+        }
+        self.retry_busy(|| {
+            debug!("save_warm_start_page {} ids", ids.len());
+            let transaction = match self.db_conn.is_autocommit() {
+                true => Some(self.transaction()?),
+                false => None,
+            };
+            self.db_conn
+                .prepare_cached("DELETE FROM warm_start_page")?
+                .execute([])?;
+            let mut stmt = self
+                .db_conn
+                .prepare_cached("INSERT INTO warm_start_page (position, id) VALUES (?1, ?2)")?;
+            for (position, id) in ids.iter().enumerate() {
+                stmt.execute(params![position as i64, id])?;
+            }
+            transaction.map_or(Ok(()), |transaction| transaction.commit())
+        })
+    }
+
+    /// The paths of the first page of the history persisted by `save_warm_start_page`, in the
+    /// same order, without those deleted or hidden since; empty while a filter is toggled.
+    pub fn warm_start_page(&self) -> Result<Vec<Path>, rusqlite::Error> {
+        if !self.is_default_listing() {
+            return Ok(vec![]);
+        }
+        let shortcuts = self.list_all_shortcuts().unwrap_or_default();
+        let paths: Vec<Path> = self
+            .db_conn
+            .prepare_cached(
+                "SELECT p.id, p.path, p.date, p.hostname FROM warm_start_page w \
+                 JOIN paths p ON p.id = w.id WHERE p.deleted_at IS NULL ORDER BY w.position",
+            )?
+            .query_map([], |row| {
+                let path_str: String = row.get(1)?;
+                Ok(Path::new(row.get(0)?, path_str, row.get(2)?, &shortcuts)
+                    .with_hostname(row.get(3)?))
+            })?
+            .collect::<Result<_, _>>()?;
+        let mut visible = Vec::with_capacity(paths.len());
+        for path in paths {
+            if !self.is_hidden_sensitive(&path.path)? {
+                visible.push(path);
+            }
+        }
+        Ok(visible)
+    }
 
-        let mut smart_rows = vec![];
-        if self.config.lock().unwrap().smart_suggestions_active && like_text.is_empty() {
-            // get current working directory
-            let cwd = std::env::current_dir().unwrap();
+    /// What the results of a fuzzy search depend on
+    fn fuzzy_search_key(
+        &self,
+        like_text: &str,
+        tokens: &SearchTokens,
+        sort: SortOrder,
+    ) -> Result<FuzzySearchKey, rusqlite::Error> {
+        let data_version: i64 = self
+            .db_conn
+            .query_row("PRAGMA data_version", [], |row| row.get(0))?;
+        let (settings, fuzzy_config) = {
             let config_lock = self.config.lock().unwrap();
-            smart_rows = self
-                .list_path_history_smart_suggestions(
-                    cwd.to_str().unwrap(),
-                    config_lock.smart_suggestions_depth,
-                    config_lock.smart_suggestions_count,
-                    shortcuts,
+            (
+                (
+                    config_lock.path_search_include_shortcuts,
+                    config_lock.current_host_only,
+                    config_lock.mix_container_history,
+                ),
+                config_lock.fuzzy.clone(),
+            )
+        };
+        Ok(FuzzySearchKey {
+            like_text: like_text.to_string(),
+            tokens: tokens.clone(),
+            sort,
+            pane_filter: self.pane_filter(),
+            reveal_sensitive: self.reveal_sensitive(),
+            show_trash: self.show_trash(),
+            suggestions_only: self.suggestions_only(),
+            case_sensitive: self.is_case_sensitive(like_text),
+            settings,
+            fuzzy_config,
+            total_changes: self.db_conn.total_changes(),
+            data_version,
+        })
+    }
+
+    /// The ids of the last fuzzy search of `table` when it was made with the same `key`, else
+    /// the ones computed by `score`, which are cached.
+    fn cached_fuzzy_ids(
+        &self,
+        table: &'static str,
+        key: FuzzySearchKey,
+        score: impl FnOnce() -> Result<Vec<i64>, rusqlite::Error>,
+    ) -> Result<Rc<Vec<i64>>, rusqlite::Error> {
+        if let Some(cache) = self.fuzzy_cache.borrow().get(table)
+            && cache.key == key
+        {
+            trace!("fuzzy search of the {} served from the cache", table);
+            return Ok(cache.ids.clone());
+        }
+        let ids = Rc::new(score()?);
+        self.fuzzy_cache.borrow_mut().insert(
+            table,
+            FuzzySearchCache {
+                key,
+                ids: ids.clone(),
+            },
+        );
+        Ok(ids)
+    }
+
+    /// Forgets the results of the previous fuzzy searches, e.g. when the data is reloaded.
+    pub fn clear_fuzzy_cache(&self) {
+        debug!("clear_fuzzy_cache");
+        self.fuzzy_cache.borrow_mut().clear();
+    }
+
+    /// The number of visits of each path, counted once and kept until `clear_visit_counts`.
+    pub fn visit_counts(&self) -> Rc<VisitCounts> {
+        if let Some(visit_counts) = self.visit_counts.borrow().as_ref() {
+            return Rc::clone(visit_counts);
+        }
+        let visit_counts = Rc::new(self.count_visits().unwrap_or_else(|e| {
+            error!("Failed to count the visits: {}", e);
+            VisitCounts::default()
+        }));
+        self.visit_counts.replace(Some(Rc::clone(&visit_counts)));
+        visit_counts
+    }
+
+    fn count_visits(&self) -> Result<VisitCounts, rusqlite::Error> {
+        let mut stmt = self
+            .db_conn
+            .prepare_cached("SELECT path, COUNT(*) FROM paths_history GROUP BY path")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)? as usize))
+        })?;
+        Ok(VisitCounts::new(rows.collect::<Result<_, _>>()?))
+    }
+
+    /// The number of visits of `path` in each of the last `weeks` weeks, the oldest week first.
+    pub fn weekly_visits(&self, path: &str, weeks: usize) -> Result<Vec<u64>, rusqlite::Error> {
+        debug!("weekly_visits path={} weeks={}", path, weeks);
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+        let mut histogram = vec![0; weeks];
+        let mut stmt = self.db_conn.prepare_cached(
+            "SELECT ((?2) - date) / (?3) AS week, COUNT(*) FROM paths_history \
+             WHERE path = (?1) AND date <= (?2) GROUP BY week HAVING week < (?4)",
+        )?;
+        let rows = stmt.query_map(params![path, now, WEEK, weeks as i64], |row| {
+            Ok((row.get::<_, i64>(0)? as usize, row.get::<_, i64>(1)? as u64))
+        })?;
+        for row in rows {
+            let (week, count) = row?;
+            histogram[weeks - 1 - week] = count;
+        }
+        Ok(histogram)
+    }
+
+    /// Lists the paths visited the most since `since` (in seconds since EPOCH), the sensitive
+    /// paths being left out.
+    ///
+    /// ### Returns
+    /// At most `limit` paths and their number of visits, the most visited first, if the
+    /// operation was successful, otherwise an error.
+    pub fn list_top_paths_since(
+        &self,
+        since: i64,
+        limit: usize,
+    ) -> Result<Vec<(String, usize)>, rusqlite::Error> {
+        debug!("list_top_paths_since since={} limit={}", since, limit);
+        self.db_conn
+            .prepare_cached(
+                "SELECT path, COUNT(*) AS visits FROM paths_history WHERE date >= (?1) \
+                 AND path NOT IN (SELECT path FROM paths WHERE sensitive != 0) \
+                 GROUP BY path ORDER BY visits DESC, path ASC LIMIT (?2)",
+            )?
+            .query_map(params![since, limit as i64], |row| {
+                Ok((row.get(0)?, row.get::<_, i64>(1)? as usize))
+            })?
+            .collect()
+    }
+
+    /// Lists the dates (in seconds since EPOCH) of the visits since `since`, the oldest first,
+    /// the visits of the sensitive paths being left out.
+    pub fn list_visit_dates_since(&self, since: i64) -> Result<Vec<i64>, rusqlite::Error> {
+        debug!("list_visit_dates_since since={}", since);
+        self.db_conn
+            .prepare_cached(
+                "SELECT date FROM paths_history WHERE date >= (?1) \
+                 AND path NOT IN (SELECT path FROM paths WHERE sensitive != 0) ORDER BY date, id",
+            )?
+            .query_map([since], |row| row.get(0))?
+            .collect()
+    }
+
+    /// Lists the visits of the history, the oldest first. The visits of the paths in the trash
+    /// are left out, and the ones of the sensitive paths unless they are revealed.
+    ///
+    /// ### Returns
+    /// The paths and the dates (in seconds since EPOCH) of their visits if the operation was
+    /// successful, otherwise an error.
+    pub fn list_history(&self) -> Result<Vec<(String, i64)>, rusqlite::Error> {
+        debug!("list_history");
+        let sensitive = match self.reveal_sensitive() {
+            true => "",
+            false => " AND path NOT IN (SELECT path FROM paths WHERE sensitive != 0)",
+        };
+        let sql = format!(
+            "SELECT path, date FROM paths_history \
+             WHERE path IN (SELECT path FROM paths WHERE deleted_at IS NULL){} ORDER BY date, id",
+            sensitive
+        );
+        self.db_conn
+            .prepare_cached(&sql)?
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect()
+    }
+
+    /// Lists the visits since `since` (in seconds since EPOCH), the oldest first, each one with
+    /// the shortcut containing its path and the host it was made from. The visits of the paths
+    /// in the trash are left out, and the ones of the sensitive paths unless they are revealed.
+    pub fn list_history_since(&self, since: i64) -> Result<Vec<Path>, rusqlite::Error> {
+        debug!("list_history_since since={}", since);
+        let shortcuts = self.list_all_shortcuts().unwrap_or_default();
+        let sensitive = match self.reveal_sensitive() {
+            true => "",
+            false => " AND path NOT IN (SELECT path FROM paths WHERE sensitive != 0)",
+        };
+        let sql = format!(
+            "SELECT id, path, date, hostname FROM paths_history WHERE date >= (?1) \
+             AND path IN (SELECT path FROM paths WHERE deleted_at IS NULL){} ORDER BY date, id",
+            sensitive
+        );
+        self.db_conn
+            .prepare_cached(&sql)?
+            .query_map([since], |row| {
+                Ok(Path::new(row.get(0)?, row.get(1)?, row.get(2)?, &shortcuts)
+                    .with_hostname(row.get(3)?))
+            })?
+            .collect()
+    }
+
+    /// Lists the last `limit` sessions of the history, a new session starting after a gap of
+    /// more than `idle_threshold` seconds between two visits. The visits of the paths in the
+    /// trash are left out, and the ones of the sensitive paths unless they are revealed.
+    ///
+    /// ### Returns
+    /// The sessions, the most recent first, if the operation was successful, otherwise an error.
+    pub fn list_sessions(
+        &self,
+        idle_threshold: i64,
+        limit: usize,
+    ) -> Result<Vec<Session>, rusqlite::Error> {
+        debug!(
+            "list_sessions idle_threshold={} limit={}",
+            idle_threshold, limit
+        );
+        let sensitive = match self.reveal_sensitive() {
+            true => "",
+            false => " AND path NOT IN (SELECT path FROM paths WHERE sensitive != 0)",
+        };
+        // the first visit of a session is the one following a gap, or the first one
+        let sql = format!(
+            "WITH visits AS (SELECT id, path, date, \
+             CASE WHEN date - LAG(date) OVER (ORDER BY date, id) <= (?1) THEN 0 ELSE 1 END AS first \
+             FROM paths_history \
+             WHERE path IN (SELECT path FROM paths WHERE deleted_at IS NULL){}), \
+             sessions AS (SELECT id, path, date, SUM(first) OVER (ORDER BY date, id) AS session \
+             FROM visits) \
+             SELECT session, path, date FROM sessions \
+             WHERE session > (SELECT MAX(session) FROM sessions) - (?2) ORDER BY date, id",
+            sensitive
+        );
+        let mut stmt = self.db_conn.prepare_cached(&sql)?;
+        let visits = stmt.query_map(params![idle_threshold, limit as i64], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, i64>(2)?,
+            ))
+        })?;
+        let mut sessions: Vec<(i64, Session)> = vec![];
+        for visit in visits {
+            let (id, path, date) = visit?;
+            match sessions.last_mut() {
+                Some((last_id, session)) if *last_id == id => {
+                    session.end = date;
+                    if session.paths.last() != Some(&path) {
+                        session.paths.push(path);
+                    }
+                }
+                _ => sessions.push((
+                    id,
+                    Session {
+                        start: date,
+                        end: date,
+                        paths: vec![path],
+                    },
+                )),
+            }
+        }
+        Ok(sessions
+            .into_iter()
+            .rev()
+            .map(|(_, session)| session)
+            .collect())
+    }
+
+    /// Lists the paths visited for the first time since `since` (in seconds since EPOCH), the
+    /// sensitive paths being left out.
+    ///
+    /// ### Returns
+    /// The paths and the date of their first visit, the oldest first, if the operation was
+    /// successful, otherwise an error.
+    pub fn list_paths_discovered_since(
+        &self,
+        since: i64,
+    ) -> Result<Vec<(String, i64)>, rusqlite::Error> {
+        debug!("list_paths_discovered_since since={}", since);
+        self.db_conn
+            .prepare_cached(
+                "SELECT path, MIN(date) AS first FROM paths_history \
+                 WHERE path NOT IN (SELECT path FROM paths WHERE sensitive != 0) \
+                 GROUP BY path HAVING first >= (?1) ORDER BY first ASC, path ASC",
+            )?
+            .query_map([since], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect()
+    }
+
+    /// Lists the shortcuts created since `since` (in seconds since EPOCH), the shortcuts created
+    /// before their creation date was recorded being left out.
+    ///
+    /// ### Returns
+    /// The shortcuts and their creation date, the oldest first, if the operation was
+    /// successful, otherwise an error.
+    pub fn list_shortcuts_created_since(
+        &self,
+        since: i64,
+    ) -> Result<Vec<(Shortcut, i64)>, rusqlite::Error> {
+        debug!("list_shortcuts_created_since since={}", since);
+        self.db_conn
+            .prepare_cached(
+                "SELECT id, name, path, description, remote, created FROM shortcuts \
+                 WHERE created >= (?1) ORDER BY created ASC, name ASC",
+            )?
+            .query_map([since], |row| {
+                Ok((
+                    Shortcut {
+                        id: row.get(0)?,
+                        name: row.get(1)?,
+                        path: row.get(2)?,
+                        description: row.get(3)?,
+                        remote: row.get(4)?,
+                    },
+                    row.get(5)?,
+                ))
+            })?
+            .collect()
+    }
+
+    /// Count the visits again on the next `visit_counts`, e.g. once the history changed.
+    pub fn clear_visit_counts(&self) {
+        debug!("clear_visit_counts");
+        self.visit_counts.replace(None);
+    }
+
+    /// Only lists the paths visited from `tmux_pane`, or all of them when None.
+    pub fn set_pane_filter(&self, tmux_pane: Option<String>) {
+        debug!("set_pane_filter {:?}", tmux_pane);
+        self.pane_filter.replace(tmux_pane);
+    }
+
+    /// The tmux pane the listed paths are restricted to, if any
+    pub fn pane_filter(&self) -> Option<String> { self.pane_filter.borrow().clone() }
+
+    /// Lists the sensitive paths along with the others, or hides them.
+    pub fn set_reveal_sensitive(&self, reveal: bool) {
+        debug!("set_reveal_sensitive {}", reveal);
+        self.reveal_sensitive.set(reveal);
+    }
+
+    /// Whether the sensitive paths are listed
+    pub fn reveal_sensitive(&self) -> bool { self.reveal_sensitive.get() }
+
+    /// Lists the deleted paths (the trash) instead of the others, or the others again.
+    pub fn set_show_trash(&self, show: bool) {
+        debug!("set_show_trash {}", show);
+        self.show_trash.set(show);
+    }
+
+    /// Whether the deleted paths are listed instead of the others
+    pub fn show_trash(&self) -> bool { self.show_trash.get() }
+
+    /// Lists only the smart suggestions for the current directory, ordered like the history,
+    /// or all the paths again.
+    pub fn set_suggestions_only(&self, suggestions_only: bool) {
+        debug!("set_suggestions_only {}", suggestions_only);
+        self.suggestions_only.set(suggestions_only);
+    }
+
+    /// Whether only the smart suggestions are listed
+    pub fn suggestions_only(&self) -> bool { self.suggestions_only.get() }
+
+    /// The paths suggested for the current directory, with the configured depth and count
+    fn current_suggestions(&self) -> Vec<String> {
+        let (depth, count) = {
+            let config_lock = self.config.lock().unwrap();
+            (
+                config_lock.smart_suggestions_depth,
+                config_lock.smart_suggestions_count,
+            )
+        };
+        let Some(cwd) = std::env::current_dir()
+            .ok()
+            .and_then(|cwd| cwd.to_str().map(String::from))
+        else {
+            return vec![];
+        };
+        match self.list_path_history_smart_suggestions(&cwd, depth, count, &[]) {
+            Ok(paths) => paths.into_iter().map(|path| path.path).collect(),
+            Err(e) => {
+                error!("Failed to list the suggestions for '{}': {}", cwd, e);
+                vec![]
+            }
+        }
+    }
+
+    /// Changes whether the searches respect the case.
+    pub fn set_case_sensitivity(&self, case_sensitivity: CaseSensitivity) {
+        debug!("set_case_sensitivity {:?}", case_sensitivity);
+        self.case_sensitivity.set(case_sensitivity);
+    }
+
+    /// Whether the searches respect the case
+    pub fn case_sensitivity(&self) -> CaseSensitivity { self.case_sensitivity.get() }
+
+    /// Whether the search of `like_text` respects the case
+    fn is_case_sensitive(&self, like_text: &str) -> bool {
+        self.case_sensitivity().is_case_sensitive(like_text)
+    }
+
+    /// The SQL condition of `column` containing the text of the parameter `param_idx`, ignoring
+    /// the case unless `case_sensitive` is set.
+    fn contains_condition(column: &str, param_idx: usize, case_sensitive: bool) -> String {
+        if case_sensitive {
+            format!("instr({}, (?{})) > 0", column, param_idx)
+        } else {
+            format!("{} LIKE '%' || (?{}) || '%'", column, param_idx)
+        }
+    }
+
+    /// Marks a path as sensitive, hiding it from the listings until they are revealed, or
+    /// clears the mark.
+    ///
+    /// ### Returns
+    /// Ok(true) if the path is in the history, otherwise Ok(false).
+    pub fn set_path_sensitive(&self, path: &str, sensitive: bool) -> Result<bool, rusqlite::Error> {
+        self.retry_busy(|| {
+            debug!("set_path_sensitive path={} sensitive={}", path, sensitive);
+            self.db_conn
+                .execute(
+                    "UPDATE paths SET sensitive = (?1) WHERE path = (?2)",
+                    params![sensitive, path],
+                )
+                .map(|count| count > 0)
+        })
+    }
+
+    /// Whether `path` is hidden from the listings for being sensitive.
+    fn is_hidden_sensitive(&self, path: &str) -> Result<bool, rusqlite::Error> {
+        if self.reveal_sensitive() {
+            return Ok(false);
+        }
+        self.db_conn
+            .prepare_cached("SELECT COUNT(*) FROM paths WHERE path = (?1) AND sensitive != 0")?
+            .query_row([path], |row| row.get::<_, i64>(0))
+            .map(|count| count > 0)
+    }
+
+    /// The SQL conditions restricting the paths to the filtered pane, host and project type, or
+    /// to the smart suggestions, their parameters being appended to `params`.
+    /// Without an explicit `host`, the paths of the current host and the ones of an unknown host
+    /// are kept when `current_host_only` is set.
+    fn history_conditions(&self, tokens: &SearchTokens, params: &mut Vec<String>) -> Vec<String> {
+        let mut conditions = vec![];
+        if let Some(tmux_pane) = self.pane_filter() {
+            params.push(tmux_pane);
+            conditions.push(format!(
+                "path IN (SELECT path FROM paths_history WHERE tmux_pane == (?{}))",
+                params.len()
+            ));
+        }
+        if let Some(host) = &tokens.host {
+            params.push(host.clone());
+            conditions.push(format!(
+                "path IN (SELECT path FROM paths_history WHERE hostname == (?{}))",
+                params.len()
+            ));
+        } else if self.config.lock().unwrap().current_host_only
+            && let Some(hostname) = &self.hostname
+        {
+            params.push(hostname.clone());
+            conditions.push(format!(
+                "path IN (SELECT path FROM paths_history WHERE hostname == (?{}) OR hostname IS NULL)",
+                params.len()
+            ));
+        }
+        if let Some(project_type) = tokens.project_type {
+            params.push(project_type.name().to_string());
+            conditions.push(format!(
+                "path IN (SELECT path FROM project_types WHERE type == (?{}))",
+                params.len()
+            ));
+        }
+        conditions.extend(self.container_condition(params));
+        if self.suggestions_only() {
+            let placeholders: Vec<String> = self
+                .current_suggestions()
+                .into_iter()
+                .map(|path| {
+                    params.push(path);
+                    format!("(?{})", params.len())
+                })
+                .collect();
+            conditions.push(format!("path IN ({})", placeholders.join(", ")));
+        }
+        if !self.reveal_sensitive() {
+            conditions.push(String::from("sensitive == 0"));
+        }
+        if self.show_trash() {
+            conditions.push(String::from("deleted_at IS NOT NULL"));
+        } else {
+            conditions.push(String::from("deleted_at IS NULL"));
+            conditions.push(String::from(LATEST_EQUIVALENT_PATH_CONDITION));
+        }
+        conditions
+    }
+
+    /// The SQL condition keeping the paths of the current context, its parameter being appended
+    /// to `params`: inside a container, the paths visited from it; on the host, the paths not
+    /// only visited from containers. None when `mix_container_history` is set.
+    fn container_condition(&self, params: &mut Vec<String>) -> Option<String> {
+        if self.config.lock().unwrap().mix_container_history {
+            return None;
+        }
+        Some(match &self.container {
+            Some(container) => {
+                params.push(container.clone());
+                format!(
+                    "path IN (SELECT path FROM paths_history WHERE container == (?{}))",
+                    params.len()
                 )
-                .unwrap();
-            // reverse the list in order to have the best suggestionstion just on top of the first into the history
-            smart_rows.reverse();
+            }
+            None => String::from(
+                "(path IN (SELECT path FROM paths_history WHERE container IS NULL) OR path NOT IN (SELECT path FROM paths_history WHERE container IS NOT NULL))",
+            ),
+        })
+    }
+
+    /// Whether `path` belongs to the current context, see `container_condition`.
+    fn is_in_container_context(&self, path: &str) -> Result<bool, rusqlite::Error> {
+        let mut params = vec![path.to_string()];
+        let Some(condition) = self.container_condition(&mut params) else {
+            return Ok(true);
+        };
+        let sql = format!(
+            "SELECT COUNT(*) FROM (SELECT (?1) AS path) WHERE {}",
+            condition
+        );
+        let count: i64 =
+            self.db_conn
+                .query_row(&sql, rusqlite::params_from_iter(params), |row| row.get(0))?;
+        Ok(count > 0)
+    }
+
+    /// Compare two paths on the given column, used to order the fuzzy search results
+    fn compare_paths(column: SortColumn, a: &Path, b: &Path) -> std::cmp::Ordering {
+        match column {
+            SortColumn::Path => a.path.cmp(&b.path),
+            _ => a.date.cmp(&b.date),
+        }
+    }
+
+    fn build_list_path_exact_sql_statement(
+        &self,
+        pos: usize,
+        len: usize,
+        like_text: &str,
+        tokens: &SearchTokens,
+        shortcuts: &[Shortcut],
+        sort: SortOrder,
+    ) -> (String, Vec<String>) {
+        let order_by = sort.order_by("date desc, id desc");
+        let mut params: Vec<String> = vec![];
+        let mut sql = String::from("SELECT id, path, date, hostname FROM paths");
+        let conditions = self.list_path_exact_conditions(like_text, tokens, shortcuts, &mut params);
+        if !conditions.is_empty() {
+            sql.push_str(&format!(" WHERE {}", conditions.join(" AND ")));
+        }
+
+        let limit_idx = params.len() + 1;
+        let offset_idx = params.len() + 2;
+        sql.push_str(&format!(
+            " ORDER BY {} LIMIT (?{}) OFFSET (?{})",
+            order_by, limit_idx, offset_idx
+        ));
+        params.push(format!("{}", len));
+        params.push(format!("{}", pos));
+
+        debug!("list_path_exact sql={} params={:?}", sql, params);
+        (sql, params)
+    }
+
+    /// The SQL conditions of an exact search of the paths, their parameters being appended to
+    /// `params`.
+    fn list_path_exact_conditions(
+        &self,
+        like_text: &str,
+        tokens: &SearchTokens,
+        shortcuts: &[Shortcut],
+        params: &mut Vec<String>,
+    ) -> Vec<String> {
+        let mut conditions: Vec<String> = vec![];
+        let case_sensitive = self.is_case_sensitive(like_text);
+        let patterns = Self::glob_patterns(like_text);
+        if !patterns.is_empty() {
+            conditions.push(Self::glob_condition(patterns, case_sensitive, params));
+        } else if !like_text.is_empty() {
+            // Find shortcuts where name or description matches the like_text
+            let like_lower = like_text.to_lowercase();
+            let contains = |text: &str| {
+                if case_sensitive {
+                    text.contains(like_text)
+                } else {
+                    text.to_lowercase().contains(&like_lower)
+                }
+            };
+
+            if self.config.lock().unwrap().path_search_include_shortcuts {
+                let matching_shortcut_paths: Vec<&str> = shortcuts
+                    .iter()
+                    .filter(|s| contains(&s.name) || s.description.as_deref().is_some_and(contains))
+                    .map(|s| s.path.as_str())
+                    .collect();
 
+                // Build WHERE clause: path matches like_text OR path starts with any matching shortcut path
+                let mut condition =
+                    format!("({}", Self::contains_condition("path", 1, case_sensitive));
+                params.push(like_text.to_string());
+
+                // Add OR conditions for each matching shortcut's path
+                for (i, shortcut_path) in matching_shortcut_paths.iter().enumerate() {
+                    let param_idx = i + 2; // +2 because ?1 is like_text
+                    condition.push_str(&format!(" OR path == (?{})", param_idx));
+                    condition.push_str(&format!(" OR path LIKE (?{}) || '/' || '%'", param_idx));
+                    params.push(shortcut_path.to_string());
+                }
+                condition.push(')');
+                conditions.push(condition);
+            } else {
+                conditions.push(Self::contains_condition("path", 1, case_sensitive));
+                params.push(like_text.to_string());
+            }
+        }
+        conditions.extend(self.history_conditions(tokens, params));
+        conditions
+    }
+
+    fn list_path_exact(
+        &self,
+        pos: usize,
+        len: usize,
+        like_text: &str,
+        tokens: &SearchTokens,
+        shortcuts: &[Shortcut],
+        sort: SortOrder,
+    ) -> Result<Vec<Path>, rusqlite::Error> {
+        debug!(
+            "list_path_exact pos={} len={} like_text={}",
+            pos, len, like_text
+        );
+        let (sql, params) =
+            self.build_list_path_exact_sql_statement(pos, len, like_text, tokens, shortcuts, sort);
+
+        let mut stmt = match self.db_conn.prepare_cached(sql.as_str()) {
+            Ok(stmt) => stmt,
+            Err(e) => {
+                error!("list_paths failed in prepare {}: {}", sql, e);
+                return Err(e);
+            }
+        };
+
+        let rows = match stmt.query_map(rusqlite::params_from_iter(params), |row| {
+            let path_str: String = row.get(1)?;
+            Ok(Path::new(row.get(0)?, path_str, row.get(2)?, shortcuts).with_hostname(row.get(3)?))
+        }) {
+            Ok(rows) => rows,
+            Err(e) => {
+                error!("list_paths failed in query_map: {}", e);
+                return Err(e);
+            }
+        };
+
+        let mut paths = Vec::new();
+        for path in rows {
+            paths.push(path?);
+        }
+        Ok(paths)
+    }
+
+    /// The smart rows of the page starting at `pos`, listed before the history, and the
+    /// position and length of the page of the history following them.
+    fn page_smart_rows(
+        mut smart_rows: Vec<Path>,
+        mut pos: usize,
+        mut len: usize,
+    ) -> (Vec<Path>, usize, usize) {
+        if !smart_rows.is_empty() {
             if pos < smart_rows.len() {
                 // we keep smart_rows.len() - pos values
                 smart_rows = smart_rows.into_iter().skip(pos).collect();
@@ -669,16 +2695,276 @@ impl Store {
                 smart_rows = vec![];
             }
         }
-
         debug!("smart_rows len={}", smart_rows.len());
+        (smart_rows, pos, len)
+    }
 
-        let (sql, params) =
-            self.build_list_path_exact_sql_statement(pos, len, like_text, shortcuts);
+    /// The smart suggestions listed on top of the history, the best one last, when they are
+    /// displayed in the list rather than in their panel and the history is neither searched nor
+    /// sorted.
+    fn list_smart_rows(
+        &self,
+        like_text: &str,
+        tokens: &SearchTokens,
+        shortcuts: &[Shortcut],
+        sort: SortOrder,
+    ) -> Vec<Path> {
+        let (smart_rows_in_list, depth, count) = {
+            let config_lock = self.config.lock().unwrap();
+            (
+                config_lock.smart_suggestions_active && !config_lock.smart_suggestions_panel,
+                config_lock.smart_suggestions_depth,
+                config_lock.smart_suggestions_count,
+            )
+        };
+        if !smart_rows_in_list
+            || self.show_trash()
+            || self.suggestions_only()
+            || !like_text.is_empty()
+            || !tokens.is_empty()
+            || sort != SortOrder::Default
+        {
+            return vec![];
+        }
+        // get current working directory
+        let cwd = std::env::current_dir().unwrap();
+        let mut smart_rows = self
+            .list_path_history_smart_suggestions(cwd.to_str().unwrap(), depth, count, shortcuts)
+            .unwrap();
+        // reverse the list in order to have the best suggestionstion just on top of the first into the history
+        smart_rows.reverse();
+        smart_rows
+    }
+
+    /// The directory and the filter of a search inside a shortcut: `api/src/han` searches the
+    /// directories of `src`, in the directory of the local shortcut `api`, containing `han`.
+    /// None when the search does not start with the name of a shortcut followed by a `/`, or
+    /// is a glob pattern.
+    fn shortcut_relative_search(
+        like_text: &str,
+        shortcuts: &[Shortcut],
+    ) -> Option<(String, String)> {
+        if !Self::glob_patterns(like_text).is_empty() {
+            return None;
+        }
+        let (name, relative) = like_text.split_once('/')?;
+        let shortcut = shortcuts.iter().find(|s| s.name == name && !s.remote)?;
+        let (sub_directory, filter) = relative.rsplit_once('/').unwrap_or(("", relative));
+        let mut parent = shortcut.path.trim_end_matches('/').to_string();
+        for segment in sub_directory
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+        {
+            parent.push('/');
+            parent.push_str(segment);
+        }
+        Some((parent, filter.to_string()))
+    }
+
+    /// Lists the paths of a search inside a shortcut, see `shortcut_relative_search`: the paths
+    /// of the history below `parent` containing `filter`, then the other directories of
+    /// `parent` containing it, found on the disk and sorted by name.
+    fn list_shortcut_relative(
+        &self,
+        parent: &str,
+        filter: &str,
+        tokens: &SearchTokens,
+        shortcuts: &[Shortcut],
+        sort: SortOrder,
+    ) -> Result<Vec<Path>, rusqlite::Error> {
+        debug!("list_shortcut_relative parent={} filter={}", parent, filter);
+        let case_sensitive = self.is_case_sensitive(filter);
+        let filter_lower = filter.to_lowercase();
+        let matches = |relative: &str| match case_sensitive {
+            true => relative.contains(filter),
+            false => relative.to_lowercase().contains(&filter_lower),
+        };
+        let prefix = format!("{}/", parent);
+
+        let mut params: Vec<String> = vec![];
+        let mut conditions = self.history_conditions(tokens, &mut params);
+        params.push(prefix.clone());
+        conditions.push(format!(
+            "substr(path, 1, length(?{0})) == (?{0})",
+            params.len()
+        ));
+        let sql = format!(
+            "SELECT id, path, date, hostname FROM paths WHERE {} ORDER BY {}",
+            conditions.join(" AND "),
+            sort.order_by("date desc, id desc")
+        );
+        let mut paths = self
+            .db_conn
+            .prepare_cached(&sql)?
+            .query_map(rusqlite::params_from_iter(params), |row| {
+                Ok(Path::new(row.get(0)?, row.get(1)?, row.get(2)?, shortcuts)
+                    .with_hostname(row.get(3)?))
+            })?
+            .collect::<Result<Vec<Path>, rusqlite::Error>>()?;
+        paths.retain(|path| matches(&path.path[prefix.len()..]));
+        if self.show_trash() || self.suggestions_only() {
+            return Ok(paths);
+        }
+
+        // the hidden directories are only listed when searched
+        let known: HashSet<&str> = paths.iter().map(|path| path.path.as_str()).collect();
+        let mut directories: Vec<String> =
+            fs::read_dir(if parent.is_empty() { "/" } else { parent })
+                .into_iter()
+                .flatten()
+                .flatten()
+                .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+                .filter_map(|entry| entry.file_name().into_string().ok())
+                .filter(|name| matches(name) && (!name.starts_with('.') || filter.starts_with('.')))
+                .map(|name| format!("{}{}", prefix, name))
+                .filter(|path| !known.contains(path.as_str()))
+                .collect();
+        directories.sort();
+        paths.extend(
+            directories
+                .into_iter()
+                .map(|directory| Path::new(0, directory, 0, shortcuts)),
+        );
+        Ok(paths)
+    }
+
+    /// Lists all the listed paths by frecency: each visit counts for 4 in the last hour, 2 in
+    /// the last day, 1/2 in the last week and 1/4 before, the most recent path coming first
+    /// among the paths with the same frecency.
+    pub fn list_paths_by_frecency(&self) -> Result<Vec<Path>, rusqlite::Error> {
+        debug!("list_paths_by_frecency");
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+        let mut params: Vec<String> = vec![];
+        let conditions = self.history_conditions(&SearchTokens::default(), &mut params);
+        let sql = format!(
+            "SELECT id, path, date, hostname, \
+             (SELECT SUM(CASE WHEN {now} - history.date < {HOUR} THEN 4.0 \
+             WHEN {now} - history.date < {DAY} THEN 2.0 \
+             WHEN {now} - history.date < {WEEK} THEN 0.5 ELSE 0.25 END) \
+             FROM paths_history AS history WHERE history.path = paths.path) AS frecency \
+             FROM paths WHERE {} ORDER BY COALESCE(frecency, 0) DESC, date DESC, id DESC",
+            conditions.join(" AND "),
+            HOUR = 3600,
+            DAY = 24 * 3600,
+            WEEK = 7 * 24 * 3600,
+        );
+        let shortcuts = self.list_all_shortcuts()?;
+        let mut stmt = self.db_conn.prepare_cached(&sql)?;
+        let rows = stmt.query_map(rusqlite::params_from_iter(params), |row| {
+            Ok(Path::new(row.get(0)?, row.get(1)?, row.get(2)?, &shortcuts)
+                .with_hostname(row.get(3)?))
+        })?;
+        rows.collect()
+    }
+
+    /// Counts the paths `list_paths_sorted` returns for a search text, without fetching them
+    /// for an exact search.
+    ///
+    /// ### Parameters
+    /// like_text: optional text to filter paths (if empty, no filtering is applied)
+    /// fuzzy: if true, count the paths of a fuzzy search
+    ///
+    /// ### Returns
+    /// The number of paths, including the smart suggestions listed on top of the history.
+    pub fn count_paths(&self, like_text: &str, fuzzy: bool) -> Result<usize, rusqlite::Error> {
+        debug!("count_paths like_text={} fuzzy={}", like_text, fuzzy);
+        let shortcuts = self.list_all_shortcuts().unwrap_or_default();
+        let (like_text, tokens) = self.split_search_tokens(like_text)?;
+        let like_text = like_text.as_str();
+        if let Some((parent, filter)) = Self::shortcut_relative_search(like_text, &shortcuts) {
+            return self
+                .list_shortcut_relative(&parent, &filter, &tokens, &shortcuts, SortOrder::Default)
+                .map(|paths| paths.len());
+        }
+        if !like_text.is_empty() && fuzzy {
+            // the fuzzy matches are only known once scored
+            return self
+                .fuzzy_path_ids(like_text, &tokens, &shortcuts, SortOrder::Default)
+                .map(|ids| ids.len());
+        }
+
+        let mut params: Vec<String> = vec![];
+        let mut sql = String::from("SELECT COUNT(*) FROM paths");
+        let conditions =
+            self.list_path_exact_conditions(like_text, &tokens, &shortcuts, &mut params);
+        if !conditions.is_empty() {
+            sql.push_str(&format!(" WHERE {}", conditions.join(" AND ")));
+        }
+        let count: i64 = self
+            .db_conn
+            .prepare_cached(&sql)?
+            .query_row(rusqlite::params_from_iter(params), |row| row.get(0))?;
+        let smart_rows = self.list_smart_rows(like_text, &tokens, &shortcuts, SortOrder::Default);
+        Ok(count as usize + smart_rows.len())
+    }
+
+    /// Lists path history from the paths_history table with pagination and optional filtering.
+    /// The results are ordered by date (descending) and ID (descending).
+    /// If `like_text` is provided, only paths containing the text are returned.
+    /// This function only performs exact matching (no fuzzy search).
+    ///
+    /// ### Parameters
+    /// pos: the starting position (offset) for pagination
+    /// len: the number of paths to return
+    /// like_text: optional text to filter paths (if empty, no filtering is applied)
+    ///
+    /// ### Returns
+    /// A vector of Path entries if the operation was successful, otherwise an error.
+    #[allow(dead_code)]
+    pub fn list_path_history(
+        &self,
+        pos: usize,
+        len: usize,
+        like_text: &str,
+    ) -> Result<Vec<Path>, rusqlite::Error> {
+        debug!(
+            "list_path_history pos={} len={} like_text={}",
+            pos, len, like_text
+        );
+        // Retrieve all shortcuts to associate with paths
+        let shortcuts = self.list_all_shortcuts().unwrap_or_default();
+        self.list_path_history_exact(pos, len, like_text, &shortcuts)
+    }
+
+    fn list_path_history_exact(
+        &self,
+        pos: usize,
+        len: usize,
+        like_text: &str,
+        shortcuts: &[Shortcut],
+    ) -> Result<Vec<Path>, rusqlite::Error> {
+        debug!(
+            "list_path_history_exact pos={} len={} like_text={}",
+            pos, len, like_text
+        );
+        let mut sql = String::from("SELECT id, path, date FROM paths_history");
+        let mut params: Vec<String> = vec![];
+
+        if !like_text.is_empty() {
+            sql.push_str(" WHERE path LIKE '%' || (?1) || '%'");
+            params.push(like_text.to_string());
+
+            let limit_idx = params.len() + 1;
+            let offset_idx = params.len() + 2;
+            sql.push_str(&format!(
+                " ORDER BY date desc, id desc LIMIT (?{}) OFFSET (?{})",
+                limit_idx, offset_idx
+            ));
+        } else {
+            sql.push_str(" ORDER BY date desc, id desc LIMIT (?1) OFFSET (?2)");
+        }
+        params.push(format!("{}", len));
+        params.push(format!("{}", pos));
+
+        debug!("list_path_history_exact sql={} params={:?}", sql, params);
 
-        let mut stmt = match self.db_conn.prepare(sql.as_str()) {
+        let mut stmt = match self.db_conn.prepare_cached(sql.as_str()) {
             Ok(stmt) => stmt,
             Err(e) => {
-                error!("list_paths failed in prepare {}: {}", sql, e);
+                error!("list_path_history failed in prepare {}: {}", sql, e);
                 return Err(e);
             }
         };
@@ -689,7 +2975,7 @@ impl Store {
         }) {
             Ok(rows) => rows,
             Err(e) => {
-                error!("list_paths failed in query_map: {}", e);
+                error!("list_path_history failed in query_map: {}", e);
                 return Err(e);
             }
         };
@@ -698,107 +2984,317 @@ impl Store {
         for path in rows {
             paths.push(path?);
         }
-        let mut final_rows = smart_rows;
-        final_rows.append(&mut paths);
+        Ok(paths)
+    }
+
+    /// Lists the visits of the paths_history table, the latest first, with the pane, host and
+    /// sensitive filters of `list_paths`.
+    ///
+    /// When `collapse_repeats` is set, the consecutive visits to the same path are grouped into
+    /// one visit counting them. The runs are computed by the query, with window functions, so
+    /// that `pos` and `len` paginate the collapsed list.
+    pub fn list_path_visits(
+        &self,
+        pos: usize,
+        len: usize,
+        host: Option<&str>,
+        collapse_repeats: bool,
+    ) -> Result<Vec<Visit>, rusqlite::Error> {
+        debug!(
+            "list_path_visits pos={} len={} host={:?} collapse_repeats={}",
+            pos, len, host, collapse_repeats
+        );
+        let shortcuts = self.list_all_shortcuts().unwrap_or_default();
+        let mut params: Vec<String> = vec![];
+        let mut conditions = vec![String::from(
+            "path IN (SELECT path FROM paths WHERE deleted_at IS NULL)",
+        )];
+        if let Some(tmux_pane) = self.pane_filter() {
+            params.push(tmux_pane);
+            conditions.push(format!("tmux_pane == (?{})", params.len()));
+        }
+        if let Some(host) = host {
+            params.push(host.to_string());
+            conditions.push(format!("hostname == (?{})", params.len()));
+        }
+        if !self.reveal_sensitive() {
+            conditions.push(String::from(
+                "path NOT IN (SELECT path FROM paths WHERE sensitive != 0)",
+            ));
+        }
+        // a run starts at each visit whose path differs from the one of the previous visit,
+        // the running count of the starts numbering the runs
+        let run = if collapse_repeats {
+            "SUM(start) OVER (ORDER BY date, id)"
+        } else {
+            "id"
+        };
+        let sql = format!(
+            "SELECT MAX(id), path, MAX(date), COUNT(*) FROM ( \
+                SELECT id, path, date, {} AS run FROM ( \
+                    SELECT id, path, date, \
+                        CASE WHEN path IS LAG(path) OVER (ORDER BY date, id) THEN 0 ELSE 1 END AS start \
+                    FROM paths_history WHERE {})) \
+            GROUP BY run ORDER BY MAX(date) desc, MAX(id) desc LIMIT (?{}) OFFSET (?{})",
+            run,
+            conditions.join(" AND "),
+            params.len() + 1,
+            params.len() + 2
+        );
+        params.push(format!("{}", len));
+        params.push(format!("{}", pos));
+        debug!("list_path_visits sql={} params={:?}", sql, params);
+
+        let mut stmt = self.db_conn.prepare_cached(&sql)?;
+        let rows = stmt.query_map(rusqlite::params_from_iter(params), |row| {
+            Ok(Visit {
+                path: Path::new(row.get(0)?, row.get(1)?, row.get(2)?, &shortcuts),
+                count: row.get::<_, i64>(3)? as usize,
+            })
+        })?;
+        rows.collect()
+    }
+
+    /// Lists the paths suggested when in `match_path`, ranked with the configured strategy.
+    pub fn list_path_history_smart_suggestions(
+        &self,
+        match_path: &str,
+        search_depth: usize,
+        suggestions_values_count: usize,
+        shortcuts: &[Shortcut],
+    ) -> Result<Vec<Path>, rusqlite::Error> {
+        Ok(self
+            .list_path_history_smart_suggestions_scored(
+                match_path,
+                search_depth,
+                suggestions_values_count,
+                shortcuts,
+            )?
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect())
+    }
+
+    /// Lists the paths suggested when in `match_path` with the breakdown of their scores, e.g.
+    /// to blend them with the scores of a fuzzy search, see `list_path_history_smart_suggestions`.
+    #[instrument(level = "debug", skip(self, shortcuts))]
+    pub fn list_path_history_smart_suggestions_scored(
+        &self,
+        match_path: &str,
+        search_depth: usize,
+        suggestions_values_count: usize,
+        shortcuts: &[Shortcut],
+    ) -> Result<Vec<(Path, SuggestionExplanation)>, rusqlite::Error> {
+        let strategy = self.config.lock().unwrap().smart_suggestions_strategy;
+        self.suggestion_explanations.borrow_mut().clear();
+        let settings = self.effective_suggestion_settings(match_path)?;
+        let search_depth = settings.depth.unwrap_or(search_depth);
+        let suggestions_values_count = settings.count.unwrap_or(suggestions_values_count);
+        let suggestions = match strategy {
+            SmartSuggestionsStrategy::Window => self.list_path_history_smart_suggestions_window(
+                match_path,
+                search_depth,
+                suggestions_values_count,
+                shortcuts,
+            ),
+            SmartSuggestionsStrategy::Transitions => self
+                .list_path_history_smart_suggestions_transitions(
+                    match_path,
+                    suggestions_values_count,
+                    shortcuts,
+                    SystemTime::now()
+                        .duration_since(UNIX_EPOCH)
+                        .unwrap()
+                        .as_secs() as i64,
+                ),
+        }?;
+        // the paths of another context would be useless jump targets
+        let mut in_context = Vec::with_capacity(suggestions.len());
+        for (suggestion, explanation) in suggestions {
+            if self.is_in_container_context(&suggestion.path)?
+                && !self.is_hidden_sensitive(&suggestion.path)?
+            {
+                self.suggestion_explanations
+                    .borrow_mut()
+                    .insert(suggestion.path.clone(), explanation.clone());
+                in_context.push((suggestion, explanation));
+            }
+        }
+        Ok(in_context)
+    }
 
-        debug!("final_row len={}", final_rows.len());
+    /// Computes the smart suggestions of `path` with the configured depth and count, and caches
+    /// them for the next start of the GUI, replacing the previous ones.
+    ///
+    /// ### Returns
+    /// The suggested paths, the best first, if the operation was successful, otherwise an error
+    #[instrument(level = "debug", skip(self))]
+    pub fn refresh_suggestions_cache(&self, path: &str) -> Result<Vec<String>, rusqlite::Error> {
+        let (depth, count) = {
+            let config_lock = self.config.lock().unwrap();
+            (
+                config_lock.smart_suggestions_depth,
+                config_lock.smart_suggestions_count,
+            )
+        };
+        let suggestions: Vec<String> = self
+            .list_path_history_smart_suggestions(path, depth, count, &[])?
+            .into_iter()
+            .map(|suggestion| suggestion.path)
+            .collect();
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+        self.retry_busy(|| {
+            debug!(
+                "refresh_suggestions_cache {} {} suggestions",
+                path,
+                suggestions.len()
+            );
+            self.db_conn
+                .prepare_cached(
+                    "INSERT OR REPLACE INTO suggestions_cache (path, suggestions, date) \
+                     VALUES (?1, ?2, ?3)",
+                )?
+                .execute(params![path, suggestions.join("\n"), now])
+                .map(|_| ())
+        })?;
+        Ok(suggestions)
+    }
+
+    /// Computes the smart suggestions of `path` again when they were never cached, or were
+    /// cached more than `SUGGESTIONS_CACHE_TTL` seconds ago.
+    fn refresh_stale_suggestions_cache(&self, path: &str) -> Result<(), rusqlite::Error> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+        let date: Option<i64> = self
+            .db_conn
+            .prepare_cached("SELECT date FROM suggestions_cache WHERE path = (?1)")?
+            .query_map([path], |row| row.get(0))?
+            .next()
+            .transpose()?;
+        if date.is_some_and(|date| date > now - SUGGESTIONS_CACHE_TTL) {
+            return Ok(());
+        }
+        self.refresh_suggestions_cache(path).map(|_| ())
+    }
+
+    /// The smart suggestions of `path` cached by `refresh_suggestions_cache`, the best first,
+    /// without those out of the container context or sensitive; None when they were never
+    /// computed.
+    pub fn cached_suggestions(
+        &self,
+        path: &str,
+        shortcuts: &[Shortcut],
+    ) -> Result<Option<Vec<Path>>, rusqlite::Error> {
+        debug!("cached_suggestions {}", path);
+        let suggestions: Option<String> = self
+            .db_conn
+            .prepare_cached("SELECT suggestions FROM suggestions_cache WHERE path = (?1)")?
+            .query_map([path], |row| row.get(0))?
+            .next()
+            .transpose()?;
+        let Some(suggestions) = suggestions else {
+            return Ok(None);
+        };
+        let mut paths = vec![];
+        for suggestion in suggestions.lines() {
+            if self.is_in_container_context(suggestion)? && !self.is_hidden_sensitive(suggestion)? {
+                let mut path = Path::new(0, suggestion.to_string(), 0, shortcuts);
+                path.smart_path = true;
+                paths.push(path);
+            }
+        }
+        Ok(Some(paths))
+    }
 
-        Ok(final_rows)
+    /// The path of the database file, None for an in-memory database
+    pub fn database_path(&self) -> Option<String> {
+        self.db_conn
+            .path()
+            .filter(|path| !path.is_empty())
+            .map(String::from)
     }
 
-    /// Lists path history from the paths_history table with pagination and optional filtering.
-    /// The results are ordered by date (descending) and ID (descending).
-    /// If `like_text` is provided, only paths containing the text are returned.
-    /// This function only performs exact matching (no fuzzy search).
-    ///
-    /// ### Parameters
-    /// pos: the starting position (offset) for pagination
-    /// len: the number of paths to return
-    /// like_text: optional text to filter paths (if empty, no filtering is applied)
-    ///
-    /// ### Returns
-    /// A vector of Path entries if the operation was successful, otherwise an error.
-    #[allow(dead_code)]
-    pub(crate) fn list_path_history(
-        &self,
-        pos: usize,
-        len: usize,
-        like_text: &str,
-    ) -> Result<Vec<Path>, rusqlite::Error> {
-        debug!(
-            "list_path_history pos={} len={} like_text={}",
-            pos, len, like_text
-        );
-        // Retrieve all shortcuts to associate with paths
-        let shortcuts = self.list_all_shortcuts().unwrap_or_default();
-        self.list_path_history_exact(pos, len, like_text, &shortcuts)
+    /// Why `path` was suggested by the last smart suggestions, None if it was not.
+    pub fn suggestion_explanation(&self, path: &str) -> Option<SuggestionExplanation> {
+        self.suggestion_explanations.borrow().get(path).cloned()
     }
 
-    fn list_path_history_exact(
+    /// Lists the paths most often visited right after `match_path`, the transitions made at
+    /// a time similar to `now` being favored.
+    fn list_path_history_smart_suggestions_transitions(
         &self,
-        pos: usize,
-        len: usize,
-        like_text: &str,
+        match_path: &str,
+        suggestions_values_count: usize,
         shortcuts: &[Shortcut],
-    ) -> Result<Vec<Path>, rusqlite::Error> {
+        now: i64,
+    ) -> Result<Vec<(Path, SuggestionExplanation)>, rusqlite::Error> {
         debug!(
-            "list_path_history_exact pos={} len={} like_text={}",
-            pos, len, like_text
+            "list_path_history_smart_suggestions_transitions match_path='{}' suggestions_values_count={}",
+            match_path, suggestions_values_count
         );
-        let mut sql = String::from("SELECT id, path, date FROM paths_history");
-        let mut params: Vec<String> = vec![];
-
-        if !like_text.is_empty() {
-            sql.push_str(" WHERE path LIKE '%' || (?1) || '%'");
-            params.push(like_text.to_string());
-
-            let limit_idx = params.len() + 1;
-            let offset_idx = params.len() + 2;
-            sql.push_str(&format!(
-                " ORDER BY date desc, id desc LIMIT (?{}) OFFSET (?{})",
-                limit_idx, offset_idx
-            ));
-        } else {
-            sql.push_str(" ORDER BY date desc, id desc LIMIT (?1) OFFSET (?2)");
+        if match_path.is_empty() {
+            return Ok(vec![]);
         }
-        params.push(format!("{}", len));
-        params.push(format!("{}", pos));
-
-        debug!("list_path_history_exact sql={} params={:?}", sql, params);
+        let now_slot = time_slot(now);
+        let skip_directory = std::env::home_dir()
+            .and_then(|p| p.to_str().map(|s| s.to_string()))
+            .unwrap_or_default();
 
-        let mut stmt = match self.db_conn.prepare(sql.as_str()) {
-            Ok(stmt) => stmt,
-            Err(e) => {
-                error!("list_path_history failed in prepare {}: {}", sql, e);
-                return Err(e);
+        let match_key = self.group_key(match_path)?;
+        let mut stmt = self.db_conn.prepare_cached(&format!(
+            "SELECT to_path, time_slot, count FROM path_transitions WHERE from_path IN ({})",
+            EQUIVALENT_PATHS_SQL
+        ))?;
+        let rows = stmt.query_map([match_path], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, i64>(1)?,
+                row.get::<_, i64>(2)?,
+            ))
+        })?;
+        let mut scores: HashMap<String, SuggestionExplanation> = HashMap::new();
+        for row in rows {
+            let (to_path, slot, count) = row?;
+            if to_path == skip_directory {
+                continue;
             }
-        };
-
-        let rows = match stmt.query_map(rusqlite::params_from_iter(params), |row| {
-            let path_str: String = row.get(1)?;
-            Ok(Path::new(row.get(0)?, path_str, row.get(2)?, shortcuts))
-        }) {
-            Ok(rows) => rows,
-            Err(e) => {
-                error!("list_path_history failed in query_map: {}", e);
-                return Err(e);
+            // the transitions to equivalent paths add up
+            let key = self.group_key(&to_path)?;
+            if key == match_key {
+                continue;
             }
-        };
-
-        let mut paths = Vec::new();
-        for path in rows {
-            paths.push(path?);
+            let explanation = scores.entry(key).or_default();
+            explanation.score += (count.max(0) as u64 * time_slot_weight(slot, now_slot)) as f64;
+            explanation.followed += count.max(0) as usize;
         }
-        Ok(paths)
+
+        let mut scores: Vec<(String, SuggestionExplanation)> = scores.into_iter().collect();
+        scores.sort_by(|a, b| b.1.score.total_cmp(&a.1.score).then_with(|| a.0.cmp(&b.0)));
+        scores
+            .into_iter()
+            .take(suggestions_values_count)
+            .map(|(key, explanation)| {
+                let mut path = Path::new(0, self.latest_equivalent_path(&key)?, 0, shortcuts);
+                path.smart_path = true;
+                Ok((path, explanation))
+            })
+            .collect()
     }
 
-    pub(crate) fn list_path_history_smart_suggestions(
+    /// Lists the paths visited right after the last visits of `match_path`, the closest
+    /// ones being favored.
+    fn list_path_history_smart_suggestions_window(
         &self,
         match_path: &str,
         search_depth: usize,
         suggestions_values_count: usize,
         shortcuts: &[Shortcut],
-    ) -> Result<Vec<Path>, rusqlite::Error> {
+    ) -> Result<Vec<(Path, SuggestionExplanation)>, rusqlite::Error> {
         debug!(
             "entering list_path_history_smart_suggestions match_path='{}' search_depth={} suggestions_values_count={}",
             match_path, search_depth, suggestions_values_count
@@ -806,7 +3302,11 @@ impl Store {
         if match_path.is_empty() {
             return Ok(vec![]);
         }
-        let mut stmt = match self.db_conn.prepare("SELECT id, path, date FROM paths_history WHERE path == (?1) ORDER BY date desc, id desc LIMIT (?2)") {
+        let match_key = self.group_key(match_path)?;
+        let mut stmt = match self.db_conn.prepare_cached(&format!(
+            "SELECT id, path, date FROM paths_history WHERE path IN ({}) ORDER BY date desc, id desc LIMIT (?2)",
+            EQUIVALENT_PATHS_SQL
+        )) {
             Ok(stmt) => stmt,
             Err(e) => {
                 error!("list_path_history failed in prepare: {}", e);
@@ -830,7 +3330,7 @@ impl Store {
         let rows: Result<Vec<Path>> = rows.collect();
         let rows = rows.unwrap();
 
-        let mut stmt = match self.db_conn.prepare("SELECT DISTINCT path FROM paths_history WHERE id > (?1) and path != (?2) ORDER BY date asc, id asc LIMIT (?3)") {
+        let mut stmt = match self.db_conn.prepare_cached("SELECT DISTINCT path FROM paths_history WHERE id > (?1) and path != (?2) ORDER BY date asc, id asc LIMIT (?3)") {
             Ok(stmt) => stmt,
             Err(e) => {
                 error!("list_path_history failed in prepare: {}", e);
@@ -838,7 +3338,14 @@ impl Store {
             }
         };
 
-        let mut sm = SmartRanker::new(search_depth, suggestions_values_count);
+        let now = Local::now().timestamp();
+        let ages = rows
+            .iter()
+            .map(|row| (now - row.date) as f64 / (24 * 3600) as f64)
+            .collect();
+        let scoring = SmartScoring::from_config(&self.config.lock().unwrap());
+        let mut sm =
+            SmartRanker::new(search_depth, suggestions_values_count).with_scoring(scoring, ages);
 
         for (set_idx, row) in rows.iter().enumerate() {
             debug!("found path: {:?}", row);
@@ -866,56 +3373,109 @@ impl Store {
             };
             for (idx, prev_row) in prev_rows.enumerate() {
                 let prev_row = prev_row?;
-                if prev_row == match_path {
+                // the visits of equivalent paths add up
+                let key = self.group_key(&prev_row)?;
+                if key == match_key {
                     trace!("skipping match_path: {:?}", prev_row);
                     break;
                 }
                 debug!("adding previous path: {:?}", prev_row);
-                sm.add_path(set_idx, prev_row, idx);
+                sm.add_path(set_idx, key, idx);
             }
         }
-        let paths = sm
-            .collect_rows()
-            .iter()
-            .map(|p| {
-                let mut path = Path::new(0, p.clone(), 0, shortcuts);
+        sm.collect_rows()
+            .into_iter()
+            .map(|row| {
+                let mut path = Path::new(0, self.latest_equivalent_path(&row.path)?, 0, shortcuts);
                 path.smart_path = true;
-                path
+                let mut depths = row.depths;
+                depths.sort();
+                let explanation = SuggestionExplanation {
+                    score: row.score,
+                    followed: depths.len(),
+                    sequences: depths.iter().map(|depth| rows[*depth].date).collect(),
+                };
+                Ok((path, explanation))
             })
-            .collect();
-        Ok(paths)
+            .collect()
     }
 
     /// Adds a new shortcut to the database.
-    /// If a shortcut with the same name already exists, it is deleted before adding the new one.
+    /// If a shortcut with the same name already exists, it is replaced by the new one, its
+    /// hints and broken state being reset and its creation date kept, in a single statement.
+    /// The shortcut is a personal one, even if it replaces the shortcut of a bundle.
     ///
     /// ### Parameters
     /// name: the name of the shortcut
     /// path: the file path associated with the shortcut
     ///
     /// ### Returns
-    /// Ok(()) if the operation was successful, otherwise an error.
-    pub(crate) fn add_shortcut(
+    /// Ok(()) if the operation was successful, otherwise an error and the previous shortcut
+    /// of that name, if any, is left unchanged.
+    pub fn upsert_shortcut(
         &self,
         name: &str,
         path: &str,
         description: Option<&str>,
     ) -> Result<(), rusqlite::Error> {
-        debug!("add_shortcut: {} {}", name, path);
-        self.delete_shortcut(name)?;
-        self.db_conn
-            .execute(
-                "INSERT INTO shortcuts (name, path, description) VALUES ((?1),(?2),(?3))",
-                (name, path, description),
-            )
-            .map_err(|e| {
-                error!(
-                    "Failed to insert shortcuts name='{}' time='{}': {}",
-                    name, path, e
-                );
-                e
-            })
-            .map(|_l| ())
+        self.retry_busy(|| {
+            debug!("upsert_shortcut: {} {}", name, path);
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs() as i64;
+            self.db_conn
+                .prepare_cached(
+                    "INSERT INTO shortcuts (name, path, description, remote, created) VALUES ((?1),(?2),(?3),(?4),(?5)) \
+                     ON CONFLICT (name) DO UPDATE SET path = excluded.path, description = excluded.description, \
+                     remote = excluded.remote, env = NULL, post_cd = NULL, broken = 0, checked = NULL, \
+                     bundle = NULL",
+                )?
+                .execute((
+                    name,
+                    path,
+                    description,
+                    RemotePath::parse(path).is_some(),
+                    now,
+                ))
+                .map_err(|e| {
+                    error!(
+                        "Failed to upsert shortcut name='{}' path='{}': {}",
+                        name, path, e
+                    );
+                    e
+                })
+                .map(|_l| ())
+        })
+    }
+
+    /// Renames a shortcut, keeping its path, description and hints.
+    ///
+    /// ### Parameters
+    /// name: the current name of the shortcut
+    /// new_name: the new name of the shortcut, which must not be the one of another shortcut
+    ///
+    /// ### Returns
+    /// Ok(false) if there is no shortcut named `name`, Ok(true) if it was renamed, otherwise
+    /// an error and the shortcut keeps its name.
+    pub fn rename_shortcut(&self, name: &str, new_name: &str) -> Result<bool, rusqlite::Error> {
+        self.retry_busy(|| {
+            debug!("rename_shortcut: {} -> {}", name, new_name);
+            let transaction = self.transaction()?;
+            let renamed = self
+                .db_conn
+                .prepare_cached("UPDATE shortcuts SET name = (?1) WHERE name = (?2)")?
+                .execute([new_name, name])
+                .map_err(|e| {
+                    error!(
+                        "Failed to rename shortcut '{}' to '{}': {}",
+                        name, new_name, e
+                    );
+                    e
+                })?;
+            transaction.commit()?;
+            Ok(renamed > 0)
+        })
     }
 
     /// Updates an existing shortcut in the database by its id.
@@ -929,27 +3489,93 @@ impl Store {
     ///
     /// ### Returns
     /// Ok(()) if the operation was successful, otherwise an error.
-    pub(crate) fn update_shortcut(
+    pub fn update_shortcut(
         &self,
         id: i64,
         name: &str,
         path: &str,
         description: Option<&str>,
     ) -> Result<(), rusqlite::Error> {
-        debug!("update_shortcut: id={} name={} path={}", id, name, path);
+        self.retry_busy(|| {
+            debug!("update_shortcut: id={} name={} path={}", id, name, path);
+            self.db_conn
+                .execute(
+                    "UPDATE shortcuts SET name = (?1), path = (?2), description = (?3), remote = (?4) WHERE id = (?5)",
+                    (name, path, description, RemotePath::parse(path).is_some(), id),
+                )
+                .map_err(|e| {
+                    error!(
+                        "Failed to update shortcut id='{}' name='{}' path='{}': {}",
+                        id, name, path, e
+                    );
+                    e
+                })
+                .map(|_l| ())
+        })
+    }
+
+    /// Changes the path of a shortcut, e.g. the one of a broken shortcut, which is no longer
+    /// broken.
+    ///
+    /// ### Parameters
+    /// id: the ID of the shortcut
+    /// path: the new path of the shortcut
+    ///
+    /// ### Returns
+    /// Ok(()) if the operation was successful, otherwise an error.
+    pub fn relocate_shortcut(&self, id: i64, path: &str) -> Result<(), rusqlite::Error> {
+        self.retry_busy(|| {
+            debug!("relocate_shortcut: id={} path={}", id, path);
+            self.db_conn
+                .execute(
+                    "UPDATE shortcuts SET path = (?1), remote = (?2), broken = 0, checked = NULL WHERE id = (?3)",
+                    (path, RemotePath::parse(path).is_some(), id),
+                )
+                .map(|_| ())
+        })
+    }
+
+    /// Checks that the paths of the local shortcuts are still directories, marking the
+    /// shortcuts whose path vanished as broken. The shortcuts checked less than `interval`
+    /// seconds ago are not checked again.
+    ///
+    /// ### Returns
+    /// The number of shortcuts checked if the operation was successful, otherwise an error.
+    pub fn check_shortcuts(&self, interval: u64) -> Result<usize, rusqlite::Error> {
+        self.retry_busy(|| {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs() as i64;
+            let shortcuts: Vec<(i64, String)> = self
+                .db_conn
+                .prepare_cached(
+                    "SELECT id, path FROM shortcuts WHERE remote = 0 AND (checked IS NULL OR checked <= (?1))",
+                )?
+                .query_map([now - interval as i64], |row| Ok((row.get(0)?, row.get(1)?)))?
+                .collect::<Result<_, _>>()?;
+            debug!("check_shortcuts {} shortcuts", shortcuts.len());
+            for (id, path) in &shortcuts {
+                let broken = !std::path::Path::new(path).is_dir();
+                if broken {
+                    info!("the path '{}' of the shortcut {} vanished", path, id);
+                }
+                self.db_conn
+                    .prepare_cached(
+                        "UPDATE shortcuts SET broken = (?1), checked = (?2) WHERE id = (?3)",
+                    )?
+                    .execute((broken, now, id))?;
+            }
+            Ok(shortcuts.len())
+        })
+    }
+
+    /// The ids of the shortcuts found broken by the last check
+    pub fn broken_shortcut_ids(&self) -> Result<HashSet<i64>, rusqlite::Error> {
         self.db_conn
-            .execute(
-                "UPDATE shortcuts SET name = (?1), path = (?2), description = (?3) WHERE id = (?4)",
-                (name, path, description, id),
-            )
-            .map_err(|e| {
-                error!(
-                    "Failed to update shortcut id='{}' name='{}' path='{}': {}",
-                    id, name, path, e
-                );
-                e
-            })
-            .map(|_l| ())
+            .prepare_cached("SELECT id FROM shortcuts WHERE broken = 1")?
+            .query_map([], |row| row.get(0))?
+            .collect()
     }
 
     /// Deletes a shortcut from the database by its name.
@@ -960,15 +3586,17 @@ impl Store {
     ///
     /// ### Returns
     /// Ok(()) if the operation was successful, otherwise an error.
-    pub(crate) fn delete_shortcut(&self, name: &str) -> Result<(), rusqlite::Error> {
-        let mut stmt = self
-            .db_conn
-            .prepare("DELETE FROM shortcuts WHERE name=(?1)")?;
-        if let Err(err) = stmt.execute([name]) {
-            error!("Failed to delete shortcut '{}': {}", name, err);
-            return Err(err);
-        }
-        Ok(())
+    pub fn delete_shortcut(&self, name: &str) -> Result<(), rusqlite::Error> {
+        self.retry_busy(|| {
+            let mut stmt = self
+                .db_conn
+                .prepare_cached("DELETE FROM shortcuts WHERE name=(?1)")?;
+            if let Err(err) = stmt.execute([name]) {
+                error!("Failed to delete shortcut '{}': {}", name, err);
+                return Err(err);
+            }
+            Ok(())
+        })
     }
 
     /// Deletes a shortcut from the database by its ID.
@@ -979,16 +3607,18 @@ impl Store {
     ///
     /// ### Returns
     /// Ok(()) if the operation was successful, otherwise an error.
-    pub(crate) fn delete_shortcut_by_id(&self, id: i64) -> Result<(), rusqlite::Error> {
-        let mut stmt = self
-            .db_conn
-            .prepare("DELETE FROM shortcuts WHERE id=(?1)")?;
-        stmt.execute([id])
-            .map_err(|e| {
-                error!("Failed to delete shortcuts by id '{}',{}", id, e);
-                e
-            })
-            .map(|_l: usize| ())
+    pub fn delete_shortcut_by_id(&self, id: i64) -> Result<(), rusqlite::Error> {
+        self.retry_busy(|| {
+            let mut stmt = self
+                .db_conn
+                .prepare_cached("DELETE FROM shortcuts WHERE id=(?1)")?;
+            stmt.execute([id])
+                .map_err(|e| {
+                    error!("Failed to delete shortcuts by id '{}',{}", id, e);
+                    e
+                })
+                .map(|_l: usize| ())
+        })
     }
 
     /// Finds a shortcut in the database by its name.
@@ -997,34 +3627,284 @@ impl Store {
     /// name: the name of the shortcut to find
     ///
     /// ### Returns
-    /// Some(path) if the shortcut is found, otherwise None.
-    pub(crate) fn find_shortcut(&self, name: &str) -> Option<Shortcut> {
-        debug!("find_shortcut {}", name);
-
-        let mut stmt = match self
-            .db_conn
-            .prepare("SELECT id, path, description FROM shortcuts WHERE name=(?1)")
-        {
-            Ok(stmt) => stmt,
-            Err(e) => {
-                error!("find_shortcut failed in prepare: {}", e);
-                return None;
+    /// Some(path) if the shortcut is found, otherwise None.
+    pub fn find_shortcut(&self, name: &str) -> Option<Shortcut> {
+        debug!("find_shortcut {}", name);
+
+        let mut stmt = match self
+            .db_conn
+            .prepare_cached("SELECT id, path, description, remote FROM shortcuts WHERE name=(?1)")
+        {
+            Ok(stmt) => stmt,
+            Err(e) => {
+                error!("find_shortcut failed in prepare: {}", e);
+                return None;
+            }
+        };
+
+        let oshort = match stmt.query_map([name], |row| {
+            Ok(Shortcut {
+                id: row.get(0)?,
+                name: name.to_string(),
+                path: row.get(1)?,
+                description: row.get(2)?,
+                remote: row.get(3)?,
+            })
+        }) {
+            Ok(mut rows) => rows.next().and_then(|row| row.ok()),
+            Err(_) => None,
+        };
+        debug!("find_shortcut {:?}", oshort);
+        oshort
+    }
+
+    /// Expands the shortcut starting a path, e.g. `docs/reports/2024` into
+    /// `/home/me/documents/reports/2024` when `docs` is a shortcut of `/home/me/documents`.
+    ///
+    /// ### Parameters
+    /// text: a shortcut name, optionally followed by a `/` and a path relative to its directory
+    ///
+    /// ### Returns
+    /// Some((shortcut, path)) if the text starts with the name of a shortcut, otherwise None.
+    pub fn expand_shortcut(&self, text: &str) -> Option<(Shortcut, String)> {
+        let (name, suffix) = text.split_once('/').unwrap_or((text, ""));
+        let shortcut = self.find_shortcut(name)?;
+        let suffix = suffix.trim_matches('/');
+        let path = match suffix.is_empty() {
+            true => shortcut.path.clone(),
+            false => format!("{}/{}", shortcut.path.trim_end_matches('/'), suffix),
+        };
+        debug!("expand_shortcut {} -> {}", text, path);
+        Some((shortcut, path))
+    }
+
+    /// Lists the shortcuts of a path, ordered by name.
+    ///
+    /// ### Parameters
+    /// path: the path the shortcuts point at
+    ///
+    /// ### Returns
+    /// The shortcuts if the operation was successful, otherwise an error.
+    pub fn list_shortcuts_by_path(&self, path: &str) -> Result<Vec<Shortcut>, rusqlite::Error> {
+        debug!("list_shortcuts_by_path {}", path);
+        self.db_conn
+            .prepare_cached(
+                "SELECT id, name, path, description, remote FROM shortcuts WHERE path=(?1) ORDER BY name asc, id desc",
+            )?
+            .query_map([path], |row| {
+                Ok(Shortcut {
+                    id: row.get(0)?,
+                    name: row.get(1)?,
+                    path: row.get(2)?,
+                    description: row.get(3)?,
+                    remote: row.get(4)?,
+                })
+            })?
+            .collect()
+    }
+
+    /// Lists the local paths of the history visited at least `min_visits` times which are not
+    /// the path of a shortcut, the sensitive paths being left out.
+    ///
+    /// ### Returns
+    /// The paths and their number of visits, the most visited first, if the operation was
+    /// successful, otherwise an error.
+    pub fn list_frequent_paths_without_shortcut(
+        &self,
+        min_visits: usize,
+    ) -> Result<Vec<(String, usize)>, rusqlite::Error> {
+        debug!("list_frequent_paths_without_shortcut {}", min_visits);
+        self.db_conn
+            .prepare_cached(
+                "SELECT path, COUNT(*) AS visits FROM paths_history
+                WHERE path NOT IN (SELECT path FROM shortcuts)
+                AND path NOT IN (SELECT path FROM paths WHERE sensitive = 1 OR remote = 1)
+                GROUP BY path HAVING visits >= (?1) ORDER BY visits DESC, path ASC",
+            )?
+            .query_map([min_visits], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect()
+    }
+
+    /// Sets the environment hints of a shortcut, replacing the previous ones.
+    ///
+    /// ### Parameters
+    /// name: the name of the shortcut
+    /// hints: the hints to apply after jumping into the shortcut
+    ///
+    /// ### Returns
+    /// Ok(()) if the operation was successful, otherwise an error.
+    pub fn set_shortcut_hints(
+        &self,
+        name: &str,
+        hints: &ShortcutHints,
+    ) -> Result<(), rusqlite::Error> {
+        self.retry_busy(|| {
+            debug!("set_shortcut_hints {} {:?}", name, hints);
+            self.db_conn
+                .execute(
+                    "UPDATE shortcuts SET env=(?1), post_cd=(?2) WHERE name=(?3)",
+                    params![hints.env_to_column(), hints.post_cd, name],
+                )
+                .map_err(|e| {
+                    error!("Failed to set the hints of shortcut '{}': {}", name, e);
+                    e
+                })
+                .map(|_| ())
+        })
+    }
+
+    /// Finds the environment hints of the shortcut named `name`.
+    ///
+    /// ### Returns
+    /// Some(hints) if the shortcut is found, otherwise None.
+    pub fn find_shortcut_hints(
+        &self,
+        name: &str,
+    ) -> Result<Option<ShortcutHints>, rusqlite::Error> {
+        debug!("find_shortcut_hints {}", name);
+        let mut stmt = self
+            .db_conn
+            .prepare_cached("SELECT env, post_cd FROM shortcuts WHERE name=(?1)")?;
+        let mut rows = stmt.query_map([name], |row| {
+            Ok(ShortcutHints::from_columns(row.get(0)?, row.get(1)?))
+        })?;
+        rows.next().transpose()
+    }
+
+    /// The bundle the shortcut `name` was installed from.
+    ///
+    /// ### Returns
+    /// Some(bundle) if the shortcut belongs to a bundle, None for a personal shortcut or when
+    /// there is no such shortcut, if the operation was successful, otherwise an error.
+    pub fn find_shortcut_bundle(&self, name: &str) -> Result<Option<String>, rusqlite::Error> {
+        debug!("find_shortcut_bundle {}", name);
+        let mut stmt = self
+            .db_conn
+            .prepare_cached("SELECT bundle FROM shortcuts WHERE name=(?1)")?;
+        let mut rows = stmt.query_map([name], |row| row.get::<_, Option<String>>(0))?;
+        Ok(rows.next().transpose()?.flatten())
+    }
+
+    /// Makes the shortcut `name` belong to `bundle`, or a personal shortcut when None.
+    pub fn set_shortcut_bundle(
+        &self,
+        name: &str,
+        bundle: Option<&str>,
+    ) -> Result<(), rusqlite::Error> {
+        self.retry_busy(|| {
+            debug!("set_shortcut_bundle {} {:?}", name, bundle);
+            self.db_conn
+                .prepare_cached("UPDATE shortcuts SET bundle = (?2) WHERE name = (?1)")?
+                .execute(params![name, bundle])
+                .map(|_| ())
+        })
+    }
+
+    /// Lists the names of the shortcuts of `bundle`, ordered by name.
+    pub fn list_bundle_shortcut_names(&self, bundle: &str) -> Result<Vec<String>, rusqlite::Error> {
+        debug!("list_bundle_shortcut_names {}", bundle);
+        self.db_conn
+            .prepare_cached("SELECT name FROM shortcuts WHERE bundle = (?1) ORDER BY name")?
+            .query_map([bundle], |row| row.get(0))?
+            .collect()
+    }
+
+    /// The version of the bundle `name`, None if it is not installed.
+    pub fn find_bundle_version(&self, name: &str) -> Result<Option<u32>, rusqlite::Error> {
+        debug!("find_bundle_version {}", name);
+        let mut stmt = self
+            .db_conn
+            .prepare_cached("SELECT version FROM bundles WHERE name=(?1)")?;
+        let mut rows = stmt.query_map([name], |row| row.get(0))?;
+        rows.next().transpose()
+    }
+
+    /// Lists the installed bundles, by name.
+    ///
+    /// ### Returns
+    /// The name, the version and the number of shortcuts of the bundles if the operation was
+    /// successful, otherwise an error.
+    pub fn list_bundles(&self) -> Result<Vec<(String, u32, usize)>, rusqlite::Error> {
+        debug!("list_bundles");
+        self.db_conn
+            .prepare_cached(
+                "SELECT bundles.name, bundles.version, COUNT(shortcuts.id) FROM bundles \
+                 LEFT JOIN shortcuts ON shortcuts.bundle = bundles.name \
+                 GROUP BY bundles.name ORDER BY bundles.name",
+            )?
+            .query_map([], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get::<_, i64>(2)? as usize))
+            })?
+            .collect()
+    }
+
+    /// Records the bundle `name` as installed at `version`.
+    pub fn set_bundle_version(&self, name: &str, version: u32) -> Result<(), rusqlite::Error> {
+        self.retry_busy(|| {
+            debug!("set_bundle_version {} {}", name, version);
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs() as i64;
+            self.db_conn
+                .prepare_cached(
+                    "INSERT OR REPLACE INTO bundles (name, version, date) VALUES ((?1),(?2),(?3))",
+                )?
+                .execute(params![name, version, now])
+                .map(|_| ())
+        })
+    }
+
+    /// Deletes the bundle `name` and its shortcuts, the personal shortcuts being kept.
+    ///
+    /// ### Returns
+    /// The number of shortcuts deleted, None if the bundle is not installed, if the operation
+    /// was successful, otherwise an error.
+    pub fn delete_bundle(&self, name: &str) -> Result<Option<usize>, rusqlite::Error> {
+        self.retry_busy(|| {
+            debug!("delete_bundle {}", name);
+            let transaction = match self.db_conn.is_autocommit() {
+                true => Some(self.transaction()?),
+                false => None,
+            };
+            if self
+                .db_conn
+                .prepare_cached("DELETE FROM bundles WHERE name = (?1)")?
+                .execute([name])?
+                == 0
+            {
+                return Ok(None);
             }
-        };
+            let deleted = self
+                .db_conn
+                .prepare_cached("DELETE FROM shortcuts WHERE bundle = (?1)")?
+                .execute([name])?;
+            if let Some(transaction) = transaction {
+                transaction.commit()?;
+            }
+            Ok(Some(deleted))
+        })
+    }
 
-        let oshort = match stmt.query_map([name], |row| {
-            Ok(Shortcut {
-                id: row.get(0)?,
-                name: name.to_string(),
-                path: row.get(1)?,
-                description: row.get(2)?,
-            })
-        }) {
-            Ok(mut rows) => rows.next().and_then(|row| row.ok()),
-            Err(_) => None,
-        };
-        debug!("find_shortcut {:?}", oshort);
-        oshort
+    /// Finds a shortcut pointing to `path` that has environment hints.
+    ///
+    /// ### Returns
+    /// Some((shortcut name, hints)) if such a shortcut is found, otherwise None.
+    pub fn find_shortcut_hints_by_path(
+        &self,
+        path: &str,
+    ) -> Result<Option<(String, ShortcutHints)>, rusqlite::Error> {
+        debug!("find_shortcut_hints_by_path {}", path);
+        let mut stmt = self.db_conn.prepare_cached(
+            "SELECT name, env, post_cd FROM shortcuts WHERE path=(?1) AND (env IS NOT NULL OR post_cd IS NOT NULL) ORDER BY name asc, id desc",
+        )?;
+        let mut rows = stmt.query_map([path], |row| {
+            Ok((
+                row.get(0)?,
+                ShortcutHints::from_columns(row.get(1)?, row.get(2)?),
+            ))
+        })?;
+        rows.next().transpose()
     }
 
     /// Lists shortcuts from the database with pagination and optional filtering.
@@ -1038,40 +3918,149 @@ impl Store {
     ///
     /// ### Returns
     /// A vector of Shortcut entries if the operation was successful, otherwise an error.
-    pub(crate) fn list_shortcuts(
+    pub fn list_shortcuts(
+        &self,
+        pos: usize,
+        len: usize,
+        like_text: &str,
+        fuzzy: bool,
+    ) -> Result<Vec<Shortcut>, rusqlite::Error> {
+        self.list_shortcuts_sorted(pos, len, like_text, fuzzy, SortOrder::Default)
+    }
+
+    /// Lists shortcuts from the database like `list_shortcuts`, with a specific ordering.
+    ///
+    /// ### Parameters
+    /// pos: the starting position (offset) for pagination
+    /// len: the number of shortcuts to return
+    /// like_text: optional text to filter shortcuts (if empty, no filtering is applied)
+    /// fuzzy: if true, perform a fuzzy search
+    /// sort: the ordering of the results (only the name and path columns apply)
+    ///
+    /// ### Returns
+    /// A vector of Shortcut entries if the operation was successful, otherwise an error.
+    pub fn list_shortcuts_sorted(
         &self,
         pos: usize,
         len: usize,
         like_text: &str,
         fuzzy: bool,
+        sort: SortOrder,
     ) -> Result<Vec<Shortcut>, rusqlite::Error> {
         debug!(
-            "list_shortcuts pos={} len={} text={} fuzzy={}",
-            pos, len, like_text, fuzzy
+            "list_shortcuts pos={} len={} text={} fuzzy={} sort={:?}",
+            pos, len, like_text, fuzzy, sort
         );
 
         if like_text.is_empty() || !fuzzy {
-            self.list_shortcuts_exact(pos, len, like_text)
+            self.list_shortcuts_exact(pos, len, like_text, sort)
         } else {
-            self.list_shortcuts_fuzzy(pos, len, like_text)
+            self.list_shortcuts_fuzzy(pos, len, like_text, sort)
+        }
+    }
+
+    /// Lists the shortcuts matching a stack of filters, ordered like the results of the last
+    /// one, like `list_paths_filtered`.
+    #[instrument(level = "debug", skip(self, filters), fields(levels = filters.len()))]
+    pub fn list_shortcuts_filtered(
+        &self,
+        pos: usize,
+        len: usize,
+        filters: &[Filter],
+        sort: SortOrder,
+    ) -> Result<Vec<Shortcut>, rusqlite::Error> {
+        let [frozen @ .., current] = filters else {
+            return self.list_shortcuts_sorted(pos, len, "", false, sort);
+        };
+        if frozen.is_empty() {
+            return self.list_shortcuts_sorted(pos, len, &current.text, current.fuzzy, sort);
+        }
+        let ids = self.narrowed_shortcut_ids(filters, sort)?;
+        let ids: Vec<i64> = ids.into_iter().skip(pos).take(len).collect();
+        self.list_shortcuts_by_ids(&ids)
+    }
+
+    /// Counts the shortcuts `list_shortcuts_filtered` returns.
+    #[instrument(level = "debug", skip_all, fields(levels = filters.len()))]
+    pub fn count_shortcuts_filtered(&self, filters: &[Filter]) -> Result<usize, rusqlite::Error> {
+        match filters {
+            [] => self.count_shortcuts("", false),
+            [current] => self.count_shortcuts(&current.text, current.fuzzy),
+            _ => self
+                .narrowed_shortcut_ids(filters, SortOrder::Default)
+                .map(|ids| ids.len()),
         }
     }
 
+    /// The ids of the shortcuts matching all the filters, in the order of the results of the
+    /// last one.
+    fn narrowed_shortcut_ids(
+        &self,
+        filters: &[Filter],
+        sort: SortOrder,
+    ) -> Result<Vec<i64>, rusqlite::Error> {
+        let shortcut_ids = |filter: &Filter, sort| -> Result<Vec<i64>, rusqlite::Error> {
+            Ok(self
+                .list_shortcuts_sorted(0, ALL_ENTRIES, &filter.text, filter.fuzzy, sort)?
+                .into_iter()
+                .map(|shortcut| shortcut.id)
+                .collect())
+        };
+        let mut ids = match filters.last() {
+            Some(current) => shortcut_ids(current, sort)?,
+            None => return Ok(vec![]),
+        };
+        for filter in &filters[..filters.len() - 1] {
+            let allowed: HashSet<i64> = shortcut_ids(filter, SortOrder::Default)?
+                .into_iter()
+                .collect();
+            ids.retain(|id| allowed.contains(id));
+        }
+        Ok(ids)
+    }
+
     fn list_shortcuts_fuzzy(
         &self,
         pos: usize,
         len: usize,
         like_text: &str,
+        sort: SortOrder,
     ) -> Result<Vec<Shortcut>, rusqlite::Error> {
         debug!(
             "list_shortcuts_fuzzy pos={} len={} like_text={}",
             pos, len, like_text
         );
+        let ids = self.fuzzy_shortcut_ids(like_text, sort)?;
+        // Paginate: skip `pos`, take `len`
+        let ids: Vec<i64> = ids.iter().skip(pos).take(len).copied().collect();
+        self.list_shortcuts_by_ids(&ids)
+    }
 
+    /// The ids of the shortcuts matching a fuzzy search, in the order of the results, cached
+    /// like the ones of the paths.
+    fn fuzzy_shortcut_ids(
+        &self,
+        like_text: &str,
+        sort: SortOrder,
+    ) -> Result<Rc<Vec<i64>>, rusqlite::Error> {
+        let key = self.fuzzy_search_key(like_text, &SearchTokens::default(), sort)?;
+        self.cached_fuzzy_ids("shortcuts", key, || self.score_shortcuts(like_text, sort))
+    }
+
+    /// Scores all the shortcuts against a fuzzy search on their name, path and description.
+    ///
+    /// ### Returns
+    /// The ids of the matching shortcuts, in the order of the results.
+    fn score_shortcuts(
+        &self,
+        like_text: &str,
+        sort: SortOrder,
+    ) -> Result<Vec<i64>, rusqlite::Error> {
+        debug!("score_shortcuts like_text={}", like_text);
         let sql = String::from(
-            "SELECT id, name, path, description FROM shortcuts ORDER BY name asc, id desc",
+            "SELECT id, name, path, description, remote FROM shortcuts ORDER BY name asc, id desc",
         );
-        let mut stmt = match self.db_conn.prepare(sql.as_str()) {
+        let mut stmt = match self.db_conn.prepare_cached(sql.as_str()) {
             Ok(stmt) => stmt,
             Err(e) => {
                 error!("list_shortcuts failed in prepare {}: {}", sql, e);
@@ -1079,15 +4068,16 @@ impl Store {
             }
         };
         let params: Vec<String> = vec![];
-        let mut matcher = Matcher::new(nucleo_matcher::Config::DEFAULT.match_paths());
-        let matches = Pattern::parse(like_text, CaseMatching::Ignore, Normalization::Smart);
-        let mut buf = Vec::new();
+        let fuzzy_config = self.config.lock().unwrap().fuzzy.clone();
+        let mut matcher =
+            matcher::build(&fuzzy_config, like_text, self.is_case_sensitive(like_text));
         let rows = stmt.query_map(rusqlite::params_from_iter(params), |row| {
             Ok(Shortcut {
                 id: row.get(0)?,
                 name: row.get(1)?,
                 path: row.get(2)?,
                 description: row.get(3)?,
+                remote: row.get(4)?,
             })
         });
 
@@ -1103,8 +4093,9 @@ impl Store {
                         s.push(' ');
                         s.push_str(desc);
                     }
-                    let s = Utf32Str::new(&s, &mut buf);
-                    matches.score(s, &mut matcher).map(|score| {
+                    matcher.score(&s).map(|score| {
+                        let score = score
+                            + Self::shortcut_name_bonus(matcher.as_mut(), &shortcut, &fuzzy_config);
                         trace!("Scoring shortcut '{}' score={:?}", shortcut.name, score);
                         (shortcut, score)
                     })
@@ -1119,17 +4110,63 @@ impl Store {
         }
         .collect();
 
-        // Sort by descending score
-        scored_shortcuts.sort_by(|a, b| b.1.cmp(&a.1));
+        match sort {
+            // Sort by descending score
+            SortOrder::Default => scored_shortcuts.sort_by_key(|a| Reverse(a.1)),
+            SortOrder::Ascending(column) => {
+                scored_shortcuts.sort_by(|a, b| Self::compare_shortcuts(column, &a.0, &b.0))
+            }
+            SortOrder::Descending(column) => {
+                scored_shortcuts.sort_by(|a, b| Self::compare_shortcuts(column, &b.0, &a.0))
+            }
+        }
 
-        // Paginate: skip `pos`, take `len`
-        let paginated = scored_shortcuts
+        Ok(scored_shortcuts
             .into_iter()
-            .skip(pos)
-            .take(len)
-            .map(|(shortcut, _)| shortcut)
-            .collect();
-        Ok(paginated)
+            .map(|(shortcut, _)| shortcut.id)
+            .collect())
+    }
+
+    /// The shortcuts of the given ids, in the same order
+    fn list_shortcuts_by_ids(&self, ids: &[i64]) -> Result<Vec<Shortcut>, rusqlite::Error> {
+        if ids.is_empty() {
+            return Ok(vec![]);
+        }
+        let sql = format!(
+            "SELECT id, name, path, description, remote FROM shortcuts WHERE id IN ({})",
+            vec!["?"; ids.len()].join(", ")
+        );
+        let mut stmt = self.db_conn.prepare_cached(&sql)?;
+        let mut shortcuts: HashMap<i64, Shortcut> = stmt
+            .query_map(rusqlite::params_from_iter(ids), |row| {
+                Ok(Shortcut {
+                    id: row.get(0)?,
+                    name: row.get(1)?,
+                    path: row.get(2)?,
+                    description: row.get(3)?,
+                    remote: row.get(4)?,
+                })
+            })?
+            .map(|shortcut| shortcut.map(|shortcut| (shortcut.id, shortcut)))
+            .collect::<Result<_, _>>()?;
+        Ok(ids.iter().filter_map(|id| shortcuts.remove(id)).collect())
+    }
+
+    /// Compare two shortcuts on the given column, used to order the fuzzy search results
+    fn compare_shortcuts(column: SortColumn, a: &Shortcut, b: &Shortcut) -> std::cmp::Ordering {
+        match column {
+            SortColumn::Path => a.path.cmp(&b.path),
+            _ => a.name.cmp(&b.name),
+        }
+    }
+
+    /// The SQL condition of an exact search of the shortcuts, the search text being `?1`
+    fn shortcut_contains_condition(case_sensitive: bool) -> String {
+        ["path", "name", "description"]
+            .iter()
+            .map(|column| Self::contains_condition(column, 1, case_sensitive))
+            .collect::<Vec<_>>()
+            .join(" OR ")
     }
 
     fn list_shortcuts_exact(
@@ -1137,22 +4174,38 @@ impl Store {
         pos: usize,
         len: usize,
         like_text: &str,
+        sort: SortOrder,
     ) -> Result<Vec<Shortcut>, rusqlite::Error> {
         debug!("list_shortcuts pos={} len={} text={}", pos, len, like_text);
+        let order_by = sort.order_by("name asc, id desc");
 
-        let mut sql = String::from("SELECT id, name, path, description FROM shortcuts");
+        let mut sql = String::from("SELECT id, name, path, description, remote FROM shortcuts");
         let mut params: Vec<String> = vec![];
-        if !like_text.is_empty() {
-            sql.push_str(" WHERE path like '%' || (?1) || '%' OR name like '%' || (?1) || '%' OR description like '%' || (?1) || '%'");
-            sql.push_str(" ORDER BY name asc, id desc LIMIT (?2) OFFSET (?3)");
+        let patterns = Self::glob_patterns(like_text);
+        let case_sensitive = self.is_case_sensitive(like_text);
+        if !patterns.is_empty() {
+            let condition = Self::glob_condition(patterns, case_sensitive, &mut params);
+            sql.push_str(&format!(
+                " WHERE {} ORDER BY {} LIMIT (?{}) OFFSET (?{})",
+                condition,
+                order_by,
+                params.len() + 1,
+                params.len() + 2
+            ));
+        } else if !like_text.is_empty() {
+            sql.push_str(&format!(
+                " WHERE {} ORDER BY {} LIMIT (?2) OFFSET (?3)",
+                Self::shortcut_contains_condition(case_sensitive),
+                order_by
+            ));
             params.push(like_text.to_string());
         } else {
-            sql.push_str(" ORDER BY name asc, id desc LIMIT (?1) OFFSET (?2)");
+            sql.push_str(&format!(" ORDER BY {} LIMIT (?1) OFFSET (?2)", order_by));
         }
         params.push(format!("{}", len));
         params.push(format!("{}", pos));
 
-        let mut stmt = match self.db_conn.prepare(sql.as_str()) {
+        let mut stmt = match self.db_conn.prepare_cached(sql.as_str()) {
             Ok(stmt) => stmt,
             Err(e) => {
                 error!("list_shortcuts failed in prepare {}: {}", sql, e);
@@ -1167,6 +4220,7 @@ impl Store {
                 name: row.get(1)?,
                 path: row.get(2)?,
                 description: row.get(3)?,
+                remote: row.get(4)?,
             })
         }) {
             Ok(rows) => rows,
@@ -1182,18 +4236,58 @@ impl Store {
         }
         Ok(shortcuts)
     }
+
+    /// Counts the shortcuts `list_shortcuts_sorted` returns for a search text, without fetching
+    /// them for an exact search.
+    ///
+    /// ### Parameters
+    /// like_text: optional text to filter shortcuts (if empty, no filtering is applied)
+    /// fuzzy: if true, count the shortcuts of a fuzzy search
+    pub fn count_shortcuts(&self, like_text: &str, fuzzy: bool) -> Result<usize, rusqlite::Error> {
+        debug!("count_shortcuts like_text={} fuzzy={}", like_text, fuzzy);
+        if !like_text.is_empty() && fuzzy {
+            return self
+                .fuzzy_shortcut_ids(like_text, SortOrder::Default)
+                .map(|ids| ids.len());
+        }
+        let patterns = Self::glob_patterns(like_text);
+        let case_sensitive = self.is_case_sensitive(like_text);
+        let count: i64 = if like_text.is_empty() {
+            self.db_conn
+                .prepare_cached("SELECT COUNT(*) FROM shortcuts")?
+                .query_row([], |row| row.get(0))?
+        } else if !patterns.is_empty() {
+            let mut params = vec![];
+            let condition = Self::glob_condition(patterns, case_sensitive, &mut params);
+            self.db_conn
+                .prepare_cached(&format!(
+                    "SELECT COUNT(*) FROM shortcuts WHERE {}",
+                    condition
+                ))?
+                .query_row(rusqlite::params_from_iter(params), |row| row.get(0))?
+        } else {
+            self.db_conn
+                .prepare_cached(&format!(
+                    "SELECT COUNT(*) FROM shortcuts WHERE {}",
+                    Self::shortcut_contains_condition(case_sensitive)
+                ))?
+                .query_row([like_text], |row| row.get(0))?
+        };
+        Ok(count as usize)
+    }
+
     /// Lists all shortcuts from the database.
     /// The results are ordered by name (ascending) and ID (descending).
     ///
     /// ### Returns
     /// A vector of all Shortcut entries if the operation was successful, otherwise an error.
-    pub(crate) fn list_all_shortcuts(&self) -> Result<Vec<Shortcut>, rusqlite::Error> {
+    pub fn list_all_shortcuts(&self) -> Result<Vec<Shortcut>, rusqlite::Error> {
         debug!("list_all_shortcuts");
         let sql = String::from(
-            "SELECT id, name, path, description FROM shortcuts ORDER BY name asc, id desc",
+            "SELECT id, name, path, description, remote FROM shortcuts ORDER BY name asc, id desc",
         );
 
-        let mut stmt = match self.db_conn.prepare(sql.as_str()) {
+        let mut stmt = match self.db_conn.prepare_cached(sql.as_str()) {
             Ok(stmt) => stmt,
             Err(e) => {
                 error!("list_paths failed in prepare {}: {}", sql, e);
@@ -1208,6 +4302,7 @@ impl Store {
                 name: row.get(1)?,
                 path: row.get(2)?,
                 description: row.get(3)?,
+                remote: row.get(4)?,
             })
         }) {
             Ok(rows) => rows,
@@ -1224,14 +4319,589 @@ impl Store {
         Ok(shortcuts)
     }
 
+    /// Saves the GUI session, replacing the previous one.
+    ///
+    /// ### Returns
+    /// Ok(()) if the operation was successful, otherwise an error.
+    pub fn save_gui_session(&self, session: &GuiSession) -> Result<(), rusqlite::Error> {
+        self.retry_busy(|| {
+            debug!("save_gui_session {:?}", session);
+            self.db_conn
+                .execute(
+                    "INSERT OR REPLACE INTO gui_session (id, search, fuzzy_match, view, selected_row) VALUES (0, ?1, ?2, ?3, ?4)",
+                    params![
+                        session.search,
+                        session.fuzzy_match,
+                        session.view,
+                        session.selected_row as i64
+                    ],
+                )
+                .map_err(|e| {
+                    error!("Failed to save the GUI session: {}", e);
+                    e
+                })
+                .map(|_| ())
+        })
+    }
+
+    /// Loads the last saved GUI session.
+    ///
+    /// ### Returns
+    /// Some(session) if a session was saved, otherwise None.
+    pub fn load_gui_session(&self) -> Result<Option<GuiSession>, rusqlite::Error> {
+        debug!("load_gui_session");
+        let mut stmt = self.db_conn.prepare_cached(
+            "SELECT search, fuzzy_match, view, selected_row FROM gui_session WHERE id = 0",
+        )?;
+        let mut rows = stmt.query_map([], |row| {
+            Ok(GuiSession {
+                search: row.get(0)?,
+                fuzzy_match: row.get(1)?,
+                view: row.get(2)?,
+                selected_row: row.get::<_, i64>(3)?.max(0) as usize,
+            })
+        })?;
+        rows.next().transpose()
+    }
+
+    /// Records a search made in a view, replacing the previous identical one. Only the
+    /// `SEARCH_HISTORY_SIZE` most recent searches of each view are kept.
+    ///
+    /// ### Parameters
+    /// view: the objects type of the view (e.g. "path" or "shortcut")
+    /// search: the search text, ignored when empty
+    /// fuzzy_match: whether the search is fuzzy
+    pub fn add_search(
+        &self,
+        view: &str,
+        search: &str,
+        fuzzy_match: bool,
+    ) -> Result<(), rusqlite::Error> {
+        self.retry_busy(|| {
+            debug!("add_search view={} search={}", view, search);
+            if search.trim().is_empty() {
+                return Ok(());
+            }
+            let date = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs() as i64;
+            self.db_conn.execute(
+                "DELETE FROM search_history WHERE view = ?1 AND search = ?2",
+                params![view, search],
+            )?;
+            self.db_conn.execute(
+                "INSERT INTO search_history (view, search, fuzzy_match, date) VALUES (?1, ?2, ?3, ?4)",
+                params![view, search, fuzzy_match, date],
+            )?;
+            self.db_conn
+                .execute(
+                    "DELETE FROM search_history WHERE view = ?1 AND id NOT IN \
+                    (SELECT id FROM search_history WHERE view = ?1 ORDER BY date DESC, id DESC LIMIT ?2)",
+                    params![view, SEARCH_HISTORY_SIZE as i64],
+                )
+                .map(|_| ())
+        })
+    }
+
+    /// Lists the searches made in a view, the most recent first.
+    pub fn list_searches(&self, view: &str) -> Result<Vec<Filter>, rusqlite::Error> {
+        debug!("list_searches view={}", view);
+        let mut stmt = self.db_conn.prepare_cached(
+            "SELECT search, fuzzy_match FROM search_history WHERE view = ?1 ORDER BY date DESC, id DESC",
+        )?;
+        let rows = stmt.query_map([view], |row| {
+            Ok(Filter {
+                text: row.get(0)?,
+                fuzzy: row.get(1)?,
+            })
+        })?;
+        rows.collect()
+    }
+
+    /// Records that a file was opened now, only the `FILES_SIZE` most recent files being kept.
+    pub fn add_file(&self, path: &str) -> Result<(), rusqlite::Error> {
+        self.add_file_with_time(path, Local::now().timestamp())
+    }
+
+    /// Records that a file was opened at `date`, see `add_file`.
+    pub fn add_file_with_time(&self, path: &str, date: i64) -> Result<(), rusqlite::Error> {
+        self.retry_busy(|| {
+            debug!("add_file path={} date={}", path, date);
+            let transaction = self.transaction()?;
+            self.db_conn
+                .prepare_cached(
+                    "INSERT INTO files (path, date) VALUES (?1, ?2) \
+                     ON CONFLICT(path) DO UPDATE SET date = excluded.date",
+                )?
+                .execute(params![path, date])?;
+            self.db_conn
+                .prepare_cached(
+                    "DELETE FROM files WHERE id NOT IN \
+                     (SELECT id FROM files ORDER BY date DESC, id DESC LIMIT ?1)",
+                )?
+                .execute([FILES_SIZE as i64])?;
+            transaction.commit()
+        })
+    }
+
+    /// Forgets a recent file by its ID.
+    pub fn delete_file_by_id(&self, id: i64) -> Result<(), rusqlite::Error> {
+        self.retry_busy(|| {
+            debug!("delete_file_by_id {}", id);
+            self.db_conn
+                .prepare_cached("DELETE FROM files WHERE id = (?1)")?
+                .execute([id])
+                .map(|_l| ())
+        })
+    }
+
+    /// Lists the recent files matching all the filters, ordered like the results of the last
+    /// one: the most recent first, or the best match first for a fuzzy search.
+    ///
+    /// ### Parameters
+    /// pos: the starting position (offset) for pagination
+    /// len: the number of files to return
+    /// filters: the filters the files must all match, the last one being the current search
+    /// sort: the ordering of the results (only the date and path columns apply)
+    #[instrument(level = "debug", skip(self, filters), fields(levels = filters.len()))]
+    pub fn list_files_filtered(
+        &self,
+        pos: usize,
+        len: usize,
+        filters: &[Filter],
+        sort: SortOrder,
+    ) -> Result<Vec<RecentFile>, rusqlite::Error> {
+        Ok(self
+            .matching_files(filters, sort)?
+            .into_iter()
+            .skip(pos)
+            .take(len)
+            .collect())
+    }
+
+    /// Counts the recent files matching all the filters, see `list_files_filtered`.
+    #[instrument(level = "debug", skip_all, fields(levels = filters.len()))]
+    pub fn count_files_filtered(&self, filters: &[Filter]) -> Result<usize, rusqlite::Error> {
+        self.matching_files(filters, SortOrder::Default)
+            .map(|files| files.len())
+    }
+
+    /// The recent files matching all the filters, scored in memory as there are at most
+    /// `FILES_SIZE` of them.
+    fn matching_files(
+        &self,
+        filters: &[Filter],
+        sort: SortOrder,
+    ) -> Result<Vec<RecentFile>, rusqlite::Error> {
+        debug!("matching_files filters={:?} sort={:?}", filters, sort);
+        let sql = format!(
+            "SELECT id, path, date FROM files ORDER BY {}",
+            sort.order_by("date desc, id desc")
+        );
+        let files = self
+            .db_conn
+            .prepare_cached(&sql)?
+            .query_map([], |row| {
+                Ok(RecentFile {
+                    id: row.get(0)?,
+                    path: row.get(1)?,
+                    date: row.get(2)?,
+                })
+            })?
+            .collect::<Result<Vec<RecentFile>, rusqlite::Error>>()?;
+        let fuzzy_config = self.config.lock().unwrap().fuzzy.clone();
+        let mut scored: Vec<(RecentFile, u32)> = files.into_iter().map(|file| (file, 0)).collect();
+        for filter in filters.iter().filter(|filter| !filter.text.is_empty()) {
+            let case_sensitive = self.is_case_sensitive(&filter.text);
+            let mut matcher = matcher::build(&fuzzy_config, &filter.text, case_sensitive);
+            let text_lower = filter.text.to_lowercase();
+            scored = scored
+                .into_iter()
+                .filter_map(|(file, _)| {
+                    let score = match (filter.fuzzy, case_sensitive) {
+                        (true, _) => matcher.score(&file.path)?,
+                        (false, true) => file.path.contains(&filter.text).then_some(0)?,
+                        (false, false) => file
+                            .path
+                            .to_lowercase()
+                            .contains(&text_lower)
+                            .then_some(0)?,
+                    };
+                    Some((file, score))
+                })
+                .collect();
+        }
+        if sort == SortOrder::Default && filters.last().is_some_and(|filter| filter.fuzzy) {
+            // stable: the most recent first among the same scores
+            scored.sort_by_key(|scored| Reverse(scored.1));
+        }
+        Ok(scored.into_iter().map(|(file, _)| file).collect())
+    }
+
+    /// Records the last command run in a directory, replacing the previous one.
+    pub fn add_command(&self, path: &str, command: &str) -> Result<(), rusqlite::Error> {
+        self.retry_busy(|| {
+            debug!("add_command path={} command={}", path, command);
+            self.db_conn
+                .prepare_cached(
+                    "INSERT OR REPLACE INTO last_commands (path, command, date) \
+                     VALUES (?1, ?2, ?3)",
+                )?
+                .execute(params![path, command, Local::now().timestamp()])
+                .map(|_| ())
+        })
+    }
+
+    /// The last command run in each of the paths having one.
+    pub fn last_commands(
+        &self,
+        paths: &[&str],
+    ) -> Result<HashMap<String, String>, rusqlite::Error> {
+        let mut commands = HashMap::new();
+        for path in paths {
+            let command: Option<String> = self
+                .db_conn
+                .prepare_cached("SELECT command FROM last_commands WHERE path = (?1)")?
+                .query_map([path], |row| row.get(0))?
+                .next()
+                .transpose()?;
+            if let Some(command) = command {
+                commands.insert(path.to_string(), command);
+            }
+        }
+        Ok(commands)
+    }
+
+    /// Set the smart suggestions parameters of a directory and of its sub directories, the
+    /// settings being removed when no parameter is set.
+    pub fn set_suggestion_settings(
+        &self,
+        path: &str,
+        settings: SuggestionSettings,
+    ) -> Result<(), rusqlite::Error> {
+        self.retry_busy(|| {
+            debug!(
+                "set_suggestion_settings path={} settings={:?}",
+                path, settings
+            );
+            if settings == SuggestionSettings::default() {
+                self.db_conn
+                    .prepare_cached("DELETE FROM suggestion_settings WHERE path = (?1)")?
+                    .execute([path])?;
+            } else {
+                self.db_conn
+                    .prepare_cached(
+                        "INSERT OR REPLACE INTO suggestion_settings (path, depth, count) \
+                         VALUES ((?1), (?2), (?3))",
+                    )?
+                    .execute(params![
+                        path,
+                        settings.depth.map(|depth| depth as i64),
+                        settings.count.map(|count| count as i64)
+                    ])?;
+            }
+            Ok(())
+        })
+    }
+
+    /// The smart suggestions parameters set for `path` itself, none when it has no settings.
+    pub fn suggestion_settings(&self, path: &str) -> Result<SuggestionSettings, rusqlite::Error> {
+        self.find_suggestion_settings(
+            "SELECT depth, count FROM suggestion_settings WHERE path = (?1)",
+            path,
+        )
+    }
+
+    /// The smart suggestions parameters applying to `path`: the ones of the closest directory
+    /// containing it, itself included.
+    pub fn effective_suggestion_settings(
+        &self,
+        path: &str,
+    ) -> Result<SuggestionSettings, rusqlite::Error> {
+        self.find_suggestion_settings(
+            "SELECT depth, count FROM suggestion_settings \
+             WHERE path = (?1) OR substr((?1), 1, length(path) + 1) = path || '/' \
+             ORDER BY length(path) DESC LIMIT 1",
+            path,
+        )
+    }
+
+    fn find_suggestion_settings(
+        &self,
+        sql: &str,
+        path: &str,
+    ) -> Result<SuggestionSettings, rusqlite::Error> {
+        let settings = self
+            .db_conn
+            .prepare_cached(sql)?
+            .query_map([path], |row| {
+                Ok(SuggestionSettings {
+                    depth: row
+                        .get::<_, Option<i64>>(0)?
+                        .map(|depth| depth.max(1) as usize),
+                    count: row
+                        .get::<_, Option<i64>>(1)?
+                        .map(|count| count.max(1) as usize),
+                })
+            })?
+            .next()
+            .transpose()?;
+        Ok(settings.unwrap_or_default())
+    }
+
+    /// Lists the cached directory sizes.
+    pub fn list_dir_sizes(&self) -> Result<Vec<(String, DirSize)>, rusqlite::Error> {
+        debug!("list_dir_sizes");
+        let mut stmt = self
+            .db_conn
+            .prepare_cached("SELECT path, size, date FROM dir_sizes")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get(0)?,
+                DirSize {
+                    size: row.get::<_, i64>(1)?.max(0) as u64,
+                    date: row.get(2)?,
+                },
+            ))
+        })?;
+        rows.collect()
+    }
+
+    /// The types of the projects of local paths, detected again when unknown or detected more
+    /// than a day ago.
+    ///
+    /// ### Returns
+    /// The type of each path which is a project if the operation was successful, otherwise an
+    /// error.
+    pub fn project_types(
+        &self,
+        paths: &[&str],
+    ) -> Result<HashMap<String, ProjectType>, rusqlite::Error> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+        let mut project_types = HashMap::new();
+        for path in paths {
+            if RemotePath::parse(path).is_some() {
+                continue;
+            }
+            let cached: Option<(Option<String>, i64)> = self
+                .db_conn
+                .prepare_cached("SELECT type, date FROM project_types WHERE path = (?1)")?
+                .query_map([path], |row| Ok((row.get(0)?, row.get(1)?)))?
+                .next()
+                .transpose()?;
+            let project_type = match cached {
+                Some((name, date)) if date > now - PROJECT_TYPE_TTL => {
+                    name.as_deref().and_then(ProjectType::from_name)
+                }
+                _ => self.detect_project_type(path, now)?,
+            };
+            if let Some(project_type) = project_type {
+                project_types.insert(path.to_string(), project_type);
+            }
+        }
+        Ok(project_types)
+    }
+
+    /// Detects the types of the projects of the local paths of the history which are unknown
+    /// or were detected more than a day ago, so that they can be searched.
+    pub fn refresh_project_types(&self) -> Result<(), rusqlite::Error> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+        let paths: Vec<String> = self
+            .db_conn
+            .prepare_cached(
+                "SELECT path FROM paths WHERE remote = 0 AND path NOT IN \
+                (SELECT path FROM project_types WHERE date > (?1))",
+            )?
+            .query_map([now - PROJECT_TYPE_TTL], |row| row.get(0))?
+            .collect::<Result<_, _>>()?;
+        debug!("refresh_project_types {} paths", paths.len());
+        if paths.is_empty() {
+            return Ok(());
+        }
+        let transaction = match self.db_conn.is_autocommit() {
+            true => Some(self.transaction()?),
+            false => None,
+        };
+        for path in &paths {
+            self.detect_project_type(path, now)?;
+        }
+        transaction.map_or(Ok(()), |transaction| transaction.commit())
+    }
+
+    /// Detects the type of the project of a path, caching it.
+    fn detect_project_type(
+        &self,
+        path: &str,
+        now: i64,
+    ) -> Result<Option<ProjectType>, rusqlite::Error> {
+        self.retry_busy(|| {
+            let project_type = ProjectType::detect(path);
+            trace!("detect_project_type {} {:?}", path, project_type);
+            self.db_conn
+                .prepare_cached(
+                    "INSERT OR REPLACE INTO project_types (path, type, date) VALUES (?1, ?2, ?3)",
+                )?
+                .execute(params![path, project_type.map(|t| t.name()), now])?;
+            Ok(project_type)
+        })
+    }
+
+    /// Caches the size of a directory, replacing the previous one.
+    pub fn set_dir_size(&self, path: &str, size: &DirSize) -> Result<(), rusqlite::Error> {
+        self.retry_busy(|| {
+            debug!("set_dir_size {} {:?}", path, size);
+            self.db_conn
+                .execute(
+                    "INSERT OR REPLACE INTO dir_sizes (path, size, date) VALUES (?1, ?2, ?3)",
+                    params![path, size.size as i64, size.date],
+                )
+                .map(|_| ())
+        })
+    }
+
+    /// Merges the paths, the history and the shortcuts of another cdir database, in a single
+    /// transaction. The other database is left untouched: a copy of it is brought to the
+    /// current schema, then attached.
+    ///
+    /// - a visit of the history already recorded at the same date is skipped;
+    /// - a path keeps the date and host of its last visit among both databases, and is
+    ///   sensitive if it is in either;
+    /// - a shortcut whose name is taken by a shortcut of another directory is dropped and
+    ///   reported as a conflict.
+    ///
+    /// ### Parameters
+    /// other_db: the path to the database file to merge
+    /// dry_run: roll the merge back, only counting what it would change
+    ///
+    /// ### Returns
+    /// The counts of the merged entries and the conflicts
+    pub fn merge_database(
+        &self,
+        other_db: &std::path::Path,
+        dry_run: bool,
+    ) -> Result<MergeSummary, Box<dyn std::error::Error>> {
+        info!("merge_database {} dry_run={}", other_db.display(), dry_run);
+        if !other_db.is_file() {
+            return Err(format!("{} does not exist", other_db.display()).into());
+        }
+        // VACUUM INTO copies the content of the WAL of the other database too
+        let copy_dir = tempfile::tempdir()?;
+        let copy = copy_dir.path().join("merged.db");
+        Connection::open_with_flags(other_db, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)?
+            .execute("VACUUM INTO (?1)", [copy.to_str().unwrap_or_default()])?;
+        drop(Store::new(&copy, self.config.clone())?);
+
+        self.db_conn.execute(
+            "ATTACH DATABASE (?1) AS other",
+            [copy.to_str().unwrap_or_default()],
+        )?;
+        let summary = self.merge_attached(dry_run);
+        if let Err(e) = self.db_conn.execute("DETACH DATABASE other", []) {
+            warn!("Failed to detach {}: {}", copy.display(), e);
+        }
+        Ok(summary?)
+    }
+
+    /// Merges the database attached as `other`, see `merge_database`.
+    fn merge_attached(&self, dry_run: bool) -> Result<MergeSummary, rusqlite::Error> {
+        let transaction = self.transaction()?;
+        let mut summary = MergeSummary::default();
+
+        let other_history: usize =
+            self.db_conn
+                .query_row("SELECT COUNT(*) FROM other.paths_history", [], |row| {
+                    row.get::<_, i64>(0)
+                })? as usize;
+        summary.history_added = self.db_conn.execute(
+            "INSERT INTO main.paths_history (path, date, tmux_pane, hostname, container) \
+             SELECT path, date, tmux_pane, hostname, container FROM other.paths_history AS o \
+             WHERE NOT EXISTS (SELECT 1 FROM main.paths_history AS m \
+             WHERE m.path = o.path AND m.date = o.date) ORDER BY o.id",
+            [],
+        )?;
+        summary.history_duplicates = other_history - summary.history_added;
+
+        summary.paths_updated = self.db_conn.execute(
+            "UPDATE main.paths SET date = o.date, hostname = o.hostname, deleted_at = o.deleted_at \
+             FROM other.paths AS o WHERE o.path = paths.path AND o.date > paths.date",
+            [],
+        )?;
+        self.db_conn.execute(
+            "UPDATE main.paths SET sensitive = 1 \
+             WHERE path IN (SELECT path FROM other.paths WHERE sensitive != 0)",
+            [],
+        )?;
+        summary.paths_added = self.db_conn.execute(
+            "INSERT INTO main.paths (path, date, hostname, remote, sensitive, deleted_at) \
+             SELECT path, date, hostname, remote, sensitive, deleted_at FROM other.paths AS o \
+             WHERE NOT EXISTS (SELECT 1 FROM main.paths AS m WHERE m.path = o.path) \
+             ORDER BY o.date, o.id",
+            [],
+        )?;
+
+        let other_shortcuts: Vec<(String, String)> = self
+            .db_conn
+            .prepare_cached("SELECT name, path FROM other.shortcuts ORDER BY name")?
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<_, _>>()?;
+        for (name, other_path) in other_shortcuts {
+            match self.find_shortcut(&name) {
+                Some(shortcut) if shortcut.path == other_path => summary.shortcuts_duplicates += 1,
+                Some(shortcut) => {
+                    warn!(
+                        "Shortcut '{}' kept on {}, not merged on {}",
+                        name, shortcut.path, other_path
+                    );
+                    summary.shortcut_conflicts.push(ShortcutConflict {
+                        name,
+                        path: shortcut.path,
+                        other_path,
+                    })
+                }
+                None => {
+                    self.db_conn.execute(
+                        "INSERT INTO main.shortcuts (name, path, description, env, post_cd, remote, created) \
+                         SELECT name, path, description, env, post_cd, remote, created \
+                         FROM other.shortcuts WHERE name = (?1)",
+                        [&name],
+                    )?;
+                    summary.shortcuts_added += 1;
+                }
+            }
+        }
+
+        if !dry_run {
+            transaction.commit()?;
+        }
+        info!("merge summary: {:?}", summary);
+        Ok(summary)
+    }
+
     /// Creates an in-memory store for testing purposes.
-    #[allow(dead_code)]
-    pub(crate) fn setup_test_store() -> Store {
+    #[doc(hidden)]
+    pub fn setup_test_store() -> Store {
         let store = Store {
             db_conn: Rc::from(Connection::open_in_memory().unwrap()),
             config: Arc::new(Mutex::new(Config::default())),
+            pane_filter: Rc::new(RefCell::new(None)),
+            reveal_sensitive: Rc::new(Cell::new(false)),
+            show_trash: Rc::new(Cell::new(false)),
+            suggestions_only: Rc::new(Cell::new(false)),
+            case_sensitivity: Rc::new(Cell::new(CaseSensitivity::default())),
+            hostname: None,
+            container: None,
+            fuzzy_cache: Rc::new(RefCell::new(HashMap::new())),
+            visit_counts: Rc::new(RefCell::new(None)),
+            suggestion_explanations: Rc::new(RefCell::new(HashMap::new())),
         };
-        store.init_schema();
+        store.init_schema().unwrap();
         store
     }
 }
@@ -1241,6 +4911,16 @@ impl Clone for Store {
         Store {
             db_conn: Rc::clone(&self.db_conn),
             config: self.config.clone(),
+            pane_filter: Rc::clone(&self.pane_filter),
+            reveal_sensitive: Rc::clone(&self.reveal_sensitive),
+            show_trash: Rc::clone(&self.show_trash),
+            suggestions_only: Rc::clone(&self.suggestions_only),
+            case_sensitivity: Rc::clone(&self.case_sensitivity),
+            hostname: self.hostname.clone(),
+            container: self.container.clone(),
+            fuzzy_cache: Rc::clone(&self.fuzzy_cache),
+            visit_counts: Rc::clone(&self.visit_counts),
+            suggestion_explanations: Rc::clone(&self.suggestion_explanations),
         }
     }
 }
@@ -1,8 +1,17 @@
-use log::LevelFilter;
-use log4rs_test_utils::test_logging::init_logging_once_for;
+use tracing::subscriber::DefaultGuard;
 
 use super::*;
 
+/// Writes the logs of the store, down to the traces, to the output of the test
+fn init_logging() -> DefaultGuard {
+    tracing::subscriber::set_default(
+        tracing_subscriber::fmt()
+            .with_env_filter("cdir_core::store=trace")
+            .with_test_writer()
+            .finish(),
+    )
+}
+
 #[test]
 fn test_path_assign_shortcut() {
     // Test 1: No shortcuts available
@@ -12,6 +21,8 @@ fn test_path_assign_shortcut() {
         date: 0,
         shortcut: None,
         smart_path: false,
+        hostname: None,
+        remote: false,
     };
     let shortcuts = [];
     path.assign_shortcut(&shortcuts);
@@ -24,12 +35,15 @@ fn test_path_assign_shortcut() {
         date: 0,
         shortcut: None,
         smart_path: false,
+        hostname: None,
+        remote: false,
     };
     let shortcuts = vec![Shortcut {
         id: 1,
         name: "docs".to_string(),
         path: "/home/user/documents".to_string(),
         description: None,
+        remote: false,
     }];
     path.assign_shortcut(&shortcuts);
     assert!(path.shortcut.is_some());
@@ -43,12 +57,15 @@ fn test_path_assign_shortcut() {
         date: 0,
         shortcut: None,
         smart_path: false,
+        hostname: None,
+        remote: false,
     };
     let shortcuts = vec![Shortcut {
         id: 1,
         name: "docs".to_string(),
         path: "/home/user/documents".to_string(),
         description: None,
+        remote: false,
     }];
     path.assign_shortcut(&shortcuts);
     assert!(path.shortcut.is_none());
@@ -60,6 +77,8 @@ fn test_path_assign_shortcut() {
         date: 0,
         shortcut: None,
         smart_path: false,
+        hostname: None,
+        remote: false,
     };
     let shortcuts = vec![
         Shortcut {
@@ -67,18 +86,21 @@ fn test_path_assign_shortcut() {
             name: "home".to_string(),
             path: "/home".to_string(),
             description: None,
+            remote: false,
         },
         Shortcut {
             id: 2,
             name: "docs".to_string(),
             path: "/home/user/documents".to_string(),
             description: None,
+            remote: false,
         },
         Shortcut {
             id: 3,
             name: "rust".to_string(),
             path: "/home/user/documents/projects/rust".to_string(),
             description: None,
+            remote: false,
         },
     ];
     path.assign_shortcut(&shortcuts);
@@ -99,14 +121,18 @@ fn test_path_assign_shortcut() {
             name: "projects".to_string(),
             path: "/home/user/documents/projects".to_string(),
             description: None,
+            remote: false,
         }),
         smart_path: false,
+        hostname: None,
+        remote: false,
     };
     let shortcuts = vec![Shortcut {
         id: 1,
         name: "home".to_string(),
         path: "/home".to_string(),
         description: None,
+        remote: false,
     }];
     path.assign_shortcut(&shortcuts);
     assert_eq!(path.shortcut.as_ref().unwrap().name, "projects");
@@ -121,14 +147,18 @@ fn test_path_assign_shortcut() {
             name: "home".to_string(),
             path: "/home".to_string(),
             description: None,
+            remote: false,
         }),
         smart_path: false,
+        hostname: None,
+        remote: false,
     };
     let shortcuts = vec![Shortcut {
         id: 2,
         name: "docs".to_string(),
         path: "/home/user/documents".to_string(),
         description: None,
+        remote: false,
     }];
     path.assign_shortcut(&shortcuts);
     assert_eq!(path.shortcut.as_ref().unwrap().name, "docs");
@@ -141,12 +171,15 @@ fn test_path_assign_shortcut() {
         date: 0,
         shortcut: None,
         smart_path: false,
+        hostname: None,
+        remote: false,
     };
     let shortcuts = vec![Shortcut {
         id: 1,
         name: "home".to_string(),
         path: "/home/abc".to_string(),
         description: None,
+        remote: false,
     }];
     path.assign_shortcut(&shortcuts);
     assert!(path.shortcut.is_none());
@@ -230,7 +263,7 @@ fn test_shortcut() {
 
     // A single entry
     store
-        .add_shortcut("shortcut_1", "/1", Some("desc1"))
+        .upsert_shortcut("shortcut_1", "/1", Some("desc1"))
         .unwrap();
     let shortcuts = store.list_shortcuts(0, 10, "", false).unwrap();
     assert_eq!(shortcuts.len(), 1);
@@ -240,7 +273,7 @@ fn test_shortcut() {
 
     // Two entries
     store
-        .add_shortcut("shortcut_2", "/2", Some("desc2"))
+        .upsert_shortcut("shortcut_2", "/2", Some("desc2"))
         .unwrap();
     let shortcuts = store.list_shortcuts(0, 10, "", false).unwrap();
     assert_eq!(shortcuts.len(), 2);
@@ -266,7 +299,9 @@ fn test_shortcut() {
     assert_eq!(shortcuts[0].name, "shortcut_1");
 
     // Test empty description
-    store.add_shortcut("shortcut_nodesc", "/1", None).unwrap();
+    store
+        .upsert_shortcut("shortcut_nodesc", "/1", None)
+        .unwrap();
     let shortcuts = store.list_shortcuts(0, 10, "", false).unwrap();
     assert_eq!(shortcuts.len(), 2);
     assert_eq!(shortcuts[0].name, "shortcut_1");
@@ -278,6 +313,16 @@ fn test_shortcut() {
     assert_eq!(shortcuts[0].name, "shortcut_1");
     assert_eq!(shortcuts[1].name, "shortcut_nodesc");
     assert_eq!(shortcuts[1].description, None);
+
+    // Both shortcuts point at the same path
+    let names: Vec<String> = store
+        .list_shortcuts_by_path("/1")
+        .unwrap()
+        .into_iter()
+        .map(|s| s.name)
+        .collect();
+    assert_eq!(names, vec!["shortcut_1", "shortcut_nodesc"]);
+    assert!(store.list_shortcuts_by_path("/2").unwrap().is_empty());
 }
 
 #[test]
@@ -369,9 +414,9 @@ fn test_list_path_exact_filter_by_shortcut_name() {
 
     // Add shortcuts
     store
-        .add_shortcut("mydocs", "/home/user/documents", None)
+        .upsert_shortcut("mydocs", "/home/user/documents", None)
         .unwrap();
-    store.add_shortcut("logs", "/var/log", None).unwrap();
+    store.upsert_shortcut("logs", "/var/log", None).unwrap();
 
     // Add paths that match shortcut prefixes
     store.add_path("/home/user/documents/file1.txt").unwrap();
@@ -403,10 +448,10 @@ fn test_list_path_exact_filter_by_shortcut_description() {
 
     // Add shortcuts with descriptions
     store
-        .add_shortcut("proj", "/home/user/projects", Some("my projects"))
+        .upsert_shortcut("proj", "/home/user/projects", Some("my projects"))
         .unwrap();
     store
-        .add_shortcut("work", "/home/user/work", Some("work files"))
+        .upsert_shortcut("work", "/home/user/work", Some("work files"))
         .unwrap();
 
     // Add paths
@@ -430,7 +475,9 @@ fn test_list_path_exact_combined_path_and_shortcut_filter() {
     let store = Store::setup_test_store();
 
     // Add a shortcut
-    store.add_shortcut("home", "/etc/hostname", None).unwrap();
+    store
+        .upsert_shortcut("home", "/etc/hostname", None)
+        .unwrap();
 
     // Add paths - some matching the text, some matching the shortcut
     store.add_path("/home/user/documents").unwrap();
@@ -467,7 +514,7 @@ fn test_list_path_exact_with_shortcut_assignment() {
 
     // Add a shortcut
     store
-        .add_shortcut("docs", "/home/user/documents", None)
+        .upsert_shortcut("docs", "/home/user/documents", None)
         .unwrap();
 
     // Add paths that should be assigned the shortcut
@@ -488,10 +535,10 @@ fn test_list_path_exact_multiple_shortcuts_picks_most_specific() {
     let store = Store::setup_test_store();
 
     // Add multiple overlapping shortcuts
-    store.add_shortcut("home", "/home", None).unwrap();
-    store.add_shortcut("user", "/home/user", None).unwrap();
+    store.upsert_shortcut("home", "/home", None).unwrap();
+    store.upsert_shortcut("user", "/home/user", None).unwrap();
     store
-        .add_shortcut("docs", "/home/user/documents", None)
+        .upsert_shortcut("docs", "/home/user/documents", None)
         .unwrap();
 
     // Add a path
@@ -509,7 +556,7 @@ fn test_list_path_exact_no_shortcut_when_no_prefix_match() {
 
     // Add a shortcut
     store
-        .add_shortcut("docs", "/home/user/documents", None)
+        .upsert_shortcut("docs", "/home/user/documents", None)
         .unwrap();
 
     // Add a path that doesn't start with the shortcut path
@@ -560,6 +607,126 @@ fn test_list_path_exact_pagination_with_filter() {
     assert_eq!(paths.len(), 0);
 }
 
+#[test]
+fn test_count_paths() {
+    let store = Store::setup_test_store();
+    assert_eq!(store.count_paths("", false).unwrap(), 0);
+
+    store
+        .upsert_shortcut("myshortcut", "/home/user2", None)
+        .unwrap();
+    store.add_path("/home/user1").unwrap();
+    store.add_path("/home/user2/src").unwrap();
+    store.add_path("/var/log").unwrap();
+
+    assert_eq!(store.count_paths("", false).unwrap(), 3);
+    assert_eq!(store.count_paths("home", false).unwrap(), 2);
+    // the paths below a matching shortcut are counted
+    assert_eq!(store.count_paths("myshortcut", false).unwrap(), 1);
+    assert_eq!(
+        store.count_paths("vrlg", true).unwrap(),
+        store.list_paths(0, 10, "vrlg", true).unwrap().len()
+    );
+    assert_eq!(store.count_paths("unrelated", false).unwrap(), 0);
+}
+
+#[test]
+fn test_count_shortcuts() {
+    let store = Store::setup_test_store();
+    store
+        .upsert_shortcut("work", "/home/user/work", None)
+        .unwrap();
+    store
+        .upsert_shortcut("logs", "/var/log", Some("system logs"))
+        .unwrap();
+
+    assert_eq!(store.count_shortcuts("", false).unwrap(), 2);
+    assert_eq!(store.count_shortcuts("system", false).unwrap(), 1);
+    assert_eq!(store.count_shortcuts("wrk", true).unwrap(), 1);
+    assert_eq!(store.count_shortcuts("unrelated", false).unwrap(), 0);
+}
+
+#[test]
+fn test_glob_search() {
+    let store = Store::setup_test_store();
+    let home = dirs::home_dir().unwrap();
+    let home = home.to_str().unwrap();
+    store.add_path(&format!("{}/work/cdir/api", home)).unwrap();
+    store
+        .add_path(&format!("{}/work/api-server", home))
+        .unwrap();
+    store.add_path(&format!("{}/work/cdir/src", home)).unwrap();
+    store.add_path("/srv/api").unwrap();
+    store.upsert_shortcut("api", "/srv/api", None).unwrap();
+
+    let paths = store.list_paths(0, 10, "~/work/**/api*", false).unwrap();
+    let mut paths: Vec<&str> = paths.iter().map(|p| p.path.as_str()).collect();
+    paths.sort();
+    assert_eq!(
+        paths,
+        vec![
+            format!("{}/work/api-server", home),
+            format!("{}/work/cdir/api", home)
+        ]
+    );
+    assert_eq!(store.count_paths("~/work/**/api*", false).unwrap(), 2);
+
+    // a relative pattern matches from the start of any segment
+    assert_eq!(store.count_paths("ap?*", false).unwrap(), 3);
+    assert_eq!(store.count_paths("ap?-*", false).unwrap(), 1);
+    // without a `*`, `?` and `[` are searched as they are
+    assert_eq!(store.count_paths("ap?", false).unwrap(), 0);
+    assert!(Store::glob_patterns("[draft]").is_empty());
+    assert_eq!(store.count_paths("/srv/*", false).unwrap(), 1);
+
+    assert_eq!(store.count_shortcuts("/srv/a*", false).unwrap(), 1);
+    assert_eq!(
+        store
+            .list_shortcuts(0, 10, "*/work/*", false)
+            .unwrap()
+            .len(),
+        0
+    );
+
+    assert!(Store::glob_patterns("src").is_empty());
+    assert_eq!(Store::glob_patterns("/srv/*"), vec!["/srv/*"]);
+    assert_eq!(Store::glob_patterns("**/a**b"), vec!["*/a*b"]);
+    assert_eq!(Store::glob_patterns("/a/**/b"), vec!["/a/b", "/a/*/b"]);
+    assert_eq!(Store::glob_patterns("/a/**/**/b"), vec!["/a/b", "/a/*/b"]);
+    // the patterns are capped, whatever the number of `**/`
+    let patterns = Store::glob_patterns(&format!("/a{}/b", "/**/x".repeat(20)));
+    assert_eq!(patterns.len(), MAX_GLOB_PATTERNS);
+    assert!(patterns.iter().all(|p| !p.contains("**")));
+}
+
+#[test]
+fn test_case_sensitivity() {
+    let store = Store::setup_test_store();
+    store.add_path("/home/user/Projects").unwrap();
+    store.add_path("/home/user/projects").unwrap();
+    store
+        .upsert_shortcut("Work", "/home/user/work", None)
+        .unwrap();
+
+    assert_eq!(store.count_paths("proj", false).unwrap(), 2);
+    assert_eq!(store.count_paths("Proj", false).unwrap(), 2);
+
+    store.set_case_sensitivity(CaseSensitivity::Smart);
+    assert_eq!(store.count_paths("proj", false).unwrap(), 2);
+    assert_eq!(store.count_paths("Proj", false).unwrap(), 1);
+    assert_eq!(store.count_paths("Proj", true).unwrap(), 1);
+    assert_eq!(store.count_paths("*/Proj*", false).unwrap(), 1);
+    assert_eq!(store.count_paths("*/proj*", false).unwrap(), 2);
+    assert_eq!(store.count_shortcuts("work", false).unwrap(), 1);
+    assert_eq!(store.count_shortcuts("WORK", false).unwrap(), 0);
+
+    store.set_case_sensitivity(CaseSensitivity::Respect);
+    assert_eq!(store.count_paths("proj", false).unwrap(), 1);
+    assert_eq!(store.count_paths("proj", true).unwrap(), 1);
+    assert_eq!(store.list_shortcuts(0, 10, "work", false).unwrap().len(), 1);
+    assert_eq!(store.list_shortcuts(0, 10, "Wor", true).unwrap().len(), 1);
+}
+
 #[test]
 fn test_list_path_exact_special_characters_in_path() {
     let store = Store::setup_test_store();
@@ -585,7 +752,7 @@ fn test_list_path_exact_filter_with_shortcut_name_and_description() {
 
     // Add shortcut with both name and description matching different text
     store
-        .add_shortcut(
+        .upsert_shortcut(
             "myshortcut",
             "/home/user/mydir",
             Some("this is my special directory"),
@@ -717,6 +884,103 @@ fn test_list_path_fuzzy_with_pagination() {
     assert!(!paths.is_empty());
 }
 
+#[test]
+fn test_fuzzy_cache() {
+    let store = Store::setup_test_store();
+    for i in 1..=6 {
+        store.add_path(&format!("/home/user/folder{}", i)).unwrap();
+    }
+
+    let all = store.list_paths(0, 10, "home", true).unwrap();
+    assert_eq!(all.len(), 6);
+    let ids = store.fuzzy_cache.borrow()["paths"].ids.clone();
+
+    // the pages reuse the scored ids
+    let mut pages = store.list_paths(0, 4, "home", true).unwrap();
+    pages.extend(store.list_paths(4, 4, "home", true).unwrap());
+    assert!(Rc::ptr_eq(&ids, &store.fuzzy_cache.borrow()["paths"].ids));
+    assert_eq!(
+        pages.iter().map(|p| p.id).collect::<Vec<_>>(),
+        all.iter().map(|p| p.id).collect::<Vec<_>>()
+    );
+
+    // a change of the data scores the paths again
+    store.add_path("/home/user/folder7").unwrap();
+    assert_eq!(store.list_paths(0, 10, "home", true).unwrap().len(), 7);
+    assert_eq!(store.count_paths("home", true).unwrap(), 7);
+
+    store.clear_fuzzy_cache();
+    assert!(store.fuzzy_cache.borrow().is_empty());
+}
+
+#[test]
+fn test_fuzzy_config_bonuses() {
+    let store = Store::setup_test_store();
+    store.add_path_with_time("/home/proj", 100).unwrap();
+    store.add_path_with_time("/home/p/r/o/j", 200).unwrap();
+    let paths = store.list_paths(0, 10, "proj", true).unwrap();
+    assert_eq!(paths[0].path, "/home/proj");
+
+    // the most recent path gets the whole recency boost
+    store.config.lock().unwrap().fuzzy.recency_boost = 1000;
+    let paths = store.list_paths(0, 10, "proj", true).unwrap();
+    assert_eq!(paths[0].path, "/home/p/r/o/j");
+
+    store.upsert_shortcut("zzz", "/home/docs", None).unwrap();
+    store.upsert_shortcut("dxoxcxs", "/srv", None).unwrap();
+    let shortcuts = store.list_shortcuts(0, 10, "docs", true).unwrap();
+    assert_eq!(shortcuts[0].name, "zzz");
+
+    // the shortcut whose name matches is ranked first
+    store.config.lock().unwrap().fuzzy.shortcut_name_bonus = 1000;
+    let shortcuts = store.list_shortcuts(0, 10, "docs", true).unwrap();
+    assert_eq!(shortcuts[0].name, "dxoxcxs");
+}
+
+#[test]
+fn test_fuzzy_recency_and_frequency() {
+    let store = Store::setup_test_store();
+    {
+        let mut config = store.config.lock().unwrap();
+        config.fuzzy.recency_boost = 0;
+        config.fuzzy.frequency_boost = 0;
+    }
+    store.add_path_with_time("/home/src/a", 100).unwrap();
+    store.add_path_with_time("/home/src/b", 300).unwrap();
+    store.add_path_with_time("/home/src/c", 200).unwrap();
+    store.add_path_with_time("/home/src/c", 250).unwrap();
+    store.add_path_with_time("/home/src/a", 260).unwrap();
+    store.add_path_with_time("/home/src/a", 270).unwrap();
+
+    // equal matches are listed from the most recent
+    let list = |store: &Store| {
+        store
+            .list_paths(0, 10, "src", true)
+            .unwrap()
+            .into_iter()
+            .map(|p| p.path)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        list(&store),
+        vec!["/home/src/b", "/home/src/a", "/home/src/c"]
+    );
+
+    // the most visited path wins with a frequency boost
+    store.config.lock().unwrap().fuzzy.frequency_boost = 100;
+    assert_eq!(
+        list(&store),
+        vec!["/home/src/a", "/home/src/c", "/home/src/b"]
+    );
+
+    // and the most recent one with a bigger recency boost
+    store.config.lock().unwrap().fuzzy.recency_boost = 1000;
+    assert_eq!(
+        list(&store),
+        vec!["/home/src/b", "/home/src/a", "/home/src/c"]
+    );
+}
+
 #[test]
 fn test_list_path_fuzzy_no_match() {
     let store = Store::setup_test_store();
@@ -736,7 +1000,7 @@ fn test_list_path_fuzzy_with_shortcut_name_scoring() {
 
     // Add a shortcut with specific name
     store
-        .add_shortcut("mydocs", "/home/user/documents", None)
+        .upsert_shortcut("mydocs", "/home/user/documents", None)
         .unwrap();
 
     // Add paths under the shortcut
@@ -761,7 +1025,7 @@ fn test_list_path_fuzzy_with_shortcut_description_scoring() {
 
     // Add shortcut with description
     store
-        .add_shortcut("proj", "/home/user/projects", Some("my important projects"))
+        .upsert_shortcut("proj", "/home/user/projects", Some("my important projects"))
         .unwrap();
 
     // Add a path
@@ -895,11 +1159,13 @@ fn test_list_path_fuzzy_multiple_shortcuts_scoring() {
     let store = Store::setup_test_store();
 
     // Add multiple shortcuts
-    store.add_shortcut("home", "/home", None).unwrap();
+    store.upsert_shortcut("home", "/home", None).unwrap();
     store
-        .add_shortcut("xyz", "/home/user/documents", None)
+        .upsert_shortcut("xyz", "/home/user/documents", None)
+        .unwrap();
+    store
+        .upsert_shortcut("work", "/home/user/work", None)
         .unwrap();
-    store.add_shortcut("work", "/home/user/work", None).unwrap();
 
     // Add paths
     store.add_path("/home/user/documents/file1").unwrap();
@@ -971,7 +1237,7 @@ fn test_path_search_include_shortcuts_enabled() {
 
     // Add a shortcut with specific name
     store
-        .add_shortcut("myshortcut", "/home/user/mydir", None)
+        .upsert_shortcut("myshortcut", "/home/user/mydir", None)
         .unwrap();
 
     // Add paths - one matching the shortcut, one not
@@ -996,12 +1262,22 @@ fn test_path_search_include_shortcuts_disabled() {
     let store = Store {
         db_conn: Rc::from(Connection::open_in_memory().unwrap()),
         config: Arc::new(Mutex::new(config)),
+        pane_filter: Rc::new(RefCell::new(None)),
+        reveal_sensitive: Rc::new(Cell::new(false)),
+        show_trash: Rc::new(Cell::new(false)),
+        suggestions_only: Rc::new(Cell::new(false)),
+        case_sensitivity: Rc::new(Cell::new(CaseSensitivity::default())),
+        hostname: None,
+        container: None,
+        fuzzy_cache: Rc::new(RefCell::new(HashMap::new())),
+        visit_counts: Rc::new(RefCell::new(None)),
+        suggestion_explanations: Rc::new(RefCell::new(HashMap::new())),
     };
-    store.init_schema();
+    store.init_schema().unwrap();
 
     // Add a shortcut with specific name
     store
-        .add_shortcut("myshortcut", "/home/user/mydir", None)
+        .upsert_shortcut("myshortcut", "/home/user/mydir", None)
         .unwrap();
 
     // Add paths - one matching the shortcut, one not
@@ -1020,7 +1296,7 @@ fn test_path_search_include_shortcuts_filter_by_description_enabled() {
 
     // Add a shortcut with a description
     store
-        .add_shortcut("proj", "/home/user/projects", Some("my important project"))
+        .upsert_shortcut("proj", "/home/user/projects", Some("my important project"))
         .unwrap();
 
     // Add paths
@@ -1045,12 +1321,22 @@ fn test_path_search_include_shortcuts_filter_by_description_disabled() {
     let store = Store {
         db_conn: Rc::from(Connection::open_in_memory().unwrap()),
         config: Arc::new(Mutex::new(config)),
+        pane_filter: Rc::new(RefCell::new(None)),
+        reveal_sensitive: Rc::new(Cell::new(false)),
+        show_trash: Rc::new(Cell::new(false)),
+        suggestions_only: Rc::new(Cell::new(false)),
+        case_sensitivity: Rc::new(Cell::new(CaseSensitivity::default())),
+        hostname: None,
+        container: None,
+        fuzzy_cache: Rc::new(RefCell::new(HashMap::new())),
+        visit_counts: Rc::new(RefCell::new(None)),
+        suggestion_explanations: Rc::new(RefCell::new(HashMap::new())),
     };
-    store.init_schema();
+    store.init_schema().unwrap();
 
     // Add a shortcut with a description
     store
-        .add_shortcut("proj", "/home/user/projects", Some("my important project"))
+        .upsert_shortcut("proj", "/home/user/projects", Some("my important project"))
         .unwrap();
 
     // Add paths
@@ -1074,12 +1360,22 @@ fn test_path_search_include_shortcuts_direct_path_match_always_works() {
     let store = Store {
         db_conn: Rc::from(Connection::open_in_memory().unwrap()),
         config: Arc::new(Mutex::new(config)),
+        pane_filter: Rc::new(RefCell::new(None)),
+        reveal_sensitive: Rc::new(Cell::new(false)),
+        show_trash: Rc::new(Cell::new(false)),
+        suggestions_only: Rc::new(Cell::new(false)),
+        case_sensitivity: Rc::new(Cell::new(CaseSensitivity::default())),
+        hostname: None,
+        container: None,
+        fuzzy_cache: Rc::new(RefCell::new(HashMap::new())),
+        visit_counts: Rc::new(RefCell::new(None)),
+        suggestion_explanations: Rc::new(RefCell::new(HashMap::new())),
     };
-    store.init_schema();
+    store.init_schema().unwrap();
 
     // Add a shortcut
     store
-        .add_shortcut("proj", "/home/user/projects", None)
+        .upsert_shortcut("proj", "/home/user/projects", None)
         .unwrap();
 
     // Add paths
@@ -1098,7 +1394,7 @@ fn test_list_path_fuzzy_with_shortcut_scoring_enabled() {
 
     // Add a shortcut with a unique name
     store
-        .add_shortcut("uniqueshortcut", "/home/user/mydir", None)
+        .upsert_shortcut("uniqueshortcut", "/home/user/mydir", None)
         .unwrap();
 
     // Add paths - one under the shortcut, one elsewhere
@@ -1123,12 +1419,22 @@ fn test_list_path_fuzzy_with_shortcut_scoring_disabled() {
     let store = Store {
         db_conn: Rc::from(Connection::open_in_memory().unwrap()),
         config: Arc::new(Mutex::new(config)),
+        pane_filter: Rc::new(RefCell::new(None)),
+        reveal_sensitive: Rc::new(Cell::new(false)),
+        show_trash: Rc::new(Cell::new(false)),
+        suggestions_only: Rc::new(Cell::new(false)),
+        case_sensitivity: Rc::new(Cell::new(CaseSensitivity::default())),
+        hostname: None,
+        container: None,
+        fuzzy_cache: Rc::new(RefCell::new(HashMap::new())),
+        visit_counts: Rc::new(RefCell::new(None)),
+        suggestion_explanations: Rc::new(RefCell::new(HashMap::new())),
     };
-    store.init_schema();
+    store.init_schema().unwrap();
 
     // Add a shortcut with a unique name
     store
-        .add_shortcut("uniqueshortcut", "/home/user/mydir", None)
+        .upsert_shortcut("uniqueshortcut", "/home/user/mydir", None)
         .unwrap();
 
     // Add paths - one under the shortcut, one elsewhere
@@ -1411,7 +1717,7 @@ fn test_list_path_history_with_shortcuts() {
 
     // Add shortcuts
     store
-        .add_shortcut("mydocs", "/home/user/documents", None)
+        .upsert_shortcut("mydocs", "/home/user/documents", None)
         .unwrap();
 
     // Add paths
@@ -1497,11 +1803,7 @@ fn test_smart_ranker_one_entry() {
 
 #[test]
 fn test_smart_ranker_two_entries() {
-    init_logging_once_for(
-        vec!["cdir::store"],
-        LevelFilter::Trace,
-        "{h({d(%H:%M:%S%.3f)} {({l}):5.5} {f}:{L} — {m}{n})}",
-    );
+    let _logging = init_logging();
 
     // 0 window
     debug!("0 window");
@@ -1517,7 +1819,7 @@ fn test_smart_ranker_two_entries() {
     sm.add_path(0, "/b".to_string(), 1);
     let rows = sm.collect_rows();
     assert_eq!(1, rows.len());
-    assert_eq!("/a", rows[0]);
+    assert_eq!("/a", rows[0].path);
 
     // 1 window, reverse order
     debug!("1 window reverse order");
@@ -1526,7 +1828,7 @@ fn test_smart_ranker_two_entries() {
     sm.add_path(0, "/a".to_string(), 0);
     let rows = sm.collect_rows();
     assert_eq!(1, rows.len());
-    assert_eq!("/a", rows[0]);
+    assert_eq!("/a", rows[0].path);
 
     // 2 window
     debug!("2 window");
@@ -1535,8 +1837,8 @@ fn test_smart_ranker_two_entries() {
     sm.add_path(0, "/b".to_string(), 1);
     let rows = sm.collect_rows();
     assert_eq!(2, rows.len());
-    assert_eq!("/a", rows[0]);
-    assert_eq!("/b", rows[1]);
+    assert_eq!("/a", rows[0].path);
+    assert_eq!("/b", rows[1].path);
 
     // 2 window, reverse order
     debug!("2 window reverse order");
@@ -1545,8 +1847,8 @@ fn test_smart_ranker_two_entries() {
     sm.add_path(0, "/a".to_string(), 0);
     let rows = sm.collect_rows();
     assert_eq!(2, rows.len());
-    assert_eq!("/a", rows[0]);
-    assert_eq!("/b", rows[1]);
+    assert_eq!("/a", rows[0].path);
+    assert_eq!("/b", rows[1].path);
 
     // 2 window, 2 same set
     debug!("2 window, same set twice");
@@ -1558,8 +1860,8 @@ fn test_smart_ranker_two_entries() {
     let rows = sm.collect_rows();
     debug!("Rows: {:?}", rows);
     assert_eq!(2, rows.len());
-    assert_eq!("/a", rows[0]);
-    assert_eq!("/b", rows[1]);
+    assert_eq!("/a", rows[0].path);
+    assert_eq!("/b", rows[1].path);
 
     // 2 window, 2 sets
     debug!("2 window, same set twice");
@@ -1570,17 +1872,13 @@ fn test_smart_ranker_two_entries() {
     let rows = sm.collect_rows();
     debug!("Rows: {:?}", rows);
     assert_eq!(2, rows.len());
-    assert_eq!("/b", rows[0]);
-    assert_eq!("/a", rows[1]);
+    assert_eq!("/b", rows[0].path);
+    assert_eq!("/a", rows[1].path);
 }
 
 #[test]
 fn test_smart_ranker_ranking_entries() {
-    init_logging_once_for(
-        vec!["cdir::store"],
-        LevelFilter::Trace,
-        "{h({d(%H:%M:%S%.3f)} {({l}):5.5} {f}:{L} — {m}{n})}",
-    );
+    let _logging = init_logging();
 
     let mut sm = SmartRanker::new(1, 5);
     sm.add_path(0, "/a".to_string(), 0);
@@ -1592,18 +1890,78 @@ fn test_smart_ranker_ranking_entries() {
     let rows = sm.collect_rows();
     debug!("Rows: {:?}", rows);
     assert_eq!(3, rows.len());
-    assert_eq!("/a", rows[0]);
-    assert_eq!("/c", rows[1]);
-    assert_eq!("/b", rows[2]);
+    assert_eq!("/a", rows[0].path);
+    assert_eq!("/c", rows[1].path);
+    assert_eq!("/b", rows[2].path);
+}
+
+#[test]
+fn test_smart_ranker_breakdown() {
+    let mut sm = SmartRanker::new(3, 2);
+    sm.add_path(0, "/a".to_string(), 0);
+    sm.add_path(2, "/a".to_string(), 1);
+    sm.add_path(2, "/a".to_string(), 0);
+    sm.add_path(1, "/b".to_string(), 1);
+
+    // the default weights: 10 for the adjacency, 1 for the recency and the frequency
+    let rows = sm.collect_rows();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].path, "/a");
+    assert_eq!(rows[0].depths, vec![0, 2]);
+    let expected = (10.0 + 1.0) + (5.0 + 1.0 / 3.0) + (10.0 + 1.0 / 3.0) + 2.0 / 3.0;
+    assert!((rows[0].score - expected).abs() < 1e-9);
+    assert_eq!(rows[1].path, "/b");
+    assert!((rows[1].score - (5.0 + 2.0 / 3.0 + 1.0 / 3.0)).abs() < 1e-9);
+}
+
+#[test]
+fn test_smart_ranker_scoring() {
+    // without recency nor frequency, only the adjacency counts
+    let adjacency_only = SmartScoring {
+        half_life_days: 0,
+        adjacency_weight: 1,
+        recency_weight: 0,
+        frequency_weight: 0,
+    };
+    let mut sm = SmartRanker::new(2, 3).with_scoring(adjacency_only, vec![]);
+    sm.add_path(0, "/a".to_string(), 1);
+    sm.add_path(1, "/b".to_string(), 0);
+    let rows = sm.collect_rows();
+    assert_eq!(rows[0].path, "/b");
+    assert_eq!(rows[0].score, 1.0);
+    assert_eq!(rows[1].score, 0.5);
+
+    // an old visit of the current directory counts less
+    let decayed = SmartScoring {
+        half_life_days: 10,
+        ..adjacency_only
+    };
+    let mut sm = SmartRanker::new(2, 3).with_scoring(decayed, vec![0.0, 20.0]);
+    sm.add_path(0, "/a".to_string(), 1);
+    sm.add_path(1, "/b".to_string(), 0);
+    let rows = sm.collect_rows();
+    assert_eq!(rows[0].path, "/a");
+    assert_eq!(rows[1].score, 0.25);
+
+    // the frequency favors the paths following many visits
+    let frequency_only = SmartScoring {
+        half_life_days: 0,
+        adjacency_weight: 0,
+        recency_weight: 0,
+        frequency_weight: 1,
+    };
+    let mut sm = SmartRanker::new(2, 3).with_scoring(frequency_only, vec![]);
+    sm.add_path(0, "/a".to_string(), 0);
+    sm.add_path(0, "/b".to_string(), 2);
+    sm.add_path(1, "/b".to_string(), 2);
+    let rows = sm.collect_rows();
+    assert_eq!(rows[0].path, "/b");
+    assert_eq!(rows[0].score, 1.0);
 }
 
 #[test]
 fn test_list_path_history_smart_suggestions_basic() {
-    init_logging_once_for(
-        vec!["cdir::store"],
-        LevelFilter::Trace,
-        "{h({d(%H:%M:%S%.3f)} {({l}):5.5} {f}:{L} — {m}{n})}",
-    );
+    let _logging = init_logging();
     let store = Store::setup_test_store();
 
     // Add some paths - including the same path multiple times
@@ -1624,6 +1982,19 @@ fn test_list_path_history_smart_suggestions_basic() {
     assert_eq!("/var/log/app1.1", suggestions[0].path);
     assert_eq!("/var/log/app1.2", suggestions[1].path);
     assert_eq!("/var/log/app1.3", suggestions[2].path);
+
+    // the ranking of each suggestion is explained
+    let explanation = store.suggestion_explanation("/var/log/app1.1").unwrap();
+    assert_eq!(explanation.followed, 1);
+    assert_eq!(explanation.sequences.len(), 1);
+    assert!(
+        explanation.score
+            > store
+                .suggestion_explanation("/var/log/app1.3")
+                .unwrap()
+                .score
+    );
+    assert_eq!(store.suggestion_explanation("/home/user/documents"), None);
 }
 
 #[test]
@@ -1711,11 +2082,7 @@ fn test_list_path_history_smart_suggestions_with_count_limit() {
 
 #[test]
 fn test_list_path_history_smart_suggestions_with_depth() {
-    init_logging_once_for(
-        vec!["cdir::store"],
-        LevelFilter::Trace,
-        "{h({d(%H:%M:%S%.3f)} {({l}):5.5} {f}:{L} — {m}{n})}",
-    );
+    let _logging = init_logging();
     let store = Store::setup_test_store();
 
     // First sequence: start -> a -> b
@@ -1810,9 +2177,9 @@ fn test_list_path_history_smart_suggestions_with_shortcuts() {
 
     // Add shortcuts
     store
-        .add_shortcut("docs", "/home/user/documents", None)
+        .upsert_shortcut("docs", "/home/user/documents", None)
         .unwrap();
-    store.add_shortcut("logs", "/var/log", None).unwrap();
+    store.upsert_shortcut("logs", "/var/log", None).unwrap();
 
     // Add path sequence
     store.add_path("/start").unwrap();
@@ -1888,11 +2255,7 @@ fn test_list_path_history_smart_suggestions_excludes_match_path() {
 
 #[test]
 fn test_list_path_history_smart_suggestions_ranking() {
-    init_logging_once_for(
-        vec!["cdir::store"],
-        LevelFilter::Trace,
-        "{h({d(%H:%M:%S%.3f)} {({l}):5.5} {f}:{L} — {m}{n})}",
-    );
+    let _logging = init_logging();
     let store = Store::setup_test_store();
 
     // First sequence: start -> a -> b -> c
@@ -1922,11 +2285,7 @@ fn test_list_path_history_smart_suggestions_ranking() {
 
 #[test]
 fn test_list_path_history_smart_suggestions_recent_sequence_priority() {
-    init_logging_once_for(
-        vec!["cdir::store"],
-        LevelFilter::Trace,
-        "{h({d(%H:%M:%S%.3f)} {({l}):5.5} {f}:{L} — {m}{n})}",
-    );
+    let _logging = init_logging();
 
     let store = Store::setup_test_store();
 
@@ -1977,11 +2336,7 @@ fn test_list_path_history_smart_suggestions_multiple_occurrences_same_path() {
 
 #[test]
 fn test_list_path_history_smart_suggestions_complex_pattern() {
-    init_logging_once_for(
-        vec!["cdir::store"],
-        LevelFilter::Trace,
-        "{h({d(%H:%M:%S%.3f)} {({l}):5.5} {f}:{L} — {m}{n})}",
-    );
+    let _logging = init_logging();
     let store = Store::setup_test_store();
 
     // Simulate realistic usage: project dir -> edit -> test -> commit
@@ -2013,3 +2368,1748 @@ fn test_list_path_history_smart_suggestions_complex_pattern() {
     // tests appears twice, should be ranked high
     assert!(suggestions.iter().any(|p| p.path == "/project/tests"));
 }
+
+#[test]
+fn test_list_paths_sorted_by_path() {
+    let store = Store::setup_test_store();
+    store.add_path_with_time("/b", 10).unwrap();
+    store.add_path_with_time("/c", 20).unwrap();
+    store.add_path_with_time("/a", 30).unwrap();
+
+    let paths = store
+        .list_paths_sorted(
+            0,
+            10,
+            "",
+            false,
+            SortOrder::Ascending(SortColumn::Path),
+            true,
+        )
+        .unwrap();
+    let paths: Vec<&str> = paths.iter().map(|p| p.path.as_str()).collect();
+    assert_eq!(paths, vec!["/a", "/b", "/c"]);
+
+    let paths = store
+        .list_paths_sorted(
+            0,
+            10,
+            "",
+            false,
+            SortOrder::Descending(SortColumn::Path),
+            true,
+        )
+        .unwrap();
+    let paths: Vec<&str> = paths.iter().map(|p| p.path.as_str()).collect();
+    assert_eq!(paths, vec!["/c", "/b", "/a"]);
+}
+
+#[test]
+fn test_list_paths_sorted_by_date_ascending_with_filter() {
+    let store = Store::setup_test_store();
+    store.add_path_with_time("/x/new", 30).unwrap();
+    store.add_path_with_time("/x/old", 10).unwrap();
+    store.add_path_with_time("/y/other", 20).unwrap();
+
+    let paths = store
+        .list_paths_sorted(
+            0,
+            10,
+            "x",
+            false,
+            SortOrder::Ascending(SortColumn::Date),
+            true,
+        )
+        .unwrap();
+    let paths: Vec<&str> = paths.iter().map(|p| p.path.as_str()).collect();
+    assert_eq!(paths, vec!["/x/old", "/x/new"]);
+}
+
+#[test]
+fn test_list_paths_sorted_fuzzy() {
+    let store = Store::setup_test_store();
+    store.add_path_with_time("/home/docs", 10).unwrap();
+    store.add_path_with_time("/home/documents", 20).unwrap();
+    store.add_path_with_time("/var/log", 30).unwrap();
+
+    let paths = store
+        .list_paths_sorted(
+            0,
+            10,
+            "doc",
+            true,
+            SortOrder::Descending(SortColumn::Path),
+            true,
+        )
+        .unwrap();
+    let paths: Vec<&str> = paths.iter().map(|p| p.path.as_str()).collect();
+    assert_eq!(paths, vec!["/home/documents", "/home/docs"]);
+}
+
+#[test]
+fn test_list_shortcuts_sorted() {
+    let store = Store::setup_test_store();
+    store.upsert_shortcut("a", "/z", None).unwrap();
+    store.upsert_shortcut("b", "/y", None).unwrap();
+    store.upsert_shortcut("c", "/x", None).unwrap();
+
+    let shortcuts = store
+        .list_shortcuts_sorted(0, 10, "", false, SortOrder::Descending(SortColumn::Name))
+        .unwrap();
+    let names: Vec<&str> = shortcuts.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, vec!["c", "b", "a"]);
+
+    let shortcuts = store
+        .list_shortcuts_sorted(0, 10, "", false, SortOrder::Ascending(SortColumn::Path))
+        .unwrap();
+    let names: Vec<&str> = shortcuts.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, vec!["c", "b", "a"]);
+
+    let shortcuts = store
+        .list_shortcuts_sorted(0, 10, "/", true, SortOrder::Ascending(SortColumn::Name))
+        .unwrap();
+    let names: Vec<&str> = shortcuts.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, vec!["a", "b", "c"]);
+}
+
+#[test]
+fn test_search_history() {
+    let store = Store::setup_test_store();
+    assert!(store.list_searches("path").unwrap().is_empty());
+
+    store.add_search("path", "src", false).unwrap();
+    store.add_search("path", "doc", true).unwrap();
+    store.add_search("path", " ", false).unwrap();
+    store.add_search("shortcut", "prj", false).unwrap();
+    // searched again, the search becomes the most recent one
+    store.add_search("path", "src", true).unwrap();
+
+    assert_eq!(
+        store.list_searches("path").unwrap(),
+        vec![Filter::new("src", true), Filter::new("doc", true)]
+    );
+    assert_eq!(
+        store.list_searches("shortcut").unwrap(),
+        vec![Filter::new("prj", false)]
+    );
+
+    for i in 0..SEARCH_HISTORY_SIZE {
+        store.add_search("path", &format!("s{}", i), false).unwrap();
+    }
+    let searches = store.list_searches("path").unwrap();
+    assert_eq!(searches.len(), SEARCH_HISTORY_SIZE);
+    assert_eq!(searches[0].text, format!("s{}", SEARCH_HISTORY_SIZE - 1));
+}
+
+#[test]
+fn test_save_and_load_gui_session() {
+    let store = Store::setup_test_store();
+    assert_eq!(store.load_gui_session().unwrap(), None);
+
+    let session = GuiSession {
+        search: String::from("work"),
+        fuzzy_match: true,
+        view: String::from("shortcut"),
+        selected_row: 3,
+    };
+    store.save_gui_session(&session).unwrap();
+    assert_eq!(store.load_gui_session().unwrap(), Some(session));
+
+    let session = GuiSession {
+        search: String::new(),
+        fuzzy_match: false,
+        view: String::from("path"),
+        selected_row: 0,
+    };
+    store.save_gui_session(&session).unwrap();
+    assert_eq!(store.load_gui_session().unwrap(), Some(session));
+}
+
+#[test]
+fn test_time_slot_weight() {
+    // monday morning
+    let slot = 1;
+    assert_eq!(time_slot_weight(slot, slot), 8);
+    // tuesday morning
+    assert_eq!(time_slot_weight(slot + 4, slot), 4);
+    // monday evening
+    assert_eq!(time_slot_weight(slot + 2, slot), 2);
+    // sunday evening
+    assert_eq!(time_slot_weight(6 * 4 + 3, slot), 1);
+}
+
+#[test]
+fn test_list_path_history_smart_suggestions_transitions() {
+    let store = Store::setup_test_store();
+    let morning: u64 = 1_700_000_000;
+    let evening = morning + 12 * 3600;
+
+    for i in 0..3 {
+        store
+            .add_path_with_time("/home/user", morning + i * 10)
+            .unwrap();
+        store
+            .add_path_with_time("/work/api", morning + i * 10 + 1)
+            .unwrap();
+    }
+    for i in 0..2 {
+        store
+            .add_path_with_time("/home/user", evening + i * 10)
+            .unwrap();
+        store
+            .add_path_with_time("/music", evening + i * 10 + 1)
+            .unwrap();
+    }
+
+    let count: i64 = store
+        .db_conn
+        .query_row(
+            "SELECT SUM(count) FROM path_transitions WHERE from_path = '/home/user'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(count, 5);
+
+    let suggest = |now: u64| -> Vec<String> {
+        store
+            .list_path_history_smart_suggestions_transitions("/home/user", 5, &[], now as i64)
+            .unwrap()
+            .into_iter()
+            .map(|(p, _)| {
+                assert!(p.smart_path);
+                p.path
+            })
+            .collect()
+    };
+    assert_eq!(suggest(morning), vec!["/work/api", "/music"]);
+    assert_eq!(suggest(evening), vec!["/music", "/work/api"]);
+}
+
+#[test]
+fn test_shortcut_hints() {
+    let store = Store::setup_test_store();
+    store.upsert_shortcut("py", "/home/py", None).unwrap();
+    store.upsert_shortcut("py_copy", "/home/py", None).unwrap();
+    assert_eq!(
+        store.find_shortcut_hints("py").unwrap(),
+        Some(ShortcutHints::default())
+    );
+    assert_eq!(store.find_shortcut_hints("unknown").unwrap(), None);
+    assert_eq!(store.find_shortcut_hints_by_path("/home/py").unwrap(), None);
+
+    let hints = ShortcutHints {
+        env: vec![
+            (String::from("VIRTUAL_ENV"), String::from("/home/py/.venv")),
+            (String::from("OPTS"), String::from("a=b")),
+        ],
+        post_cd: Some(String::from("source .venv/bin/activate")),
+    };
+    store.set_shortcut_hints("py_copy", &hints).unwrap();
+    assert_eq!(
+        store.find_shortcut_hints("py_copy").unwrap(),
+        Some(hints.clone())
+    );
+    assert_eq!(
+        store.find_shortcut_hints_by_path("/home/py").unwrap(),
+        Some((String::from("py_copy"), hints))
+    );
+
+    // the hints are kept when the shortcut is edited
+    let shortcut = store.find_shortcut("py_copy").unwrap();
+    store
+        .update_shortcut(shortcut.id, "py2", "/home/py", None)
+        .unwrap();
+    assert!(
+        !store
+            .find_shortcut_hints("py2")
+            .unwrap()
+            .unwrap()
+            .is_empty()
+    );
+
+    store
+        .set_shortcut_hints("py2", &ShortcutHints::default())
+        .unwrap();
+    assert_eq!(store.find_shortcut_hints_by_path("/home/py").unwrap(), None);
+}
+
+#[test]
+fn test_pane_filter() {
+    let store = Store::setup_test_store();
+    store.add_path_from_pane("/pane1/a", Some("%1")).unwrap();
+    store.add_path_from_pane("/pane2/b", Some("%2")).unwrap();
+    store.add_path_from_pane("/both", Some("%2")).unwrap();
+    store.add_path_from_pane("/both", Some("%1")).unwrap();
+    store.add_path("/no_pane").unwrap();
+
+    let list = |text: &str, fuzzy: bool| -> Vec<String> {
+        let mut paths: Vec<String> = store
+            .list_paths(0, 10, text, fuzzy)
+            .unwrap()
+            .into_iter()
+            .map(|p| p.path)
+            .collect();
+        paths.sort();
+        paths
+    };
+    assert_eq!(list("", false).len(), 4);
+
+    store.set_pane_filter(Some(String::from("%1")));
+    assert_eq!(store.clone().pane_filter(), Some(String::from("%1")));
+    assert_eq!(list("", false), vec!["/both", "/pane1/a"]);
+    assert_eq!(list("pane", false), vec!["/pane1/a"]);
+    assert_eq!(list("bth", true), vec!["/both"]);
+
+    store.set_pane_filter(Some(String::from("%2")));
+    assert_eq!(list("", false), vec!["/both", "/pane2/b"]);
+    assert_eq!(list("pane", true), vec!["/pane2/b"]);
+
+    store.set_pane_filter(None);
+    assert_eq!(list("", false).len(), 4);
+}
+
+#[test]
+fn test_split_host_filter() {
+    assert_eq!(
+        Store::split_host_filter("src host:laptop"),
+        (String::from("src"), Some(String::from("laptop")))
+    );
+    assert_eq!(
+        Store::split_host_filter("host:laptop"),
+        (String::new(), Some(String::from("laptop")))
+    );
+    assert_eq!(
+        Store::split_host_filter("a  b"),
+        (String::from("a  b"), None)
+    );
+    assert_eq!(
+        Store::split_host_filter("host: x"),
+        (String::from("host: x"), None)
+    );
+}
+
+#[test]
+fn test_host_filter() {
+    let config = Config {
+        current_host_only: true,
+        ..Default::default()
+    };
+    let mut store = Store::setup_test_store();
+    store.config = Arc::new(Mutex::new(config));
+
+    store.add_path_with_time("/unknown", 1).unwrap();
+    store.hostname = Some(String::from("laptop"));
+    store.add_path("/laptop/a").unwrap();
+    store.hostname = Some(String::from("server"));
+    store.add_path("/server/b").unwrap();
+    store.add_path("/both").unwrap();
+    store.hostname = Some(String::from("laptop"));
+    store.add_path("/both").unwrap();
+
+    let list = |text: &str, fuzzy: bool| -> Vec<(String, Option<String>)> {
+        let mut paths: Vec<(String, Option<String>)> = store
+            .list_paths(0, 10, text, fuzzy)
+            .unwrap()
+            .into_iter()
+            .map(|p| (p.path, p.hostname))
+            .collect();
+        paths.sort();
+        paths
+    };
+    let laptop = Some(String::from("laptop"));
+    assert_eq!(
+        list("", false),
+        vec![
+            (String::from("/both"), laptop.clone()),
+            (String::from("/laptop/a"), laptop.clone()),
+            (String::from("/unknown"), None),
+        ]
+    );
+    assert_eq!(
+        list("host:server", false),
+        vec![
+            (String::from("/both"), laptop.clone()),
+            (String::from("/server/b"), Some(String::from("server"))),
+        ]
+    );
+    assert_eq!(
+        list("srv host:server", true),
+        vec![(String::from("/server/b"), Some(String::from("server")))]
+    );
+
+    store.config.lock().unwrap().current_host_only = false;
+    assert_eq!(list("", false).len(), 4);
+}
+
+#[test]
+fn test_container_filter() {
+    let mut store = Store::setup_test_store();
+    store.add_path_with_time("/unknown", 1).unwrap();
+    store.add_path("/host").unwrap();
+    store.container = Some(String::from("box"));
+    store.add_path("/box").unwrap();
+    store.add_path("/both").unwrap();
+    store.container = Some(String::from("other"));
+    store.add_path("/other").unwrap();
+    store.container = None;
+    store.add_path("/both").unwrap();
+
+    let list = |store: &Store| -> Vec<String> {
+        let mut paths: Vec<String> = store
+            .list_paths(0, 10, "", false)
+            .unwrap()
+            .into_iter()
+            .map(|p| p.path)
+            .collect();
+        paths.sort();
+        paths
+    };
+    assert_eq!(list(&store), vec!["/both", "/host", "/unknown"]);
+    let suggestions = store
+        .list_path_history_smart_suggestions("/host", 10, 10, &[])
+        .unwrap();
+    assert!(
+        suggestions
+            .iter()
+            .all(|p| p.path != "/box" && p.path != "/other")
+    );
+
+    store.container = Some(String::from("box"));
+    assert_eq!(list(&store), vec!["/both", "/box"]);
+    let suggestions = store
+        .list_path_history_smart_suggestions("/host", 10, 10, &[])
+        .unwrap();
+    assert!(
+        suggestions
+            .iter()
+            .all(|p| p.path == "/both" || p.path == "/box")
+    );
+
+    store.config.lock().unwrap().mix_container_history = true;
+    assert_eq!(list(&store).len(), 5);
+}
+
+#[test]
+fn test_add_paths_from_pane() {
+    let store = Store::setup_test_store();
+    store
+        .add_paths_from_pane(
+            &[String::from("/a"), String::from("/b"), String::from("/a")],
+            Some("%1"),
+        )
+        .unwrap();
+    let mut paths: Vec<String> = store
+        .list_paths(0, 10, "", false)
+        .unwrap()
+        .into_iter()
+        .map(|p| p.path)
+        .collect();
+    paths.sort();
+    assert_eq!(paths, vec!["/a", "/b"]);
+    let history: i64 = store
+        .db_conn
+        .query_row(
+            "SELECT COUNT(*) FROM paths_history WHERE tmux_pane = '%1'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(history, 3);
+}
+
+#[test]
+fn test_canonical_path() {
+    assert_eq!(canonical_path("/nonexistent/a/"), "/nonexistent/a");
+    assert_eq!(canonical_path("/nonexistent/./a/../b"), "/nonexistent/b");
+    assert_eq!(canonical_path("/.."), "/");
+    assert_eq!(canonical_path("relative/"), "relative/");
+    assert_eq!(canonical_path("me@server:/srv/"), "me@server:/srv/");
+
+    let dir = tempfile::tempdir().unwrap();
+    let target = dir.path().canonicalize().unwrap().join("target");
+    fs::create_dir(&target).unwrap();
+    let link = dir.path().join("link");
+    std::os::unix::fs::symlink(&target, &link).unwrap();
+    assert_eq!(
+        canonical_path(&format!("{}/", link.display())),
+        target.display().to_string()
+    );
+}
+
+#[test]
+fn test_add_path_canonicalized() {
+    let store = Store::setup_test_store();
+    store.config.lock().unwrap().canonicalize_paths = true;
+    store.add_path("/nonexistent/proj/").unwrap();
+    store.add_path("/nonexistent/proj").unwrap();
+    store.add_path("/nonexistent/other/../proj").unwrap();
+    let paths = store.list_paths(0, 10, "", false).unwrap();
+    assert_eq!(paths.len(), 1);
+    assert_eq!(paths[0].path, "/nonexistent/proj");
+
+    store.config.lock().unwrap().canonicalize_paths = false;
+    store.add_path("/nonexistent/proj/").unwrap();
+    assert_eq!(store.list_paths(0, 10, "", false).unwrap().len(), 2);
+}
+
+#[test]
+fn test_list_paths_inside_shortcut() {
+    let store = Store::setup_test_store();
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path().display().to_string();
+    for sub_directory in ["src/handlers", "src/models", "docs", ".git"] {
+        fs::create_dir_all(dir.path().join(sub_directory)).unwrap();
+    }
+    store.upsert_shortcut("api", &root, None).unwrap();
+    store
+        .add_path_with_time(&format!("{}/src/models", root), 10)
+        .unwrap();
+    store
+        .add_path_with_time(&format!("{}/src/gone", root), 20)
+        .unwrap();
+    store.add_path_with_time("/elsewhere/src", 30).unwrap();
+
+    let listed = |search: &str| -> Vec<(String, bool)> {
+        store
+            .list_paths(0, 10, search, false)
+            .unwrap()
+            .into_iter()
+            .map(|path| (path.path.replacen(&root, "", 1), path.is_in_history()))
+            .collect()
+    };
+    // the history first, then the directories never visited
+    assert_eq!(
+        listed("api/src/"),
+        vec![
+            (String::from("/src/gone"), true),
+            (String::from("/src/models"), true),
+            (String::from("/src/handlers"), false),
+        ]
+    );
+    assert_eq!(
+        listed("api/src/hand"),
+        vec![(String::from("/src/handlers"), false)]
+    );
+    assert_eq!(
+        listed("api/"),
+        vec![
+            (String::from("/src/gone"), true),
+            (String::from("/src/models"), true),
+            (String::from("/docs"), false),
+            (String::from("/src"), false),
+        ]
+    );
+    assert_eq!(listed("api/.g"), vec![(String::from("/.git"), false)]);
+    assert_eq!(store.count_paths("api/src/", false).unwrap(), 3);
+    assert_eq!(store.list_paths(1, 1, "api/src/", false).unwrap().len(), 1);
+
+    // not a shortcut: a plain search
+    assert_eq!(listed("apx/src").len(), 0);
+}
+
+#[test]
+fn test_link_paths() {
+    let store = Store::setup_test_store();
+    store.add_path_with_time("/real/proj", 10).unwrap();
+    store.add_path_with_time("/alias/proj", 20).unwrap();
+    store.add_path_with_time("/other", 30).unwrap();
+    assert_eq!(store.list_paths(0, 10, "", false).unwrap().len(), 3);
+
+    store.link_paths("/real/proj", "/alias/proj").unwrap();
+    let paths: Vec<String> = store
+        .list_paths(0, 10, "", false)
+        .unwrap()
+        .into_iter()
+        .map(|p| p.path)
+        .collect();
+    // the group is displayed with the form visited last
+    assert_eq!(paths, vec!["/other", "/alias/proj"]);
+
+    // joining two groups
+    store.add_path_with_time("/mnt/proj", 40).unwrap();
+    store.add_path_with_time("/bind/proj", 5).unwrap();
+    store.link_paths("/mnt/proj", "/bind/proj").unwrap();
+    store.link_paths("/alias/proj", "/mnt/proj").unwrap();
+    let paths: Vec<String> = store
+        .list_paths(0, 10, "", false)
+        .unwrap()
+        .into_iter()
+        .map(|p| p.path)
+        .collect();
+    assert_eq!(paths, vec!["/mnt/proj", "/other"]);
+    assert_eq!(
+        store.group_key("/bind/proj").unwrap(),
+        store.group_key("/real/proj").unwrap()
+    );
+}
+
+#[test]
+fn test_link_resolved_path() {
+    let dir = tempfile::tempdir().unwrap();
+    let target = dir.path().canonicalize().unwrap().join("target");
+    fs::create_dir(&target).unwrap();
+    let link = dir.path().canonicalize().unwrap().join("link");
+    std::os::unix::fs::symlink(&target, &link).unwrap();
+    let target = target.to_str().unwrap();
+    let link = link.to_str().unwrap();
+
+    let store = Store::setup_test_store();
+    store.add_path(target).unwrap();
+    store.add_path(link).unwrap();
+    let paths = store.list_paths(0, 10, "", false).unwrap();
+    assert_eq!(paths.len(), 1);
+    assert_eq!(paths[0].path, link);
+}
+
+#[test]
+fn test_smart_suggestions_with_links() {
+    let store = Store::setup_test_store();
+    store.config.lock().unwrap().smart_suggestions_strategy = SmartSuggestionsStrategy::Transitions;
+    for (idx, path) in [
+        "/work",
+        "/real/proj",
+        "/work",
+        "/alias/proj",
+        "/work",
+        "/other",
+    ]
+    .iter()
+    .enumerate()
+    {
+        store.add_path_with_time(path, 100 + idx as u64).unwrap();
+    }
+    store.link_paths("/real/proj", "/alias/proj").unwrap();
+    let suggestions: Vec<String> = store
+        .list_path_history_smart_suggestions("/work", 5, 5, &[])
+        .unwrap()
+        .into_iter()
+        .map(|p| p.path)
+        .collect();
+    // the two transitions to the project add up, and its last visited form is suggested
+    assert_eq!(suggestions, vec!["/alias/proj", "/other"]);
+}
+
+#[test]
+fn test_sensitive_paths() {
+    let store = Store::setup_test_store();
+    store.add_path_with_time("/public", 10).unwrap();
+    store.add_path_with_time("/client", 20).unwrap();
+    assert!(store.set_path_sensitive("/client", true).unwrap());
+    assert!(!store.set_path_sensitive("/unknown", true).unwrap());
+
+    let list = |fuzzy: bool| -> Vec<String> {
+        store
+            .list_paths(0, 10, if fuzzy { "c" } else { "" }, fuzzy)
+            .unwrap()
+            .into_iter()
+            .map(|p| p.path)
+            .collect()
+    };
+    assert_eq!(list(false), vec!["/public"]);
+    assert_eq!(list(true), vec!["/public"]);
+
+    // the flag survives a new visit
+    store.add_path_with_time("/client", 30).unwrap();
+    assert_eq!(list(false), vec!["/public"]);
+
+    store.set_reveal_sensitive(true);
+    assert_eq!(list(false), vec!["/client", "/public"]);
+    store.set_reveal_sensitive(false);
+
+    store.set_path_sensitive("/client", false).unwrap();
+    assert_eq!(list(false), vec!["/client", "/public"]);
+}
+
+#[test]
+fn test_dir_sizes() {
+    let store = Store::setup_test_store();
+    assert!(store.list_dir_sizes().unwrap().is_empty());
+    store
+        .set_dir_size("/tmp", &DirSize { size: 10, date: 1 })
+        .unwrap();
+    store
+        .set_dir_size("/tmp", &DirSize { size: 20, date: 2 })
+        .unwrap();
+    assert_eq!(
+        store.list_dir_sizes().unwrap(),
+        vec![(String::from("/tmp"), DirSize { size: 20, date: 2 })]
+    );
+}
+
+#[test]
+fn test_remote_flag() {
+    let store = Store::setup_test_store();
+    store.add_path("/home/me").unwrap();
+    store.add_path("me@server:/srv/app").unwrap();
+    store
+        .upsert_shortcut("srv", "me@server:/srv", None)
+        .unwrap();
+
+    let paths = store.list_paths(0, 10, "", false).unwrap();
+    assert!(paths[0].remote);
+    assert!(!paths[1].remote);
+    let remote_paths: i64 = store
+        .db_conn
+        .query_row("SELECT COUNT(*) FROM paths WHERE remote = 1", [], |row| {
+            row.get(0)
+        })
+        .unwrap();
+    assert_eq!(remote_paths, 1);
+
+    let shortcut = store.find_shortcut("srv").unwrap();
+    assert!(shortcut.remote);
+    store
+        .update_shortcut(shortcut.id, "srv", "/srv", None)
+        .unwrap();
+    assert!(!store.list_all_shortcuts().unwrap()[0].remote);
+}
+
+#[test]
+fn test_broken_shortcuts() {
+    let store = Store::setup_test_store();
+    let root = tempfile::tempdir().unwrap();
+    let work = root.path().join("work");
+    std::fs::create_dir(&work).unwrap();
+    let work = work.to_str().unwrap();
+    let gone = format!("{}/gone", root.path().to_str().unwrap());
+    store.upsert_shortcut("work", work, None).unwrap();
+    store.upsert_shortcut("gone", &gone, None).unwrap();
+    store
+        .upsert_shortcut("srv", "me@server:/gone", None)
+        .unwrap();
+
+    // the remote shortcuts are not checked
+    assert_eq!(store.check_shortcuts(3600).unwrap(), 2);
+    assert_eq!(store.check_shortcuts(3600).unwrap(), 0);
+    let gone_id = store.find_shortcut("gone").unwrap().id;
+    assert_eq!(
+        store.broken_shortcut_ids().unwrap(),
+        HashSet::from([gone_id])
+    );
+
+    store.relocate_shortcut(gone_id, work).unwrap();
+    assert_eq!(store.find_shortcut("gone").unwrap().path, work);
+    assert!(store.broken_shortcut_ids().unwrap().is_empty());
+    assert_eq!(store.check_shortcuts(3600).unwrap(), 1);
+    assert!(store.broken_shortcut_ids().unwrap().is_empty());
+}
+
+#[test]
+fn test_git_root_shortcuts() {
+    let store = Store::setup_test_store();
+    let root = tempfile::tempdir().unwrap();
+    for dir in ["api/.git", "api/src", "old/api/.git", "notes"] {
+        std::fs::create_dir_all(root.path().join(dir)).unwrap();
+    }
+    let path = |dir: &str| format!("{}/{}", root.path().to_str().unwrap(), dir);
+
+    // disabled by default
+    store.add_path(&path("api/src")).unwrap();
+    assert!(store.list_all_shortcuts().unwrap().is_empty());
+
+    store.config.lock().unwrap().auto_shortcut_git_roots = true;
+    store.add_path(&path("api/src")).unwrap();
+    store.add_path(&path("api")).unwrap();
+    store.add_path(&path("notes")).unwrap();
+    store.add_path(&path("old/api")).unwrap();
+    let shortcuts: Vec<(String, String)> = store
+        .list_all_shortcuts()
+        .unwrap()
+        .into_iter()
+        .map(|shortcut| (shortcut.name, shortcut.path))
+        .collect();
+    assert_eq!(
+        shortcuts,
+        vec![
+            (String::from("api"), path("api")),
+            (String::from("old-api"), path("old/api")),
+        ]
+    );
+}
+
+#[test]
+fn test_project_types() {
+    let store = Store::setup_test_store();
+    let root = tempfile::tempdir().unwrap();
+    let path = |dir: &str| format!("{}/{}", root.path().to_str().unwrap(), dir);
+    for (dir, marker) in [("cdir", "Cargo.toml"), ("web", "package.json")] {
+        std::fs::create_dir(path(dir)).unwrap();
+        std::fs::write(format!("{}/{}", path(dir), marker), "").unwrap();
+    }
+    std::fs::create_dir(path("notes")).unwrap();
+    for dir in ["cdir", "web", "notes"] {
+        store.add_path(&path(dir)).unwrap();
+    }
+    store.add_path("me@server:/srv/cdir").unwrap();
+
+    assert_eq!(store.count_paths("type:rust", false).unwrap(), 1);
+    assert_eq!(store.count_paths("type:node web", false).unwrap(), 1);
+    assert_eq!(store.count_paths("type:node cdir", false).unwrap(), 0);
+    assert_eq!(store.count_paths("type:go", false).unwrap(), 0);
+    assert_eq!(
+        store.list_paths(0, 10, "type:rust cd", true).unwrap()[0].path,
+        path("cdir")
+    );
+    // an unknown type is searched as text
+    assert_eq!(store.count_paths("type:java", false).unwrap(), 0);
+
+    let cdir = path("cdir");
+    let notes = path("notes");
+    let types = store
+        .project_types(&[&cdir, &notes, "me@server:/srv/cdir"])
+        .unwrap();
+    assert_eq!(types, HashMap::from([(cdir.clone(), ProjectType::Rust)]));
+
+    // the cached types are not detected again
+    std::fs::remove_file(format!("{}/Cargo.toml", cdir)).unwrap();
+    assert_eq!(store.project_types(&[&cdir]).unwrap().len(), 1);
+    store
+        .db_conn
+        .execute("UPDATE project_types SET date = 0", [])
+        .unwrap();
+    assert!(store.project_types(&[&cdir]).unwrap().is_empty());
+}
+
+#[test]
+fn test_connection_tuning() {
+    let root = tempfile::tempdir().unwrap();
+    let db_path = root.path().join("cdir.db");
+    let store = Store::new(&db_path, Arc::new(Mutex::new(Config::default()))).unwrap();
+    let pragma = |store: &Store, name: &str| -> String {
+        store
+            .db_conn
+            .query_row(&format!("PRAGMA {}", name), [], |row| {
+                row.get::<_, rusqlite::types::Value>(0)
+            })
+            .map(|value| match value {
+                rusqlite::types::Value::Text(text) => text,
+                rusqlite::types::Value::Integer(int) => int.to_string(),
+                other => format!("{:?}", other),
+            })
+            .unwrap()
+    };
+    assert_eq!(pragma(&store, "journal_mode"), "wal");
+    assert_eq!(pragma(&store, "synchronous"), "1");
+    assert_eq!(pragma(&store, "busy_timeout"), "5000");
+
+    // a write waits for the lock held by another process instead of failing
+    let (locked_tx, locked_rx) = std::sync::mpsc::channel();
+    let writer_path = db_path.clone();
+    let writer = std::thread::spawn(move || {
+        let conn = Connection::open(writer_path).unwrap();
+        conn.execute_batch("BEGIN IMMEDIATE").unwrap();
+        locked_tx.send(()).unwrap();
+        std::thread::sleep(Duration::from_millis(200));
+        conn.execute_batch("COMMIT").unwrap();
+    });
+    locked_rx.recv().unwrap();
+    store.add_path("/tmp/busy").unwrap();
+    writer.join().unwrap();
+    assert_eq!(store.count_paths("busy", false).unwrap(), 1);
+    drop(store);
+
+    let config = Config {
+        database: DatabaseConfig {
+            journal_mode: JournalMode::Delete,
+            synchronous: Synchronous::Full,
+            busy_timeout_ms: 100,
+        },
+        ..Default::default()
+    };
+    let store = Store::new(&db_path, Arc::new(Mutex::new(config))).unwrap();
+    assert_eq!(pragma(&store, "journal_mode"), "delete");
+    assert_eq!(pragma(&store, "synchronous"), "2");
+    assert_eq!(pragma(&store, "busy_timeout"), "100");
+}
+
+#[test]
+fn test_new_unopenable_database() {
+    let root = tempfile::tempdir().unwrap();
+    // a file stands where the directory of the database should be
+    let file = root.path().join("file");
+    std::fs::write(&file, "").unwrap();
+    let config = Arc::new(Mutex::new(Config::default()));
+    assert!(Store::new(&file.join("cdir.db"), config).is_err());
+}
+
+#[test]
+fn test_concurrent_writers() {
+    let root = tempfile::tempdir().unwrap();
+    let db_path = root.path().join("cdir.db");
+    drop(Store::new(&db_path, Arc::new(Mutex::new(Config::default()))).unwrap());
+
+    let writers: Vec<_> = (0..4)
+        .map(|writer| {
+            let db_path = db_path.clone();
+            std::thread::spawn(move || {
+                let store = Store::new(&db_path, Arc::new(Mutex::new(Config::default()))).unwrap();
+                for idx in 0..50 {
+                    store
+                        .add_path(&format!("/tmp/w{}/p{}", writer, idx))
+                        .unwrap();
+                    store
+                        .upsert_shortcut(&format!("w{}p{}", writer, idx), "/tmp", None)
+                        .unwrap();
+                }
+            })
+        })
+        .collect();
+    for writer in writers {
+        writer.join().unwrap();
+    }
+
+    let store = Store::new(&db_path, Arc::new(Mutex::new(Config::default()))).unwrap();
+    assert_eq!(store.count_paths("", false).unwrap(), 200);
+    assert_eq!(store.list_all_shortcuts().unwrap().len(), 200);
+}
+
+#[test]
+fn test_retry_busy() {
+    let store = Store::setup_test_store();
+    let busy = || {
+        rusqlite::Error::SqliteFailure(rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_BUSY), None)
+    };
+    let mut attempts = 0;
+    let result = store.retry_busy(|| {
+        attempts += 1;
+        match attempts {
+            1 | 2 => Err(busy()),
+            _ => Ok(attempts),
+        }
+    });
+    assert_eq!(result.unwrap(), 3);
+
+    // the other errors are not retried
+    let mut attempts = 0;
+    let result: Result<(), _> = store.retry_busy(|| {
+        attempts += 1;
+        Err(rusqlite::Error::InvalidQuery)
+    });
+    assert!(result.is_err());
+    assert_eq!(attempts, 1);
+
+    // a write within a transaction is retried with the whole transaction
+    let transaction = store.transaction().unwrap();
+    let mut attempts = 0;
+    let result: Result<(), _> = store.retry_busy(|| {
+        attempts += 1;
+        Err(busy())
+    });
+    assert!(is_busy(&result.unwrap_err()));
+    assert_eq!(attempts, 1);
+    drop(transaction);
+}
+
+#[test]
+fn test_upsert_shortcut() {
+    let store = Store::setup_test_store();
+    store
+        .upsert_shortcut("w", "/home/work", Some("work"))
+        .unwrap();
+    let shortcut = store.find_shortcut("w").unwrap();
+    store
+        .set_shortcut_hints(
+            "w",
+            &ShortcutHints {
+                env: vec![],
+                post_cd: Some(String::from("ls")),
+            },
+        )
+        .unwrap();
+
+    // the shortcut is replaced in place, its hints being reset
+    store.upsert_shortcut("w", "/home/other", None).unwrap();
+    let shortcuts = store.list_all_shortcuts().unwrap();
+    assert_eq!(shortcuts.len(), 1);
+    assert_eq!(shortcuts[0].id, shortcut.id);
+    assert_eq!(shortcuts[0].path, "/home/other");
+    assert_eq!(shortcuts[0].description, None);
+    assert_eq!(
+        store.find_shortcut_hints("w").unwrap(),
+        Some(ShortcutHints::default())
+    );
+
+    // a failure in the middle of the replacement leaves the shortcut as it was
+    store
+        .db_conn
+        .execute_batch(
+            "CREATE TEMP TRIGGER fail_update BEFORE UPDATE ON shortcuts \
+             BEGIN SELECT RAISE(ABORT, 'simulated failure'); END;",
+        )
+        .unwrap();
+    assert!(store.upsert_shortcut("w", "/home/third", None).is_err());
+    assert_eq!(store.find_shortcut("w").unwrap().path, "/home/other");
+    store.upsert_shortcut("o", "/home/o", None).unwrap();
+    assert_eq!(store.list_all_shortcuts().unwrap().len(), 2);
+}
+
+#[test]
+fn test_expand_shortcut() {
+    let store = Store::setup_test_store();
+    store
+        .upsert_shortcut("docs", "/home/me/documents", None)
+        .unwrap();
+    store.upsert_shortcut("root", "/", None).unwrap();
+
+    let expanded = |text: &str| store.expand_shortcut(text).map(|(_, path)| path);
+    assert_eq!(
+        expanded("docs/reports/2024").as_deref(),
+        Some("/home/me/documents/reports/2024")
+    );
+    assert_eq!(
+        expanded("docs/reports/").as_deref(),
+        Some("/home/me/documents/reports")
+    );
+    assert_eq!(expanded("docs").as_deref(), Some("/home/me/documents"));
+    assert_eq!(expanded("root/etc").as_deref(), Some("/etc"));
+    assert_eq!(expanded("doc/reports"), None);
+    assert_eq!(expanded("/home/me"), None);
+    assert_eq!(store.expand_shortcut("docs/a").unwrap().0.name, "docs");
+}
+
+#[test]
+fn test_rename_shortcut() {
+    let store = Store::setup_test_store();
+    store
+        .upsert_shortcut("w", "/home/work", Some("work"))
+        .unwrap();
+    store.upsert_shortcut("o", "/home/other", None).unwrap();
+    let hints = ShortcutHints {
+        env: vec![(String::from("A"), String::from("b"))],
+        post_cd: None,
+    };
+    store.set_shortcut_hints("w", &hints).unwrap();
+
+    assert!(store.rename_shortcut("w", "work").unwrap());
+    assert!(store.find_shortcut("w").is_none());
+    let shortcut = store.find_shortcut("work").unwrap();
+    assert_eq!(shortcut.path, "/home/work");
+    assert_eq!(shortcut.description.as_deref(), Some("work"));
+    assert_eq!(store.find_shortcut_hints("work").unwrap(), Some(hints));
+
+    assert!(!store.rename_shortcut("missing", "m").unwrap());
+
+    // a shortcut is not renamed over another one
+    assert!(store.rename_shortcut("work", "o").is_err());
+    assert_eq!(store.find_shortcut("work").unwrap().path, "/home/work");
+    assert_eq!(store.find_shortcut("o").unwrap().path, "/home/other");
+
+    // nor partially renamed by a failure
+    store
+        .db_conn
+        .execute_batch(
+            "CREATE TEMP TRIGGER fail_rename AFTER UPDATE ON shortcuts \
+             BEGIN SELECT RAISE(ABORT, 'simulated failure'); END;",
+        )
+        .unwrap();
+    assert!(store.rename_shortcut("work", "w").is_err());
+    assert!(store.find_shortcut("w").is_none());
+    assert!(store.find_shortcut("work").is_some());
+}
+
+#[test]
+fn test_unique_shortcut_names_migration() {
+    let store = Store::setup_test_store();
+    store
+        .db_conn
+        .execute_batch(
+            "DROP INDEX shortcuts_name; \
+             INSERT INTO shortcuts (name, path) VALUES ('w', '/old'), ('w', '/new'), ('o', '/o');",
+        )
+        .unwrap();
+    let duplicates = store.list_duplicate_shortcuts().unwrap();
+    assert_eq!(duplicates.len(), 1);
+    let (id, name, path) = &duplicates[0];
+    assert_eq!((name.as_str(), path.as_str()), ("w", "/old"));
+    store
+        .db_conn
+        .execute_batch(include_str!("../dbschema/17.sql"))
+        .unwrap();
+
+    // the older shortcut is kept under another name
+    let shortcuts = store.list_all_shortcuts().unwrap();
+    assert_eq!(shortcuts.len(), 3);
+    assert_eq!(store.find_shortcut("w").unwrap().path, "/new");
+    assert_eq!(
+        store.find_shortcut(&format!("w~{}", id)).unwrap().path,
+        "/old"
+    );
+    assert!(store.list_duplicate_shortcuts().unwrap().is_empty());
+}
+
+#[test]
+fn test_trash() {
+    let store = Store::setup_test_store();
+    for path in ["/trash/a", "/trash/b", "/trash/c"] {
+        store.add_path(path).unwrap();
+    }
+    let listed = |store: &Store| -> Vec<String> {
+        store
+            .list_paths(0, 10, "/trash/", false)
+            .unwrap()
+            .into_iter()
+            .map(|path| path.path)
+            .collect()
+    };
+    let id = |path: &str| {
+        store
+            .db_conn
+            .query_row("SELECT id FROM paths WHERE path = (?1)", [path], |row| {
+                row.get::<_, i64>(0)
+            })
+            .unwrap()
+    };
+
+    store.delete_path_by_id(id("/trash/b")).unwrap();
+    assert_eq!(listed(&store), vec!["/trash/c", "/trash/a"]);
+    store.set_show_trash(true);
+    assert_eq!(listed(&store), vec!["/trash/b"]);
+    assert_eq!(store.count_paths("trash", true).unwrap(), 1);
+
+    store.restore_path_by_id(id("/trash/b")).unwrap();
+    assert!(listed(&store).is_empty());
+    store.set_show_trash(false);
+    assert_eq!(listed(&store).len(), 3);
+
+    // only the paths of the trash are purged
+    store.purge_path_by_id(id("/trash/a")).unwrap();
+    assert_eq!(listed(&store).len(), 3);
+    store.delete_path_by_id(id("/trash/a")).unwrap();
+    store.purge_path_by_id(id("/trash/a")).unwrap();
+    store.set_show_trash(true);
+    assert!(listed(&store).is_empty());
+    store.set_show_trash(false);
+    assert_eq!(listed(&store), vec!["/trash/c", "/trash/b"]);
+
+    // a path visited again leaves the trash
+    store.delete_path_by_id(id("/trash/b")).unwrap();
+    store.add_path("/trash/b").unwrap();
+    assert_eq!(listed(&store), vec!["/trash/b", "/trash/c"]);
+
+    // the paths in the trash for too long are purged
+    store.delete_path_by_id(id("/trash/c")).unwrap();
+    store.delete_path_by_id(id("/trash/b")).unwrap();
+    store
+        .db_conn
+        .execute(
+            "UPDATE paths SET deleted_at = (?1) WHERE path = '/trash/c'",
+            [Local::now().timestamp() - 31 * 24 * 3600],
+        )
+        .unwrap();
+    assert_eq!(store.purge_trash(30).unwrap(), 1);
+    store.set_show_trash(true);
+    assert_eq!(listed(&store), vec!["/trash/b"]);
+}
+
+#[test]
+fn test_merge_database() {
+    let root = tempfile::tempdir().unwrap();
+    let config = Arc::new(Mutex::new(Config::default()));
+    let store = Store::new(&root.path().join("cdir.db"), config.clone()).unwrap();
+    let other_path = root.path().join("other.db");
+    let other = Store::new(&other_path, config).unwrap();
+    store
+        .db_conn
+        .execute_batch(
+            "INSERT INTO paths (path, date) VALUES ('/both', 100), ('/mine', 100), ('/stale', 300); \
+             INSERT INTO paths_history (path, date) VALUES ('/both', 100), ('/mine', 100), ('/stale', 300); \
+             INSERT INTO shortcuts (name, path) VALUES ('same', '/s'), ('taken', '/mine');",
+        )
+        .unwrap();
+    other
+        .db_conn
+        .execute_batch(
+            "INSERT INTO paths (path, date, sensitive) VALUES ('/both', 200, 0), ('/theirs', 150, 0), ('/stale', 50, 1); \
+             INSERT INTO paths_history (path, date) VALUES ('/both', 100), ('/both', 200), ('/theirs', 150), ('/stale', 50); \
+             INSERT INTO shortcuts (name, path, description) VALUES ('same', '/s', NULL), ('taken', '/theirs', NULL), ('new', '/theirs', 'merged');",
+        )
+        .unwrap();
+    drop(other);
+
+    // a dry run reports the same merge without applying it
+    let dry_run = store.merge_database(&other_path, true).unwrap();
+    assert!(store.find_shortcut("new").is_none());
+
+    let summary = store.merge_database(&other_path, false).unwrap();
+    assert_eq!(summary, dry_run);
+    assert_eq!(
+        summary,
+        MergeSummary {
+            paths_added: 1,
+            paths_updated: 1,
+            history_added: 3,
+            history_duplicates: 1,
+            shortcuts_added: 1,
+            shortcuts_duplicates: 1,
+            shortcut_conflicts: vec![ShortcutConflict {
+                name: "taken".to_string(),
+                path: "/mine".to_string(),
+                other_path: "/theirs".to_string(),
+            }],
+        }
+    );
+    let date = |path: &str| {
+        store
+            .db_conn
+            .query_row("SELECT date FROM paths WHERE path = (?1)", [path], |row| {
+                row.get::<_, i64>(0)
+            })
+            .unwrap()
+    };
+    // the last visit wins, the older one of the other database is only in the history
+    assert_eq!(date("/both"), 200);
+    assert_eq!(date("/stale"), 300);
+    assert_eq!(date("/theirs"), 150);
+    let sensitive: bool = store
+        .db_conn
+        .query_row(
+            "SELECT sensitive FROM paths WHERE path = '/stale'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert!(sensitive);
+    assert_eq!(store.find_shortcut("taken").unwrap().path, "/mine");
+    assert_eq!(
+        store.find_shortcut("new").unwrap().description.as_deref(),
+        Some("merged")
+    );
+
+    // merging again changes nothing
+    let summary = store.merge_database(&other_path, false).unwrap();
+    assert_eq!(summary.paths_added + summary.paths_updated, 0);
+    assert_eq!(summary.history_added, 0);
+    assert_eq!(summary.shortcuts_added, 0);
+    assert!(
+        store
+            .merge_database(&root.path().join("missing.db"), false)
+            .is_err()
+    );
+}
+
+#[test]
+fn test_list_paths_by_frecency() {
+    let store = Store::setup_test_store();
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let month = 30 * 24 * 3600;
+    // visited often a month ago, less than visited twice now
+    for offset in 0..6 {
+        store
+            .add_path_with_time("/old", now - month - offset)
+            .unwrap();
+    }
+    store.add_path_with_time("/recent", now - 60).unwrap();
+    store.add_path_with_time("/recent", now - 30).unwrap();
+    store.add_path_with_time("/once", now - 10).unwrap();
+    store.add_path_with_time("/hidden", now).unwrap();
+    store.set_path_sensitive("/hidden", true).unwrap();
+
+    let paths: Vec<String> = store
+        .list_paths_by_frecency()
+        .unwrap()
+        .into_iter()
+        .map(|path| path.path)
+        .collect();
+    assert_eq!(paths, vec!["/recent", "/once", "/old"]);
+}
+
+#[test]
+fn test_suggestions_only() {
+    let store = Store::setup_test_store();
+    let cwd = std::env::current_dir().unwrap();
+    let cwd = cwd.to_str().unwrap();
+    for (path, date) in [
+        ("/elsewhere", 100),
+        (cwd, 101),
+        ("/next", 102),
+        (cwd, 103),
+        ("/after", 104),
+    ] {
+        store.add_path_with_time(path, date).unwrap();
+    }
+    let all = store.count_paths("", false).unwrap();
+
+    store.set_suggestions_only(true);
+    let listed: Vec<String> = store
+        .list_paths(0, 10, "", false)
+        .unwrap()
+        .into_iter()
+        .map(|path| path.path)
+        .collect();
+    assert!(listed.contains(&"/next".to_string()));
+    assert!(!listed.contains(&"/elsewhere".to_string()));
+    assert!(listed.iter().all(|path| path != cwd));
+    assert_eq!(store.count_paths("", false).unwrap(), listed.len());
+    assert_eq!(
+        store.list_paths(0, 10, "nxt", true).unwrap()[0].path,
+        "/next"
+    );
+
+    store.set_suggestions_only(false);
+    assert_eq!(store.count_paths("", false).unwrap(), all);
+}
+
+#[test]
+fn test_list_path_visits() {
+    let store = Store::setup_test_store();
+    for (path, date) in [
+        ("/a", 100),
+        ("/b", 101),
+        ("/b", 102),
+        ("/b", 103),
+        ("/a", 104),
+        ("/a", 105),
+        ("/c", 106),
+    ] {
+        store.add_path_with_time(path, date).unwrap();
+    }
+    let summary = |visits: Vec<Visit>| -> Vec<(String, i64, usize)> {
+        visits
+            .into_iter()
+            .map(|visit| (visit.path.path, visit.path.date, visit.count))
+            .collect()
+    };
+
+    assert_eq!(store.list_path_visits(0, 10, None, false).unwrap().len(), 7);
+    assert_eq!(
+        summary(store.list_path_visits(0, 10, None, true).unwrap()),
+        vec![
+            (String::from("/c"), 106, 1),
+            (String::from("/a"), 105, 2),
+            (String::from("/b"), 103, 3),
+            (String::from("/a"), 100, 1),
+        ]
+    );
+    // the pagination applies to the collapsed visits
+    assert_eq!(
+        summary(store.list_path_visits(1, 2, None, true).unwrap()),
+        vec![(String::from("/a"), 105, 2), (String::from("/b"), 103, 3)]
+    );
+
+    store.set_path_sensitive("/b", true).unwrap();
+    assert_eq!(
+        summary(store.list_path_visits(0, 10, None, true).unwrap()),
+        vec![(String::from("/c"), 106, 1), (String::from("/a"), 105, 3)]
+    );
+}
+
+#[test]
+fn test_visit_counts() {
+    let store = Store::setup_test_store();
+    for path in ["/a", "/b", "/a", "/a"] {
+        store.add_path(path).unwrap();
+    }
+    let visit_counts = store.visit_counts();
+    assert_eq!(visit_counts.count("/a"), 3);
+    assert_eq!(visit_counts.count("/b"), 1);
+    assert_eq!(visit_counts.count("/c"), 0);
+    assert_eq!(visit_counts.heat("/a"), 1.0);
+    assert!((visit_counts.heat("/b") - 1.0 / 3.0).abs() < f64::EPSILON);
+
+    // the counts are kept until cleared
+    store.add_path("/b").unwrap();
+    assert_eq!(store.visit_counts().count("/b"), 1);
+    store.clear_visit_counts();
+    assert_eq!(store.visit_counts().count("/b"), 2);
+}
+
+#[test]
+fn test_weekly_visits() {
+    let store = Store::setup_test_store();
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let week = 7 * 24 * 3600;
+    for date in [
+        now,
+        now - 10,
+        now - week - 10,
+        now - 3 * week - 10,
+        now - 9 * week,
+    ] {
+        store.add_path_with_time("/a", date).unwrap();
+    }
+    store.add_path_with_time("/b", now).unwrap();
+
+    assert_eq!(
+        store.weekly_visits("/a", 8).unwrap(),
+        vec![0, 0, 0, 0, 1, 0, 1, 2]
+    );
+    assert_eq!(store.weekly_visits("/c", 8).unwrap(), vec![0; 8]);
+}
+
+#[test]
+fn test_usage_since() {
+    let store = Store::setup_test_store();
+    for (path, date) in [
+        ("/old", 100),
+        ("/old", 1000),
+        ("/new", 1100),
+        ("/new", 1200),
+        ("/secret", 1300),
+    ] {
+        store.add_path_with_time(path, date).unwrap();
+    }
+    store.set_path_sensitive("/secret", true).unwrap();
+
+    assert_eq!(
+        store.list_top_paths_since(1000, 10).unwrap(),
+        vec![(String::from("/new"), 2), (String::from("/old"), 1)]
+    );
+    assert_eq!(
+        store.list_top_paths_since(1000, 1).unwrap(),
+        vec![(String::from("/new"), 2)]
+    );
+    assert_eq!(
+        store.list_visit_dates_since(1000).unwrap(),
+        vec![1000, 1100, 1200]
+    );
+    assert_eq!(
+        store.list_paths_discovered_since(1000).unwrap(),
+        vec![(String::from("/new"), 1100)]
+    );
+}
+
+#[test]
+fn test_list_history_since() {
+    let store = Store::setup_test_store();
+    for (path, date) in [
+        ("/old", 100),
+        ("/work/api", 1000),
+        ("/other", 1100),
+        ("/secret", 1200),
+    ] {
+        store.add_path_with_time(path, date).unwrap();
+    }
+    store.set_path_sensitive("/secret", true).unwrap();
+    store.upsert_shortcut("w", "/work", None).unwrap();
+
+    let history = store.list_history_since(1000).unwrap();
+    assert_eq!(
+        history
+            .iter()
+            .map(|path| (path.path.as_str(), path.date))
+            .collect::<Vec<_>>(),
+        vec![("/work/api", 1000), ("/other", 1100)]
+    );
+    assert_eq!(history[0].shortcut.as_ref().unwrap().name, "w");
+    assert!(history[1].shortcut.is_none());
+
+    store.set_reveal_sensitive(true);
+    assert_eq!(store.list_history_since(1000).unwrap().len(), 3);
+}
+
+#[test]
+fn test_list_shortcuts_created_since() {
+    let store = Store::setup_test_store();
+    store.upsert_shortcut("w", "/work", None).unwrap();
+    store
+        .db_conn
+        .execute(
+            "INSERT INTO shortcuts (name, path) VALUES ('legacy', '/legacy')",
+            [],
+        )
+        .unwrap();
+
+    let created = store.list_shortcuts_created_since(0).unwrap();
+    assert_eq!(created.len(), 1);
+    assert_eq!(created[0].0.name, "w");
+    assert!(
+        store
+            .list_shortcuts_created_since(created[0].1 + 1)
+            .unwrap()
+            .is_empty()
+    );
+
+    // updating a shortcut keeps its creation date
+    store.upsert_shortcut("w", "/other", None).unwrap();
+    assert_eq!(
+        store.list_shortcuts_created_since(0).unwrap()[0].1,
+        created[0].1
+    );
+}
+
+#[test]
+fn test_recent_files() {
+    let store = Store::setup_test_store();
+    store
+        .add_file_with_time("/home/me/api/src/main.rs", 10)
+        .unwrap();
+    store
+        .add_file_with_time("/home/me/api/README.md", 20)
+        .unwrap();
+    store.add_file_with_time("/etc/hosts", 30).unwrap();
+    // opened again: moved first
+    store
+        .add_file_with_time("/home/me/api/src/main.rs", 40)
+        .unwrap();
+
+    let listed = |filters: &[Filter], sort| -> Vec<String> {
+        store
+            .list_files_filtered(0, 10, filters, sort)
+            .unwrap()
+            .into_iter()
+            .map(|file| file.path)
+            .collect()
+    };
+    assert_eq!(
+        listed(&[], SortOrder::Default),
+        vec![
+            "/home/me/api/src/main.rs",
+            "/etc/hosts",
+            "/home/me/api/README.md"
+        ]
+    );
+    assert_eq!(
+        listed(&[Filter::new("api", false)], SortOrder::Default),
+        vec!["/home/me/api/src/main.rs", "/home/me/api/README.md"]
+    );
+    assert_eq!(
+        listed(
+            &[Filter::new("api", false), Filter::new("readme", false)],
+            SortOrder::Default
+        ),
+        vec!["/home/me/api/README.md"]
+    );
+    assert_eq!(
+        listed(&[Filter::new("hsts", true)], SortOrder::Default),
+        vec!["/etc/hosts"]
+    );
+    assert_eq!(
+        listed(&[], SortOrder::Ascending(SortColumn::Path))[0],
+        "/etc/hosts"
+    );
+    assert_eq!(
+        store
+            .count_files_filtered(&[Filter::new("api", false)])
+            .unwrap(),
+        2
+    );
+
+    let hosts = store
+        .list_files_filtered(1, 1, &[], SortOrder::Default)
+        .unwrap();
+    assert_eq!(hosts[0].date, 30);
+    store.delete_file_by_id(hosts[0].id).unwrap();
+    assert_eq!(store.count_files_filtered(&[]).unwrap(), 2);
+}
+
+#[test]
+fn test_last_commands() {
+    let store = Store::setup_test_store();
+    store.add_command("/home/me/api", "cargo build").unwrap();
+    store.add_command("/home/me/api", "cargo test").unwrap();
+    store.add_command("/tmp", "ls -l").unwrap();
+
+    let commands = store
+        .last_commands(&["/home/me/api", "/tmp", "/var"])
+        .unwrap();
+    assert_eq!(commands.len(), 2);
+    assert_eq!(commands["/home/me/api"], "cargo test");
+    assert_eq!(commands["/tmp"], "ls -l");
+}
+
+#[test]
+fn test_list_sessions() {
+    let store = Store::setup_test_store();
+    // a morning session, a repeated visit collapsed
+    store.add_path_with_time("/home/me/api", 1000).unwrap();
+    store.add_path_with_time("/home/me/api/src", 1300).unwrap();
+    store.add_path_with_time("/home/me/api", 1600).unwrap();
+    store.add_path_with_time("/home/me/api", 1700).unwrap();
+    // an afternoon session, after a gap of more than 30 minutes
+    store.add_path_with_time("/home/me/docs", 5000).unwrap();
+    store.add_path_with_time("/tmp", 6000).unwrap();
+
+    let sessions = store.list_sessions(1800, 10).unwrap();
+    assert_eq!(
+        sessions,
+        vec![
+            Session {
+                start: 5000,
+                end: 6000,
+                paths: vec![String::from("/home/me/docs"), String::from("/tmp")],
+            },
+            Session {
+                start: 1000,
+                end: 1700,
+                paths: vec![
+                    String::from("/home/me/api"),
+                    String::from("/home/me/api/src"),
+                    String::from("/home/me/api"),
+                ],
+            },
+        ]
+    );
+
+    // only the last sessions
+    let sessions = store.list_sessions(1800, 1).unwrap();
+    assert_eq!(sessions.len(), 1);
+    assert_eq!(sessions[0].start, 5000);
+
+    // a shorter threshold splits the afternoon
+    assert_eq!(store.list_sessions(600, 10).unwrap().len(), 3);
+}
+
+#[test]
+fn test_suggestion_settings() {
+    let store = Store::setup_test_store();
+    let monorepo = SuggestionSettings {
+        depth: Some(50),
+        count: None,
+    };
+    store
+        .set_suggestion_settings("/home/me/monorepo", monorepo)
+        .unwrap();
+    assert_eq!(
+        store.suggestion_settings("/home/me/monorepo").unwrap(),
+        monorepo
+    );
+    // the sub directories inherit the settings, not the directories sharing a prefix
+    assert_eq!(
+        store
+            .effective_suggestion_settings("/home/me/monorepo/api")
+            .unwrap(),
+        monorepo
+    );
+    assert_eq!(
+        store
+            .effective_suggestion_settings("/home/me/monorepo2")
+            .unwrap(),
+        SuggestionSettings::default()
+    );
+    // the closest directory wins
+    let api = SuggestionSettings {
+        depth: None,
+        count: Some(1),
+    };
+    store
+        .set_suggestion_settings("/home/me/monorepo/api", api)
+        .unwrap();
+    assert_eq!(
+        store
+            .effective_suggestion_settings("/home/me/monorepo/api/src")
+            .unwrap(),
+        api
+    );
+    // no parameter left: the settings are removed
+    store
+        .set_suggestion_settings("/home/me/monorepo/api", SuggestionSettings::default())
+        .unwrap();
+    assert_eq!(
+        store
+            .effective_suggestion_settings("/home/me/monorepo/api/src")
+            .unwrap(),
+        monorepo
+    );
+}
+
+#[test]
+fn test_list_path_history_smart_suggestions_settings() {
+    let store = Store::setup_test_store();
+    store.add_path("/home/user/documents").unwrap();
+    store.add_path("/var/log/app1.1").unwrap();
+    store.add_path("/var/log/app1.2").unwrap();
+    store.add_path("/var/log/app1.3").unwrap();
+    store
+        .set_suggestion_settings(
+            "/home/user",
+            SuggestionSettings {
+                depth: None,
+                count: Some(1),
+            },
+        )
+        .unwrap();
+
+    // the count of the directory overrides the one given
+    let shortcuts = store.list_all_shortcuts().unwrap_or_default();
+    let suggestions = store
+        .list_path_history_smart_suggestions("/home/user/documents", 1, 3, &shortcuts)
+        .unwrap();
+    assert_eq!(suggestions.len(), 1);
+    assert_eq!("/var/log/app1.1", suggestions[0].path);
+}
+
+#[test]
+fn test_suggestions_cache() {
+    let store = Store::setup_test_store();
+    store.config.lock().unwrap().smart_suggestions_active = true;
+    store.add_path("/home/user/documents").unwrap();
+    store.add_path("/var/log/app1.1").unwrap();
+    store.add_path("/var/log/app1.2").unwrap();
+
+    // cached by add_path, before the next directories were visited
+    let shortcuts = store.list_all_shortcuts().unwrap_or_default();
+    assert_eq!(
+        store
+            .cached_suggestions("/home/user/documents", &shortcuts)
+            .unwrap()
+            .map(|paths| paths.len()),
+        Some(0)
+    );
+    assert!(
+        store
+            .cached_suggestions("/home/user/music", &shortcuts)
+            .unwrap()
+            .is_none()
+    );
+
+    let suggestions = store
+        .refresh_suggestions_cache("/home/user/documents")
+        .unwrap();
+    assert_eq!(suggestions, vec!["/var/log/app1.1", "/var/log/app1.2"]);
+    let cached = store
+        .cached_suggestions("/home/user/documents", &shortcuts)
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        cached
+            .iter()
+            .map(|path| path.path.as_str())
+            .collect::<Vec<_>>(),
+        suggestions
+    );
+    assert!(cached.iter().all(|path| path.smart_path));
+}
+
+#[test]
+fn test_warm_start_page() {
+    let store = Store::setup_test_store();
+    store.add_path_with_time("/a", 100).unwrap();
+    store.add_path_with_time("/b", 200).unwrap();
+    store.add_path_with_time("/c", 300).unwrap();
+    assert!(store.warm_start_page().unwrap().is_empty());
+
+    let ids: Vec<i64> = store
+        .list_paths(0, 3, "", false)
+        .unwrap()
+        .iter()
+        .map(|path| path.id)
+        .collect();
+    store.save_warm_start_page(&ids).unwrap();
+    let paths = |store: &Store| {
+        store
+            .warm_start_page()
+            .unwrap()
+            .into_iter()
+            .map(|path| path.path)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(paths(&store), vec!["/c", "/b", "/a"]);
+
+    // the paths deleted or sensitive since are not shown
+    store.delete_path_by_id(ids[0]).unwrap();
+    store.set_path_sensitive("/b", true).unwrap();
+    assert_eq!(paths(&store), vec!["/a"]);
+
+    // nothing is shown nor persisted while a filter is toggled
+    store.set_show_trash(true);
+    assert!(store.warm_start_page().unwrap().is_empty());
+    store.save_warm_start_page(&[]).unwrap();
+    store.set_show_trash(false);
+    assert_eq!(paths(&store), vec!["/a"]);
+}
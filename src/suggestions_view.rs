@@ -0,0 +1,344 @@
+use std::sync::{Arc, Mutex};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+use tokio::sync::broadcast::Sender;
+use tracing::{debug, error};
+
+use crate::{
+    config::Config,
+    keybindings::{self, Action, Scope},
+    store::{Path, Store},
+    tui::{
+        EventCaptured, GenericEvent, ManagerAction, View, ViewBuilder, ViewManager,
+        event::{ApplicationEvent, ViewManagerEvent},
+    },
+};
+
+#[cfg(test)]
+#[path = "suggestions_view_tests.rs"]
+mod suggestions_view_tests;
+
+/// Maximum number of suggestions displayed, each one being chosen with a function key
+const MAX_SUGGESTIONS: usize = 9;
+
+/// The bounds of the percentage of the view the panel takes at most
+const MIN_RATIO: u16 = 10;
+const MAX_RATIO: u16 = 90;
+
+/// The percentage the split moves by with ctrl+up and ctrl+down
+const RATIO_STEP: i16 = 5;
+
+/// The id of the application event published when the suggestions of a directory have been
+/// computed again in the background, its payload being a `SuggestionsPayload`
+pub const SUGGESTIONS_REFRESHED_EVENT: &str = "suggestions.refreshed";
+
+/// The payload of the `SUGGESTIONS_REFRESHED_EVENT` event
+#[derive(Debug)]
+pub struct SuggestionsPayload {
+    /// The directory the suggestions are made for
+    pub path: String,
+    /// The suggested paths, the best first
+    pub suggestions: Vec<String>,
+}
+
+/// A function type formatting a suggested path for the given width
+pub type SuggestionFormatFn = Box<dyn Fn(&Path, u16) -> Line<'static>>;
+
+/// The suggestions shown by the panel, shared with its container to lay it out
+pub struct SuggestionsState {
+    suggestions: Vec<Path>,
+    collapsed: bool,
+    /// The percentage of the view the panel takes at most
+    ratio: u16,
+}
+
+impl SuggestionsState {
+    pub fn new(ratio: u16) -> Self {
+        SuggestionsState {
+            suggestions: vec![],
+            collapsed: false,
+            ratio: ratio.clamp(MIN_RATIO, MAX_RATIO),
+        }
+    }
+
+    /// The number of lines of the panel within a view of `view_height` lines: nothing without
+    /// suggestions, only the title when collapsed, else the suggestions up to the ratio of the
+    /// view, with at least one of them.
+    pub fn height(&self, view_height: u16) -> u16 {
+        match (self.suggestions.is_empty(), self.collapsed) {
+            (true, _) => 0,
+            (false, true) => 1,
+            (false, false) => {
+                let max_height = (view_height as u32 * self.ratio as u32 / 100) as u16;
+                (1 + self.suggestions.len() as u16).min(max_height.max(2))
+            }
+        }
+    }
+
+    /// The suggestions drawn in a panel of `height` lines below its title, the others not
+    /// being formatted
+    fn visible(&self, height: u16) -> &[Path] {
+        let count = match self.collapsed {
+            true => 0,
+            false => self
+                .suggestions
+                .len()
+                .min(height.saturating_sub(1) as usize),
+        };
+        &self.suggestions[..count]
+    }
+
+    /// Move the split between the panel and the table by `step` percents, returning the new
+    /// ratio if it changed
+    fn move_split(&mut self, step: i16) -> Option<u16> {
+        let ratio = (self.ratio as i16 + step).clamp(MIN_RATIO as i16, MAX_RATIO as i16) as u16;
+        if ratio == self.ratio {
+            return None;
+        }
+        self.ratio = ratio;
+        Some(ratio)
+    }
+}
+
+/// A collapsible panel showing the directories predicted from the current directory,
+/// on top of the history view.
+pub struct SuggestionsView {
+    store: Store,
+    config: Arc<Mutex<Config>>,
+    state: Arc<Mutex<SuggestionsState>>,
+    tx: Sender<GenericEvent>,
+    format_fn: SuggestionFormatFn,
+}
+
+impl SuggestionsView {
+    pub fn builder(
+        vm: &ViewManager,
+        store: Store,
+        config: Arc<Mutex<Config>>,
+        state: Arc<Mutex<SuggestionsState>>,
+        format_fn: SuggestionFormatFn,
+    ) -> ViewBuilder {
+        ViewBuilder::from(Box::new(SuggestionsView {
+            store,
+            config,
+            state,
+            tx: vm.tx(),
+            format_fn,
+        }))
+    }
+
+    /// Load the suggestions for the current directory, if the panel is enabled: the cached ones
+    /// are shown at once, then computed again in a background task.
+    fn load(&mut self) {
+        {
+            let config_lock = self.config.lock().unwrap();
+            if !config_lock.smart_suggestions_active || !config_lock.smart_suggestions_panel {
+                return;
+            }
+        }
+        let Some(cwd) = std::env::current_dir()
+            .ok()
+            .and_then(|cwd| cwd.to_str().map(String::from))
+        else {
+            return;
+        };
+        let shortcuts = self.store.list_all_shortcuts().unwrap_or_default();
+        match self.store.cached_suggestions(&cwd, &shortcuts) {
+            Ok(Some(suggestions)) => self.set_suggestions(&cwd, suggestions),
+            Ok(None) => debug!("no cached suggestions for '{}'", cwd),
+            Err(e) => error!("Failed to read the cached suggestions for '{}': {}", cwd, e),
+        }
+        self.refresh(cwd);
+    }
+
+    /// Compute the suggestions of `cwd` again and cache them, in a background task with its own
+    /// connection to the database which publishes a `SUGGESTIONS_REFRESHED_EVENT` event once
+    /// done, or at once without a runtime or a database file.
+    fn refresh(&mut self, cwd: String) {
+        let (Ok(handle), Some(database_path)) = (
+            tokio::runtime::Handle::try_current(),
+            self.store.database_path(),
+        ) else {
+            match self.store.refresh_suggestions_cache(&cwd) {
+                Ok(suggestions) => self.apply_refreshed(&cwd, &suggestions),
+                Err(e) => error!("Failed to list the suggestions for '{}': {}", cwd, e),
+            }
+            return;
+        };
+        let config = self.config.clone();
+        let tx = self.tx.clone();
+        handle.spawn_blocking(move || {
+            let store = match Store::new(std::path::Path::new(&database_path), config) {
+                Ok(store) => store,
+                Err(e) => {
+                    error!("Failed to open the database '{}': {}", database_path, e);
+                    return;
+                }
+            };
+            let suggestions = match store.refresh_suggestions_cache(&cwd) {
+                Ok(suggestions) => suggestions,
+                Err(e) => {
+                    error!("Failed to list the suggestions for '{}': {}", cwd, e);
+                    return;
+                }
+            };
+            let event = GenericEvent::ApplicationEvent(ApplicationEvent {
+                id: String::from(SUGGESTIONS_REFRESHED_EVENT),
+                payload: Some(Arc::new(SuggestionsPayload {
+                    path: cwd,
+                    suggestions,
+                })),
+            });
+            if let Err(e) = tx.send(event) {
+                error!(
+                    "Failed to send '{}' event: {}",
+                    SUGGESTIONS_REFRESHED_EVENT, e
+                );
+            }
+            // the height of the panel follows the number of suggestions
+            let _ = tx.send(GenericEvent::ViewManagerEvent(ViewManagerEvent::Resize));
+        });
+    }
+
+    /// Show the suggestions computed again for `cwd`
+    fn apply_refreshed(&mut self, cwd: &str, suggestions: &[String]) {
+        let shortcuts = self.store.list_all_shortcuts().unwrap_or_default();
+        let suggestions = suggestions
+            .iter()
+            .map(|suggestion| {
+                let mut path = Path::new(0, suggestion.clone(), 0, &shortcuts);
+                path.smart_path = true;
+                path
+            })
+            .collect();
+        self.set_suggestions(cwd, suggestions);
+    }
+
+    fn set_suggestions(&mut self, cwd: &str, mut suggestions: Vec<Path>) {
+        // the count of the directory may be larger than the function keys
+        suggestions.truncate(MAX_SUGGESTIONS);
+        debug!("{} suggestions for '{}'", suggestions.len(), cwd);
+        self.state.lock().unwrap().suggestions = suggestions;
+    }
+}
+
+impl View for SuggestionsView {
+    fn init(&mut self) { self.load(); }
+
+    fn draw(&mut self, frame: &mut ratatui::Frame, area: Rect, active: bool) {
+        debug!("draw area='{}' active='{}", area, active);
+        let state = self.state.lock().unwrap();
+        if state.suggestions.is_empty() || area.height == 0 {
+            return;
+        }
+        let config_lock = self.config.lock().unwrap();
+        let styles = &config_lock.styles;
+        if let Some(bg_color) = &styles.background_color {
+            let background = Paragraph::new("").style(Style::default().bg(*bg_color));
+            frame.render_widget(background, area);
+        }
+
+        // the suggestions beyond the height of the panel are not shown
+        let visible = state.visible(area.height);
+        let mut constraints = vec![Constraint::Length(1)];
+        constraints.extend(visible.iter().map(|_| Constraint::Length(1)));
+        let lines = Layout::vertical(constraints).split(area);
+
+        let title = format!(
+            "{} suggestions (ctrl+g)",
+            if state.collapsed { "▸" } else { "▾" }
+        );
+        frame.render_widget(
+            Paragraph::new(Span::styled(title, styles.title_style)),
+            lines[0],
+        );
+        if state.collapsed {
+            return;
+        }
+        for (index, (suggestion, line_area)) in visible.iter().zip(lines.iter().skip(1)).enumerate()
+        {
+            let [key_area, path_area] =
+                Layout::horizontal([Constraint::Length(5), Constraint::Fill(1)]).areas(*line_area);
+            frame.render_widget(
+                Paragraph::new(Span::styled(format!(" F{} ", index + 1), styles.date_style)),
+                key_area,
+            );
+            frame.render_widget(
+                Paragraph::new((self.format_fn)(suggestion, path_area.width)),
+                path_area,
+            );
+        }
+    }
+
+    fn handle_key_event(&mut self, key_event: KeyEvent) -> (EventCaptured, ManagerAction) {
+        match (
+            keybindings::action(Scope::Suggestions, &key_event),
+            key_event.code,
+        ) {
+            (Some(Action::CollapseSuggestions), _) => {
+                let mut state = self.state.lock().unwrap();
+                state.collapsed = !state.collapsed;
+                debug!("suggestions collapsed={}", state.collapsed);
+                return (
+                    EventCaptured::No,
+                    ManagerAction::new(true).with_resize(true),
+                );
+            }
+            (Some(action @ (Action::GrowSuggestions | Action::ShrinkSuggestions)), _) => {
+                let step = if action == Action::GrowSuggestions {
+                    RATIO_STEP
+                } else {
+                    -RATIO_STEP
+                };
+                let mut state = self.state.lock().unwrap();
+                if state.suggestions.is_empty() || state.collapsed {
+                    return (EventCaptured::No, ManagerAction::new(false));
+                }
+                if let Some(ratio) = state.move_split(step) {
+                    debug!("suggestions panel ratio={}", ratio);
+                    let mut config = self.config.lock().unwrap();
+                    config.suggestions_panel_ratio = ratio;
+                    if let Err(e) = config.save() {
+                        error!("Failed to save config: {}", e);
+                    }
+                }
+                return (
+                    EventCaptured::No,
+                    ManagerAction::new(true).with_resize(true),
+                );
+            }
+            (Some(Action::ChooseSuggestion), KeyCode::F(n)) => {
+                let state = self.state.lock().unwrap();
+                if !state.collapsed
+                    && let Some(suggestion) = state.suggestions.get(n as usize - 1)
+                {
+                    debug!("suggestion F{} chosen: {}", n, suggestion.path);
+                    let event = GenericEvent::ViewManagerEvent(ViewManagerEvent::Exit(Some(
+                        suggestion.path.clone(),
+                    )));
+                    let _ = self.tx.send(event);
+                }
+            }
+            _ => {}
+        }
+        (EventCaptured::No, ManagerAction::new(false))
+    }
+
+    fn handle_application_event(&mut self, ae: &ApplicationEvent) {
+        if ae.id == "data.reload" {
+            self.load();
+        }
+        if ae.id == SUGGESTIONS_REFRESHED_EVENT
+            && let Some(payload) = &ae.payload
+            && let Some(payload) = payload.downcast_ref::<SuggestionsPayload>()
+        {
+            self.apply_refreshed(&payload.path, &payload.suggestions);
+        }
+    }
+}
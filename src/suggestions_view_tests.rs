@@ -0,0 +1,62 @@
+use super::*;
+
+fn suggestion(path: &str) -> Path {
+    Path {
+        id: 0,
+        path: path.to_string(),
+        date: 0,
+        shortcut: None,
+        smart_path: true,
+        hostname: None,
+        remote: false,
+    }
+}
+
+#[test]
+fn test_suggestions_state_height() {
+    let mut state = SuggestionsState::new(50);
+    assert_eq!(state.height(20), 0);
+
+    state.suggestions = vec![suggestion("/tmp/a"), suggestion("/tmp/b")];
+    assert_eq!(state.height(20), 3);
+
+    state.collapsed = true;
+    assert_eq!(state.height(20), 1);
+}
+
+#[test]
+fn test_suggestions_state_ratio() {
+    let mut state = SuggestionsState::new(50);
+    state.suggestions = (0..6)
+        .map(|idx| suggestion(&format!("/tmp/{}", idx)))
+        .collect();
+    assert_eq!(state.height(40), 7);
+    // the panel takes at most the ratio of the view, with one suggestion at least
+    assert_eq!(state.height(10), 5);
+    assert_eq!(state.height(2), 2);
+
+    assert_eq!(state.move_split(-RATIO_STEP), Some(45));
+    assert_eq!(state.height(10), 4);
+    // the ratio stays within its bounds
+    assert_eq!(SuggestionsState::new(95).ratio, MAX_RATIO);
+    let mut state = SuggestionsState::new(MIN_RATIO);
+    assert_eq!(state.move_split(-RATIO_STEP), None);
+    assert_eq!(state.move_split(RATIO_STEP), Some(15));
+}
+
+#[test]
+fn test_suggestions_state_visible() {
+    let mut state = SuggestionsState::new(50);
+    state.suggestions = (0..MAX_SUGGESTIONS)
+        .map(|idx| suggestion(&format!("/tmp/{}", idx)))
+        .collect();
+    // only the suggestions fitting below the title are formatted
+    assert_eq!(state.visible(4).len(), 3);
+    assert_eq!(state.visible(4)[2].path, "/tmp/2");
+    assert_eq!(state.visible(40).len(), MAX_SUGGESTIONS);
+    assert!(state.visible(1).is_empty());
+    assert!(state.visible(0).is_empty());
+
+    state.collapsed = true;
+    assert!(state.visible(40).is_empty());
+}
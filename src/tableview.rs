@@ -1,46 +1,191 @@
 use std::{
     any::Any,
+    collections::HashMap,
+    io::Write,
+    ops::Range,
     rc::Rc,
     sync::{Arc, Mutex},
 };
 
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent};
-use log::{debug, info, trace, warn};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{
     Frame,
     layout::{Constraint, Layout, Position, Rect},
     prelude::Style,
     style::Stylize,
-    widgets::{Paragraph, Row, Table, TableState},
+    text::{Line, Span},
+    widgets::{
+        Block, Borders, Clear, Paragraph, Row, Scrollbar, ScrollbarOrientation, ScrollbarState,
+        Table, TableState,
+    },
 };
 use tokio::sync::broadcast;
+use tracing::{debug, error, info, trace, warn};
 
 use crate::{
+    clipboard,
+    command_output_view::CommandOutputView,
+    command_prompt::CommandPrompt,
     config::Config,
     confirmation::Confirmation,
-    model::{DataViewModel, ListFunction},
-    search_text_view::SearchDescriptionPayload,
+    details_view::{Details, DetailsView},
+    keybindings::{self, Action, Scope},
+    list_indicator_view::publish_status_message,
+    model::{
+        CountFunction, DATA_PREFETCH_EVENT, DATA_RECONCILE_EVENT, DataPrefetchPayload,
+        DataReconcilePayload, DataViewModel, ListFunction, WarmStart,
+    },
+    open_with,
+    search_text_view::{
+        SEARCH_COMPLETION_EVENT, SearchCompletionPayload, SearchDescriptionPayload,
+    },
+    store::{SortColumn, SortOrder},
     theme::ThemeStyles,
     tui::{
         EventCaptured, GenericEvent, ManagerAction, View, ViewBuilder, ViewManager,
-        event::{ApplicationEvent, ViewManagerEvent},
+        event::{ApplicationEvent, ExternalCommandOutput, ViewManagerEvent},
     },
 };
 
+#[cfg(test)]
+#[path = "tableview_tests.rs"]
+mod tableview_tests;
+
 const TABLE_HEADER_LENGTH: usize = 1;
+const COMMAND_OUTPUT_EVENT_ID: &str = "command.output";
 const JUMP_OFFSET: usize = 10;
+/// Number of rows scrolled by a notch of the mouse wheel
+const WHEEL_SCROLL_ROWS: i64 = 3;
 
 const TABLE_COLUMN_SPACING: u16 = 1;
-const TABLE_HIGHLIGHT_SYMBOL: &str = "> ";
 
-/// A function type that converts a vector of items of type T into a vector of table rows.
-pub type RowifyFn<T> = Box<dyn Fn(&[T], &[u16]) -> Vec<Row<'static>>>;
+/// Number of rows, from the top of the table, that can be chosen with alt+digit
+const QUICK_SELECT_ROWS: usize = 9;
+const QUICK_SELECT_COLUMN_WIDTH: u16 = 1;
+
+/// The prefix announcing the selected row in the screen reader mode
+const LINEAR_SELECTION_PREFIX: &str = "> ";
+/// The prefix of the other rows in the screen reader mode
+const LINEAR_PREFIX: &str = "  ";
+/// The separator of the cells of a row in the screen reader mode
+const LINEAR_CELL_SEPARATOR: &str = "  ";
+
+/// The plain text of a row in the screen reader mode: its non empty cells, the selected row
+/// being announced with a `>` prefix
+fn linear_line(cells: &[Line], selected: bool) -> String {
+    let texts: Vec<String> = cells
+        .iter()
+        .map(|cell| {
+            cell.spans
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect::<String>()
+                .trim()
+                .to_string()
+        })
+        .filter(|text| !text.is_empty())
+        .collect();
+    let prefix = if selected {
+        LINEAR_SELECTION_PREFIX
+    } else {
+        LINEAR_PREFIX
+    };
+    format!("{}{}", prefix, texts.join(LINEAR_CELL_SEPARATOR))
+}
+
+/// Ring the bell of the terminal, announcing a move of the selection in the screen reader mode
+fn ring_bell() {
+    let mut stdout = std::io::stdout();
+    if let Err(e) = stdout.write_all(b"\x07").and_then(|_| stdout.flush()) {
+        warn!("Failed to ring the bell: {}", e);
+    }
+}
+
+/// A function type that converts a vector of items of type T into the cells of table rows.
+pub type RowifyFn<T> = Box<dyn Fn(&[T], &[u16]) -> Vec<Vec<Line<'static>>>>;
 
 /// A function type that deletes an item of type T into the store
 pub type DeleteFn<T> = Box<dyn Fn(&T)>;
 
+/// A function type that restores a deleted item of type T, returning whether it was restored
+pub type RestoreFn<T> = Box<dyn Fn(&T) -> bool>;
+
 pub type EditorViewBuilder<T> = Box<dyn Fn(T) -> Box<ViewBuilder>>;
 
+/// A function type that returns the (name, value) fields describing an item of type T in full
+pub type DetailsFn<T> = Box<dyn Fn(&T) -> Details>;
+
+/// A function type that returns the name of the shortcut associated with an item of type T, if any
+pub type ShortcutNameFn<T> = Box<dyn Fn(&T) -> Option<String>>;
+
+/// A function type that returns the section grouping an item of type T in the table, None when
+/// the items are not grouped
+pub type SectionFn<T> = Box<dyn Fn(&Config, &T) -> Option<String>>;
+
+/// A function type that returns the secondary line drawn dimmed under each of the items of type
+/// T, None for the items without one
+pub type SubtitleFn<T> = Box<dyn Fn(&Config, &[T]) -> Vec<Option<String>>>;
+
+/// A function type that returns the title and the lines of the small window drawn over the
+/// table while an item of type T is selected, None for the items without one
+pub type PopoverFn<T> = Box<dyn Fn(&Config, &T) -> Option<(String, Vec<String>)>>;
+
+/// The maximum width of the window drawn over the table for the selected entry
+const POPOVER_WIDTH: u16 = 56;
+
+/// The mark starting the secondary line of an entry
+const SUBTITLE_MARK: &str = "↳ ";
+
+/// A row of the table: the header of a section, the entry at an index of the view, or the
+/// secondary line of this entry
+#[derive(Debug, Clone, PartialEq)]
+enum DisplayRow {
+    Section(String),
+    Entry(usize),
+    Subtitle(usize),
+}
+
+/// The rows of the entries of the view, `sections` being their sections: a header row is
+/// inserted before each entry starting a section, including the first one, and a secondary
+/// row after each entry `subtitled`.
+fn display_rows(sections: &[Option<String>], subtitled: &[bool]) -> Vec<DisplayRow> {
+    let mut rows = vec![];
+    for (idx, section) in sections.iter().enumerate() {
+        if let Some(section) = section
+            && (idx == 0 || sections[idx - 1].as_ref() != Some(section))
+        {
+            rows.push(DisplayRow::Section(section.clone()));
+        }
+        rows.push(DisplayRow::Entry(idx));
+        if subtitled.get(idx) == Some(&true) {
+            rows.push(DisplayRow::Subtitle(idx));
+        }
+    }
+    rows
+}
+
+/// The display rows drawn in `visible` lines out of `count`, scrolled so that the `selected`
+/// one is the last drawn when it is below the first lines: only these rows are built.
+fn visible_rows(count: usize, selected: Option<usize>, visible: usize) -> Range<usize> {
+    let first = selected
+        .map_or(0, |selected| (selected + 1).saturating_sub(visible))
+        .min(count);
+    first..(first + visible).min(count)
+}
+
+/// The indexes of the entries of the display `rows`, from the first one to the last one
+fn entries_range(rows: &[DisplayRow]) -> Range<usize> {
+    let mut entries = rows.iter().filter_map(|row| match row {
+        DisplayRow::Entry(idx) => Some(*idx),
+        DisplayRow::Section(_) | DisplayRow::Subtitle(_) => None,
+    });
+    match (entries.next(), entries.next_back()) {
+        (Some(first), Some(last)) => first..last + 1,
+        (Some(first), None) => first..first + 1,
+        (None, _) => 0..0,
+    }
+}
+
 /// A function type that searches a collection of T to find the index to focus on
 pub type FindFocusFn<T> = Box<dyn Fn(&[T]) -> usize>;
 
@@ -50,14 +195,86 @@ pub type ColumnNamesFn = Box<dyn Fn(&Config) -> Vec<String>>;
 /// A function type that returns column constraints based on configuration
 pub type ColumnConstraintsFn = Box<dyn Fn(&Config) -> Vec<Constraint>>;
 
+/// The parts of a TableView, given to `TableView::builder`
+pub struct TableViewParams<T> {
+    /// The type of the items, e.g. `path`, naming the events of the table
+    pub objects_type: String,
+    /// Returns the column names based on configuration
+    pub column_names_fn: ColumnNamesFn,
+    /// Returns the column constraints based on configuration
+    pub column_constraints_fn: ColumnConstraintsFn,
+    /// Lists items of type T from the store
+    pub list_fn: Box<ListFunction<T>>,
+    /// Counts the items of type T listed by `list_fn`
+    pub count_fn: Box<CountFunction>,
+    /// Converts a vector of items of type T into a vector of table rows
+    pub rowify: RowifyFn<T>,
+    /// Converts an item of type T into a string
+    pub stringify: fn(&T) -> String,
+    /// The configuration object containing color settings
+    pub config: Arc<Mutex<Config>>,
+    /// The state shared by the tables
+    pub view_state: Arc<Mutex<TableViewState>>,
+    /// Deletes an item of type T from the store
+    pub delete_fn: DeleteFn<T>,
+    /// Restores a deleted item of type T, if supported
+    pub restore_fn: Option<RestoreFn<T>>,
+    pub editor_modal_view_builder: Option<EditorViewBuilder<T>>,
+    pub relocate_modal_view_builder: Option<EditorViewBuilder<T>>,
+    /// Searches a collection of T to find the index to focus on
+    pub find_focus_fn: FindFocusFn<T>,
+    /// Returns the details of an item of type T, shown in full
+    pub details_fn: DetailsFn<T>,
+    /// Returns the name of the shortcut associated with an item of type T
+    pub shortcut_name_fn: ShortcutNameFn<T>,
+    /// The columns (column name, sort column) the table can be sorted on
+    pub sort_columns: Vec<(&'static str, SortColumn)>,
+    /// Whether the first rows are numbered and can be chosen with alt+digit
+    pub quick_select: bool,
+    /// Returns the section grouping an item of type T, if any
+    pub section_fn: Option<SectionFn<T>>,
+    /// Returns the secondary lines of items of type T, if any
+    pub subtitle_fn: Option<SubtitleFn<T>>,
+    /// Returns the window drawn over the table for the selected item of type T, if any
+    pub popover_fn: Option<PopoverFn<T>>,
+    /// The first page of items of type T of the previous session, shown at startup until
+    /// they are queried, if any
+    pub warm_start: Option<WarmStart<T>>,
+}
+
+/// Column widths resolved for a given table width and set of constraints, kept between
+/// frames so that redraws with an unchanged layout skip the width computation.
+struct ColumnLayoutCache {
+    total_width: u16,
+    constraints: Vec<Constraint>,
+    widths: Vec<u16>,
+}
+
 pub struct TableViewState {
     pub display_with_shortcuts: bool,
+    /// The search (text, fuzzy match) the tables start with, when restoring a session
+    pub initial_search: Option<(String, bool)>,
+    /// The rows to select once the tables are first filled, by objects type
+    pub initial_rows: HashMap<String, usize>,
+    /// The index of the selected row of each table, by objects type
+    pub selected_rows: HashMap<String, usize>,
+    /// Whether the entries can only be chosen: they are neither changed nor opened with a
+    /// command
+    pub select_only: bool,
+    /// Whether the tables start without the state of the previous session, e.g. after a
+    /// crash: the first page of the history is not shown while it is queried
+    pub safe_mode: bool,
 }
 
 impl TableViewState {
     pub fn new() -> Self {
         TableViewState {
             display_with_shortcuts: true,
+            initial_search: None,
+            initial_rows: HashMap::new(),
+            selected_rows: HashMap::new(),
+            select_only: false,
+            safe_mode: false,
         }
     }
 }
@@ -66,6 +283,7 @@ impl TableViewState {
 pub struct TableView<T: Clone> {
     vm: Rc<ViewManager>,
     tx: broadcast::Sender<GenericEvent>,
+    objects_type: String,
     data_model: DataViewModel<T>,
     column_names_fn: ColumnNamesFn,
     column_constraints_fn: ColumnConstraintsFn,
@@ -77,11 +295,33 @@ pub struct TableView<T: Clone> {
     styles: ThemeStyles,
     view_state: Arc<Mutex<TableViewState>>,
     delete_fn: DeleteFn<T>,
+    restore_fn: Option<RestoreFn<T>>,
     editor_modal_view_builder: Option<EditorViewBuilder<T>>,
+    relocate_modal_view_builder: Option<EditorViewBuilder<T>>,
     find_focus_fn: FindFocusFn<T>,
+    details_fn: DetailsFn<T>,
+    shortcut_name_fn: ShortcutNameFn<T>,
+    layout_cache: Option<ColumnLayoutCache>,
+    sort_columns: Vec<(&'static str, SortColumn)>, // (column name, sort column) the table can be sorted on
+    running_command: Option<String>, // The command run from this table, whose output is awaited
+    quick_select: bool, // Whether the first rows are numbered and can be chosen with alt+digit
+    announced_row: Option<usize>, // The absolute index of the row last drawn as selected in the screen reader mode
+    section_fn: Option<SectionFn<T>>, // The sections grouping the entries when sorted by date
+    subtitle_fn: Option<SubtitleFn<T>>, // The secondary lines drawn under the entries
+    subtitles: Vec<Option<String>>, // The secondary lines last drawn, by entry
+    popover_fn: Option<PopoverFn<T>>, // The window drawn over the table for the selected entry
+    display_rows: Vec<DisplayRow>, // The rows last drawn, the section headers included
+    display_offset: usize,        // The index of the first row last drawn
 }
 
 impl<T: Clone + 'static> View for TableView<T> {
+    fn init(&mut self) {
+        if let Some((search_string, fuzzy_match)) = &self.view_state.lock().unwrap().initial_search
+        {
+            self.data_model.set_filter(search_string, *fuzzy_match);
+        }
+    }
+
     fn resize(&mut self, area: Rect) -> Vec<(u16, Rect)> {
         debug!("resize {}", area);
 
@@ -90,6 +330,16 @@ impl<T: Clone + 'static> View for TableView<T> {
 
         self.data_model
             .update(self.data_model.first, self.table_rows_count, true);
+
+        let initial_row = self
+            .view_state
+            .lock()
+            .unwrap()
+            .initial_rows
+            .remove(&self.objects_type);
+        if let Some(row) = initial_row {
+            self.restore_selected_row(row);
+        }
         vec![]
     }
 
@@ -116,42 +366,47 @@ impl<T: Clone + 'static> View for TableView<T> {
 
     fn handle_key_event(&mut self, key_event: KeyEvent) -> (EventCaptured, ManagerAction) {
         debug!("handle_key_event");
-        match key_event.code {
-            KeyCode::Enter => {
+        let shift = key_event.modifiers.contains(KeyModifiers::SHIFT);
+        let action = keybindings::action(Scope::Tables, &key_event);
+        if self.view_state.lock().unwrap().select_only && self.is_forbidden_when_select_only(action)
+        {
+            publish_status_message(&self.vm, "select only");
+            return (EventCaptured::Yes, ManagerAction::new(true));
+        }
+        match (action, key_event.code) {
+            (Some(Action::Choose), _) => {
                 debug!("send exit event");
                 let event =
                     GenericEvent::ViewManagerEvent(ViewManagerEvent::Exit(self.handle_chosen()));
                 let _ = self.tx.send(event);
             }
-            KeyCode::Home => {
+            (Some(Action::Top), _) => {
                 self.data_model.update(0, self.table_rows_count, true);
                 self.table_state.select_cell(Some((0, 0)))
             }
-            KeyCode::Down => {
-                self.handle_down(key_event.modifiers.contains(KeyModifiers::SHIFT), false);
-            }
-            KeyCode::Up => {
-                self.handle_up(key_event.modifiers.contains(KeyModifiers::SHIFT), false);
+            (Some(Action::Down), _) => self.handle_down(shift, false),
+            (Some(Action::Up), _) => self.handle_up(shift, false),
+            (Some(Action::PageDown), _) => self.handle_down(shift, true),
+            (Some(Action::PageUp), _) => self.handle_up(shift, true),
+            (Some(Action::ToggleShortcuts), _) => {
+                let mut view_state_lock = self.view_state.lock().unwrap();
+                view_state_lock.display_with_shortcuts = !view_state_lock.display_with_shortcuts;
             }
-            KeyCode::PageDown => {
-                self.handle_down(key_event.modifiers.contains(KeyModifiers::SHIFT), true);
+            (Some(Action::Delete), _) => self.handle_delete(),
+            (Some(Action::Edit), _) => self.handle_modal_event(false),
+            (Some(Action::Relocate), _) => self.handle_modal_event(true),
+            (Some(Action::Restore), _) => self.handle_restore(),
+            (Some(Action::Sort), _) => self.handle_sort(),
+            (Some(Action::Details), _) => self.handle_details(),
+            (Some(Action::RunCommand), _) => self.handle_run_command(),
+            (Some(Action::Copy), _) => self.handle_copy(false),
+            (Some(Action::CopyShortcutName), _) => self.handle_copy(true),
+            (Some(Action::CopyLastCommand), _) => self.handle_copy_subtitle(),
+            (Some(Action::QuickSelect), KeyCode::Char(c)) if self.quick_select => {
+                self.handle_quick_select(c as usize - '1' as usize)
             }
-            KeyCode::PageUp => {
-                self.handle_up(key_event.modifiers.contains(KeyModifiers::SHIFT), true);
-            }
-            KeyCode::Char(c) => {
-                if key_event.modifiers == KeyModifiers::CONTROL {
-                    match c {
-                        'a' => {
-                            let mut view_state_lock = self.view_state.lock().unwrap();
-                            view_state_lock.display_with_shortcuts =
-                                !view_state_lock.display_with_shortcuts;
-                        }
-                        'd' => self.handle_delete(),
-                        'e' => self.handle_modal_event(),
-                        _ => {}
-                    }
-                }
+            (Some(Action::QuickSelect | Action::OpenWith), KeyCode::Char(c)) => {
+                self.handle_open_with(c)
             }
             _ => {
                 warn!("Unknown action key={}", key_event.code);
@@ -162,51 +417,21 @@ impl<T: Clone + 'static> View for TableView<T> {
     }
 
     fn handle_mouse_event(&mut self, area: Rect, mouse_event: MouseEvent) -> ManagerAction {
-        let mut ma = ManagerAction::new(false);
-
-        if !matches!(mouse_event.kind, crossterm::event::MouseEventKind::Down(_)) {
-            return ma;
-        }
-
         let position = Position::new(mouse_event.column, mouse_event.row);
 
         // Check if the mouse event is within the view's area
         if !area.contains(position) {
-            return ma;
+            return ManagerAction::new(false);
         }
 
-        let relative_y = position.y.saturating_sub(area.y);
-
-        // Check if the click is within the table rows area
-        if relative_y >= TABLE_HEADER_LENGTH as u16 {
-            let row_index = relative_y - TABLE_HEADER_LENGTH as u16;
-            trace!("clicked row index: {}", row_index);
-
-            // Update selection if the clicked row is valid
-            if let Some(entries) = &self.data_model.entries {
-                let data_index = self.data_model.first + row_index as usize;
-
-                // if the selected index is the current one, exit the application with the chosen item
-                if let Some(selected_row) = self.selected_row()
-                    && selected_row == data_index
-                {
-                    debug!("send exit event");
-                    let event = GenericEvent::ViewManagerEvent(ViewManagerEvent::Exit(
-                        self.handle_chosen(),
-                    ));
-                    let _ = self.tx.send(event);
-                    return ma;
-                }
-
-                // else select the clicked row
-                if data_index < entries.len() {
-                    self.table_state.select(Some(data_index));
-                    ma.redraw = true;
-                }
+        match mouse_event.kind {
+            MouseEventKind::ScrollDown => self.handle_scroll(WHEEL_SCROLL_ROWS),
+            MouseEventKind::ScrollUp => self.handle_scroll(-WHEEL_SCROLL_ROWS),
+            MouseEventKind::Down(MouseButton::Left) => {
+                self.handle_click(position.y.saturating_sub(area.y))
             }
+            _ => ManagerAction::new(false),
         }
-
-        ma
     }
 
     fn handle_application_event(&mut self, ae: &ApplicationEvent) {
@@ -219,20 +444,57 @@ impl<T: Clone + 'static> View for TableView<T> {
                 "event text={} fuzzy={}",
                 payload.search_string, payload.fuzzy_match
             );
+            self.data_model.set_frozen_filters(&payload.frozen_filters);
             self.data_model.set_fuzzy_match(payload.fuzzy_match);
             self.data_model.update_filter(
                 self.table_rows_count,
                 payload.search_string.as_str(),
                 payload.fuzzy_match,
             );
-            if self.data_model.entries.is_none() {
-                self.table_state.select_cell(None);
-            } else {
-                self.table_state.select_cell(Some((
-                    (self.find_focus_fn)(self.data_model.entries.as_ref().unwrap()),
-                    0,
-                )));
+            match &self.data_model.entries {
+                None => self.table_state.select_cell(None),
+                Some(entries) => self
+                    .table_state
+                    .select_cell(Some(((self.find_focus_fn)(entries), 0))),
+            }
+            self.publish_completion();
+
+            let _ = self
+                .tx
+                .send(GenericEvent::ViewManagerEvent(ViewManagerEvent::Redraw));
+        } else if ae.id == COMMAND_OUTPUT_EVENT_ID
+            && let Some(payload) = &ae.payload
+            && let Some(payload) = payload.downcast_ref::<ExternalCommandOutput>()
+            && let Some(command) = self.running_command.take()
+        {
+            let title = match payload.exit_code {
+                Some(code) => format!("{} (exit code {})", command, code),
+                None => command,
+            };
+            let vb = CommandOutputView::builder(title, &payload.output, self.styles.clone());
+            self.vm.show_modal_generic(vb, None);
+            let _ = self
+                .tx
+                .send(GenericEvent::ViewManagerEvent(ViewManagerEvent::Redraw));
+        } else if ae.id == DATA_PREFETCH_EVENT
+            && let Some(payload) = &ae.payload
+            && let Some(payload) = payload.downcast_ref::<DataPrefetchPayload>()
+            && payload.objects_type == self.objects_type
+        {
+            self.data_model.prefetch(self.table_rows_count);
+        } else if ae.id == DATA_RECONCILE_EVENT
+            && let Some(payload) = &ae.payload
+            && let Some(payload) = payload.downcast_ref::<DataReconcilePayload>()
+            && payload.objects_type == self.objects_type
+            && self.data_model.reconcile(self.table_rows_count)
+        {
+            match &self.data_model.entries {
+                None => self.table_state.select_cell(None),
+                Some(entries) => self
+                    .table_state
+                    .select_cell(Some(((self.find_focus_fn)(entries), 0))),
             }
+            self.publish_completion();
 
             let _ = self
                 .tx
@@ -243,6 +505,7 @@ impl<T: Clone + 'static> View for TableView<T> {
                 (self.find_focus_fn)(self.data_model.entries.as_ref().unwrap()),
                 0,
             )));
+            self.publish_completion();
 
             let _ = self
                 .tx
@@ -252,40 +515,40 @@ impl<T: Clone + 'static> View for TableView<T> {
 }
 
 impl<T: Clone + 'static> TableView<T> {
-    /// Create a ViewBuilder for a new TableView instance.
-    ///
-    /// ### Parameters
-    /// - `column_names_fn`: A boxed closure that returns the column names based on configuration
-    /// - `column_constraints_fn`: A boxed closure that returns the column constraints based on configuration
-    /// - `list_fn`: A boxed function that lists items of type T from the store
-    /// - `rowify`: A boxed function that converts a vector of items of type T into a vector of table rows.
-    /// - `stringify`: A function that converts an item of type T into a string
-    /// - `config`: A reference to the configuration object containing color settings.
-    /// - `view_state`: A reference-counted, mutable boolean indicating the current view state.
-    /// - `delete_fn`: A boxed function that deletes an item of type T from the store
-    /// - `find_focus_fn`: A boxed function that searches a collection of T to find the index to focus on
-    ///
-    /// ### Returns
-    /// A ViewBuilder for the TableView.
-    pub fn builder(
-        vm: Rc<ViewManager>,
-        objects_type: String,
-        column_names_fn: ColumnNamesFn,
-        column_constraints_fn: ColumnConstraintsFn,
-        list_fn: Box<ListFunction<T>>,
-        rowify: RowifyFn<T>,
-        stringify: fn(&T) -> String,
-        config: Arc<Mutex<Config>>,
-        view_state: Arc<Mutex<TableViewState>>,
-        delete_fn: DeleteFn<T>,
-        editor_modal_view_builder: Option<EditorViewBuilder<T>>,
-        find_focus_fn: FindFocusFn<T>,
-    ) -> ViewBuilder {
+    /// Create a ViewBuilder for a new TableView instance, made of `params`.
+    pub fn builder(vm: Rc<ViewManager>, params: TableViewParams<T>) -> ViewBuilder {
+        let TableViewParams {
+            objects_type,
+            column_names_fn,
+            column_constraints_fn,
+            list_fn,
+            count_fn,
+            rowify,
+            stringify,
+            config,
+            view_state,
+            delete_fn,
+            restore_fn,
+            editor_modal_view_builder,
+            relocate_modal_view_builder,
+            find_focus_fn,
+            details_fn,
+            shortcut_name_fn,
+            sort_columns,
+            quick_select,
+            section_fn,
+            subtitle_fn,
+            popover_fn,
+            warm_start,
+        } = params;
         let styles = config.lock().unwrap().styles.clone();
         ViewBuilder::from(Box::new(TableView {
             vm: vm.clone(),
             tx: vm.tx(),
-            data_model: DataViewModel::new(objects_type, vm.tx(), list_fn, false),
+            objects_type: objects_type.clone(),
+            data_model: DataViewModel::new(objects_type, vm.tx(), list_fn, false)
+                .with_count_fn(count_fn)
+                .with_warm_start(warm_start),
             column_names_fn,
             column_constraints_fn,
             table_state: TableState::default(),
@@ -296,17 +559,87 @@ impl<T: Clone + 'static> TableView<T> {
             styles,
             view_state,
             delete_fn,
+            restore_fn,
             editor_modal_view_builder,
+            relocate_modal_view_builder,
             find_focus_fn,
+            details_fn,
+            shortcut_name_fn,
+            layout_cache: None,
+            sort_columns,
+            running_command: None,
+            quick_select,
+            announced_row: None,
+            section_fn,
+            subtitle_fn,
+            subtitles: vec![],
+            popover_fn,
+            display_rows: vec![],
+            display_offset: 0,
         }))
     }
 
+    /// Publish the completion of the search offered by the paths of the displayed entries
+    fn publish_completion(&self) {
+        let event = GenericEvent::ApplicationEvent(ApplicationEvent {
+            id: String::from(SEARCH_COMPLETION_EVENT),
+            payload: Some(Arc::new(SearchCompletionPayload {
+                objects_type: self.objects_type.clone(),
+                search_string: self.data_model.search_string().to_string(),
+                completion: self.data_model.completion(self.stringify),
+            })),
+        });
+        if let Err(e) = self.tx.send(event) {
+            error!("Failed to send '{}' event: {}", SEARCH_COMPLETION_EVENT, e);
+        }
+    }
+
+    /// Whether an action changes the entries or runs a command
+    fn is_forbidden_when_select_only(&self, action: Option<Action>) -> bool {
+        match action {
+            Some(
+                Action::Delete
+                | Action::Edit
+                | Action::Relocate
+                | Action::Restore
+                | Action::RunCommand
+                | Action::OpenWith,
+            ) => true,
+            // without quick select, the digits run the "open with" commands
+            Some(Action::QuickSelect) => !self.quick_select,
+            _ => false,
+        }
+    }
+
     /// Get the index of the currently selected row, if any.
     fn selected_row(&self) -> Option<usize> {
         let selected = self.table_state.selected_cell();
         selected.map(|pos| pos.0)
     }
 
+    /// Select the row at the absolute index `row`, scrolling the data so that it is visible.
+    fn restore_selected_row(&mut self, row: usize) {
+        debug!("restore_selected_row row={}", row);
+        let rows_count = self.table_rows_count as usize;
+        if rows_count == 0 {
+            return;
+        }
+        let first = (row + 1).saturating_sub(rows_count);
+        if first > 0 {
+            self.data_model.update(first, self.table_rows_count, true);
+            if self.data_model.entries.is_none() {
+                // the row is beyond the data
+                self.data_model.update(0, self.table_rows_count, true);
+            }
+        }
+        if let Some(entries) = &self.data_model.entries {
+            let selected = row
+                .saturating_sub(self.data_model.first)
+                .min(entries.len() - 1);
+            self.table_state.select_cell(Some((selected, 0)));
+        }
+    }
+
     /// Handle the chosen item and return its string representation.
     fn handle_chosen(&self) -> Option<String> {
         debug!("handle_chosen");
@@ -319,6 +652,67 @@ impl<T: Clone + 'static> TableView<T> {
         }
     }
 
+    /// Handle a click on the line `relative_y` of the table: select the clicked row, or choose it
+    /// when it is already selected, so that a double-click chooses any row.
+    fn handle_click(&mut self, relative_y: u16) -> ManagerAction {
+        // Check if the click is within the table rows area
+        if relative_y < TABLE_HEADER_LENGTH as u16 {
+            return ManagerAction::new(false);
+        }
+        let display_row = self.display_offset + (relative_y - TABLE_HEADER_LENGTH as u16) as usize;
+        // the section headers cannot be selected
+        let Some(DisplayRow::Entry(row_index)) = self.display_rows.get(display_row) else {
+            return ManagerAction::new(false);
+        };
+        let row_index = *row_index;
+        trace!("clicked row index: {}", row_index);
+
+        let Some(entries) = &self.data_model.entries else {
+            return ManagerAction::new(false);
+        };
+        if row_index >= entries.len() {
+            return ManagerAction::new(false);
+        }
+
+        // if the selected row is clicked, exit the application with the chosen item
+        if self.selected_row() == Some(row_index) {
+            debug!("send exit event");
+            let event =
+                GenericEvent::ViewManagerEvent(ViewManagerEvent::Exit(self.handle_chosen()));
+            let _ = self.tx.send(event);
+            return ManagerAction::new(false);
+        }
+
+        self.table_state.select(Some(row_index));
+        ManagerAction::new(true)
+    }
+
+    /// Scroll the table by `offset` rows, keeping the selection on the same entry while it is
+    /// visible.
+    fn handle_scroll(&mut self, offset: i64) -> ManagerAction {
+        if self.data_model.entries.is_none() {
+            debug!("No data");
+            return ManagerAction::new(false);
+        }
+        // the last entries are already displayed
+        if offset > 0 && self.data_model.length < self.table_rows_count {
+            return ManagerAction::new(false);
+        }
+        let first = self.data_model.first as i64;
+        self.data_model
+            .update_to_offset(offset, self.table_rows_count);
+        let moved = self.data_model.first as i64 - first;
+        if moved == 0 {
+            return ManagerAction::new(false);
+        }
+        if let Some(current_row) = self.selected_row() {
+            let last = self.data_model.length.saturating_sub(1) as i64;
+            let next = (current_row as i64 - moved).clamp(0, last);
+            self.table_state.select(Some(next as usize));
+        }
+        ManagerAction::new(true)
+    }
+
     /// Handle moving the selection down in the table.
     fn handle_down(&mut self, jump: bool, page: bool) {
         if self.data_model.entries.is_none() {
@@ -414,14 +808,203 @@ impl<T: Clone + 'static> TableView<T> {
         }
     }
 
+    /// Restore the selected item, if it is deleted and the table supports it.
+    fn handle_restore(&mut self) {
+        debug!("handle_restore");
+        if let Some(restore_fn) = &self.restore_fn
+            && let Some(items) = &self.data_model.entries
+            && let Some(current_row) = self.selected_row()
+            && restore_fn(&items[current_row])
+        {
+            publish_status_message(&self.vm, "restored");
+            self.data_model.reload();
+        }
+    }
+
+    /// Show the selected item in full, in a modal.
+    fn handle_details(&mut self) {
+        debug!("handle_details");
+        if let Some(items) = &self.data_model.entries
+            && let Some(current_row) = self.selected_row()
+            && let Some(item) = items.get(current_row)
+        {
+            let vb = DetailsView::builder(
+                String::from("Details"),
+                (self.details_fn)(item),
+                self.styles.clone(),
+            );
+            self.vm.show_modal_generic(vb, None);
+        }
+    }
+
+    /// Copy the selected item to the clipboard: its path, or the name of its shortcut if
+    /// `shortcut_name` is set.
+    fn handle_copy(&mut self, shortcut_name: bool) {
+        debug!("handle_copy shortcut_name={}", shortcut_name);
+        let Some(item) = self
+            .data_model
+            .entries
+            .as_ref()
+            .zip(self.selected_row())
+            .and_then(|(items, row)| items.get(row))
+        else {
+            return;
+        };
+        let text = if shortcut_name {
+            match (self.shortcut_name_fn)(item) {
+                Some(name) => name,
+                None => {
+                    publish_status_message(&self.vm, "no shortcut");
+                    return;
+                }
+            }
+        } else {
+            (self.stringify)(item)
+        };
+        self.copy(&text);
+    }
+
+    /// Copy the secondary line of the selected entry to the clipboard, e.g. the last command
+    /// run in a directory.
+    fn handle_copy_subtitle(&mut self) {
+        debug!("handle_copy_subtitle");
+        let Some(row) = self.selected_row() else {
+            return;
+        };
+        match self.subtitles.get(row).cloned().flatten() {
+            Some(text) => self.copy(&text),
+            None => publish_status_message(&self.vm, "no command"),
+        }
+    }
+
+    /// Copy a text to the clipboard, reporting the outcome on the status line.
+    fn copy(&self, text: &str) {
+        match clipboard::copy(text) {
+            Ok(()) => {
+                info!("copied '{}' to the clipboard", text);
+                publish_status_message(&self.vm, "copied");
+            }
+            Err(e) => {
+                warn!("failed to copy to the clipboard: {}", e);
+                publish_status_message(&self.vm, "copy failed");
+            }
+        }
+    }
+
+    /// The cells of the entries of the `drawn` display rows, indexed by entry: the entries
+    /// scrolled out are not built, drawing a page costing the same whatever its length.
+    fn rowify_drawn(&self, drawn: Range<usize>, widths: &[u16]) -> Vec<Option<Vec<Line<'static>>>> {
+        let entries = self.data_model.entries.as_deref().unwrap_or_default();
+        let mut cells = vec![None; entries.len()];
+        let range = entries_range(&self.display_rows[drawn]);
+        if !range.is_empty() {
+            let rows = (self.rowify)(&entries[range.clone()], widths);
+            for (cell, row) in cells[range].iter_mut().zip(rows) {
+                *cell = Some(row);
+            }
+        }
+        cells
+    }
+
+    /// The index of the first entry drawn in the table, the rows above it being scrolled out
+    fn first_drawn_entry(&self) -> usize {
+        self.display_rows
+            .iter()
+            .skip(self.display_offset)
+            .find_map(|row| match row {
+                DisplayRow::Entry(idx) => Some(*idx),
+                DisplayRow::Section(_) | DisplayRow::Subtitle(_) => None,
+            })
+            .unwrap_or_default()
+    }
+
+    /// Choose the row at `index` from the top of the table, the GUI exiting with it.
+    fn handle_quick_select(&mut self, index: usize) {
+        debug!("handle_quick_select index={}", index);
+        let row = self.first_drawn_entry() + index;
+        if let Some(items) = &self.data_model.entries
+            && let Some(item) = items.get(row)
+        {
+            self.table_state.select(Some(row));
+            let event = GenericEvent::ViewManagerEvent(ViewManagerEvent::Exit(Some((self
+                .stringify)(
+                item
+            ))));
+            let _ = self.tx.send(event);
+        }
+    }
+
+    /// The cell numbering the row at `index`, if it can be chosen with alt+digit.
+    fn quick_select_cell(&self, index: usize) -> Line<'static> {
+        match index.checked_sub(self.first_drawn_entry()) {
+            Some(position) if position < QUICK_SELECT_ROWS => {
+                Line::styled((position + 1).to_string(), self.styles.date_style)
+            }
+            _ => Line::from(""),
+        }
+    }
+
+    /// Run the "open with" command bound to the key on the selected item, if any.
+    fn handle_open_with(&mut self, key: char) {
+        let command_line = self
+            .config
+            .lock()
+            .unwrap()
+            .commands
+            .get(&key.to_string())
+            .cloned();
+        let Some(command_line) = command_line else {
+            debug!("no command bound to alt+{}", key);
+            return;
+        };
+        if let Some(items) = &self.data_model.entries
+            && let Some(current_row) = self.selected_row()
+            && let Some(item) = items.get(current_row)
+        {
+            let command = open_with::build_open_command(&command_line, &(self.stringify)(item));
+            let _ = self.tx.send(GenericEvent::ViewManagerEvent(
+                ViewManagerEvent::RunExternalCommand(command),
+            ));
+        }
+    }
+
+    fn command_prompt_callback(&mut self, prompt: &CommandPrompt) -> ManagerAction {
+        if let Some(command_line) = prompt.command() {
+            info!("running '{}' in '{}'", command_line, prompt.directory());
+            self.running_command = Some(command_line.to_string());
+            let command = open_with::build_run_command(
+                command_line,
+                prompt.directory(),
+                COMMAND_OUTPUT_EVENT_ID,
+            );
+            let _ = self.tx.send(GenericEvent::ViewManagerEvent(
+                ViewManagerEvent::RunExternalCommand(command),
+            ));
+        }
+        ManagerAction::new(true)
+    }
+
+    /// Prompt for a command to run in the selected directory, its output being shown once done.
+    fn handle_run_command(&mut self) {
+        debug!("handle_run_command");
+        if let Some(items) = &self.data_model.entries
+            && let Some(current_row) = self.selected_row()
+            && let Some(item) = items.get(current_row)
+        {
+            let vb = CommandPrompt::builder((self.stringify)(item), self.styles.clone());
+            self.vm.show_modal(vb, Some(Self::command_prompt_callback));
+        }
+    }
+
     fn modal_editor_callback(this: &mut dyn View, _: &dyn View) -> ManagerAction {
         let this = (this as &mut dyn Any).downcast_mut::<Self>().unwrap();
         this.data_model.reload();
         ManagerAction::new(true)
     }
 
-    fn handle_modal_event(&mut self) {
-        debug!("handle_modal_event");
+    /// Open the editor of the selected entry, or the picker relocating it
+    fn handle_modal_event(&mut self, relocate: bool) {
+        debug!("handle_modal_event relocate={}", relocate);
         let mut current_row: usize = 0;
         if self.data_model.entries.is_some() {
             current_row = match self.selected_row() {
@@ -432,7 +1015,12 @@ impl<T: Clone + 'static> TableView<T> {
                 }
             };
         }
-        if let Some(modal_view_builder) = &mut self.editor_modal_view_builder
+        let modal_view_builder = if relocate {
+            &self.relocate_modal_view_builder
+        } else {
+            &self.editor_modal_view_builder
+        };
+        if let Some(modal_view_builder) = modal_view_builder
             && let Some(items) = &self.data_model.entries
         {
             debug!("calling show_modal_generic");
@@ -442,6 +1030,55 @@ impl<T: Clone + 'static> TableView<T> {
         }
     }
 
+    /// Cycle through the sort orders: each sortable column ascending then descending,
+    /// then back to the default ordering.
+    fn handle_sort(&mut self) {
+        let orders: Vec<SortOrder> = std::iter::once(SortOrder::Default)
+            .chain(self.sort_columns.iter().flat_map(|(_, column)| {
+                [
+                    SortOrder::Ascending(*column),
+                    SortOrder::Descending(*column),
+                ]
+            }))
+            .collect();
+        let current = orders
+            .iter()
+            .position(|o| *o == self.data_model.sort())
+            .unwrap_or(0);
+        let next = orders[(current + 1) % orders.len()];
+        debug!("handle_sort next={:?}", next);
+        self.data_model.set_sort(self.table_rows_count, next);
+        if self.data_model.entries.is_some() {
+            self.table_state.select_cell(Some((0, 0)));
+        } else {
+            self.table_state.select_cell(None);
+        }
+    }
+
+    /// Decorate the column names with an arrow on the column the table is sorted on.
+    fn decorate_column_names(&self, column_names: Vec<String>) -> Vec<String> {
+        let (column, arrow) = match self.data_model.sort() {
+            SortOrder::Default => return column_names,
+            SortOrder::Ascending(column) => (column, "↑"),
+            SortOrder::Descending(column) => (column, "↓"),
+        };
+        let sorted_name = self
+            .sort_columns
+            .iter()
+            .find(|(_, c)| *c == column)
+            .map(|(name, _)| *name);
+        column_names
+            .into_iter()
+            .map(|name| {
+                if Some(name.as_str()) == sorted_name {
+                    format!("{} {}", name, arrow)
+                } else {
+                    name
+                }
+            })
+            .collect()
+    }
+
     fn resolve_column_widths(constraints: &[Constraint], total_width: u16) -> Vec<u16> {
         use ratatui::layout::Constraint::*;
         let mut widths = vec![0; constraints.len()];
@@ -489,43 +1126,242 @@ impl<T: Clone + 'static> TableView<T> {
         widths
     }
 
+    /// Return the column widths for the given constraints and total width, reusing the
+    /// widths computed for the previous frame when neither of them changed.
+    fn column_widths(&mut self, constraints: &[Constraint], total_width: u16) -> Vec<u16> {
+        if let Some(cache) = &self.layout_cache
+            && cache.total_width == total_width
+            && cache.constraints == constraints
+        {
+            trace!("column widths reused from the layout cache");
+            return cache.widths.clone();
+        }
+        let widths = Self::resolve_column_widths(constraints, total_width);
+        self.layout_cache = Some(ColumnLayoutCache {
+            total_width,
+            constraints: constraints.to_vec(),
+            widths: widths.clone(),
+        });
+        widths
+    }
+
     /// Render a table with some rows and columns.
+    /// The rows of the entries of the view, with the headers of their sections when they are
+    /// sorted by date
+    fn build_display_rows(&self) -> Vec<DisplayRow> {
+        let entries = self.data_model.entries.as_deref().unwrap_or_default();
+        let by_date = matches!(
+            self.data_model.sort(),
+            SortOrder::Default
+                | SortOrder::Ascending(SortColumn::Date)
+                | SortOrder::Descending(SortColumn::Date)
+        );
+        let sections: Vec<Option<String>> = match &self.section_fn {
+            Some(section_fn) if by_date => {
+                let config_lock = self.config.lock().unwrap();
+                entries
+                    .iter()
+                    .map(|entry| section_fn(&config_lock, entry))
+                    .collect()
+            }
+            _ => vec![None; entries.len()],
+        };
+        let subtitled: Vec<bool> = self.subtitles.iter().map(Option::is_some).collect();
+        display_rows(&sections, &subtitled)
+    }
+
+    /// Draw the window of the selected entry, if any, in the bottom right corner of the table.
+    fn render_popover(&self, frame: &mut Frame, area: Rect) {
+        let (Some(popover_fn), Some(row), Some(entries)) = (
+            &self.popover_fn,
+            self.selected_row(),
+            self.data_model.entries.as_ref(),
+        ) else {
+            return;
+        };
+        let Some(entry) = entries.get(row) else {
+            return;
+        };
+        let Some((title, lines)) = popover_fn(&self.config.lock().unwrap(), entry) else {
+            return;
+        };
+        // the borders take 2 lines and 2 columns
+        let width = (lines
+            .iter()
+            .chain([&title])
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or_default() as u16
+            + 4)
+        .min(POPOVER_WIDTH)
+        .min(area.width);
+        let height = (lines.len() as u16 + 2).min(area.height);
+        let popover_area = Rect {
+            x: area.x + area.width - width,
+            y: area.y + area.height - height,
+            width,
+            height,
+        };
+        let mut block = Block::default()
+            .title(Span::styled(
+                format!(" {} ", title),
+                self.styles.title_style,
+            ))
+            .borders(Borders::ALL);
+        if let Some(border_color) = self.styles.border_color {
+            block = block.border_style(Style::default().fg(border_color));
+        }
+        let lines: Vec<Line> = lines
+            .into_iter()
+            .map(|line| Line::from(Span::styled(line, self.styles.text_style)))
+            .collect();
+        frame.render_widget(Clear, popover_area);
+        if let Some(bg_color) = &self.styles.background_color {
+            frame.render_widget(
+                Paragraph::new("").style(Style::default().bg(*bg_color)),
+                popover_area,
+            );
+        }
+        frame.render_widget(Paragraph::new(lines).block(block), popover_area);
+    }
+
+    /// The secondary lines of the entries of the view
+    fn build_subtitles(&self) -> Vec<Option<String>> {
+        let entries = self.data_model.entries.as_deref().unwrap_or_default();
+        match &self.subtitle_fn {
+            Some(subtitle_fn) => subtitle_fn(&self.config.lock().unwrap(), entries),
+            None => vec![None; entries.len()],
+        }
+    }
+
+    /// Render the entries as plain lines for the screen readers, without styles, scrollbar or
+    /// highlighting: the selected row is announced by its prefix, and by the bell of the
+    /// terminal when it moves if `bell` is set.
+    fn render_lines(
+        &mut self,
+        frame: &mut Frame,
+        area: Rect,
+        column_constraints: &[Constraint],
+        column_names: Vec<String>,
+        bell: bool,
+    ) {
+        if self.selected_row().is_none() && self.data_model.length > 0 {
+            self.table_state.select_cell(Some((
+                (self.find_focus_fn)(self.data_model.entries.as_ref().unwrap()),
+                0,
+            )));
+        }
+        let selected = self.selected_row();
+
+        let width = area
+            .width
+            .saturating_sub(LINEAR_SELECTION_PREFIX.len() as u16);
+        let actual_width = self.column_widths(column_constraints, width);
+
+        self.subtitles = self.build_subtitles();
+        self.display_rows = self.build_display_rows();
+        // the rows are scrolled so that the selected one is visible
+        let visible = (area.height as usize).saturating_sub(TABLE_HEADER_LENGTH);
+        let selected_display_row = self
+            .display_rows
+            .iter()
+            .position(|row| selected.is_some_and(|selected| *row == DisplayRow::Entry(selected)))
+            .unwrap_or_default();
+        let drawn = visible_rows(self.display_rows.len(), Some(selected_display_row), visible);
+        self.display_offset = drawn.start;
+        let cells = self.rowify_drawn(drawn.clone(), &actual_width);
+
+        let mut lines = vec![Line::from(format!(
+            "{}{}",
+            LINEAR_PREFIX,
+            column_names.join(LINEAR_CELL_SEPARATOR)
+        ))];
+        lines.extend(self.display_rows[drawn].iter().map(|row| match row {
+            DisplayRow::Section(section) => Line::from(format!("{}{}", LINEAR_PREFIX, section)),
+            DisplayRow::Entry(row) => Line::from(linear_line(
+                cells[*row].as_deref().unwrap_or_default(),
+                selected == Some(*row),
+            )),
+            DisplayRow::Subtitle(row) => Line::from(format!(
+                "{}{}{}",
+                LINEAR_PREFIX,
+                SUBTITLE_MARK,
+                self.subtitles[*row].as_deref().unwrap_or_default()
+            )),
+        }));
+        frame.render_widget(Paragraph::new(lines), area);
+
+        let announced_row = selected.map(|row| self.data_model.first + row);
+        if bell
+            && announced_row.is_some()
+            && self.announced_row.is_some()
+            && announced_row != self.announced_row
+        {
+            ring_bell();
+        }
+        self.announced_row = announced_row;
+        if let Some(row) = announced_row {
+            self.view_state
+                .lock()
+                .unwrap()
+                .selected_rows
+                .insert(self.objects_type.clone(), row);
+        }
+    }
+
     pub fn render_table(&mut self, frame: &mut Frame, area: Rect) {
         debug!(
             "render_table data_first={} data_length={}",
             self.data_model.first, self.data_model.length
         );
 
+        // the scrollbar takes the last column when the entries do not fit in the table
+        let total = self
+            .data_model
+            .total()
+            .filter(|total| *total > self.table_rows_count as usize);
+        let (area, scrollbar_area) = match total {
+            Some(_) => {
+                let [table, scrollbar] =
+                    Layout::horizontal([Constraint::Fill(1), Constraint::Length(1)]).areas(area);
+                (table, Some(scrollbar))
+            }
+            None => (area, None),
+        };
+
         let config_lock = self.config.lock().unwrap();
-        let column_constraints = (self.column_constraints_fn)(&config_lock);
+        let mut column_constraints = (self.column_constraints_fn)(&config_lock);
         let column_names = (self.column_names_fn)(&config_lock);
+        let (screen_reader, screen_reader_bell) =
+            (config_lock.screen_reader, config_lock.screen_reader_bell);
         drop(config_lock);
+        if screen_reader {
+            self.render_lines(
+                frame,
+                area,
+                &column_constraints,
+                column_names,
+                screen_reader_bell,
+            );
+            return;
+        }
+        let mut column_names = self.decorate_column_names(column_names);
 
-        let actual_width = Self::resolve_column_widths(
+        let quick_select_width = if self.quick_select {
+            QUICK_SELECT_COLUMN_WIDTH + TABLE_COLUMN_SPACING
+        } else {
+            0
+        };
+        let actual_width = self.column_widths(
             &column_constraints,
-            area.width - TABLE_HIGHLIGHT_SYMBOL.len() as u16 - TABLE_COLUMN_SPACING * 2,
+            area.width.saturating_sub(
+                self.styles.highlight_symbol.len() as u16
+                    + TABLE_COLUMN_SPACING * 2
+                    + quick_select_width,
+            ),
         );
         debug!("area widht={} col_width={:?}", area.width, actual_width);
 
-        let rows: Vec<Row> = self
-            .data_model
-            .entries
-            .as_ref()
-            .map_or(vec![], |entries| (self.rowify)(entries, &actual_width));
-
-        let table = Table::new(rows, column_constraints)
-            .header(
-                Row::new(column_names).style(
-                    Style::new()
-                        .bg(self.styles.header_bg_color.unwrap())
-                        .fg(self.styles.header_fg_color.unwrap())
-                        .bold(),
-                ),
-            )
-            .column_spacing(TABLE_COLUMN_SPACING)
-            .row_highlight_style(Style::new().bg(self.styles.highlight_color.unwrap()).bold())
-            .highlight_symbol(TABLE_HIGHLIGHT_SYMBOL);
-
         if self.selected_row().is_none() && self.data_model.length > 0 {
             self.table_state.select_cell(Some((
                 (self.find_focus_fn)(self.data_model.entries.as_ref().unwrap()),
@@ -533,7 +1369,97 @@ impl<T: Clone + 'static> TableView<T> {
             )));
             debug!("No row selected: select 0")
         }
+        self.subtitles = self.build_subtitles();
+        self.display_rows = self.build_display_rows();
+        // the selection is kept on the entries, the section headers shifting their rows
+        let selected_display_row = self.selected_row().and_then(|selected| {
+            self.display_rows
+                .iter()
+                .position(|row| *row == DisplayRow::Entry(selected))
+        });
+        // only the rows scrolled into the table are built
+        let drawn = visible_rows(
+            self.display_rows.len(),
+            selected_display_row,
+            (area.height as usize).saturating_sub(TABLE_HEADER_LENGTH),
+        );
+        let mut cells = self.rowify_drawn(drawn.clone(), &actual_width);
+        let rows: Vec<Row> = self.display_rows[drawn.clone()]
+            .iter()
+            .map(|row| match row {
+                DisplayRow::Section(section) => {
+                    let mut cells = vec![Line::from(section.clone())];
+                    if self.quick_select {
+                        cells.insert(0, Line::default());
+                    }
+                    Row::new(cells).style(self.styles.text_em_style.bold())
+                }
+                DisplayRow::Entry(index) => {
+                    let mut cells = cells[*index].take().unwrap_or_default();
+                    if self.quick_select {
+                        cells.insert(0, self.quick_select_cell(*index));
+                    }
+                    Row::new(cells)
+                }
+                DisplayRow::Subtitle(index) => {
+                    // drawn in the widest column, usually the one of the paths
+                    let widest = (0..actual_width.len())
+                        .max_by_key(|column| actual_width[*column])
+                        .unwrap_or_default();
+                    let mut cells = vec![Line::default(); actual_width.len()];
+                    cells[widest] = Line::from(format!(
+                        "{}{}",
+                        SUBTITLE_MARK,
+                        self.subtitles[*index].as_deref().unwrap_or_default()
+                    ));
+                    if self.quick_select {
+                        cells.insert(0, Line::default());
+                    }
+                    Row::new(cells).style(Style::new().dim())
+                }
+            })
+            .collect();
+        if self.quick_select {
+            column_constraints.insert(0, Constraint::Length(QUICK_SELECT_COLUMN_WIDTH));
+            column_names.insert(0, String::new());
+        }
 
-        frame.render_stateful_widget(table, area, &mut self.table_state);
+        let table = Table::new(rows, column_constraints)
+            .header(Row::new(column_names).style(self.styles.header_style().bold()))
+            .column_spacing(TABLE_COLUMN_SPACING)
+            .row_highlight_style(self.styles.highlight(Style::new().bold()))
+            .highlight_symbol(self.styles.highlight_symbol);
+
+        let mut display_state = TableState::default()
+            .with_selected(selected_display_row.map(|selected| selected - drawn.start));
+        frame.render_stateful_widget(table, area, &mut display_state);
+        self.display_offset = drawn.start + display_state.offset();
+
+        if let (Some(total), Some(scrollbar_area)) = (total, scrollbar_area) {
+            let mut scrollbar_state = ScrollbarState::new(total)
+                .position(self.data_model.first)
+                .viewport_content_length(self.table_rows_count as usize);
+            let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                .begin_symbol(None)
+                .end_symbol(None)
+                .thumb_style(Style::new().fg(self.styles.highlight_color.unwrap()));
+            let scrollbar_area = Rect {
+                y: scrollbar_area.y + TABLE_HEADER_LENGTH as u16,
+                height: scrollbar_area
+                    .height
+                    .saturating_sub(TABLE_HEADER_LENGTH as u16),
+                ..scrollbar_area
+            };
+            frame.render_stateful_widget(scrollbar, scrollbar_area, &mut scrollbar_state);
+        }
+        self.render_popover(frame, area);
+
+        if let Some(row) = self.selected_row() {
+            self.view_state
+                .lock()
+                .unwrap()
+                .selected_rows
+                .insert(self.objects_type.clone(), self.data_model.first + row);
+        }
     }
 }
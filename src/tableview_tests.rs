@@ -0,0 +1,90 @@
+use ratatui::text::{Line, Span};
+
+use super::{DisplayRow, display_rows, entries_range, linear_line, visible_rows};
+
+#[test]
+fn test_linear_line() {
+    let cells = vec![
+        Line::from("2024-01-02 10:00   "),
+        Line::from(vec![Span::raw("~"), Span::raw("/work/api")]),
+        Line::from(" "),
+        Line::from("the api"),
+    ];
+    // the empty cells are left out
+    assert_eq!(
+        linear_line(&cells, false),
+        "  2024-01-02 10:00  ~/work/api  the api"
+    );
+    // the selection is announced
+    assert_eq!(
+        linear_line(&cells, true),
+        "> 2024-01-02 10:00  ~/work/api  the api"
+    );
+}
+
+#[test]
+fn test_display_rows() {
+    let today = Some(String::from("Today"));
+    let older = Some(String::from("Older"));
+    // a header before the first entry of each section, none for the entries without section
+    assert_eq!(
+        display_rows(&[None, today.clone(), today, older], &[]),
+        vec![
+            DisplayRow::Entry(0),
+            DisplayRow::Section(String::from("Today")),
+            DisplayRow::Entry(1),
+            DisplayRow::Entry(2),
+            DisplayRow::Section(String::from("Older")),
+            DisplayRow::Entry(3),
+        ]
+    );
+    assert_eq!(
+        display_rows(&[None, None], &[]),
+        vec![DisplayRow::Entry(0), DisplayRow::Entry(1)]
+    );
+    // a secondary row after each entry having one
+    assert_eq!(
+        display_rows(&[None, None], &[true, false]),
+        vec![
+            DisplayRow::Entry(0),
+            DisplayRow::Subtitle(0),
+            DisplayRow::Entry(1)
+        ]
+    );
+}
+
+#[test]
+fn test_visible_rows() {
+    // the first rows until the selection goes below them
+    assert_eq!(visible_rows(1000, None, 20), 0..20);
+    assert_eq!(visible_rows(1000, Some(19), 20), 0..20);
+    assert_eq!(visible_rows(1000, Some(20), 20), 1..21);
+    assert_eq!(visible_rows(1000, Some(999), 20), 980..1000);
+    // fewer rows than lines
+    assert_eq!(visible_rows(5, Some(4), 20), 0..5);
+    assert_eq!(visible_rows(0, None, 20), 0..0);
+    assert_eq!(visible_rows(5, Some(4), 0), 5..5);
+}
+
+#[test]
+fn test_entries_range() {
+    let rows = display_rows(
+        &[
+            None,
+            Some(String::from("Today")),
+            Some(String::from("Today")),
+            Some(String::from("Older")),
+        ],
+        &[false, true],
+    );
+    // only the entries of the rows drawn are built
+    let drawn = visible_rows(rows.len(), Some(4), 2);
+    assert_eq!(
+        rows[drawn.clone()],
+        [DisplayRow::Subtitle(1), DisplayRow::Entry(2)]
+    );
+    assert_eq!(entries_range(&rows[drawn]), 2..3);
+    assert_eq!(entries_range(&rows), 0..4);
+    assert_eq!(entries_range(&rows[5..6]), 0..0);
+    assert_eq!(entries_range(&[]), 0..0);
+}
@@ -1,6 +1,12 @@
 use ratatui::style::{Color, Modifier, Style};
 use serde::{Deserialize, Serialize};
 
+use crate::tui::notification::NotificationStyles;
+
+#[cfg(test)]
+#[path = "theme_tests.rs"]
+mod theme_tests;
+
 const DEFAULT_TITLE: fn() -> Option<String> = || Some(String::from("#1d5cba"));
 const DEFAULT_BACKGROUND_COLOR: fn() -> Option<String> = || Some(String::from("#ffffff"));
 const DEFAULT_LEFT_BACKGROUND_COLOR: fn() -> Option<String> = || None;
@@ -21,7 +27,15 @@ const DEFAULT_FREE_TEXT_AREA_BG: fn() -> Option<String> = || Some(String::from("
 
 const DEFAULT_HOME_TILD: fn() -> Option<String> = || Some(String::from("#888888"));
 
+const DEFAULT_COLOR_HEAT: fn() -> Option<String> = || Some(String::from("#e8743b"));
+
 const DEFAULT_NONE: fn() -> Option<String> = || None;
+
+/// The symbol before the selected row of the tables
+const HIGHLIGHT_SYMBOL: &str = "> ";
+
+/// The larger symbol before the selected row of the tables in the accessible modes
+const ACCESSIBLE_HIGHLIGHT_SYMBOL: &str = ">> ";
 const DEFAULT_BOOL_NONE: fn() -> Option<bool> = || None;
 
 /// Represents the color configuration for various UI elements.
@@ -119,6 +133,9 @@ pub struct Theme {
 
     #[serde(default = "DEFAULT_BOOL_NONE")]
     pub home_tilde_italic: Option<bool>,
+
+    #[serde(default = "DEFAULT_NONE")]
+    pub heat: Option<String>,
 }
 
 impl Default for Theme {
@@ -155,6 +172,7 @@ impl Default for Theme {
             home_tilde: DEFAULT_HOME_TILD(),
             home_tilde_bold: DEFAULT_BOOL_NONE(),
             home_tilde_italic: DEFAULT_BOOL_NONE(),
+            heat: DEFAULT_COLOR_HEAT(),
         }
     }
 }
@@ -286,6 +304,11 @@ impl Theme {
                 .home_tilde_italic
                 .or(theme.home_tilde_italic)
                 .or(DEFAULT_BOOL_NONE()),
+            heat: self
+                .heat
+                .clone()
+                .or(theme.heat.clone())
+                .or(DEFAULT_COLOR_HEAT()),
         }
     }
 }
@@ -307,6 +330,16 @@ pub struct ThemeStyles {
     pub description_style: Style,
     pub free_text_area_bg_color: Option<Color>,
     pub home_tilde_style: Style,
+    /// The visit frequency bars of the history
+    pub heat_style: Style,
+    /// Added to the headers and bars, see `header_style`
+    pub header_modifier: Modifier,
+    /// Added to the highlighted entries, see `highlight`
+    pub highlight_modifier: Modifier,
+    /// The symbol before the selected row of the tables
+    pub highlight_symbol: &'static str,
+    /// Whether the styles are the ones of an accessible mode, rendered without colors
+    pub accessible: bool,
 }
 
 impl ThemeStyles {
@@ -367,6 +400,101 @@ impl ThemeStyles {
                 theme.home_tilde_bold,
                 theme.home_tilde_italic,
             ),
+            heat_style: Self::build_style(theme.heat.as_ref(), None, None),
+            header_modifier: Modifier::empty(),
+            highlight_modifier: Modifier::empty(),
+            highlight_symbol: HIGHLIGHT_SYMBOL,
+            accessible: false,
+        }
+    }
+
+    /// The styles of the accessible modes, bypassing the themes: the colors of the terminal
+    /// are kept, the headers and the highlighted entries being in reverse video.
+    /// `high_contrast` makes the whole text bold, else only the emphasized text is.
+    pub fn accessible(high_contrast: bool) -> ThemeStyles {
+        let text_style = if high_contrast {
+            Style::new().add_modifier(Modifier::BOLD)
+        } else {
+            Style::new()
+        };
+        let em_style = Style::new().add_modifier(Modifier::BOLD);
+        let bold = if high_contrast {
+            Modifier::BOLD
+        } else {
+            Modifier::empty()
+        };
+        ThemeStyles {
+            title_style: em_style,
+            background_color: Some(Color::Reset),
+            left_background_color: None,
+            border_color: Some(Color::Reset),
+            text_style,
+            text_em_style: em_style,
+            date_style: text_style,
+            path_style: text_style,
+            highlight_color: Some(Color::Reset),
+            shortcut_name_style: em_style,
+            header_fg_color: Some(Color::Reset),
+            header_bg_color: Some(Color::Reset),
+            description_style: text_style,
+            free_text_area_bg_color: Some(Color::Reset),
+            home_tilde_style: text_style,
+            heat_style: text_style,
+            header_modifier: Modifier::REVERSED | bold,
+            highlight_modifier: Modifier::REVERSED | Modifier::BOLD,
+            highlight_symbol: ACCESSIBLE_HIGHLIGHT_SYMBOL,
+            accessible: true,
+        }
+    }
+
+    /// The style of the headers and of the bars
+    pub fn header_style(&self) -> Style {
+        Style::default()
+            .bg(self.header_bg_color.unwrap())
+            .fg(self.header_fg_color.unwrap())
+            .add_modifier(self.header_modifier)
+    }
+
+    /// Highlight `style`, e.g. for the selected entry of a list.
+    /// A style already in reverse video, e.g. a header, is highlighted by reverting it back.
+    pub fn highlight(&self, style: Style) -> Style {
+        let style = match self.highlight_color {
+            Some(highlight_color) => style.bg(highlight_color),
+            None => style,
+        };
+        let reversed = style
+            .add_modifier
+            .intersection(self.highlight_modifier & Modifier::REVERSED);
+        style
+            .add_modifier(self.highlight_modifier - reversed)
+            .remove_modifier(reversed)
+    }
+
+    /// The style of a button of `color`, e.g. green for a confirmation, filled when `selected`.
+    /// The accessible modes drop the color, the selected button being in reverse video.
+    pub fn button_style(&self, color: Color, selected: bool) -> Style {
+        match (self.accessible, selected) {
+            (true, true) => Style::default().add_modifier(Modifier::REVERSED | Modifier::BOLD),
+            (true, false) => Style::default(),
+            (false, true) => Style::default()
+                .fg(Color::Black)
+                .bg(color)
+                .add_modifier(Modifier::BOLD),
+            (false, false) => Style::default().fg(color),
+        }
+    }
+
+    /// The styles of the notifications. The accessible modes render them in reverse video
+    /// instead of colors, the warnings and the errors also in bold.
+    pub fn notification_styles(&self) -> NotificationStyles {
+        if !self.accessible {
+            return NotificationStyles::default();
+        }
+        let reversed = Style::default().add_modifier(Modifier::REVERSED);
+        NotificationStyles {
+            info: reversed,
+            warning: reversed.add_modifier(Modifier::BOLD),
+            error: reversed.add_modifier(Modifier::BOLD),
         }
     }
 }
@@ -0,0 +1,102 @@
+use ratatui::style::{Color, Modifier, Style};
+
+use crate::{config::Accessibility, theme::ThemeStyles};
+
+#[test]
+fn test_accessibility_resolve() {
+    assert_eq!(Accessibility::None.resolve(None), Accessibility::None);
+    assert_eq!(Accessibility::None.resolve(Some("")), Accessibility::None);
+    assert_eq!(
+        Accessibility::None.resolve(Some("1")),
+        Accessibility::Monochrome
+    );
+    // a mode set in the configuration wins
+    assert_eq!(
+        Accessibility::HighContrast.resolve(Some("1")),
+        Accessibility::HighContrast
+    );
+}
+
+#[test]
+fn test_highlight() {
+    let styles = ThemeStyles::default();
+    assert_eq!(
+        styles.highlight(Style::new()),
+        Style::new().bg(styles.highlight_color.unwrap())
+    );
+
+    let styles = ThemeStyles::accessible(false);
+    let highlighted = styles.highlight(Style::new());
+    assert!(highlighted.add_modifier.contains(Modifier::REVERSED));
+    assert!(highlighted.add_modifier.contains(Modifier::BOLD));
+    assert_eq!(highlighted.bg, Some(Color::Reset));
+
+    // a header in reverse video is highlighted by reverting it back
+    let highlighted = styles.highlight(styles.header_style());
+    assert!(!highlighted.add_modifier.contains(Modifier::REVERSED));
+    assert!(highlighted.sub_modifier.contains(Modifier::REVERSED));
+    assert!(highlighted.add_modifier.contains(Modifier::BOLD));
+}
+
+#[test]
+fn test_accessible() {
+    let monochrome = ThemeStyles::accessible(false);
+    assert_eq!(monochrome.path_style, Style::new());
+    assert_eq!(
+        monochrome.shortcut_name_style,
+        Style::new().add_modifier(Modifier::BOLD)
+    );
+    assert_eq!(monochrome.header_modifier, Modifier::REVERSED);
+
+    let high_contrast = ThemeStyles::accessible(true);
+    assert_eq!(
+        high_contrast.path_style,
+        Style::new().add_modifier(Modifier::BOLD)
+    );
+    assert_eq!(
+        high_contrast.header_modifier,
+        Modifier::REVERSED | Modifier::BOLD
+    );
+    assert_ne!(
+        high_contrast.highlight_symbol,
+        ThemeStyles::default().highlight_symbol
+    );
+}
+
+#[test]
+fn test_accessible_colors() {
+    let styles = ThemeStyles::default();
+    assert_eq!(
+        styles.button_style(Color::Green, false),
+        Style::new().fg(Color::Green)
+    );
+    assert_eq!(styles.button_style(Color::Red, true).bg, Some(Color::Red));
+    assert_eq!(styles.notification_styles().error.bg, Some(Color::Red));
+
+    // no color in the accessible modes, the selection and the notifications being reversed
+    for styles in [
+        ThemeStyles::accessible(false),
+        ThemeStyles::accessible(true),
+    ] {
+        assert_eq!(styles.button_style(Color::Green, false), Style::new());
+        assert_eq!(
+            styles.button_style(Color::Red, true),
+            Style::new().add_modifier(Modifier::REVERSED | Modifier::BOLD)
+        );
+        let notification_styles = styles.notification_styles();
+        for style in [
+            notification_styles.info,
+            notification_styles.warning,
+            notification_styles.error,
+        ] {
+            assert_eq!((style.fg, style.bg), (None, None));
+            assert!(style.add_modifier.contains(Modifier::REVERSED));
+        }
+        assert!(
+            notification_styles
+                .error
+                .add_modifier
+                .contains(Modifier::BOLD)
+        );
+    }
+}
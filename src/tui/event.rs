@@ -2,42 +2,73 @@
 
 use std::{any::Any, sync::Arc};
 
+use crossterm::event::KeyEvent;
+
+use crate::tui::notification::Notification;
+
 #[cfg(test)]
 #[path = "event_tests.rs"]
 mod event_tests;
 
 type Payload = dyn Any + Send + Sync;
 
+/// An external program to run in the foreground, the terminal being handed over to it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExternalCommand {
+    pub program: String,
+    pub args: Vec<String>,
+    /// The working directory of the program, the current one if not set
+    pub cwd: Option<String>,
+    /// If set, the output of the program is captured and published as an application event
+    /// with this id and an `ExternalCommandOutput` payload
+    pub output_event_id: Option<String>,
+}
+
+/// The captured output of an external command
+#[derive(Debug, Clone)]
+pub struct ExternalCommandOutput {
+    /// The exit code of the command, None if it could not be run or was killed by a signal
+    pub exit_code: Option<i32>,
+    /// The standard output followed by the standard error, or the reason why the command failed
+    pub output: String,
+}
+
 #[derive(Debug, Clone)]
-pub(crate) enum ViewManagerEvent {
+pub enum ViewManagerEvent {
     Redraw,
     Resize,
     Exit(Option<String>),
+    /// Suspend the UI, run the command and restore the UI once it is done
+    RunExternalCommand(ExternalCommand),
+    /// Display a transient message
+    Notify(Notification),
+    /// Handle a key as if it was pressed, e.g. to run the action chosen in a menu
+    Key(KeyEvent),
 }
 
 #[derive(Debug, Clone)]
-pub(crate) struct ApplicationEvent {
-    pub(crate) id: String,
-    pub(crate) payload: Option<Arc<Payload>>,
+pub struct ApplicationEvent {
+    pub id: String,
+    pub payload: Option<Arc<Payload>>,
 }
 
-pub(crate) struct ApplicationEventBuilder {
+pub struct ApplicationEventBuilder {
     id: String,
     payload: Option<Arc<Payload>>,
 }
 
 impl ApplicationEventBuilder {
-    pub(crate) fn new(id: &str) -> ApplicationEventBuilder {
+    pub fn new(id: &str) -> ApplicationEventBuilder {
         ApplicationEventBuilder {
             id: id.to_string(),
             payload: None,
         }
     }
-    pub(crate) fn with_payload(mut self, payload: Arc<Payload>) -> ApplicationEventBuilder {
+    pub fn with_payload(mut self, payload: Arc<Payload>) -> ApplicationEventBuilder {
         self.payload = Some(payload);
         self
     }
-    pub(crate) fn build(self) -> ApplicationEvent {
+    pub fn build(self) -> ApplicationEvent {
         ApplicationEvent {
             id: self.id,
             payload: self.payload,
@@ -46,7 +77,7 @@ impl ApplicationEventBuilder {
 }
 
 #[derive(Debug, Clone)]
-pub(crate) enum GenericEvent {
+pub enum GenericEvent {
     ViewManagerEvent(ViewManagerEvent),
     ApplicationEvent(ApplicationEvent),
 }
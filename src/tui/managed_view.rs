@@ -5,8 +5,8 @@ use std::{
     sync::atomic::{AtomicUsize, Ordering},
 };
 
-use log::trace;
 use ratatui::{layout::Rect, widgets::Clear};
+use tracing::trace;
 
 use crate::tui::view::View;
 
@@ -1,12 +1,14 @@
-pub(crate) use crate::tui::{
+pub use crate::tui::{
     event::GenericEvent,
+    notification::{Notification, notify},
     view::{EventCaptured, ManagerAction, View},
     view_builder::ViewBuilder,
     view_manager::ViewManager,
 };
 
-pub(crate) mod event;
-pub(crate) mod managed_view;
-pub(crate) mod view;
-pub(crate) mod view_builder;
-pub(crate) mod view_manager;
+pub mod event;
+pub mod managed_view;
+pub mod notification;
+pub mod view;
+pub mod view_builder;
+pub mod view_manager;
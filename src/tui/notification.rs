@@ -0,0 +1,208 @@
+use std::time::{Duration, Instant};
+
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    widgets::{Clear, Paragraph},
+};
+use tokio::sync::broadcast;
+use tracing::error;
+use unicode_width::UnicodeWidthStr;
+
+use crate::tui::{
+    View,
+    event::{GenericEvent, ViewManagerEvent},
+};
+
+#[cfg(test)]
+#[path = "notification_tests.rs"]
+mod notification_tests;
+
+/// How long an information stays displayed
+const INFO_DURATION: Duration = Duration::from_secs(3);
+/// How long a warning or an error stays displayed
+const ERROR_DURATION: Duration = Duration::from_secs(5);
+
+/// The number of notifications displayed at once, the oldest ones being dropped
+const MAX_NOTIFICATIONS: usize = 4;
+
+/// The severity of a notification, setting its colors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationLevel {
+    Info,
+    Warning,
+    Error,
+}
+
+/// A transient message displayed in the bottom right corner of the terminal
+#[derive(Debug, Clone, PartialEq)]
+pub struct Notification {
+    pub message: String,
+    pub level: NotificationLevel,
+    /// How long the notification stays displayed
+    pub duration: Duration,
+}
+
+impl Notification {
+    pub fn info(message: impl Into<String>) -> Notification {
+        Notification {
+            message: message.into(),
+            level: NotificationLevel::Info,
+            duration: INFO_DURATION,
+        }
+    }
+
+    pub fn warning(message: impl Into<String>) -> Notification {
+        Notification {
+            message: message.into(),
+            level: NotificationLevel::Warning,
+            duration: ERROR_DURATION,
+        }
+    }
+
+    pub fn error(message: impl Into<String>) -> Notification {
+        Notification {
+            message: message.into(),
+            level: NotificationLevel::Error,
+            duration: ERROR_DURATION,
+        }
+    }
+
+    pub fn with_duration(mut self, duration: Duration) -> Notification {
+        self.duration = duration;
+        self
+    }
+}
+
+/// The styles of the notifications by level, see `ThemeStyles::notification_styles`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NotificationStyles {
+    pub info: Style,
+    pub warning: Style,
+    pub error: Style,
+}
+
+impl NotificationStyles {
+    fn style(&self, level: &NotificationLevel) -> Style {
+        match level {
+            NotificationLevel::Info => self.info,
+            NotificationLevel::Warning => self.warning,
+            NotificationLevel::Error => self.error,
+        }
+    }
+}
+
+impl Default for NotificationStyles {
+    fn default() -> Self {
+        NotificationStyles {
+            info: Style::default().fg(Color::Black).bg(Color::Gray),
+            warning: Style::default().fg(Color::Black).bg(Color::Yellow),
+            error: Style::default().fg(Color::White).bg(Color::Red),
+        }
+    }
+}
+
+/// Posts a notification, displayed by the view manager until its duration elapses, or until
+/// it is dismissed in the reduced-motion mode.
+pub fn notify(tx: &broadcast::Sender<GenericEvent>, notification: Notification) {
+    let event = GenericEvent::ViewManagerEvent(ViewManagerEvent::Notify(notification));
+    if let Err(e) = tx.send(event) {
+        error!("Failed to send a notification: {}", e);
+    }
+}
+
+/// The notifications being displayed, stacked from the bottom right corner of its area, the
+/// most recent one at the bottom.
+#[derive(Default)]
+pub struct NotificationView {
+    /// The notifications with their expiration, none when they are kept until dismissed, the
+    /// most recent last
+    notifications: Vec<(Notification, Option<Instant>)>,
+    /// Whether the notifications are kept until dismissed, rather than disappearing after
+    /// their duration
+    persistent: bool,
+    styles: NotificationStyles,
+}
+
+impl NotificationView {
+    pub fn new() -> NotificationView { NotificationView::default() }
+
+    /// Keeps the notifications displayed until they are dismissed, the reduced-motion mode.
+    pub fn set_persistent(&mut self, persistent: bool) { self.persistent = persistent; }
+
+    pub fn persistent(&self) -> bool { self.persistent }
+
+    pub fn set_styles(&mut self, styles: NotificationStyles) { self.styles = styles; }
+
+    /// Displays a notification from `now` on.
+    pub fn push(&mut self, notification: Notification, now: Instant) {
+        let expiration = (!self.persistent).then(|| now + notification.duration);
+        self.notifications.push((notification, expiration));
+        if self.notifications.len() > MAX_NOTIFICATIONS {
+            self.notifications.remove(0);
+        }
+    }
+
+    /// Drops the notifications kept until dismissed, returning whether some were displayed.
+    pub fn dismiss(&mut self) -> bool {
+        if !self.persistent || self.notifications.is_empty() {
+            return false;
+        }
+        self.notifications.clear();
+        true
+    }
+
+    /// Drops the notifications expired at `now`, returning the ones still displayed.
+    pub fn visible(&mut self, now: Instant) -> Vec<&Notification> {
+        self.notifications
+            .retain(|(_, expiration)| expiration.is_none_or(|expiration| now < expiration));
+        self.notifications
+            .iter()
+            .map(|(notification, _)| notification)
+            .collect()
+    }
+
+    /// The areas of the notifications of `widths` displayed in `area`, stacked on its last rows,
+    /// the area being at least as high as the number of notifications
+    fn areas(area: Rect, widths: &[usize]) -> Vec<Rect> {
+        let count = widths.len() as u16;
+        widths
+            .iter()
+            .enumerate()
+            .map(|(idx, width)| {
+                let width = (*width as u16).min(area.width);
+                let y = area.bottom().saturating_sub(count - idx as u16);
+                Rect::new(area.right() - width, y, width, 1)
+            })
+            .collect()
+    }
+}
+
+impl View for NotificationView {
+    fn draw(&mut self, frame: &mut ratatui::Frame, area: Rect, _active: bool) {
+        let styles = self.styles;
+        let notifications = self.visible(Instant::now());
+        if notifications.is_empty() || area.height == 0 {
+            return;
+        }
+        // the notifications do not cover more than the area
+        let notifications =
+            &notifications[notifications.len().saturating_sub(area.height as usize)..];
+        let texts: Vec<String> = notifications
+            .iter()
+            .map(|notification| format!(" {} ", notification.message))
+            .collect();
+        let widths: Vec<usize> = texts.iter().map(|text| text.width()).collect();
+        for ((notification, text), rect) in notifications
+            .iter()
+            .zip(texts)
+            .zip(Self::areas(area, &widths))
+        {
+            frame.render_widget(Clear, rect);
+            frame.render_widget(
+                Paragraph::new(text).style(styles.style(&notification.level)),
+                rect,
+            );
+        }
+    }
+}
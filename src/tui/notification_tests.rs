@@ -0,0 +1,81 @@
+use super::*;
+
+#[test]
+fn test_expiration() {
+    let now = Instant::now();
+    let mut view = NotificationView::new();
+    view.push(Notification::info("saved"), now);
+    view.push(
+        Notification::error("failed").with_duration(Duration::from_secs(10)),
+        now,
+    );
+    assert_eq!(view.visible(now).len(), 2);
+
+    let later = now + INFO_DURATION;
+    let visible = view.visible(later);
+    assert_eq!(visible.len(), 1);
+    assert_eq!(visible[0].level, NotificationLevel::Error);
+    assert!(view.visible(now + Duration::from_secs(10)).is_empty());
+}
+
+#[test]
+fn test_persistent() {
+    let now = Instant::now();
+    let mut view = NotificationView::new();
+    view.set_persistent(true);
+    view.push(Notification::info("saved"), now);
+    view.push(Notification::error("failed"), now);
+    // no timer removes them
+    assert_eq!(view.visible(now + Duration::from_secs(3600)).len(), 2);
+
+    assert!(view.dismiss());
+    assert!(view.visible(now).is_empty());
+    assert!(!view.dismiss());
+
+    // the notifications with a timer are not dismissed
+    view.set_persistent(false);
+    view.push(Notification::info("saved"), now);
+    assert!(!view.dismiss());
+    assert_eq!(view.visible(now).len(), 1);
+}
+
+#[test]
+fn test_max_notifications() {
+    let now = Instant::now();
+    let mut view = NotificationView::new();
+    for idx in 0..MAX_NOTIFICATIONS + 2 {
+        view.push(Notification::info(idx.to_string()), now);
+    }
+    let visible = view.visible(now);
+    assert_eq!(visible.len(), MAX_NOTIFICATIONS);
+    // the oldest ones are dropped
+    assert_eq!(visible[0].message, "2");
+}
+
+#[test]
+fn test_areas() {
+    let area = Rect::new(0, 0, 40, 10);
+    assert_eq!(
+        NotificationView::areas(area, &[8, 60]),
+        vec![Rect::new(32, 8, 8, 1), Rect::new(0, 9, 40, 1)]
+    );
+}
+
+#[test]
+fn test_styles() {
+    let mut view = NotificationView::new();
+    let reversed = Style::default().add_modifier(ratatui::style::Modifier::REVERSED);
+    view.set_styles(NotificationStyles {
+        info: reversed,
+        warning: reversed,
+        error: reversed,
+    });
+    view.push(Notification::error("failed"), Instant::now());
+    let mut term = ratatui::Terminal::new(ratatui::backend::TestBackend::new(20, 2)).unwrap();
+    term.draw(|frame| view.draw(frame, frame.area(), true))
+        .unwrap();
+    // the notification takes the given style rather than its color
+    let cell = &term.backend().buffer()[(19, 1)];
+    assert_eq!(cell.bg, Color::Reset);
+    assert!(cell.modifier.contains(ratatui::style::Modifier::REVERSED));
+}
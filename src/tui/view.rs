@@ -8,9 +8,9 @@ use crate::tui::event::ApplicationEvent;
 /// Represents actions that the ViewManager should take after handling an event.
 #[derive(Debug, Clone, Copy)]
 pub struct ManagerAction {
-    pub(crate) redraw: bool,
-    pub(crate) resize: bool,
-    pub(crate) close: bool,
+    pub redraw: bool,
+    pub resize: bool,
+    pub close: bool,
 }
 
 impl ManagerAction {
@@ -108,6 +108,18 @@ pub trait View: Any {
         (EventCaptured::No, ManagerAction::new(false))
     }
 
+    /// Handles the escape key before it closes the modal view or exits, the keyboard events
+    /// broadcast being followed.
+    ///
+    /// Returns true when the view used the key, e.g. to leave a mode, which then closes nothing.
+    fn handle_escape(&mut self) -> bool { false }
+
+    /// Handles the tab key before it switches the top level view, like `handle_escape`.
+    ///
+    /// Returns true when the view used the key, e.g. to complete a text, which then switches
+    /// nothing.
+    fn handle_tab(&mut self) -> bool { false }
+
     /// Handles a mouse event.
     ///
     /// Returns a ManagerAction specifying what the ViewManager should do next.
@@ -121,4 +133,14 @@ pub trait View: Any {
         let _ = mouse_event;
         ManagerAction::new(false)
     }
+
+    /// Handles a text pasted in the terminal, received at once when the terminal supports
+    /// bracketed paste.
+    ///
+    /// The text is dispatched like a keyboard event, the returned tuple having the same meaning.
+    /// Default implementation does not capture the text.
+    fn handle_paste(&mut self, text: &str) -> (EventCaptured, ManagerAction) {
+        let _ = text;
+        (EventCaptured::No, ManagerAction::new(false))
+    }
 }
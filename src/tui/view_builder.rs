@@ -8,19 +8,19 @@ pub struct ViewBuilder {
 
 #[allow(unused)]
 impl ViewBuilder {
-    pub(crate) fn from(view: Box<dyn View>) -> ViewBuilder {
+    pub fn from(view: Box<dyn View>) -> ViewBuilder {
         ViewBuilder {
             view,
             children: Vec::new(),
             publish_events: false,
         }
     }
-    pub(crate) fn child(mut self, id: u16, child_view: ViewBuilder) -> ViewBuilder {
+    pub fn child(mut self, id: u16, child_view: ViewBuilder) -> ViewBuilder {
         self.children.push((id, child_view));
         self
     }
 
-    pub(crate) fn with_publish_events(mut self, publis_events: bool) -> ViewBuilder {
+    pub fn with_publish_events(mut self, publis_events: bool) -> ViewBuilder {
         self.publish_events = publis_events;
         self
     }
@@ -1,21 +1,32 @@
-use std::{cell::RefCell, collections::HashSet, ops::Add, rc::Rc};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashSet,
+    ops::Add,
+    rc::Rc,
+    sync::{Arc, Mutex, Once, PoisonError},
+    thread::ThreadId,
+    time::{Duration, Instant},
+};
 
 use crossterm::event::{
-    DisableMouseCapture, EnableMouseCapture, Event, EventStream, KeyCode, KeyEvent, KeyModifiers,
-    MouseEvent,
+    DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture, Event,
+    EventStream, KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind,
 };
-use log::{debug, info, trace, warn};
 use ratatui::layout::{Position, Rect};
 use tokio::{
     select,
     sync::{broadcast, broadcast::error::RecvError},
 };
 use tokio_stream::StreamExt;
+use tracing::{debug, error, info, instrument, trace, warn};
 
 use crate::tui::{
     ViewBuilder,
-    event::{GenericEvent, ViewManagerEvent},
+    event::{
+        ApplicationEvent, ExternalCommand, ExternalCommandOutput, GenericEvent, ViewManagerEvent,
+    },
     managed_view::ManagedView,
+    notification::{self, Notification, NotificationStyles, NotificationView},
     view::{EventCaptured, ManagerAction, View},
 };
 
@@ -26,6 +37,15 @@ mod view_manager_tests;
 type ModalCallBack = Box<dyn FnOnce(&mut dyn View, &dyn View) -> ManagerAction>;
 type HelpViewBuilderCallBack = Box<dyn Fn() -> ViewBuilder>;
 type ConfigViewBuilderCallBack = Box<dyn Fn() -> ViewBuilder>;
+type CommandPaletteBuilderCallBack = Box<dyn Fn() -> ViewBuilder>;
+type LogViewBuilderCallBack = Box<dyn Fn() -> ViewBuilder>;
+type ViewEventHandler<'a> = &'a dyn Fn(&mut dyn View) -> (EventCaptured, ManagerAction);
+
+/// The views from a top level view down to its active view
+type ViewChain = Vec<Rc<RefCell<ManagedView>>>;
+
+/// The minimal time between two frames when none is set, about 60 frames per second
+const DEFAULT_FRAME_BUDGET: Duration = Duration::from_millis(16);
 
 /// Represents a modal view entry with its associated parent and close callback.
 struct ModalEntry {
@@ -45,14 +65,35 @@ pub struct ViewManager {
     top_level_view_idx: RefCell<usize>,
 
     receive_events_views: RefCell<Vec<Rc<RefCell<ManagedView>>>>,
-    active_view: RefCell<Vec<Option<Vec<Rc<RefCell<ManagedView>>>>>>,
+    active_view: RefCell<Vec<Option<ViewChain>>>,
     modal_views: RefCell<Vec<Rc<RefCell<ModalEntry>>>>,
+    /// The view displayed on the last row below every top level view, if any
+    status_bar: RefCell<Option<Rc<RefCell<ManagedView>>>>,
+    /// The view displayed on the first row above every top level view, if any
+    menu_bar: RefCell<Option<Rc<RefCell<ManagedView>>>>,
+    /// The notifications, drawn above every view
+    notification_view: RefCell<NotificationView>,
     context_view: RefCell<Option<Rc<RefCell<ManagedView>>>>,
 
     global_help_view_builder_cb: RefCell<Option<HelpViewBuilderCallBack>>,
     global_config_view_builder_cb: RefCell<Option<ConfigViewBuilderCallBack>>,
+    global_command_palette_builder_cb: RefCell<Option<CommandPaletteBuilderCallBack>>,
+    global_log_view_builder_cb: RefCell<Option<LogViewBuilderCallBack>>,
 
     exit_string: RefCell<Option<String>>,
+
+    /// Time spent drawing the views during the last frame
+    last_frame_duration: RefCell<Option<Duration>>,
+
+    /// The minimal time between two frames, the redraws requested meanwhile being coalesced
+    frame_budget: Cell<Duration>,
+
+    /// The ids of the application events of which only the last one is handled when several
+    /// are pending
+    coalesced_events: RefCell<HashSet<String>>,
+
+    /// External command to run once the current event is handled
+    pending_external_command: RefCell<Option<ExternalCommand>>,
 }
 
 #[allow(unused)]
@@ -71,15 +112,30 @@ impl ViewManager {
             receive_events_views: RefCell::new(vec![]),
             active_view: RefCell::new(vec![]),
             modal_views: RefCell::new(vec![]),
+            status_bar: RefCell::new(None),
+            menu_bar: RefCell::new(None),
+            notification_view: RefCell::new(NotificationView::new()),
             context_view: RefCell::new(None),
             global_help_view_builder_cb: RefCell::new(None),
             global_config_view_builder_cb: RefCell::new(None),
+            global_command_palette_builder_cb: RefCell::new(None),
+            global_log_view_builder_cb: RefCell::new(None),
             exit_string: RefCell::new(None),
+            last_frame_duration: RefCell::new(None),
+            frame_budget: Cell::new(DEFAULT_FRAME_BUDGET),
+            coalesced_events: RefCell::new(HashSet::new()),
+            pending_external_command: RefCell::new(None),
         }
     }
 
     pub fn tx(&self) -> broadcast::Sender<GenericEvent> { self.tx.clone() }
 
+    /// Posts a notification, displayed until its duration elapses or, with reduced motion,
+    /// until the next key.
+    pub fn notify(&self, notification: Notification) {
+        notification::notify(&self.tx, notification)
+    }
+
     pub fn set_global_help_view(&self, help_view: HelpViewBuilderCallBack) {
         self.global_help_view_builder_cb.replace(Some(help_view));
     }
@@ -89,6 +145,67 @@ impl ViewManager {
             .replace(Some(config_view));
     }
 
+    /// Sets the modal opened with ctrl+shift+p or ctrl+space, whatever the displayed view.
+    pub fn set_global_command_palette(&self, command_palette: CommandPaletteBuilderCallBack) {
+        self.global_command_palette_builder_cb
+            .replace(Some(command_palette));
+    }
+
+    /// Sets the modal opened with ctrl+shift+l or F11, showing the logs of the application.
+    pub fn set_global_log_view(&self, log_view: LogViewBuilderCallBack) {
+        self.global_log_view_builder_cb.replace(Some(log_view));
+    }
+
+    /// Sets the minimal time between two frames: the views are drawn once for all the events
+    /// handled meanwhile. A zero budget draws after each event.
+    pub fn set_frame_budget(&self, frame_budget: Duration) { self.frame_budget.set(frame_budget); }
+
+    /// Keeps the notifications displayed until the next key rather than for their duration,
+    /// for the users sensitive to motion.
+    pub fn set_reduced_motion(&self, reduced_motion: bool) {
+        self.notification_view
+            .borrow_mut()
+            .set_persistent(reduced_motion);
+    }
+
+    /// Sets the styles of the notifications, e.g. the ones of an accessible mode.
+    pub fn set_notification_styles(&self, styles: NotificationStyles) {
+        self.notification_view.borrow_mut().set_styles(styles);
+    }
+
+    /// Sets the ids of the application events describing a whole state, e.g. the search: when
+    /// several of them are pending, only the last one is handled.
+    pub fn set_coalesced_events(&self, ids: &[&str]) {
+        self.coalesced_events
+            .replace(ids.iter().map(|id| id.to_string()).collect());
+    }
+
+    /// Drops the pending application events superseded by a later one of the same coalesced id,
+    /// the other events keeping their order.
+    fn coalesce(
+        &self,
+        events: Vec<Result<GenericEvent, RecvError>>,
+    ) -> Vec<Result<GenericEvent, RecvError>> {
+        let coalesced_events = self.coalesced_events.borrow();
+        let mut seen: HashSet<String> = HashSet::new();
+        let mut kept: Vec<_> = events
+            .into_iter()
+            .rev()
+            .filter(|event| match event {
+                Ok(GenericEvent::ApplicationEvent(ae)) if coalesced_events.contains(&ae.id) => {
+                    let last = seen.insert(ae.id.clone());
+                    if !last {
+                        trace!("coalescing application event '{}'", ae.id);
+                    }
+                    last
+                }
+                _ => true,
+            })
+            .collect();
+        kept.reverse();
+        kept
+    }
+
     /// Returns a centered rectangle of the specified width and height within the given area.
     ///
     /// If the requested width or height is larger than the area, it will be clamped
@@ -151,7 +268,7 @@ impl ViewManager {
     /// * `close_callback` - An optional closure that receives a mutable reference to the parent view and a reference to the modal view with their concrete types
     ///
     /// # Example
-    /// ```
+    /// ```ignore
     /// self.vm.show_modal(AlertView::builder("hello"), Some(|main_view: &mut MainView, alert: &AlertView| {
     ///     info!("callback = {}", main_view.lf);
     ///     main_view.lf += 1;
@@ -230,10 +347,48 @@ impl ViewManager {
         );
     }
 
+    /// Sets the view displayed on the last row of the terminal, below every top level view.
+    ///
+    /// The status bar is never active, but it receives the application events when built with
+    /// `with_publish_events`.
+    pub fn set_status_bar(&self, v: ViewBuilder) {
+        debug!("Setting the status bar");
+        let rc = Rc::new(RefCell::new(v.build()));
+        self.init_view_tree(&mut rc.borrow_mut());
+        self.register_receive_views(rc.clone());
+        self.status_bar.replace(Some(rc));
+    }
+
+    /// Sets the view displayed on the first row of the terminal, above every top level view.
+    ///
+    /// The menu bar is never active: it receives the clicks on its row and F10 when no modal is
+    /// displayed, and opens its menus as modals.
+    pub fn set_menu_bar(&self, v: ViewBuilder) {
+        debug!("Setting the menu bar");
+        let rc = Rc::new(RefCell::new(v.build()));
+        self.init_view_tree(&mut rc.borrow_mut());
+        self.menu_bar.replace(Some(rc));
+    }
+
     pub fn resize(&self, columns: u16, rows: u16) {
         trace!("ViewManager resize to {}x{}", columns, rows);
         self.term_size.replace(Some((columns, rows)));
-        let area = Rect::new(0, 0, columns, rows);
+        let mut area = Rect::new(0, 0, columns, rows);
+        if let Some(status_bar) = self.status_bar.borrow().as_ref()
+            && rows > 1
+        {
+            area.height -= 1;
+            let status_bar_area = Rect::new(0, rows - 1, columns, 1);
+            self.resize_managed_view(&mut status_bar.borrow_mut(), status_bar_area);
+        }
+        if let Some(menu_bar) = self.menu_bar.borrow().as_ref()
+            && area.height > 1
+        {
+            area.y += 1;
+            area.height -= 1;
+            let menu_bar_area = Rect::new(0, 0, columns, 1);
+            self.resize_managed_view(&mut menu_bar.borrow_mut(), menu_bar_area);
+        }
         for mv in self.views.borrow().iter() {
             let mut managed_view = mv.borrow_mut();
             self.resize_managed_view(&mut managed_view, area);
@@ -253,8 +408,12 @@ impl ViewManager {
         }
     }
 
+    /// Time spent drawing the views during the last frame, if any frame was drawn yet.
+    pub fn last_frame_duration(&self) -> Option<Duration> { *self.last_frame_duration.borrow() }
+
     pub fn draw(&self, frame: &mut ratatui::Frame) {
         trace!("ViewManager draw");
+        let frame_start = Instant::now();
         let top_level_view_idx = *self.top_level_view_idx.borrow();
         let active_view_id = self.active_view.borrow()[top_level_view_idx]
             .as_ref()
@@ -276,6 +435,13 @@ impl ViewManager {
             managed_view.draw(frame, active_view_id, false);
         }
 
+        if let Some(status_bar) = self.status_bar.borrow().as_ref() {
+            status_bar.borrow_mut().draw(frame, None, false);
+        }
+        if let Some(menu_bar) = self.menu_bar.borrow().as_ref() {
+            menu_bar.borrow_mut().draw(frame, None, false);
+        }
+
         // draw modal views
         let len = self.modal_views.borrow().len();
         for (index, entry) in self.modal_views.borrow().iter().enumerate() {
@@ -293,6 +459,14 @@ impl ViewManager {
             };
             modal_entry.modal_view.draw(frame, p, false);
         }
+
+        // the notifications are drawn over the views, above the status bar
+        let area = self.views.borrow()[top_level_view_idx].borrow().area;
+        self.notification_view.borrow_mut().draw(frame, area, false);
+
+        let frame_duration = frame_start.elapsed();
+        self.last_frame_duration.replace(Some(frame_duration));
+        debug!("frame drawn in {}us", frame_duration.as_micros());
         trace!("exit ViewManager draw");
     }
 
@@ -371,6 +545,12 @@ impl ViewManager {
     /// `(event_captured, merged_action)` - Whether the event was captured and the resulting action
     fn handle_active_view_key_event(&self, key_event: KeyEvent) -> Option<ManagerAction> {
         debug!("handle_active_view_key_event {:?}", key_event);
+        self.handle_active_view_event(&|view| view.handle_key_event(key_event))
+    }
+
+    /// Sends an event handled like the keyboard events to the active view hierarchy, see
+    /// `handle_active_view_key_event`.
+    fn handle_active_view_event(&self, handler: ViewEventHandler) -> Option<ManagerAction> {
         let top_level_view_idx = *self.top_level_view_idx.borrow();
         let active_view_vec = &self.active_view.borrow()[top_level_view_idx];
         let views = active_view_vec.as_ref()?;
@@ -381,7 +561,7 @@ impl ViewManager {
         // Iterate from leaf to root, giving child views first chance to handle events
         for view in views.iter().rev() {
             let (event_captured, action) =
-                self.process_view_key_event(key_event, view, &mut called_views);
+                self.process_view_event(handler, view, &mut called_views);
             merged_action.merge(&action);
 
             if let EventCaptured::Yes = event_captured {
@@ -392,9 +572,40 @@ impl ViewManager {
         Some(merged_action)
     }
 
-    fn process_view_key_event(
+    /// Offers a key to the active view hierarchy, from the leaf to the root, e.g.
+    /// `View::handle_escape`.
+    ///
+    /// Returns true when a view used it.
+    fn offer_active_view_key(&self, offer: fn(&mut dyn View) -> bool) -> bool {
+        let top_level_view_idx = *self.top_level_view_idx.borrow();
+        let active_view_vec = self.active_view.borrow()[top_level_view_idx].clone();
+        let Some(views) = active_view_vec else {
+            return false;
+        };
+        views
+            .iter()
+            .rev()
+            .any(|view| Self::offer_view_key(offer, view))
+    }
+
+    fn offer_view_key(offer: fn(&mut dyn View) -> bool, view: &Rc<RefCell<ManagedView>>) -> bool {
+        let mut managed_view = view.borrow_mut();
+        if offer(managed_view.view.as_mut()) {
+            return true;
+        }
+        if !managed_view.view.broadcast_keyboard_events() {
+            return false;
+        }
+        let children: Vec<_> = managed_view.children.to_vec();
+        drop(managed_view);
+        children
+            .iter()
+            .any(|child| Self::offer_view_key(offer, child))
+    }
+
+    fn process_view_event(
         &self,
-        key_event: KeyEvent,
+        handler: ViewEventHandler,
         view: &Rc<RefCell<ManagedView>>,
         called_views: &mut HashSet<String>,
     ) -> (EventCaptured, ManagerAction) {
@@ -409,7 +620,7 @@ impl ViewManager {
 
         let mut managed_view = view.borrow_mut();
 
-        let (event_captured, mut merged_action) = managed_view.view.handle_key_event(key_event);
+        let (event_captured, mut merged_action) = handler(managed_view.view.as_mut());
         let should_broadcast = managed_view.view.broadcast_keyboard_events();
         let children: Vec<_> = managed_view.children.to_vec();
         drop(managed_view);
@@ -423,7 +634,7 @@ impl ViewManager {
                     child.borrow().id,
                     child.borrow().unique_id
                 );
-                let (_, action) = self.process_view_key_event(key_event, &child, called_views);
+                let (_, action) = self.process_view_event(handler, &child, called_views);
                 merged_action.merge(&action);
             }
             debug!("end of broadcast");
@@ -449,6 +660,19 @@ impl ViewManager {
         ManagerAction::new(false)
     }
 
+    /// Sends a pasted text to the topmost modal if any, else to the active view hierarchy like a
+    /// keyboard event.
+    pub fn handle_paste_event(&self, text: &str) -> ManagerAction {
+        trace!("handle_paste_event {} chars", text.chars().count());
+        if let Some(modal_action) =
+            self.handle_modal_event(|modal_entry| modal_entry.modal_view.view.handle_paste(text).1)
+        {
+            return modal_action;
+        }
+        self.handle_active_view_event(&|view| view.handle_paste(text))
+            .unwrap_or_else(|| ManagerAction::new(false))
+    }
+
     /// Initializes the active view based on a sequence of IDs.
     ///
     /// The IDs should form a path from root to leaf view, where each ID
@@ -476,7 +700,7 @@ impl ViewManager {
         &self,
         views: &[Rc<RefCell<ManagedView>>],
         ids: &[usize],
-    ) -> Option<Vec<Rc<RefCell<ManagedView>>>> {
+    ) -> Option<ViewChain> {
         if ids.is_empty() {
             return None;
         }
@@ -532,7 +756,11 @@ impl ViewManager {
     pub fn handle_mouse_event(&self, mouse_event: MouseEvent) -> ManagerAction {
         //trace!("handle_mouse_event {:?}", mouse_event);
 
-        if !matches!(mouse_event.kind, crossterm::event::MouseEventKind::Down(_)) {
+        let scroll = matches!(
+            mouse_event.kind,
+            MouseEventKind::ScrollUp | MouseEventKind::ScrollDown
+        );
+        if !scroll && !matches!(mouse_event.kind, MouseEventKind::Down(_)) {
             return ManagerAction::new(false);
         }
 
@@ -541,8 +769,26 @@ impl ViewManager {
             return modal_action;
         }
 
-        // on mouse down, activate the view at the mouse position
         let position = Position::new(mouse_event.column, mouse_event.row);
+
+        // the clicks on the menu bar open its menus
+        if !scroll && let Some(menu_bar) = self.menu_bar.borrow().as_ref() {
+            let mut menu_bar = menu_bar.borrow_mut();
+            let area = menu_bar.area;
+            if area.contains(position) {
+                return menu_bar.view.handle_mouse_event(area, mouse_event);
+            }
+        }
+
+        // the wheel scrolls the views under the mouse, without moving the focus
+        if scroll {
+            return self
+                .search_active_view(position)
+                .map(|views| self.dispatch_mouse_event(mouse_event, &views))
+                .unwrap_or_else(|| ManagerAction::new(false));
+        }
+
+        // on mouse down, activate the view at the mouse position
         self.activate_view_at_position(position);
 
         // let's notify the views
@@ -573,6 +819,16 @@ impl ViewManager {
         let active_view_vec = &self.active_view.borrow()[top_level_view_idx];
         let views = active_view_vec.as_ref()?;
 
+        Some(self.dispatch_mouse_event(mouse_event, views))
+    }
+
+    /// Sends a mouse event to a stack of views, from the leaf to the root, and merges their
+    /// actions.
+    fn dispatch_mouse_event(
+        &self,
+        mouse_event: MouseEvent,
+        views: &[Rc<RefCell<ManagedView>>],
+    ) -> ManagerAction {
         let mut called_views: HashSet<String> = HashSet::new();
         let mut merged_action = ManagerAction::new(false);
 
@@ -582,7 +838,7 @@ impl ViewManager {
             merged_action.merge(&action);
         }
 
-        Some(merged_action)
+        merged_action
     }
 
     fn process_view_mouse_event(
@@ -662,6 +918,7 @@ impl ViewManager {
         }
     }
 
+    #[instrument(level = "debug", skip_all)]
     pub fn handle_broadcast_event(&self, event: &Result<GenericEvent, RecvError>) -> ManagerAction {
         let mut manager_action: ManagerAction = ManagerAction::new(false);
         match event {
@@ -677,6 +934,31 @@ impl ViewManager {
                         self.exit_string.replace(payload.clone());
                         manager_action.close = true;
                     }
+                    ViewManagerEvent::RunExternalCommand(command) => {
+                        self.pending_external_command.replace(Some(command.clone()));
+                    }
+                    ViewManagerEvent::Key(key_event) => {
+                        manager_action =
+                            self.handle_crossterm_event(Some(Ok(Event::Key(*key_event))));
+                    }
+                    ViewManagerEvent::Notify(notification) => {
+                        debug!("notification: {}", notification.message);
+                        let mut notification_view = self.notification_view.borrow_mut();
+                        notification_view.push(notification.clone(), Instant::now());
+                        manager_action.redraw = true;
+                        // redraw once the notification expired to remove it
+                        if !notification_view.persistent()
+                            && let Ok(handle) = tokio::runtime::Handle::try_current()
+                        {
+                            let tx = self.tx.clone();
+                            let duration = notification.duration;
+                            handle.spawn(async move {
+                                tokio::time::sleep(duration).await;
+                                let _ = tx
+                                    .send(GenericEvent::ViewManagerEvent(ViewManagerEvent::Redraw));
+                            });
+                        }
+                    }
                 },
                 GenericEvent::ApplicationEvent(ae) => {
                     debug!("received application event: '{}'", ae.id);
@@ -697,12 +979,16 @@ impl ViewManager {
         manager_action
     }
 
+    #[instrument(level = "debug", skip_all)]
     pub fn handle_crossterm_event(
         &self,
         crossterm_event: Option<std::io::Result<Event>>,
     ) -> ManagerAction {
         //trace!("received crossterm event: {:?}", crossterm_event);
         let mut manager_action: ManagerAction = ManagerAction::new(false);
+        // the notifications kept until dismissed go away with the next key
+        let dismissed = matches!(crossterm_event, Some(Ok(Event::Key(_))))
+            && self.notification_view.borrow_mut().dismiss();
         match crossterm_event {
             Some(Ok(event)) => match event {
                 Event::Resize(columns, rows) => {
@@ -727,13 +1013,28 @@ impl ViewManager {
                 Event::Mouse(mouse_event) => {
                     manager_action = self.handle_mouse_event(mouse_event);
                 }
+                Event::Paste(text) => {
+                    manager_action = self.handle_paste_event(&text);
+                }
                 Event::Key(key_event) => match key_event.code {
                     KeyCode::Esc => {
+                        if self.modal_views.borrow().is_empty()
+                            && self.offer_active_view_key(|view| view.handle_escape())
+                        {
+                            manager_action.redraw = true;
+                        } else {
+                            manager_action.close = true;
+                        }
+                    }
+                    KeyCode::Char('q') if key_event.modifiers == KeyModifiers::CONTROL => {
                         manager_action.close = true;
                     }
                     _ => {
                         if key_event.code == KeyCode::Tab && self.modal_views.borrow().is_empty() {
-                            self.switch_to_next_top_level_view();
+                            // e.g. the completion of the search comes before the switch
+                            if !self.offer_active_view_key(|view| view.handle_tab()) {
+                                self.switch_to_next_top_level_view();
+                            }
                             manager_action.redraw = true;
                         } else if key_event.modifiers.contains(KeyModifiers::CONTROL)
                             && let KeyCode::Char('h') = key_event.code
@@ -742,6 +1043,27 @@ impl ViewManager {
                         {
                             self.show_modal_generic(global_help_view_builder_cb(), None);
                             manager_action.redraw = true;
+                        } else if key_event.code == KeyCode::F(10)
+                            && self.modal_views.borrow().is_empty()
+                            && let Some(menu_bar) = self.menu_bar.borrow().as_ref()
+                        {
+                            let (_, action) =
+                                menu_bar.borrow_mut().view.handle_key_event(key_event);
+                            manager_action = action;
+                        } else if Self::is_command_palette_key(&key_event)
+                            && self.modal_views.borrow().is_empty()
+                            && let Some(global_command_palette_builder_cb) =
+                                &self.global_command_palette_builder_cb.borrow().as_ref()
+                        {
+                            self.show_modal_generic(global_command_palette_builder_cb(), None);
+                            manager_action.redraw = true;
+                        } else if Self::is_log_view_key(&key_event)
+                            && self.modal_views.borrow().is_empty()
+                            && let Some(global_log_view_builder_cb) =
+                                &self.global_log_view_builder_cb.borrow().as_ref()
+                        {
+                            self.show_modal_generic(global_log_view_builder_cb(), None);
+                            manager_action.redraw = true;
                         } else if key_event.code == KeyCode::F(12)
                             && let Some(global_config_view_builder_cb) =
                                 &self.global_config_view_builder_cb.borrow().as_ref()
@@ -760,9 +1082,44 @@ impl ViewManager {
             Some(Err(e)) => println!("Error: {e:?}\r"),
             None => {}
         };
+        manager_action.redraw |= dismissed;
         manager_action
     }
 
+    /// Whether the key opens the command palette: ctrl+shift+p, or ctrl+space for the terminals
+    /// not reporting shift with control
+    fn is_command_palette_key(key_event: &KeyEvent) -> bool {
+        match key_event.code {
+            KeyCode::Char('p' | 'P') => {
+                key_event.modifiers == KeyModifiers::CONTROL | KeyModifiers::SHIFT
+            }
+            KeyCode::Char(' ') => key_event.modifiers == KeyModifiers::CONTROL,
+            _ => false,
+        }
+    }
+
+    /// Whether the key opens the log viewer: ctrl+shift+l, or F11 for the terminals not
+    /// reporting the shift modifier
+    fn is_log_view_key(key_event: &KeyEvent) -> bool {
+        match key_event.code {
+            KeyCode::Char('l' | 'L') => {
+                key_event.modifiers == KeyModifiers::CONTROL | KeyModifiers::SHIFT
+            }
+            KeyCode::F(11) => key_event.modifiers == KeyModifiers::NONE,
+            _ => false,
+        }
+    }
+
+    /// The index of the displayed top level view
+    pub fn top_level_view_idx(&self) -> usize { *self.top_level_view_idx.borrow() }
+
+    /// Display the top level view at `idx`, in the order the views were added
+    pub fn set_top_level_view_idx(&self, idx: usize) {
+        if idx < self.views.borrow().len() {
+            self.top_level_view_idx.replace(idx);
+        }
+    }
+
     fn switch_to_next_top_level_view(&self) {
         let tlvi = self.top_level_view_idx.borrow();
         let idx = tlvi.add(1) % self.views.borrow().len();
@@ -771,32 +1128,126 @@ impl ViewManager {
         self.top_level_view_idx.replace(idx);
     }
 
+    /// Hand the terminal over to an external command: the UI is suspended while the command
+    /// runs and restored afterwards.
+    /// When requested by the command, its output is captured and published.
+    fn run_external_command(&self, command: &ExternalCommand) {
+        info!("running external command {:?}", command);
+        restore_terminal();
+
+        let mut process = std::process::Command::new(&command.program);
+        process.args(&command.args);
+        if let Some(cwd) = &command.cwd {
+            process.current_dir(cwd);
+        }
+
+        let Some(output_event_id) = &command.output_event_id else {
+            match process.status() {
+                Ok(status) if !status.success() => {
+                    warn!("external command {:?} exited with {}", command, status)
+                }
+                Ok(_) => {}
+                Err(e) => error!("failed to run external command {:?}: {}", command, e),
+            }
+            return;
+        };
+
+        let command_output = match process.output() {
+            Ok(output) => {
+                let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+                text.push_str(&String::from_utf8_lossy(&output.stderr));
+                ExternalCommandOutput {
+                    exit_code: output.status.code(),
+                    output: text,
+                }
+            }
+            Err(e) => {
+                error!("failed to run external command {:?}: {}", command, e);
+                ExternalCommandOutput {
+                    exit_code: None,
+                    output: format!("failed to run the command: {}", e),
+                }
+            }
+        };
+        let event = GenericEvent::ApplicationEvent(ApplicationEvent {
+            id: output_event_id.clone(),
+            payload: Some(Arc::new(command_output)),
+        });
+        if let Err(e) = self.tx.send(event) {
+            error!("Failed to send '{}' event: {}", output_event_id, e);
+        }
+    }
+
     pub async fn event_loop(&self) -> Option<String> {
+        install_panic_hook();
         let mut stdout = std::io::stdout();
         crossterm::execute!(stdout, EnableMouseCapture).expect("failed to enable mouse capture");
+        // restores the terminal however the loop ends, a panic unwinding through it included
+        let _terminal = scopeguard::guard((), |()| {
+            set_event_loop_thread(None);
+            restore_terminal();
+        });
+        // a pasted text is received at once instead of key by key
+        crossterm::execute!(stdout, EnableBracketedPaste)
+            .expect("failed to enable bracketed paste");
+
+        // subscribe first, the views publishing their state while being laid out and drawn,
+        // e.g. to query the entries shown from the previous session once drawn
+        let mut rx = self.tx.subscribe();
 
         let mut term = ratatui::init();
         let init_rect = term.get_frame().area();
         self.resize(init_rect.width, init_rect.height);
 
         // initial draw
+        let first_draw = tracing::info_span!("first_draw").entered();
         let _ = term.draw(|frame| {
             self.draw(frame);
         });
+        first_draw.exit();
+        let mut last_draw = Instant::now();
+        // the redraw and resize requested since the last frame
+        let mut pending_action = ManagerAction::new(false);
 
         let mut crossterm_reader = EventStream::new();
-        let mut rx = self.tx.subscribe();
 
         let mut manager_action: ManagerAction = ManagerAction::new(false);
         while !manager_action.close {
             let crossterm_event_next = crossterm_reader.next();
+            let next_frame = tokio::time::Instant::from_std(last_draw + self.frame_budget.get());
+            let frame_pending = pending_action.redraw() || pending_action.resize();
+            // the keys are handled first, so that the events they publish are coalesced
             select! {
-                broadcast_event = rx.recv() => {
-                    manager_action = self.handle_broadcast_event(&broadcast_event);
-                }
+                biased;
                 crossterm_event = crossterm_event_next => {
                     manager_action = self.handle_crossterm_event(crossterm_event);
                 }
+                broadcast_event = rx.recv() => {
+                    let mut events = vec![broadcast_event];
+                    while let Ok(event) = rx.try_recv() {
+                        events.push(Ok(event));
+                    }
+                    manager_action = ManagerAction::new(false);
+                    for event in self.coalesce(events) {
+                        manager_action.merge(&self.handle_broadcast_event(&event));
+                    }
+                }
+                _ = tokio::time::sleep_until(next_frame), if frame_pending => {
+                    manager_action = ManagerAction::new(false);
+                }
+            }
+
+            let external_command = self.pending_external_command.take();
+            if let Some(command) = external_command {
+                // the event stream would compete with the command for the terminal input
+                drop(crossterm_reader);
+                self.run_external_command(&command);
+                term = ratatui::init();
+                crossterm::execute!(std::io::stdout(), EnableMouseCapture, EnableBracketedPaste)
+                    .expect("failed to enable mouse capture");
+                term.clear().ok();
+                crossterm_reader = EventStream::new();
+                manager_action.resize = true;
             }
 
             if manager_action.close {
@@ -807,14 +1258,20 @@ impl ViewManager {
                     manager_action.redraw = true;
                 }
             }
-            if manager_action.resize() {
+            pending_action.redraw |= manager_action.redraw();
+            pending_action.resize |= manager_action.resize();
+            if manager_action.close || last_draw.elapsed() < self.frame_budget.get() {
+                continue;
+            }
+            if pending_action.resize() {
                 debug!("ViewManager resizing");
                 let init_rect = term.get_frame().area();
                 self.resize(init_rect.width, init_rect.height);
-                manager_action.redraw = true;
+                pending_action.redraw = true;
             }
-            if manager_action.redraw() {
+            if pending_action.redraw() {
                 debug!("ViewManager redrawing");
+                let _draw = tracing::info_span!("draw").entered();
                 let _ =
                     term.draw(|frame| {
                         if self.term_size.borrow().is_none_or(|(w, h)| {
@@ -825,12 +1282,58 @@ impl ViewManager {
                         }
                         self.draw(frame);
                     });
+                last_draw = Instant::now();
             }
+            pending_action = ManagerAction::new(false);
         }
-        let mut stdout = std::io::stdout();
-        crossterm::execute!(stdout, DisableMouseCapture).expect("failed to disable mouse capture");
-        ratatui::restore();
 
         self.exit_string.take()
     }
 }
+
+/// Leave the raw mode and the alternate screen, and disable the mouse capture and the
+/// bracketed paste, whatever the state the terminal is in.
+pub fn restore_terminal() {
+    let _ = crossterm::execute!(
+        std::io::stdout(),
+        DisableMouseCapture,
+        DisableBracketedPaste
+    );
+    ratatui::restore();
+}
+
+/// The thread running the event loop, if any.
+static EVENT_LOOP_THREAD: Mutex<Option<ThreadId>> = Mutex::new(None);
+
+fn set_event_loop_thread(thread: Option<ThreadId>) {
+    *EVENT_LOOP_THREAD
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner) = thread;
+}
+
+/// Whether the current thread runs the event loop, i.e. owns the terminal.
+fn on_event_loop_thread() -> bool {
+    let thread = *EVENT_LOOP_THREAD
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    thread == Some(std::thread::current().id())
+}
+
+/// Restore the terminal when the event loop thread panics, before the previous hook prints the
+/// panic, so that the message is readable and the shell usable. A panic of another thread, e.g.
+/// a blocking task caught by its join handle, leaves the terminal to the event loop. Installed
+/// once per process, recording the calling thread as the event loop one.
+fn install_panic_hook() {
+    set_event_loop_thread(Some(std::thread::current().id()));
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            if on_event_loop_thread() {
+                restore_terminal();
+                error!("panic: {}", info);
+            }
+            previous(info);
+        }));
+    });
+}
@@ -1,3 +1,5 @@
+use std::cell::Cell;
+
 use ratatui::layout::Rect;
 
 use super::*;
@@ -163,3 +165,387 @@ fn test_centered_rect() {
     assert_eq!(centered.width, 50);
     assert_eq!(centered.height, 50);
 }
+
+#[test]
+fn test_last_frame_duration() {
+    let vm = ViewManager::new();
+    vm.add_view(0, TestView::builder(), &[]);
+    vm.resize(20, 10);
+    assert!(vm.last_frame_duration().is_none());
+
+    let mut term = ratatui::Terminal::new(ratatui::backend::TestBackend::new(20, 10)).unwrap();
+    term.draw(|frame| vm.draw(frame)).unwrap();
+    assert!(vm.last_frame_duration().is_some());
+}
+
+#[test]
+fn test_scroll_keeps_active_view() {
+    let vm = ViewManager::new();
+    vm.add_view(10, RootView::builder(), &[]);
+    {
+        let views = vm.views.borrow();
+        let root = views[0].borrow();
+        root.children[0].borrow_mut().area = Rect::new(0, 0, 10, 5);
+        root.children[1].borrow_mut().area = Rect::new(0, 5, 10, 5);
+    }
+    vm.initialize_active_view(0, &[10, 0]);
+
+    let scroll = MouseEvent {
+        kind: MouseEventKind::ScrollDown,
+        column: 2,
+        row: 7,
+        modifiers: KeyModifiers::NONE,
+    };
+    vm.handle_mouse_event(scroll);
+    assert_eq!(
+        vm.active_view.borrow()[0].as_ref().unwrap()[1].borrow().id,
+        0
+    );
+
+    let click = MouseEvent {
+        kind: MouseEventKind::Down(crossterm::event::MouseButton::Left),
+        ..scroll
+    };
+    vm.handle_mouse_event(click);
+    assert_eq!(
+        vm.active_view.borrow()[0].as_ref().unwrap()[1].borrow().id,
+        1
+    );
+}
+
+/// A view in a mode left with esc, as long as `levels` is positive
+struct EscapeView {
+    levels: Rc<Cell<u16>>,
+}
+impl View for EscapeView {
+    fn draw(&mut self, _: &mut ratatui::Frame, _: Rect, _: bool) {}
+    fn handle_escape(&mut self) -> bool {
+        let levels = self.levels.get();
+        self.levels.set(levels.saturating_sub(1));
+        levels > 0
+    }
+}
+
+struct BroadcastingView {}
+impl View for BroadcastingView {
+    fn draw(&mut self, _: &mut ratatui::Frame, _: Rect, _: bool) {}
+    fn capture_focus(&self) -> bool { true }
+    fn broadcast_keyboard_events(&self) -> bool { true }
+}
+
+#[test]
+fn test_escape() {
+    let vm = ViewManager::new();
+    let levels = Rc::new(Cell::new(1));
+    vm.add_view(
+        10,
+        ViewBuilder::from(Box::new(BroadcastingView {})).child(
+            0,
+            ViewBuilder::from(Box::new(EscapeView {
+                levels: levels.clone(),
+            })),
+        ),
+        &[],
+    );
+    vm.initialize_active_view(0, &[10]);
+    let escape = || {
+        vm.handle_crossterm_event(Some(Ok(Event::Key(KeyEvent::new(
+            KeyCode::Esc,
+            KeyModifiers::NONE,
+        )))))
+    };
+
+    // the child the key is broadcast to leaves its mode
+    let action = escape();
+    assert!(!action.close());
+    assert_eq!(levels.get(), 0);
+
+    // then the key exits
+    assert!(escape().close());
+}
+
+/// A view completing a text with tab, as long as `completions` is positive
+struct CompletionView {
+    completions: Rc<Cell<u16>>,
+}
+impl View for CompletionView {
+    fn draw(&mut self, _: &mut ratatui::Frame, _: Rect, _: bool) {}
+    fn handle_tab(&mut self) -> bool {
+        let completions = self.completions.get();
+        self.completions.set(completions.saturating_sub(1));
+        completions > 0
+    }
+}
+
+#[test]
+fn test_tab() {
+    let vm = ViewManager::new();
+    let completions = Rc::new(Cell::new(1));
+    vm.add_view(
+        10,
+        ViewBuilder::from(Box::new(BroadcastingView {})).child(
+            0,
+            ViewBuilder::from(Box::new(CompletionView {
+                completions: completions.clone(),
+            })),
+        ),
+        &[],
+    );
+    vm.add_view(11, TestView::builder(), &[]);
+    vm.initialize_active_view(0, &[10]);
+    let tab = || {
+        vm.handle_crossterm_event(Some(Ok(Event::Key(KeyEvent::new(
+            KeyCode::Tab,
+            KeyModifiers::NONE,
+        )))))
+    };
+
+    // the child the key is broadcast to completes its text
+    assert!(tab().redraw());
+    assert_eq!(completions.get(), 0);
+    assert_eq!(vm.top_level_view_idx(), 0);
+
+    // then the key switches the view
+    tab();
+    assert_eq!(vm.top_level_view_idx(), 1);
+}
+
+#[test]
+fn test_status_bar() {
+    let vm = ViewManager::new();
+    vm.add_view(10, RootView::builder(), &[]);
+    vm.resize(80, 24);
+    assert_eq!(vm.views.borrow()[0].borrow().area, Rect::new(0, 0, 80, 24));
+
+    vm.set_status_bar(TestView::builder().with_publish_events(true));
+    vm.resize(80, 24);
+    assert_eq!(vm.views.borrow()[0].borrow().area, Rect::new(0, 0, 80, 23));
+    let status_bar = vm.status_bar.borrow().clone().unwrap();
+    assert_eq!(status_bar.borrow().area, Rect::new(0, 23, 80, 1));
+    assert!(
+        vm.receive_events_views
+            .borrow()
+            .iter()
+            .any(|view| Rc::ptr_eq(view, &status_bar))
+    );
+}
+
+#[test]
+fn test_notify() {
+    let vm = ViewManager::new();
+    let action = vm.handle_broadcast_event(&Ok(GenericEvent::ViewManagerEvent(
+        ViewManagerEvent::Notify(Notification::info("saved")),
+    )));
+    assert!(action.redraw());
+    let mut notification_view = vm.notification_view.borrow_mut();
+    let visible = notification_view.visible(Instant::now());
+    assert_eq!(visible.len(), 1);
+    assert_eq!(visible[0].message, "saved");
+}
+
+#[test]
+fn test_notify_reduced_motion() {
+    let vm = ViewManager::new();
+    vm.add_view(10, RootView::builder(), &[]);
+    vm.set_reduced_motion(true);
+    vm.handle_broadcast_event(&Ok(GenericEvent::ViewManagerEvent(
+        ViewManagerEvent::Notify(Notification::error("failed")),
+    )));
+    // no timer removes the notification
+    let later = Instant::now() + Duration::from_secs(3600);
+    assert_eq!(vm.notification_view.borrow_mut().visible(later).len(), 1);
+
+    // the next key dismisses it
+    let action = vm.handle_crossterm_event(Some(Ok(Event::Key(KeyEvent::new(
+        KeyCode::Char('x'),
+        KeyModifiers::NONE,
+    )))));
+    assert!(action.redraw());
+    assert!(
+        vm.notification_view
+            .borrow_mut()
+            .visible(Instant::now())
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_command_palette() {
+    let vm = ViewManager::new();
+    vm.add_view(10, RootView::builder(), &[]);
+    vm.set_global_command_palette(Box::new(TestView::builder));
+    let key = |code, modifiers| {
+        vm.handle_crossterm_event(Some(Ok(Event::Key(KeyEvent::new(code, modifiers)))))
+    };
+
+    assert!(!key(KeyCode::Char('p'), KeyModifiers::CONTROL).redraw());
+    assert!(vm.modal_views.borrow().is_empty());
+    assert!(key(KeyCode::Char(' '), KeyModifiers::CONTROL).redraw());
+    assert_eq!(vm.modal_views.borrow().len(), 1);
+    // the palette is not opened over a modal
+    key(
+        KeyCode::Char('P'),
+        KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+    );
+    assert_eq!(vm.modal_views.borrow().len(), 1);
+
+    // a replayed key is handled as if it was pressed
+    vm.close_modal();
+    vm.handle_broadcast_event(&Ok(GenericEvent::ViewManagerEvent(ViewManagerEvent::Key(
+        KeyEvent::new(KeyCode::Char(' '), KeyModifiers::CONTROL),
+    ))));
+    assert_eq!(vm.modal_views.borrow().len(), 1);
+}
+
+#[test]
+fn test_log_view() {
+    let vm = ViewManager::new();
+    vm.add_view(10, RootView::builder(), &[]);
+    vm.set_global_log_view(Box::new(TestView::builder));
+    let key = |code, modifiers| {
+        vm.handle_crossterm_event(Some(Ok(Event::Key(KeyEvent::new(code, modifiers)))))
+    };
+
+    key(KeyCode::Char('l'), KeyModifiers::CONTROL);
+    assert!(vm.modal_views.borrow().is_empty());
+    assert!(key(KeyCode::Char('L'), KeyModifiers::CONTROL | KeyModifiers::SHIFT).redraw());
+    assert_eq!(vm.modal_views.borrow().len(), 1);
+    // the logs are not opened over a modal
+    key(KeyCode::Char('L'), KeyModifiers::CONTROL | KeyModifiers::SHIFT);
+    assert_eq!(vm.modal_views.borrow().len(), 1);
+
+    // F11 opens them on the terminals not reporting ctrl+shift
+    vm.close_modal();
+    assert!(key(KeyCode::F(11), KeyModifiers::NONE).redraw());
+    assert_eq!(vm.modal_views.borrow().len(), 1);
+}
+
+struct MenuBarView {
+    opened: Rc<Cell<u16>>,
+}
+impl View for MenuBarView {
+    fn draw(&mut self, _: &mut ratatui::Frame, _: Rect, _: bool) {}
+
+    fn handle_key_event(&mut self, _: KeyEvent) -> (EventCaptured, ManagerAction) {
+        self.opened.set(self.opened.get() + 1);
+        (EventCaptured::Yes, ManagerAction::new(true))
+    }
+
+    fn handle_mouse_event(&mut self, _: Rect, _: MouseEvent) -> ManagerAction {
+        self.opened.set(self.opened.get() + 1);
+        ManagerAction::new(true)
+    }
+}
+
+#[test]
+fn test_menu_bar() {
+    let vm = ViewManager::new();
+    vm.add_view(10, RootView::builder(), &[]);
+    let opened = Rc::new(Cell::new(0));
+    vm.set_menu_bar(ViewBuilder::from(Box::new(MenuBarView {
+        opened: opened.clone(),
+    })));
+    vm.set_status_bar(TestView::builder());
+    vm.resize(80, 24);
+    assert_eq!(vm.views.borrow()[0].borrow().area, Rect::new(0, 1, 80, 22));
+    let menu_bar = vm.menu_bar.borrow().clone().unwrap();
+    assert_eq!(menu_bar.borrow().area, Rect::new(0, 0, 80, 1));
+
+    // F10 and the clicks on the first row go to the menu bar
+    let f10 = Event::Key(KeyEvent::new(KeyCode::F(10), KeyModifiers::NONE));
+    assert!(vm.handle_crossterm_event(Some(Ok(f10.clone()))).redraw());
+    let click = |row| MouseEvent {
+        kind: MouseEventKind::Down(crossterm::event::MouseButton::Left),
+        column: 3,
+        row,
+        modifiers: KeyModifiers::NONE,
+    };
+    vm.handle_mouse_event(click(0));
+    vm.handle_mouse_event(click(5));
+    assert_eq!(opened.get(), 2);
+
+    // not while a modal is displayed
+    vm.show_modal_generic(TestView::builder(), None);
+    vm.handle_crossterm_event(Some(Ok(f10)));
+    vm.handle_mouse_event(click(0));
+    assert_eq!(opened.get(), 2);
+}
+
+#[test]
+fn test_coalesce() {
+    let vm = ViewManager::new();
+    vm.set_coalesced_events(&["search"]);
+    let event = |id: &str, payload: &str| {
+        Ok(GenericEvent::ApplicationEvent(ApplicationEvent {
+            id: String::from(id),
+            payload: Some(Arc::new(String::from(payload))),
+        }))
+    };
+    let events = vec![
+        event("search", "a"),
+        event("other", "1"),
+        Ok(GenericEvent::ViewManagerEvent(ViewManagerEvent::Redraw)),
+        event("search", "ab"),
+        event("other", "2"),
+        event("search", "abc"),
+    ];
+    let kept: Vec<String> = vm
+        .coalesce(events)
+        .into_iter()
+        .map(|event| match event {
+            Ok(GenericEvent::ApplicationEvent(ae)) => format!(
+                "{}={}",
+                ae.id,
+                ae.payload.unwrap().downcast_ref::<String>().unwrap()
+            ),
+            _ => String::from("vm"),
+        })
+        .collect();
+    // only the last search is kept, the other events keeping their order
+    assert_eq!(kept, vec!["other=1", "vm", "other=2", "search=abc"]);
+}
+
+struct PasteView {
+    pasted: Rc<RefCell<String>>,
+}
+impl View for PasteView {
+    fn draw(&mut self, _: &mut ratatui::Frame, _: Rect, _: bool) {}
+
+    fn handle_paste(&mut self, text: &str) -> (EventCaptured, ManagerAction) {
+        self.pasted.borrow_mut().push_str(text);
+        (EventCaptured::Yes, ManagerAction::new(true))
+    }
+}
+
+#[test]
+fn test_paste() {
+    let vm = ViewManager::new();
+    let pasted = Rc::new(RefCell::new(String::new()));
+    let root = ViewBuilder::from(Box::new(PasteView {
+        pasted: pasted.clone(),
+    }))
+    .child(0, LeafView::builder());
+    vm.add_view(0, root, &[0]);
+    vm.initialize_active_view(0, &[0, 0]);
+
+    // the text goes up from the active leaf to the view handling it
+    let action = vm.handle_crossterm_event(Some(Ok(Event::Paste(String::from("/tmp/a b")))));
+    assert!(action.redraw());
+    assert_eq!(*pasted.borrow(), "/tmp/a b");
+
+    // a modal receives the text instead
+    vm.show_modal_generic(TestView::builder(), None);
+    let action = vm.handle_paste_event("/tmp/c");
+    assert!(!action.redraw());
+    assert_eq!(*pasted.borrow(), "/tmp/a b");
+}
+
+#[test]
+fn test_event_loop_thread() {
+    set_event_loop_thread(Some(std::thread::current().id()));
+    assert!(on_event_loop_thread());
+    assert!(!std::thread::spawn(on_event_loop_thread).join().unwrap());
+
+    set_event_loop_thread(None);
+    assert!(!on_event_loop_thread());
+}
@@ -0,0 +1,1438 @@
+//! End-to-end tests running the compiled `cdir` binary against a temporary HOME.
+
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Command, Output, Stdio},
+};
+
+use rusqlite::Connection;
+use tempfile::TempDir;
+
+/// A sandboxed cdir environment: a temporary HOME holding the configuration and the database.
+struct Sandbox {
+    home: TempDir,
+}
+
+impl Sandbox {
+    /// Create a sandbox with a ready-to-use configuration file, so that the first run does not
+    /// go through the interactive initialization.
+    fn new() -> Sandbox { Self::with_config("") }
+
+    /// Create a sandbox like `new`, appending `extra_config` to the configuration file.
+    fn with_config(extra_config: &str) -> Sandbox {
+        let home = tempfile::tempdir().unwrap();
+        let sandbox = Sandbox { home };
+        fs::create_dir_all(sandbox.config_path().parent().unwrap()).unwrap();
+        fs::write(
+            sandbox.config_path(),
+            format!(
+                "db_path: {}\nlog_file: null\n{}",
+                sandbox.db_path().display(),
+                extra_config
+            ),
+        )
+        .unwrap();
+        sandbox
+    }
+
+    /// Create a sandbox without any configuration file.
+    fn empty() -> Sandbox {
+        Sandbox {
+            home: tempfile::tempdir().unwrap(),
+        }
+    }
+
+    fn config_path(&self) -> PathBuf { self.home.path().join(".config/cdir/config.yaml") }
+
+    fn db_path(&self) -> PathBuf { self.home.path().join("cdir.db") }
+
+    fn file(&self, name: &str, contents: &str) -> PathBuf {
+        let path = self.home.path().join(name);
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    /// The cdir command of the sandbox, isolated from the environment of the tests
+    fn cdir(&self, args: &[&str]) -> Command {
+        let mut command = Command::new(env!("CARGO_BIN_EXE_cdir"));
+        command
+            .args(args)
+            .env("HOME", self.home.path())
+            .env_remove("CDIR_CONFIG")
+            .env_remove("XDG_CONFIG_HOME")
+            .env_remove("XDG_DATA_HOME")
+            .env_remove("TMUX_PANE")
+            .env("HOSTNAME", "cdir-test");
+        command
+    }
+
+    fn command(&self, args: &[&str]) -> Output { self.command_with_env(&[], args) }
+
+    /// Run cdir like `command`, with the given environment variables, e.g. to run it as if it
+    /// were in a tmux pane or on another host.
+    fn command_with_env(&self, env: &[(&str, &str)], args: &[&str]) -> Output {
+        self.cdir(args)
+            .envs(env.iter().copied())
+            .output()
+            .expect("failed to run cdir")
+    }
+
+    /// Run cdir like `stdout`, from the directory `dir`, e.g. to get the smart suggestions of
+    /// this directory.
+    fn stdout_in(&self, dir: &std::path::Path, args: &[&str]) -> String {
+        let output = self
+            .cdir(args)
+            .current_dir(dir)
+            .output()
+            .expect("failed to run cdir");
+        assert!(output.status.success(), "cdir {:?} failed", args);
+        String::from_utf8(output.stdout).unwrap()
+    }
+
+    fn command_with_stdin(&self, stdin: &str, args: &[&str]) -> Output {
+        let mut child = self
+            .cdir(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .expect("failed to run cdir");
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(stdin.as_bytes())
+            .unwrap();
+        child.wait_with_output().expect("failed to run cdir")
+    }
+
+    fn run(&self, args: &[&str]) -> Output {
+        let output = self.command(args);
+        assert!(
+            output.status.success(),
+            "cdir {:?} failed: {}",
+            args,
+            String::from_utf8_lossy(&output.stderr)
+        );
+        output
+    }
+
+    fn stdout(&self, args: &[&str]) -> String { String::from_utf8(self.run(args).stdout).unwrap() }
+
+    fn db(&self) -> Connection { Connection::open(self.db_path()).unwrap() }
+
+    fn paths(&self) -> Vec<String> {
+        let db = self.db();
+        let mut stmt = db
+            .prepare("SELECT path FROM paths ORDER BY date DESC")
+            .unwrap();
+        stmt.query_map([], |row| row.get(0))
+            .unwrap()
+            .map(|r| r.unwrap())
+            .collect()
+    }
+
+    fn shortcuts(&self) -> Vec<(String, String, Option<String>)> {
+        let db = self.db();
+        let mut stmt = db
+            .prepare("SELECT name, path, description FROM shortcuts ORDER BY name")
+            .unwrap();
+        stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+            .unwrap()
+            .map(|r| r.unwrap())
+            .collect()
+    }
+}
+
+fn path_str(path: &Path) -> &str { path.to_str().unwrap() }
+
+#[test]
+fn test_first_run_initializes_configuration() {
+    let sandbox = Sandbox::empty();
+    let stdout = sandbox.stdout(&["config-file"]);
+    assert!(stdout.contains(path_str(&sandbox.config_path())));
+    assert!(sandbox.config_path().exists());
+    assert!(sandbox.home.path().join(".cdirsh").exists());
+    assert!(
+        sandbox
+            .home
+            .path()
+            .join(".config/cdir/themes/default.yaml")
+            .exists()
+    );
+}
+
+#[test]
+fn test_add_path() {
+    let sandbox = Sandbox::new();
+    sandbox.run(&["add-path", "/tmp/a"]);
+    sandbox.run(&["add-path", "/tmp/b"]);
+    sandbox.run(&["add-path", "/tmp/a"]);
+
+    let paths = sandbox.paths();
+    assert_eq!(paths.len(), 2);
+    assert!(paths.contains(&String::from("/tmp/a")));
+    assert!(paths.contains(&String::from("/tmp/b")));
+
+    let history: i64 = sandbox
+        .db()
+        .query_row("SELECT COUNT(*) FROM paths_history", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(history, 3);
+}
+
+#[test]
+fn test_concurrent_add_path() {
+    let sandbox = Sandbox::new();
+    sandbox.run(&["add-path", "/tmp/init"]);
+
+    // shell hooks of several terminals adding paths at the same time
+    std::thread::scope(|scope| {
+        for writer in 0..6 {
+            let sandbox = &sandbox;
+            scope.spawn(move || {
+                for idx in 0..8 {
+                    sandbox.run(&["add-path", &format!("/tmp/w{}/p{}", writer, idx)]);
+                }
+            });
+        }
+    });
+
+    assert_eq!(sandbox.paths().len(), 49);
+    let history: i64 = sandbox
+        .db()
+        .query_row("SELECT COUNT(*) FROM paths_history", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(history, 49);
+}
+
+#[test]
+fn test_add_path_batch() {
+    let sandbox = Sandbox::new();
+    let output = sandbox.command_with_stdin("/tmp/a\n\n/tmp/b\n/tmp/a\n", &["add-path", "--batch"]);
+    assert!(output.status.success());
+
+    let paths = sandbox.paths();
+    assert_eq!(paths.len(), 2);
+    assert!(paths.contains(&String::from("/tmp/a")));
+    assert!(paths.contains(&String::from("/tmp/b")));
+    let history: i64 = sandbox
+        .db()
+        .query_row("SELECT COUNT(*) FROM paths_history", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(history, 3);
+
+    // a single invalid entry rejects the whole batch
+    let output = sandbox.command_with_stdin("/tmp/c\nme@:/srv\n", &["add-path", "--batch"]);
+    assert!(!output.status.success());
+    assert_eq!(sandbox.paths().len(), 2);
+
+    assert!(!sandbox.command(&["add-path"]).status.success());
+    assert!(
+        !sandbox
+            .command(&["add-path", "--batch", "/tmp/d"])
+            .status
+            .success()
+    );
+}
+
+#[test]
+fn test_add_print_delete_shortcut() {
+    let sandbox = Sandbox::new();
+    sandbox.run(&["add-shortcut", "w", "/home/work", "Work", "--allow-missing"]);
+    sandbox.run(&["add-shortcut", "t", "/tmp"]);
+    assert_eq!(
+        sandbox.shortcuts(),
+        vec![
+            (String::from("t"), String::from("/tmp"), None),
+            (
+                String::from("w"),
+                String::from("/home/work"),
+                Some(String::from("Work"))
+            ),
+        ]
+    );
+
+    assert_eq!(sandbox.stdout(&["print-shortcut", "w"]), "/home/work");
+
+    // nobody can confirm the deletion without a terminal
+    let output = sandbox.command_with_stdin("y\n", &["delete-shortcut", "w"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--yes"));
+    assert_eq!(sandbox.shortcuts().len(), 2);
+
+    sandbox.run(&["delete-shortcut", "w", "--yes"]);
+    assert_eq!(sandbox.shortcuts().len(), 1);
+    assert_eq!(
+        sandbox.command(&["print-shortcut", "w"]).status.code(),
+        Some(1)
+    );
+}
+
+#[test]
+fn test_add_shortcut_conflicts() {
+    let sandbox = Sandbox::new();
+    sandbox.run(&["add-shortcut", "w", "/home/work", "--allow-missing"]);
+
+    let output = sandbox.command(&["add-shortcut", "w", "/home/other", "--allow-missing"]);
+    assert!(!output.status.success());
+    assert_eq!(sandbox.stdout(&["print-shortcut", "w"]), "/home/work");
+
+    sandbox.run(&[
+        "add-shortcut",
+        "w",
+        "/home/other",
+        "--allow-missing",
+        "--force",
+    ]);
+    assert_eq!(sandbox.stdout(&["print-shortcut", "w"]), "/home/other");
+
+    // a shortcut of the same path is added with a warning
+    let output = sandbox.run(&["add-shortcut", "o", "/home/other", "--allow-missing"]);
+    assert!(String::from_utf8_lossy(&output.stderr).contains("shortcuts w"));
+    assert_eq!(sandbox.shortcuts().len(), 2);
+}
+
+#[test]
+fn test_rename_update_shortcut() {
+    let sandbox = Sandbox::new();
+    sandbox.run(&["add-shortcut", "w", "/home/work", "Work", "--allow-missing"]);
+    sandbox.run(&["add-shortcut", "o", "/home/other", "--allow-missing"]);
+
+    sandbox.run(&["rename-shortcut", "w", "work"]);
+    assert_eq!(sandbox.stdout(&["print-shortcut", "work"]), "/home/work");
+    let code = |args: &[&str]| sandbox.command(args).status.code();
+    assert_eq!(code(&["rename-shortcut", "work", "o"]), Some(4));
+    assert_eq!(code(&["rename-shortcut", "missing", "m"]), Some(1));
+
+    sandbox.run(&[
+        "update-shortcut",
+        "work",
+        "--path",
+        "/home/work2",
+        "--allow-missing",
+    ]);
+    assert_eq!(
+        sandbox.shortcuts()[1],
+        (
+            String::from("work"),
+            String::from("/home/work2"),
+            Some(String::from("Work"))
+        )
+    );
+    sandbox.run(&["update-shortcut", "work", "--description", ""]);
+    assert_eq!(sandbox.shortcuts()[1].2, None);
+    assert_eq!(
+        code(&["update-shortcut", "work", "--path", "/no/such/dir"]),
+        Some(1)
+    );
+    assert_eq!(
+        code(&["update-shortcut", "missing", "--path", "/tmp"]),
+        Some(1)
+    );
+    assert_eq!(code(&["update-shortcut", "work"]), Some(64));
+    assert_eq!(sandbox.stdout(&["print-shortcut", "work"]), "/home/work2");
+}
+
+#[test]
+fn test_expand() {
+    let sandbox = Sandbox::new();
+    let reports = sandbox.home.path().join("documents/reports/2024");
+    fs::create_dir_all(&reports).unwrap();
+    let documents = sandbox.home.path().join("documents");
+    sandbox.run(&["add-shortcut", "docs", documents.to_str().unwrap()]);
+
+    assert_eq!(
+        sandbox.stdout(&["expand", "docs/reports/2024"]),
+        format!("{}\n", reports.display())
+    );
+    assert_eq!(
+        sandbox.stdout(&["expand", "--eval", "docs/reports/2024/"]),
+        format!("cd '{}'\n", reports.display())
+    );
+    let code = |args: &[&str]| sandbox.command(args).status.code();
+    assert_eq!(code(&["expand", "docs/reports/2025"]), Some(1));
+    assert_eq!(code(&["expand", "unknown/reports"]), Some(1));
+}
+
+#[test]
+fn test_broken_shortcuts() {
+    let sandbox = Sandbox::new();
+    let dir = sandbox.home.path().join("project");
+    fs::create_dir(&dir).unwrap();
+
+    let output = sandbox.command(&["add-shortcut", "gone", "/no/such/dir"]);
+    assert!(!output.status.success());
+    assert!(sandbox.shortcuts().is_empty());
+
+    sandbox.run(&["add-shortcut", "gone", "/no/such/dir", "--allow-missing"]);
+    sandbox.run(&["add-shortcut", "p", path_str(&dir)]);
+    sandbox.run(&["add-shortcut", "srv", "me@server:/srv/app"]);
+    assert_eq!(sandbox.stdout(&["check-shortcuts"]), "gone /no/such/dir\n");
+
+    fs::remove_dir(&dir).unwrap();
+    assert_eq!(
+        sandbox.stdout(&["check-shortcuts"]),
+        format!("gone /no/such/dir\np {}\n", dir.display())
+    );
+}
+
+#[test]
+fn test_shortcut_hints() {
+    let sandbox = Sandbox::new();
+    sandbox.run(&[
+        "add-shortcut",
+        "py",
+        "/home/py",
+        "--allow-missing",
+        "--env",
+        "VIRTUAL_ENV=/home/py/.venv",
+        "--post-cd",
+        "source .venv/bin/activate",
+    ]);
+    sandbox.run(&["add-shortcut", "t", "/tmp"]);
+
+    assert_eq!(sandbox.stdout(&["print-shortcut", "py"]), "/home/py");
+    assert_eq!(
+        sandbox.stdout(&["print-shortcut", "--eval", "py"]),
+        "cd '/home/py'\nexport VIRTUAL_ENV='/home/py/.venv'\nsource .venv/bin/activate\n"
+    );
+    assert_eq!(
+        sandbox.stdout(&["print-shortcut", "--eval", "t"]),
+        "cd '/tmp'\n"
+    );
+    let output = sandbox.command(&["print-shortcut", "--eval", "unknown"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+
+    let output = sandbox.command(&["add-shortcut", "bad", "/tmp", "--env", "1BAD=x"]);
+    assert!(!output.status.success());
+    assert_eq!(sandbox.shortcuts().len(), 2);
+}
+
+#[test]
+fn test_import_paths() {
+    let sandbox = Sandbox::new();
+    let file = sandbox.file(
+        "paths.yaml",
+        "- date: \"1700000000\"\n  path: /tmp/old\n- date: \"1700000100\"\n  path: /tmp/new\n",
+    );
+    assert_eq!(
+        sandbox.stdout(&["import-paths", path_str(&file)]),
+        "2 imported, 0 duplicates skipped, 0 invalid\n"
+    );
+    assert_eq!(
+        sandbox.paths(),
+        vec![String::from("/tmp/new"), String::from("/tmp/old")]
+    );
+    assert_eq!(
+        sandbox.stdout(&["import-paths", path_str(&file)]),
+        "0 imported, 2 duplicates skipped, 0 invalid\n"
+    );
+    assert!(
+        !sandbox
+            .command(&["import-paths", "/nonexistent.yaml"])
+            .status
+            .success()
+    );
+}
+
+#[test]
+fn test_import_shortcuts_rejects() {
+    let sandbox = Sandbox::new();
+    let file = sandbox.file(
+        "shortcuts.yaml",
+        "- name: t\n  path: /tmp\n- name: u\n  pth: /usr\n",
+    );
+    let rejects = sandbox.home.path().join("rejects.yaml");
+
+    let output = sandbox.command(&["import-shortcuts", path_str(&file), "--validate-only"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("rejected entry 2 (line 3)"));
+    assert!(sandbox.shortcuts().is_empty());
+
+    let stdout = sandbox.stdout(&[
+        "import-shortcuts",
+        path_str(&file),
+        "--rejects",
+        path_str(&rejects),
+    ]);
+    assert_eq!(stdout, "1 imported, 0 duplicates skipped, 1 invalid\n");
+    assert_eq!(sandbox.shortcuts().len(), 1);
+    assert!(fs::read_to_string(&rejects).unwrap().contains("pth: /usr"));
+}
+
+#[test]
+fn test_export_import_toml_json() {
+    let sandbox = Sandbox::new();
+    sandbox.run(&["add-shortcut", "t", "/tmp", "Temporary", "--allow-missing"]);
+    sandbox.run(&["add-path", "/tmp/here"]);
+    let toml = sandbox.home.path().join("shortcuts.toml");
+    sandbox.run(&["export-shortcuts", path_str(&toml)]);
+    assert!(
+        fs::read_to_string(&toml)
+            .unwrap()
+            .starts_with("[[shortcuts]]")
+    );
+    let json = sandbox.stdout(&["export-paths", "--format", "json"]);
+    assert!(json.contains("\"path\": \"/tmp/here\""));
+
+    let other = Sandbox::new();
+    assert_eq!(
+        other.stdout(&["import-shortcuts", path_str(&toml)]),
+        "1 imported, 0 duplicates skipped, 0 invalid\n"
+    );
+    assert_eq!(other.shortcuts(), sandbox.shortcuts());
+    let paths = other.file("paths.txt", &json);
+    other.run(&["import-paths", path_str(&paths), "--format", "json"]);
+    assert_eq!(other.paths(), vec!["/tmp/here"]);
+}
+
+#[test]
+fn test_dry_run() {
+    let sandbox = Sandbox::new();
+    sandbox.run(&["add-shortcut", "t", "/tmp", "--allow-missing"]);
+    let file = sandbox.file(
+        "shortcuts.yaml",
+        "- name: t\n  path: /usr\n- name: u\n  path: /usr\n",
+    );
+
+    let stdout = sandbox.stdout(&["--dry-run", "import-shortcuts", path_str(&file)]);
+    assert_eq!(
+        stdout,
+        "would replace the shortcut t (/tmp)\n\
+         2 imported, 0 duplicates skipped, 0 invalid (dry run, nothing imported)\n"
+    );
+    assert_eq!(
+        sandbox.stdout(&["delete-shortcut", "t", "--dry-run"]),
+        "would delete the shortcut t (/tmp)\n"
+    );
+    assert_eq!(
+        sandbox.stdout(&["add-shortcut", "t", "/usr", "--force", "--dry-run"]),
+        "would replace the shortcut t (/tmp)\n"
+    );
+    assert_eq!(
+        sandbox.stdout(&["rename-shortcut", "t", "u", "--dry-run"]),
+        "would rename the shortcut t to u\n"
+    );
+    assert_eq!(
+        sandbox.stdout(&["update-shortcut", "t", "--path", "/usr", "--dry-run"]),
+        "would update the shortcut t (/usr)\n"
+    );
+    assert_eq!(
+        sandbox.shortcuts(),
+        vec![(String::from("t"), String::from("/tmp"), None)]
+    );
+
+    assert!(
+        !sandbox
+            .command(&["add-shortcut", "u", "/usr", "--dry-run"])
+            .status
+            .success()
+    );
+    assert!(
+        !sandbox
+            .command(&["add-path", "/tmp/a", "--dry-run"])
+            .status
+            .success()
+    );
+    assert!(sandbox.paths().is_empty());
+}
+
+#[test]
+fn test_profile() {
+    let sandbox = Sandbox::new();
+    let output = sandbox.command(&["--profile", "add-path", "/tmp/a"]);
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    for step in ["config_load", "db_open", "schema_check", "run"] {
+        assert!(stderr.contains(step), "missing {} in {}", step, stderr);
+    }
+    let output = sandbox.command(&["add-path", "/tmp/b"]);
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("db_open"));
+}
+
+#[test]
+fn test_logs_on_stderr() {
+    let sandbox = Sandbox::new();
+    let file = sandbox.file("shortcuts.yaml", "- name: u\n  pth: /usr\n");
+
+    let output = sandbox.command(&["import-shortcuts", path_str(&file)]);
+    assert!(String::from_utf8_lossy(&output.stderr).contains("WARN: "));
+    let output = sandbox.command(&["-q", "import-shortcuts", path_str(&file)]);
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("WARN: "));
+    let output = sandbox.command(&["-v", "import-shortcuts", path_str(&file)]);
+    assert!(String::from_utf8_lossy(&output.stderr).contains("DEBUG: "));
+
+    let quiet = Sandbox::with_config("log_to_stderr: false\n");
+    let file = quiet.file("shortcuts.yaml", "- name: u\n  pth: /usr\n");
+    let output = quiet.command(&["import-shortcuts", path_str(&file)]);
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("WARN: "));
+}
+
+#[test]
+fn test_log_file() {
+    let sandbox = Sandbox::new();
+    let log_file = sandbox.home.path().join("logs/cdir.log");
+    fs::write(
+        sandbox.config_path(),
+        format!(
+            "db_path: {}\nlog_file: {}\nlog_format: json\n",
+            sandbox.db_path().display(),
+            log_file.display()
+        ),
+    )
+    .unwrap();
+
+    sandbox.run(&["add-path", "/tmp/a"]);
+    let logs = fs::read_to_string(&log_file).unwrap();
+    assert!(!logs.is_empty());
+    assert!(!logs.contains("\"DEBUG\""));
+
+    let output = sandbox.command_with_env(
+        &[("CDIR_LOG", "cdir_core::store=debug")],
+        &["add-path", "/tmp/b"],
+    );
+    assert!(output.status.success());
+    let logs = fs::read_to_string(&log_file).unwrap();
+    let line = logs
+        .lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+        .find(|line| line["level"] == "DEBUG")
+        .unwrap();
+    assert_eq!(line["target"], "cdir_core::store");
+}
+
+#[test]
+fn test_merge() {
+    let sandbox = Sandbox::new();
+    sandbox.run(&["add-path", "/tmp/here"]);
+    sandbox.run(&["add-shortcut", "w", "/tmp/here", "--allow-missing"]);
+    let laptop = Sandbox::new();
+    laptop.run(&["add-path", "/tmp/there"]);
+    laptop.run(&["add-shortcut", "w", "/tmp/there", "--allow-missing"]);
+    laptop.run(&["add-shortcut", "t", "/tmp/there", "--allow-missing"]);
+    let laptop_db = laptop.db_path();
+
+    assert_eq!(
+        sandbox.stdout(&["merge", "--from", path_str(&laptop_db)]),
+        "paths: 1 added, 0 updated\n\
+         history: 1 added, 0 duplicates skipped\n\
+         shortcuts: 1 added, 0 duplicates skipped, 1 conflicts\n  \
+         w: kept /tmp/here, dropped /tmp/there\n"
+    );
+    assert_eq!(
+        sandbox.paths(),
+        vec![String::from("/tmp/there"), String::from("/tmp/here")]
+    );
+    assert_eq!(sandbox.stdout(&["print-shortcut", "t"]), "/tmp/there");
+    assert_eq!(sandbox.stdout(&["print-shortcut", "w"]), "/tmp/here");
+    // the merged database is left unchanged
+    assert_eq!(laptop.paths(), vec![String::from("/tmp/there")]);
+    assert!(
+        !sandbox
+            .command(&["merge", "--from", "/nonexistent.db"])
+            .status
+            .success()
+    );
+}
+
+#[test]
+fn test_import_shortcuts() {
+    let sandbox = Sandbox::new();
+    sandbox.run(&["add-shortcut", "w", "/old/work", "--allow-missing"]);
+    let file = sandbox.file(
+        "shortcuts.yaml",
+        "- name: w\n  path: /home/work\n  description: Work\n- name: t\n  path: /tmp\n",
+    );
+    sandbox.run(&["import-shortcuts", path_str(&file)]);
+    assert_eq!(
+        sandbox.shortcuts(),
+        vec![
+            (String::from("t"), String::from("/tmp"), None),
+            (
+                String::from("w"),
+                String::from("/home/work"),
+                Some(String::from("Work"))
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_exec() {
+    let sandbox = Sandbox::new();
+    let file = sandbox.file(
+        "batch.yaml",
+        "- add_shortcut: {name: w, path: /home/work, description: Work}\n- add_shortcut: {name: t, path: /tmp}\n",
+    );
+
+    let stdout = sandbox.stdout(&["exec", path_str(&file), "--dry-run"]);
+    assert!(stdout.contains("add shortcut w /home/work"));
+    assert!(sandbox.shortcuts().is_empty());
+
+    sandbox.run(&["exec", path_str(&file)]);
+    assert_eq!(sandbox.shortcuts().len(), 2);
+
+    let failing = sandbox.file(
+        "failing.yaml",
+        "- delete_shortcut: w\n- mark_sensitive: /not/visited\n",
+    );
+    assert!(
+        !sandbox
+            .command(&["exec", path_str(&failing)])
+            .status
+            .success()
+    );
+    assert_eq!(sandbox.shortcuts().len(), 2);
+}
+
+#[test]
+fn test_bundle() {
+    let sandbox = Sandbox::new();
+    sandbox.run(&["add-shortcut", "mine", "/tmp/mine", "--allow-missing"]);
+    let v1 = sandbox.file(
+        "v1.yaml",
+        "name: team\nversion: 1\nshortcuts:\n  - name: mono\n    path: /src/mono\n  - name: mine\n    path: /src/other\n",
+    );
+    let stdout = sandbox.stdout(&["bundle", "install", path_str(&v1)]);
+    assert!(stdout.contains("1 added"));
+    assert!(stdout.contains("mine: kept the existing shortcut"));
+    assert_eq!(sandbox.shortcuts().len(), 2);
+
+    assert_eq!(
+        sandbox.stdout(&["bundle", "list"]),
+        "team 1 (1 shortcuts)\n"
+    );
+
+    sandbox.run(&["bundle", "remove", "team", "-y"]);
+    assert_eq!(
+        sandbox.shortcuts(),
+        vec![(String::from("mine"), String::from("/tmp/mine"), None)]
+    );
+    assert!(
+        !sandbox
+            .command(&["bundle", "remove", "team", "-y"])
+            .status
+            .success()
+    );
+}
+
+#[test]
+fn test_signed_bundle() {
+    let sandbox = Sandbox::new();
+    let key = sandbox.home.path().join("team.key");
+    let public_key = sandbox.stdout(&["bundle", "keygen", path_str(&key)]);
+    let public_key = public_key.trim();
+    // an existing key is not overwritten
+    assert!(
+        !sandbox
+            .command(&["bundle", "keygen", path_str(&key)])
+            .status
+            .success()
+    );
+
+    let file = sandbox.file(
+        "team.yaml",
+        "name: team\nversion: 1\nshortcuts:\n  - name: mono\n    path: /src/mono\n",
+    );
+    let install = |sandbox: &Sandbox| {
+        sandbox
+            .command(&[
+                "bundle",
+                "install",
+                path_str(&file),
+                "--public-key",
+                public_key,
+            ])
+            .status
+            .code()
+    };
+    // not signed yet
+    assert_eq!(install(&sandbox), Some(4));
+
+    sandbox.run(&["bundle", "sign", path_str(&file), "--key", path_str(&key)]);
+    assert_eq!(install(&sandbox), Some(0));
+    assert_eq!(sandbox.shortcuts().len(), 1);
+
+    // a bundle changed after being signed is refused, also with the key of the configuration
+    fs::write(
+        &file,
+        "name: team\nversion: 2\nshortcuts:\n  - name: mono\n    path: /tmp/evil\n",
+    )
+    .unwrap();
+    assert_eq!(install(&sandbox), Some(4));
+    let configured = Sandbox::with_config(&format!("bundle_public_keys: [{}]\n", public_key));
+    assert!(
+        !configured
+            .command(&["bundle", "install", path_str(&file)])
+            .status
+            .success()
+    );
+    assert_eq!(
+        sandbox.shortcuts(),
+        vec![(String::from("mono"), String::from("/src/mono"), None)]
+    );
+}
+
+#[test]
+fn test_import_shortcuts_sha256() {
+    let sandbox = Sandbox::new();
+    let file = sandbox.file("shortcuts.yaml", "- name: t\n  path: /tmp\n");
+
+    assert!(
+        !sandbox
+            .command(&["import-shortcuts", path_str(&file), "--sha256", "0123"])
+            .status
+            .success()
+    );
+    assert!(sandbox.shortcuts().is_empty());
+
+    sandbox.run(&[
+        "import-shortcuts",
+        path_str(&file),
+        "--sha256",
+        "7df75e6c774aedda8a2251287dfe5aad90b079a8398550cd2d950231d51b7717",
+    ]);
+    assert_eq!(sandbox.shortcuts().len(), 1);
+}
+
+#[test]
+fn test_lasts() {
+    let sandbox = Sandbox::new();
+    sandbox.run(&["add-path", "/tmp/first"]);
+    sandbox.run(&["add-path", "/tmp/second"]);
+    let stdout = sandbox.stdout(&["lasts"]);
+    assert!(stdout.contains("/tmp/first"));
+    assert!(stdout.contains("/tmp/second"));
+}
+
+#[test]
+fn test_lasts_visits() {
+    let sandbox = Sandbox::new();
+    sandbox.run(&["add-path", "/tmp/first"]);
+    sandbox.run(&["add-path", "/tmp/second"]);
+    sandbox.run(&["add-path", "/tmp/second"]);
+
+    let stdout = sandbox.stdout(&["lasts", "--visits"]);
+    assert_eq!(stdout.matches("/tmp/second").count(), 2);
+
+    let stdout = sandbox.stdout(&["lasts", "--visits", "--collapse-repeats"]);
+    assert_eq!(stdout.matches("/tmp/second").count(), 1);
+    assert!(stdout.contains("/tmp/second ×2"));
+    assert!(stdout.contains("/tmp/first\n"));
+
+    assert!(
+        !sandbox
+            .command(&["lasts", "--collapse-repeats"])
+            .status
+            .success()
+    );
+}
+
+#[test]
+fn test_report() {
+    let sandbox = Sandbox::new();
+    sandbox.run(&["add-path", "/tmp/work"]);
+    sandbox.run(&["add-path", "/tmp/work"]);
+    sandbox.run(&["add-shortcut", "w", "/tmp/work", "--allow-missing"]);
+
+    let stdout = sandbox.stdout(&["report"]);
+    assert!(stdout.starts_with("# cdir usage report"));
+    assert!(stdout.contains("| /tmp/work | 2 |"));
+    assert!(stdout.contains("| w | /tmp/work |"));
+
+    let stdout = sandbox.stdout(&["report", "--format", "html", "--since", "2w"]);
+    assert!(stdout.contains("<td>/tmp/work</td><td>2</td>"));
+
+    assert!(
+        !sandbox
+            .command(&["report", "--since", "soon"])
+            .status
+            .success()
+    );
+}
+
+#[test]
+fn test_export_history() {
+    let sandbox = Sandbox::new();
+    sandbox.run(&["add-path", "/tmp/work,old"]);
+    sandbox.run(&["add-path", "/tmp/secret"]);
+    sandbox.run(&["mark-sensitive", "/tmp/secret"]);
+
+    let stdout = sandbox.stdout(&["export-history", "--since", "1d"]);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[0], "timestamp,path,shortcut,hostname");
+    assert_eq!(lines.len(), 2);
+    assert!(lines[1].contains(",\"/tmp/work,old\","));
+
+    let stdout = sandbox.stdout(&["export-history", "--include-sensitive"]);
+    assert_eq!(stdout.lines().count(), 3);
+    assert!(
+        !sandbox
+            .command(&["export-history", "--since", "soon"])
+            .status
+            .success()
+    );
+}
+
+#[test]
+fn test_export_paths_sensitive() {
+    let sandbox = Sandbox::new();
+    sandbox.run(&["add-path", "/tmp/work"]);
+    sandbox.run(&["add-path", "/tmp/secret"]);
+    sandbox.run(&["mark-sensitive", "/tmp/secret"]);
+
+    let yaml = sandbox.stdout(&["export-paths"]);
+    assert!(yaml.contains("/tmp/work"));
+    assert!(!yaml.contains("/tmp/secret"));
+    let yaml = sandbox.stdout(&["export-paths", "--include-sensitive"]);
+    assert!(yaml.contains("/tmp/secret"));
+}
+
+#[test]
+fn test_lasts_pane() {
+    let sandbox = Sandbox::new();
+    sandbox.command_with_env(&[("TMUX_PANE", "%1")], &["add-path", "/tmp/first"]);
+    sandbox.command_with_env(&[("TMUX_PANE", "%2")], &["add-path", "/tmp/second"]);
+    sandbox.run(&["add-path", "/tmp/third"]);
+
+    let output = sandbox.command_with_env(&[("TMUX_PANE", "%1")], &["lasts", "--pane"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("/tmp/first"));
+    assert!(!stdout.contains("/tmp/second"));
+    assert!(!stdout.contains("/tmp/third"));
+
+    assert!(!sandbox.command(&["lasts", "--pane"]).status.success());
+}
+
+#[test]
+fn test_pretty_print_path() {
+    let sandbox = Sandbox::new();
+    sandbox.run(&["add-shortcut", "w", "/home/work", "--allow-missing"]);
+    assert_eq!(
+        sandbox.stdout(&["pretty-print-path", "/home/work/project", "false"]),
+        "[w]/project"
+    );
+}
+
+#[test]
+fn test_columns_configuration_is_validated() {
+    let sandbox = Sandbox::with_config(
+        "columns:\n  history:\n    - name: path\n    - name: description\n      weight: 2\n",
+    );
+    sandbox.run(&["add-path", "/tmp/a"]);
+
+    let sandbox = Sandbox::with_config("columns:\n  history:\n    - name: owner\n");
+    let output = sandbox.command(&["add-path", "/tmp/a"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("unknown column 'owner'"));
+
+    let sandbox = Sandbox::with_config(
+        "columns:\n  shortcuts:\n    - name: path\n      width: 10\n      weight: 1\n",
+    );
+    let output = sandbox.command(&["add-path", "/tmp/a"]);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_commands_configuration_is_validated() {
+    let sandbox = Sandbox::with_config("commands:\n  o: xdg-open {path}\n");
+    sandbox.run(&["add-path", "/tmp/a"]);
+
+    let sandbox = Sandbox::with_config("commands:\n  open: xdg-open {path}\n");
+    let output = sandbox.command(&["add-path", "/tmp/a"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("single character"));
+
+    let sandbox = Sandbox::with_config("commands:\n  '1': xdg-open {path}\n");
+    let output = sandbox.command(&["add-path", "/tmp/a"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("reserved"));
+}
+
+#[test]
+fn test_gui_fuzzy_requires_query() {
+    let sandbox = Sandbox::new();
+    let output = sandbox.command(&["gui", "--fuzzy"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--query"));
+}
+
+#[test]
+fn test_gui_auto_selects_single_result() {
+    let sandbox = Sandbox::new();
+    sandbox.run(&["add-path", "/tmp/project"]);
+    sandbox.run(&["add-path", "/tmp/other"]);
+    assert_eq!(
+        sandbox.stdout(&["gui", "--query", "proj", "--auto"]).trim(),
+        "/tmp/project"
+    );
+
+    let sandbox = Sandbox::with_config("auto_select_single_result: true\n");
+    sandbox.run(&["add-path", "/tmp/project"]);
+    let file = sandbox.home.path().join("selected");
+    sandbox.run(&["gui", "--query", "proj", path_str(&file)]);
+    assert_eq!(fs::read_to_string(file).unwrap(), "/tmp/project");
+}
+
+#[test]
+fn test_gui_view() {
+    let sandbox = Sandbox::new();
+    sandbox.run(&["add-shortcut", "www", "/srv/www", "--allow-missing"]);
+    sandbox.run(&["add-path", "/tmp/www"]);
+    sandbox.run(&["add-path", "/tmp/www-old"]);
+    assert_eq!(
+        sandbox
+            .stdout(&[
+                "gui",
+                "--view",
+                "shortcuts",
+                "--select-only",
+                "-q",
+                "www",
+                "--auto"
+            ])
+            .trim(),
+        "/srv/www"
+    );
+    let output = sandbox.command(&["gui", "--view", "bookmarks"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("shortcuts"));
+}
+
+#[test]
+fn test_picker() {
+    let sandbox = Sandbox::new();
+    sandbox.run(&["add-shortcut", "t", "/tmp", "--allow-missing"]);
+    sandbox.run(&["add-path", "/tmp/project"]);
+    sandbox.run(&["add-path", "/tmp/other"]);
+    sandbox.run(&["add-path", "/var/project"]);
+    assert_eq!(
+        sandbox.stdout(&["picker", "--query", "project"]),
+        "/var/project\n/tmp/project\n"
+    );
+    assert_eq!(
+        sandbox.stdout(&[
+            "picker", "--query", "project", "--format", "vim", "--limit", "1"
+        ]),
+        "/var/project\t\t\n"
+    );
+    let fuzzy = sandbox.stdout(&["picker", "-q", "tmp proj", "-f", "--format", "vim"]);
+    let fields: Vec<&str> = fuzzy.trim_end().split('\t').collect();
+    assert_eq!(fields[..2], ["/tmp/project", "t"]);
+    assert!(fields[2].parse::<u32>().is_ok());
+
+    let selected = sandbox.stdout(&["gui", "-q", "tmp proj", "-f", "--auto", "--format", "json"]);
+    assert!(selected.starts_with(r#"{"path":"/tmp/project","shortcut":"t","score":"#));
+    assert!(
+        !sandbox
+            .command(&["gui", "-q", "proj", "--auto", "--format", "json", "--eval"])
+            .status
+            .success()
+    );
+}
+
+/// Visit `a`, `a/b`, `a` and `c` in a directory of the sandbox, the smart suggestions of `a`
+/// being `a/b` and `c`, and return `a`
+fn visit_smart_suggestions(sandbox: &Sandbox) -> PathBuf {
+    let root = sandbox.home.path().join("sb");
+    for dir in ["a/b", "c"] {
+        fs::create_dir_all(root.join(dir)).unwrap();
+    }
+    for dir in ["a", "a/b", "a", "c"] {
+        sandbox.run(&["add-path", root.join(dir).to_str().unwrap()]);
+    }
+    root.join("a")
+}
+
+/// Whether the lines of `output` are listed once each
+fn no_duplicates(output: &str) -> bool {
+    let lines: Vec<&str> = output.lines().collect();
+    let unique: std::collections::HashSet<&str> = lines.iter().copied().collect();
+    !lines.is_empty() && unique.len() == lines.len()
+}
+
+#[test]
+fn test_picker_without_smart_suggestions() {
+    let sandbox = Sandbox::new();
+    let cwd = visit_smart_suggestions(&sandbox);
+    // the smart suggestions of the current directory are not paths of the history
+    let vim = sandbox.stdout_in(&cwd, &["picker", "--format", "vim"]);
+    assert!(no_duplicates(&vim), "{}", vim);
+    assert_eq!(vim.lines().count(), 3);
+    assert_eq!(
+        sandbox
+            .stdout_in(&cwd, &["picker", "--limit", "2"])
+            .lines()
+            .count(),
+        2
+    );
+}
+
+#[test]
+fn test_hooks() {
+    let sandbox = Sandbox::with_config(
+        "hooks:\n  on_add_path: echo \"$CDIR_HOOK $CDIR_PATH\" >> \"$HOME/hooks.log\"\n  on_select: echo \"$CDIR_HOOK $CDIR_SHORTCUT $CDIR_PATH\" >> \"$HOME/hooks.log\"\n  on_delete_shortcut: echo \"$CDIR_HOOK $CDIR_SHORTCUT $CDIR_PATH\" >> \"$HOME/hooks.log\"\n",
+    );
+    sandbox.run(&["add-path", "/home/work"]);
+    sandbox.run(&["add-shortcut", "w", "/home/work", "--allow-missing"]);
+    assert_eq!(sandbox.stdout(&["print-shortcut", "w"]), "/home/work");
+    assert!(
+        !sandbox
+            .command(&["print-shortcut", "unknown"])
+            .status
+            .success()
+    );
+    sandbox.run(&["delete-shortcut", "w", "--yes"]);
+    assert!(
+        !sandbox
+            .command(&["delete-shortcut", "unknown", "--yes"])
+            .status
+            .success()
+    );
+
+    assert_eq!(
+        fs::read_to_string(sandbox.home.path().join("hooks.log")).unwrap(),
+        "on_add_path /home/work\non_select w /home/work\non_delete_shortcut w /home/work\n"
+    );
+}
+
+#[test]
+fn test_hosts() {
+    let sandbox = Sandbox::new();
+    sandbox.command_with_env(&[("HOSTNAME", "laptop")], &["add-path", "/tmp/laptop"]);
+    sandbox.command_with_env(&[("HOSTNAME", "server")], &["add-path", "/tmp/server"]);
+
+    let stdout = sandbox.stdout(&["lasts", "--host", "server"]);
+    assert!(stdout.contains("/tmp/server"));
+    assert!(!stdout.contains("/tmp/laptop"));
+
+    let stdout = sandbox.stdout(&["lasts"]);
+    assert!(stdout.contains("/tmp/server"));
+    assert!(stdout.contains("/tmp/laptop"));
+
+    let sandbox_current_host = Sandbox::with_config("current_host_only: true\n");
+    sandbox_current_host.command_with_env(&[("HOSTNAME", "laptop")], &["add-path", "/tmp/laptop"]);
+    sandbox_current_host.command_with_env(&[("HOSTNAME", "server")], &["add-path", "/tmp/server"]);
+    let output = sandbox_current_host.command_with_env(&[("HOSTNAME", "laptop")], &["lasts"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("/tmp/laptop"));
+    assert!(!stdout.contains("/tmp/server"));
+}
+
+#[test]
+fn test_sensitive_paths() {
+    let sandbox = Sandbox::new();
+    sandbox.run(&["add-path", "/tmp/public"]);
+    sandbox.run(&["add-path", "/tmp/client"]);
+    sandbox.run(&["mark-sensitive", "/tmp/client"]);
+    assert!(
+        !sandbox
+            .command(&["mark-sensitive", "/tmp/unknown"])
+            .status
+            .success()
+    );
+
+    let stdout = sandbox.stdout(&["lasts"]);
+    assert!(stdout.contains("/tmp/public"));
+    assert!(!stdout.contains("/tmp/client"));
+    let stdout = sandbox.stdout(&["lasts", "--include-sensitive"]);
+    assert!(stdout.contains("/tmp/client"));
+
+    sandbox.run(&["mark-sensitive", "--clear", "/tmp/client"]);
+    assert!(sandbox.stdout(&["lasts"]).contains("/tmp/client"));
+}
+
+#[test]
+fn test_link_paths() {
+    let sandbox = Sandbox::new();
+    sandbox.run(&["add-path", "/tmp/real"]);
+    sandbox.run(&["add-path", "/tmp/alias"]);
+    sandbox.run(&["link-paths", "/tmp/real", "/tmp/alias"]);
+
+    let stdout = sandbox.stdout(&["lasts"]);
+    assert!(stdout.contains("/tmp/alias"));
+    assert!(!stdout.contains("/tmp/real"));
+}
+
+#[test]
+fn test_containers() {
+    let sandbox = Sandbox::new();
+    sandbox.command_with_env(&[("CONTAINER_ID", "box")], &["add-path", "/tmp/box"]);
+    sandbox.command_with_env(&[("CONTAINER_ID", "other")], &["add-path", "/tmp/other"]);
+
+    let output = sandbox.command_with_env(&[("CONTAINER_ID", "box")], &["lasts"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("/tmp/box"));
+    assert!(!stdout.contains("/tmp/other"));
+
+    let sandbox_mixed = Sandbox::with_config("mix_container_history: true\n");
+    sandbox_mixed.command_with_env(&[("CONTAINER_ID", "box")], &["add-path", "/tmp/box"]);
+    sandbox_mixed.command_with_env(&[("CONTAINER_ID", "other")], &["add-path", "/tmp/other"]);
+    let output = sandbox_mixed.command_with_env(&[("CONTAINER_ID", "box")], &["lasts"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("/tmp/box"));
+    assert!(stdout.contains("/tmp/other"));
+}
+
+#[test]
+fn test_remote_entries() {
+    let sandbox = Sandbox::with_config("remote_command_template: mosh {target} -- cd {path}\n");
+    sandbox.run(&["add-shortcut", "srv", "me@server:/srv/app"]);
+    assert_eq!(
+        sandbox.stdout(&["print-shortcut", "--eval", "srv"]),
+        "mosh me@server -- cd /srv/app\n"
+    );
+    assert_eq!(
+        sandbox.stdout(&["print-shortcut", "srv"]),
+        "me@server:/srv/app"
+    );
+
+    assert!(
+        !sandbox
+            .command(&["add-shortcut", "bad", "me@server:srv"])
+            .status
+            .success()
+    );
+    assert!(!sandbox.command(&["add-path", "me@:/srv"]).status.success());
+    sandbox.run(&["add-path", "me@server:~/work"]);
+    assert_eq!(sandbox.paths(), vec![String::from("me@server:~/work")]);
+
+    let sandbox = Sandbox::with_config("remote_command_template: ssh server\n");
+    assert!(!sandbox.command(&["lasts"]).status.success());
+}
+
+#[test]
+fn test_help_keys() {
+    let sandbox = Sandbox::new();
+    let output = sandbox.stdout(&["help-keys"]);
+    assert!(output.starts_with("All views:"));
+    assert!(output.contains("esc or ctrl+q"));
+    assert!(output.contains("Directory history:"));
+}
+
+#[test]
+fn test_suggest_shortcuts() {
+    let sandbox = Sandbox::new();
+    for _ in 0..10 {
+        sandbox.run(&["add-path", "/home/me/work/api"]);
+        sandbox.run(&["add-path", "/home/me/old/api"]);
+    }
+    sandbox.run(&["add-path", "/home/me/work/web"]);
+    assert_eq!(
+        sandbox.stdout(&["suggest-shortcuts"]),
+        "api /home/me/old/api (10 visits)\nwork-api /home/me/work/api (10 visits)\n"
+    );
+
+    let output = sandbox.command(&["suggest-shortcuts", "--accept", "web"]);
+    assert!(!output.status.success());
+    assert!(sandbox.shortcuts().is_empty());
+
+    sandbox.run(&["suggest-shortcuts", "--accept", "work-api"]);
+    assert_eq!(
+        sandbox.shortcuts(),
+        vec![(
+            String::from("work-api"),
+            String::from("/home/me/work/api"),
+            None
+        )]
+    );
+    sandbox.run(&["suggest-shortcuts", "--all"]);
+    assert_eq!(sandbox.shortcuts().len(), 2);
+    assert_eq!(sandbox.stdout(&["suggest-shortcuts"]), "");
+}
+
+#[test]
+fn test_serve() {
+    let sandbox = Sandbox::new();
+    sandbox.run(&["add-path", "/tmp/project"]);
+    let output = sandbox.command_with_stdin(
+        concat!(
+            r#"{"jsonrpc": "2.0", "method": "addPath", "params": {"path": "/tmp/other"}}"#,
+            "\n",
+            r#"{"jsonrpc": "2.0", "id": 1, "method": "query", "params": {"search": "project"}}"#,
+            "\n",
+            r#"{"jsonrpc": "2.0", "id": 2, "method": "listShortcuts"}"#,
+            "\n",
+        ),
+        &["serve", "--stdio"],
+    );
+    assert!(output.status.success());
+    let responses: Vec<&str> = std::str::from_utf8(&output.stdout)
+        .unwrap()
+        .lines()
+        .collect();
+    assert_eq!(responses.len(), 2);
+    assert!(responses[0].starts_with(r#"{"id":1,"jsonrpc":"2.0","result":[{"date":"#));
+    assert!(responses[0].contains(r#""path":"/tmp/project""#));
+    assert_eq!(responses[1], r#"{"id":2,"jsonrpc":"2.0","result":[]}"#);
+    assert_eq!(sandbox.paths().len(), 2);
+    assert!(!sandbox.command(&["serve"]).status.success());
+}
+
+#[test]
+fn test_list_paths_shortcuts() {
+    let sandbox = Sandbox::new();
+    sandbox.run(&["add-shortcut", "w", "/home/work", "Work", "--allow-missing"]);
+    sandbox.run(&["add-shortcut", "t", "/tmp", "--allow-missing"]);
+    sandbox.run(&["add-path", "/home/work/api"]);
+    sandbox.run(&["add-path", "/var/log"]);
+
+    assert_eq!(
+        sandbox.stdout(&["list-shortcuts"]),
+        "t /tmp\nw /home/work\n"
+    );
+    assert_eq!(
+        sandbox.stdout(&["list-shortcuts", "--filter", "work", "--format", "vim"]),
+        "w\t/home/work\tWork\n"
+    );
+    assert_eq!(
+        sandbox.stdout(&["list-paths", "--filter", "api"]),
+        "/home/work/api\n"
+    );
+    assert_eq!(
+        sandbox
+            .stdout(&["list-paths", "--fuzzy", "wrkapi", "--format", "vim"])
+            .lines()
+            .next()
+            .unwrap()
+            .split('\t')
+            .take(2)
+            .collect::<Vec<_>>(),
+        vec!["/home/work/api", "w"]
+    );
+    assert_eq!(
+        sandbox
+            .stdout(&["list-paths", "--limit", "1"])
+            .lines()
+            .count(),
+        1
+    );
+    assert!(
+        !sandbox
+            .command(&["list-paths", "--filter", "a", "--fuzzy", "a"])
+            .status
+            .success()
+    );
+}
+
+#[test]
+fn test_list_paths_without_smart_suggestions() {
+    let sandbox = Sandbox::new();
+    let cwd = visit_smart_suggestions(&sandbox);
+    let plain = sandbox.stdout_in(&cwd, &["list-paths"]);
+    assert!(no_duplicates(&plain), "{}", plain);
+    assert_eq!(
+        plain.lines().next(),
+        Some(cwd.with_file_name("c").to_str().unwrap())
+    );
+    assert_eq!(plain.lines().count(), 3);
+    let json = sandbox.stdout_in(&cwd, &["list-paths", "--format", "json"]);
+    assert!(no_duplicates(&json), "{}", json);
+    assert_eq!(json.lines().count(), 3);
+}
+
+#[test]
+fn test_fzf_resolve() {
+    let sandbox = Sandbox::new();
+    sandbox.run(&["add-shortcut", "t", "/tmp", "--allow-missing"]);
+    sandbox.run(&["add-path", "/var/log"]);
+    sandbox.run(&["add-path", "/tmp/project"]);
+    sandbox.run(&["add-path", "/tmp/project"]);
+    assert_eq!(sandbox.stdout(&["fzf"]), "/tmp/project\t[t]\n/var/log\n");
+
+    assert_eq!(sandbox.stdout(&["resolve", "/var/log\t[v]"]), "/var/log\n");
+    assert_eq!(sandbox.paths()[0], "/var/log");
+    assert!(!sandbox.command(&["resolve", ""]).status.success());
+}
+
+#[test]
+fn test_exit_codes() {
+    let sandbox = Sandbox::new();
+    let code = |args: &[&str]| sandbox.command(args).status.code();
+    sandbox.run(&["add-shortcut", "w", "/home/work", "--allow-missing"]);
+
+    // not found
+    assert_eq!(code(&["print-shortcut", "unknown"]), Some(1));
+    assert_eq!(code(&["delete-shortcut", "unknown", "--yes"]), Some(1));
+    assert_eq!(code(&["import-shortcuts", "missing.yaml"]), Some(1));
+    assert_eq!(code(&["add-shortcut", "m", "/missing/directory"]), Some(1));
+    // invalid input
+    assert_eq!(code(&["add-shortcut", "w", "/tmp"]), Some(4));
+    assert_eq!(code(&["add-path", "me@:/srv"]), Some(4));
+    // cancelled, nobody can confirm without a terminal
+    assert_eq!(code(&["delete-shortcut", "w"]), Some(6));
+    // usage
+    assert_eq!(code(&["print-shortcut"]), Some(64));
+    assert_eq!(code(&["--unknown-flag"]), Some(64));
+    assert_eq!(code(&["--dry-run", "add-path", "/tmp"]), Some(64));
+    assert_eq!(code(&["--help"]), Some(0));
+
+    assert_eq!(sandbox.shortcuts().len(), 1);
+}
+
+#[test]
+fn test_config_error_exit_code() {
+    let sandbox = Sandbox::with_config("database:\n  busy_timeout_ms: not-a-number\n");
+    let output = sandbox.command(&["check-shortcuts"]);
+    assert_eq!(output.status.code(), Some(3));
+    assert!(!output.stderr.is_empty());
+}
+
+#[test]
+fn test_database_error_exit_code() {
+    let sandbox = Sandbox::empty();
+    fs::create_dir_all(sandbox.config_path().parent().unwrap()).unwrap();
+    // the directory of the database is a file
+    fs::write(
+        sandbox.config_path(),
+        format!(
+            "db_path: {}\nlog_file: null\n",
+            sandbox.config_path().join("cdir.db").display()
+        ),
+    )
+    .unwrap();
+    let output = sandbox.command(&["add-path", "/tmp"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Failed to open the database"));
+}
+
+#[test]
+fn test_add_file() {
+    let sandbox = Sandbox::new();
+    sandbox.run(&["add-file", "/home/me/notes.md"]);
+    sandbox.run(&["add-file", "/etc/hosts"]);
+    sandbox.run(&["add-file", "/home/me/notes.md"]);
+
+    let db = sandbox.db();
+    let mut stmt = db.prepare("SELECT path FROM files ORDER BY path").unwrap();
+    let files: Vec<String> = stmt
+        .query_map([], |row| row.get(0))
+        .unwrap()
+        .map(|r| r.unwrap())
+        .collect();
+    assert_eq!(files, vec!["/etc/hosts", "/home/me/notes.md"]);
+}
+
+#[test]
+fn test_add_cmd() {
+    let sandbox = Sandbox::new();
+    sandbox.run(&["add-cmd", "/home/me/api", "cargo build"]);
+    sandbox.run(&["add-cmd", "/home/me/api", "cargo test"]);
+    // blank commands are ignored
+    sandbox.run(&["add-cmd", "/home/me/api", "  "]);
+
+    let db = sandbox.db();
+    let command: String = db
+        .query_row(
+            "SELECT command FROM last_commands WHERE path = '/home/me/api'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(command, "cargo test");
+}